@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: UNLICENSED
+
+pragma solidity ^0.7.0;
+
+/// @dev Interface of the ENS registry, only the methods used to resolve names are included
+interface IENS {
+    /// @dev Returns the address of the resolver for the given namehash
+    function resolver(bytes32 node) external view returns (address);
+}
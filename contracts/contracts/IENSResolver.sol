@@ -0,0 +1,9 @@
+// SPDX-License-Identifier: UNLICENSED
+
+pragma solidity ^0.7.0;
+
+/// @dev Interface of the ENS public resolver, only the address resolution is included
+interface IENSResolver {
+    /// @dev Returns the Ethereum address associated with the given namehash
+    function addr(bytes32 node) external view returns (address);
+}
@@ -6,6 +6,7 @@ use structopt::StructOpt;
 
 use serde::{Deserialize, Serialize};
 
+use zksync_api::api_server::tx_sender_settings::SharedTxSenderSettings;
 use zksync_api::fee_ticker::{run_updaters, FeeTicker, TickerInfo};
 use zksync_core::{genesis_init, run_core, wait_for_tasks};
 use zksync_eth_client::EthereumGateway;
@@ -14,7 +15,9 @@ use zksync_gateway_watcher::run_gateway_watcher_if_multiplexed;
 use zksync_witness_generator::run_prover_server;
 
 use tokio::task::JoinHandle;
-use zksync_config::configs::api::{PrivateApiConfig, PrometheusConfig, TokenConfig};
+use zksync_config::configs::api::{
+    AdminApiConfig, PrivateApiConfig, PrometheusConfig, TokenConfig,
+};
 use zksync_config::{
     configs::api::{CommonApiConfig, JsonRpcConfig, ProverApiConfig, RestApiConfig, Web3Config},
     ChainConfig, ContractsConfig, DBConfig, ETHClientConfig, ETHSenderConfig, ETHWatchConfig,
@@ -151,8 +154,10 @@ async fn main() -> anyhow::Result<()> {
 }
 
 async fn run_server(components: &ComponentsToRun) {
-    let connection_pool = ConnectionPool::new(None);
-    let read_only_connection_pool = ConnectionPool::new_readonly_pool(None);
+    let slow_query_threshold = DBConfig::from_env().slow_query_threshold();
+    let connection_pool = ConnectionPool::new(None).with_slow_query_threshold(slow_query_threshold);
+    let read_only_connection_pool =
+        ConnectionPool::new_readonly_pool(None).with_slow_query_threshold(slow_query_threshold);
     let (stop_signal_sender, mut stop_signal_receiver) = mpsc::channel(256);
 
     let mut tasks = vec![];
@@ -170,6 +175,12 @@ async fn run_server(components: &ComponentsToRun) {
         // Run price fetchers
         let mut price_tasks = run_price_updaters(connection_pool.clone());
         tasks.append(&mut price_tasks);
+        // Token metadata is reconciled by a single component, not by every API replica.
+        tasks.push(zksync_api::token_metadata::run_token_metadata_updater(
+            connection_pool.clone(),
+            create_eth_gateway(),
+            TokenConfig::from_env().metadata_refresh_interval(),
+        ));
     }
 
     if components.0.iter().any(|c| {
@@ -191,19 +202,23 @@ async fn run_server(components: &ComponentsToRun) {
             tasks.push(task);
         }
 
+        let common_config = CommonApiConfig::from_env();
+
         // Run signer
         let (sign_check_sender, sign_check_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
         tasks.push(zksync_api::signature_checker::start_sign_checker(
-            eth_gateway,
+            eth_gateway.clone(),
             sign_check_receiver,
+            &common_config,
         ));
 
         let contracts_config = ContractsConfig::from_env();
-        let common_config = CommonApiConfig::from_env();
         let token_config = TokenConfig::from_env();
         let chain_config = ChainConfig::from_env();
         let fee_ticker_config = TickerConfig::from_env();
-        let ticker_info = Box::new(TickerInfo::new(read_only_connection_pool.clone()));
+        let ticker_info = TickerInfo::new(read_only_connection_pool.clone());
+        tasks.push(ticker_info.keep_price_cache_updated());
+        let ticker_info = Box::new(ticker_info);
 
         let ticker = FeeTicker::new_with_default_validator(
             ticker_info,
@@ -211,6 +226,34 @@ async fn run_server(components: &ComponentsToRun) {
             chain_config.max_blocks_to_aggregate(),
             read_only_connection_pool.clone(),
         );
+        tasks.push(
+            ticker
+                .gas_price_scale()
+                .clone()
+                .keep_updated(read_only_connection_pool.clone()),
+        );
+        tasks.push(zksync_api::api_server::admin_server::start_admin_server(
+            AdminApiConfig::from_env(),
+            connection_pool.clone(),
+            &common_config,
+            ticker.gas_price_scale().clone(),
+        ));
+        // Submission settings are shared by all the APIs, so the overrides are reloaded once.
+        let tx_sender_settings = SharedTxSenderSettings::new(&common_config);
+        tasks.push(
+            tx_sender_settings
+                .clone()
+                .keep_updated(connection_pool.clone()),
+        );
+        tasks.push(
+            zksync_api::api_server::rate_limiter::run_rate_limit_cleaner(
+                connection_pool.clone(),
+                tx_sender_settings.clone(),
+            ),
+        );
+        tasks.push(zksync_api::api_server::run_tx_request_ids_cleaner(
+            connection_pool.clone(),
+        ));
 
         if components.0.contains(&Component::RpcWebSocketApi) {
             let (mempool_tx_request_sender, mempool_tx_request_receiver) =
@@ -219,6 +262,8 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.state_keeper.mempool_max_nonce_gap,
+                chain_config.state_keeper.mempool_insert_window(),
             ));
             tasks.push(zksync_api::api_server::rpc_subscriptions::start_ws_server(
                 read_only_connection_pool.clone(),
@@ -230,6 +275,7 @@ async fn run_server(components: &ComponentsToRun) {
                 chain_config.state_keeper.miniblock_iteration_interval(),
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                tx_sender_settings.clone(),
             ));
         }
 
@@ -240,6 +286,8 @@ async fn run_server(components: &ComponentsToRun) {
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
                 chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.state_keeper.mempool_max_nonce_gap,
+                chain_config.state_keeper.mempool_insert_window(),
             ));
             tasks.push(zksync_api::api_server::rpc_server::start_rpc_server(
                 read_only_connection_pool.clone(),
@@ -250,6 +298,7 @@ async fn run_server(components: &ComponentsToRun) {
                 &token_config,
                 mempool_tx_request_sender,
                 eth_watch_config.confirmations_for_eth_event,
+                tx_sender_settings.clone(),
             ));
         }
 
@@ -259,7 +308,9 @@ async fn run_server(components: &ComponentsToRun) {
             tasks.push(run_mempool_tx_handler(
                 connection_pool.clone(),
                 mempool_tx_request_receiver,
-                chain_config.state_keeper.block_chunk_sizes,
+                chain_config.state_keeper.block_chunk_sizes.clone(),
+                chain_config.state_keeper.mempool_max_nonce_gap,
+                chain_config.state_keeper.mempool_insert_window(),
             ));
             let private_config = PrivateApiConfig::from_env();
             tasks.push(zksync_api::api_server::rest::start_server_thread_detached(
@@ -269,8 +320,10 @@ async fn run_server(components: &ComponentsToRun) {
                 contracts_config.contract_addr,
                 ticker,
                 sign_check_sender,
+                eth_gateway,
                 mempool_tx_request_sender,
                 private_config.url,
+                tx_sender_settings,
             ));
         }
     }
@@ -351,7 +404,9 @@ pub fn run_forced_exit(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
     let mempool_task = run_mempool_tx_handler(
         connection_pool.clone(),
         mempool_tx_request_receiver,
-        chain_config.state_keeper.block_chunk_sizes,
+        chain_config.state_keeper.block_chunk_sizes.clone(),
+        chain_config.state_keeper.mempool_max_nonce_gap,
+        chain_config.state_keeper.mempool_insert_window(),
     );
     let forced_exit_task = run_forced_exit_requests_actors(
         connection_pool,
@@ -388,7 +443,7 @@ pub fn run_eth_sender(connection_pool: ConnectionPool) -> JoinHandle<()> {
 
 pub fn run_price_updaters(connection_pool: ConnectionPool) -> Vec<JoinHandle<()>> {
     let ticker_config = TickerConfig::from_env();
-    run_updaters(connection_pool, &ticker_config)
+    run_updaters(connection_pool, &ticker_config, create_eth_gateway())
 }
 
 pub fn create_eth_gateway() -> EthereumGateway {
@@ -0,0 +1,27 @@
+//! Stores the order hashes of the swaps executed before they were stored along with the swap,
+//! so the receipts of these swaps can be requested by the order hash as well.
+
+use zksync_storage::StorageProcessor;
+
+/// Number of the swaps processed in a single database transaction.
+const SWAPS_PER_ITERATION: i64 = 1000;
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut storage = StorageProcessor::establish_connection().await?;
+    let mut last_sequence_number = 0;
+    while let Some(sequence_number) = storage
+        .chain()
+        .operations_ext_schema()
+        .backfill_swap_orders(last_sequence_number, SWAPS_PER_ITERATION)
+        .await?
+    {
+        last_sequence_number = sequence_number;
+        println!(
+            "Processed swaps up to the sequence number {}",
+            last_sequence_number
+        );
+    }
+    println!("Order hashes of all the executed swaps are stored");
+    Ok(())
+}
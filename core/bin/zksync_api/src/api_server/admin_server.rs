@@ -0,0 +1,827 @@
+//! Admin API server.
+//!
+//! Provides endpoints to inspect and override the runtime parameters of the fee ticker
+//! and to manage the budget of the current subsidy. Conflicts of the token metadata
+//! detected by the token metadata updater are listed for the operator review.
+//! Tokens can be manually accepted or rejected for the fee payment, every such change
+//! is recorded to the audit log along with the subject of the token it was made with.
+//! Transactions waiting in the mempool can be listed for the inspection, the whole mempool can be
+//! exported to a file along with the reasons the transactions are held, e.g. to attach it to
+//! the bug reports about the transaction ordering.
+//! Sender addresses and transaction types can be exempted from the fee checks, the transactions
+//! accepted because of the exemptions are listed from the audit log.
+//! Addresses can be added to the sender and the recipient block lists, the transactions
+//! involving them are rejected by the API.
+//! Submission settings (fee checks, rate limits, mempool caps) can be overridden, the overrides
+//! are picked up by all the API replicas without the restart.
+//! All the requests must be authorized with a JWT signed by the `API_ADMIN_SECRET_AUTH` secret.
+
+// Built-in deps
+use std::thread;
+// External deps
+use actix_web::{
+    dev::ServiceRequest, http::header, web, App, HttpMessage, HttpResponse, HttpServer,
+};
+use actix_web_httpauth::{
+    extractors::{
+        bearer::{BearerAuth, Config},
+        AuthenticationError,
+    },
+    middleware::HttpAuthentication,
+};
+use bigdecimal::BigDecimal;
+use jsonwebtoken::{decode, errors::Error as JwtError, DecodingKey, Validation};
+use num::{rational::Ratio, BigUint, One, Zero};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+// Workspace deps
+use zksync_api_types::v02::mempool::{MempoolTxsQuery, MAX_MEMPOOL_TXS_LIMIT};
+use zksync_config::configs::api::{AdminApiConfig, CommonApiConfig};
+use zksync_storage::{
+    misc::records::TxSenderSettingOverride, tokens::ResolveTokenError, ConnectionPool,
+    StorageProcessor,
+};
+use zksync_types::{Address, AddressList, FeeExemption, FeeExemptionRecord, TokenId, TokenLike};
+use zksync_utils::{
+    big_decimal_to_ratio,
+    panic_notify::{spawn_panic_handler, ThreadPanicNotify},
+    ratio_to_big_decimal, ratio_to_scaled_u64, scaled_big_decimal_to_ratio, scaled_u64_to_ratio,
+};
+// Local deps
+use crate::{api_server::tx_sender_settings::TxSenderSettings, fee_ticker::GasPriceScale};
+
+/// Precision of the gas price scale returned by the API.
+const SCALE_PRECISION: usize = 4;
+/// Precision of the USD amounts returned by the API, matches the scale of the stored subsidies.
+const USD_PRECISION: usize = 6;
+/// Number of the latest fee acceptance changes returned by the API.
+const FEE_ACCEPTANCE_CHANGES_LIMIT: u32 = 100;
+/// Number of the latest fee exemption usages returned by the API.
+const FEE_EXEMPTION_USAGES_LIMIT: u32 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PayloadAuthToken {
+    /// Subject (whom auth token refers to).
+    sub: String,
+    /// Expiration time (as UTC timestamp).
+    exp: usize,
+}
+
+#[derive(Debug, Clone)]
+struct AppState {
+    secret_auth: String,
+    pool: ConnectionPool,
+    gas_price_scale: GasPriceScale,
+    subsidy_type: String,
+    /// Budget of the subsidy used until it's set in the database.
+    default_subsidy_budget_usd: Ratio<BigUint>,
+    /// Fee exemptions from the config, they can't be removed via the API.
+    config_fee_exemptions: Vec<FeeExemption>,
+    /// Submission settings from the config, the overrides are applied on top of them.
+    config_tx_sender_settings: TxSenderSettings,
+}
+
+/// The structure that stores the secret key for checking JsonWebToken matching.
+struct AuthTokenValidator<'a> {
+    decoding_key: DecodingKey<'a>,
+}
+
+impl<'a> AuthTokenValidator<'a> {
+    fn new(secret: &'a str) -> Self {
+        Self {
+            decoding_key: DecodingKey::from_secret(secret.as_ref()),
+        }
+    }
+
+    /// Checks whether the secret key and the authorization token match.
+    fn validate_auth_token(&self, token: &str) -> Result<PayloadAuthToken, JwtError> {
+        let token_data =
+            decode::<PayloadAuthToken>(token, &self.decoding_key, &Validation::default())?;
+
+        Ok(token_data.claims)
+    }
+
+    async fn validator(
+        &self,
+        req: ServiceRequest,
+        credentials: BearerAuth,
+    ) -> actix_web::Result<ServiceRequest> {
+        let config = req.app_data::<Config>().cloned().unwrap_or_default();
+
+        let claims = self
+            .validate_auth_token(credentials.token())
+            .map_err(|_| AuthenticationError::from(config))?;
+        // Subject of the token is used to audit the changes made through the API.
+        req.extensions_mut().insert(claims);
+
+        Ok(req)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GasPriceScaleInfo {
+    factor: BigDecimal,
+    pinned: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct PinGasPriceScaleRequest {
+    factor: BigDecimal,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubsidyInfo {
+    subsidy_type: String,
+    budget_usd: BigDecimal,
+    used_usd: BigDecimal,
+    remaining_usd: BigDecimal,
+    /// Full cost of the subsidized transactions.
+    subsidized_volume_usd: BigDecimal,
+    subsidized_txs: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubsidyBudgetRequest {
+    budget_usd: BigDecimal,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeAcceptanceRequest {
+    /// `null` removes the manual override, so the fee token policy decides again.
+    is_fee_token: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeAcceptanceInfo {
+    token_id: TokenId,
+    is_fee_token: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockAddressRequest {
+    reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeExemptionsInfo {
+    config: Vec<FeeExemption>,
+    added: Vec<FeeExemptionRecord>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TxSenderSettingsInfo {
+    config: TxSenderSettings,
+    overrides: Vec<TxSenderSettingOverride>,
+    effective: TxSenderSettings,
+}
+
+fn gas_price_scale_info(scale: &GasPriceScale) -> GasPriceScaleInfo {
+    GasPriceScaleInfo {
+        factor: ratio_to_big_decimal(&scale.factor(), SCALE_PRECISION),
+        pinned: scale.is_pinned(),
+    }
+}
+
+async fn get_gas_price_scale(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(gas_price_scale_info(&data.gas_price_scale)))
+}
+
+async fn pin_gas_price_scale(
+    data: web::Data<AppState>,
+    request: web::Json<PinGasPriceScaleRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let factor =
+        big_decimal_to_ratio(&request.factor).map_err(actix_web::error::ErrorBadRequest)?;
+    if factor < Ratio::one() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "Gas price scale should not be less than 1",
+        ));
+    }
+
+    vlog::info!("Gas price scale is pinned to {}", request.factor);
+    data.gas_price_scale.pin(factor);
+    Ok(HttpResponse::Ok().json(gas_price_scale_info(&data.gas_price_scale)))
+}
+
+async fn unpin_gas_price_scale(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    vlog::info!("Gas price scale is unpinned");
+    data.gas_price_scale.unpin();
+    Ok(HttpResponse::Ok().json(gas_price_scale_info(&data.gas_price_scale)))
+}
+
+async fn subsidy_info(data: &AppState) -> actix_web::Result<SubsidyInfo> {
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let mut misc_schema = storage.misc_schema();
+    let budget_usd = misc_schema
+        .get_subsidy_budget(&data.subsidy_type)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .map(scaled_u64_to_ratio)
+        .unwrap_or_else(|| data.default_subsidy_budget_usd.clone());
+    let stats = misc_schema
+        .get_subsidy_stats(&data.subsidy_type)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    let used_usd = scaled_big_decimal_to_ratio(stats.used_usd_scaled)
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let subsidized_volume_usd = scaled_big_decimal_to_ratio(stats.full_cost_usd_scaled)
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let remaining_usd = if budget_usd > used_usd {
+        &budget_usd - &used_usd
+    } else {
+        Ratio::zero()
+    };
+
+    Ok(SubsidyInfo {
+        subsidy_type: data.subsidy_type.clone(),
+        budget_usd: ratio_to_big_decimal(&budget_usd, USD_PRECISION),
+        used_usd: ratio_to_big_decimal(&used_usd, USD_PRECISION),
+        remaining_usd: ratio_to_big_decimal(&remaining_usd, USD_PRECISION),
+        subsidized_volume_usd: ratio_to_big_decimal(&subsidized_volume_usd, USD_PRECISION),
+        subsidized_txs: stats.subsidized_txs,
+    })
+}
+
+async fn get_token_metadata_conflicts(
+    data: web::Data<AppState>,
+) -> actix_web::Result<HttpResponse> {
+    let conflicts = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .tokens_schema()
+        .load_token_metadata_conflicts()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(conflicts))
+}
+
+async fn resolve_token(
+    storage: &mut StorageProcessor<'_>,
+    token: &str,
+) -> actix_web::Result<TokenId> {
+    match storage
+        .tokens_schema()
+        .resolve_token(TokenLike::parse(token))
+        .await
+    {
+        Ok(token) => Ok(token.id),
+        Err(err @ ResolveTokenError::NotFound) => Err(actix_web::error::ErrorNotFound(err)),
+        Err(err @ ResolveTokenError::AmbiguousSymbol { .. }) => {
+            Err(actix_web::error::ErrorBadRequest(err))
+        }
+        Err(ResolveTokenError::Other(err)) => Err(actix_web::error::ErrorInternalServerError(err)),
+    }
+}
+
+async fn set_fee_acceptance(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    token: web::Path<String>,
+    request: web::Json<FeeAcceptanceRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let token_id = resolve_token(&mut storage, &token).await?;
+
+    let mut tokens_schema = storage.tokens_schema();
+    tokens_schema
+        .set_fee_token_acceptance(token_id, request.is_fee_token, &claims.sub)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Fee acceptance of the token {} is set to {:?} by {}",
+        token_id,
+        request.is_fee_token,
+        claims.sub
+    );
+
+    let is_fee_token = tokens_schema
+        .is_fee_token(token_id)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(FeeAcceptanceInfo {
+        token_id,
+        is_fee_token,
+    }))
+}
+
+async fn get_fee_acceptance_changes(
+    data: web::Data<AppState>,
+    token: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let mut storage = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    let token_id = resolve_token(&mut storage, &token).await?;
+
+    let changes = storage
+        .tokens_schema()
+        .load_fee_token_acceptance_changes(token_id, FEE_ACCEPTANCE_CHANGES_LIMIT)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(changes))
+}
+
+async fn get_mempool_txs(
+    data: web::Data<AppState>,
+    query: web::Query<MempoolTxsQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let limit = query.limit.unwrap_or(MAX_MEMPOOL_TXS_LIMIT);
+    if limit > MAX_MEMPOOL_TXS_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "Limit should be less than or equal to {}",
+            MAX_MEMPOOL_TXS_LIMIT
+        )));
+    }
+
+    let txs = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .chain()
+        .mempool_schema()
+        .load_mempool_txs(
+            query.tx_type.as_deref(),
+            limit,
+            query.offset.unwrap_or_default(),
+        )
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(txs))
+}
+
+/// Returns the whole mempool as a JSON file named after the time of the snapshot.
+async fn export_mempool_snapshot(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+) -> actix_web::Result<HttpResponse> {
+    let snapshot = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .chain()
+        .mempool_schema()
+        .load_mempool_snapshot()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    vlog::info!(
+        "Mempool snapshot with {} transactions is exported by {}",
+        snapshot.txs.len(),
+        claims.sub
+    );
+    let file_name = format!(
+        "mempool-snapshot-{}.json",
+        snapshot.created_at.format("%Y%m%dT%H%M%SZ")
+    );
+    Ok(HttpResponse::Ok()
+        .insert_header((
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"{}\"", file_name),
+        ))
+        .json(snapshot))
+}
+
+async fn get_fee_exemptions(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let added = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .load_fee_exemptions()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(FeeExemptionsInfo {
+        config: data.config_fee_exemptions.clone(),
+        added,
+    }))
+}
+
+async fn add_fee_exemption(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    path: web::Path<(String, String)>,
+) -> actix_web::Result<HttpResponse> {
+    let (kind, value) = path.into_inner();
+    let exemption =
+        FeeExemption::from_parts(&kind, &value).map_err(actix_web::error::ErrorBadRequest)?;
+
+    data.pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .add_fee_exemption(&exemption, &claims.sub)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Fee exemption {} {} is added by {}",
+        kind,
+        value,
+        claims.sub
+    );
+
+    Ok(HttpResponse::Ok().json(exemption))
+}
+
+async fn remove_fee_exemption(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    path: web::Path<(String, String)>,
+) -> actix_web::Result<HttpResponse> {
+    let (kind, value) = path.into_inner();
+    let exemption =
+        FeeExemption::from_parts(&kind, &value).map_err(actix_web::error::ErrorBadRequest)?;
+
+    let removed = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .remove_fee_exemption(&exemption)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound(
+            "Fee exemption is not found",
+        ));
+    }
+    vlog::info!(
+        "Fee exemption {} {} is removed by {}",
+        kind,
+        value,
+        claims.sub
+    );
+
+    Ok(HttpResponse::Ok().json(exemption))
+}
+
+async fn get_fee_exemption_usages(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let usages = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .load_fee_exemption_usages(FEE_EXEMPTION_USAGES_LIMIT)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(usages))
+}
+
+fn parse_blocked_address(list: &str, address: &str) -> actix_web::Result<(AddressList, Address)> {
+    let list = list.parse().map_err(actix_web::error::ErrorBadRequest)?;
+    let address = address.parse().map_err(|err| {
+        actix_web::error::ErrorBadRequest(format!("Invalid address {}: {}", address, err))
+    })?;
+    Ok((list, address))
+}
+
+async fn get_blocked_addresses(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let blocked = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .load_blocked_addresses()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok(HttpResponse::Ok().json(blocked))
+}
+
+async fn add_blocked_address(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    path: web::Path<(String, String)>,
+    request: web::Json<BlockAddressRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let (list, address) = path.into_inner();
+    let (list, address) = parse_blocked_address(&list, &address)?;
+
+    data.pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .add_blocked_address(address, list, &request.reason, &claims.sub)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Address {:?} is added to the {} block list by {}: {}",
+        address,
+        list,
+        claims.sub,
+        request.reason
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_blocked_address(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    path: web::Path<(String, String)>,
+) -> actix_web::Result<HttpResponse> {
+    let (list, address) = path.into_inner();
+    let (list, address) = parse_blocked_address(&list, &address)?;
+
+    let removed = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .remove_blocked_address(address, list)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound(
+            "Address is not on the block list",
+        ));
+    }
+    vlog::info!(
+        "Address {:?} is removed from the {} block list by {}",
+        address,
+        list,
+        claims.sub
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn get_subsidy(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(subsidy_info(&data).await?))
+}
+
+async fn set_subsidy_budget(
+    data: web::Data<AppState>,
+    request: web::Json<SubsidyBudgetRequest>,
+) -> actix_web::Result<HttpResponse> {
+    let budget_usd =
+        big_decimal_to_ratio(&request.budget_usd).map_err(actix_web::error::ErrorBadRequest)?;
+
+    data.pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .set_subsidy_budget(&data.subsidy_type, ratio_to_scaled_u64(budget_usd))
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Budget of the subsidy {} is set to {} USD",
+        data.subsidy_type,
+        request.budget_usd
+    );
+
+    Ok(HttpResponse::Ok().json(subsidy_info(&data).await?))
+}
+
+async fn load_tx_sender_setting_overrides(
+    data: &AppState,
+) -> actix_web::Result<Vec<TxSenderSettingOverride>> {
+    data.pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .load_tx_sender_setting_overrides()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)
+}
+
+async fn get_tx_sender_settings(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let overrides = load_tx_sender_setting_overrides(&data).await?;
+    let effective = data
+        .config_tx_sender_settings
+        .with_overrides(
+            overrides
+                .iter()
+                .map(|setting| (setting.name.as_str(), &setting.value)),
+        )
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok().json(TxSenderSettingsInfo {
+        config: data.config_tx_sender_settings.clone(),
+        overrides,
+        effective,
+    }))
+}
+
+async fn set_tx_sender_setting(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    name: web::Path<String>,
+    value: web::Json<serde_json::Value>,
+) -> actix_web::Result<HttpResponse> {
+    let name = name.into_inner();
+    let value = value.into_inner();
+
+    // Settings are validated together, so the override can't be stored if it makes
+    // the settings invalid.
+    let overrides = load_tx_sender_setting_overrides(&data).await?;
+    data.config_tx_sender_settings
+        .with_overrides(
+            overrides
+                .iter()
+                .filter(|setting| setting.name != name)
+                .map(|setting| (setting.name.as_str(), &setting.value))
+                .chain(std::iter::once((name.as_str(), &value))),
+        )
+        .map_err(actix_web::error::ErrorBadRequest)?;
+
+    data.pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .set_tx_sender_setting_override(&name, &value, &claims.sub)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    vlog::info!(
+        "Submission setting {} is set to {} by {}",
+        name,
+        value,
+        claims.sub
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn remove_tx_sender_setting(
+    data: web::Data<AppState>,
+    claims: web::ReqData<PayloadAuthToken>,
+    name: web::Path<String>,
+) -> actix_web::Result<HttpResponse> {
+    let name = name.into_inner();
+
+    let removed = data
+        .pool
+        .access_storage()
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .misc_schema()
+        .remove_tx_sender_setting_override(&name)
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    if !removed {
+        return Err(actix_web::error::ErrorNotFound(
+            "Submission setting is not overridden",
+        ));
+    }
+    vlog::info!(
+        "Override of the submission setting {} is removed by {}",
+        name,
+        claims.sub
+    );
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[must_use]
+pub fn start_admin_server(
+    config: AdminApiConfig,
+    pool: ConnectionPool,
+    common_config: &CommonApiConfig,
+    gas_price_scale: GasPriceScale,
+) -> JoinHandle<()> {
+    let subsidy_type = common_config.subsidy_name.clone();
+    let default_subsidy_budget_usd = common_config.max_subsidy_usd();
+    let config_fee_exemptions = common_config
+        .fee_exempt_senders
+        .iter()
+        .copied()
+        .map(FeeExemption::Sender)
+        .chain(
+            common_config
+                .fee_exempt_tx_types
+                .iter()
+                .cloned()
+                .map(FeeExemption::TxType),
+        )
+        .collect();
+    let config_tx_sender_settings = TxSenderSettings::from_config(common_config);
+    let (handler, panic_sender) = spawn_panic_handler();
+
+    thread::Builder::new()
+        .name("admin_server".to_string())
+        .spawn(move || {
+            let _panic_sentinel = ThreadPanicNotify(panic_sender.clone());
+            let actix_runtime = actix_rt::System::new();
+
+            actix_runtime.block_on(async move {
+                let app_state = AppState {
+                    secret_auth: config.secret_auth.clone(),
+                    pool,
+                    gas_price_scale,
+                    subsidy_type,
+                    default_subsidy_budget_usd,
+                    config_fee_exemptions,
+                    config_tx_sender_settings,
+                };
+
+                HttpServer::new(move || {
+                    let auth = HttpAuthentication::bearer(move |req, credentials| async {
+                        let secret_auth = req
+                            .app_data::<web::Data<AppState>>()
+                            .expect("failed get AppState upon receipt of the authentication token")
+                            .secret_auth
+                            .clone();
+                        AuthTokenValidator::new(&secret_auth)
+                            .validator(req, credentials)
+                            .await
+                    });
+
+                    App::new()
+                        .wrap(auth)
+                        .app_data(web::Data::new(app_state.clone()))
+                        .route("/gas_price_scale", web::get().to(get_gas_price_scale))
+                        .route("/gas_price_scale/pin", web::post().to(pin_gas_price_scale))
+                        .route(
+                            "/gas_price_scale/pin",
+                            web::delete().to(unpin_gas_price_scale),
+                        )
+                        .route("/subsidy", web::get().to(get_subsidy))
+                        .route(
+                            "/token_metadata_conflicts",
+                            web::get().to(get_token_metadata_conflicts),
+                        )
+                        .route("/subsidy/budget", web::put().to(set_subsidy_budget))
+                        .route(
+                            "/tokens/{token}/fee_acceptance",
+                            web::put().to(set_fee_acceptance),
+                        )
+                        .route(
+                            "/tokens/{token}/fee_acceptance/changes",
+                            web::get().to(get_fee_acceptance_changes),
+                        )
+                        .route("/mempool/txs", web::get().to(get_mempool_txs))
+                        .route("/mempool/snapshot", web::get().to(export_mempool_snapshot))
+                        .route("/fee_exemptions", web::get().to(get_fee_exemptions))
+                        .route(
+                            "/fee_exemptions/usages",
+                            web::get().to(get_fee_exemption_usages),
+                        )
+                        .route(
+                            "/fee_exemptions/{kind}/{value}",
+                            web::put().to(add_fee_exemption),
+                        )
+                        .route(
+                            "/fee_exemptions/{kind}/{value}",
+                            web::delete().to(remove_fee_exemption),
+                        )
+                        .route("/blocked_addresses", web::get().to(get_blocked_addresses))
+                        .route(
+                            "/blocked_addresses/{list}/{address}",
+                            web::put().to(add_blocked_address),
+                        )
+                        .route(
+                            "/blocked_addresses/{list}/{address}",
+                            web::delete().to(remove_blocked_address),
+                        )
+                        .route("/tx_sender_settings", web::get().to(get_tx_sender_settings))
+                        .route(
+                            "/tx_sender_settings/{name}",
+                            web::put().to(set_tx_sender_setting),
+                        )
+                        .route(
+                            "/tx_sender_settings/{name}",
+                            web::delete().to(remove_tx_sender_setting),
+                        )
+                })
+                .bind(&config.bind_addr())
+                .expect("failed to bind")
+                .run()
+                .await
+                .expect("Admin API server has crashed");
+            })
+        })
+        .expect("failed to start admin server");
+
+    handler
+}
@@ -0,0 +1,236 @@
+//! Compliance checks of the submitted transactions.
+//!
+//! Every submission is passed to the `ComplianceHook` which may veto it. By default the
+//! senders and the recipients of the transactions are checked against the address lists
+//! stored in the database and managed via the admin API, so the changes take effect
+//! immediately on all the API replicas. Senders include the accounts referenced only by
+//! their ids, i.e. the initiators of the forced exits and the accounts of the swap orders.
+
+// Built-in uses
+use std::collections::{HashMap, HashSet};
+
+// Workspace uses
+use zksync_storage::ConnectionPool;
+use zksync_types::{AccountId, Address, ZkSyncTx};
+
+// Local uses
+use super::tx_sender::SubmitError;
+
+#[async_trait::async_trait]
+pub trait ComplianceHook: Send + Sync + 'static {
+    /// Returns an error if the transactions must not be accepted.
+    /// Transactions of a batch are checked together.
+    async fn check_txs(&self, txs: &[&ZkSyncTx]) -> Result<(), SubmitError>;
+}
+
+/// Rejects the transactions sent from the addresses on the sender list or moving funds to the
+/// addresses on the recipient list.
+#[derive(Debug, Clone)]
+pub struct AddressListChecker {
+    pool: ConnectionPool,
+}
+
+impl AddressListChecker {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+/// Returns the ids of the senders whose addresses are not included in the transactions.
+fn sender_account_ids(txs: &[&ZkSyncTx]) -> Vec<AccountId> {
+    let account_ids: HashSet<_> = txs.iter().flat_map(|tx| tx.sender_account_ids()).collect();
+    account_ids.into_iter().collect()
+}
+
+/// Returns the senders and the recipients of the transactions without duplicates.
+/// Senders referenced by their ids are taken from the resolved accounts.
+fn involved_addresses(
+    txs: &[&ZkSyncTx],
+    accounts: &HashMap<AccountId, Address>,
+) -> (Vec<Address>, Vec<Address>) {
+    let senders: HashSet<_> = txs
+        .iter()
+        .map(|tx| tx.account())
+        .chain(
+            txs.iter()
+                .flat_map(|tx| tx.sender_account_ids())
+                .filter_map(|account_id| accounts.get(&account_id).copied()),
+        )
+        .collect();
+    let recipients: HashSet<_> = txs.iter().flat_map(|tx| tx.recipients()).collect();
+    (
+        senders.into_iter().collect(),
+        recipients.into_iter().collect(),
+    )
+}
+
+#[async_trait::async_trait]
+impl ComplianceHook for AddressListChecker {
+    async fn check_txs(&self, txs: &[&ZkSyncTx]) -> Result<(), SubmitError> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let account_ids = sender_account_ids(txs);
+        let accounts = if account_ids.is_empty() {
+            HashMap::new()
+        } else {
+            storage
+                .chain()
+                .account_schema()
+                .account_addresses_by_ids(&account_ids)
+                .await
+                .map_err(SubmitError::internal)?
+        };
+
+        let (senders, recipients) = involved_addresses(txs, &accounts);
+        let blocked = storage
+            .misc_schema()
+            .find_blocked_addresses(&senders, &recipients)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        match blocked.into_iter().next() {
+            Some(blocked) => {
+                vlog::info!(
+                    "Rejected the submission involving the address {:?} on the {} list",
+                    blocked.address,
+                    blocked.list
+                );
+                metrics::increment_counter!("api.compliance.blocked_submissions", "list" => blocked.list.as_str());
+                Err(SubmitError::AddressBlocked {
+                    address: blocked.address,
+                    list: blocked.list,
+                })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::error::{ErrorCode, TxError};
+    use actix_web::http::StatusCode;
+    use zksync_types::{
+        tx::{Order, Withdraw},
+        AddressList, ForcedExit, Nonce, Swap, TokenId, Transfer,
+    };
+
+    fn sorted(mut addresses: Vec<Address>) -> Vec<Address> {
+        addresses.sort();
+        addresses
+    }
+
+    fn order(account_id: AccountId, recipient_address: Address) -> Order {
+        Order {
+            account_id,
+            recipient_address,
+            nonce: Nonce(0),
+            token_buy: TokenId(0),
+            token_sell: TokenId(1),
+            price: (1u32.into(), 1u32.into()),
+            amount: 100u32.into(),
+            time_range: Default::default(),
+            signature: Default::default(),
+        }
+    }
+
+    #[test]
+    fn involved_addresses_of_txs() {
+        let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+        let transfer = ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(1),
+            alice,
+            bob,
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        )));
+        let withdraw = ZkSyncTx::Withdraw(Box::new(Withdraw::new(
+            AccountId(1),
+            alice,
+            carol,
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(1),
+            Default::default(),
+            None,
+        )));
+
+        assert!(sender_account_ids(&[&transfer, &withdraw]).is_empty());
+        let (senders, recipients) = involved_addresses(&[&transfer, &withdraw], &HashMap::new());
+        assert_eq!(senders, vec![alice]);
+        assert_eq!(sorted(recipients), sorted(vec![bob, carol]));
+    }
+
+    #[test]
+    fn involved_addresses_of_swap() {
+        let (submitter, alice, bob) = (Address::random(), Address::random(), Address::random());
+        let (alice_recipient, bob_recipient) = (Address::random(), Address::random());
+        let swap = ZkSyncTx::Swap(Box::new(Swap::new(
+            AccountId(1),
+            submitter,
+            Nonce(0),
+            (
+                order(AccountId(2), alice_recipient),
+                order(AccountId(3), bob_recipient),
+            ),
+            (100u32.into(), 100u32.into()),
+            10u32.into(),
+            TokenId(0),
+            None,
+        )));
+
+        let mut account_ids = sender_account_ids(&[&swap]);
+        account_ids.sort();
+        assert_eq!(account_ids, vec![AccountId(2), AccountId(3)]);
+        // Accounts of the orders are checked along with the submitter.
+        let accounts = vec![(AccountId(2), alice), (AccountId(3), bob)]
+            .into_iter()
+            .collect();
+        let (senders, recipients) = involved_addresses(&[&swap], &accounts);
+        assert_eq!(sorted(senders), sorted(vec![submitter, alice, bob]));
+        assert_eq!(
+            sorted(recipients),
+            sorted(vec![alice_recipient, bob_recipient])
+        );
+    }
+
+    #[test]
+    fn involved_addresses_of_forced_exit() {
+        let (initiator, target) = (Address::random(), Address::random());
+        let forced_exit = ZkSyncTx::ForcedExit(Box::new(ForcedExit::new(
+            AccountId(1),
+            target,
+            TokenId(0),
+            10u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        )));
+
+        assert_eq!(sender_account_ids(&[&forced_exit]), vec![AccountId(1)]);
+        // Initiator is checked as the sender, the target receives the funds.
+        let accounts = vec![(AccountId(1), initiator)].into_iter().collect();
+        let (senders, recipients) = involved_addresses(&[&forced_exit], &accounts);
+        assert_eq!(sorted(senders), sorted(vec![initiator, target]));
+        assert_eq!(recipients, vec![target]);
+    }
+
+    #[test]
+    fn address_blocked_error() {
+        let error = TxError::submit_rejected(SubmitError::AddressBlocked {
+            address: Address::zero(),
+            list: AddressList::Recipient,
+        });
+        assert_eq!(error.code, ErrorCode::AddressBlocked);
+        assert_eq!(error.http_status, StatusCode::FORBIDDEN);
+    }
+}
@@ -1,4 +1,6 @@
-use super::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp};
+use super::rpc_server::types::{
+    BlockEventResp, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+};
 use futures::{channel::mpsc, select, stream::StreamExt};
 use jsonrpc_pubsub::{
     typed::{Sink, Subscriber},
@@ -7,6 +9,7 @@ use jsonrpc_pubsub::{
 use std::time::Duration;
 use zksync_config::configs::api::TokenConfig;
 use zksync_storage::ConnectionPool;
+use zksync_types::aggregated_operations::AggregatedOperation;
 use zksync_types::tx::TxHash;
 use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, ActionType, Address};
@@ -42,6 +45,10 @@ pub enum EventSubscribeRequest {
         action: ActionType,
         subscriber: Subscriber<ResponseAccountState>,
     },
+    Block {
+        action: ActionType,
+        subscriber: Subscriber<BlockEventResp>,
+    },
 }
 
 pub enum EventNotifierRequest {
@@ -55,21 +62,17 @@ struct SubscriptionSender<T> {
     sink: Sink<T>,
 }
 
-pub fn start_sub_notifier(
+/// Spawns the fetcher of the committed and verified blocks and of the operations executed
+/// in the pending block, which are sent to the returned channels.
+pub fn spawn_event_fetcher(
     db_pool: ConnectionPool,
-    mut subscription_stream: mpsc::Receiver<EventNotifierRequest>,
-    api_requests_caches_size: usize,
     miniblock_interval: Duration,
-    token_config: &TokenConfig,
-) -> tokio::task::JoinHandle<()> {
-    let (new_block_sender, mut new_block_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
-    let (new_txs_sender, mut new_txs_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
-
-    let mut notifier = OperationNotifier::new(
-        api_requests_caches_size,
-        db_pool.clone(),
-        token_config.invalidate_token_cache_period(),
-    );
+) -> (
+    mpsc::Receiver<AggregatedOperation>,
+    mpsc::Receiver<ExecutedOps>,
+) {
+    let (new_block_sender, new_block_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
+    let (new_txs_sender, new_txs_receiver) = mpsc::channel(NOTIFIER_CHANNEL_CAPACITY);
 
     tokio::spawn(async move {
         let fetcher = EventFetcher::new(
@@ -81,8 +84,28 @@ pub fn start_sub_notifier(
         .await
         .expect("Unable to create event fetcher");
 
-        tokio::spawn(fetcher.run());
+        fetcher.run().await
+    });
+    (new_block_receiver, new_txs_receiver)
+}
+
+pub fn start_sub_notifier(
+    db_pool: ConnectionPool,
+    mut subscription_stream: mpsc::Receiver<EventNotifierRequest>,
+    api_requests_caches_size: usize,
+    miniblock_interval: Duration,
+    token_config: &TokenConfig,
+) -> tokio::task::JoinHandle<()> {
+    let (mut new_block_receiver, mut new_txs_receiver) =
+        spawn_event_fetcher(db_pool.clone(), miniblock_interval);
+
+    let mut notifier = OperationNotifier::new(
+        api_requests_caches_size,
+        db_pool,
+        token_config.invalidate_token_cache_period(),
+    );
 
+    tokio::spawn(async move {
         loop {
             select! {
                 new_block = new_block_receiver.next() => {
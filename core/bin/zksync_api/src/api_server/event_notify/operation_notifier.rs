@@ -1,5 +1,5 @@
 use crate::api_server::rpc_server::types::{
-    BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    BlockEventResp, BlockInfo, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
 };
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
 use std::time::{Duration, Instant};
@@ -10,8 +10,9 @@ use zksync_types::BlockNumber;
 use zksync_types::{block::ExecutedOperations, AccountId, ActionType, Address, PriorityOpId};
 
 use super::{
-    state::NotifierState, sub_store::SubStorage, EventNotifierRequest, EventSubscribeRequest,
-    ExecutedOps,
+    state::NotifierState,
+    sub_store::{AllBlocks, SubStorage},
+    EventNotifierRequest, EventSubscribeRequest, ExecutedOps,
 };
 
 pub struct OperationNotifier {
@@ -20,6 +21,7 @@ pub struct OperationNotifier {
     tx_subs: SubStorage<TxHash, TransactionInfoResp>,
     prior_op_subs: SubStorage<PriorityOpId, ETHOpInfoResp>,
     account_subs: SubStorage<AccountId, ResponseAccountState>,
+    block_subs: SubStorage<AllBlocks, BlockEventResp>,
 }
 
 impl OperationNotifier {
@@ -33,6 +35,7 @@ impl OperationNotifier {
             tx_subs: SubStorage::new(),
             prior_op_subs: SubStorage::new(),
             account_subs: SubStorage::new(),
+            block_subs: SubStorage::new(),
         }
     }
 
@@ -64,6 +67,9 @@ impl OperationNotifier {
                     self.add_account_update_sub(address, action, subscriber)
                         .await
                 }
+                EventSubscribeRequest::Block { action, subscriber } => {
+                    self.add_block_sub(action, subscriber)
+                }
             }
             .map_err(|e| anyhow::format_err!("Failed to add sub: {}", e)),
             EventNotifierRequest::Unsub(sub_id) => self
@@ -86,6 +92,17 @@ impl OperationNotifier {
         };
 
         for block in blocks {
+            self.block_subs.broadcast(
+                AllBlocks,
+                action,
+                BlockEventResp {
+                    block_number: i64::from(*block.block_number),
+                    new_root_hash: block.new_root_hash,
+                    committed: true,
+                    verified: action == ActionType::VERIFY,
+                },
+            );
+
             self.handle_executed_operations(
                 block.block_transactions.clone(),
                 action,
@@ -180,7 +197,8 @@ impl OperationNotifier {
     fn handle_unsub(&mut self, sub_id: SubscriptionId) -> Result<(), anyhow::Error> {
         self.prior_op_subs.remove(sub_id.clone())?;
         self.tx_subs.remove(sub_id.clone())?;
-        self.account_subs.remove(sub_id)?;
+        self.account_subs.remove(sub_id.clone())?;
+        self.block_subs.remove(sub_id)?;
         Ok(())
     }
 
@@ -295,4 +313,14 @@ impl OperationNotifier {
         metrics::histogram!("api.notifier.add_account_update_sub", start.elapsed());
         Ok(())
     }
+
+    /// Add subscription to the lifecycle events of all the blocks.
+    fn add_block_sub(
+        &mut self,
+        action: ActionType,
+        sub: Subscriber<BlockEventResp>,
+    ) -> Result<(), anyhow::Error> {
+        let sub_id = self.block_subs.generate_sub_id(AllBlocks, action);
+        self.block_subs.insert_new(sub_id, sub, AllBlocks, action)
+    }
 }
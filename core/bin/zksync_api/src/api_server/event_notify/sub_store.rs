@@ -1,6 +1,6 @@
 //! Storage for subscription objects.
 use super::SubscriptionSender;
-use std::{cmp::Ord, collections::BTreeMap, str::FromStr};
+use std::{cmp::Ord, collections::BTreeMap, fmt, str::FromStr};
 use zksync_types::{tx::TxHash, AccountId, ActionType, PriorityOpId};
 
 use jsonrpc_pubsub::{
@@ -12,6 +12,7 @@ const MAX_LISTENERS_PER_ENTITY: usize = 2048;
 const TX_SUB_PREFIX: &str = "txsub";
 const ETHOP_SUB_PREFIX: &str = "eosub";
 const ACCOUNT_SUB_PREFIX: &str = "acsub";
+const BLOCK_SUB_PREFIX: &str = "blsub";
 
 pub trait ActionId {
     fn sub_type() -> &'static str;
@@ -35,6 +36,33 @@ impl ActionId for AccountId {
     }
 }
 
+/// Identifier of the subscriptions to the events of all the blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AllBlocks;
+
+impl fmt::Display for AllBlocks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "all")
+    }
+}
+
+impl FromStr for AllBlocks {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(AllBlocks),
+            _ => anyhow::bail!("Unknown blocks subscription: {}", s),
+        }
+    }
+}
+
+impl ActionId for AllBlocks {
+    fn sub_type() -> &'static str {
+        BLOCK_SUB_PREFIX
+    }
+}
+
 #[derive(Debug)]
 pub struct SubStorage<ID, RESP> {
     storage: BTreeMap<(ID, ActionType), Vec<SubscriptionSender<RESP>>>,
@@ -144,6 +172,20 @@ where
         }
     }
 
+    /// Notifies the subscribers keeping them subscribed for the next events.
+    /// Subscribers that can't receive the event anymore are removed.
+    pub fn broadcast(&mut self, action_id: ID, action_type: ActionType, event: RESP) {
+        if let Some(subs) = self.storage.get_mut(&(action_id, action_type)) {
+            subs.retain(|sub| match sub.sink.notify(Ok(event.clone())) {
+                Ok(()) => true,
+                Err(e) => {
+                    vlog::warn!("{}", e.to_string());
+                    false
+                }
+            });
+        }
+    }
+
     pub fn respond_once(
         &mut self,
         sub_id: SubscriptionId,
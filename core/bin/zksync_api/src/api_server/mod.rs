@@ -1,17 +1,24 @@
 //! API server handles endpoints for interaction with node.
 //!
+//! `mod admin_server` - api is used to manage the runtime parameters of the server.
 //! `mod rest` - api is used for block explorer.
 //! `mod rpc_server` - JSON rpc via HTTP (for request reply functions)
 //! `mod rpc_subscriptions` - JSON rpc via WebSocket (for request reply functions and subscriptions)
 
+pub mod admin_server;
+pub mod compliance;
 mod event_notify;
 pub mod forced_exit_checker;
 mod helpers;
+pub mod rate_limiter;
 pub mod rest;
 pub mod rpc_server;
 pub mod rpc_subscriptions;
 mod tx_sender;
+pub mod tx_sender_settings;
 pub mod web3;
 
+pub use tx_sender::run_tx_request_ids_cleaner;
+
 /// Amount of threads used by each server to serve requests.
 const THREADS_PER_SERVER: usize = 128;
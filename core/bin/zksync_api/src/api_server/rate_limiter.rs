@@ -0,0 +1,403 @@
+//! Rate limiting of the transaction submissions.
+//!
+//! Every submission takes a token from the bucket of the sender IP and from the bucket of
+//! each sender account. Buckets are refilled at the constant rate up to their capacity,
+//! so the short bursts are allowed while the sustained spam is rejected.
+//!
+//! Submissions are checked against the buckets kept in memory, so the limiter doesn't touch
+//! the database on the submission path. Tokens taken locally are periodically moved to the
+//! buckets stored in the database, and the local buckets are updated with the shared state,
+//! so the limits are shared by all the API replicas up to the synchronization interval.
+//! Limits are taken from the submission settings, so they can be changed at runtime.
+
+// Built-in uses
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+// External uses
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+
+// Workspace uses
+use zksync_storage::{
+    misc::records::{RateLimitBucket, RateLimitTokens},
+    ConnectionPool,
+};
+use zksync_types::Address;
+
+// Local uses
+use super::{
+    tx_sender::SubmitError,
+    tx_sender_settings::{SharedTxSenderSettings, TxSenderSettings},
+};
+
+/// Interval of removing the unused buckets.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(600);
+/// Interval of moving the locally taken tokens to the shared buckets.
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Capacity and refill rate of the bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BucketLimits {
+    capacity: f64,
+    refill_per_sec: f64,
+}
+
+impl BucketLimits {
+    fn new(burst: u32, per_minute: u32) -> Self {
+        Self {
+            capacity: burst.max(1) as f64,
+            refill_per_sec: per_minute.max(1) as f64 / 60.0,
+        }
+    }
+
+    /// Time it takes to refill the empty bucket.
+    fn refill_time(&self) -> Duration {
+        Duration::from_secs_f64(self.capacity / self.refill_per_sec)
+    }
+}
+
+/// Refills the bucket and checks whether it has a token.
+/// If the bucket is empty, returns the time after which the token will be available.
+fn refill(
+    bucket: &mut RateLimitBucket,
+    limits: BucketLimits,
+    now: DateTime<Utc>,
+) -> Result<(), Duration> {
+    let elapsed = (now - bucket.updated_at).to_std().unwrap_or_default();
+    bucket.tokens =
+        (bucket.tokens + elapsed.as_secs_f64() * limits.refill_per_sec).min(limits.capacity);
+    bucket.updated_at = now;
+
+    if bucket.tokens >= 1.0 {
+        Ok(())
+    } else {
+        Err(Duration::from_secs_f64(
+            (1.0 - bucket.tokens) / limits.refill_per_sec,
+        ))
+    }
+}
+
+/// Limits of the IP and the account buckets.
+fn bucket_limits(settings: &TxSenderSettings) -> (BucketLimits, BucketLimits) {
+    (
+        BucketLimits::new(
+            settings.rate_limit_ip_burst,
+            settings.rate_limit_ip_per_minute,
+        ),
+        BucketLimits::new(
+            settings.rate_limit_account_burst,
+            settings.rate_limit_account_per_minute,
+        ),
+    )
+}
+
+#[derive(Debug)]
+struct LocalBucket {
+    bucket: RateLimitBucket,
+    limits: BucketLimits,
+    /// Tokens taken since the last synchronization with the shared bucket.
+    unsynced: f64,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    buckets: HashMap<String, LocalBucket>,
+    /// Whether the task moving the taken tokens to the shared buckets is running.
+    syncing: bool,
+}
+
+impl LimiterState {
+    /// Takes a token from every given bucket, either from all of them or from none.
+    fn take_tokens(
+        &mut self,
+        keys: &[(String, BucketLimits)],
+        now: DateTime<Utc>,
+    ) -> Result<(), Duration> {
+        for (key, limits) in keys {
+            let local = self
+                .buckets
+                .entry(key.clone())
+                .or_insert_with(|| LocalBucket {
+                    bucket: RateLimitBucket {
+                        key: key.clone(),
+                        tokens: limits.capacity,
+                        updated_at: now,
+                    },
+                    limits: *limits,
+                    unsynced: 0.0,
+                });
+            local.limits = *limits;
+            refill(&mut local.bucket, *limits, now)?;
+        }
+        for (key, _) in keys {
+            let local = self.buckets.get_mut(key).expect("bucket was created above");
+            local.bucket.tokens -= 1.0;
+            local.unsynced += 1.0;
+        }
+        Ok(())
+    }
+
+    /// Returns the tokens taken since the last synchronization and forgets the full buckets
+    /// which weren't used for a while, they will be loaded from the database on the next use.
+    fn take_unsynced(&mut self, now: DateTime<Utc>) -> Vec<RateLimitTokens> {
+        self.buckets.retain(|_, local| {
+            let idle = (now - local.bucket.updated_at).to_std().unwrap_or_default();
+            local.unsynced > 0.0 || idle < local.limits.refill_time()
+        });
+        self.buckets
+            .values_mut()
+            .filter(|local| local.unsynced > 0.0)
+            .map(|local| {
+                let taken = std::mem::take(&mut local.unsynced);
+                RateLimitTokens {
+                    key: local.bucket.key.clone(),
+                    capacity: local.limits.capacity,
+                    refill_per_sec: local.limits.refill_per_sec,
+                    taken,
+                }
+            })
+            .collect()
+    }
+
+    /// Replaces the local buckets with the shared ones, keeping the tokens taken meanwhile.
+    fn apply_shared(&mut self, shared: Vec<RateLimitBucket>, now: DateTime<Utc>) {
+        for shared in shared {
+            if let Some(local) = self.buckets.get_mut(&shared.key) {
+                local.bucket.tokens = (shared.tokens - local.unsynced).max(0.0);
+                local.bucket.updated_at = now;
+            }
+        }
+    }
+
+    /// Returns the tokens which failed to be synchronized, they are retried on the next attempt.
+    fn restore_unsynced(&mut self, updates: Vec<RateLimitTokens>) {
+        for update in updates {
+            if let Some(local) = self.buckets.get_mut(&update.key) {
+                local.unsynced += update.taken;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmissionRateLimiter {
+    pool: ConnectionPool,
+    state: Arc<Mutex<LimiterState>>,
+}
+
+impl SubmissionRateLimiter {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self {
+            pool,
+            state: Arc::default(),
+        }
+    }
+
+    /// Takes a token for the submission from the IP (if it's known) and from every sender account.
+    /// Tokens are taken either from all the buckets or from none of them.
+    /// Does nothing if the rate limiting is disabled in the settings.
+    pub fn check(
+        &self,
+        settings: &TxSenderSettings,
+        ip: Option<&str>,
+        senders: &[Address],
+    ) -> Result<(), SubmitError> {
+        if !settings.rate_limit_enabled {
+            return Ok(());
+        }
+        let (ip_limits, account_limits) = bucket_limits(settings);
+
+        let mut keys: Vec<_> = senders
+            .iter()
+            .map(|sender| (format!("account:{:?}", sender), account_limits))
+            .chain(ip.map(|ip| (format!("ip:{}", ip), ip_limits)))
+            .collect();
+        keys.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+        keys.dedup_by(|(lhs, _), (rhs, _)| lhs == rhs);
+
+        let mut state = self.state.lock().unwrap();
+        if let Err(retry_after) = state.take_tokens(&keys, Utc::now()) {
+            metrics::increment_counter!("api.rate_limiter.rejected_submissions");
+            return Err(SubmitError::RateLimited(retry_after));
+        }
+        if !state.syncing {
+            state.syncing = true;
+            tokio::spawn(self.clone().sync());
+        }
+
+        Ok(())
+    }
+
+    /// Moves the taken tokens to the shared buckets until there are no tokens left to move.
+    async fn sync(self) {
+        loop {
+            tokio::time::sleep(SYNC_INTERVAL).await;
+            let updates = {
+                let mut state = self.state.lock().unwrap();
+                let updates = state.take_unsynced(Utc::now());
+                if updates.is_empty() {
+                    state.syncing = false;
+                    return;
+                }
+                updates
+            };
+
+            match self.take_shared_tokens(&updates).await {
+                Ok(shared) => self.state.lock().unwrap().apply_shared(shared, Utc::now()),
+                Err(err) => {
+                    vlog::warn!("Can't synchronize the rate limit buckets: {}", err);
+                    self.state.lock().unwrap().restore_unsynced(updates);
+                }
+            }
+        }
+    }
+
+    async fn take_shared_tokens(
+        &self,
+        updates: &[RateLimitTokens],
+    ) -> anyhow::Result<Vec<RateLimitBucket>> {
+        let shared = self
+            .pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .take_rate_limit_tokens(updates)
+            .await?;
+        Ok(shared)
+    }
+}
+
+/// Buckets that weren't used for this time are full.
+fn stale_after(settings: &TxSenderSettings) -> Duration {
+    let (ip_limits, account_limits) = bucket_limits(settings);
+    ip_limits.refill_time().max(account_limits.refill_time())
+}
+
+/// Runs the task that periodically removes the unused rate limit buckets.
+/// Buckets are removed even if the rate limiting is disabled, since it may be enabled at runtime.
+#[must_use]
+pub fn run_rate_limit_cleaner(
+    pool: ConnectionPool,
+    settings: SharedTxSenderSettings,
+) -> JoinHandle<()> {
+    async fn remove_stale_buckets(
+        pool: &ConnectionPool,
+        settings: &SharedTxSenderSettings,
+    ) -> anyhow::Result<u64> {
+        let stale_after = stale_after(&settings.current());
+        let removed = pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .remove_stale_rate_limit_buckets(Utc::now() - chrono::Duration::from_std(stale_after)?)
+            .await?;
+        Ok(removed)
+    }
+
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(CLEANUP_INTERVAL);
+        loop {
+            timer.tick().await;
+            if let Err(err) = remove_stale_buckets(&pool, &settings).await {
+                vlog::warn!("Can't remove the stale rate limit buckets: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::error::{ErrorCode, TxError};
+    use actix_web::http::StatusCode;
+
+    fn bucket(tokens: f64, updated_at: DateTime<Utc>) -> RateLimitBucket {
+        RateLimitBucket {
+            key: "ip:127.0.0.1".to_string(),
+            tokens,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn token_bucket() {
+        // 2 tokens in a burst, 1 token every 2 seconds.
+        let limits = BucketLimits::new(2, 30);
+        let now = Utc::now();
+        let mut bucket = bucket(0.0, now);
+
+        assert_eq!(
+            refill(&mut bucket, limits, now),
+            Err(Duration::from_secs(2))
+        );
+        let now = now + chrono::Duration::seconds(1);
+        assert_eq!(
+            refill(&mut bucket, limits, now),
+            Err(Duration::from_secs(1))
+        );
+        let now = now + chrono::Duration::seconds(1);
+        assert!(refill(&mut bucket, limits, now).is_ok());
+
+        // Bucket is not refilled over its capacity.
+        let now = now + chrono::Duration::hours(1);
+        assert!(refill(&mut bucket, limits, now).is_ok());
+        assert_eq!(bucket.tokens, limits.capacity);
+        assert_eq!(limits.refill_time(), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn local_buckets() {
+        let ip_limits = BucketLimits::new(3, 60);
+        let account_limits = BucketLimits::new(1, 60);
+        let ip = ("ip:127.0.0.1".to_string(), ip_limits);
+        let account = ("account:0x01".to_string(), account_limits);
+        let now = Utc::now();
+        let mut state = LimiterState::default();
+
+        assert!(state
+            .take_tokens(&[account.clone(), ip.clone()], now)
+            .is_ok());
+        // Account bucket is empty, so the token is not taken from the IP bucket either.
+        assert!(state
+            .take_tokens(&[account.clone(), ip.clone()], now)
+            .is_err());
+        assert!(state.take_tokens(&[ip.clone()], now).is_ok());
+
+        let mut updates = state.take_unsynced(now);
+        updates.sort_by(|lhs, rhs| lhs.key.cmp(&rhs.key));
+        assert_eq!(
+            updates
+                .iter()
+                .map(|update| update.taken)
+                .collect::<Vec<_>>(),
+            vec![1.0, 2.0]
+        );
+        assert!(state.take_unsynced(now).is_empty());
+
+        // Another replica has taken the rest of the IP tokens meanwhile.
+        assert!(state.take_tokens(&[ip.clone()], now).is_ok());
+        state.apply_shared(vec![bucket(1.0, now)], now);
+        assert!(state.take_tokens(&[ip.clone()], now).is_err());
+
+        // Tokens which failed to be synchronized are retried.
+        state.restore_unsynced(updates);
+        assert_eq!(state.take_unsynced(now).len(), 2);
+
+        // Full buckets are forgotten once idle.
+        let later = now + chrono::Duration::minutes(5);
+        assert!(state.take_unsynced(later).is_empty());
+        assert!(state.buckets.is_empty());
+    }
+
+    #[test]
+    fn rate_limited_error() {
+        let error = TxError::submit_rejected(SubmitError::RateLimited(Duration::from_millis(1500)));
+        assert_eq!(error.code, ErrorCode::RateLimited);
+        assert_eq!(error.http_status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error.retry_after, Some(2));
+    }
+}
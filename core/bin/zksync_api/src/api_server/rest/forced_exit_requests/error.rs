@@ -105,7 +105,13 @@ impl SumbitErrorCode {
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
             SubmitError::Toggle2FA(_) => Self::Other,
+            SubmitError::ReplaceTx(_) => Self::Other,
             SubmitError::PriceError(_) => Self::Other,
+            SubmitError::RateLimited(_) => Self::Other,
+            SubmitError::MempoolFull { .. } => Self::Other,
+            SubmitError::TooManyPendingTxs { .. } => Self::Other,
+            SubmitError::AddressBlocked { .. } => Self::Other,
+            SubmitError::FeeTooLow { .. } => Self::TxAdd,
         }
     }
 
@@ -2,15 +2,17 @@ use actix_cors::Cors;
 use actix_web::{web, App, HttpResponse, HttpServer};
 use futures::channel::mpsc;
 use std::net::SocketAddr;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_types::{SequentialTxId, H160};
 
 use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 
-use self::v01::api_decl::ApiV01;
+use self::{v01::api_decl::ApiV01, v02::tx_status_events::TxStatusEvents};
+use crate::eth_checker::EthereumChecker;
 use crate::signature_checker::VerifySignatureRequest;
 
-use super::tx_sender::TxSender;
+use super::{tx_sender::TxSender, tx_sender_settings::SharedTxSenderSettings};
 
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use crate::fee_ticker::FeeTicker;
@@ -28,9 +30,25 @@ async fn start_server(
     api_v01: ApiV01,
     fee_ticker: FeeTicker,
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
+    eth_checker: EthereumChecker,
     bind_to: SocketAddr,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+    tx_sender_settings: SharedTxSenderSettings,
 ) {
+    let status_events = TxStatusEvents::new();
+    status_events.spawn_listener(
+        api_v01.main_database_connection_pool.clone(),
+        api_v01.config.event_listener.channel_name.clone(),
+    );
+    let ws_monitor = v02::ws::start_monitor(
+        api_v01.connection_pool.clone(),
+        api_v01
+            .config
+            .chain
+            .state_keeper
+            .miniblock_iteration_interval(),
+    );
+
     HttpServer::new(move || {
         let api_v01 = api_v01.clone();
         // This api stores forced exit requests, it's necessary to use main database connection
@@ -53,8 +71,17 @@ async fn start_server(
                 &api_v01.config.api.common,
                 &api_v01.config.api.token_config,
                 mempool_tx_sender.clone(),
-            );
-            v02::api_scope(tx_sender, &api_v01.config, api_v01.network_status.clone())
+            )
+            .with_settings(tx_sender_settings.clone());
+            v02::api_scope(
+                tx_sender,
+                api_v01.main_database_connection_pool.clone(),
+                eth_checker.clone(),
+                &api_v01.config,
+                api_v01.network_status.clone(),
+                status_events.clone(),
+                ws_monitor.clone(),
+            )
         };
         App::new()
             .wrap(
@@ -95,8 +122,10 @@ pub fn start_server_thread_detached(
     contract_address: H160,
     fee_ticker: FeeTicker,
     sign_verifier: mpsc::Sender<VerifySignatureRequest>,
+    eth_gateway: EthereumGateway,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     core_address: String,
+    tx_sender_settings: SharedTxSenderSettings,
 ) -> JoinHandle<()> {
     let (handler, panic_sender) = spawn_panic_handler();
 
@@ -131,8 +160,10 @@ pub fn start_server_thread_detached(
                     api_v01,
                     fee_ticker,
                     sign_verifier,
+                    EthereumChecker::new(eth_gateway),
                     listen_addr,
                     mempool_tx_sender.clone(),
+                    tx_sender_settings,
                 )
                 .await;
             });
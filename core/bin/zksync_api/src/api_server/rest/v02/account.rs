@@ -1,33 +1,76 @@
 //! Account part of API implementation.
 
 // Built-in uses
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::Instant;
 
 // External uses
-use actix_web::{web, Scope};
+use actix_web::{
+    web::{self, Json},
+    HttpRequest, Scope,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use num::{BigUint, Zero};
+use tokio::sync::Mutex;
 
 // Workspace uses
 use zksync_api_types::v02::{
-    account::{Account, AccountAddressOrId, AccountState, IncomingAccountTxsQuery},
+    account::{
+        Account, AccountAddressOrId, AccountExitProof, AccountExitProofQuery, AccountFees,
+        AccountMempool, AccountMempoolTx, AccountNfts, AccountNonce, AccountPendingBalances,
+        AccountRegistration, AccountState, AccountStateQuery, AccountsBatchRequest,
+        AccountsBatchResponse, BalanceHistoryQuery, BalanceHistoryResolution, BalancePoint,
+        EIP1271SignatureCheckRequest, IncomingAccountTxsQuery, PendingPriorityOp, PubKeyChange,
+        MAX_ACCOUNTS_BATCH_SIZE,
+    },
+    block::StoredBlockInfo,
     pagination::{
-        parse_query, AccountTxsRequest, ApiEither, Paginated, PaginationQuery, PendingOpsRequest,
+        parse_query, AccountTxsFilters, AccountTxsRequest, ApiEither, Paginated, PaginationQuery,
+        PendingOpsRequest,
     },
+    token::NFT,
     transaction::{Transaction, TxHashSerializeWrapper},
 };
-use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
+use zksync_crypto::{
+    circuit::{account::CircuitAccount, CircuitAccountTree},
+    params::{account_tree_depth, MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL},
+};
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Address, BlockNumber, SerialId, TokenLike};
+use zksync_types::{
+    block::Block, tx::TxHash, AccountId, AccountMap, Address, BlockNumber, Nonce, PubKeyHash,
+    SerialId, TokenId, TokenLike, ZkSyncTx,
+};
+use zksync_utils::BigUintSerdeWrapper;
 
 // Local uses
 use super::{
+    block::block_number_by_position,
     error::{Error, InvalidDataError},
     paginate_trait::Paginate,
-    response::ApiResult,
+    response::{ApiResult, ResolvedAddress},
+};
+use crate::{
+    api_server::helpers::get_depositing, api_try, eth_checker::EthereumChecker,
+    fee_ticker::PriceError, utils::shared_lru_cache::SharedLruCache,
 };
-use crate::{api_server::helpers::get_depositing, api_try, fee_ticker::PriceError};
+
+/// Period for which the resolved ENS names are cached.
+const ENS_NAME_CACHE_PERIOD_SECS: i64 = 60 * 60;
+/// Number of the reconstructed account states of the finalized blocks kept in memory.
+const ACCOUNT_STATES_CACHE_SIZE: usize = 10_000;
+
+/// Account tree of the finalized state the exit data is provided for.
+struct ExitTree {
+    block_number: BlockNumber,
+    accounts: AccountMap,
+    tree: CircuitAccountTree,
+}
 
 /// Shared data between `api/v02/accounts` endpoints.
 #[derive(Clone)]
@@ -35,14 +78,29 @@ struct ApiAccountData {
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    eth_checker: EthereumChecker,
+    exodus_mode: Arc<AtomicBool>,
+    exit_tree: Arc<Mutex<Option<Arc<ExitTree>>>>,
+    /// States of the accounts as of the finalized blocks, which can't change anymore.
+    account_states:
+        SharedLruCache<(AccountId, BlockNumber), Option<(BlockNumber, zksync_types::Account)>>,
 }
 
 impl ApiAccountData {
-    fn new(pool: ConnectionPool, tokens: TokenDBCache, confirmations_for_eth_event: u64) -> Self {
+    fn new(
+        pool: ConnectionPool,
+        tokens: TokenDBCache,
+        confirmations_for_eth_event: u64,
+        eth_checker: EthereumChecker,
+    ) -> Self {
         Self {
             pool,
             tokens,
             confirmations_for_eth_event,
+            eth_checker,
+            exodus_mode: Arc::new(AtomicBool::new(false)),
+            exit_tree: Arc::new(Mutex::new(None)),
+            account_states: SharedLruCache::new(ACCOUNT_STATES_CACHE_SIZE),
         }
     }
 
@@ -105,6 +163,50 @@ impl ApiAccountData {
         }
     }
 
+    /// Same as `parse_account_id_or_address`, but also accepts ENS names.
+    /// The address the name was resolved to is reported in the response envelope.
+    async fn resolve_account_id_or_address(
+        &self,
+        req: &HttpRequest,
+        account_address_or_id: &str,
+    ) -> Result<AccountAddressOrId, Error> {
+        // Neither account ids nor addresses contain dots.
+        if !account_address_or_id.contains('.') {
+            return self.parse_account_id_or_address(account_address_or_id);
+        }
+        let address = self.resolve_ens_name(account_address_or_id).await?;
+        req.extensions_mut().insert(ResolvedAddress(address));
+        Ok(AccountAddressOrId::Address(address))
+    }
+
+    /// Resolves the ENS name through the Ethereum node, the results are cached in the database.
+    async fn resolve_ens_name(&self, name: &str) -> Result<Address, Error> {
+        let name = name.to_lowercase();
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let resolved_after = Utc::now() - chrono::Duration::seconds(ENS_NAME_CACHE_PERIOD_SECS);
+        let cached_address = storage
+            .misc_schema()
+            .get_ens_name_address(&name, resolved_after)
+            .await
+            .map_err(Error::storage)?;
+        if let Some(address) = cached_address {
+            return Ok(address);
+        }
+
+        let address = self
+            .eth_checker
+            .resolve_ens_name(&name)
+            .await
+            .map_err(Error::from)?
+            .ok_or_else(|| Error::from(InvalidDataError::EnsNameNotResolved))?;
+        storage
+            .misc_schema()
+            .store_ens_name(&name, address)
+            .await
+            .map_err(Error::storage)?;
+        Ok(address)
+    }
+
     async fn api_account(
         &self,
         account: zksync_types::Account,
@@ -112,37 +214,10 @@ impl ApiAccountData {
         last_update_in_block: BlockNumber,
         storage: &mut StorageProcessor<'_>,
     ) -> Result<Account, Error> {
-        let mut balances = BTreeMap::new();
-        let mut nfts = BTreeMap::new();
-        for (token_id, balance) in account.get_nonzero_balances() {
-            match token_id.0 {
-                NFT_TOKEN_ID_VAL => {
-                    // Don't include special token to balances or nfts
-                }
-                MIN_NFT_TOKEN_ID..=NFT_TOKEN_ID_VAL => {
-                    // https://github.com/rust-lang/rust/issues/37854
-                    // Exclusive range is an experimental feature, but we have already checked the last value in the previous step
-                    nfts.insert(
-                        token_id,
-                        self.tokens
-                            .get_nft_by_id(storage, token_id)
-                            .await
-                            .map_err(Error::storage)?
-                            .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?
-                            .into(),
-                    );
-                }
-                _ => {
-                    let token_symbol = self
-                        .tokens
-                        .token_symbol(storage, token_id)
-                        .await
-                        .map_err(Error::storage)?
-                        .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?;
-                    balances.insert(token_symbol, balance);
-                }
-            }
-        }
+        let balances = self
+            .balances_by_symbol(account.get_nonzero_balances(), storage)
+            .await?;
+        let nfts = self.owned_nfts(&account, storage).await?;
         let minted_nfts = account
             .minted_nfts
             .iter()
@@ -169,20 +244,90 @@ impl ApiAccountData {
         })
     }
 
-    async fn account_committed_info(
+    async fn balances_by_symbol(
+        &self,
+        balances: HashMap<TokenId, BigUintSerdeWrapper>,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<BTreeMap<String, BigUintSerdeWrapper>, Error> {
+        let mut balances_by_symbol = BTreeMap::new();
+        for (token_id, balance) in balances {
+            match token_id.0 {
+                MIN_NFT_TOKEN_ID..=NFT_TOKEN_ID_VAL => {
+                    // NFTs are collected separately, the special token isn't included at all
+                }
+                _ => {
+                    let token_symbol = self
+                        .tokens
+                        .token_symbol(storage, token_id)
+                        .await
+                        .map_err(Error::storage)?
+                        .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?;
+                    balances_by_symbol.insert(token_symbol, balance);
+                }
+            }
+        }
+        Ok(balances_by_symbol)
+    }
+
+    async fn owned_nfts(
+        &self,
+        account: &zksync_types::Account,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<BTreeMap<TokenId, NFT>, Error> {
+        let mut nfts = BTreeMap::new();
+        for (token_id, _) in account.get_nonzero_balances() {
+            // Don't include special token to nfts
+            if (MIN_NFT_TOKEN_ID..NFT_TOKEN_ID_VAL).contains(&token_id.0) {
+                nfts.insert(
+                    token_id,
+                    self.tokens
+                        .get_nft_by_id(storage, token_id)
+                        .await
+                        .map_err(Error::storage)?
+                        .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?
+                        .into(),
+                );
+            }
+        }
+        Ok(nfts)
+    }
+
+    async fn accounts_by_pubkey_hash(
+        &self,
+        pub_key_hash: &PubKeyHash,
+    ) -> Result<Vec<Account>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let account_ids = storage
+            .chain()
+            .account_schema()
+            .account_ids_by_pubkey_hash(pub_key_hash)
+            .await
+            .map_err(Error::storage)?;
+        // Every account state is loaded with its own connection.
+        drop(storage);
+
+        let mut accounts = Vec::with_capacity(account_ids.len());
+        for account_id in account_ids {
+            if let Some(account) = self.account_committed_info(account_id).await? {
+                accounts.push(account);
+            }
+        }
+        Ok(accounts)
+    }
+
+    async fn committed_account(
         &self,
         account_id: AccountId,
+        storage: &mut StorageProcessor<'_>,
     ) -> Result<Option<Account>, Error> {
-        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
-        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
-        let ((last_block, _), account) = transaction
+        let ((last_block, _), account) = storage
             .chain()
             .account_schema()
             .last_committed_state_for_account(account_id)
             .await
             .map_err(Error::storage)?;
-        let result = if let Some(account) = account {
-            let last_block = transaction
+        if let Some(account) = account {
+            let last_block = storage
                 .chain()
                 .account_schema()
                 .last_committed_block_with_update_for_acc(
@@ -192,16 +337,70 @@ impl ApiAccountData {
                 .await
                 .map_err(Error::storage)?;
             Ok(Some(
-                self.api_account(account, account_id, last_block, &mut transaction)
+                self.api_account(account, account_id, last_block, storage)
                     .await?,
             ))
         } else {
             Ok(None)
-        };
+        }
+    }
+
+    async fn account_committed_info(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<Account>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let result = self.committed_account(account_id, &mut transaction).await;
         transaction.commit().await.map_err(Error::storage)?;
         result
     }
 
+    async fn accounts_batch(&self, accounts: Vec<String>) -> Result<AccountsBatchResponse, Error> {
+        if accounts.len() > MAX_ACCOUNTS_BATCH_SIZE {
+            return Err(Error::from(InvalidDataError::TooManyAccounts));
+        }
+        let requested = accounts
+            .into_iter()
+            .map(|account| {
+                let address_or_id = self.parse_account_id_or_address(&account)?;
+                Ok((account, address_or_id))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let addresses: Vec<Address> = requested
+            .iter()
+            .filter_map(|(_, address_or_id)| match address_or_id {
+                AccountAddressOrId::Address(address) => Some(*address),
+                AccountAddressOrId::Id(_) => None,
+            })
+            .collect();
+
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let account_ids = transaction
+            .chain()
+            .account_schema()
+            .account_ids_by_addresses(&addresses)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut response = AccountsBatchResponse::with_capacity(requested.len());
+        for (account, address_or_id) in requested {
+            let account_id = match address_or_id {
+                AccountAddressOrId::Id(account_id) => Some(account_id),
+                AccountAddressOrId::Address(address) => account_ids.get(&address).copied(),
+            };
+            let state = if let Some(account_id) = account_id {
+                self.committed_account(account_id, &mut transaction).await?
+            } else {
+                None
+            };
+            response.insert(account, state);
+        }
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(response)
+    }
+
     async fn account_finalized_info(
         &self,
         account_id: AccountId,
@@ -231,6 +430,106 @@ impl ApiAccountData {
         result
     }
 
+    async fn account_state_for_block(
+        &self,
+        account_id: AccountId,
+        block_position: &str,
+    ) -> Result<Option<Account>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let block_number = block_number_by_position(&mut transaction, block_position).await?;
+        let account_state = match self.account_states.get(&(account_id, block_number)) {
+            Some(account_state) => account_state,
+            None => {
+                let account_state = transaction
+                    .chain()
+                    .account_schema()
+                    .account_state_for_block(account_id, block_number)
+                    .await
+                    .map_err(Error::storage)?;
+                let last_finalized_block = transaction
+                    .chain()
+                    .block_schema()
+                    .get_last_verified_confirmed_block()
+                    .await
+                    .map_err(Error::storage)?;
+                if block_number <= last_finalized_block {
+                    self.account_states
+                        .insert((account_id, block_number), account_state.clone());
+                }
+                account_state
+            }
+        };
+        let result = if let Some((last_block, account)) = account_state {
+            Ok(Some(
+                self.api_account(account, account_id, last_block, &mut transaction)
+                    .await?,
+            ))
+        } else {
+            Ok(None)
+        };
+        transaction.commit().await.map_err(Error::storage)?;
+        result
+    }
+
+    async fn account_nonce(&self, account_id: AccountId) -> Result<Option<AccountNonce>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let ((_, finalized), committed) = transaction
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let result = if let Some(committed) = committed {
+            let mempool_nonce = transaction
+                .chain()
+                .mempool_schema()
+                .get_max_nonce_for_account(account_id)
+                .await
+                .map_err(Error::storage)?;
+            let next = match mempool_nonce {
+                Some(nonce) if nonce >= committed.nonce => nonce + 1,
+                _ => committed.nonce,
+            };
+            Some(AccountNonce {
+                committed: committed.nonce,
+                finalized: finalized.map(|account| account.nonce).unwrap_or_default(),
+                next,
+            })
+        } else {
+            None
+        };
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(result)
+    }
+
+    async fn account_nfts(&self, account_id: AccountId) -> Result<Option<AccountNfts>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let mut transaction = storage.start_transaction().await.map_err(Error::storage)?;
+        let ((_, finalized), committed) = transaction
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let result = if let Some(committed) = committed {
+            let finalized = if let Some(finalized) = finalized {
+                self.owned_nfts(&finalized, &mut transaction).await?
+            } else {
+                BTreeMap::new()
+            };
+            Some(AccountNfts {
+                committed: self.owned_nfts(&committed, &mut transaction).await?,
+                finalized,
+            })
+        } else {
+            None
+        };
+        transaction.commit().await.map_err(Error::storage)?;
+        Ok(result)
+    }
+
     async fn account_full_info(
         &self,
         address: Address,
@@ -290,21 +589,39 @@ impl ApiAccountData {
         })
     }
 
+    async fn account_balance_history(
+        &self,
+        account_id: AccountId,
+        token_like: TokenLike,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        resolution: BalanceHistoryResolution,
+    ) -> Result<Option<Vec<BalancePoint>>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let token = self.tokens.resolve_token(&mut storage, token_like).await?;
+        let history = storage
+            .chain()
+            .account_schema()
+            .get_account_balance_history(account_id, token.id, from, to, resolution)
+            .await
+            .map_err(Error::storage)?;
+        Ok(Some(history))
+    }
+
     async fn account_txs(
         &self,
         query: PaginationQuery<ApiEither<TxHash>>,
         address: Address,
         token_like: Option<TokenLike>,
         second_address: Option<Address>,
+        filters: AccountTxsFilters,
     ) -> Result<Paginated<Transaction, TxHashSerializeWrapper>, Error> {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         let token = if let Some(token_like) = token_like {
             Some(
                 self.tokens
-                    .get_token(&mut storage, token_like.clone())
-                    .await
-                    .map_err(Error::storage)?
-                    .ok_or_else(|| Error::from(PriceError::token_not_found(token_like)))?
+                    .resolve_token(&mut storage, token_like)
+                    .await?
                     .id,
             )
         } else {
@@ -316,6 +633,7 @@ impl ApiAccountData {
                 address,
                 token,
                 second_address,
+                filters,
             },
             limit: query.limit,
             direction: query.direction,
@@ -345,14 +663,467 @@ impl ApiAccountData {
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
         storage.paginate_checked(&new_query).await
     }
+
+    async fn account_pending_balance(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountPendingBalances>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let (_, account) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let account = match account {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .get_account_txs(account_id, account.address)
+            .await
+            .map_err(Error::storage)?;
+
+        let committed = account.get_nonzero_balances();
+        let mut pending = committed.clone();
+        for tx in txs {
+            apply_pending_tx(&mut pending, &tx.tx, account_id, account.address);
+        }
+        pending.retain(|_, balance| !balance.0.is_zero());
+
+        Ok(Some(AccountPendingBalances {
+            committed: self.balances_by_symbol(committed, &mut storage).await?,
+            pending: self.balances_by_symbol(pending, &mut storage).await?,
+        }))
+    }
+
+    async fn account_mempool(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Option<AccountMempool>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let (_, account) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let account = match account {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .get_account_txs(account_id, account.address)
+            .await
+            .map_err(Error::storage)?;
+
+        Ok(Some(AccountMempool {
+            committed_nonce: account.nonce,
+            txs: queued_account_txs(txs.into_iter().map(|tx| tx.tx), account_id, account.nonce),
+        }))
+    }
+
+    async fn account_pubkey_history(&self, address: Address) -> Result<Vec<PubKeyChange>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_pubkey_changes(address)
+            .await
+            .map_err(Error::storage)
+    }
+
+    async fn account_pending_priority_ops(
+        &self,
+        address: Address,
+    ) -> Result<Vec<PendingPriorityOp>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let ops = storage
+            .chain()
+            .mempool_schema()
+            .get_pending_priority_ops(address)
+            .await
+            .map_err(Error::storage)?;
+        Ok(ops
+            .into_iter()
+            .map(|op| PendingPriorityOp::new(op, self.confirmations_for_eth_event))
+            .collect())
+    }
+
+    async fn account_registration(
+        &self,
+        account_id: Option<AccountId>,
+    ) -> Result<AccountRegistration, Error> {
+        let account_id = if let Some(account_id) = account_id {
+            account_id
+        } else {
+            return Ok(AccountRegistration::default());
+        };
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let created_in_block = storage
+            .chain()
+            .account_schema()
+            .account_creation_block(account_id)
+            .await
+            .map_err(Error::storage)?;
+        if created_in_block.is_none() {
+            return Ok(AccountRegistration::default());
+        }
+        let (_, account) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(Error::storage)?;
+        let signing_key_set = account
+            .map(|account| account.pub_key_hash != PubKeyHash::default())
+            .unwrap_or_default();
+
+        Ok(AccountRegistration {
+            account_id: Some(account_id),
+            created_in_block,
+            signing_key_set,
+        })
+    }
+
+    async fn account_fees(&self, address: Address) -> Result<AccountFees, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let fees = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_fees(address)
+            .await
+            .map_err(Error::storage)?;
+
+        let mut account_fees = AccountFees::default();
+        for fee in fees {
+            let token_symbol = self
+                .tokens
+                .token_symbol(&mut storage, fee.token_id)
+                .await
+                .map_err(Error::storage)?
+                .ok_or_else(|| Error::from(PriceError::token_not_found(fee.token_id)))?;
+            account_fees
+                .total
+                .entry(token_symbol.clone())
+                .or_default()
+                .0 += &fee.total_fee;
+            account_fees
+                .by_tx_type
+                .entry(fee.tx_type)
+                .or_default()
+                .insert(token_symbol, fee.total_fee.into());
+        }
+        Ok(account_fees)
+    }
+
+    /// Account tree of the last finalized state is rebuilt once per finalized block,
+    /// so the exit data is only provided in the exodus mode.
+    async fn account_exit_proof(
+        &self,
+        account_id: AccountId,
+        token_like: TokenLike,
+    ) -> Result<Option<AccountExitProof>, Error> {
+        if !self.is_exodus_mode().await? {
+            return Err(Error::from(InvalidDataError::ExodusModeNotActive));
+        }
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let token = self.tokens.resolve_token(&mut storage, token_like).await?;
+        let exit_tree = self.finalized_exit_tree(&mut storage).await?;
+        let account = if let Some(account) = exit_tree.accounts.get(&account_id) {
+            account.clone()
+        } else {
+            return Ok(None);
+        };
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(exit_tree.block_number)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::storage("Last finalized block is not stored"))?;
+
+        let (nft_creator_id, nft_creator_address, nft_serial_id, nft_content_hash) =
+            if token.id.0 >= MIN_NFT_TOKEN_ID {
+                let nft = storage
+                    .tokens_schema()
+                    .get_nft(token.id)
+                    .await
+                    .map_err(Error::storage)?
+                    .ok_or_else(|| Error::from(PriceError::token_not_found(token.id)))?;
+                (
+                    nft.creator_id,
+                    nft.creator_address,
+                    nft.serial_id,
+                    nft.content_hash,
+                )
+            } else {
+                // The placeholder creator address should be the address of the account with id 0.
+                let creator_address = exit_tree
+                    .accounts
+                    .get(&AccountId(0))
+                    .map(|account| account.address)
+                    .unwrap_or_default();
+                (AccountId(0), creator_address, 0, Default::default())
+            };
+
+        let amount = account.get_balance(token.id);
+        let owner = account.address;
+        let token_id = token.id;
+        let (account_path, balance_path) = tokio::task::spawn_blocking(move || {
+            let balance_path = CircuitAccount::from(account)
+                .subtree
+                .merkle_path(*token_id)
+                .into_iter()
+                .map(|(hash, _)| Block::encode_fr_for_eth(hash))
+                .collect();
+            let account_path = exit_tree
+                .tree
+                .merkle_path(*account_id)
+                .into_iter()
+                .map(|(hash, _)| Block::encode_fr_for_eth(hash))
+                .collect();
+            (account_path, balance_path)
+        })
+        .await
+        .map_err(|err| Error::from(anyhow::Error::from(err)))?;
+
+        Ok(Some(AccountExitProof {
+            stored_block_info: StoredBlockInfo {
+                block_number: block.block_number,
+                priority_operations: block.number_of_processed_prior_ops(),
+                pending_onchain_operations_hash: block.get_onchain_operations_block_info().1,
+                timestamp: block.timestamp,
+                state_hash: block.get_eth_encoded_root(),
+                commitment: block.block_commitment,
+            },
+            owner,
+            account_id,
+            token_id,
+            amount: amount.into(),
+            nft_creator_id,
+            nft_creator_address,
+            nft_serial_id,
+            nft_content_hash,
+            account_path,
+            balance_path,
+        }))
+    }
+
+    /// Exodus mode can't be left once activated, so the contract isn't queried after that.
+    async fn is_exodus_mode(&self) -> Result<bool, Error> {
+        if self.exodus_mode.load(Ordering::Relaxed) {
+            return Ok(true);
+        }
+        let exodus_mode = self
+            .eth_checker
+            .is_exodus_mode()
+            .await
+            .map_err(Error::from)?;
+        self.exodus_mode.store(exodus_mode, Ordering::Relaxed);
+        Ok(exodus_mode)
+    }
+
+    /// Returns the account tree of the last finalized state, rebuilding it if a new block
+    /// was finalized. Concurrent requests wait for a single rebuild.
+    async fn finalized_exit_tree(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<Arc<ExitTree>, Error> {
+        let mut cached_tree = self.exit_tree.lock().await;
+        let block_number = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+        if let Some(exit_tree) = cached_tree.as_ref() {
+            if exit_tree.block_number == block_number {
+                return Ok(exit_tree.clone());
+            }
+        }
+
+        let (block_number, accounts) = storage
+            .chain()
+            .state_schema()
+            .load_verified_state()
+            .await
+            .map_err(Error::storage)?;
+        let exit_tree = tokio::task::spawn_blocking(move || {
+            let mut tree = CircuitAccountTree::new(account_tree_depth());
+            for (id, account) in &accounts {
+                tree.insert(**id, CircuitAccount::from(account.clone()));
+            }
+            // Hashes are computed once here instead of the first request for the path.
+            tree.root_hash();
+            ExitTree {
+                block_number,
+                accounts,
+                tree,
+            }
+        })
+        .await
+        .map_err(|err| Error::from(anyhow::Error::from(err)))?;
+
+        let exit_tree = Arc::new(exit_tree);
+        *cached_tree = Some(exit_tree.clone());
+        Ok(exit_tree)
+    }
+
+    async fn is_contract_wallet(&self, address: Address) -> Result<bool, Error> {
+        self.eth_checker
+            .is_contract(address)
+            .await
+            .map_err(Error::from)
+    }
+
+    async fn check_eip1271_signature(
+        &self,
+        address: Address,
+        request: EIP1271SignatureCheckRequest,
+    ) -> Result<bool, Error> {
+        self.eth_checker
+            .is_eip1271_signature_correct(address, &request.message, request.signature)
+            .await
+            .map_err(Error::from)
+    }
+}
+
+fn credit(balances: &mut HashMap<TokenId, BigUintSerdeWrapper>, token: TokenId, amount: &BigUint) {
+    balances.entry(token).or_default().0 += amount;
+}
+
+/// Debits exceeding the balance leave it at zero.
+fn debit(balances: &mut HashMap<TokenId, BigUintSerdeWrapper>, token: TokenId, amount: &BigUint) {
+    let balance = &mut balances.entry(token).or_default().0;
+    *balance = if *balance > *amount {
+        &*balance - amount
+    } else {
+        BigUint::zero()
+    };
+}
+
+/// Applies the balance changes the mempool transaction makes for the given account.
+fn apply_pending_tx(
+    balances: &mut HashMap<TokenId, BigUintSerdeWrapper>,
+    tx: &ZkSyncTx,
+    account_id: AccountId,
+    address: Address,
+) {
+    match tx {
+        ZkSyncTx::Transfer(tx) => {
+            if tx.account_id == account_id {
+                debit(balances, tx.token, &(&tx.amount + &tx.fee));
+            }
+            if tx.to == address {
+                credit(balances, tx.token, &tx.amount);
+            }
+        }
+        ZkSyncTx::Withdraw(tx) => {
+            if tx.account_id == account_id {
+                debit(balances, tx.token, &(&tx.amount + &tx.fee));
+            }
+        }
+        ZkSyncTx::ChangePubKey(tx) => {
+            if tx.account_id == account_id {
+                debit(balances, tx.fee_token, &tx.fee);
+            }
+        }
+        ZkSyncTx::ForcedExit(tx) => {
+            if tx.initiator_account_id == account_id {
+                debit(balances, tx.token, &tx.fee);
+            }
+            // The whole balance of the target is withdrawn.
+            if tx.target == address {
+                balances.remove(&tx.token);
+            }
+        }
+        ZkSyncTx::MintNFT(tx) => {
+            if tx.creator_id == account_id {
+                debit(balances, tx.fee_token, &tx.fee);
+            }
+        }
+        ZkSyncTx::WithdrawNFT(tx) => {
+            if tx.account_id == account_id {
+                debit(balances, tx.fee_token, &tx.fee);
+            }
+        }
+        ZkSyncTx::Swap(tx) => {
+            if tx.submitter_id == account_id {
+                debit(balances, tx.fee_token, &tx.fee);
+            }
+            // Each order sells its amount and its recipient gets the amount of the other order.
+            let (order_0, order_1) = &tx.orders;
+            let (amount_0, amount_1) = &tx.amounts;
+            for (order, sold, bought) in
+                [(order_0, amount_0, amount_1), (order_1, amount_1, amount_0)]
+            {
+                if order.account_id == account_id {
+                    debit(balances, order.token_sell, sold);
+                }
+                if order.recipient_address == address {
+                    credit(balances, order.token_buy, bought);
+                }
+            }
+        }
+        ZkSyncTx::Close(_) => {}
+    }
+}
+
+/// Picks the transactions initiated by the account, sorts them by nonce and marks
+/// the ones that are preceded by missing nonces.
+fn queued_account_txs(
+    txs: impl Iterator<Item = ZkSyncTx>,
+    account_id: AccountId,
+    committed_nonce: Nonce,
+) -> Vec<AccountMempoolTx> {
+    let mut txs: Vec<_> = txs
+        .filter(|tx| tx.account_id().ok() == Some(account_id))
+        .collect();
+    txs.sort_by_key(|tx| tx.nonce());
+
+    let mut expected_nonce = committed_nonce;
+    txs.into_iter()
+        .map(|tx| {
+            let nonce = tx.nonce();
+            let nonce_gap = nonce > expected_nonce;
+            expected_nonce = std::cmp::max(expected_nonce, nonce + 1);
+            AccountMempoolTx {
+                tx_hash: tx.hash(),
+                tx_type: tx.variance_name(),
+                nonce,
+                nonce_gap,
+            }
+        })
+        .collect()
+}
+
+async fn accounts_by_pubkey_hash(
+    data: web::Data<ApiAccountData>,
+    pub_key_hash: web::Path<String>,
+) -> ApiResult<Vec<Account>> {
+    let start = Instant::now();
+    let pub_key_hash = api_try!(PubKeyHash::from_hex(&pub_key_hash)
+        .map_err(|_| Error::from(InvalidDataError::InvalidPubKeyHash)));
+    let res = data.accounts_by_pubkey_hash(&pub_key_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_by_pubkey_hash");
+    res
 }
 
 async fn account_committed_info(
     data: web::Data<ApiAccountData>,
+    req: HttpRequest,
     account_id_or_address: web::Path<String>,
 ) -> ApiResult<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
     let res = if let Some(account_id) = account_id {
         data.account_committed_info(account_id).await.into()
@@ -363,28 +1134,136 @@ async fn account_committed_info(
     res
 }
 
+async fn accounts_batch(
+    data: web::Data<ApiAccountData>,
+    Json(body): Json<AccountsBatchRequest>,
+) -> ApiResult<AccountsBatchResponse> {
+    let start = Instant::now();
+    let res = data.accounts_batch(body.accounts).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "accounts_batch");
+    res
+}
+
 async fn account_finalized_info(
     data: web::Data<ApiAccountData>,
+    req: HttpRequest,
     account_id_or_address: web::Path<String>,
 ) -> ApiResult<Option<Account>> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
     let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
     let res = if let Some(account_id) = account_id {
         data.account_finalized_info(account_id).await.into()
     } else {
         ApiResult::Ok(None)
     };
-    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_info");
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_finalized_info");
+    res
+}
+
+async fn account_state_for_block(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<AccountStateQuery>,
+) -> ApiResult<Option<Account>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_state_for_block(account_id, &query.block)
+            .await
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_state_for_block");
+    res
+}
+
+async fn account_nonce(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Option<AccountNonce>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_nonce(account_id).await.into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_nonce");
+    res
+}
+
+async fn account_nfts(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Option<AccountNfts>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_nfts(account_id).await.into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_nfts");
+    res
+}
+
+async fn account_balance_history(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<BalanceHistoryQuery>,
+) -> ApiResult<Option<Vec<BalancePoint>>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let token_like = TokenLike::parse(&query.token);
+    let from = query.from.unwrap_or_else(|| Utc.timestamp(0, 0));
+    let to = query.to.unwrap_or_else(Utc::now);
+    let resolution = query.resolution.unwrap_or_default();
+    let res = if let Some(account_id) = account_id {
+        data.account_balance_history(account_id, token_like, from, to, resolution)
+            .await
+            .into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_balance_history");
     res
 }
 
 async fn account_full_info(
     data: web::Data<ApiAccountData>,
+    req: HttpRequest,
     account_id_or_address: web::Path<String>,
 ) -> ApiResult<AccountState> {
     let start = Instant::now();
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
@@ -397,6 +1276,7 @@ async fn account_full_info(
 
 async fn account_txs(
     data: web::Data<ApiAccountData>,
+    req: HttpRequest,
     account_id_or_address: web::Path<String>,
     web::Query(query): web::Query<IncomingAccountTxsQuery>,
 ) -> ApiResult<Paginated<Transaction, TxHashSerializeWrapper>> {
@@ -408,7 +1288,10 @@ async fn account_txs(
     })
     .map_err(Error::from));
 
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
     let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
 
     let second_address = if let Some(second_account) = query.second_account {
@@ -421,9 +1304,15 @@ async fn account_txs(
     };
 
     let token_like = query.token.map(|token| TokenLike::parse(&token));
+    let filters = AccountTxsFilters {
+        tx_direction: query.tx_direction,
+        tx_type: query.tx_type,
+        from_time: query.from_time,
+        to_time: query.to_time,
+    };
 
     let res = data
-        .account_txs(pagination, address, token_like, second_address)
+        .account_txs(pagination, address, token_like, second_address, filters)
         .await
         .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_txs");
@@ -432,12 +1321,16 @@ async fn account_txs(
 
 async fn account_pending_txs(
     data: web::Data<ApiAccountData>,
+    req: HttpRequest,
     account_id_or_address: web::Path<String>,
     web::Query(query): web::Query<PaginationQuery<String>>,
 ) -> ApiResult<Paginated<Transaction, SerialId>> {
     let start = Instant::now();
     let query = api_try!(parse_query(query).map_err(Error::from));
-    let address_or_id = api_try!(data.parse_account_id_or_address(&account_id_or_address));
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
     let address = api_try!(
         data.get_address_by_address_or_id(address_or_id.clone())
             .await
@@ -451,15 +1344,180 @@ async fn account_pending_txs(
     res
 }
 
+async fn account_pending_balance(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Option<AccountPendingBalances>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_pending_balance(account_id).await.into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_pending_balance");
+    res
+}
+
+async fn account_mempool(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Option<AccountMempool>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = if let Some(account_id) = account_id {
+        data.account_mempool(account_id).await.into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_mempool");
+    res
+}
+
+async fn account_pubkey_history(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<PubKeyChange>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_pubkey_history(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_pubkey_history");
+    res
+}
+
+async fn account_pending_priority_ops(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<Vec<PendingPriorityOp>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_pending_priority_ops(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_pending_priority_ops");
+    res
+}
+
+async fn account_registration(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<AccountRegistration> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let res = data.account_registration(account_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_registration");
+    res
+}
+
+async fn account_fees(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<AccountFees> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.account_fees(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_fees");
+    res
+}
+
+async fn account_exit_proof(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+    web::Query(query): web::Query<AccountExitProofQuery>,
+) -> ApiResult<Option<AccountExitProof>> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let account_id = api_try!(data.get_id_by_address_or_id(address_or_id).await);
+    let token_like = TokenLike::parse(&query.token);
+    let res = if let Some(account_id) = account_id {
+        data.account_exit_proof(account_id, token_like).await.into()
+    } else {
+        ApiResult::Ok(None)
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_exit_proof");
+    res
+}
+
+async fn account_is_contract_wallet(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+) -> ApiResult<bool> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.is_contract_wallet(address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_is_contract_wallet");
+    res
+}
+
+async fn account_check_eip1271_signature(
+    data: web::Data<ApiAccountData>,
+    req: HttpRequest,
+    account_id_or_address: web::Path<String>,
+    Json(request): Json<EIP1271SignatureCheckRequest>,
+) -> ApiResult<bool> {
+    let start = Instant::now();
+    let address_or_id = api_try!(
+        data.resolve_account_id_or_address(&req, &account_id_or_address)
+            .await
+    );
+    let address = api_try!(data.get_address_by_address_or_id(address_or_id).await);
+    let res = data.check_eip1271_signature(address, request).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "account_check_eip1271_signature");
+    res
+}
+
 pub fn api_scope(
     pool: ConnectionPool,
     tokens: TokenDBCache,
     confirmations_for_eth_event: u64,
+    eth_checker: EthereumChecker,
 ) -> Scope {
-    let data = ApiAccountData::new(pool, tokens, confirmations_for_eth_event);
+    let data = ApiAccountData::new(pool, tokens, confirmations_for_eth_event, eth_checker);
 
     web::scope("accounts")
         .app_data(web::Data::new(data))
+        .route("batch", web::post().to(accounts_batch))
+        .route(
+            "by_pubkey_hash/{pub_key_hash}",
+            web::get().to(accounts_by_pubkey_hash),
+        )
         .route(
             "{account_id_or_address}/committed",
             web::get().to(account_committed_info),
@@ -468,6 +1526,23 @@ pub fn api_scope(
             "{account_id_or_address}/finalized",
             web::get().to(account_finalized_info),
         )
+        .route(
+            "{account_id_or_address}/state",
+            web::get().to(account_state_for_block),
+        )
+        .route(
+            "{account_id_or_address}/nonce",
+            web::get().to(account_nonce),
+        )
+        .route("{account_id_or_address}/nfts", web::get().to(account_nfts))
+        .route(
+            "{account_id_or_address}/pending_balance",
+            web::get().to(account_pending_balance),
+        )
+        .route(
+            "{account_id_or_address}/balance_history",
+            web::get().to(account_balance_history),
+        )
         .route("{account_id_or_address}", web::get().to(account_full_info))
         .route(
             "{account_id_or_address}/transactions",
@@ -477,26 +1552,62 @@ pub fn api_scope(
             "{account_id_or_address}/transactions/pending",
             web::get().to(account_pending_txs),
         )
+        .route(
+            "{account_id_or_address}/pubkey_history",
+            web::get().to(account_pubkey_history),
+        )
+        .route(
+            "{account_id_or_address}/pending_priority_ops",
+            web::get().to(account_pending_priority_ops),
+        )
+        .route("{account_id_or_address}/fees", web::get().to(account_fees))
+        .route(
+            "{account_id_or_address}/registration",
+            web::get().to(account_registration),
+        )
+        .route(
+            "{account_id_or_address}/proof",
+            web::get().to(account_exit_proof),
+        )
+        .route(
+            "{account_id_or_address}/is_contract_wallet",
+            web::get().to(account_is_contract_wallet),
+        )
+        .route(
+            "{account_id_or_address}/check_eip1271_signature",
+            web::post().to(account_check_eip1271_signature),
+        )
+        .route(
+            "{account_id_or_address}/mempool",
+            web::get().to(account_mempool),
+        )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::api_server::rest::v02::{
+        error::ErrorCode,
         test_utils::{deserialize_response_result, TestServerConfig},
         SharedData,
     };
+    use chrono::{Duration, Utc};
+    use ethabi::Token;
     use num::BigUint;
     use serde::Deserialize;
     use zksync_api_client::rest::client::Client;
     use zksync_api_types::v02::{
         account::{DepositingAccountBalances, DepositingFunds},
         pagination::{PaginationDirection, PaginationQuery},
-        transaction::{L1Transaction, TransactionData},
+        transaction::{L1Transaction, TransactionData, TxInBlockStatus},
         ApiVersion,
     };
+    use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
     use zksync_storage::StorageProcessor;
-    use zksync_types::{AccountId, Address, Deposit, PriorityOp, TokenId, ZkSyncPriorityOp, H256};
+    use zksync_types::{
+        tx::Transfer, AccountId, Address, Deposit, Nonce, PriorityOp, TokenId, ZkSyncPriorityOp,
+        H256,
+    };
 
     // While the values of the PendingOpsFlattenRequest's fields are never directly
     // used in the tests, we still need them to specify the JSON format of the `unconfirmed_ops` endpoint input in tests.
@@ -513,6 +1624,7 @@ mod tests {
     struct TestServer {
         api_server: actix_test::TestServer,
         pool: ConnectionPool,
+        eth: MockEthereum,
         confirmations_for_eth_event: u64,
     }
 
@@ -522,6 +1634,8 @@ mod tests {
             cfg.fill_database().await?;
 
             let pool = cfg.pool.clone();
+            let eth = MockEthereum::default();
+            let server_eth = eth.clone();
 
             let shared_data = SharedData {
                 net: cfg.config.chain.eth.network,
@@ -535,6 +1649,7 @@ mod tests {
                             cfg.config.api.token_config.invalidate_token_cache_period(),
                         ),
                         cfg.config.eth_watch.confirmations_for_eth_event,
+                        EthereumChecker::new(EthereumGateway::Mock(server_eth.clone())),
                     )
                 },
                 Some(shared_data),
@@ -545,6 +1660,7 @@ mod tests {
                 Self {
                     api_server,
                     pool,
+                    eth,
                     confirmations_for_eth_event: cfg.config.eth_watch.confirmations_for_eth_event,
                 },
             ))
@@ -576,6 +1692,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn pending_tx_balances() {
+        let account_id = AccountId(1);
+        let address = Address::random();
+        let mut balances = HashMap::new();
+        balances.insert(TokenId(0), BigUintSerdeWrapper(BigUint::from(100u32)));
+
+        // Outgoing transfer debits both the amount and the fee.
+        let outgoing = Transfer::new(
+            account_id,
+            address,
+            Address::random(),
+            TokenId(0),
+            60u32.into(),
+            10u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        apply_pending_tx(&mut balances, &outgoing.clone().into(), account_id, address);
+        assert_eq!(balances[&TokenId(0)].0, BigUint::from(30u32));
+
+        // Incoming transfer credits the amount only.
+        let incoming = Transfer::new(
+            AccountId(2),
+            Address::random(),
+            address,
+            TokenId(1),
+            50u32.into(),
+            10u32.into(),
+            Nonce(0),
+            Default::default(),
+            None,
+        );
+        apply_pending_tx(&mut balances, &incoming.into(), account_id, address);
+        assert_eq!(balances[&TokenId(1)].0, BigUint::from(50u32));
+
+        // The balance can't go below zero.
+        apply_pending_tx(&mut balances, &outgoing.into(), account_id, address);
+        assert!(balances[&TokenId(0)].0.is_zero());
+    }
+
+    #[test]
+    fn queued_txs_nonce_gaps() {
+        let account_id = AccountId(1);
+        let address = Address::random();
+        let transfer = |account_id, nonce| -> ZkSyncTx {
+            Transfer::new(
+                account_id,
+                address,
+                Address::random(),
+                TokenId(0),
+                10u32.into(),
+                1u32.into(),
+                Nonce(nonce),
+                Default::default(),
+                None,
+            )
+            .into()
+        };
+        let txs = vec![
+            transfer(account_id, 5),
+            transfer(AccountId(2), 0),
+            transfer(account_id, 3),
+            transfer(account_id, 2),
+        ];
+
+        let queued = queued_account_txs(txs.into_iter(), account_id, Nonce(2));
+        let nonces: Vec<_> = queued.iter().map(|tx| (tx.nonce, tx.nonce_gap)).collect();
+        assert_eq!(
+            nonces,
+            vec![(Nonce(2), false), (Nonce(3), false), (Nonce(5), true)]
+        );
+        assert!(queued.iter().all(|tx| tx.tx_type == "Transfer"));
+    }
+
     #[actix_rt::test]
     #[cfg_attr(
         not(feature = "api_test"),
@@ -606,11 +1798,81 @@ mod tests {
             account_committed_info_by_address
         );
 
+        // ENS names are resolved from the cache without querying the Ethereum node.
+        server
+            .pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .store_ens_name("account.eth", address)
+            .await?;
+        let response = client.account_info("Account.eth", "committed").await?;
+        assert_eq!(response.request.resolved_address, Some(address));
+        let account_committed_info_by_ens: Account = deserialize_response_result(response)?;
+        assert_eq!(account_committed_info_by_id, account_committed_info_by_ens);
+
+        let response = client
+            .account_info(&account_id.to_string(), "committed")
+            .await?;
+        assert!(response.request.resolved_address.is_none());
+
         let response = client
             .account_info(&format!("{:?}", address), "finalized")
             .await?;
         let account_finalized_info: Option<Account> = deserialize_response_result(response)?;
 
+        // Exit data isn't provided until the contract enters the exodus mode.
+        let mut eth = server.eth.clone();
+        eth.add_main_contract_response("exodusMode", vec![Token::Bool(false)])
+            .await;
+        let response = client
+            .account_exit_proof(&format!("{:?}", address), "ETH")
+            .await?;
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::ExodusModeNotActive);
+
+        eth.add_main_contract_response("exodusMode", vec![Token::Bool(true)])
+            .await;
+        let response = client
+            .account_exit_proof(&format!("{:?}", address), "ETH")
+            .await?;
+        let exit_proof: Option<AccountExitProof> = deserialize_response_result(response)?;
+        assert_eq!(exit_proof.is_some(), account_finalized_info.is_some());
+        if let Some(exit_proof) = exit_proof {
+            assert_eq!(exit_proof.owner, address);
+            assert_eq!(exit_proof.account_id, account_id);
+            assert_eq!(exit_proof.account_path.len(), account_tree_depth());
+        }
+
+        let response = client
+            .account_state_for_block(&format!("{:?}", address), "lastCommitted")
+            .await?;
+        let account_state: Option<Account> = deserialize_response_result(response)?;
+        let account_state =
+            account_state.expect("Account should exist in the last committed block");
+        assert_eq!(account_state.account_id, account_id);
+        assert_eq!(account_state.nonce, account_committed_info_by_id.nonce);
+        assert_eq!(
+            account_state.balances,
+            account_committed_info_by_id.balances
+        );
+        assert_eq!(
+            account_state.pub_key_hash,
+            account_committed_info_by_id.pub_key_hash
+        );
+
+        // There are no account updates in the genesis block.
+        let response = client
+            .account_state_for_block(&account_id.to_string(), "0")
+            .await?;
+        let account_state: Option<Account> = deserialize_response_result(response)?;
+        assert!(account_state.is_none());
+
+        let response = client
+            .account_state_for_block(&account_id.to_string(), "lastSeen")
+            .await?;
+        assert!(response.error.is_some());
+
         {
             let mut storage = server.pool.access_storage().await?;
             storage
@@ -677,11 +1939,129 @@ mod tests {
         let account_full_info: AccountState = deserialize_response_result(response)?;
         assert_eq!(
             account_full_info.committed,
-            Some(account_committed_info_by_id)
+            Some(account_committed_info_by_id.clone())
         );
         assert_eq!(account_full_info.finalized, account_finalized_info);
         assert_eq!(account_full_info.depositing, expected_depositing);
 
+        let response = client
+            .accounts_by_pubkey_hash(&account_committed_info_by_id.pub_key_hash.as_hex())
+            .await?;
+        let accounts: Vec<Account> = deserialize_response_result(response)?;
+        for account in &accounts {
+            assert_eq!(
+                account.pub_key_hash,
+                account_committed_info_by_id.pub_key_hash
+            );
+        }
+        // Accounts without the signing key set have no public key updates.
+        if account_committed_info_by_id.pub_key_hash != PubKeyHash::default() {
+            assert!(accounts
+                .iter()
+                .any(|account| account.account_id == account_id));
+        }
+
+        let response = client.accounts_by_pubkey_hash("0xdead").await?;
+        assert!(response.error.is_some());
+
+        let unknown_address = format!("{:?}", Address::repeat_byte(0xde));
+        let response = client
+            .accounts_batch(vec![
+                account_id.to_string(),
+                format!("{:?}", address),
+                unknown_address.clone(),
+            ])
+            .await?;
+        let accounts: AccountsBatchResponse = deserialize_response_result(response)?;
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(
+            accounts[&account_id.to_string()],
+            Some(account_committed_info_by_id.clone())
+        );
+        assert_eq!(
+            accounts[&format!("{:?}", address)],
+            Some(account_committed_info_by_id.clone())
+        );
+        assert_eq!(accounts[&unknown_address], None);
+
+        let response = client
+            .accounts_batch(vec![account_id.to_string(); MAX_ACCOUNTS_BATCH_SIZE + 1])
+            .await?;
+        assert!(response.error.is_some());
+
+        let response = client
+            .account_pubkey_history(&account_id.to_string())
+            .await?;
+        let pubkey_history: Vec<PubKeyChange> = deserialize_response_result(response)?;
+        if let Some(last_change) = pubkey_history
+            .iter()
+            .rev()
+            .find(|change| change.status != TxInBlockStatus::Rejected)
+        {
+            assert_eq!(
+                last_change.new_pub_key_hash,
+                account_committed_info_by_id.pub_key_hash
+            );
+        }
+
+        let response = client.account_nonce(&account_id.to_string()).await?;
+        let account_nonce: Option<AccountNonce> = deserialize_response_result(response)?;
+        let account_nonce = account_nonce.expect("Account should exist");
+        assert_eq!(account_nonce.committed, account_committed_info_by_id.nonce);
+        assert_eq!(
+            account_nonce.finalized,
+            account_finalized_info
+                .as_ref()
+                .map(|account| account.nonce)
+                .unwrap_or_default()
+        );
+        assert!(account_nonce.next >= account_nonce.committed);
+
+        let response = client.account_nfts(&format!("{:?}", address)).await?;
+        let account_nfts: Option<AccountNfts> = deserialize_response_result(response)?;
+        let account_nfts = account_nfts.expect("Account should exist");
+        assert_eq!(account_nfts.committed, account_committed_info_by_id.nfts);
+        assert_eq!(
+            account_nfts.finalized,
+            account_finalized_info
+                .map(|account| account.nfts)
+                .unwrap_or_default()
+        );
+
+        let response = client
+            .account_nfts(&format!("{:?}", Address::repeat_byte(0xde)))
+            .await?;
+        let account_nfts: Option<AccountNfts> = deserialize_response_result(response)?;
+        assert!(account_nfts.is_none());
+
+        let query = BalanceHistoryQuery {
+            token: "ETH".to_string(),
+            from: None,
+            to: None,
+            resolution: Some(BalanceHistoryResolution::Hour),
+        };
+        let response = client
+            .account_balance_history(&account_id.to_string(), &query)
+            .await?;
+        let history: Option<Vec<BalancePoint>> = deserialize_response_result(response)?;
+        let history = history.expect("Account should exist");
+        assert!(history
+            .windows(2)
+            .all(|points| points[0].timestamp < points[1].timestamp));
+
+        let response = client
+            .account_balance_history(&format!("{:?}", Address::repeat_byte(0xde)), &query)
+            .await?;
+        let history: Option<Vec<BalancePoint>> = deserialize_response_result(response)?;
+        assert!(history.is_none());
+
+        let response = client
+            .account_pending_balance(&account_id.to_string())
+            .await?;
+        let balances: Option<AccountPendingBalances> = deserialize_response_result(response)?;
+        let balances = balances.expect("Account should exist");
+        assert_eq!(balances.committed, account_committed_info_by_id.balances);
+
         let query = PaginationQuery {
             from: ApiEither::from(tx_hash),
             limit: 1,
@@ -691,6 +2071,38 @@ mod tests {
         let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
         assert_eq!(txs.list[0].tx_hash, tx_hash);
 
+        let filters = AccountTxsFilters {
+            to_time: Some(Utc::now() + Duration::days(1)),
+            ..Default::default()
+        };
+        let response = client
+            .account_txs_with_filters(&query, &account_id.to_string(), &filters)
+            .await?;
+        let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
+        assert_eq!(txs.list[0].tx_hash, tx_hash);
+
+        let filters = AccountTxsFilters {
+            from_time: Some(Utc::now() + Duration::days(1)),
+            ..Default::default()
+        };
+        let response = client
+            .account_txs_with_filters(&query, &account_id.to_string(), &filters)
+            .await?;
+        let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
+        assert!(txs.list.is_empty());
+        assert_eq!(txs.pagination.count, 0);
+
+        // Every transaction of the account has the account itself as a counterparty.
+        let response = client
+            .account_txs_with_counterparty(
+                &query,
+                &account_id.to_string(),
+                &format!("{:?}", address),
+            )
+            .await?;
+        let txs: Paginated<Transaction, TxHash> = deserialize_response_result(response)?;
+        assert_eq!(txs.list[0].tx_hash, tx_hash);
+
         let query = PaginationQuery {
             from: ApiEither::from_str("latest").unwrap(),
             limit: 2,
@@ -711,6 +2123,66 @@ mod tests {
             _ => panic!("account_pending_txs returned L2 tx"),
         }
 
+        let response = client
+            .account_pending_priority_ops(&format!("{:?}", address))
+            .await?;
+        let ops: Vec<PendingPriorityOp> = deserialize_response_result(response)?;
+        assert_eq!(ops.len(), 3);
+        for op in &ops {
+            assert_eq!(
+                op.expected_accept_block,
+                25 + server.confirmations_for_eth_event
+            );
+            match &op.op {
+                L1Transaction::Deposit(deposit) => {
+                    assert_eq!(deposit.amount, BigUint::from(100500u64));
+                    assert_eq!(deposit.to, address);
+                }
+                _ => panic!("should return deposit"),
+            }
+        }
+
+        let response = client
+            .account_registration(&format!("{:?}", address))
+            .await?;
+        let registration: AccountRegistration = deserialize_response_result(response)?;
+        assert_eq!(registration.account_id, Some(account_id));
+        assert!(registration.created_in_block.is_some());
+        assert_eq!(
+            registration.signing_key_set,
+            account_committed_info_by_id.pub_key_hash != PubKeyHash::default()
+        );
+
+        let response = client
+            .account_registration(&format!("{:?}", Address::repeat_byte(0xde)))
+            .await?;
+        let registration: AccountRegistration = deserialize_response_result(response)?;
+        assert_eq!(registration, AccountRegistration::default());
+
+        let response = client.account_fees(&format!("{:?}", address)).await?;
+        let fees: AccountFees = deserialize_response_result(response)?;
+        for fees_by_token in fees.by_tx_type.values() {
+            for token in fees_by_token.keys() {
+                assert!(fees.total.contains_key(token));
+            }
+        }
+
+        // Mock Ethereum client has no contracts deployed.
+        let response = client
+            .account_is_contract_wallet(&format!("{:?}", address))
+            .await?;
+        let is_contract: bool = deserialize_response_result(response)?;
+        assert!(!is_contract);
+
+        let response = client.account_mempool(&account_id.to_string()).await?;
+        let mempool: Option<AccountMempool> = deserialize_response_result(response)?;
+        let mempool = mempool.unwrap();
+        assert_eq!(mempool.committed_nonce, account_committed_info_by_id.nonce);
+        assert!(mempool
+            .txs
+            .windows(2)
+            .all(|pair| pair[0].nonce <= pair[1].nonce));
+
         server.stop().await;
         Ok(())
     }
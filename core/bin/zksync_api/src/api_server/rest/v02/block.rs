@@ -1,36 +1,76 @@
 //! Block part of API implementation.
 
 // Built-in uses
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 use std::time::Instant;
 
 // External uses
-use actix_web::{web, Scope};
+use actix_web::{web, Either, HttpResponse, Scope};
+use futures::stream::{self, Stream};
 
 // Workspace uses
 use zksync_api_types::v02::{
-    block::{BlockInfo, BlockStatus},
-    pagination::{parse_query, ApiEither, BlockAndTxHash, Paginated, PaginationQuery},
+    block::{
+        AccountStateDiff, AggregatedProofInfo, BalanceChange, BlockInfo, BlockL1Transaction,
+        BlockProofInfo, BlockStateDiff, BlockStatus, BlocksExportQuery, BlocksStats, ExportedBlock,
+        FinalizedBlocks, FinalizedBlocksQuery, PendingBlockInfo, PendingBlockTx, PubKeyHashChange,
+        MAX_BLOCKS_EXPORT_RANGE, MAX_FINALIZED_BLOCKS,
+    },
+    pagination::{
+        parse_query, ApiEither, BlockAndTxHash, Latest, Paginated, PaginationDirection,
+        PaginationQuery,
+    },
     transaction::{Transaction, TxData, TxHashSerializeWrapper},
 };
+use zksync_config::ZkSyncConfig;
 use zksync_crypto::{convert::FeConvert, Fr};
-use zksync_storage::{chain::block::records::StorageBlockDetails, ConnectionPool, QueryResult};
-use zksync_types::{tx::TxHash, BlockNumber, H256};
+use zksync_storage::{
+    chain::{block::records::StorageBlockDetails, operations::records::StoredAggregatedEthTx},
+    ConnectionPool, StorageProcessor,
+};
+use zksync_token_db_cache::TokenDBCache;
+use zksync_types::{
+    aggregated_operations::AggregatedActionType,
+    block::{ExecutedOperations, PendingBlock},
+    tx::TxHash,
+    AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, H256,
+};
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
+    hash::parse_block_hash,
     paginate_trait::Paginate,
     response::ApiResult,
 };
-use crate::{api_try, utils::block_details_cache::BlockDetailsCache};
+use crate::{api_try, fee_ticker::PriceError, utils::block_details_cache::BlockDetailsCache};
 
-pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
+/// Creates the block info from its details, taking the Ethereum transactions
+/// covering the block from the provided ones.
+pub fn block_info_from_details(
+    details: StorageBlockDetails,
+    eth_txs: &[StoredAggregatedEthTx],
+) -> BlockInfo {
     let status = if details.is_verified() {
         BlockStatus::Finalized
     } else {
         BlockStatus::Committed
     };
+    let l1_transactions = eth_txs
+        .iter()
+        .filter(|tx| tx.from_block <= details.block_number && details.block_number <= tx.to_block)
+        .map(|tx| BlockL1Transaction {
+            action_type: AggregatedActionType::from_str(&tx.action_type).unwrap_or_else(|err| {
+                panic!(
+                    "Database provided an incorrect action_type field: {:?}, an error occurred {}",
+                    tx.action_type, err
+                )
+            }),
+            tx_hash: H256::from_slice(&tx.tx_hash),
+            gas_used: tx.gas_used.map(|gas_used| gas_used as u64),
+        })
+        .collect();
     BlockInfo {
         block_number: BlockNumber(details.block_number as u32),
         new_state_root: Fr::from_bytes(&details.new_state_root).unwrap_or_else(|err| {
@@ -40,26 +80,303 @@ pub fn block_info_from_details(details: StorageBlockDetails) -> BlockInfo {
             )
         }),
         block_size: details.block_size as u64,
+        tx_count: details.tx_count as u64,
         commit_tx_hash: details.commit_tx_hash.map(|bytes| H256::from_slice(&bytes)),
         verify_tx_hash: details.verify_tx_hash.map(|bytes| H256::from_slice(&bytes)),
         committed_at: details.committed_at,
         finalized_at: details.verified_at,
         status,
+        l1_transactions,
+    }
+}
+
+/// Creates the pending block info, `max_chunks` is the size of the largest block
+/// the state keeper may create.
+pub fn pending_block_info(block: PendingBlock, max_chunks: usize) -> PendingBlockInfo {
+    let transactions = block
+        .success_operations
+        .into_iter()
+        .chain(
+            block
+                .failed_txs
+                .into_iter()
+                .map(|tx| ExecutedOperations::Tx(Box::new(tx))),
+        )
+        .map(|op| {
+            let tx_type = op.variance_name();
+            let block_index = op.block_index();
+            match op {
+                ExecutedOperations::Tx(tx) => PendingBlockTx {
+                    tx_hash: tx.signed_tx.tx.hash(),
+                    tx_type,
+                    block_index,
+                    success: tx.success,
+                    fail_reason: tx.fail_reason,
+                },
+                ExecutedOperations::PriorityOp(op) => PendingBlockTx {
+                    tx_hash: op.priority_op.tx_hash(),
+                    tx_type,
+                    block_index,
+                    success: true,
+                    fail_reason: None,
+                },
+            }
+        })
+        .collect();
+    PendingBlockInfo {
+        block_number: block.number,
+        chunks_used: max_chunks.saturating_sub(block.chunks_left) as u64,
+        max_chunks: max_chunks as u64,
+        timestamp: block.timestamp,
+        transactions,
     }
 }
 
+/// Folds the account updates made by the block into the per account changes,
+/// `addresses` are used to fill the addresses of the touched accounts.
+pub fn block_state_diff(
+    block_number: BlockNumber,
+    updates: AccountUpdates,
+    addresses: &HashMap<AccountId, Address>,
+) -> BlockStateDiff {
+    let mut accounts: BTreeMap<AccountId, AccountStateDiff> = BTreeMap::new();
+    for (account_id, update) in updates {
+        let nonces = match &update {
+            AccountUpdate::Create { nonce, .. }
+            | AccountUpdate::Delete { nonce, .. }
+            | AccountUpdate::MintNFT { nonce, .. }
+            | AccountUpdate::RemoveNFT { nonce, .. } => (*nonce, *nonce),
+            AccountUpdate::UpdateBalance {
+                old_nonce,
+                new_nonce,
+                ..
+            }
+            | AccountUpdate::ChangePubKeyHash {
+                old_nonce,
+                new_nonce,
+                ..
+            } => (*old_nonce, *new_nonce),
+        };
+        let diff = accounts
+            .entry(account_id)
+            .or_insert_with(|| AccountStateDiff {
+                account_id,
+                address: addresses.get(&account_id).copied(),
+                created: false,
+                old_nonce: nonces.0,
+                new_nonce: nonces.0,
+                pub_key_hash: None,
+                balances: BTreeMap::new(),
+                minted_nfts: Vec::new(),
+            });
+        diff.new_nonce = nonces.1;
+
+        match update {
+            AccountUpdate::Create { address, .. } => {
+                diff.created = true;
+                diff.address = Some(address);
+            }
+            AccountUpdate::UpdateBalance {
+                balance_update: (token_id, old_balance, new_balance),
+                ..
+            } => {
+                diff.balances
+                    .entry(token_id)
+                    .or_insert_with(|| BalanceChange {
+                        old: old_balance.into(),
+                        new: Default::default(),
+                    })
+                    .new = new_balance.into();
+            }
+            AccountUpdate::ChangePubKeyHash {
+                old_pub_key_hash,
+                new_pub_key_hash,
+                ..
+            } => {
+                diff.pub_key_hash
+                    .get_or_insert(PubKeyHashChange {
+                        old: old_pub_key_hash,
+                        new: Default::default(),
+                    })
+                    .new = new_pub_key_hash;
+            }
+            AccountUpdate::MintNFT { token, .. } => diff.minted_nfts.push(token.id),
+            AccountUpdate::Delete { .. } | AccountUpdate::RemoveNFT { .. } => {}
+        }
+    }
+    BlockStateDiff {
+        block_number,
+        accounts: accounts.into_values().collect(),
+    }
+}
+
+/// Loads the confirmed Ethereum transactions covering the blocks in the given range.
+pub(super) async fn block_eth_txs(
+    storage: &mut StorageProcessor<'_>,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<StoredAggregatedEthTx>, Error> {
+    storage
+        .chain()
+        .operations_schema()
+        .get_aggregated_eth_txs_for_blocks(from_block, to_block)
+        .await
+        .map_err(Error::storage)
+}
+
+/// Collects the statistics of the blocks in the given range, keying the fees
+/// by the token symbols.
+pub(super) async fn blocks_stats(
+    storage: &mut StorageProcessor<'_>,
+    tokens: &TokenDBCache,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<BlocksStats, Error> {
+    let stats = storage
+        .chain()
+        .block_schema()
+        .get_blocks_stats(from_block, to_block)
+        .await
+        .map_err(Error::storage)?;
+
+    let mut fees = BTreeMap::new();
+    for (token_id, fee) in stats.fees {
+        let token_symbol = tokens
+            .token_symbol(storage, token_id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(PriceError::token_not_found(token_id)))?;
+        fees.insert(token_symbol, fee.into());
+    }
+    Ok(BlocksStats {
+        from_block,
+        to_block,
+        tx_counts: stats.tx_counts,
+        fees,
+        active_accounts: stats.active_accounts,
+    })
+}
+
+/// Number of blocks loaded from the database at once during the export.
+const EXPORT_CHUNK_SIZE: u32 = 100;
+
+/// Loads all the transactions of the block in the order of their execution.
+async fn all_block_transactions(
+    storage: &mut StorageProcessor<'_>,
+    block_number: BlockNumber,
+    tx_count: u32,
+) -> Result<Vec<Transaction>, Error> {
+    if tx_count == 0 {
+        return Ok(Vec::new());
+    }
+    let query = PaginationQuery {
+        from: BlockAndTxHash {
+            block_number,
+            tx_hash: ApiEither {
+                inner: zksync_api_types::Either::Right(Latest),
+            },
+        },
+        limit: tx_count,
+        direction: PaginationDirection::Older,
+    };
+    let mut txs = storage
+        .chain()
+        .block_schema()
+        .get_block_transactions_page(&query)
+        .await
+        .map_err(Error::storage)?
+        .unwrap_or_default();
+    txs.reverse();
+    Ok(txs)
+}
+
+/// Exports at most `limit` committed blocks starting from `from_block` as newline-delimited JSON.
+/// Returns the serialized blocks and the number of the last exported one.
+async fn export_blocks_chunk(
+    storage: &mut StorageProcessor<'_>,
+    from_block: BlockNumber,
+    limit: u32,
+) -> Result<Option<(web::Bytes, BlockNumber)>, Error> {
+    let blocks = storage
+        .chain()
+        .block_schema()
+        .load_block_range_asc(from_block, limit)
+        .await
+        .map_err(Error::storage)?;
+    let last_block = match blocks.last() {
+        Some(block) => BlockNumber(block.block_number as u32),
+        None => return Ok(None),
+    };
+    let eth_txs = block_eth_txs(storage, from_block, last_block).await?;
+
+    let mut lines = Vec::new();
+    for details in blocks {
+        let block = block_info_from_details(details, &eth_txs);
+        let transactions =
+            all_block_transactions(storage, block.block_number, block.tx_count as u32).await?;
+        let exported = ExportedBlock {
+            block,
+            transactions,
+        };
+        serde_json::to_writer(&mut lines, &exported).expect("Should be correct serializable");
+        lines.push(b'\n');
+    }
+    Ok(Some((web::Bytes::from(lines), last_block)))
+}
+
+/// Streams the committed blocks in the given range, loading them from the database chunk by chunk.
+fn export_blocks_stream(
+    pool: ConnectionPool,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> impl Stream<Item = Result<web::Bytes, anyhow::Error>> {
+    stream::try_unfold(Some(from_block), move |next_block| {
+        let pool = pool.clone();
+        async move {
+            let next_block = match next_block {
+                Some(next_block) if next_block <= to_block => next_block,
+                _ => return Ok(None),
+            };
+            let limit = std::cmp::min(EXPORT_CHUNK_SIZE, *to_block - *next_block + 1);
+            let mut storage = pool.access_storage().await?;
+            let chunk = export_blocks_chunk(&mut storage, next_block, limit)
+                .await
+                .map_err(|err| anyhow::anyhow!(err.message))?;
+            Ok(chunk.map(|(lines, last_block)| (lines, Some(last_block + 1))))
+        }
+    })
+}
+
 /// Shared data between `api/v0.2/blocks` endpoints.
 #[derive(Debug, Clone)]
 struct ApiBlockData {
     pool: ConnectionPool,
     verified_blocks_cache: BlockDetailsCache,
+    tokens: TokenDBCache,
+    /// Size of the largest block the state keeper may create.
+    max_block_chunks: usize,
 }
 
 impl ApiBlockData {
-    fn new(pool: ConnectionPool, verified_blocks_cache: BlockDetailsCache) -> Self {
+    fn new(
+        config: &ZkSyncConfig,
+        pool: ConnectionPool,
+        verified_blocks_cache: BlockDetailsCache,
+        tokens: TokenDBCache,
+    ) -> Self {
+        let max_block_chunks = config
+            .chain
+            .state_keeper
+            .block_chunk_sizes
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or_default();
         Self {
             pool,
             verified_blocks_cache,
+            tokens,
+            max_block_chunks,
         }
     }
 
@@ -73,7 +390,9 @@ impl ApiBlockData {
             .await
             .map_err(Error::storage)?;
         if let Some(details) = details {
-            Ok(Some(block_info_from_details(details)))
+            let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+            let eth_txs = block_eth_txs(&mut storage, block_number, block_number).await?;
+            Ok(Some(block_info_from_details(details, &eth_txs)))
         } else {
             Ok(None)
         }
@@ -83,21 +402,28 @@ impl ApiBlockData {
         &self,
         block_position: &str,
     ) -> Result<BlockNumber, Error> {
-        if let Ok(number) = u32::from_str(block_position) {
-            Ok(BlockNumber(number))
-        } else {
-            match block_position {
-                "lastCommitted" => self
-                    .get_last_committed_block_number()
-                    .await
-                    .map_err(Error::storage),
-                "lastFinalized" => self
-                    .get_last_finalized_block_number()
-                    .await
-                    .map_err(Error::storage),
-                _ => Err(Error::from(InvalidDataError::InvalidBlockPosition)),
-            }
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        block_number_by_position(&mut storage, block_position).await
+    }
+
+    /// Finds the block by its new state root or its commitment.
+    async fn block_number_by_root(&self, hash: H256) -> Result<Option<BlockNumber>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let by_root = storage
+            .chain()
+            .block_schema()
+            .get_block_number_by_hash(hash.as_bytes())
+            .await
+            .map_err(Error::storage)?;
+        if by_root.is_some() {
+            return Ok(by_root);
         }
+        storage
+            .chain()
+            .block_schema()
+            .get_block_number_by_commitment(hash.as_bytes())
+            .await
+            .map_err(Error::storage)
     }
 
     async fn block_page(
@@ -127,6 +453,151 @@ impl ApiBlockData {
         storage.paginate_checked(&new_query).await
     }
 
+    async fn block_state_diff(&self, block_number: BlockNumber) -> Result<BlockStateDiff, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let updates = storage
+            .chain()
+            .state_schema()
+            .load_state_diff_for_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        let account_ids: Vec<AccountId> = updates.iter().map(|(id, _)| *id).collect();
+        let addresses = storage
+            .chain()
+            .account_schema()
+            .account_addresses_by_ids(&account_ids)
+            .await
+            .map_err(Error::storage)?;
+        Ok(block_state_diff(block_number, updates, &addresses))
+    }
+
+    async fn finalized_blocks(&self, since_block: BlockNumber) -> Result<FinalizedBlocks, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let last_finalized = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await
+            .map_err(Error::storage)?;
+        if since_block >= last_finalized {
+            return Ok(FinalizedBlocks {
+                blocks: Vec::new(),
+                last_finalized,
+            });
+        }
+
+        let from_block = since_block + 1;
+        let limit = std::cmp::min(MAX_FINALIZED_BLOCKS, *last_finalized - *since_block);
+        let details = storage
+            .chain()
+            .block_schema()
+            .load_block_range_asc(from_block, limit)
+            .await
+            .map_err(Error::storage)?;
+        let to_block = from_block + (limit - 1);
+        let eth_txs = block_eth_txs(&mut storage, from_block, to_block).await?;
+        let blocks = details
+            .into_iter()
+            .map(|details| block_info_from_details(details, &eth_txs))
+            .filter(|block| block.status == BlockStatus::Finalized)
+            .collect();
+        Ok(FinalizedBlocks {
+            blocks,
+            last_finalized,
+        })
+    }
+
+    async fn pending_block(&self) -> Result<Option<PendingBlockInfo>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let pending_block = storage
+            .chain()
+            .block_schema()
+            .load_pending_block()
+            .await
+            .map_err(Error::storage)?;
+        Ok(pending_block.map(|block| pending_block_info(block, self.max_block_chunks)))
+    }
+
+    async fn block_stats(&self, block_position: &str) -> Result<BlocksStats, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let block_number = block_number_by_position(&mut storage, block_position).await?;
+        blocks_stats(&mut storage, &self.tokens, block_number, block_number).await
+    }
+
+    async fn block_proof(
+        &self,
+        block_number: BlockNumber,
+    ) -> Result<Option<BlockProofInfo>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        let block = match block {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let commitment = H256::from_slice(&block.commitment);
+        let mut public_input = commitment.to_fixed_bytes();
+        public_input[0] &= 0xffu8 >> 3;
+        let public_input = Fr::from_bytes(&public_input).unwrap_or_else(|err| {
+            panic!(
+                "Database provided an incorrect commitment field: {:?}, an error occurred {}",
+                block.commitment, err
+            )
+        });
+
+        let aggregated_proof = storage
+            .prover_schema()
+            .load_aggregated_proof_for_block(block_number)
+            .await
+            .map_err(Error::storage)?;
+        let aggregated_proof = match aggregated_proof {
+            Some((first_block, last_block, proof)) => {
+                let prove_action = AggregatedActionType::PublishProofBlocksOnchain.to_string();
+                let prove_tx_hash = block_eth_txs(&mut storage, block_number, block_number)
+                    .await?
+                    .into_iter()
+                    .find(|tx| tx.action_type == prove_action)
+                    .map(|tx| H256::from_slice(&tx.tx_hash));
+                Some(AggregatedProofInfo {
+                    first_block,
+                    last_block,
+                    proof: proof.serialize_aggregated_proof(),
+                    prove_tx_hash,
+                })
+            }
+            None => None,
+        };
+
+        Ok(Some(BlockProofInfo {
+            block_number,
+            commitment,
+            public_input,
+            aggregated_proof,
+        }))
+    }
+
+    /// Checks the requested range and returns the stream of the exported blocks.
+    async fn export_blocks(
+        &self,
+        query: BlocksExportQuery,
+    ) -> Result<impl Stream<Item = Result<web::Bytes, anyhow::Error>>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let from_block = block_number_by_position(&mut storage, &query.from).await?;
+        let to_block = block_number_by_position(&mut storage, &query.to).await?;
+        if from_block > to_block || *to_block - *from_block >= MAX_BLOCKS_EXPORT_RANGE {
+            return Err(Error::from(InvalidDataError::InvalidBlocksExportRange));
+        }
+        Ok(export_blocks_stream(
+            self.pool.clone(),
+            from_block,
+            to_block,
+        ))
+    }
+
     async fn tx_data(
         &self,
         block_number: BlockNumber,
@@ -140,23 +611,32 @@ impl ApiBlockData {
             .await
             .map_err(Error::storage)?)
     }
+}
 
-    async fn get_last_committed_block_number(&self) -> QueryResult<BlockNumber> {
-        let mut storage = self.pool.access_storage().await?;
-        storage
-            .chain()
-            .block_schema()
-            .get_last_committed_confirmed_block()
-            .await
-    }
-
-    async fn get_last_finalized_block_number(&self) -> QueryResult<BlockNumber> {
-        let mut storage = self.pool.access_storage().await?;
-        storage
-            .chain()
-            .block_schema()
-            .get_last_verified_confirmed_block()
-            .await
+/// Resolves the block position, which is either a block number, `lastCommitted`
+/// or `lastFinalized`, into the block number.
+pub(super) async fn block_number_by_position(
+    storage: &mut StorageProcessor<'_>,
+    block_position: &str,
+) -> Result<BlockNumber, Error> {
+    if let Ok(number) = u32::from_str(block_position) {
+        Ok(BlockNumber(number))
+    } else {
+        match block_position {
+            "lastCommitted" => storage
+                .chain()
+                .block_schema()
+                .get_last_committed_confirmed_block()
+                .await
+                .map_err(Error::storage),
+            "lastFinalized" => storage
+                .chain()
+                .block_schema()
+                .get_last_verified_confirmed_block()
+                .await
+                .map_err(Error::storage),
+            _ => Err(Error::from(InvalidDataError::InvalidBlockPosition)),
+        }
     }
 }
 
@@ -186,6 +666,54 @@ async fn block_by_position(
     res
 }
 
+async fn pending_block(data: web::Data<ApiBlockData>) -> ApiResult<Option<PendingBlockInfo>> {
+    let start = Instant::now();
+    let res = data.pending_block().await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "pending_block");
+    res
+}
+
+async fn finalized_blocks(
+    data: web::Data<ApiBlockData>,
+    web::Query(query): web::Query<FinalizedBlocksQuery>,
+) -> ApiResult<FinalizedBlocks> {
+    let start = Instant::now();
+    let res = data.finalized_blocks(query.since_block).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "finalized_blocks");
+    res
+}
+
+async fn export_blocks(
+    data: web::Data<ApiBlockData>,
+    web::Query(query): web::Query<BlocksExportQuery>,
+) -> Either<HttpResponse, ApiResult<()>> {
+    let start = Instant::now();
+    let res = match data.export_blocks(query).await {
+        Ok(blocks) => Either::Left(
+            HttpResponse::Ok()
+                .content_type("application/x-ndjson")
+                .streaming(Box::pin(blocks)),
+        ),
+        Err(err) => Either::Right(ApiResult::from(err)),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "export_blocks");
+    res
+}
+
+async fn block_by_root(
+    data: web::Data<ApiBlockData>,
+    hash: web::Path<String>,
+) -> ApiResult<Option<BlockInfo>> {
+    let start = Instant::now();
+    let hash = api_try!(parse_block_hash(&hash));
+    let res = match api_try!(data.block_number_by_root(hash).await) {
+        Some(block_number) => data.block_info(block_number).await.into(),
+        None => ApiResult::Ok(None),
+    };
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_by_root");
+    res
+}
+
 async fn block_transactions(
     data: web::Data<ApiBlockData>,
     block_position: web::Path<String>,
@@ -199,24 +727,72 @@ async fn block_transactions(
     res
 }
 
+async fn block_stats(
+    data: web::Data<ApiBlockData>,
+    block_position: web::Path<String>,
+) -> ApiResult<BlocksStats> {
+    let start = Instant::now();
+    let res = data.block_stats(&block_position).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_stats");
+    res
+}
+
+async fn block_proof(
+    data: web::Data<ApiBlockData>,
+    block_position: web::Path<String>,
+) -> ApiResult<Option<BlockProofInfo>> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_proof(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_proof");
+    res
+}
+
+async fn block_state_diff(
+    data: web::Data<ApiBlockData>,
+    block_position: web::Path<String>,
+) -> ApiResult<BlockStateDiff> {
+    let start = Instant::now();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
+    let res = data.block_state_diff(block_number).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "block_state_diff");
+    res
+}
+
 async fn transaction_in_block(
     data: web::Data<ApiBlockData>,
-    path: web::Path<(BlockNumber, u64)>,
+    path: web::Path<(String, u64)>,
 ) -> ApiResult<Option<TxData>> {
     let start = Instant::now();
-    let (block_number, block_index) = *path;
+    let (block_position, block_index) = path.into_inner();
+    let block_number = api_try!(data.get_block_number_by_position(&block_position).await);
     let res = api_try!(data.tx_data(block_number, block_index).await);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "transaction_in_block");
     ApiResult::Ok(res)
 }
 
-pub fn api_scope(pool: ConnectionPool, cache: BlockDetailsCache) -> Scope {
-    let data = ApiBlockData::new(pool, cache);
+pub fn api_scope(
+    config: &ZkSyncConfig,
+    pool: ConnectionPool,
+    cache: BlockDetailsCache,
+    tokens: TokenDBCache,
+) -> Scope {
+    let data = ApiBlockData::new(config, pool, cache, tokens);
 
     web::scope("blocks")
         .app_data(web::Data::new(data))
         .route("", web::get().to(block_pagination))
+        .route("pending", web::get().to(pending_block))
+        .route("export", web::get().to(export_blocks))
+        .route("finalized", web::get().to(finalized_blocks))
         .route("{block_position}", web::get().to(block_by_position))
+        .route("by_root/{hash}", web::get().to(block_by_root))
+        .route("{block_position}/stats", web::get().to(block_stats))
+        .route("{block_position}/proof", web::get().to(block_proof))
+        .route(
+            "{block_position}/state_diff",
+            web::get().to(block_state_diff),
+        )
         .route(
             "{block_position}/transactions",
             web::get().to(block_transactions),
@@ -237,6 +813,50 @@ mod tests {
     use zksync_api_types::v02::{
         pagination::PaginationDirection, transaction::TransactionData, ApiVersion,
     };
+    use zksync_types::{Nonce, TokenId};
+
+    #[test]
+    fn state_diff_folds_account_updates() {
+        let account_id = AccountId(1);
+        let address = Address::repeat_byte(0x11);
+        let token_id = TokenId(0);
+        let updates = vec![
+            (
+                account_id,
+                AccountUpdate::Create {
+                    address,
+                    nonce: Nonce(0),
+                },
+            ),
+            (
+                account_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: Nonce(0),
+                    new_nonce: Nonce(0),
+                    balance_update: (token_id, 0u32.into(), 100u32.into()),
+                },
+            ),
+            (
+                account_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: Nonce(0),
+                    new_nonce: Nonce(1),
+                    balance_update: (token_id, 100u32.into(), 90u32.into()),
+                },
+            ),
+        ];
+
+        let diff = block_state_diff(BlockNumber(1), updates, &HashMap::new());
+        assert_eq!(diff.accounts.len(), 1);
+        let account = &diff.accounts[0];
+        assert!(account.created);
+        assert_eq!(account.address, Some(address));
+        assert_eq!((account.old_nonce, account.new_nonce), (Nonce(0), Nonce(1)));
+        assert!(account.pub_key_hash.is_none());
+        let balance = &account.balances[&token_id];
+        assert_eq!(balance.old.0.to_string(), "0");
+        assert_eq!(balance.new.0.to_string(), "90");
+    }
 
     #[actix_rt::test]
     #[cfg_attr(
@@ -252,7 +872,14 @@ mod tests {
             api_version: ApiVersion::V02,
         };
         let (client, server) = cfg.start_server(
-            |cfg: &TestServerConfig| api_scope(cfg.pool.clone(), BlockDetailsCache::new(10)),
+            |cfg: &TestServerConfig| {
+                api_scope(
+                    &cfg.config,
+                    cfg.pool.clone(),
+                    BlockDetailsCache::new(10),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                )
+            },
             Some(shared_data),
         );
 
@@ -272,6 +899,51 @@ mod tests {
         let response = client.block_by_position("2").await?;
         let block: BlockInfo = deserialize_response_result(response)?;
         assert_eq!(block, expected_blocks.list[1]);
+        let commit_tx = block
+            .l1_transactions
+            .iter()
+            .find(|tx| tx.action_type == AggregatedActionType::CommitBlocks)
+            .expect("Committed block should have the commit transaction");
+        assert_eq!(Some(commit_tx.tx_hash), block.commit_tx_hash);
+
+        let expected_pending = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let max_chunks = cfg
+                .config
+                .chain
+                .state_keeper
+                .block_chunk_sizes
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or_default();
+            storage
+                .chain()
+                .block_schema()
+                .load_pending_block()
+                .await?
+                .map(|block| pending_block_info(block, max_chunks))
+        };
+        let response = client.pending_block().await?;
+        let pending: Option<PendingBlockInfo> = deserialize_response_result(response)?;
+        assert_eq!(pending, expected_pending);
+
+        let root_hash = format!("0x{}", hex::encode(block.new_state_root.to_bytes()));
+        let response = client.block_by_root(&root_hash).await?;
+        let by_root: Option<BlockInfo> = deserialize_response_result(response)?;
+        assert_eq!(by_root.unwrap().block_number, block.block_number);
+
+        let last_committed = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_last_committed_confirmed_block()
+                .await?
+        };
+        let response = client.block_by_position("lastCommitted").await?;
+        let block: Option<BlockInfo> = deserialize_response_result(response)?;
+        assert_eq!(block.unwrap().block_number, last_committed);
 
         let response = client.block_pagination(&query).await?;
         let paginated: Paginated<BlockInfo, BlockNumber> = deserialize_response_result(response)?;
@@ -287,6 +959,83 @@ mod tests {
                 .await?
         };
         assert!(expected_txs.len() >= 3);
+        assert_eq!(
+            expected_blocks.list[2].tx_count as usize,
+            expected_txs.len()
+        );
+
+        let response = client.block_stats(&block_number.to_string()).await?;
+        let stats: BlocksStats = deserialize_response_result(response)?;
+        assert_eq!(stats.from_block, block_number);
+        assert_eq!(stats.to_block, block_number);
+        assert_eq!(
+            stats.tx_counts.values().sum::<u64>() as usize,
+            expected_txs.len()
+        );
+
+        let last_finalized = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_last_verified_confirmed_block()
+                .await?
+        };
+        let response = client.finalized_blocks(BlockNumber(0)).await?;
+        let finalized: FinalizedBlocks = deserialize_response_result(response)?;
+        assert_eq!(finalized.last_finalized, last_finalized);
+        assert_eq!(finalized.blocks.len() as u32, *last_finalized);
+        assert!(finalized
+            .blocks
+            .iter()
+            .all(|block| block.status == BlockStatus::Finalized));
+
+        let exported = client.blocks_export("1", "3").await?;
+        let exported: Vec<ExportedBlock> = exported
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(exported.len(), 3);
+        for (exported, expected) in exported.iter().zip(&expected_blocks.list) {
+            assert_eq!(&exported.block, expected);
+            assert_eq!(exported.transactions.len() as u64, expected.tx_count);
+        }
+
+        let expected_diff = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let updates = storage
+                .chain()
+                .state_schema()
+                .load_state_diff_for_block(block_number)
+                .await?;
+            let account_ids: Vec<AccountId> = updates.iter().map(|(id, _)| *id).collect();
+            let addresses = storage
+                .chain()
+                .account_schema()
+                .account_addresses_by_ids(&account_ids)
+                .await?;
+            block_state_diff(block_number, updates, &addresses)
+        };
+        let response = client.block_state_diff(&block_number.to_string()).await?;
+        let diff: BlockStateDiff = deserialize_response_result(response)?;
+        assert_eq!(diff, expected_diff);
+
+        let expected_commitment = {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .chain()
+                .block_schema()
+                .get_storage_block(block_number)
+                .await?
+                .expect("Block should exist")
+                .commitment
+        };
+        let response = client.block_proof(&block_number.to_string()).await?;
+        let proof: Option<BlockProofInfo> = deserialize_response_result(response)?;
+        let proof = proof.expect("Proof data should exist for the stored block");
+        assert_eq!(proof.block_number, block_number);
+        assert_eq!(proof.commitment.as_bytes(), expected_commitment.as_slice());
+
         let tx_hash_str = expected_txs.first().unwrap().tx_hash.as_str();
         let tx_hash = TxHash::from_str(tx_hash_str).unwrap();
 
@@ -325,7 +1074,7 @@ mod tests {
             }
             let response = client
                 .transaction_in_block(
-                    expected_tx.block_number as u32,
+                    &expected_tx.block_number.to_string(),
                     expected_tx.block_index.unwrap() as u32,
                 )
                 .await?;
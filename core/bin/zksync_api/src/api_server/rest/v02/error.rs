@@ -2,16 +2,26 @@
 use std::fmt::{Display, Formatter};
 
 // External uses
+use actix_web::http::StatusCode;
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use thiserror::Error;
 
 // Workspace uses
-use zksync_api_types::v02::pagination::{UnknownFromParameter, MAX_LIMIT};
+use zksync_api_types::v02::{
+    account::MAX_ACCOUNTS_BATCH_SIZE,
+    block::{MAX_BLOCKS_EXPORT_RANGE, MAX_BLOCKS_STATS_RANGE},
+    pagination::{UnknownFromParameter, MAX_LIMIT},
+    transaction::MAX_TX_STATUSES_BATCH_SIZE,
+};
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
+use zksync_storage::tokens::ResolveTokenError;
 
 // Local uses
-use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
+use crate::{
+    api_server::tx_sender::{retry_after_secs, SubmitError},
+    fee_ticker::PriceError,
+};
 
 #[derive(Serialize_repr, Debug, Deserialize_repr, Clone, PartialEq)]
 #[repr(u16)]
@@ -27,6 +37,23 @@ pub enum ErrorCode {
     PaginationLimitTooBig = 206,
     QueryDeserializationError = 207,
     InvalidNFTTokenId = 208,
+    TooManyTxHashes = 209,
+    InvalidTxHashFormat = 210,
+    InvalidTxHashLength = 211,
+    InvalidPubKeyHash = 212,
+    TooManyAccounts = 213,
+    EnsNameNotResolved = 214,
+    InvalidBlockHash = 215,
+    InvalidBlocksRange = 216,
+    InvalidBlocksExportRange = 217,
+    InvalidTimeRange = 218,
+    AmbiguousTokenSymbol = 219,
+    TokenNotRegistered = 220,
+    InvalidTokenMetadata = 221,
+    NFTMetadataUnavailable = 222,
+    TokenPriceUnavailable = 223,
+    IncompatibleToken = 224,
+    ExodusModeNotActive = 225,
     StorageError = 300,
     TokenNotFound = 500,
     ExternalApiError = 501,
@@ -39,6 +66,12 @@ pub enum ErrorCode {
     InappropriateFeeToken = 606,
     CommunicationCoreServer = 607,
     Toggle2FAError = 608,
+    ReplaceTxError = 609,
+    RateLimited = 610,
+    MempoolFull = 611,
+    TooManyPendingTxs = 612,
+    AddressBlocked = 613,
+    FeeTooLow = 614,
     Other = 60_000,
 }
 
@@ -49,6 +82,15 @@ pub struct Error {
     pub error_type: String,
     pub code: ErrorCode,
     pub message: String,
+    /// Details of the error, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    /// HTTP status code of the response, it is not a part of the response body.
+    #[serde(skip)]
+    pub http_status: StatusCode,
+    /// Value of the `Retry-After` header of the response in seconds.
+    #[serde(skip)]
+    pub retry_after: Option<u64>,
 }
 
 /// Trait that can be used to map custom errors to the object.
@@ -60,6 +102,21 @@ pub trait ApiError: std::fmt::Display {
     fn message(&self) -> String {
         self.to_string()
     }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Most of the errors are reported with the `200 OK` status code
+    /// and should be distinguished by the `code` field of the response body.
+    fn http_status(&self) -> StatusCode {
+        StatusCode::OK
+    }
+
+    /// Number of seconds the client should wait before retrying the request.
+    fn retry_after(&self) -> Option<u64> {
+        None
+    }
 }
 
 impl<T> From<T> for Error
@@ -71,6 +128,9 @@ where
             error_type: t.error_type(),
             code: t.code(),
             message: t.message(),
+            data: t.data(),
+            http_status: t.http_status(),
+            retry_after: t.retry_after(),
         }
     }
 }
@@ -85,7 +145,7 @@ impl Error {
     }
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
 pub enum InvalidDataError {
     #[error("Cannot show price in zero price token")]
     TokenZeroPriceError,
@@ -103,6 +163,46 @@ pub enum InvalidDataError {
     PaginationLimitTooBig,
     #[error("NFT token ID should be greater than or equal to {}", MIN_NFT_TOKEN_ID)]
     InvalidNFTTokenId,
+    #[error(
+        "Number of requested transactions should be less than or equal to {}",
+        MAX_TX_STATUSES_BATCH_SIZE
+    )]
+    TooManyTxHashes,
+    #[error("Cannot parse public key hash, it should be in the `sync:` prefixed hex format")]
+    InvalidPubKeyHash,
+    #[error(
+        "Number of requested accounts should be less than or equal to {}",
+        MAX_ACCOUNTS_BATCH_SIZE
+    )]
+    TooManyAccounts,
+    #[error("ENS name cannot be resolved")]
+    EnsNameNotResolved,
+    #[error("Cannot parse block hash, it should be a 32 bytes long hex string")]
+    InvalidBlockHash,
+    #[error(
+        "Invalid blocks range, `from` should not exceed `to` and the range should contain at most {} blocks",
+        MAX_BLOCKS_STATS_RANGE
+    )]
+    InvalidBlocksRange,
+    #[error(
+        "Invalid blocks range, `from` should not exceed `to` and the range should contain at most {} blocks",
+        MAX_BLOCKS_EXPORT_RANGE
+    )]
+    InvalidBlocksExportRange,
+    #[error("Invalid time range, `from` should not exceed `to`")]
+    InvalidTimeRange,
+    #[error("Token is not added to the governance contract")]
+    TokenNotRegistered,
+    #[error("Cannot load the symbol and decimals of the ERC-20 token")]
+    InvalidTokenMetadata,
+    #[error("Cannot fetch the NFT metadata from IPFS")]
+    NFTMetadataUnavailable,
+    #[error("Price of the token is not fetched yet")]
+    TokenPriceUnavailable,
+    #[error("Token didn't pass the ERC-20 compatibility checks, see `GET /tokens/{{address}}/compatibility`")]
+    IncompatibleToken,
+    #[error("Exit data is only available when the contract is in the exodus mode")]
+    ExodusModeNotActive,
 }
 
 impl ApiError for InvalidDataError {
@@ -120,6 +220,102 @@ impl ApiError for InvalidDataError {
             Self::TransactionNotFound => ErrorCode::TransactionNotFound,
             Self::PaginationLimitTooBig => ErrorCode::PaginationLimitTooBig,
             Self::InvalidNFTTokenId => ErrorCode::InvalidNFTTokenId,
+            Self::TooManyTxHashes => ErrorCode::TooManyTxHashes,
+            Self::InvalidPubKeyHash => ErrorCode::InvalidPubKeyHash,
+            Self::TooManyAccounts => ErrorCode::TooManyAccounts,
+            Self::EnsNameNotResolved => ErrorCode::EnsNameNotResolved,
+            Self::InvalidBlockHash => ErrorCode::InvalidBlockHash,
+            Self::InvalidBlocksRange => ErrorCode::InvalidBlocksRange,
+            Self::InvalidBlocksExportRange => ErrorCode::InvalidBlocksExportRange,
+            Self::InvalidTimeRange => ErrorCode::InvalidTimeRange,
+            Self::TokenNotRegistered => ErrorCode::TokenNotRegistered,
+            Self::InvalidTokenMetadata => ErrorCode::InvalidTokenMetadata,
+            Self::NFTMetadataUnavailable => ErrorCode::NFTMetadataUnavailable,
+            Self::TokenPriceUnavailable => ErrorCode::TokenPriceUnavailable,
+            Self::IncompatibleToken => ErrorCode::IncompatibleToken,
+            Self::ExodusModeNotActive => ErrorCode::ExodusModeNotActive,
+        }
+    }
+}
+
+/// Errors of the transaction endpoints.
+///
+/// Unlike the other API errors, they are reported with the HTTP status codes matching the error.
+#[derive(Error, Debug)]
+pub enum TxError {
+    #[error("Cannot parse transaction hash: {0}")]
+    InvalidHashFormat(String),
+    #[error("Transaction hash should be 32 bytes long")]
+    InvalidHashLength,
+    #[error("Transaction is not found")]
+    TxNotFound,
+    #[error("Storage is unavailable: {0}")]
+    StorageUnavailable(String),
+    #[error("{0}")]
+    SubmitRejected(#[from] SubmitError),
+}
+
+impl TxError {
+    pub fn storage_unavailable(err: impl Display) -> Error {
+        Error::from(Self::StorageUnavailable(err.to_string()))
+    }
+
+    pub fn submit_rejected(err: SubmitError) -> Error {
+        Error::from(Self::SubmitRejected(err))
+    }
+}
+
+impl ApiError for TxError {
+    fn error_type(&self) -> String {
+        match self {
+            Self::InvalidHashFormat(_) | Self::InvalidHashLength | Self::TxNotFound => {
+                String::from("invalidDataError")
+            }
+            Self::StorageUnavailable(_) => String::from("storageError"),
+            Self::SubmitRejected(err) => err.error_type(),
+        }
+    }
+
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidHashFormat(_) => ErrorCode::InvalidTxHashFormat,
+            Self::InvalidHashLength => ErrorCode::InvalidTxHashLength,
+            Self::TxNotFound => ErrorCode::TransactionNotFound,
+            Self::StorageUnavailable(_) => ErrorCode::StorageError,
+            Self::SubmitRejected(err) => err.code(),
+        }
+    }
+
+    fn http_status(&self) -> StatusCode {
+        match self {
+            Self::InvalidHashFormat(_) | Self::InvalidHashLength => StatusCode::BAD_REQUEST,
+            Self::TxNotFound => StatusCode::NOT_FOUND,
+            Self::StorageUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
+            Self::SubmitRejected(err) => match err {
+                SubmitError::Internal(_) | SubmitError::PriceError(_) => {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+                SubmitError::MempoolCommunication(_) => StatusCode::SERVICE_UNAVAILABLE,
+                SubmitError::RateLimited(_)
+                | SubmitError::MempoolFull { .. }
+                | SubmitError::TooManyPendingTxs { .. } => StatusCode::TOO_MANY_REQUESTS,
+                SubmitError::AddressBlocked { .. } => StatusCode::FORBIDDEN,
+                _ => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+        }
+    }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::SubmitRejected(err) => err.data(),
+            _ => None,
+        }
+    }
+
+    fn retry_after(&self) -> Option<u64> {
+        match self {
+            Self::SubmitRejected(err) => err.retry_after(),
+            _ => None,
         }
     }
 }
@@ -200,8 +396,41 @@ impl ApiError for SubmitError {
             Self::MempoolCommunication(_) => ErrorCode::CommunicationCoreServer,
             Self::Internal(_) => ErrorCode::InternalError,
             Self::Toggle2FA(_) => ErrorCode::Toggle2FAError,
+            Self::ReplaceTx(_) => ErrorCode::ReplaceTxError,
             Self::Other(_) => ErrorCode::Other,
             Self::PriceError(_) => ErrorCode::InternalError,
+            Self::RateLimited(_) => ErrorCode::RateLimited,
+            Self::MempoolFull { .. } => ErrorCode::MempoolFull,
+            Self::FeeTooLow { .. } => ErrorCode::FeeTooLow,
+            Self::TooManyPendingTxs { .. } => ErrorCode::TooManyPendingTxs,
+            Self::AddressBlocked { .. } => ErrorCode::AddressBlocked,
+        }
+    }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::FeeTooLow {
+                token,
+                required_fee,
+                provided_fee,
+                shortfall,
+                ..
+            } => Some(serde_json::json!({
+                "token": token,
+                "requiredFee": required_fee.to_string(),
+                "providedFee": provided_fee.to_string(),
+                "shortfall": shortfall.to_string(),
+            })),
+            _ => None,
+        }
+    }
+
+    fn retry_after(&self) -> Option<u64> {
+        match self {
+            Self::RateLimited(retry_after) | Self::MempoolFull { retry_after, .. } => {
+                Some(retry_after_secs(retry_after))
+            }
+            _ => None,
         }
     }
 }
@@ -220,6 +449,24 @@ impl ApiError for PriceError {
     }
 }
 
+impl ApiError for ResolveTokenError {
+    fn error_type(&self) -> String {
+        match self {
+            Self::NotFound => String::from("tokenError"),
+            Self::AmbiguousSymbol { .. } => String::from("invalidDataError"),
+            Self::Other(_) => String::from("storageError"),
+        }
+    }
+
+    fn code(&self) -> ErrorCode {
+        match self {
+            Self::NotFound => ErrorCode::TokenNotFound,
+            Self::AmbiguousSymbol { .. } => ErrorCode::AmbiguousTokenSymbol,
+            Self::Other(_) => ErrorCode::StorageError,
+        }
+    }
+}
+
 impl ApiError for UnknownFromParameter {
     fn error_type(&self) -> String {
         String::from("invalidDataError")
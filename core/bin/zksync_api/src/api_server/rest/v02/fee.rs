@@ -10,10 +10,19 @@ use actix_web::{
 };
 
 // Workspace uses
-use zksync_api_types::v02::fee::{ApiFee, BatchFeeRequest, TxFeeRequest};
+use zksync_api_types::v02::fee::{
+    ApiFee, BatchFeeRequest, FeeHistoryEntry, FeeHistoryQuery, TxFeeRequest,
+    MAX_FEE_HISTORY_ENTRIES,
+};
+use zksync_storage::misc::records::StoredFeeQuote;
+use zksync_types::{TokenId, TokenLike};
+use zksync_utils::{big_decimal_to_ratio, UnsignedRatioSerializeAsDecimal};
 
 // Local uses
-use super::{error::Error, response::ApiResult};
+use super::{
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
 use crate::{
     api_server::tx_sender::{SubmitError, TxSender},
     api_try,
@@ -29,6 +38,118 @@ impl ApiFeeData {
     fn new(tx_sender: TxSender) -> Self {
         Self { tx_sender }
     }
+
+    async fn fee_history(&self, query: FeeHistoryQuery) -> Result<Vec<FeeHistoryEntry>, Error> {
+        if query.from > query.to {
+            return Err(Error::from(InvalidDataError::InvalidTimeRange));
+        }
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let token_id = match query.token {
+            Some(token) => {
+                let token = self
+                    .tx_sender
+                    .tokens
+                    .resolve_token(&mut storage, TokenLike::parse(&token))
+                    .await?;
+                Some(token.id)
+            }
+            None => None,
+        };
+
+        let quotes = storage
+            .misc_schema()
+            .load_fee_history(
+                query.tx_type.as_deref(),
+                token_id,
+                query.from,
+                query.to,
+                MAX_FEE_HISTORY_ENTRIES,
+            )
+            .await
+            .map_err(Error::storage)?;
+        quotes.into_iter().map(fee_history_entry).collect()
+    }
+
+    /// Quotes the fee of the transaction, the discount of the current subsidy is applied
+    /// if the fee type is subsidized.
+    async fn tx_fee(&self, body: TxFeeRequest) -> Result<ApiFee, Error> {
+        let fee = self
+            .tx_sender
+            .ticker
+            .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like.clone(), body.address)
+            .await?;
+        self.tx_sender
+            .ticker
+            .record_fee_quote(body.token_like.clone(), fee.normal_fee.clone());
+
+        let price_updated_at = fee.price_updated_at;
+        let quoted_fee = match self
+            .tx_sender
+            .apply_subsidy_discount(None, &fee, body.token_like)
+            .await?
+        {
+            Some(discounted_fee) => discounted_fee.subsidized_fee,
+            None => fee.normal_fee,
+        };
+        Ok(ApiFee::from_fee(quoted_fee, price_updated_at))
+    }
+
+    /// Same as `tx_fee`, but for the batch of the transactions.
+    async fn batch_fee(&self, body: BatchFeeRequest) -> Result<ApiFee, Error> {
+        let txs: Vec<_> = body
+            .transactions
+            .into_iter()
+            .map(|tx| (tx.tx_type.into(), tx.address))
+            .collect();
+        let discount_candidates: Vec<_> = txs
+            .iter()
+            .map(|(tx_type, address)| (None, *tx_type, *address))
+            .collect();
+        let fee = self
+            .tx_sender
+            .ticker
+            .get_batch_from_ticker_in_wei(body.token_like.clone(), txs)
+            .await?;
+
+        let price_updated_at = fee.price_updated_at;
+        let quoted_fee = match self
+            .tx_sender
+            .apply_batch_subsidy_discount(&discount_candidates, &fee, body.token_like)
+            .await?
+        {
+            Some(discounted_fee) => discounted_fee.subsidized_fee,
+            None => fee.normal_fee,
+        };
+        Ok(ApiFee::from_batch_fee(quoted_fee, price_updated_at))
+    }
+}
+
+fn fee_history_entry(quote: StoredFeeQuote) -> Result<FeeHistoryEntry, Error> {
+    let to_biguint = |value: &_| {
+        big_decimal_to_ratio(value)
+            .map(|ratio| ratio.to_integer())
+            .map_err(Error::storage)
+    };
+
+    Ok(FeeHistoryEntry {
+        tx_type: quote.tx_type,
+        token_id: TokenId(quote.token_id as u32),
+        gas_price_wei: to_biguint(&quote.gas_price_wei)?,
+        token_price_usd: UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(
+            &quote.token_price_usd.to_string(),
+        )
+        .map_err(Error::storage)?,
+        gas_fee: to_biguint(&quote.gas_fee)?,
+        zkp_fee: to_biguint(&quote.zkp_fee)?,
+        total_fee: to_biguint(&quote.total_fee)?,
+        quoted_at: quote.created_at,
+    })
 }
 
 async fn get_tx_fee(
@@ -45,15 +166,7 @@ async fn get_tx_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
-    // TODO implement subsidies for v02 api ZKS-888
-    let res = data
-        .tx_sender
-        .ticker
-        .get_fee_from_ticker_in_wei(body.tx_type.into(), body.token_like, body.address)
-        .await
-        .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+    let res = data.tx_fee(body).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_tx_fee");
     res
 }
@@ -72,23 +185,21 @@ async fn get_batch_fee(
     if !token_allowed {
         return Error::from(SubmitError::InappropriateFeeToken).into();
     }
-    let txs = body
-        .transactions
-        .into_iter()
-        .map(|tx| (tx.tx_type.into(), tx.address))
-        .collect();
-    let res = data
-        .tx_sender
-        .ticker
-        .get_batch_from_ticker_in_wei(body.token_like, txs)
-        .await
-        .map(|fee| fee.normal_fee.into())
-        .map_err(Error::from)
-        .into();
+    let res = data.batch_fee(body).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_batch_fee");
     res
 }
 
+async fn get_fee_history(
+    data: web::Data<ApiFeeData>,
+    web::Query(query): web::Query<FeeHistoryQuery>,
+) -> ApiResult<Vec<FeeHistoryEntry>> {
+    let start = Instant::now();
+    let res = data.fee_history(query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_fee_history");
+    res
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiFeeData::new(tx_sender);
 
@@ -96,6 +207,7 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .app_data(web::Data::new(data))
         .route("", web::post().to(get_tx_fee))
         .route("/batch", web::post().to(get_batch_fee))
+        .route("/history", web::get().to(get_fee_history))
 }
 
 #[cfg(test)]
@@ -210,6 +322,27 @@ mod tests {
         assert_eq!(api_batch_fee.zkp_fee, BigUint::from(1u32));
         assert_eq!(api_batch_fee.total_fee, BigUint::from(2u32));
 
+        let now = Utc::now();
+        let response = client
+            .fee_history(None, None, now, now - chrono::Duration::hours(1))
+            .await?;
+        let expected_error = Error::from(InvalidDataError::InvalidTimeRange);
+        let error = serde_json::from_value::<Error>(response.error.unwrap()).unwrap();
+        assert_eq!(error, expected_error);
+
+        let response = client
+            .fee_history(
+                Some("Withdraw".to_string()),
+                Some("0".to_string()),
+                now - chrono::Duration::hours(1),
+                now,
+            )
+            .await?;
+        let history: Vec<FeeHistoryEntry> = deserialize_response_result(response)?;
+        assert!(history
+            .iter()
+            .all(|entry| entry.tx_type == "Withdraw" && entry.token_id == TokenId(0)));
+
         server.stop().await;
         Ok(())
     }
@@ -0,0 +1,97 @@
+//! Parsing of hashes passed to the API as path parameters.
+
+// Workspace uses
+use zksync_types::{
+    tx::{TxHash, TxHashDecodeError},
+    H256,
+};
+
+// Local uses
+use super::error::{Error, InvalidDataError, TxError};
+
+/// Error of decoding the hash, mapped to the API error of the particular hash kind.
+enum HashDecodeError {
+    InvalidHex(hex::FromHexError),
+    InvalidLength,
+}
+
+/// Decodes the 32 bytes long hex hash, which may be prefixed with either `0x` or the
+/// `type_prefix` of the hash kind (e.g. `sync-tx:`).
+fn decode_hash(hash: &str, type_prefix: &str) -> Result<H256, HashDecodeError> {
+    let hex_str = hash
+        .strip_prefix("0x")
+        .or_else(|| hash.strip_prefix(type_prefix))
+        .unwrap_or(hash);
+    let bytes = hex::decode(hex_str).map_err(HashDecodeError::InvalidHex)?;
+    if bytes.len() != H256::len_bytes() {
+        return Err(HashDecodeError::InvalidLength);
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+/// Parses a transaction (or batch) hash given in one of the supported forms:
+/// `0x…`, `sync-tx:…` or bare hex.
+/// Bare hex is accepted only by the API, `TxHash::from_str` requires the prefix.
+pub(super) fn parse_tx_hash(tx_hash: &str) -> Result<TxHash, Error> {
+    let hash = decode_hash(tx_hash, "sync-tx:").map_err(|err| {
+        Error::from(match err {
+            HashDecodeError::InvalidLength => TxError::InvalidHashLength,
+            HashDecodeError::InvalidHex(err) => {
+                TxError::InvalidHashFormat(TxHashDecodeError::DecodeHex(err).to_string())
+            }
+        })
+    })?;
+    Ok(TxHash::from_slice(hash.as_bytes()).expect("hash is 32 bytes long"))
+}
+
+/// Parses a block hash given in one of the supported forms: `0x…`, `sync-bl:…` or bare hex.
+pub(super) fn parse_block_hash(hash: &str) -> Result<H256, Error> {
+    decode_hash(hash, "sync-bl:").map_err(|_| Error::from(InvalidDataError::InvalidBlockHash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::error::ErrorCode;
+    use actix_web::http::StatusCode;
+
+    #[test]
+    fn parse_tx_hash_prefixes() {
+        let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+        let hex = hex::encode(&[1; 32]);
+
+        assert_eq!(parse_tx_hash(&tx_hash.to_string()).unwrap(), tx_hash);
+        assert_eq!(parse_tx_hash(&format!("0x{}", hex)).unwrap(), tx_hash);
+        assert_eq!(parse_tx_hash(&hex).unwrap(), tx_hash);
+    }
+
+    #[test]
+    fn parse_tx_hash_errors() {
+        let error = parse_tx_hash("0xdeadbeef").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidTxHashLength);
+        assert_eq!(error.http_status, StatusCode::BAD_REQUEST);
+
+        let error = parse_tx_hash("deadbeef").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidTxHashLength);
+        assert_eq!(error.http_status, StatusCode::BAD_REQUEST);
+
+        let error = parse_tx_hash("sync-tx:not_a_hex_string").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidTxHashFormat);
+        assert_eq!(error.http_status, StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn parse_block_hash_prefixes() {
+        let hash = H256::repeat_byte(1);
+        let hex = hex::encode(hash.as_bytes());
+
+        assert_eq!(parse_block_hash(&format!("0x{}", hex)).unwrap(), hash);
+        assert_eq!(parse_block_hash(&format!("sync-bl:{}", hex)).unwrap(), hash);
+        assert_eq!(parse_block_hash(&hex).unwrap(), hash);
+
+        let error = parse_block_hash("sync-tx:01").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidBlockHash);
+        let error = parse_block_hash("0xdeadbeef").unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidBlockHash);
+    }
+}
@@ -1,4 +1,5 @@
 // External uses
+use actix::Addr;
 use actix_web::{
     web::{self},
     Scope,
@@ -7,24 +8,32 @@ use actix_web::{
 use crate::api_server::rest::network_status::SharedNetworkStatus;
 use zksync_api_types::v02::ApiVersion;
 use zksync_config::ZkSyncConfig;
+use zksync_storage::ConnectionPool;
 use zksync_types::network::Network;
 
 // Local uses
+use self::{tx_status_events::TxStatusEvents, ws::WsMonitor};
 use crate::api_server::tx_sender::TxSender;
+use crate::eth_checker::EthereumChecker;
 
 mod account;
 mod block;
 mod config;
 pub mod error;
 mod fee;
+mod hash;
+mod mempool;
 mod paginate_impl;
 mod paginate_trait;
 mod response;
+mod stats;
 mod status;
 #[cfg(test)]
 pub mod test_utils;
 mod token;
 mod transaction;
+pub mod tx_status_events;
+pub mod ws;
 
 #[derive(Debug, Clone, Copy)]
 pub struct SharedData {
@@ -34,8 +43,12 @@ pub struct SharedData {
 
 pub(crate) fn api_scope(
     tx_sender: TxSender,
+    main_pool: ConnectionPool,
+    eth_checker: EthereumChecker,
     zk_config: &ZkSyncConfig,
     network_status: SharedNetworkStatus,
+    status_events: TxStatusEvents,
+    ws_monitor: Addr<WsMonitor>,
 ) -> Scope {
     let data = SharedData {
         net: zk_config.chain.eth.network,
@@ -47,19 +60,30 @@ pub(crate) fn api_scope(
             tx_sender.pool.clone(),
             tx_sender.tokens.clone(),
             zk_config.eth_watch.confirmations_for_eth_event,
+            eth_checker.clone(),
         ))
         .service(block::api_scope(
+            zk_config,
             tx_sender.pool.clone(),
             tx_sender.blocks.clone(),
+            tx_sender.tokens.clone(),
         ))
         .service(config::api_scope(zk_config))
         .service(fee::api_scope(tx_sender.clone()))
+        .service(mempool::api_scope(tx_sender.pool.clone()))
+        .service(stats::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+        ))
         .service(status::api_scope(network_status))
         .service(token::api_scope(
             zk_config,
             tx_sender.pool.clone(),
+            main_pool,
             tx_sender.tokens.clone(),
             tx_sender.ticker.clone(),
+            eth_checker,
         ))
-        .service(transaction::api_scope(tx_sender))
+        .service(ws::api_scope(ws_monitor))
+        .service(transaction::api_scope(tx_sender, status_events))
 }
@@ -19,7 +19,7 @@ use zksync_types::{BlockNumber, SerialId, Token, TokenId};
 
 // Local uses
 use super::{
-    block::block_info_from_details,
+    block::{block_eth_txs, block_info_from_details},
     error::{Error, InvalidDataError},
     paginate_trait::Paginate,
 };
@@ -111,7 +111,22 @@ impl Paginate<ApiEither<BlockNumber>> for StorageProcessor<'_> {
             .load_block_page(&query)
             .await
             .map_err(Error::storage)?;
-        let blocks: Vec<BlockInfo> = blocks.into_iter().map(block_info_from_details).collect();
+        let min_block = blocks.iter().map(|block| block.block_number).min();
+        let max_block = blocks.iter().map(|block| block.block_number).max();
+        let eth_txs = if let (Some(min_block), Some(max_block)) = (min_block, max_block) {
+            block_eth_txs(
+                &mut transaction,
+                BlockNumber(min_block as u32),
+                BlockNumber(max_block as u32),
+            )
+            .await?
+        } else {
+            Vec::new()
+        };
+        let blocks: Vec<BlockInfo> = blocks
+            .into_iter()
+            .map(|block| block_info_from_details(block, &eth_txs))
+            .collect();
 
         transaction.commit().await.map_err(Error::storage)?;
 
@@ -251,6 +266,7 @@ impl Paginate<AccountTxsRequest> for StorageProcessor<'_> {
                 query.from.address,
                 query.from.token,
                 query.from.second_address,
+                &query.from.filters,
             )
             .await
             .map_err(Error::storage)?;
@@ -325,6 +341,7 @@ impl Paginate<PendingOpsRequest> for StorageProcessor<'_> {
                     fail_reason: None,
                     created_at: None,
                     batch_id: None,
+                    time_range: None,
                 }
             })
             .collect();
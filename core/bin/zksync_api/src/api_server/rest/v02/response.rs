@@ -3,17 +3,27 @@ use std::collections::HashMap;
 use std::convert::From;
 
 // External uses
-use actix_web::{web::Data, HttpRequest, HttpResponse, Responder};
+use actix_web::{
+    http::{header, StatusCode},
+    web::Data,
+    HttpRequest, HttpResponse, Responder,
+};
 use chrono::Utc;
 use qstring::QString;
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_api_types::v02::{Request, Response, ResultStatus};
+use zksync_types::Address;
 
 // Local uses
 use super::{error::Error, SharedData};
 
+/// Address the ENS name from the request path was resolved to,
+/// stored in the request extensions by the endpoints accepting ENS names.
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedAddress(pub Address);
+
 // This struct is needed to wrap all api responses is `Response` struct by implementing `Responder` trait for it.
 // We can't use simple `Result`, because `actix-web` has already `Responder` implementation for it.
 // Because of this we can't use '?' operator in implementations of endpoints.
@@ -43,28 +53,44 @@ impl<R: Serialize> Responder for ApiResult<R> {
             resource: String::from(req.path()),
             args,
             timestamp: Utc::now(),
+            resolved_address: req
+                .extensions()
+                .get::<ResolvedAddress>()
+                .map(|resolved| resolved.0),
         };
 
-        let response = match self {
-            ApiResult::Ok(res) => Response {
-                request,
-                status: ResultStatus::Success,
-                result: Some(serde_json::to_value(res).unwrap()),
-                error: None,
-            },
-            ApiResult::Error(err) => Response {
-                request,
-                status: ResultStatus::Error,
-                result: None,
-                error: Some(serde_json::to_value(err).unwrap()),
-            },
+        let retry_after = match &self {
+            ApiResult::Error(err) => err.retry_after,
+            ApiResult::Ok(_) => None,
+        };
+        let (response, http_status) = match self {
+            ApiResult::Ok(res) => (
+                Response {
+                    request,
+                    status: ResultStatus::Success,
+                    result: Some(serde_json::to_value(res).unwrap()),
+                    error: None,
+                },
+                StatusCode::OK,
+            ),
+            ApiResult::Error(err) => (
+                Response {
+                    request,
+                    status: ResultStatus::Error,
+                    result: None,
+                    error: Some(serde_json::to_value(&err).unwrap()),
+                },
+                err.http_status,
+            ),
         };
 
         let body = serde_json::to_string(&response).expect("Should be correct serializable");
 
-        HttpResponse::Ok()
-            .content_type("application/json")
-            .body(body)
+        let mut response = HttpResponse::build(http_status);
+        if let Some(retry_after) = retry_after {
+            response.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+        response.content_type("application/json").body(body)
     }
 }
 
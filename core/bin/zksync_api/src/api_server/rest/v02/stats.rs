@@ -0,0 +1,125 @@
+//! Stats part of API implementation.
+
+// Built-in uses
+use std::time::Instant;
+
+// External uses
+use actix_web::{web, Scope};
+
+// Workspace uses
+use zksync_api_types::v02::block::{BlocksStats, BlocksStatsQuery, MAX_BLOCKS_STATS_RANGE};
+use zksync_storage::ConnectionPool;
+use zksync_token_db_cache::TokenDBCache;
+
+// Local uses
+use super::{
+    block::{block_number_by_position, blocks_stats},
+    error::{Error, InvalidDataError},
+    response::ApiResult,
+};
+use crate::api_try;
+
+/// Shared data between `api/v0.2/stats` endpoints.
+#[derive(Debug, Clone)]
+struct ApiStatsData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+impl ApiStatsData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache) -> Self {
+        Self { pool, tokens }
+    }
+
+    async fn blocks_stats(&self, query: BlocksStatsQuery) -> Result<BlocksStats, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let from_block = block_number_by_position(&mut storage, &query.from).await?;
+        let to_block = block_number_by_position(&mut storage, &query.to).await?;
+        if from_block > to_block || *to_block - *from_block >= MAX_BLOCKS_STATS_RANGE {
+            return Err(Error::from(InvalidDataError::InvalidBlocksRange));
+        }
+        blocks_stats(&mut storage, &self.tokens, from_block, to_block).await
+    }
+}
+
+// Server implementation
+
+async fn get_blocks_stats(
+    data: web::Data<ApiStatsData>,
+    web::Query(query): web::Query<BlocksStatsQuery>,
+) -> ApiResult<BlocksStats> {
+    let start = Instant::now();
+    let res = api_try!(data.blocks_stats(query).await);
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_blocks_stats");
+    ApiResult::Ok(res)
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache) -> Scope {
+    let data = ApiStatsData::new(pool, tokens);
+
+    web::scope("stats")
+        .app_data(web::Data::new(data))
+        .route("blocks", web::get().to(get_blocks_stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{
+        test_utils::{deserialize_response_result, TestServerConfig},
+        SharedData,
+    };
+    use zksync_api_types::v02::ApiVersion;
+    use zksync_types::BlockNumber;
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn stats_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (client, server) = cfg.start_server(
+            |cfg: &TestServerConfig| {
+                api_scope(
+                    cfg.pool.clone(),
+                    TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
+                )
+            },
+            Some(shared_data),
+        );
+
+        let expected_tx_count: u64 = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let mut tx_count = 0;
+            for block_number in 1..=3 {
+                tx_count += storage
+                    .chain()
+                    .block_schema()
+                    .get_block_transactions(BlockNumber(block_number))
+                    .await?
+                    .len() as u64;
+            }
+            tx_count
+        };
+
+        let response = client.blocks_stats("1", "3").await?;
+        let stats: BlocksStats = deserialize_response_result(response)?;
+        assert_eq!(stats.from_block, BlockNumber(1));
+        assert_eq!(stats.to_block, BlockNumber(3));
+        assert_eq!(stats.tx_counts.values().sum::<u64>(), expected_tx_count);
+        assert!(stats.active_accounts > 0);
+
+        let response = client.blocks_stats("3", "1").await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        Ok(())
+    }
+}
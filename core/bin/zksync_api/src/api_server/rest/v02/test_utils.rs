@@ -39,9 +39,9 @@ use zksync_types::{
     prover::ProverJobType,
     tx::ChangePubKeyType,
     AccountId, AccountMap, AccountUpdate, Address, BlockNumber, Deposit, DepositOp,
-    ExecutedOperations, ExecutedPriorityOp, ExecutedTx, FullExit, FullExitOp, MintNFTOp, Nonce,
-    PriorityOp, Token, TokenId, TokenKind, TokenLike, TokenPrice, Transfer, TransferOp, ZkSyncOp,
-    ZkSyncTx, H256, NFT,
+    ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Fee, FullExit, FullExitOp, MintNFTOp,
+    Nonce, PriorityOp, Token, TokenId, TokenKind, TokenLike, TokenPrice, Transfer, TransferOp,
+    ZkSyncOp, ZkSyncTx, H256, NFT,
 };
 use zksync_utils::{big_decimal_to_ratio, scaled_u64_to_ratio, UnsignedRatioSerializeAsDecimal};
 
@@ -889,6 +889,15 @@ impl FeeTickerInfo for DummyFeeTickerInfo {
         })
     }
 
+    async fn store_fee_quote(
+        &self,
+        _token_id: TokenId,
+        _fee: &Fee,
+        _token_price_usd: &Ratio<BigUint>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
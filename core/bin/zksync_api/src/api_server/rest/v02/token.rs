@@ -1,8 +1,10 @@
 //! Tokens part of API implementation.
 
 // Built-in uses
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::time::Instant;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 // External uses
 use actix_web::{
@@ -10,28 +12,39 @@ use actix_web::{
     Scope,
 };
 use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
 use num::{rational::Ratio, BigUint, FromPrimitive};
 
 // Workspace uses
 use zksync_api_types::v02::{
     pagination::{parse_query, ApiEither, Paginated, PaginationQuery},
-    token::{ApiNFT, ApiToken, TokenPrice},
+    token::{
+        ApiFeeTokenDecision, ApiNFT, ApiNFTMetadata, ApiToken, SourcedTokenPrice,
+        TokenCompatibilityReport, TokenListingRequest, TokenPrice, TokenPriceHistoryEntry,
+        TokenPriceHistoryQuery, TokenPriceSourceInfo, MAX_FEE_TOKEN_DECISIONS,
+        MAX_TOKEN_PRICE_HISTORY_ENTRIES,
+    },
 };
 use zksync_config::ZkSyncConfig;
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
-use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_storage::{tokens::ResolveTokenError, ConnectionPool, StorageProcessor};
 use zksync_token_db_cache::TokenDBCache;
-use zksync_types::{tx::TxHash, AccountId, Token, TokenId, TokenLike};
+use zksync_types::{AccountId, Address, Token, TokenId, TokenKind, TokenLike};
+use zksync_utils::ratio_to_big_decimal;
 
 // Local uses
 use super::{
     error::{Error, InvalidDataError},
+    hash::parse_tx_hash,
     paginate_trait::Paginate,
     response::ApiResult,
 };
 use crate::{
     api_try,
-    fee_ticker::{FeeTicker, PriceError, TokenPriceRequestType},
+    eth_checker::EthereumChecker,
+    fee_ticker::{FeeTicker, TokenPriceRequestType},
+    nft_metadata::IpfsClient,
+    token_compatibility::TokenCompatibilityChecker,
 };
 
 /// Shared data between `api/v0.2/tokens` endpoints.
@@ -41,14 +54,34 @@ struct ApiTokenData {
     fee_ticker: FeeTicker,
     tokens: TokenDBCache,
     pool: ConnectionPool,
+    /// Connection pool of the main database, used to store the listed tokens.
+    main_pool: ConnectionPool,
+    eth_checker: EthereumChecker,
+    compatibility_checker: TokenCompatibilityChecker,
+    governance_addr: Address,
+    /// Number of confirmations of the block at which the governance contract is checked.
+    confirmations_for_eth_event: u64,
+    /// Outcomes of the recent Ethereum lookups made by the token listing requests.
+    listing_lookups: Arc<Mutex<HashMap<Address, ListingLookup>>>,
+    ipfs: IpfsClient,
+    nft_metadata_ttl: chrono::Duration,
+}
+
+/// Outcome of the Ethereum lookup of the token made by the listing request.
+#[derive(Debug, Clone, Copy)]
+struct ListingLookup {
+    made_at: Instant,
+    result: Result<(), InvalidDataError>,
 }
 
 impl ApiTokenData {
     fn new(
         config: &ZkSyncConfig,
         pool: ConnectionPool,
+        main_pool: ConnectionPool,
         tokens: TokenDBCache,
         fee_ticker: FeeTicker,
+        eth_checker: EthereumChecker,
     ) -> Self {
         Self {
             min_market_volume: Ratio::from(
@@ -56,23 +89,72 @@ impl ApiTokenData {
                     .expect("TickerConfig::liquidity_volume must be positive"),
             ),
             pool,
+            main_pool,
             tokens,
             fee_ticker,
+            compatibility_checker: TokenCompatibilityChecker::new(
+                eth_checker.clone(),
+                config.contracts.contract_addr,
+            ),
+            eth_checker,
+            governance_addr: config.contracts.governance_addr,
+            confirmations_for_eth_event: config.eth_watch.confirmations_for_eth_event,
+            listing_lookups: Default::default(),
+            ipfs: IpfsClient::new(config.api.token_config.ipfs_gateway_url.clone()),
+            nft_metadata_ttl: chrono::Duration::from_std(
+                config.api.token_config.nft_metadata_ttl(),
+            )
+            .expect("NFT metadata TTL is too big"),
         }
     }
 }
 
+/// Precision of the prices returned by the API, matches the precision used by the fee ticker.
+const PRICE_PRECISION: usize = 100;
+/// Listing requests of the same token don't query Ethereum again during this interval.
+const LISTING_LOOKUP_COOLDOWN: Duration = Duration::from_secs(60);
+/// Maximum number of the tokens whose listing lookups are remembered.
+const MAX_LISTING_LOOKUPS: usize = 10_000;
+
+/// Symbol assigned by the token handler to the tokens missing in the trusted tokens list.
+fn default_token_symbol(token_id: TokenId) -> String {
+    format!("ERC20-{}", token_id)
+}
+
 impl ApiTokenData {
-    async fn is_token_enabled_for_fees(
+    /// Returns the subset of the given tokens that are accepted for paying fees.
+    /// The decision of the fee token policy is used if it was made, otherwise
+    /// the token is checked against the minimal market volume.
+    async fn tokens_enabled_for_fees(
         &self,
         storage: &mut StorageProcessor<'_>,
-        token_id: TokenId,
-    ) -> Result<bool, Error> {
-        let result = storage
+        tokens: Vec<TokenId>,
+    ) -> Result<HashSet<TokenId>, Error> {
+        let flags = storage
             .tokens_schema()
-            .filter_tokens_by_market_volume(vec![token_id], &self.min_market_volume)
+            .load_fee_token_flags(&tokens)
             .await
             .map_err(Error::storage)?;
+        let (decided, undecided): (Vec<TokenId>, Vec<TokenId>) =
+            tokens.into_iter().partition(|id| flags.contains_key(id));
+
+        let mut result = storage
+            .tokens_schema()
+            .filter_tokens_by_market_volume(undecided, &self.min_market_volume)
+            .await
+            .map_err(Error::storage)?;
+        result.extend(decided.into_iter().filter(|id| flags[id]));
+        Ok(result)
+    }
+
+    async fn is_token_enabled_for_fees(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_id: TokenId,
+    ) -> Result<bool, Error> {
+        let result = self
+            .tokens_enabled_for_fees(storage, vec![token_id])
+            .await?;
         Ok(!result.is_empty())
     }
 
@@ -87,11 +169,9 @@ impl ApiTokenData {
             Ok(paginated_tokens) => {
                 let tokens_to_check: Vec<TokenId> =
                     paginated_tokens.list.iter().map(|token| token.id).collect();
-                let tokens_enabled_for_fees = storage
-                    .tokens_schema()
-                    .filter_tokens_by_market_volume(tokens_to_check, &self.min_market_volume)
-                    .await
-                    .map_err(Error::storage)?;
+                let tokens_enabled_for_fees = self
+                    .tokens_enabled_for_fees(&mut storage, tokens_to_check)
+                    .await?;
                 let list = paginated_tokens
                     .list
                     .into_iter()
@@ -126,18 +206,8 @@ impl ApiTokenData {
         // from the db.
         let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
 
-        let token = self
-            .tokens
-            .get_token(&mut storage, token_like)
-            .await
-            .map_err(Error::storage)?;
-        if let Some(token) = token {
-            Ok(token)
-        } else {
-            Err(Error::from(PriceError::token_not_found(
-                "Token not found in storage",
-            )))
-        }
+        let token = self.tokens.resolve_token(&mut storage, token_like).await?;
+        Ok(token)
     }
 
     async fn api_token(&self, token_like: TokenLike) -> Result<ApiToken, Error> {
@@ -152,6 +222,237 @@ impl ApiTokenData {
         ))
     }
 
+    /// Returns the outcome of the Ethereum lookup of the token made during the cooldown.
+    fn recent_listing_lookup(&self, address: Address) -> Option<Result<(), InvalidDataError>> {
+        let lookups = self.listing_lookups.lock().unwrap();
+        lookups
+            .get(&address)
+            .filter(|lookup| lookup.made_at.elapsed() < LISTING_LOOKUP_COOLDOWN)
+            .map(|lookup| lookup.result)
+    }
+
+    fn record_listing_lookup(&self, address: Address, result: Result<(), InvalidDataError>) {
+        let mut lookups = self.listing_lookups.lock().unwrap();
+        if lookups.len() >= MAX_LISTING_LOOKUPS {
+            lookups.retain(|_, lookup| lookup.made_at.elapsed() < LISTING_LOOKUP_COOLDOWN);
+        }
+        // Lookups aren't remembered while the cache is full of the recent ones.
+        if lookups.len() < MAX_LISTING_LOOKUPS {
+            let made_at = Instant::now();
+            lookups.insert(address, ListingLookup { made_at, result });
+        }
+    }
+
+    /// Loads the id of the token from the governance contract and its ERC-20 metadata.
+    /// Tokens have to pass the ERC-20 compatibility checks, including the ones stored by the
+    /// server from the `NewToken` events. Outer error is returned if Ethereum node is unavailable.
+    async fn lookup_listed_token(
+        &self,
+        address: Address,
+    ) -> Result<Result<(TokenId, String, u8), InvalidDataError>, Error> {
+        let token_id = match self
+            .eth_checker
+            .registered_token_id(
+                self.governance_addr,
+                address,
+                self.confirmations_for_eth_event,
+            )
+            .await
+            .map_err(Error::from)?
+        {
+            Some(token_id) => token_id,
+            None => return Ok(Err(InvalidDataError::TokenNotRegistered)),
+        };
+        if !self.compatibility_checker.check(address).await.compatible {
+            return Ok(Err(InvalidDataError::IncompatibleToken));
+        }
+        match self.eth_checker.erc20_metadata(address).await {
+            Ok((symbol, decimals)) => Ok(Ok((token_id, symbol, decimals))),
+            Err(err) => {
+                vlog::warn!(
+                    "Failed to load metadata of the token {:?}: {}",
+                    address,
+                    err
+                );
+                Ok(Err(InvalidDataError::InvalidTokenMetadata))
+            }
+        }
+    }
+
+    /// Lists the token added to the governance contract using its ERC-20 metadata.
+    /// Tokens stored with the default symbol get their metadata updated.
+    /// The outcome of the lookup is reused by the requests made during the cooldown.
+    async fn list_token(&self, address: Address) -> Result<ApiToken, Error> {
+        let mut storage = self
+            .main_pool
+            .access_storage()
+            .await
+            .map_err(Error::storage)?;
+        let stored_token = storage
+            .tokens_schema()
+            .get_token(TokenLike::Address(address))
+            .await
+            .map_err(Error::storage)?;
+        let recent_lookup = self.recent_listing_lookup(address);
+        if let Some(token) = stored_token {
+            if token.symbol != default_token_symbol(token.id) || recent_lookup == Some(Ok(())) {
+                let enabled_for_fees = self
+                    .is_token_enabled_for_fees(&mut storage, token.id)
+                    .await?;
+                return Ok(ApiToken::from_token_and_eligibility(
+                    token,
+                    enabled_for_fees,
+                ));
+            }
+        }
+        if let Some(Err(err)) = recent_lookup {
+            return Err(Error::from(err));
+        }
+
+        let lookup = self.lookup_listed_token(address).await?;
+        self.record_listing_lookup(address, lookup.as_ref().map(|_| ()).map_err(|err| *err));
+        let (token_id, symbol, decimals) = lookup.map_err(Error::from)?;
+
+        // Symbol of the token shouldn't be confused with the symbols of the already listed tokens.
+        let symbol = match storage
+            .tokens_schema()
+            .resolve_token(TokenLike::Symbol(symbol.clone()))
+            .await
+        {
+            Err(ResolveTokenError::NotFound) => symbol,
+            Ok(token) if token.id == token_id => symbol,
+            Err(ResolveTokenError::Other(err)) => return Err(Error::storage(err)),
+            _ => default_token_symbol(token_id),
+        };
+        let token = Token::new(token_id, address, &symbol, decimals, TokenKind::ERC20);
+        storage
+            .tokens_schema()
+            .store_or_update_token(token.clone())
+            .await
+            .map_err(Error::storage)?;
+        vlog::info!(
+            "Token {} ({:?}) is listed with id {}",
+            symbol,
+            address,
+            token_id
+        );
+
+        let enabled_for_fees = self
+            .is_token_enabled_for_fees(&mut storage, token_id)
+            .await?;
+        Ok(ApiToken::from_token_and_eligibility(
+            token,
+            enabled_for_fees,
+        ))
+    }
+
+    /// Returns the metadata of the NFT, it's fetched from IPFS if the cached one is outdated.
+    /// The outdated metadata is returned if IPFS gateway is unavailable.
+    async fn nft_metadata(&self, id: TokenId) -> Result<Option<ApiNFTMetadata>, Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let nft = match storage
+            .tokens_schema()
+            .get_nft_with_factories(id)
+            .await
+            .map_err(Error::storage)?
+        {
+            Some(nft) => nft,
+            None => return Ok(None),
+        };
+        let cached = storage
+            .tokens_schema()
+            .load_nft_metadata(id)
+            .await
+            .map_err(Error::storage)?;
+
+        let (metadata, fetched_at) = match cached {
+            Some(cached) if Utc::now() - cached.fetched_at < self.nft_metadata_ttl => {
+                (cached.metadata, cached.fetched_at)
+            }
+            cached => match self.ipfs.fetch_json(&nft.content_hash).await {
+                Ok(metadata) => {
+                    self.main_pool
+                        .access_storage()
+                        .await
+                        .map_err(Error::storage)?
+                        .tokens_schema()
+                        .store_nft_metadata(id, &metadata)
+                        .await
+                        .map_err(Error::storage)?;
+                    (metadata, Utc::now())
+                }
+                Err(err) => {
+                    vlog::warn!("Failed to fetch metadata of the NFT {}: {}", id, err);
+                    let cached = cached
+                        .ok_or_else(|| Error::from(InvalidDataError::NFTMetadataUnavailable))?;
+                    (cached.metadata, cached.fetched_at)
+                }
+            },
+        };
+
+        Ok(Some(ApiNFTMetadata {
+            id: nft.id,
+            content_hash: nft.content_hash,
+            creator_id: nft.creator_id,
+            creator_address: nft.creator_address,
+            serial_id: nft.serial_id,
+            metadata,
+            fetched_at,
+        }))
+    }
+
+    async fn fee_token_decisions(
+        &self,
+        token_like: TokenLike,
+    ) -> Result<Vec<ApiFeeTokenDecision>, Error> {
+        let token = self.token(token_like).await?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let decisions = storage
+            .tokens_schema()
+            .load_fee_token_decisions(token.id, MAX_FEE_TOKEN_DECISIONS)
+            .await
+            .map_err(Error::storage)?;
+        Ok(decisions
+            .into_iter()
+            .map(|decision| ApiFeeTokenDecision {
+                token_id: decision.token_id,
+                is_fee_token: decision.is_fee_token,
+                market_volume: ratio_to_big_decimal(&decision.market_volume, 2),
+                created_at: decision.created_at,
+            })
+            .collect())
+    }
+
+    async fn token_price_history(
+        &self,
+        token_like: TokenLike,
+        query: TokenPriceHistoryQuery,
+    ) -> Result<Vec<TokenPriceHistoryEntry>, Error> {
+        if query.from > query.to {
+            return Err(Error::from(InvalidDataError::InvalidTimeRange));
+        }
+
+        let token = self.token(token_like).await?;
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let prices = storage
+            .tokens_schema()
+            .load_token_price_history(
+                token.id,
+                query.from,
+                query.to,
+                MAX_TOKEN_PRICE_HISTORY_ENTRIES,
+            )
+            .await
+            .map_err(Error::storage)?;
+        Ok(prices
+            .into_iter()
+            .map(|price| TokenPriceHistoryEntry {
+                usd_price: price.usd_price,
+                last_updated: price.last_updated,
+            })
+            .collect())
+    }
+
     async fn token_price_usd(&self, token: TokenLike) -> Result<BigDecimal, Error> {
         self.fee_ticker
             .get_token_price(token, TokenPriceRequestType::USDForOneToken)
@@ -186,6 +487,60 @@ impl ApiTokenData {
             }
         }
     }
+
+    /// Loads the stored USD price of the token along with its source.
+    async fn usd_price_with_source(
+        &self,
+        token: &Token,
+    ) -> Result<(Ratio<BigUint>, TokenPriceSourceInfo), Error> {
+        let mut storage = self.pool.access_storage().await.map_err(Error::storage)?;
+        let (price, source) = storage
+            .tokens_schema()
+            .get_ticker_price_with_source(token.id)
+            .await
+            .map_err(Error::storage)?
+            .ok_or_else(|| Error::from(InvalidDataError::TokenPriceUnavailable))?;
+        let source = TokenPriceSourceInfo {
+            token_id: token.id,
+            source,
+            last_updated: price.last_updated,
+        };
+        Ok((price.usd_price, source))
+    }
+
+    async fn token_price_with_sources(
+        &self,
+        token_like: TokenLike,
+        currency: &str,
+    ) -> Result<SourcedTokenPrice, Error> {
+        let token = self.token(token_like).await?;
+        let (usd_price, source) = self.usd_price_with_source(&token).await?;
+
+        let (price, sources) = if currency == "usd" {
+            (usd_price, vec![source])
+        } else if let Ok(second_token_id) = u32::from_str(currency) {
+            let second_token = self.token(TokenId(second_token_id).into()).await?;
+            let (second_usd_price, second_source) =
+                self.usd_price_with_source(&second_token).await?;
+            if second_usd_price.is_zero() {
+                return Err(Error::from(InvalidDataError::TokenZeroPriceError));
+            }
+            (usd_price / second_usd_price, vec![source, second_source])
+        } else {
+            return Err(Error::from(InvalidDataError::InvalidCurrency));
+        };
+
+        Ok(SourcedTokenPrice {
+            price: TokenPrice {
+                token_id: token.id,
+                token_symbol: token.symbol,
+                price_in: currency.to_string(),
+                decimals: token.decimals,
+                price: ratio_to_big_decimal(&price, PRICE_PRECISION),
+            },
+            sources,
+        })
+    }
 }
 
 // Server implementation
@@ -212,6 +567,49 @@ async fn token_info(
     res
 }
 
+async fn list_token(
+    data: web::Data<ApiTokenData>,
+    web::Json(request): web::Json<TokenListingRequest>,
+) -> ApiResult<ApiToken> {
+    let start = Instant::now();
+    let res = data.list_token(request.address).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "list_token");
+    res
+}
+
+async fn token_compatibility(
+    data: web::Data<ApiTokenData>,
+    address: web::Path<Address>,
+) -> ApiResult<TokenCompatibilityReport> {
+    let start = Instant::now();
+    let report = data.compatibility_checker.check(address.into_inner()).await;
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_compatibility");
+    ApiResult::Ok(report)
+}
+
+async fn token_price_history(
+    data: web::Data<ApiTokenData>,
+    token_like_string: web::Path<String>,
+    web::Query(query): web::Query<TokenPriceHistoryQuery>,
+) -> ApiResult<Vec<TokenPriceHistoryEntry>> {
+    let start = Instant::now();
+    let token_like = TokenLike::parse(&token_like_string);
+    let res = data.token_price_history(token_like, query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_price_history");
+    res
+}
+
+async fn token_fee_decisions(
+    data: web::Data<ApiTokenData>,
+    token_like_string: web::Path<String>,
+) -> ApiResult<Vec<ApiFeeTokenDecision>> {
+    let start = Instant::now();
+    let token_like = TokenLike::parse(&token_like_string);
+    let res = data.fee_token_decisions(token_like).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_fee_decisions");
+    res
+}
+
 // TODO: take `currency` as enum.
 // Currently actix path extractor doesn't work with enums: https://github.com/actix/actix-web/issues/318 (ZKS-628)
 async fn token_price(
@@ -235,6 +633,21 @@ async fn token_price(
     })
 }
 
+async fn token_price_with_sources(
+    data: web::Data<ApiTokenData>,
+    path: web::Path<(String, String)>,
+) -> ApiResult<SourcedTokenPrice> {
+    let start = Instant::now();
+    let (token_like_string, currency) = path.into_inner();
+    let token_like = TokenLike::parse(&token_like_string);
+    let res = data
+        .token_price_with_sources(token_like, &currency)
+        .await
+        .into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "token_price_with_sources");
+    res
+}
+
 async fn get_nft(
     data: web::Data<ApiTokenData>,
     id: web::Path<TokenId>,
@@ -253,6 +666,19 @@ async fn get_nft(
     ApiResult::Ok(nft)
 }
 
+async fn get_nft_metadata(
+    data: web::Data<ApiTokenData>,
+    id: web::Path<TokenId>,
+) -> ApiResult<Option<ApiNFTMetadata>> {
+    let start = Instant::now();
+    if id.0 < MIN_NFT_TOKEN_ID {
+        return Error::from(InvalidDataError::InvalidNFTTokenId).into();
+    }
+    let res = data.nft_metadata(*id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft_metadata");
+    res
+}
+
 async fn get_nft_owner(
     data: web::Data<ApiTokenData>,
     id: web::Path<TokenId>,
@@ -274,14 +700,15 @@ async fn get_nft_owner(
 
 async fn get_nft_id_by_tx_hash(
     data: web::Data<ApiTokenData>,
-    tx_hash: web::Path<TxHash>,
+    tx_hash: web::Path<String>,
 ) -> ApiResult<Option<TokenId>> {
     let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
     let mut storage = api_try!(data.pool.access_storage().await.map_err(Error::storage));
     let nft_id = api_try!(storage
         .chain()
         .state_schema()
-        .get_nft_id_by_tx_hash(*tx_hash)
+        .get_nft_id_by_tx_hash(tx_hash)
         .await
         .map_err(Error::storage));
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_nft_id_by_tx_hash");
@@ -291,21 +718,41 @@ async fn get_nft_id_by_tx_hash(
 pub fn api_scope(
     config: &ZkSyncConfig,
     pool: ConnectionPool,
+    main_pool: ConnectionPool,
     tokens_db: TokenDBCache,
     fee_ticker: FeeTicker,
+    eth_checker: EthereumChecker,
 ) -> Scope {
-    let data = ApiTokenData::new(config, pool, tokens_db, fee_ticker);
+    let data = ApiTokenData::new(config, pool, main_pool, tokens_db, fee_ticker, eth_checker);
 
     web::scope("tokens")
         .app_data(web::Data::new(data))
         .route("", web::get().to(token_pagination))
+        .route("", web::post().to(list_token))
         .route("{token_like}", web::get().to(token_info))
         .route(
             "{token_like}/priceIn/{currency}",
             web::get().to(token_price),
         )
+        .route(
+            "{token_like}/priceIn/{currency}/sources",
+            web::get().to(token_price_with_sources),
+        )
+        .route(
+            "{address}/compatibility",
+            web::get().to(token_compatibility),
+        )
+        .route(
+            "{token_like}/price_history",
+            web::get().to(token_price_history),
+        )
+        .route(
+            "{token_like}/fee_decisions",
+            web::get().to(token_fee_decisions),
+        )
         .route("nft/{id}", web::get().to(get_nft))
         .route("nft/{id}/owner", web::get().to(get_nft_owner))
+        .route("nft/{id}/metadata", web::get().to(get_nft_metadata))
         .route(
             "nft_id_by_tx_hash/{tx_hash}",
             web::get().to(get_nft_id_by_tx_hash),
@@ -316,11 +763,13 @@ pub fn api_scope(
 mod tests {
     use super::*;
     use crate::api_server::rest::v02::{
+        error::ErrorCode,
         test_utils::{deserialize_response_result, dummy_fee_ticker, TestServerConfig},
         SharedData,
     };
     use zksync_api_types::v02::{pagination::PaginationDirection, ApiVersion};
-    use zksync_types::{Address, BlockNumber, ZkSyncTx};
+    use zksync_eth_client::{clients::mock::MockEthereum, EthereumGateway};
+    use zksync_types::{tx::TxHash, Address, BlockNumber, ZkSyncTx};
 
     async fn is_token_enabled_for_fees(
         storage: &mut StorageProcessor<'_>,
@@ -365,8 +814,10 @@ mod tests {
                 api_scope(
                     &cfg.config,
                     cfg.pool.clone(),
+                    cfg.pool.clone(),
                     TokenDBCache::new(cfg.config.api.token_config.invalidate_token_cache_period()),
                     fee_ticker.clone(),
+                    EthereumChecker::new(EthereumGateway::Mock(MockEthereum::default())),
                 )
             },
             Some(shared_data),
@@ -456,11 +907,137 @@ mod tests {
         let response = client.token_price(&token_like, "333").await?;
         assert!(response.error.is_some());
 
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .update_fee_token_flag(token.id, false, &Ratio::from_integer(BigUint::from(5u32)))
+                .await?;
+        }
+        let now = Utc::now();
+        let stored_price = zksync_types::TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(10u32)),
+            last_updated: now,
+        };
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .store_token_price_history(token.id, &stored_price)
+                .await?;
+        }
+        let response = client
+            .token_price_history(
+                &token_like,
+                now - chrono::Duration::minutes(1),
+                now + chrono::Duration::minutes(1),
+            )
+            .await?;
+        let history: Vec<TokenPriceHistoryEntry> = deserialize_response_result(response)?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].usd_price, stored_price.usd_price);
+
+        let response = client
+            .token_price_history(&token_like, now, now - chrono::Duration::minutes(1))
+            .await?;
+        assert!(response.error.is_some());
+
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .update_historical_ticker_price(token.id, stored_price.clone(), Some("CoinGecko"))
+                .await?;
+        }
+        let response = client.token_price_with_sources(&token_like, "usd").await?;
+        let sourced_price: SourcedTokenPrice = deserialize_response_result(response)?;
+        assert_eq!(sourced_price.price, expected_token_price);
+        assert_eq!(sourced_price.sources.len(), 1);
+        assert_eq!(sourced_price.sources[0].token_id, token.id);
+        assert_eq!(
+            sourced_price.sources[0].source.as_deref(),
+            Some("CoinGecko")
+        );
+
+        let response = client.token_price_with_sources(&token_like, "eur").await?;
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::InvalidCurrency);
+
+        let response = client.token_fee_decisions(&token_like).await?;
+        let decisions: Vec<ApiFeeTokenDecision> = deserialize_response_result(response)?;
+        assert_eq!(decisions[0].token_id, token.id);
+        assert!(!decisions[0].is_fee_token);
+        assert_eq!(decisions[0].market_volume, BigDecimal::from(5));
+
+        // The decision of the fee token policy takes precedence over the market volume.
+        let response = client.token_by_id(&token_like).await?;
+        let api_token: ApiToken = deserialize_response_result(response)?;
+        assert!(!api_token.enabled_for_fees);
+
+        // Symbols matching several tokens can't be used to resolve the token.
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            for &(id, symbol) in &[(1000u32, "AMBIG"), (1001u32, "Ambig")] {
+                storage
+                    .tokens_schema()
+                    .store_or_update_token(Token::new(
+                        TokenId(id),
+                        Address::from_low_u64_be(id as u64),
+                        symbol,
+                        18,
+                        zksync_types::TokenKind::ERC20,
+                    ))
+                    .await?;
+            }
+        }
+        let response = client
+            .token_by_id(&TokenLike::Symbol(String::from("ambig")))
+            .await?;
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::AmbiguousTokenSymbol);
+
+        let response = client.token_by_id(&TokenLike::Id(TokenId(1001))).await?;
+        let api_token: ApiToken = deserialize_response_result(response)?;
+        assert_eq!(api_token.symbol, "Ambig");
+
+        // Mock client reports that there is no contract at any address.
+        let response = client
+            .token_compatibility(Address::repeat_byte(0x11))
+            .await?;
+        let report: TokenCompatibilityReport = deserialize_response_result(response)?;
+        assert!(!report.compatible);
+        assert_eq!(report.checks.len(), 1);
+        assert_eq!(
+            report.checks[0].status,
+            zksync_api_types::v02::token::CompatibilityCheckStatus::Failed
+        );
+
+        // Listing of the already listed token returns it without querying the contracts.
+        let response = client.list_token(expected_api_token.address).await?;
+        let listed_token: ApiToken = deserialize_response_result(response)?;
+        assert_eq!(listed_token.id, expected_api_token.id);
+        assert_eq!(listed_token.symbol, expected_api_token.symbol);
+
         let nft_id = TokenId(65542);
         let response = client.nft_by_id(nft_id).await?;
         let nft: ApiNFT = deserialize_response_result(response)?;
         assert_eq!(nft.id, nft_id);
 
+        // Fresh metadata is served from the cache without querying IPFS.
+        let metadata = serde_json::json!({ "name": "Test NFT" });
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            storage
+                .tokens_schema()
+                .store_nft_metadata(nft_id, &metadata)
+                .await?;
+        }
+        let response = client.nft_metadata(nft_id).await?;
+        let nft_metadata: ApiNFTMetadata = deserialize_response_result(response)?;
+        assert_eq!(nft_metadata.id, nft_id);
+        assert_eq!(nft_metadata.content_hash, nft.content_hash);
+        assert_eq!(nft_metadata.metadata, metadata);
+
         let response = client.nft_owner_by_id(nft_id).await?;
         let owner_id: AccountId = deserialize_response_result(response)?;
         let expected_owner_id = {
@@ -1,37 +1,69 @@
 //! Transactions part of API implementation.
 
 // Built-in uses
-use std::time::Instant;
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    net::IpAddr,
+    time::{Duration, Instant},
+};
 // External uses
 use actix_web::{
     web::{self, Json},
-    Scope,
+    HttpRequest, Scope,
 };
+use tokio::sync::broadcast::error::RecvError;
 
 // Workspace uses
 use zksync_api_types::{
     v02::transaction::{
-        ApiTxBatch, IncomingTxBatch, L1Receipt, L1Transaction, Receipt, SubmitBatchResponse,
-        Toggle2FA, Toggle2FAResponse, Transaction, TransactionData, TxData, TxHashSerializeWrapper,
-        TxInBlockStatus,
+        ApiTxBatch, CancelTx, CancelTxResponse, DecodedTx, IncomingTxBatch, L1Receipt,
+        L1Transaction, Receipt, SubmitBatchResponse, SubmitTxResponse, Toggle2FA,
+        Toggle2FAResponse, Transaction, TransactionData, TxBytes, TxData, TxHashSerializeWrapper,
+        TxInBlockStatus, TxInclusionProof, TxL1Commit, TxRawData, TxSignatureCheck,
+        TxStatusesRequest, TxStatusesResponse, TxToDecode, TxWaitQuery, MAX_TX_STATUSES_BATCH_SIZE,
+        MAX_TX_WAIT_TIMEOUT_SECS, REQUEST_ID_HEADER,
     },
     TxWithSignature,
 };
-use zksync_types::{tx::TxHash, EthBlockId};
+use zksync_crypto::{convert::FeConvert, Fr};
+use zksync_types::{
+    aggregated_operations::{AggregatedActionType, AggregatedOperation},
+    block::Block,
+    tx::TxHash,
+    EthBlockId, SerialId, ZkSyncTx,
+};
 
 // Local uses
-use super::{error::Error, response::ApiResult};
-use crate::api_server::tx_sender::{SubmitError, TxSender};
+use super::{
+    error::{Error, InvalidDataError, TxError},
+    hash::parse_tx_hash,
+    response::ApiResult,
+    tx_status_events::TxStatusEvents,
+};
+use crate::{
+    api_server::{
+        rpc_server::{
+            ip_insert_middleware::CLOUDFLARE_CONNECTING_IP_HEADER, types::RequestMetadata,
+        },
+        tx_sender::{SubmitError, TxSender},
+    },
+    api_try,
+};
 
 /// Shared data between `api/v0.2/transactions` endpoints.
 #[derive(Clone)]
 struct ApiTransactionData {
     tx_sender: TxSender,
+    status_events: TxStatusEvents,
 }
 
 impl ApiTransactionData {
-    fn new(tx_sender: TxSender) -> Self {
-        Self { tx_sender }
+    fn new(tx_sender: TxSender, status_events: TxStatusEvents) -> Self {
+        Self {
+            tx_sender,
+            status_events,
+        }
     }
 
     async fn tx_status(&self, tx_hash: TxHash) -> Result<Option<Receipt>, Error> {
@@ -41,7 +73,7 @@ impl ApiTransactionData {
             .pool
             .access_storage()
             .await
-            .map_err(Error::storage)?;
+            .map_err(TxError::storage_unavailable)?;
 
         // 1. Try to find the already received/executed operation.
         if let Some(receipt) = storage
@@ -49,23 +81,110 @@ impl ApiTransactionData {
             .operations_ext_schema()
             .tx_receipt_api_v02(tx_hash.as_ref())
             .await
-            .map_err(Error::storage)?
+            .map_err(TxError::storage_unavailable)?
         {
             Ok(Some(receipt))
         }
-        // 2. Try to find the pending operation.
+        // 2. Try to find the pending operation by its Ethereum transaction hash.
         else if let Some(op) = storage
             .chain()
             .mempool_schema()
             .get_pending_operation_by_hash(tx_hash.into())
             .await
-            .map_err(Error::core_api)?
+            .map_err(TxError::storage_unavailable)?
         {
+            let last_eth_block = storage
+                .chain()
+                .mempool_schema()
+                .get_last_watched_eth_block()
+                .await
+                .map_err(TxError::storage_unavailable)?;
             Ok(Some(Receipt::L1(L1Receipt {
                 status: TxInBlockStatus::Queued,
                 eth_block: EthBlockId(op.eth_block),
                 rollup_block: None,
+                block_index: None,
                 id: op.serial_id,
+                created_at: None,
+                committed_at: None,
+                finalized_at: None,
+                estimated_finalization: None,
+                confirmations: last_eth_block
+                    .map(|last_eth_block| last_eth_block.saturating_sub(op.eth_block)),
+            })))
+        }
+        // 3. Try to find the swap that filled the order with the given hash.
+        else if let Some(swap_hash) = storage
+            .chain()
+            .operations_ext_schema()
+            .get_swap_tx_hash_by_order_hash(tx_hash.as_ref())
+            .await
+            .map_err(TxError::storage_unavailable)?
+        {
+            storage
+                .chain()
+                .operations_ext_schema()
+                .tx_receipt_api_v02(swap_hash.as_ref())
+                .await
+                .map_err(TxError::storage_unavailable)
+        }
+        // 4. No operation found, return nothing.
+        else {
+            Ok(None)
+        }
+    }
+
+    async fn priority_op_status(&self, serial_id: SerialId) -> Result<Option<Receipt>, Error> {
+        // Executed operations are looked up by `u32` id.
+        let executed_op_id = match u32::try_from(serial_id) {
+            Ok(id) => id,
+            Err(_) => return Ok(None),
+        };
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+
+        // 1. Try to find the executed operation.
+        if let Some(op) = storage
+            .chain()
+            .operations_schema()
+            .get_executed_priority_operation(executed_op_id)
+            .await
+            .map_err(TxError::storage_unavailable)?
+        {
+            drop(storage);
+            self.tx_status(TxHash::from_slice(&op.tx_hash).unwrap())
+                .await
+        }
+        // 2. Try to find the pending operation.
+        else if let Some(op) = storage
+            .chain()
+            .mempool_schema()
+            .get_pending_operation_by_serial_id(serial_id)
+            .await
+            .map_err(TxError::storage_unavailable)?
+        {
+            let last_eth_block = storage
+                .chain()
+                .mempool_schema()
+                .get_last_watched_eth_block()
+                .await
+                .map_err(TxError::storage_unavailable)?;
+            Ok(Some(Receipt::L1(L1Receipt {
+                status: TxInBlockStatus::Queued,
+                eth_block: EthBlockId(op.eth_block),
+                rollup_block: None,
+                block_index: None,
+                id: op.serial_id,
+                created_at: None,
+                committed_at: None,
+                finalized_at: None,
+                estimated_finalization: None,
+                confirmations: last_eth_block
+                    .map(|last_eth_block| last_eth_block.saturating_sub(op.eth_block)),
             })))
         }
         // 3. No operation found, return nothing.
@@ -74,19 +193,82 @@ impl ApiTransactionData {
         }
     }
 
+    /// Waits until the transaction reaches the requested status or the timeout
+    /// elapses, and returns the latest known receipt.
+    async fn wait_tx_status(
+        &self,
+        tx_hash: TxHash,
+        query: TxWaitQuery,
+    ) -> Result<Option<Receipt>, Error> {
+        let timeout = query
+            .timeout
+            .unwrap_or(MAX_TX_WAIT_TIMEOUT_SECS)
+            .min(MAX_TX_WAIT_TIMEOUT_SECS);
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(timeout);
+        // Subscribe before the first check, so the changes made right after it are not missed.
+        let mut events = self.status_events.subscribe();
+
+        loop {
+            let receipt = self.tx_status(tx_hash).await?;
+            let status_reached = receipt
+                .as_ref()
+                .map(|receipt| receipt.status().has_reached(query.status))
+                .unwrap_or(false);
+            if status_reached {
+                return Ok(receipt);
+            }
+
+            // The status is only rechecked when it may have changed.
+            loop {
+                match tokio::time::timeout_at(deadline, events.recv()).await {
+                    Ok(Ok(event)) if !event.affects(tx_hash) => continue,
+                    Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => break,
+                    Ok(Err(RecvError::Closed)) | Err(_) => return Ok(receipt),
+                }
+            }
+        }
+    }
+
+    async fn tx_statuses(&self, tx_hashes: Vec<TxHash>) -> Result<TxStatusesResponse, Error> {
+        if tx_hashes.len() > MAX_TX_STATUSES_BATCH_SIZE {
+            return Err(Error::from(InvalidDataError::TooManyTxHashes));
+        }
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+        let mut receipts = storage
+            .chain()
+            .operations_ext_schema()
+            .tx_receipts_api_v02(&tx_hashes)
+            .await
+            .map_err(TxError::storage_unavailable)?;
+
+        let mut statuses = TxStatusesResponse::with_capacity(tx_hashes.len());
+        for tx_hash in tx_hashes {
+            statuses
+                .entry(TxHashSerializeWrapper(tx_hash))
+                .or_insert_with(|| receipts.remove(&tx_hash));
+        }
+        Ok(statuses)
+    }
+
     async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<TxData>, Error> {
         let mut storage = self
             .tx_sender
             .pool
             .access_storage()
             .await
-            .map_err(Error::storage)?;
+            .map_err(TxError::storage_unavailable)?;
         if let Some(data) = storage
             .chain()
             .operations_ext_schema()
             .tx_data_api_v02(tx_hash.as_ref())
             .await
-            .map_err(Error::storage)?
+            .map_err(TxError::storage_unavailable)?
         {
             Ok(Some(data))
         } else if let Some(op) = storage
@@ -94,7 +276,7 @@ impl ApiTransactionData {
             .mempool_schema()
             .get_pending_operation_by_hash(tx_hash.into())
             .await
-            .map_err(Error::core_api)?
+            .map_err(TxError::storage_unavailable)?
         {
             let tx_hash = op.tx_hash();
             let tx = Transaction {
@@ -111,30 +293,222 @@ impl ApiTransactionData {
                 fail_reason: None,
                 created_at: None,
                 batch_id: None,
+                time_range: None,
             };
 
             Ok(Some(TxData {
                 tx,
                 eth_signature: None,
+                eth_sign_payload: None,
+                batch: None,
+                nft: None,
             }))
         } else {
             Ok(None)
         }
     }
 
+    async fn tx_raw_data(&self, tx_hash: TxHash) -> Result<TxRawData, Error> {
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+        let signed_tx = storage
+            .chain()
+            .operations_ext_schema()
+            .get_signed_tx(tx_hash.as_ref())
+            .await
+            .map_err(TxError::storage_unavailable)?
+            .ok_or_else(|| Error::from(TxError::TxNotFound))?;
+
+        Ok(TxRawData {
+            tx_hash,
+            tx_bytes: signed_tx.tx.get_bytes(),
+            eth_signature: signed_tx
+                .eth_sign_data
+                .map(|eth_sign_data| eth_sign_data.signature),
+        })
+    }
+
+    async fn tx_proof(&self, tx_hash: TxHash) -> Result<Option<TxInclusionProof>, Error> {
+        // Only operations successfully executed in a block have public data.
+        let receipt = match self.tx_status(tx_hash).await? {
+            Some(receipt) if receipt.status() != TxInBlockStatus::Rejected => receipt,
+            _ => return Ok(None),
+        };
+        let (block_number, block_index) = match (receipt.rollup_block(), receipt.block_index()) {
+            (Some(block_number), Some(block_index)) => (block_number, block_index),
+            _ => return Ok(None),
+        };
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+        // The block is not created until it is sealed.
+        let block = match transaction
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await
+            .map_err(TxError::storage_unavailable)?
+        {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let previous_block = transaction
+            .chain()
+            .block_schema()
+            .get_storage_block(block_number - 1)
+            .await
+            .map_err(TxError::storage_unavailable)?
+            .ok_or_else(|| {
+                Error::from(TxError::StorageUnavailable(format!(
+                    "previous block {} is missing",
+                    *block_number - 1
+                )))
+            })?;
+        transaction
+            .commit()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+
+        let (public_data_offset, op_public_data) = match block.get_op_public_data(block_index) {
+            Some(op_public_data) => op_public_data,
+            None => return Ok(None),
+        };
+        let previous_root_hash = Fr::from_bytes(&previous_block.root_hash).unwrap_or_else(|err| {
+            panic!(
+                "Database provided an incorrect root_hash field: {:?}, an error occurred {}",
+                previous_block.root_hash, err
+            )
+        });
+
+        Ok(Some(TxInclusionProof {
+            block_number,
+            block_index,
+            fee_account: block.fee_account,
+            previous_root_hash: Block::encode_fr_for_eth(previous_root_hash),
+            new_root_hash: block.get_eth_encoded_root(),
+            timestamp: block.timestamp,
+            public_data: block.get_eth_public_data(),
+            onchain_op_commitment: block.get_onchain_op_commitment(),
+            public_data_offset: public_data_offset as u32,
+            op_public_data,
+            block_commitment: block.block_commitment,
+        }))
+    }
+
+    async fn tx_l1_commit(&self, tx_hash: TxHash) -> Result<Option<TxL1Commit>, Error> {
+        let receipt = match self.tx_status(tx_hash).await? {
+            Some(receipt) if receipt.status() != TxInBlockStatus::Rejected => receipt,
+            _ => return Ok(None),
+        };
+        let (block_number, block_index) = match (receipt.rollup_block(), receipt.block_index()) {
+            (Some(block_number), Some(block_index)) => (block_number, block_index),
+            _ => return Ok(None),
+        };
+
+        let mut storage = self
+            .tx_sender
+            .pool
+            .access_storage()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+        let (commit_op_id, commit_op) = match transaction
+            .chain()
+            .operations_schema()
+            .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+            .await
+            .map_err(TxError::storage_unavailable)?
+        {
+            Some((id, AggregatedOperation::CommitBlocks(commit_op))) => (id, commit_op),
+            _ => return Ok(None),
+        };
+        // The hash is only known once the commit transaction is confirmed.
+        let commit_tx_hash = match transaction
+            .ethereum_schema()
+            .aggregated_op_final_hash_by_id(commit_op_id)
+            .await
+            .map_err(TxError::storage_unavailable)?
+        {
+            Some(commit_tx_hash) => commit_tx_hash,
+            None => return Ok(None),
+        };
+        transaction
+            .commit()
+            .await
+            .map_err(TxError::storage_unavailable)?;
+
+        let op_public_data = commit_op
+            .blocks
+            .iter()
+            .find(|block| block.block_number == block_number)
+            .and_then(|block| block.get_op_public_data(block_index));
+        let block_public_data_offset = commit_op.public_data_calldata_offset(block_number);
+        match (op_public_data, block_public_data_offset) {
+            (Some((public_data_offset, op_public_data)), Some(block_public_data_offset)) => {
+                Ok(Some(TxL1Commit {
+                    commit_tx_hash,
+                    block_number,
+                    block_index,
+                    calldata_offset: (block_public_data_offset + public_data_offset) as u32,
+                    public_data_length: op_public_data.len() as u32,
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn decode_tx(&self, tx: TxToDecode) -> Result<DecodedTx, SubmitError> {
+        let tx = match tx {
+            TxToDecode::Tx(tx) => tx,
+            TxToDecode::Bytes(TxBytes { tx_bytes }) => ZkSyncTx::from_bytes(&tx_bytes)
+                .map_err(|err| SubmitError::InvalidParams(err.to_string()))?,
+        };
+        // Unsigned and malformed transactions are decoded as well, the check only reports them.
+        let error = tx
+            .clone()
+            .check_correctness()
+            .err()
+            .map(|err| err.to_string());
+        let token = self.tx_sender.token_info_from_id(tx.token_id()).await?;
+
+        Ok(DecodedTx {
+            tx_hash: tx.hash(),
+            tx_bytes: tx.get_bytes(),
+            eth_sign_message: tx.get_ethereum_sign_message(token),
+            tx,
+            valid: error.is_none(),
+            error,
+        })
+    }
+
     async fn get_batch(&self, batch_hash: TxHash) -> Result<Option<ApiTxBatch>, Error> {
         let mut storage = self
             .tx_sender
             .pool
             .access_storage()
             .await
-            .map_err(Error::storage)?;
+            .map_err(TxError::storage_unavailable)?;
         storage
             .chain()
             .operations_ext_schema()
             .get_batch_info(batch_hash)
             .await
-            .map_err(Error::storage)
+            .map_err(TxError::storage_unavailable)
     }
 }
 
@@ -142,35 +516,156 @@ impl ApiTransactionData {
 
 async fn tx_status(
     data: web::Data<ApiTransactionData>,
-    tx_hash: web::Path<TxHash>,
-) -> ApiResult<Option<Receipt>> {
+    tx_hash: web::Path<String>,
+) -> ApiResult<Receipt> {
     let start = Instant::now();
-    let res = data.tx_status(*tx_hash).await.into();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let res = data
+        .tx_status(tx_hash)
+        .await
+        .and_then(|receipt| receipt.ok_or_else(|| Error::from(TxError::TxNotFound)))
+        .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_status");
     res
 }
 
+async fn priority_op_status(
+    data: web::Data<ApiTransactionData>,
+    serial_id: web::Path<SerialId>,
+) -> ApiResult<Option<Receipt>> {
+    let start = Instant::now();
+    let res = data.priority_op_status(*serial_id).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "priority_op_status");
+    res
+}
+
+async fn wait_tx_status(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<String>,
+    web::Query(query): web::Query<TxWaitQuery>,
+) -> ApiResult<Option<Receipt>> {
+    let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let res = data.wait_tx_status(tx_hash, query).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "wait_tx_status");
+    res
+}
+
+async fn tx_statuses(
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<TxStatusesRequest>,
+) -> ApiResult<TxStatusesResponse> {
+    let start = Instant::now();
+    let res = data.tx_statuses(body.tx_hashes).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_statuses");
+    res
+}
+
 async fn tx_data(
     data: web::Data<ApiTransactionData>,
-    tx_hash: web::Path<TxHash>,
-) -> ApiResult<Option<TxData>> {
+    tx_hash: web::Path<String>,
+) -> ApiResult<TxData> {
     let start = Instant::now();
-    let res = data.tx_data(*tx_hash).await.into();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let res = data
+        .tx_data(tx_hash)
+        .await
+        .and_then(|tx_data| tx_data.ok_or_else(|| Error::from(TxError::TxNotFound)))
+        .into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_data");
     res
 }
 
+async fn tx_raw_data(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<String>,
+) -> ApiResult<TxRawData> {
+    let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let res = data.tx_raw_data(tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_raw_data");
+    res
+}
+
+async fn tx_proof(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<String>,
+) -> ApiResult<Option<TxInclusionProof>> {
+    let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let res = data.tx_proof(tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_proof");
+    res
+}
+
+async fn tx_l1_commit(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<String>,
+) -> ApiResult<Option<TxL1Commit>> {
+    let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let res = data.tx_l1_commit(tx_hash).await.into();
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "tx_l1_commit");
+    res
+}
+
+/// Extracts the IP of the user. The header set by Cloudflare is taken only from the trusted proxies,
+/// since anyone else could put an arbitrary IP there, the IP of the peer is used otherwise.
+fn request_metadata(
+    req: &HttpRequest,
+    trusted_proxies: &HashSet<IpAddr>,
+) -> Option<RequestMetadata> {
+    let peer_ip = req.peer_addr().map(|addr| addr.ip());
+    let forwarded_ip = req
+        .headers()
+        .get(CLOUDFLARE_CONNECTING_IP_HEADER)
+        .and_then(|ip| ip.to_str().ok());
+    user_ip(peer_ip, forwarded_ip, trusted_proxies).map(|ip| RequestMetadata { ip })
+}
+
+/// If no trusted proxies are configured, the server is expected to be reachable only
+/// via Cloudflare, so the header is taken from any peer, as the JSON RPC API does.
+fn user_ip(
+    peer_ip: Option<IpAddr>,
+    forwarded_ip: Option<&str>,
+    trusted_proxies: &HashSet<IpAddr>,
+) -> Option<String> {
+    let trusted = trusted_proxies.is_empty()
+        || peer_ip.map_or(false, |peer_ip| trusted_proxies.contains(&peer_ip));
+    match forwarded_ip.filter(|_| trusted) {
+        Some(ip) => Some(ip.to_owned()),
+        None => peer_ip.map(|ip| ip.to_string()),
+    }
+}
+
 async fn submit_tx(
+    req: HttpRequest,
     data: web::Data<ApiTransactionData>,
     Json(body): Json<TxWithSignature>,
-) -> ApiResult<TxHashSerializeWrapper> {
+) -> ApiResult<SubmitTxResponse> {
     let start = Instant::now();
-    let tx_hash = data
-        .tx_sender
-        .submit_tx(body.tx, body.signature, None)
-        .await;
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .map(|value| value.to_str().map(str::to_owned));
+    let metadata = request_metadata(&req, &data.tx_sender.trusted_proxies);
+    let response = match request_id {
+        Some(Ok(request_id)) => {
+            data.tx_sender
+                .submit_tx_with_request_id(request_id, body.tx, body.signature, metadata)
+                .await
+        }
+        Some(Err(_)) => Err(SubmitError::invalid_params(
+            "request ID must contain only visible ASCII characters",
+        )),
+        None => {
+            data.tx_sender
+                .submit_tx(body.tx, body.signature, metadata)
+                .await
+        }
+    };
 
-    if let Err(err) = &tx_hash {
+    if let Err(err) = &response {
         let err_label = match err {
             SubmitError::IncorrectTx(err) => err.clone(),
             SubmitError::TxAdd(err) => err.to_string(),
@@ -180,19 +675,121 @@ async fn submit_tx(
         metrics::increment_counter!("rejected_txs", &labels);
     }
 
-    let tx_hash = tx_hash.map_err(Error::from);
+    let response = response.map_err(TxError::submit_rejected);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_tx");
+    response.into()
+}
+
+/// Accepts the transaction after the cheap checks, the rest of the verification is completed
+/// asynchronously and its progress and failure are reported by the receipt of the transaction.
+async fn submit_tx_async(
+    req: HttpRequest,
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<TxWithSignature>,
+) -> ApiResult<TxHashSerializeWrapper> {
+    let start = Instant::now();
+    let tx_hash = data
+        .tx_sender
+        .submit_tx_async(
+            body.tx,
+            body.signature,
+            request_metadata(&req, &data.tx_sender.trusted_proxies),
+        )
+        .await
+        .map_err(TxError::submit_rejected);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_tx_async");
+    tx_hash.map(TxHashSerializeWrapper).into()
+}
+
+async fn validate_tx(
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<TxWithSignature>,
+) -> ApiResult<TxHashSerializeWrapper> {
+    let start = Instant::now();
+    let tx_hash = data
+        .tx_sender
+        .validate_tx(body.tx, body.signature, None)
+        .await
+        .map_err(TxError::submit_rejected);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "validate_tx");
     tx_hash.map(TxHashSerializeWrapper).into()
 }
 
+async fn verify_tx_signature(
+    data: web::Data<ApiTransactionData>,
+    Json(body): Json<TxWithSignature>,
+) -> ApiResult<TxSignatureCheck> {
+    let start = Instant::now();
+    let check = data
+        .tx_sender
+        .check_tx_signatures(body.tx, body.signature)
+        .await
+        .map_err(TxError::submit_rejected);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "verify_tx_signature");
+    check.into()
+}
+
+async fn decode_tx(
+    data: web::Data<ApiTransactionData>,
+    Json(tx): Json<TxToDecode>,
+) -> ApiResult<DecodedTx> {
+    let start = Instant::now();
+    let decoded_tx = data.decode_tx(tx).await.map_err(TxError::submit_rejected);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "decode_tx");
+    decoded_tx.into()
+}
+
+async fn cancel_tx(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<String>,
+    Json(body): Json<CancelTx>,
+) -> ApiResult<CancelTxResponse> {
+    let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let response = data
+        .tx_sender
+        .cancel_tx(tx_hash, body)
+        .await
+        .map_err(TxError::submit_rejected);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "cancel_tx");
+    response.into()
+}
+
+async fn replace_tx(
+    data: web::Data<ApiTransactionData>,
+    tx_hash: web::Path<String>,
+    Json(body): Json<TxWithSignature>,
+) -> ApiResult<SubmitTxResponse> {
+    let start = Instant::now();
+    let tx_hash = api_try!(parse_tx_hash(&tx_hash));
+    let response = data
+        .tx_sender
+        .replace_tx(tx_hash, body.tx, body.signature, None)
+        .await
+        .map_err(TxError::submit_rejected);
+
+    metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "replace_tx");
+    response.into()
+}
+
 async fn submit_batch(
+    req: HttpRequest,
     data: web::Data<ApiTransactionData>,
     Json(body): Json<IncomingTxBatch>,
 ) -> ApiResult<SubmitBatchResponse> {
     let start = Instant::now();
     let response = data
         .tx_sender
-        .submit_txs_batch(body.txs, body.signature, None)
+        .submit_txs_batch(
+            body.txs,
+            body.signature,
+            request_metadata(&req, &data.tx_sender.trusted_proxies),
+        )
         .await;
 
     if let Err(err) = &response {
@@ -205,7 +802,7 @@ async fn submit_batch(
         metrics::increment_counter!("rejected_txs", &labels);
     }
 
-    let response = response.map_err(Error::from);
+    let response = response.map_err(TxError::submit_rejected);
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "submit_batch");
     response.into()
 }
@@ -219,7 +816,7 @@ async fn toggle_2fa(
         .tx_sender
         .toggle_2fa(toggle_2fa)
         .await
-        .map_err(Error::from);
+        .map_err(TxError::submit_rejected);
 
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "toggle_2fa");
     response.into()
@@ -227,22 +824,35 @@ async fn toggle_2fa(
 
 async fn get_batch(
     data: web::Data<ApiTransactionData>,
-    batch_hash: web::Path<TxHash>,
+    batch_hash: web::Path<String>,
 ) -> ApiResult<Option<ApiTxBatch>> {
     let start = Instant::now();
-    let res = data.get_batch(*batch_hash).await.into();
+    let batch_hash = api_try!(parse_tx_hash(&batch_hash));
+    let res = data.get_batch(batch_hash).await.into();
     metrics::histogram!("api", start.elapsed(), "type" => "v02", "endpoint_name" => "get_batch");
     res
 }
 
-pub fn api_scope(tx_sender: TxSender) -> Scope {
-    let data = ApiTransactionData::new(tx_sender);
+pub fn api_scope(tx_sender: TxSender, status_events: TxStatusEvents) -> Scope {
+    let data = ApiTransactionData::new(tx_sender, status_events);
 
     web::scope("transactions")
         .app_data(web::Data::new(data))
         .route("", web::post().to(submit_tx))
+        .route("async", web::post().to(submit_tx_async))
+        .route("priority_op/{serial_id}", web::get().to(priority_op_status))
+        .route("validate", web::post().to(validate_tx))
+        .route("decode", web::post().to(decode_tx))
+        .route("verify_signature", web::post().to(verify_tx_signature))
         .route("{tx_hash}", web::get().to(tx_status))
+        .route("{tx_hash}", web::delete().to(cancel_tx))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route("{tx_hash}/raw", web::get().to(tx_raw_data))
+        .route("{tx_hash}/proof", web::get().to(tx_proof))
+        .route("{tx_hash}/l1_commit", web::get().to(tx_l1_commit))
+        .route("{tx_hash}/wait", web::get().to(wait_tx_status))
+        .route("{tx_hash}/replace", web::post().to(replace_tx))
+        .route("/status/batch", web::post().to(tx_statuses))
         .route("/batches", web::post().to(submit_batch))
         .route("/batches/{batch_hash}", web::get().to(get_batch))
         .route("/toggle2FA", web::post().to(toggle_2fa))
@@ -251,42 +861,90 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api_server::compliance::ComplianceHook;
     use crate::api_server::rest::v02::{
+        error::ErrorCode,
         test_utils::{
             deserialize_response_result, dummy_fee_ticker, dummy_sign_verifier, TestServerConfig,
-            TestTransactions,
+            TestTransactions, VERIFIED_OP_SERIAL_ID,
         },
         SharedData,
     };
     use crate::fee_ticker::validator::cache::TokenInMemoryCache;
     use chrono::Utc;
     use futures::{channel::mpsc, StreamExt};
-    use num::{rational::Ratio, BigUint};
-    use std::collections::HashMap;
-    use std::str::FromStr;
+    use num::{rational::Ratio, BigUint, Zero};
+    use std::{collections::HashMap, str::FromStr};
     use tokio::task::JoinHandle;
     use zksync_api_types::v02::{
         transaction::{L2Receipt, TxHashSerializeWrapper},
         ApiVersion,
     };
     use zksync_mempool::MempoolTransactionRequest;
+    use zksync_storage::ConnectionPool;
+    use zksync_test_account::ZkSyncAccount;
     use zksync_types::{
+        helpers::{closest_packable_fee_amount, closest_packable_token_amount},
         tokens::{Token, TokenMarketVolume},
         tx::{
-            EthBatchSignData, EthBatchSignatures, PackedEthSignature, TxEthSignature,
-            TxEthSignatureVariant,
+            error::TxAddError, Close, EthBatchSignData, EthBatchSignatures, PackedEthSignature,
+            TxEthSignature, TxEthSignatureVariant,
         },
-        Address, BlockNumber, SignedZkSyncTx, TokenId, TokenKind, TokenLike,
+        AccountId, Address, AddressList, BlockNumber, Nonce, SignedZkSyncTx, TokenId, TokenKind,
+        TokenLike, H256,
     };
 
-    fn submit_txs_loopback() -> (mpsc::Sender<MempoolTransactionRequest>, JoinHandle<()>) {
+    #[test]
+    fn user_ip_from_trusted_proxies() {
+        let peer_ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let user_ip_addr = "100.100.100.100";
+
+        // Without the configured proxies the header is taken from any peer.
+        let no_proxies = HashSet::new();
+        assert_eq!(
+            user_ip(Some(peer_ip), Some(user_ip_addr), &no_proxies),
+            Some(user_ip_addr.to_owned())
+        );
+        assert_eq!(
+            user_ip(Some(peer_ip), None, &no_proxies),
+            Some(peer_ip.to_string())
+        );
+
+        // Otherwise only the configured proxies may report the IP of the user.
+        let proxies: HashSet<IpAddr> = [peer_ip].iter().copied().collect();
+        assert_eq!(
+            user_ip(Some(peer_ip), Some(user_ip_addr), &proxies),
+            Some(user_ip_addr.to_owned())
+        );
+        let other_peer_ip: IpAddr = "10.0.0.2".parse().unwrap();
+        assert_eq!(
+            user_ip(Some(other_peer_ip), Some(user_ip_addr), &proxies),
+            Some(other_peer_ip.to_string())
+        );
+    }
+
+    /// Accepts all the submitted transactions without storing them. Replacements are performed
+    /// in the database, so the replaced transaction is gone.
+    fn submit_txs_loopback(
+        pool: ConnectionPool,
+    ) -> (mpsc::Sender<MempoolTransactionRequest>, JoinHandle<()>) {
         let (mempool_tx_request_sender, mut mempool_tx_request_receiver) = mpsc::channel(100);
 
         let task = tokio::spawn(async move {
             while let Some(tx) = mempool_tx_request_receiver.next().await {
                 match tx {
                     MempoolTransactionRequest::NewTx(_, resp) => {
-                        resp.send(Ok(())).unwrap_or_default()
+                        resp.send(Ok(None)).unwrap_or_default()
+                    }
+                    MempoolTransactionRequest::ReplaceTx(tx_hash, tx, resp) => {
+                        let mut storage = pool.access_storage().await.unwrap();
+                        let replaced = storage
+                            .chain()
+                            .mempool_schema()
+                            .replace_tx(tx_hash, &tx)
+                            .await
+                            .map_err(|_| TxAddError::DbError);
+                        resp.send(replaced).unwrap_or_default()
                     }
                     MempoolTransactionRequest::NewPriorityOps(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
@@ -294,6 +952,9 @@ mod tests {
                     MempoolTransactionRequest::NewTxsBatch(_, _, resp) => {
                         resp.send(Ok(())).unwrap_or_default()
                     }
+                    MempoolTransactionRequest::UpdateLastEthBlock(_, resp) => {
+                        resp.send(Ok(())).unwrap_or_default()
+                    }
                 }
             }
         });
@@ -307,10 +968,9 @@ mod tests {
         ignore = "Use `zk test rust-api` command to perform this test"
     )]
     async fn transactions_scope() -> anyhow::Result<()> {
-        let (sender, task) = submit_txs_loopback();
-
         let cfg = TestServerConfig::default();
         cfg.fill_database().await?;
+        let (sender, task) = submit_txs_loopback(cfg.pool.clone());
 
         let shared_data = SharedData {
             net: cfg.config.chain.eth.network,
@@ -346,14 +1006,17 @@ mod tests {
 
         let (client, server) = cfg.start_server(
             move |cfg: &TestServerConfig| {
-                api_scope(TxSender::new(
-                    cfg.pool.clone(),
-                    dummy_sign_verifier(),
-                    dummy_fee_ticker(&prices, Some(cache.clone())),
-                    &cfg.config.api.common,
-                    &cfg.config.api.token_config,
-                    sender.clone(),
-                ))
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, Some(cache.clone())),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                    ),
+                    TxStatusEvents::new(),
+                )
             },
             Some(shared_data),
         );
@@ -362,8 +1025,72 @@ mod tests {
         let response = client
             .submit_tx(tx.clone(), TxEthSignatureVariant::Single(None))
             .await?;
-        let tx_hash: TxHash = deserialize_response_result(response)?;
-        assert_eq!(tx.hash(), tx_hash);
+        let submitted: SubmitTxResponse = deserialize_response_result(response)?;
+        assert_eq!(submitted.tx_hash.0, tx.hash());
+        assert!(submitted.replaced_tx_hash.is_none());
+
+        // The sender account has no balance to pay the fee, so the dry run should fail.
+        let response = client
+            .validate_tx(tx.clone(), TxEthSignatureVariant::Single(None))
+            .await?;
+        let error = response.error.expect("Validation should fail");
+        assert!(error["message"]
+            .as_str()
+            .unwrap()
+            .contains(&TxAddError::InsufficientBalance.to_string()));
+
+        let response = client.decode_tx(TxToDecode::Tx(tx.clone())).await?;
+        let decoded_tx: DecodedTx = deserialize_response_result(response)?;
+        assert_eq!(decoded_tx.tx_hash, tx.hash());
+        assert_eq!(decoded_tx.tx_bytes, tx.get_bytes());
+        assert!(decoded_tx.valid);
+        assert!(decoded_tx.error.is_none());
+
+        // Bytes don't contain the signature, so the transaction is decoded but reported invalid.
+        let tx_bytes = tx.get_bytes();
+        let response = client
+            .decode_tx(TxToDecode::Bytes(TxBytes { tx_bytes }))
+            .await?;
+        let decoded_tx: DecodedTx = deserialize_response_result(response)?;
+        assert_eq!(decoded_tx.tx_hash, tx.hash());
+        assert!(!decoded_tx.valid);
+        assert!(decoded_tx.error.is_some());
+
+        let response = client
+            .decode_tx(TxToDecode::Bytes(TxBytes {
+                tx_bytes: vec![0xff; 3],
+            }))
+            .await?;
+        assert!(response.error.is_some());
+
+        let response = client
+            .verify_tx_signature(tx.clone(), TxEthSignatureVariant::Single(None))
+            .await?;
+        let check: TxSignatureCheck = deserialize_response_result(response)?;
+        assert_eq!(check.zksync_signature_valid, Some(true));
+        assert_eq!(check.eth_signature_valid, Some(true));
+        assert!(check.error.is_none());
+
+        // Retrying the submission with the same request ID should return the original hash.
+        let txs = TestServerConfig::gen_zk_txs(100_u64).txs;
+        let (request_tx, another_tx) = (txs[1].0.clone(), txs[2].0.clone());
+        let request_id = request_tx.hash().to_string();
+        for _ in 0..2 {
+            let response = client
+                .submit_tx_with_request_id(
+                    request_tx.clone(),
+                    TxEthSignatureVariant::Single(None),
+                    &request_id,
+                )
+                .await?;
+            let submitted: SubmitTxResponse = deserialize_response_result(response)?;
+            assert_eq!(submitted.tx_hash.0, request_tx.hash());
+        }
+        // The request ID can't be reused for another transaction.
+        let response = client
+            .submit_tx_with_request_id(another_tx, TxEthSignatureVariant::Single(None), &request_id)
+            .await?;
+        assert!(response.error.is_some());
 
         let TestTransactions { acc, txs } = TestServerConfig::gen_zk_txs(1_00);
         let eth = Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20);
@@ -411,6 +1138,23 @@ mod tests {
         let submit_batch_response: SubmitBatchResponse = deserialize_response_result(response)?;
         assert_eq!(submit_batch_response, expected_response);
 
+        // Single signature may be made over the message covering the batch hash.
+        let batch_hash_signature = {
+            let eth_private_key = acc
+                .try_get_eth_private_key()
+                .expect("Should have ETH private key");
+            let tx_hashes: Vec<_> = good_batch.iter().map(|tx| tx.tx.hash()).collect();
+            let message = EthBatchSignData::get_batch_hash_sign_message(&tx_hashes);
+            let eth_sig = PackedEthSignature::sign(eth_private_key, &message).unwrap();
+
+            EthBatchSignatures::Single(TxEthSignature::EthereumSignature(eth_sig))
+        };
+        let response = client
+            .submit_batch(good_batch.clone(), Some(batch_hash_signature))
+            .await?;
+        let response: SubmitBatchResponse = deserialize_response_result(response)?;
+        assert_eq!(response, expected_response);
+
         {
             let mut storage = cfg.pool.access_storage().await?;
             let txs: Vec<_> = good_batch
@@ -437,7 +1181,7 @@ mod tests {
         );
         assert_eq!(batch.batch_status.last_state, TxInBlockStatus::Queued);
 
-        let tx_hash = {
+        let (tx_hash, tx_block_index) = {
             let mut storage = cfg.pool.access_storage().await?;
 
             let transactions = storage
@@ -446,27 +1190,36 @@ mod tests {
                 .get_block_transactions(BlockNumber(1))
                 .await?;
 
-            TxHash::from_str(&transactions[0].tx_hash).unwrap()
+            (
+                TxHash::from_str(&transactions[0].tx_hash).unwrap(),
+                transactions[0].block_index.map(|index| index as u32),
+            )
         };
         let response = client.tx_status(tx_hash).await?;
         let tx_status: Receipt = deserialize_response_result(response)?;
-        let expected_tx_status = Receipt::L2(L2Receipt {
-            tx_hash,
-            rollup_block: Some(BlockNumber(1)),
-            status: TxInBlockStatus::Finalized,
-            fail_reason: None,
-        });
-        assert_eq!(tx_status, expected_tx_status);
+        match tx_status {
+            Receipt::L2(receipt) => {
+                assert_eq!(receipt.tx_hash, tx_hash);
+                assert_eq!(receipt.rollup_block, Some(BlockNumber(1)));
+                assert_eq!(receipt.block_index, tx_block_index);
+                assert_eq!(receipt.status, TxInBlockStatus::Finalized);
+                assert_eq!(receipt.fail_reason, None);
+                assert!(receipt.fee.is_some());
+                assert!(receipt.fee_token.is_some());
+            }
+            Receipt::L1(_) => panic!("Should be L2 receipt"),
+        }
 
         let response = client.tx_data(tx_hash).await?;
-        let tx_data: Option<TxData> = deserialize_response_result(response)?;
-        assert_eq!(tx_data.unwrap().tx.tx_hash, tx_hash);
+        let tx_data: TxData = deserialize_response_result(response)?;
+        assert_eq!(tx_data.tx.tx_hash, tx_hash);
 
-        let pending_tx_hash = {
+        let (pending_tx_hash, pending_tx_bytes) = {
             let mut storage = cfg.pool.access_storage().await?;
 
             let tx = TestServerConfig::gen_zk_txs(1_u64).txs[0].0.clone();
             let tx_hash = tx.hash();
+            let tx_bytes = tx.get_bytes();
             storage
                 .chain()
                 .mempool_schema()
@@ -477,26 +1230,439 @@ mod tests {
                 })
                 .await?;
 
-            tx_hash
+            (tx_hash, tx_bytes)
         };
         let response = client.tx_status(pending_tx_hash).await?;
         let tx_status: Receipt = deserialize_response_result(response)?;
+        // The time the transaction was added to the mempool is set by the database.
+        let created_at = match &tx_status {
+            Receipt::L2(receipt) => receipt.created_at,
+            Receipt::L1(_) => None,
+        };
+        assert!(created_at.is_some());
         let expected_tx_status = Receipt::L2(L2Receipt {
             tx_hash: pending_tx_hash,
             rollup_block: None,
+            block_index: None,
             status: TxInBlockStatus::Queued,
             fail_reason: None,
+            fee: None,
+            fee_token: None,
+            created_at,
+            committed_at: None,
+            finalized_at: None,
+            estimated_finalization: None,
+            swap: None,
         });
         assert_eq!(tx_status, expected_tx_status);
 
+        // The transaction is already finalized, so the response should be returned immediately.
+        let response = client
+            .wait_tx_status(tx_hash, TxInBlockStatus::Finalized, Some(10))
+            .await?;
+        let tx_status: Option<Receipt> = deserialize_response_result(response)?;
+        assert_eq!(
+            tx_status.as_ref().map(Receipt::status),
+            Some(TxInBlockStatus::Finalized)
+        );
+
+        // The queued transaction can't be committed, so the request should time out.
+        let response = client
+            .wait_tx_status(pending_tx_hash, TxInBlockStatus::Committed, Some(1))
+            .await?;
+        let tx_status: Option<Receipt> = deserialize_response_result(response)?;
+        assert_eq!(
+            tx_status.as_ref().map(Receipt::status),
+            Some(TxInBlockStatus::Queued)
+        );
+
+        let response = client.priority_op_status(VERIFIED_OP_SERIAL_ID).await?;
+        let op_status: Option<Receipt> = deserialize_response_result(response)?;
+        match op_status {
+            Some(Receipt::L1(receipt)) => {
+                assert_eq!(receipt.id, VERIFIED_OP_SERIAL_ID);
+                assert_eq!(receipt.rollup_block, Some(BlockNumber(2)));
+                assert_eq!(receipt.block_index, Some(2));
+            }
+            other => panic!("Should be L1 receipt, got {:?}", other),
+        }
+
+        let response = client.priority_op_status(1_000_000).await?;
+        let op_status: Option<Receipt> = deserialize_response_result(response)?;
+        assert!(op_status.is_none());
+
+        let unknown_tx_hash = TestServerConfig::gen_zk_txs(1_u64).txs[0].0.hash();
+        let response = client
+            .tx_statuses(vec![tx_hash, pending_tx_hash, unknown_tx_hash])
+            .await?;
+        let tx_statuses: TxStatusesResponse = deserialize_response_result(response)?;
+        assert_eq!(tx_statuses.len(), 3);
+        assert_eq!(
+            tx_statuses[&TxHashSerializeWrapper(pending_tx_hash)],
+            Some(expected_tx_status)
+        );
+        assert_eq!(
+            tx_statuses[&TxHashSerializeWrapper(tx_hash)]
+                .as_ref()
+                .map(Receipt::status),
+            Some(TxInBlockStatus::Finalized)
+        );
+        assert_eq!(tx_statuses[&TxHashSerializeWrapper(unknown_tx_hash)], None);
+
+        let too_many_hashes = vec![unknown_tx_hash; MAX_TX_STATUSES_BATCH_SIZE + 1];
+        let response = client.tx_statuses(too_many_hashes).await?;
+        assert!(response.error.is_some());
+
         let response = client.tx_data(pending_tx_hash).await?;
-        let tx_data: Option<TxData> = deserialize_response_result(response)?;
-        assert_eq!(tx_data.unwrap().tx.tx_hash, pending_tx_hash);
+        let tx_data: TxData = deserialize_response_result(response)?;
+        assert_eq!(tx_data.tx.tx_hash, pending_tx_hash);
+
+        let response = client.tx_raw_data(pending_tx_hash).await?;
+        let raw_data: TxRawData = deserialize_response_result(response)?;
+        assert_eq!(raw_data.tx_hash, pending_tx_hash);
+        assert_eq!(raw_data.tx_bytes, pending_tx_bytes);
+        assert!(raw_data.eth_signature.is_none());
+
+        let response = client.tx_proof(tx_hash).await?;
+        let proof: Option<TxInclusionProof> = deserialize_response_result(response)?;
+        let proof = proof.expect("Proof for the executed transaction should be available");
+        assert_eq!(proof.block_number, BlockNumber(1));
+        let offset = proof.public_data_offset as usize;
+        assert_eq!(
+            &proof.public_data[offset..offset + proof.op_public_data.len()],
+            proof.op_public_data.as_slice()
+        );
+
+        let response = client.tx_l1_commit(tx_hash).await?;
+        let l1_commit: Option<TxL1Commit> = deserialize_response_result(response)?;
+        let l1_commit = l1_commit.expect("Commit of the executed transaction should be available");
+        assert_eq!(l1_commit.block_number, BlockNumber(1));
+        assert_eq!(l1_commit.block_index, proof.block_index);
+        assert_eq!(
+            l1_commit.public_data_length as usize,
+            proof.op_public_data.len()
+        );
+
+        let response = client.tx_proof(pending_tx_hash).await?;
+        let proof: Option<TxInclusionProof> = deserialize_response_result(response)?;
+        assert!(proof.is_none());
+
+        let response = client.tx_l1_commit(pending_tx_hash).await?;
+        let l1_commit: Option<TxL1Commit> = deserialize_response_result(response)?;
+        assert!(l1_commit.is_none());
 
         let tx = TestServerConfig::gen_zk_txs(1_u64).txs[0].0.clone();
+        let response = client.tx_status(tx.hash()).await?;
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::TransactionNotFound);
+
         let response = client.tx_data(tx.hash()).await?;
-        let tx_data: Option<TxData> = deserialize_response_result(response)?;
-        assert!(tx_data.is_none());
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::TransactionNotFound);
+
+        let response = client.tx_raw_data(tx.hash()).await?;
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::TransactionNotFound);
+
+        // The replacement has to pay a higher fee than the queued transaction.
+        let response = client
+            .replace_tx(pending_tx_hash, tx, TxEthSignatureVariant::Single(None))
+            .await?;
+        let error = response
+            .error
+            .expect("Replacement with the same fee should fail");
+        assert!(error["message"]
+            .as_str()
+            .unwrap()
+            .contains(&TxAddError::ReplacementFeeTooLow.to_string()));
+
+        // Replace the queued transaction with the one from the same account and with the same nonce.
+        let replacement_tx = TestServerConfig::gen_zk_txs(100_u64).txs[0].0.clone();
+        let response = client
+            .replace_tx(
+                pending_tx_hash,
+                replacement_tx.clone(),
+                TxEthSignatureVariant::Single(None),
+            )
+            .await?;
+        let replacement: SubmitTxResponse = deserialize_response_result(response)?;
+        assert_eq!(replacement.tx_hash.0, replacement_tx.hash());
+        assert_eq!(
+            replacement.replaced_tx_hash,
+            Some(TxHashSerializeWrapper(pending_tx_hash))
+        );
+
+        // The replaced transaction is not queued anymore, its receipt tells what replaced it.
+        let response = client.tx_status(pending_tx_hash).await?;
+        let tx_status: Receipt = deserialize_response_result(response)?;
+        match tx_status {
+            Receipt::L2(receipt) => {
+                assert_eq!(receipt.status, TxInBlockStatus::Expired);
+                assert!(receipt
+                    .fail_reason
+                    .unwrap()
+                    .contains(&replacement_tx.hash().to_string()));
+            }
+            Receipt::L1(_) => panic!("Should be L2 receipt"),
+        }
+
+        // The transaction is not in the mempool anymore, so it can't be replaced again.
+        let response = client
+            .replace_tx(
+                pending_tx_hash,
+                replacement_tx,
+                TxEthSignatureVariant::Single(None),
+            )
+            .await?;
+        assert!(response.error.is_some());
+
+        // Cancel the queued transaction.
+        let cancelled_tx_hash = {
+            let mut storage = cfg.pool.access_storage().await?;
+
+            let tx = TestServerConfig::gen_zk_txs(1_u64).txs[0].0.clone();
+            let tx_hash = tx.hash();
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_tx(&SignedZkSyncTx {
+                    tx,
+                    eth_sign_data: None,
+                    created_at: Utc::now(),
+                })
+                .await?;
+
+            tx_hash
+        };
+        // Signatures are not checked by the dummy verifier.
+        let cancel_tx = CancelTx {
+            timestamp: Utc::now(),
+            signature: TxEthSignature::EthereumSignature(
+                PackedEthSignature::sign(&H256::random(), b"cancel").unwrap(),
+            ),
+        };
+        let response = client
+            .cancel_tx(cancelled_tx_hash, cancel_tx.clone())
+            .await?;
+        let cancel_response: CancelTxResponse = deserialize_response_result(response)?;
+        assert!(cancel_response.success);
+
+        let response = client.tx_status(cancelled_tx_hash).await?;
+        let error: Error = serde_json::from_value(response.error.unwrap())?;
+        assert_eq!(error.code, ErrorCode::TransactionNotFound);
+
+        let response = client.cancel_tx(cancelled_tx_hash, cancel_tx).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        task.abort();
+        Ok(())
+    }
+
+    /// Checks that the fee of the batch may be paid in a token other than the one
+    /// transferred by the rest of the batch.
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn batch_fee_in_another_token() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (sender, task) = submit_txs_loopback(cfg.pool.clone());
+
+        let eth = Token::new(TokenId(0), Default::default(), "ETH", 18, TokenKind::ERC20);
+        let phnx = Token::new(TokenId(1), Default::default(), "PHNX", 18, TokenKind::ERC20);
+        let mut tokens = HashMap::new();
+        tokens.insert(TokenLike::Id(TokenId(0)), eth.clone());
+        let mut market = HashMap::new();
+        market.insert(
+            TokenId(0),
+            TokenMarketVolume {
+                market_volume: Ratio::from_integer(BigUint::from(400u32)),
+                last_updated: Utc::now(),
+            },
+        );
+        let cache = TokenInMemoryCache::new()
+            .with_tokens(tokens)
+            .with_market(market);
+        let prices = vec![
+            (TokenLike::Id(TokenId(0)), 10500_u64.into()),
+            (TokenLike::Id(TokenId(1)), 10_u64.into()),
+        ];
+
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&prices, Some(cache.clone())),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                    ),
+                    TxStatusEvents::new(),
+                )
+            },
+            None,
+        );
+
+        let from = ZkSyncAccount::rand();
+        from.set_account_id(Some(AccountId(0xf00d)));
+        let to = Address::random();
+        let amount = closest_packable_token_amount(&10_u64.into());
+        // PHNX is transferred without the fee, the whole fee is paid in ETH by the rest of the batch.
+        let sign_batch = |eth_fees: &[u64]| {
+            let transfer = from
+                .sign_transfer(
+                    phnx.id,
+                    &phnx.symbol,
+                    amount.clone(),
+                    BigUint::zero(),
+                    &to,
+                    Some(Nonce(0)),
+                    false,
+                    Default::default(),
+                )
+                .0;
+            let mut txs = vec![(ZkSyncTx::Transfer(Box::new(transfer)), phnx.clone())];
+            for (nonce, fee) in (1..).zip(eth_fees) {
+                let fee_transfer = from
+                    .sign_transfer(
+                        eth.id,
+                        &eth.symbol,
+                        amount.clone(),
+                        closest_packable_fee_amount(&BigUint::from(*fee)),
+                        &to,
+                        Some(Nonce(nonce)),
+                        false,
+                        Default::default(),
+                    )
+                    .0;
+                txs.push((ZkSyncTx::Transfer(Box::new(fee_transfer)), eth.clone()));
+            }
+
+            let batch_message = EthBatchSignData::get_batch_sign_message(
+                txs.iter()
+                    .map(|(tx, token)| (tx.clone(), token.clone(), tx.account()))
+                    .collect(),
+            );
+            let eth_private_key = from
+                .try_get_eth_private_key()
+                .expect("Should have ETH private key");
+            let eth_sig = PackedEthSignature::sign(eth_private_key, &batch_message).unwrap();
+            let batch = txs
+                .into_iter()
+                .map(|(tx, _token)| TxWithSignature {
+                    tx,
+                    signature: TxEthSignatureVariant::Single(None),
+                })
+                .collect::<Vec<_>>();
+            (
+                batch,
+                EthBatchSignatures::Single(TxEthSignature::EthereumSignature(eth_sig)),
+            )
+        };
+
+        // 1 ETH paid by two transactions covers the fee of the whole batch.
+        let half_eth = 5 * 10_u64.pow(17);
+        let (batch, batch_signature) = sign_batch(&[half_eth, half_eth]);
+        let tx_hashes: Vec<_> = batch.iter().map(|tx| tx.tx.hash()).collect();
+        let response = client.submit_batch(batch, Some(batch_signature)).await?;
+        let response: SubmitBatchResponse = deserialize_response_result(response)?;
+        assert_eq!(response.batch_hash, TxHash::batch_hash(&tx_hashes));
+
+        // The batch is rejected if none of the transactions pays the fee.
+        let (batch, batch_signature) = sign_batch(&[0]);
+        let response = client.submit_batch(batch, Some(batch_signature)).await?;
+        assert!(response.error.is_some());
+
+        server.stop().await;
+        task.abort();
+        Ok(())
+    }
+
+    struct RejectAllHook;
+
+    #[async_trait::async_trait]
+    impl ComplianceHook for RejectAllHook {
+        async fn check_txs(&self, _txs: &[&ZkSyncTx]) -> Result<(), SubmitError> {
+            Err(SubmitError::AddressBlocked {
+                address: Address::zero(),
+                list: AddressList::Sender,
+            })
+        }
+    }
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn async_submission() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+        let (sender, task) = submit_txs_loopback(cfg.pool.clone());
+
+        let (client, server) = cfg.start_server(
+            move |cfg: &TestServerConfig| {
+                api_scope(
+                    TxSender::new(
+                        cfg.pool.clone(),
+                        dummy_sign_verifier(),
+                        dummy_fee_ticker(&[], None),
+                        &cfg.config.api.common,
+                        &cfg.config.api.token_config,
+                        sender.clone(),
+                    )
+                    .with_compliance_hook(RejectAllHook),
+                    TxStatusEvents::new(),
+                )
+            },
+            None,
+        );
+
+        // The transaction is accepted before the verification.
+        let tx = TestServerConfig::gen_zk_txs(100_u64).txs[0].0.clone();
+        let response = client
+            .submit_tx_async(tx.clone(), TxEthSignatureVariant::Single(None))
+            .await?;
+        let tx_hash: TxHash = deserialize_response_result(response)?;
+        assert_eq!(tx_hash, tx.hash());
+
+        // The receipt is pending until the verification is completed,
+        // then the failure of the verification is reported by it.
+        let mut receipt = None;
+        for _ in 0..100 {
+            let response = client.tx_status(tx_hash).await?;
+            let current: Option<Receipt> = deserialize_response_result(response)?;
+            let current = current.expect("Receipt of the submitted transaction should be stored");
+            if current.status() != TxInBlockStatus::PendingVerification {
+                receipt = Some(current);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        match receipt.expect("Verification of the transaction should be completed") {
+            Receipt::L2(receipt) => {
+                assert_eq!(receipt.status, TxInBlockStatus::RejectedPreBlock);
+                assert!(receipt.fail_reason.is_some());
+            }
+            Receipt::L1(_) => panic!("Should be L2 receipt"),
+        }
+
+        // Transactions that can't be accepted at all are still rejected synchronously.
+        let close_tx = ZkSyncTx::Close(Box::new(Close {
+            account: tx.account(),
+            nonce: tx.nonce(),
+            signature: Default::default(),
+            time_range: Default::default(),
+        }));
+        let response = client
+            .submit_tx_async(close_tx, TxEthSignatureVariant::Single(None))
+            .await?;
+        assert!(response.error.is_some());
 
         server.stop().await;
         task.abort();
@@ -0,0 +1,140 @@
+//! Notifications about the changes that may affect the transaction statuses.
+//!
+//! The block committer stores an event for every committed, finalized or reverted block and for
+//! every transaction in it, and the database notifies the listeners about the new events.
+//! A single listener per API server loads the new events and broadcasts them, so the requests
+//! waiting for the transaction status recheck it only when it may have changed.
+
+// Built-in uses
+use std::{convert::TryFrom, str::FromStr, time::Duration};
+// External uses
+use tokio::{sync::broadcast, task::JoinHandle};
+// Workspace uses
+use zksync_storage::{event::records::EventType, listener::StorageListener, ConnectionPool};
+use zksync_types::{
+    event::{EventData, EventId, ZkSyncEvent},
+    tx::TxHash,
+};
+
+/// Number of the events kept for the slow receivers, the ones lagging behind recheck the status.
+const EVENTS_CHANNEL_CAPACITY: usize = 4096;
+/// Interval between the attempts to reconnect to the database after the listener failure.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatusEvent {
+    /// The transaction with the given hash was committed, finalized or rejected.
+    Tx(TxHash),
+    /// The block was committed, finalized or reverted. Priority operations looked up by
+    /// the Ethereum hash are only notified this way.
+    Block,
+    /// Events may have been missed, every transaction status should be rechecked.
+    Resync,
+}
+
+impl TxStatusEvent {
+    /// Checks whether the event may change the status of the given transaction.
+    pub fn affects(&self, tx_hash: TxHash) -> bool {
+        match self {
+            Self::Tx(hash) => *hash == tx_hash,
+            Self::Block | Self::Resync => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TxStatusEvents {
+    sender: broadcast::Sender<TxStatusEvent>,
+}
+
+impl Default for TxStatusEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TxStatusEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENTS_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TxStatusEvent> {
+        self.sender.subscribe()
+    }
+
+    fn notify(&self, event: TxStatusEvent) {
+        // There may be no requests waiting at the moment.
+        self.sender.send(event).ok();
+    }
+
+    /// Spawns the task listening for the events stored in the database on the given channel.
+    pub fn spawn_listener(&self, pool: ConnectionPool, channel_name: String) -> JoinHandle<()> {
+        let events = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(err) = events.listen(&pool, &channel_name).await {
+                    vlog::warn!("Transaction status events listener has failed: {}", err);
+                }
+                events.notify(TxStatusEvent::Resync);
+                tokio::time::sleep(RECONNECT_INTERVAL).await;
+            }
+        })
+    }
+
+    async fn listen(&self, pool: &ConnectionPool, channel_name: &str) -> anyhow::Result<()> {
+        let mut listener = StorageListener::connect().await?;
+        listener.listen(channel_name).await?;
+        let mut last_event_id = pool
+            .access_storage()
+            .await?
+            .event_schema()
+            .get_last_event_id()
+            .await?
+            .unwrap_or(EventId(0));
+
+        loop {
+            // Notification only carries the event ID, the events are loaded in order starting
+            // from the last processed one, so the events notified during the reconnection
+            // of the listener are not lost.
+            listener.recv().await?;
+            let events = pool
+                .access_storage()
+                .await?
+                .event_schema()
+                .fetch_new_events(last_event_id)
+                .await?;
+
+            for stored_event in events {
+                last_event_id = EventId(stored_event.id as u64);
+                if stored_event.event_type == EventType::Account {
+                    continue;
+                }
+                match ZkSyncEvent::try_from(stored_event)?.data {
+                    EventData::Transaction(event) => {
+                        let tx_hash = TxHash::from_str(&event.tx_hash)?;
+                        self.notify(TxStatusEvent::Tx(tx_hash));
+                    }
+                    EventData::Block(_) => self.notify(TxStatusEvent::Block),
+                    EventData::Account(_) => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_affects_tx() {
+        let tx_hash = TxHash::from_str(&format!("sync-tx:{}", "11".repeat(32))).unwrap();
+        let other_hash = TxHash::from_str(&format!("sync-tx:{}", "22".repeat(32))).unwrap();
+
+        assert!(TxStatusEvent::Tx(tx_hash).affects(tx_hash));
+        assert!(!TxStatusEvent::Tx(other_hash).affects(tx_hash));
+        assert!(TxStatusEvent::Block.affects(tx_hash));
+        assert!(TxStatusEvent::Resync.affects(tx_hash));
+    }
+}
@@ -0,0 +1,38 @@
+// Built-in uses
+// External uses
+use actix::prelude::*;
+// Workspace uses
+use zksync_api_types::v02::ws::{WsNotification, WsTopic};
+// Local uses
+use super::subscriber::WsSubscriber;
+
+/// Adds the subscriber to the list of the topic listeners.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct Subscribe {
+    pub topic: WsTopic,
+    pub subscriber: Addr<WsSubscriber>,
+}
+
+/// Removes the subscriber from the list of the topic listeners.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct Unsubscribe {
+    pub topic: WsTopic,
+    pub subscriber: Addr<WsSubscriber>,
+}
+
+/// Removes the subscriber from all the topics, sent once the connection is closed.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct RemoveSubscriber(pub Addr<WsSubscriber>);
+
+/// Rejects the subscription because the monitor has too many of them.
+#[derive(Debug, Message)]
+#[rtype(result = "()")]
+pub struct RejectSubscription(pub WsTopic);
+
+/// Notification to be forwarded to the WebSocket client.
+#[derive(Debug, Clone, Message)]
+#[rtype(result = "()")]
+pub struct Notify(pub WsNotification);
@@ -0,0 +1,128 @@
+//! WebSocket part of API implementation.
+//!
+//! Clients subscribe to topics (e.g. the status of a transaction) by sending
+//! JSON-serialized `WsRequest` messages and receive `WsNotification` messages
+//! every time the state of the topic changes.
+
+// Built-in uses
+use std::time::Duration;
+// External uses
+use actix::prelude::*;
+use actix_web::{web, Error, HttpRequest, HttpResponse, Scope};
+use actix_web_actors::ws;
+// Workspace uses
+use zksync_storage::ConnectionPool;
+// Local uses
+use self::subscriber::WsSubscriber;
+
+pub use self::monitor::WsMonitor;
+
+mod messages;
+mod monitor;
+mod subscriber;
+
+/// Shared data between `api/v0.2/ws` connections.
+#[derive(Debug, Clone)]
+struct ApiWsData {
+    monitor: Addr<WsMonitor>,
+}
+
+async fn ws_index(
+    req: HttpRequest,
+    stream: web::Payload,
+    data: web::Data<ApiWsData>,
+) -> Result<HttpResponse, Error> {
+    ws::start(WsSubscriber::new(data.monitor.clone()), &req, stream)
+}
+
+/// Starts the monitor shared by all the connections of the server.
+pub fn start_monitor(pool: ConnectionPool, miniblock_interval: Duration) -> Addr<WsMonitor> {
+    WsMonitor::new(pool, miniblock_interval).start()
+}
+
+pub fn api_scope(monitor: Addr<WsMonitor>) -> Scope {
+    let data = ApiWsData { monitor };
+
+    web::scope("ws")
+        .app_data(web::Data::new(data))
+        .route("", web::get().to(ws_index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api_server::rest::v02::{test_utils::TestServerConfig, SharedData};
+    use futures::{SinkExt, StreamExt};
+    use std::str::FromStr;
+    use zksync_api_types::v02::{
+        transaction::{TxHashSerializeWrapper, TxInBlockStatus},
+        ws::{WsNotification, WsRequest, WsTopic},
+        ApiVersion,
+    };
+    use zksync_types::{tx::TxHash, BlockNumber};
+
+    #[actix_rt::test]
+    #[cfg_attr(
+        not(feature = "api_test"),
+        ignore = "Use `zk test rust-api` command to perform this test"
+    )]
+    async fn ws_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let shared_data = SharedData {
+            net: cfg.config.chain.eth.network,
+            api_version: ApiVersion::V02,
+        };
+        let (_client, mut server) = cfg.start_server(
+            |cfg: &TestServerConfig| {
+                api_scope(start_monitor(cfg.pool.clone(), Duration::from_millis(100)))
+            },
+            Some(shared_data),
+        );
+
+        let tx_hash = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let transactions = storage
+                .chain()
+                .block_schema()
+                .get_block_transactions(BlockNumber(1))
+                .await?;
+            TxHash::from_str(&transactions[0].tx_hash).unwrap()
+        };
+        let topic = WsTopic::TxStatus {
+            tx_hash: TxHashSerializeWrapper(tx_hash),
+        };
+
+        let mut connection = server
+            .ws_at("/api/v0.2/ws")
+            .await
+            .expect("Failed to connect to the WebSocket endpoint");
+        let request = WsRequest::Subscribe {
+            topic: topic.clone(),
+        };
+        connection
+            .send(ws::Message::Text(serde_json::to_string(&request)?.into()))
+            .await
+            .expect("Failed to send the subscription request");
+
+        let frame = connection
+            .next()
+            .await
+            .expect("Connection was closed")
+            .expect("Failed to receive the notification");
+        let notification: WsNotification = match frame {
+            ws::Frame::Text(bytes) => serde_json::from_slice(&bytes)?,
+            frame => panic!("Unexpected frame: {:?}", frame),
+        };
+        assert_eq!(notification.topic(), topic);
+        match notification {
+            WsNotification::TxStatus { receipt, .. } => {
+                assert_eq!(receipt.status(), TxInBlockStatus::Finalized);
+            }
+        }
+
+        server.stop().await;
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+// Built-in uses
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+// External uses
+use actix::prelude::*;
+// Workspace uses
+use zksync_api_types::v02::{
+    transaction::TxHashSerializeWrapper,
+    ws::{WsNotification, WsTopic},
+};
+use zksync_storage::ConnectionPool;
+use zksync_types::{
+    aggregated_operations::AggregatedOperation, block::ExecutedOperations, tx::TxHash,
+};
+// Local uses
+use super::{
+    messages::{Notify, RejectSubscription, RemoveSubscriber, Subscribe, Unsubscribe},
+    subscriber::WsSubscriber,
+};
+use crate::api_server::event_notify::{spawn_event_fetcher, ExecutedOps};
+
+/// Maximum number of the subscriptions of all the connections served by the monitor.
+const MAX_SUBSCRIPTIONS: usize = 100_000;
+/// Interval between the checks of all the subscribed topics. Unlike the execution of
+/// the transactions, the changes in the mempool (e.g. the expiration of the transaction)
+/// are not reported by the events, so they are only noticed by these checks.
+const RESYNC_INTERVAL: Duration = Duration::from_secs(30);
+
+/// The actor that keeps track of the subscriptions, listens to the events of the executed
+/// operations and of the committed and finalized blocks and notifies the subscribers
+/// of the affected topics.
+#[derive(Debug)]
+pub struct WsMonitor {
+    pool: ConnectionPool,
+    miniblock_interval: Duration,
+    subscribers: HashMap<WsTopic, HashSet<Addr<WsSubscriber>>>,
+    /// Total number of the subscriptions of all the topics.
+    subscriptions_count: usize,
+    /// The last notification sent for every subscribed topic, used to send only the changes.
+    last_notifications: HashMap<WsTopic, WsNotification>,
+}
+
+impl WsMonitor {
+    pub fn new(pool: ConnectionPool, miniblock_interval: Duration) -> Self {
+        Self {
+            pool,
+            miniblock_interval,
+            subscribers: HashMap::new(),
+            subscriptions_count: 0,
+            last_notifications: HashMap::new(),
+        }
+    }
+
+    /// Loads the current state of the given topics that are still subscribed in a single
+    /// storage request and notifies the subscribers about the changes.
+    fn load_topics(&mut self, tx_hashes: Vec<TxHash>, ctx: &mut <Self as Actor>::Context) {
+        let tx_hashes: Vec<TxHash> = tx_hashes
+            .into_iter()
+            .filter(|tx_hash| self.subscribers.contains_key(&tx_topic(*tx_hash)))
+            .collect();
+        if tx_hashes.is_empty() {
+            return;
+        }
+
+        let pool = self.pool.clone();
+        async move {
+            let mut storage = pool.access_storage().await?;
+            storage
+                .chain()
+                .operations_ext_schema()
+                .tx_receipts_api_v02(&tx_hashes)
+                .await
+        }
+        .into_actor(self)
+        .map(|result, act, _| match result {
+            Ok(receipts) => {
+                for (tx_hash, receipt) in receipts {
+                    act.notify(WsNotification::TxStatus {
+                        tx_hash: TxHashSerializeWrapper(tx_hash),
+                        receipt,
+                    });
+                }
+            }
+            Err(err) => {
+                vlog::warn!("Failed to load the state of WebSocket topics: {}", err);
+            }
+        })
+        .spawn(ctx);
+    }
+
+    /// Reloads the topics of the transactions affected by the executed operations.
+    fn handle_operations(
+        &mut self,
+        operations: &[ExecutedOperations],
+        ctx: &mut <Self as Actor>::Context,
+    ) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+        let tx_hashes = operations
+            .iter()
+            .flat_map(|operation| match operation {
+                ExecutedOperations::Tx(tx) => vec![tx.signed_tx.hash()],
+                // Priority operations may be looked up by the Ethereum hash as well.
+                ExecutedOperations::PriorityOp(op) => {
+                    let mut hashes = vec![op.priority_op.tx_hash()];
+                    hashes.extend(TxHash::from_slice(op.priority_op.eth_hash.as_bytes()));
+                    hashes
+                }
+            })
+            .collect();
+        self.load_topics(tx_hashes, ctx);
+    }
+
+    /// Sends the notification to the topic subscribers if it differs from the previous one.
+    fn notify(&mut self, notification: WsNotification) {
+        let topic = notification.topic();
+        // Topic may have been unsubscribed while its state was loaded.
+        let subscribers = match self.subscribers.get(&topic) {
+            Some(subscribers) => subscribers,
+            None => return,
+        };
+        if self.last_notifications.get(&topic) == Some(&notification) {
+            return;
+        }
+        for subscriber in subscribers {
+            subscriber.do_send(Notify(notification.clone()));
+        }
+
+        if notification.is_final() {
+            // Nothing will change for this topic anymore.
+            self.remove_topic(&topic);
+        } else {
+            self.last_notifications.insert(topic, notification);
+        }
+    }
+
+    fn remove_topic(&mut self, topic: &WsTopic) {
+        if let Some(subscribers) = self.subscribers.remove(topic) {
+            self.subscriptions_count -= subscribers.len();
+        }
+        self.last_notifications.remove(topic);
+    }
+
+    fn remove_subscriber(&mut self, topic: &WsTopic, subscriber: &Addr<WsSubscriber>) {
+        if let Some(subscribers) = self.subscribers.get_mut(topic) {
+            if subscribers.remove(subscriber) {
+                self.subscriptions_count -= 1;
+            }
+            if subscribers.is_empty() {
+                self.remove_topic(topic);
+            }
+        }
+    }
+}
+
+fn tx_topic(tx_hash: TxHash) -> WsTopic {
+    WsTopic::TxStatus {
+        tx_hash: TxHashSerializeWrapper(tx_hash),
+    }
+}
+
+impl Actor for WsMonitor {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(1 << 16);
+
+        let (blocks, executed_ops) =
+            spawn_event_fetcher(self.pool.clone(), self.miniblock_interval);
+        ctx.add_stream(blocks);
+        ctx.add_stream(executed_ops);
+        ctx.run_interval(RESYNC_INTERVAL, |act, ctx| {
+            let tx_hashes = act
+                .subscribers
+                .keys()
+                .map(|topic| match topic {
+                    WsTopic::TxStatus { tx_hash } => tx_hash.0,
+                })
+                .collect();
+            act.load_topics(tx_hashes, ctx);
+        });
+    }
+}
+
+impl StreamHandler<AggregatedOperation> for WsMonitor {
+    fn finished(&mut self, _ctx: &mut Self::Context) {
+        // Topics are still updated by the periodic checks.
+        vlog::error!("Blocks events stream of the WebSocket monitor has finished");
+    }
+
+    fn handle(&mut self, operation: AggregatedOperation, ctx: &mut Self::Context) {
+        let blocks = match operation {
+            AggregatedOperation::CommitBlocks(operation) => operation.blocks,
+            AggregatedOperation::ExecuteBlocks(operation) => operation.blocks,
+            _ => return,
+        };
+        for block in blocks {
+            self.handle_operations(&block.block_transactions, ctx);
+        }
+    }
+}
+
+impl StreamHandler<ExecutedOps> for WsMonitor {
+    fn finished(&mut self, _ctx: &mut Self::Context) {
+        vlog::error!("Executed operations stream of the WebSocket monitor has finished");
+    }
+
+    fn handle(&mut self, executed_ops: ExecutedOps, ctx: &mut Self::Context) {
+        self.handle_operations(&executed_ops.operations, ctx);
+    }
+}
+
+impl Handler<Subscribe> for WsMonitor {
+    type Result = ();
+
+    fn handle(&mut self, msg: Subscribe, ctx: &mut Self::Context) {
+        if self.subscriptions_count >= MAX_SUBSCRIPTIONS {
+            msg.subscriber.do_send(RejectSubscription(msg.topic));
+            return;
+        }
+
+        let subscribers = self.subscribers.entry(msg.topic.clone()).or_default();
+        if !subscribers.insert(msg.subscriber.clone()) {
+            return;
+        }
+        self.subscriptions_count += 1;
+
+        // Newcomers receive the last known state right away, the state
+        // of the new topics is loaded from the storage.
+        match self.last_notifications.get(&msg.topic) {
+            Some(notification) => msg.subscriber.do_send(Notify(notification.clone())),
+            None => match msg.topic {
+                WsTopic::TxStatus { tx_hash } => self.load_topics(vec![tx_hash.0], ctx),
+            },
+        }
+    }
+}
+
+impl Handler<Unsubscribe> for WsMonitor {
+    type Result = ();
+
+    fn handle(&mut self, msg: Unsubscribe, _ctx: &mut Self::Context) {
+        self.remove_subscriber(&msg.topic, &msg.subscriber);
+    }
+}
+
+impl Handler<RemoveSubscriber> for WsMonitor {
+    type Result = ();
+
+    fn handle(&mut self, msg: RemoveSubscriber, _ctx: &mut Self::Context) {
+        let topics: Vec<_> = self.subscribers.keys().cloned().collect();
+        for topic in topics {
+            self.remove_subscriber(&topic, &msg.0);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// Built-in uses
+use std::collections::HashSet;
+// External uses
+use actix::prelude::*;
+use actix_web_actors::ws;
+// Workspace uses
+use zksync_api_types::v02::ws::{WsRequest, WsTopic};
+// Local uses
+use super::{
+    messages::{Notify, RejectSubscription, RemoveSubscriber, Subscribe, Unsubscribe},
+    monitor::WsMonitor,
+};
+
+/// Maximum number of topics a single connection can be subscribed to.
+const MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 100;
+
+/// The WebSocket actor. Created for each connected client.
+#[derive(Debug)]
+pub struct WsSubscriber {
+    /// Topics the client is subscribed to.
+    topics: HashSet<WsTopic>,
+    /// The address of the [`WsMonitor`] that tracks the topics.
+    monitor: Addr<WsMonitor>,
+}
+
+impl WsSubscriber {
+    pub fn new(monitor: Addr<WsMonitor>) -> Self {
+        Self {
+            topics: HashSet::new(),
+            monitor,
+        }
+    }
+
+    fn handle_request(&mut self, request: WsRequest, ctx: &mut <Self as Actor>::Context) {
+        match request {
+            WsRequest::Subscribe { topic } => {
+                if self.topics.contains(&topic) {
+                    return;
+                }
+                if self.topics.len() >= MAX_SUBSCRIPTIONS_PER_CONNECTION {
+                    let reason = Some(ws::CloseReason {
+                        code: ws::CloseCode::Policy,
+                        description: Some(format!(
+                            "Number of subscriptions should be less than or equal to {}",
+                            MAX_SUBSCRIPTIONS_PER_CONNECTION
+                        )),
+                    });
+                    ctx.close(reason);
+                    self.shutdown(ctx);
+                    return;
+                }
+                self.topics.insert(topic.clone());
+                self.monitor.do_send(Subscribe {
+                    topic,
+                    subscriber: ctx.address(),
+                });
+            }
+            WsRequest::Unsubscribe { topic } => {
+                if self.topics.remove(&topic) {
+                    self.monitor.do_send(Unsubscribe {
+                        topic,
+                        subscriber: ctx.address(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Removes the subscriber from the monitor and stops the execution context.
+    /// Should be called instead of `ctx.stop()`.
+    fn shutdown(&mut self, ctx: &mut <Self as Actor>::Context) {
+        self.monitor.do_send(RemoveSubscriber(ctx.address()));
+        ctx.stop();
+    }
+}
+
+impl Actor for WsSubscriber {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(1 << 10);
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for WsSubscriber {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(request) => self.handle_request(request, ctx),
+                Err(err) => {
+                    // The client provided invalid JSON, give
+                    // them the error message and close the connection.
+                    let reason = Some(ws::CloseReason {
+                        code: ws::CloseCode::Policy,
+                        description: Some(err.to_string()),
+                    });
+                    ctx.close(reason);
+                    self.shutdown(ctx);
+                }
+            },
+            Ok(ws::Message::Close(reason)) => {
+                // Send back the close frame.
+                ctx.close(reason);
+                self.shutdown(ctx);
+            }
+            Err(err) => {
+                let reason = Some(ws::CloseReason {
+                    code: ws::CloseCode::Error,
+                    description: Some(err.to_string()),
+                });
+                ctx.close(reason);
+                self.shutdown(ctx);
+            }
+            _ => {}
+        }
+    }
+
+    fn finished(&mut self, ctx: &mut Self::Context) {
+        // The client disconnected without sending the close frame.
+        self.shutdown(ctx);
+    }
+}
+
+impl Handler<Notify> for WsSubscriber {
+    type Result = ();
+
+    fn handle(&mut self, msg: Notify, ctx: &mut Self::Context) {
+        let topic = msg.0.topic();
+        if !self.topics.contains(&topic) {
+            return;
+        }
+        if msg.0.is_final() {
+            // The monitor has already dropped this topic.
+            self.topics.remove(&topic);
+        }
+        let json = serde_json::to_string(&msg.0).unwrap();
+        ctx.text(json);
+    }
+}
+
+impl Handler<RejectSubscription> for WsSubscriber {
+    type Result = ();
+
+    fn handle(&mut self, msg: RejectSubscription, ctx: &mut Self::Context) {
+        if !self.topics.remove(&msg.0) {
+            return;
+        }
+        let reason = Some(ws::CloseReason {
+            code: ws::CloseCode::Policy,
+            description: Some("Server has too many subscriptions, try again later".to_string()),
+        });
+        ctx.close(reason);
+        self.shutdown(ctx);
+    }
+}
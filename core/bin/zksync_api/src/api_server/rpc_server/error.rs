@@ -3,7 +3,7 @@ use jsonrpc_core::ErrorCode;
 use zksync_types::tx::error::TxAddError;
 // Workspace uses
 // Local uses
-use crate::api_server::tx_sender::SubmitError;
+use crate::api_server::tx_sender::{retry_after_secs, SubmitError};
 
 #[derive(Debug, Clone, Copy)]
 pub enum RpcErrorCodes {
@@ -22,6 +22,11 @@ pub enum RpcErrorCodes {
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
     Toggle2FA = 304,
+    ReplaceTx = 305,
+    RateLimited = 306,
+    MempoolFull = 307,
+    TooManyPendingTxs = 308,
+    AddressBlocked = 309,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -41,6 +46,9 @@ impl From<TxAddError> for RpcErrorCodes {
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
             TxAddError::EthSignaturesLimitExceeded => Self::Other,
+            TxAddError::InsufficientBalance => Self::Other,
+            TxAddError::ReplacementFeeTooLow => Self::FeeTooLow,
+            TxAddError::NonceTooFarAhead => Self::NonceMismatch,
         }
     }
 }
@@ -82,6 +90,11 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::ReplaceTx(inner) => Self {
+                code: RpcErrorCodes::ReplaceTx.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::InappropriateFeeToken => Self {
                 code: RpcErrorCodes::InappropriateFeeToken.into(),
                 message: inner.to_string(),
@@ -107,6 +120,58 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: error.to_string(),
                 data: None,
             },
+            SubmitError::RateLimited(retry_after) => Self {
+                code: RpcErrorCodes::RateLimited.into(),
+                message: inner.to_string(),
+                data: Some(serde_json::json!({
+                    "retryAfter": retry_after_secs(&retry_after)
+                })),
+            },
+            SubmitError::MempoolFull { depth, retry_after } => Self {
+                code: RpcErrorCodes::MempoolFull.into(),
+                message: inner.to_string(),
+                data: Some(serde_json::json!({
+                    "mempoolDepth": depth,
+                    "retryAfter": retry_after_secs(&retry_after)
+                })),
+            },
+            SubmitError::FeeTooLow {
+                error,
+                ref token,
+                ref required_fee,
+                ref provided_fee,
+                ref shortfall,
+            } => Self {
+                code: RpcErrorCodes::from(error).into(),
+                message: error.to_string(),
+                data: Some(serde_json::json!({
+                    "token": token,
+                    "requiredFee": required_fee.to_string(),
+                    "providedFee": provided_fee.to_string(),
+                    "shortfall": shortfall.to_string(),
+                })),
+            },
+            SubmitError::TooManyPendingTxs {
+                account_id,
+                pending,
+                limit,
+            } => Self {
+                code: RpcErrorCodes::TooManyPendingTxs.into(),
+                message: inner.to_string(),
+                data: Some(serde_json::json!({
+                    "accountId": account_id,
+                    "pendingTxs": pending,
+                    "limit": limit,
+                })),
+            },
+            SubmitError::AddressBlocked { address, list } => Self {
+                code: RpcErrorCodes::AddressBlocked.into(),
+                message: inner.to_string(),
+                data: Some(serde_json::json!({
+                    "address": address,
+                    "list": list,
+                })),
+            },
         }
     }
 }
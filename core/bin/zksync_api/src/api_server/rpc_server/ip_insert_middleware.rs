@@ -8,7 +8,7 @@ use jsonrpc_http_server::{RequestMiddleware, RequestMiddlewareAction};
 
 use super::types::RequestMetadata;
 
-const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
+pub(crate) const CLOUDFLARE_CONNECTING_IP_HEADER: &str = "CF-Connecting-IP";
 const METADATA_PARAM_NAME: &str = "extracted_request_metadata";
 
 /// Unfortunately, the JSON-RPC library does not natively support retrieving any information about the HTTP request,
@@ -18,6 +18,8 @@ const METADATA_PARAM_NAME: &str = "extracted_request_metadata";
 ///
 /// IpInsertMiddleWare is the middleware that gets the value of the `CF-Connecting-IP` header of the HTTP request and appends it as the last
 /// parameter of the JSON-RPC call.
+///
+/// The middleware doesn't know the address of the peer, so the JSON-RPC API should only be reachable via the trusted proxies.
 pub struct IpInsertMiddleWare;
 
 /// Structure that is used to describe the minimum and the maximum number
@@ -23,14 +23,14 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use crate::{signature_checker::VerifySignatureRequest, utils::shared_lru_cache::AsyncLruCache};
 
 pub mod error;
-mod ip_insert_middleware;
+pub(crate) mod ip_insert_middleware;
 mod rpc_impl;
 mod rpc_trait;
 pub mod types;
 
 pub use self::rpc_trait::Rpc;
 use self::types::*;
-use super::tx_sender::TxSender;
+use super::{tx_sender::TxSender, tx_sender_settings::SharedTxSenderSettings};
 use crate::fee_ticker::FeeTicker;
 use ip_insert_middleware::IpInsertMiddleWare;
 use zksync_mempool::MempoolTransactionRequest;
@@ -47,6 +47,7 @@ pub struct RpcApp {
 }
 
 impl RpcApp {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         connection_pool: ConnectionPool,
         sign_verify_request_sender: mpsc::Sender<VerifySignatureRequest>,
@@ -55,6 +56,7 @@ impl RpcApp {
         token_config: &TokenConfig,
         confirmations_for_eth_event: u64,
         mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
+        tx_sender_settings: SharedTxSenderSettings,
     ) -> Self {
         let api_requests_caches_size = config.caches_size;
 
@@ -65,7 +67,8 @@ impl RpcApp {
             config,
             token_config,
             mempool_tx_sender,
-        );
+        )
+        .with_settings(tx_sender_settings);
 
         RpcApp {
             cache_of_executed_priority_operations: AsyncLruCache::new(api_requests_caches_size),
@@ -266,6 +269,7 @@ pub fn start_rpc_server(
     token_config: &TokenConfig,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    tx_sender_settings: SharedTxSenderSettings,
 ) -> JoinHandle<()> {
     let addr = config.http_bind_addr();
     let rpc_app = RpcApp::new(
@@ -276,6 +280,7 @@ pub fn start_rpc_server(
         token_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        tx_sender_settings,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
@@ -286,7 +291,7 @@ pub fn start_rpc_server(
 
         let server = ServerBuilder::new(io)
             .threads(super::THREADS_PER_SERVER)
-            .request_middleware(IpInsertMiddleWare {})
+            .request_middleware(IpInsertMiddleWare)
             .start_http(&addr)
             .unwrap();
         server.wait();
@@ -8,7 +8,7 @@ use zksync_api_types::{
     v02::{
         fee::ApiTxFeeTypes,
         token::ApiNFT,
-        transaction::{Toggle2FA, Toggle2FAResponse},
+        transaction::{SubmitTxResponse, Toggle2FA, Toggle2FAResponse},
     },
     TxWithSignature,
 };
@@ -143,7 +143,7 @@ impl RpcApp {
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<TxHash> {
+    ) -> Result<SubmitTxResponse> {
         let start = Instant::now();
 
         let result = self
@@ -296,6 +296,9 @@ impl RpcApp {
             .get_fee_from_ticker_in_wei(tx_type.into(), token.clone(), address)
             .await
             .map_err(SubmitError::Internal)?;
+        self.tx_sender
+            .ticker
+            .record_fee_quote(token.clone(), result.normal_fee.clone());
 
         let should_subsidize_cpk = self
             .tx_sender
@@ -309,6 +312,12 @@ impl RpcApp {
 
         let fee = if should_subsidize_cpk {
             result.subsidized_fee
+        } else if let Some(discounted_fee) = self
+            .tx_sender
+            .apply_subsidy_discount(None, &result, token)
+            .await?
+        {
+            discounted_fee.subsidized_fee
         } else {
             result.normal_fee
         };
@@ -350,6 +359,10 @@ impl RpcApp {
             .zip(addresses.iter().cloned()))
         .collect();
 
+        let discount_candidates: Vec<_> = transactions
+            .iter()
+            .map(|(tx_type, address)| (None, *tx_type, *address))
+            .collect();
         let result = self
             .tx_sender
             .ticker
@@ -369,6 +382,12 @@ impl RpcApp {
 
         let fee = if should_subsidize_cpk {
             result.subsidized_fee
+        } else if let Some(discounted_fee) = self
+            .tx_sender
+            .apply_batch_subsidy_discount(&discount_candidates, &result, token)
+            .await?
+        {
+            discounted_fee.subsidized_fee
         } else {
             result.normal_fee
         };
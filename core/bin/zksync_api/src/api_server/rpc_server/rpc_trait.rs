@@ -9,7 +9,7 @@ use zksync_api_types::{
     v02::{
         fee::ApiTxFeeTypes,
         token::ApiNFT,
-        transaction::{Toggle2FA, Toggle2FAResponse},
+        transaction::{SubmitTxResponse, Toggle2FA, Toggle2FAResponse},
     },
     TxWithSignature,
 };
@@ -42,14 +42,14 @@ pub trait Rpc {
     #[rpc(name = "tx_info", returns = "ETHOpInfoResp")]
     fn tx_info(&self, hash: TxHash) -> BoxFutureResult<TransactionInfoResp>;
 
-    #[rpc(name = "tx_submit", returns = "TxHash")]
+    #[rpc(name = "tx_submit", returns = "SubmitTxResponse")]
     fn tx_submit(
         &self,
         tx: Box<ZkSyncTx>,
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> BoxFutureResult<TxHash>;
+    ) -> BoxFutureResult<SubmitTxResponse>;
 
     #[rpc(name = "submit_txs_batch", returns = "Vec<TxHash>")]
     fn submit_txs_batch(
@@ -132,7 +132,7 @@ impl Rpc for RpcApp {
         signature: Box<TxEthSignatureVariant>,
         fast_processing: Option<bool>,
         meta: Option<RequestMetadata>,
-    ) -> BoxFutureResult<TxHash> {
+    ) -> BoxFutureResult<SubmitTxResponse> {
         spawn!(self._impl_tx_submit(tx, signature, fast_processing, meta))
     }
 
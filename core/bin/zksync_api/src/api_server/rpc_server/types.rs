@@ -9,7 +9,11 @@ use zksync_api_types::v02::{
     account::{DepositingAccountBalances, EthAccountType},
     token::NFT,
 };
-use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL};
+use zksync_crypto::{
+    params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID_VAL},
+    serialization::FrSerde,
+    Fr,
+};
 use zksync_storage::StorageProcessor;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{Account, AccountId, Address, Nonce, PubKeyHash, TokenId};
@@ -103,6 +107,17 @@ pub struct BlockInfo {
     pub verified: bool,
 }
 
+/// Event sent to the block subscribers once a block is committed or finalized.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockEventResp {
+    pub block_number: i64,
+    #[serde(with = "FrSerde")]
+    pub new_root_hash: Fr,
+    pub committed: bool,
+    pub verified: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionInfoResp {
@@ -20,7 +20,10 @@ use zksync_utils::panic_notify::{spawn_panic_handler, ThreadPanicNotify};
 use crate::fee_ticker::FeeTicker;
 use crate::{
     api_server::event_notify::{start_sub_notifier, EventNotifierRequest, EventSubscribeRequest},
-    api_server::rpc_server::types::{ETHOpInfoResp, ResponseAccountState, TransactionInfoResp},
+    api_server::rpc_server::types::{
+        BlockEventResp, ETHOpInfoResp, ResponseAccountState, TransactionInfoResp,
+    },
+    api_server::tx_sender_settings::SharedTxSenderSettings,
     signature_checker::VerifySignatureRequest,
 };
 
@@ -82,6 +85,25 @@ pub trait RpcPubSub {
         meta: Option<Self::Metadata>,
         subscription: SubscriptionId,
     ) -> Result<bool>;
+
+    #[pubsub(
+        subscription = "block",
+        subscribe,
+        name = "block_subscribe",
+        alias("block_sub")
+    )]
+    fn subscribe_block(
+        &self,
+        meta: Self::Metadata,
+        subscriber: Subscriber<BlockEventResp>,
+        action_type: ActionType,
+    );
+    #[pubsub(subscription = "block", unsubscribe, name = "block_unsubscribe")]
+    fn unsubscribe_block(
+        &self,
+        meta: Option<Self::Metadata>,
+        subscription: SubscriptionId,
+    ) -> Result<bool>;
 }
 
 impl RpcPubSub for RpcSubApp {
@@ -170,6 +192,29 @@ impl RpcPubSub for RpcSubApp {
             .unwrap_or_default();
         Ok(true)
     }
+
+    fn subscribe_block(
+        &self,
+        _meta: Self::Metadata,
+        subscriber: Subscriber<BlockEventResp>,
+        action: ActionType,
+    ) {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Sub(EventSubscribeRequest::Block {
+                action,
+                subscriber,
+            }))
+            .unwrap_or_default();
+    }
+
+    fn unsubscribe_block(&self, _meta: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+        self.event_sub_sender
+            .clone()
+            .try_send(EventNotifierRequest::Unsub(id))
+            .unwrap_or_default();
+        Ok(true)
+    }
 }
 
 struct RpcSubApp {
@@ -188,6 +233,7 @@ pub fn start_ws_server(
     miniblock_iteration_interval: Duration,
     mempool_tx_sender: mpsc::Sender<MempoolTransactionRequest>,
     confirmations_for_eth_event: u64,
+    tx_sender_settings: SharedTxSenderSettings,
 ) -> JoinHandle<()> {
     let addr = config.ws_bind_addr();
 
@@ -209,6 +255,7 @@ pub fn start_ws_server(
         token_config,
         confirmations_for_eth_event,
         mempool_tx_sender,
+        tx_sender_settings,
     );
 
     let (handler, panic_sender) = spawn_panic_handler();
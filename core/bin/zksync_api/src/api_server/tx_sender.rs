@@ -1,16 +1,18 @@
 //! Helper module to submit transactions into the zkSync Network.
 
 // Built-in uses
-use std::iter::FromIterator;
 use std::{
     collections::{HashMap, HashSet},
     fmt::Display,
+    net::IpAddr,
     str::FromStr,
+    sync::Arc,
+    time::Instant,
 };
 
 // External uses
 use bigdecimal::BigDecimal;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
@@ -19,10 +21,14 @@ use itertools::izip;
 use num::rational::Ratio;
 use num::{bigint::ToBigInt, BigUint, Zero};
 use thiserror::Error;
+use tokio::{sync::Semaphore, task::JoinHandle};
 
 // Workspace uses
 use zksync_api_types::{
-    v02::transaction::{SubmitBatchResponse, Toggle2FA, Toggle2FAResponse, TxHashSerializeWrapper},
+    v02::transaction::{
+        CancelTx, CancelTxResponse, SubmitBatchResponse, SubmitTxResponse, Toggle2FA,
+        Toggle2FAResponse, TxHashSerializeWrapper, TxSignatureCheck,
+    },
     TxWithSignature,
 };
 use zksync_storage::misc::records::Subsidy;
@@ -33,31 +39,50 @@ use zksync_types::{
         EthBatchSignData, EthBatchSignatures, EthSignData, Order, SignedZkSyncTx, TxEthSignature,
         TxEthSignatureVariant, TxHash,
     },
-    AccountId, Address, PubKeyHash, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
+    AccountId, Address, AddressList, FeeExemption, Nonce, OutputFeeType, PubKeyHash, Token,
+    TokenId, TokenLike, TxFeeTypes, ZkSyncTx, H160,
 };
 use zksync_utils::{
     big_decimal_to_ratio, biguint_to_big_decimal, ratio_to_scaled_u64, scaled_big_decimal_to_ratio,
+    scaled_u64_to_ratio,
 };
 
 // Local uses
 use crate::{
-    api_server::forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+    api_server::{
+        compliance::{AddressListChecker, ComplianceHook},
+        forced_exit_checker::{ForcedExitAccountAgeChecker, ForcedExitChecker},
+        rate_limiter::SubmissionRateLimiter,
+        tx_sender_settings::{SharedTxSenderSettings, TxSenderSettings},
+    },
     fee_ticker::{ResponseBatchFee, ResponseFee, TokenPriceRequestType},
     signature_checker::{
-        BatchRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest, VerifiedTx,
-        VerifySignatureRequest,
+        BatchRequest, CancelTxRequest, OrderRequest, RequestData, Toggle2FARequest, TxRequest,
+        VerifiedTx, VerifySignatureRequest,
     },
-    tx_error::Toggle2FAError,
+    tx_error::{ReplaceTxError, Toggle2FAError},
     utils::block_details_cache::BlockDetailsCache,
 };
 use zksync_config::configs::api::{CommonApiConfig, TokenConfig};
-use zksync_mempool::MempoolTransactionRequest;
+use zksync_mempool::{pays_higher_fee, MempoolTransactionRequest};
 use zksync_types::tx::error::TxAddError;
 
 use super::rpc_server::types::RequestMetadata;
 use crate::fee_ticker::{FeeTicker, PriceError};
 
 const VALIDNESS_INTERVAL_MINUTES: i64 = 40;
+/// Maximum length of the client-provided request ID used to deduplicate submissions.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+/// Time after which the request ID can be reused.
+const TX_REQUEST_ID_TTL: std::time::Duration = std::time::Duration::from_secs(24 * 3600);
+/// Time after which the reservation of the request ID whose submission wasn't finished
+/// (e.g. because the server was restarted) can be taken over by a retry.
+const TX_REQUEST_ID_SUBMISSION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(300);
+/// Interval of removing the expired request IDs.
+const TX_REQUEST_IDS_CLEANUP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(600);
+/// Time after which the asynchronous submission may be retried if too many transactions
+/// are being verified.
+const ASYNC_VERIFICATION_RETRY_AFTER: std::time::Duration = std::time::Duration::from_secs(1);
 
 #[derive(Clone)]
 pub struct TxSender {
@@ -70,9 +95,6 @@ pub struct TxSender {
 
     pub forced_exit_checker: ForcedExitChecker,
     pub blocks: BlockDetailsCache,
-    /// List of account IDs that do not have to pay fees for operations.
-    pub fee_free_accounts: HashSet<AccountId>,
-    pub enforce_pubkey_change_fee: bool,
     // Limit the number of both transactions and Ethereum signatures per batch.
     pub max_number_of_transactions_per_batch: usize,
     pub max_number_of_authors_per_batch: usize,
@@ -80,6 +102,21 @@ pub struct TxSender {
     pub current_subsidy_type: String,
     pub max_subsidy_usd: Ratio<BigUint>,
     pub subsidized_ips: HashSet<String>,
+    /// Accounts and fee types which fees are discounted by the current subsidy.
+    pub subsidized_accounts: HashSet<Address>,
+    pub subsidized_tx_types: HashSet<String>,
+    pub subsidy_discount_percent: u32,
+    /// Fee checks, rate limits and mempool caps that may be changed without the restart.
+    pub settings: SharedTxSenderSettings,
+
+    /// Limits the submissions per IP and per sender account.
+    pub rate_limiter: SubmissionRateLimiter,
+    /// Proxies allowed to report the IP of the user.
+    pub trusted_proxies: HashSet<IpAddr>,
+    /// Hook that may veto the submitted transactions.
+    pub compliance_hook: Arc<dyn ComplianceHook>,
+    /// Limits the number of the asynchronously submitted transactions being verified at once.
+    pub async_verifications: Arc<Semaphore>,
 }
 
 #[derive(Debug, Error)]
@@ -100,11 +137,44 @@ pub enum SubmitError {
     // it is helpful to re-use IncorrectEthSignature and DbError
     #[error("Failed to toggle 2FA: {0}.")]
     Toggle2FA(#[from] Toggle2FAError),
+    #[error("Failed to replace transaction: {0}.")]
+    ReplaceTx(#[from] ReplaceTxError),
 
     #[error("Communication error with the mempool: {0}.")]
     MempoolCommunication(String),
     #[error("Price error {0}")]
     PriceError(#[from] PriceError),
+    #[error("Too many transactions submitted, retry after {} seconds.", retry_after_secs(.0))]
+    RateLimited(std::time::Duration),
+    #[error(
+        "Mempool is full ({depth} transactions are queued), retry after {} seconds.",
+        retry_after_secs(.retry_after)
+    )]
+    MempoolFull {
+        depth: usize,
+        retry_after: std::time::Duration,
+    },
+    /// Same as the `TxAdd` error with the fee error, but also reports the fees in the smallest
+    /// units of the fee token, so the wallets can re-quote the fee.
+    #[error("Transaction adding error: {error}.")]
+    FeeTooLow {
+        error: TxAddError,
+        token: String,
+        required_fee: BigUint,
+        provided_fee: BigUint,
+        shortfall: BigUint,
+    },
+    #[error(
+        "Account {account_id} has too many pending transactions ({pending} are queued, \
+         the limit is {limit}), wait for them to be executed."
+    )]
+    TooManyPendingTxs {
+        account_id: AccountId,
+        pending: usize,
+        limit: usize,
+    },
+    #[error("Transaction involves the address {address:?} blocked on the {list} list.")]
+    AddressBlocked { address: Address, list: AddressList },
     #[error("Internal error.")]
     Internal(#[from] anyhow::Error),
     #[error("{0}")]
@@ -127,6 +197,117 @@ impl SubmitError {
     pub fn invalid_params(msg: impl Display) -> Self {
         Self::InvalidParams(msg.to_string())
     }
+
+    fn fee_too_low(
+        error: TxAddError,
+        token: &Token,
+        required_fee: BigUint,
+        provided_fee: BigUint,
+    ) -> Self {
+        // Doesn't underflow even if the provided fee covers the required one.
+        let shortfall = if required_fee > provided_fee {
+            &required_fee - &provided_fee
+        } else {
+            BigUint::from(0u32)
+        };
+        Self::FeeTooLow {
+            error,
+            token: token.symbol.clone(),
+            shortfall,
+            required_fee,
+            provided_fee,
+        }
+    }
+
+    /// Short label of the rejection reason used in the metrics.
+    /// Unlike the error message, it doesn't contain any submission-specific data.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            Self::AccountCloseDisabled => "account_close_disabled",
+            Self::InvalidParams(_) => "invalid_params",
+            Self::UnsupportedFastProcessing => "unsupported_fast_processing",
+            Self::IncorrectTx(_) => "incorrect_tx",
+            Self::TxAdd(err) => match err {
+                TxAddError::NonceMismatch => "nonce_mismatch",
+                TxAddError::IncorrectTx(_) => "incorrect_tx",
+                TxAddError::TxFeeTooLow | TxAddError::TxBatchFeeTooLow => "fee_too_low",
+                TxAddError::EIP1271SignatureVerificationFail
+                | TxAddError::IncorrectEthSignature => "incorrect_eth_signature",
+                TxAddError::MissingEthSignature => "missing_eth_signature",
+                TxAddError::ChangePkNotAuthorized => "change_pk_not_authorized",
+                TxAddError::Other | TxAddError::DbError => "internal",
+                TxAddError::EmptyBatch => "empty_batch",
+                TxAddError::BatchTooBig | TxAddError::EthSignaturesLimitExceeded => "batch_too_big",
+                TxAddError::BatchWithdrawalsOverload => "batch_withdrawals_overload",
+                TxAddError::InsufficientBalance => "insufficient_balance",
+                TxAddError::ReplacementFeeTooLow => "replacement_fee_too_low",
+                TxAddError::NonceTooFarAhead => "nonce_too_far_ahead",
+            },
+            Self::InappropriateFeeToken => "inappropriate_fee_token",
+            Self::Toggle2FA(_) => "toggle_2fa",
+            Self::ReplaceTx(_) => "replace_tx",
+            Self::MempoolCommunication(_) | Self::Internal(_) | Self::Other(_) => "internal",
+            Self::PriceError(_) => "price_error",
+            Self::RateLimited(_) => "rate_limited",
+            Self::MempoolFull { .. } => "mempool_full",
+            Self::FeeTooLow { .. } => "fee_too_low",
+            Self::TooManyPendingTxs { .. } => "too_many_pending_txs",
+            Self::AddressBlocked { .. } => "address_blocked",
+        }
+    }
+
+    /// Checks whether the error is caused by the failure of the server or of its dependencies
+    /// (the database, the mempool or the token price sources) rather than by the transaction,
+    /// so the same transaction may be accepted if it's submitted again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::TxAdd(err) => matches!(err, TxAddError::Other | TxAddError::DbError),
+            Self::PriceError(err) => !matches!(err, PriceError::TokenNotFound(_)),
+            Self::MempoolCommunication(_) | Self::Internal(_) | Self::Other(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// Reports the outcome of the submission of the transactions of the given types.
+/// Every transaction of the rejected batch is reported as rejected.
+fn report_submission<T>(tx_types: &[String], result: &Result<T, SubmitError>) {
+    let outcome = if result.is_ok() {
+        "accepted"
+    } else {
+        "rejected"
+    };
+    for tx_type in tx_types {
+        metrics::increment_counter!("api.tx_sender.submitted_txs", "type" => tx_type.clone(), "result" => outcome);
+        if let Err(err) = result {
+            metrics::increment_counter!("api.tx_sender.rejected_txs", "type" => tx_type.clone(), "reason" => err.reason());
+        }
+    }
+}
+
+/// Finds the account that would exceed the limit of the transactions in the mempool and returns
+/// it along with the number of its queued transactions. The submitted transactions with the nonces
+/// of the queued ones are replacements, so they don't increase the number of the pending transactions.
+fn find_pending_txs_overflow(
+    submitted: &HashMap<AccountId, HashSet<Nonce>>,
+    queued: &HashMap<AccountId, HashSet<Nonce>>,
+    limit: usize,
+) -> Option<(AccountId, usize)> {
+    let no_nonces = HashSet::new();
+    submitted.iter().find_map(|(account_id, nonces)| {
+        let queued = queued.get(account_id).unwrap_or(&no_nonces);
+        let new_txs = nonces.difference(queued).count();
+        if new_txs > 0 && queued.len() + new_txs > limit {
+            Some((*account_id, queued.len()))
+        } else {
+            None
+        }
+    })
+}
+
+/// Number of whole seconds the client should wait before retrying the submission.
+pub fn retry_after_secs(retry_after: &std::time::Duration) -> u64 {
+    retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0)
 }
 
 #[macro_export]
@@ -156,6 +337,8 @@ impl TxSender {
 
         Self {
             mempool_tx_sender,
+            rate_limiter: SubmissionRateLimiter::new(connection_pool.clone()),
+            compliance_hook: Arc::new(AddressListChecker::new(connection_pool.clone())),
             pool: connection_pool,
             sign_verify_requests: sign_verify_request_sender,
             ticker,
@@ -163,18 +346,183 @@ impl TxSender {
             forced_exit_checker: ForcedExitChecker::new(
                 config.forced_exit_minimum_account_age_secs,
             ),
-            enforce_pubkey_change_fee: config.enforce_pubkey_change_fee,
             blocks: BlockDetailsCache::new(config.caches_size),
 
-            fee_free_accounts: HashSet::from_iter(config.fee_free_accounts.clone()),
             max_number_of_transactions_per_batch,
             max_number_of_authors_per_batch,
             current_subsidy_type: config.subsidy_name.clone(),
             max_subsidy_usd: config.max_subsidy_usd(),
             subsidized_ips: config.subsidized_ips.clone().into_iter().collect(),
+            subsidized_accounts: config.subsidized_accounts.iter().copied().collect(),
+            subsidized_tx_types: config.subsidized_tx_types.iter().cloned().collect(),
+            subsidy_discount_percent: config.subsidy_discount_percent.min(100),
+            settings: SharedTxSenderSettings::new(config),
+            trusted_proxies: config.trusted_proxies.iter().copied().collect(),
+            async_verifications: Arc::new(Semaphore::new(config.signature_verifier_queue_size)),
         }
     }
 
+    /// Replaces the default compliance hook checking the address lists from the database.
+    pub fn with_compliance_hook(mut self, hook: impl ComplianceHook) -> Self {
+        self.compliance_hook = Arc::new(hook);
+        self
+    }
+
+    /// Replaces the settings taken from the config with the shared ones,
+    /// which are kept updated by `SharedTxSenderSettings::keep_updated`.
+    pub fn with_settings(mut self, settings: SharedTxSenderSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Rejects the submission if the IP of the request or one of the senders
+    /// exceeded the submission rate limit.
+    fn check_rate_limits(
+        &self,
+        settings: &TxSenderSettings,
+        extracted_request_metadata: &Option<RequestMetadata>,
+        senders: &[Address],
+    ) -> Result<(), SubmitError> {
+        let ip = extracted_request_metadata
+            .as_ref()
+            .map(|meta| meta.ip.as_str());
+        self.rate_limiter.check(settings, ip, senders)
+    }
+
+    /// Rejects the submission with the given error if the provided fee doesn't cover the required one
+    /// even when scaled up by the configured tolerance. The fees are compared in USD, so the tolerance
+    /// of at least 1 cent is the same for all the tokens.
+    async fn check_fee(
+        &self,
+        settings: &TxSenderSettings,
+        error: TxAddError,
+        token: &Token,
+        required_fee: &BigUint,
+        provided_fee: &BigUint,
+    ) -> Result<(), SubmitError> {
+        let token_price_in_usd = self
+            .ticker
+            .get_token_price(TokenLike::Id(token.id), TokenPriceRequestType::USDForOneWei)
+            .await?;
+        // Converting `BigUint` to `BigInt` is safe.
+        let required = BigDecimal::from(required_fee.to_bigint().unwrap()) * &token_price_in_usd;
+        let provided = BigDecimal::from(provided_fee.to_bigint().unwrap()) * &token_price_in_usd;
+        // Scaling the provided fee since the price may change between signing the transaction and sending it to the server.
+        let scaled_provided = scale_user_fee_up(provided, settings.fee_tolerance_percent);
+        if required > scaled_provided {
+            vlog::debug!(
+                "User provided fee in {} is too low, required: {}, provided: {}",
+                token.symbol,
+                required_fee,
+                provided_fee,
+            );
+            metrics::increment_counter!("api.tx_sender.underpriced_txs");
+            return Err(SubmitError::fee_too_low(
+                error,
+                token,
+                required_fee.clone(),
+                provided_fee.clone(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Records the transaction accepted without the fee check to the audit log.
+    async fn store_fee_exemption_usage(
+        &self,
+        tx: &ZkSyncTx,
+        exemption: &FeeExemption,
+    ) -> Result<(), SubmitError> {
+        vlog::info!(
+            "Transaction {} is exempted from the fee check by the {} exemption {}",
+            tx.hash(),
+            exemption.kind(),
+            exemption.value()
+        );
+        metrics::increment_counter!("api.tx_sender.fee_exempted_txs");
+
+        self.pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .misc_schema()
+            .store_fee_exemption_usage(tx.hash(), tx.account(), &tx.variance_name(), exemption)
+            .await
+            .map_err(SubmitError::internal)
+    }
+
+    /// Rejects the submission if the mempool can't fit the submitted transactions.
+    async fn check_mempool_capacity(
+        &self,
+        settings: &TxSenderSettings,
+        txs_count: usize,
+    ) -> Result<(), SubmitError> {
+        if settings.mempool_capacity == 0 {
+            return Ok(());
+        }
+
+        let depth = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .chain()
+            .mempool_schema()
+            .get_mempool_size()
+            .await
+            .map_err(SubmitError::internal)? as usize;
+        if depth + txs_count > settings.mempool_capacity {
+            metrics::increment_counter!("api.tx_sender.mempool_full");
+            return Err(SubmitError::MempoolFull {
+                depth,
+                retry_after: settings.mempool_full_retry_after(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Rejects the submission if one of the accounts would have more than the allowed number
+    /// of transactions in the mempool.
+    async fn check_pending_txs_limit<'a>(
+        &self,
+        settings: &TxSenderSettings,
+        txs: impl IntoIterator<Item = &'a ZkSyncTx>,
+    ) -> Result<(), SubmitError> {
+        if settings.max_pending_txs_per_account == 0 {
+            return Ok(());
+        }
+
+        let mut submitted = HashMap::<AccountId, HashSet<Nonce>>::new();
+        for tx in txs {
+            if let Ok(account_id) = tx.account_id() {
+                submitted.entry(account_id).or_default().insert(tx.nonce());
+            }
+        }
+        let account_ids: Vec<_> = submitted.keys().copied().collect();
+        let queued = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .chain()
+            .mempool_schema()
+            .get_queued_nonces(&account_ids)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        if let Some((account_id, pending)) =
+            find_pending_txs_overflow(&submitted, &queued, settings.max_pending_txs_per_account)
+        {
+            metrics::increment_counter!("api.tx_sender.too_many_pending_txs");
+            return Err(SubmitError::TooManyPendingTxs {
+                account_id,
+                pending,
+                limit: settings.max_pending_txs_per_account,
+            });
+        }
+        Ok(())
+    }
+
     /// If `ForcedExit` has Ethereum siganture (e.g. it's a part of a batch), an actual signer
     /// is initiator, not the target, thus, this function will perform a database query to acquire
     /// the corresponding address.
@@ -259,18 +607,7 @@ impl TxSender {
         &self,
         toggle_2fa: Toggle2FA,
     ) -> Result<(), SubmitError> {
-        let current_time = Utc::now();
-        let request_time = toggle_2fa.timestamp;
-        let validness_interval = Duration::minutes(VALIDNESS_INTERVAL_MINUTES);
-
-        if current_time - validness_interval > request_time
-            || current_time + validness_interval < request_time
-        {
-            return Err(SubmitError::InvalidParams(format!(
-                "Timestamp differs by more than {} minutes",
-                VALIDNESS_INTERVAL_MINUTES
-            )));
-        }
+        check_request_timestamp(toggle_2fa.timestamp)?;
 
         let message = toggle_2fa.get_ethereum_sign_message().into_bytes();
 
@@ -295,6 +632,129 @@ impl TxSender {
         Ok(())
     }
 
+    /// Loads the transaction which is still awaiting in the mempool and thus can be replaced.
+    async fn get_replaceable_tx(&self, tx_hash: TxHash) -> Result<SignedZkSyncTx, SubmitError> {
+        self.pool
+            .access_storage()
+            .await
+            .map_err(|_| SubmitError::ReplaceTx(ReplaceTxError::DbError))?
+            .chain()
+            .mempool_schema()
+            .get_single_tx(tx_hash.as_ref())
+            .await
+            .map_err(|_| SubmitError::ReplaceTx(ReplaceTxError::DbError))?
+            .ok_or(SubmitError::ReplaceTx(ReplaceTxError::NotQueued))
+    }
+
+    /// Removes the queued transaction, fails if it was already taken into the pending block.
+    async fn remove_queued_tx(&self, tx_hash: TxHash) -> Result<(), SubmitError> {
+        let removed = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|_| SubmitError::ReplaceTx(ReplaceTxError::DbError))?
+            .chain()
+            .mempool_schema()
+            .remove_queued_tx(tx_hash)
+            .await
+            .map_err(|_| SubmitError::ReplaceTx(ReplaceTxError::DbError))?;
+        if !removed {
+            return Err(SubmitError::ReplaceTx(ReplaceTxError::NotQueued));
+        }
+        Ok(())
+    }
+
+    /// Removes the queued transaction from the mempool.
+    /// The request has to be signed by the Ethereum key of the transaction sender.
+    pub async fn cancel_tx(
+        &self,
+        tx_hash: TxHash,
+        cancel_tx: CancelTx,
+    ) -> Result<CancelTxResponse, SubmitError> {
+        let queued_tx = self.get_replaceable_tx(tx_hash).await?;
+
+        if matches!(
+            self.get_tx_sender_type(&queued_tx.tx).await?,
+            EthAccountType::CREATE2
+        ) {
+            return Err(SubmitError::ReplaceTx(ReplaceTxError::CREATE2));
+        }
+
+        check_request_timestamp(cancel_tx.timestamp)?;
+
+        let message = cancel_tx.get_ethereum_sign_message(tx_hash).into_bytes();
+        let signer = self
+            .get_tx_sender(&queued_tx.tx)
+            .await
+            .or(Err(SubmitError::ReplaceTx(ReplaceTxError::DbError)))?;
+
+        let eth_sign_data = EthSignData {
+            signature: cancel_tx.signature,
+            message,
+        };
+        let (sender, receiever) = oneshot::channel();
+
+        let request = VerifySignatureRequest {
+            data: RequestData::CancelTx(CancelTxRequest {
+                sign_data: eth_sign_data,
+                sender: signer,
+            }),
+            response: sender,
+        };
+
+        send_verify_request_and_recv(request, self.sign_verify_requests.clone(), receiever).await?;
+
+        self.remove_queued_tx(tx_hash).await?;
+        Ok(CancelTxResponse { success: true })
+    }
+
+    /// Replaces the queued transaction with a new one from the same account and with the same nonce.
+    /// The new transaction goes through all the checks performed by `submit_tx`, the mempool swaps
+    /// the transactions atomically unless the queued one is already taken into the pending block.
+    /// As with the resubmission with the same nonce (see `submit_tx`), the new transaction has to pay
+    /// a higher fee in the same token, so the replacement can't be used to lower the fee.
+    pub async fn replace_tx(
+        &self,
+        tx_hash: TxHash,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitTxResponse, SubmitError> {
+        let queued_tx = self.get_replaceable_tx(tx_hash).await?;
+
+        if tx.hash() == tx_hash {
+            return Err(SubmitError::ReplaceTx(ReplaceTxError::SameTx));
+        }
+        if queued_tx.tx.account_id().ok() != tx.account_id().ok()
+            || queued_tx.tx.nonce() != tx.nonce()
+        {
+            return Err(SubmitError::ReplaceTx(
+                ReplaceTxError::AccountOrNonceMismatch,
+            ));
+        }
+        if !pays_higher_fee(&tx, &queued_tx.tx) {
+            return Err(SubmitError::TxAdd(TxAddError::ReplacementFeeTooLow));
+        }
+
+        let tx_types = [tx.variance_name()];
+        let result = async {
+            let settings = self.settings.current();
+            // Replacement doesn't increase the number of the queued transactions.
+            self.check_rate_limits(&settings, &extracted_request_metadata, &[tx.account()])?;
+            self.verify_and_queue_tx(
+                &settings,
+                tx,
+                signature,
+                extracted_request_metadata,
+                Some(tx_hash),
+            )
+            .await
+        }
+        .await;
+        report_submission(&tx_types, &result);
+        result
+    }
+
     async fn verify_order_eth_signature(
         &self,
         order: &Order,
@@ -362,7 +822,7 @@ impl TxSender {
         signature: TxEthSignatureVariant,
         fast_processing: Option<bool>,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<TxHash, SubmitError> {
+    ) -> Result<SubmitTxResponse, SubmitError> {
         let fast_processing = fast_processing.unwrap_or(false);
         if fast_processing && !tx.is_withdraw() {
             return Err(SubmitError::UnsupportedFastProcessing);
@@ -396,6 +856,22 @@ impl TxSender {
         result
     }
 
+    /// Returns the budget of the current subsidy. The budget stored in the database
+    /// takes precedence over the one from the config.
+    pub async fn subsidy_budget_usd(&self) -> Result<Ratio<BigUint>, anyhow::Error> {
+        let budget = self
+            .pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .get_subsidy_budget(&self.current_subsidy_type)
+            .await?;
+
+        Ok(budget
+            .map(scaled_u64_to_ratio)
+            .unwrap_or_else(|| self.max_subsidy_usd.clone()))
+    }
+
     pub async fn can_subsidize(
         &self,
         new_subsidy_usd: Ratio<BigUint>,
@@ -408,9 +884,10 @@ impl TxSender {
             .get_total_used_subsidy_for_type(&self.current_subsidy_type)
             .await?;
         let subsidized_already_usd = scaled_big_decimal_to_ratio(subsidized_already)?;
+        let budget_usd = self.subsidy_budget_usd().await?;
 
-        let result = if self.max_subsidy_usd > subsidized_already_usd {
-            &self.max_subsidy_usd - &subsidized_already_usd >= new_subsidy_usd
+        let result = if budget_usd > subsidized_already_usd {
+            &budget_usd - &subsidized_already_usd >= new_subsidy_usd
         } else {
             false
         };
@@ -438,66 +915,668 @@ impl TxSender {
                 .await
                 .map_err(SubmitError::Internal)?;
 
-        Ok(result)
+        Ok(result)
+    }
+
+    /// Checks whether the fee of the given type paid by the given account is discounted by
+    /// the current subsidy. The sender is not known for the fee quotes, so only the fee type
+    /// is checked for them.
+    pub fn is_fee_discounted(&self, sender: Option<Address>, fee_type: OutputFeeType) -> bool {
+        sender.map_or(false, |sender| self.subsidized_accounts.contains(&sender))
+            || self.subsidized_tx_types.contains(fee_type.name())
+    }
+
+    /// Applies the discount of the current subsidy to the fee if either the sender of the transaction
+    /// or its fee type is subsidized and the remaining budget covers the discount.
+    ///
+    /// Returns the fee data with the discounted fee set as `subsidized_fee`.
+    pub async fn apply_subsidy_discount(
+        &self,
+        sender: Option<Address>,
+        fee_data: &ResponseFee,
+        token: TokenLike,
+    ) -> Result<Option<ResponseFee>, SubmitError> {
+        if !self.is_fee_discounted(sender, fee_data.normal_fee.fee_type) {
+            return Ok(None);
+        }
+        let discount = self
+            .subsidy_discount(&fee_data.normal_fee.total_fee, token)
+            .await?;
+
+        Ok(discount.map(|(discounted_fee, subsidy_size_usd)| {
+            let mut subsidized_fee = fee_data.normal_fee.clone();
+            subsidized_fee.total_fee = discounted_fee;
+            ResponseFee {
+                subsidized_fee,
+                subsidy_size_usd,
+                ..fee_data.clone()
+            }
+        }))
+    }
+
+    /// Same as `apply_subsidy_discount`, but for the batch of the transactions given as their senders,
+    /// fee types and recipients. The discount is applied only if the fee of every transaction is discounted.
+    pub async fn apply_batch_subsidy_discount(
+        &self,
+        txs: &[(Option<Address>, TxFeeTypes, Address)],
+        fee_data: &ResponseBatchFee,
+        token: TokenLike,
+    ) -> Result<Option<ResponseBatchFee>, SubmitError> {
+        if txs.is_empty() {
+            return Ok(None);
+        }
+        for (sender, tx_type, recipient) in txs {
+            let fee_type = self
+                .ticker
+                .output_fee_type(*tx_type, *recipient)
+                .await
+                .map_err(SubmitError::Internal)?;
+            if !self.is_fee_discounted(*sender, fee_type) {
+                return Ok(None);
+            }
+        }
+        let discount = self
+            .subsidy_discount(&fee_data.normal_fee.total_fee, token)
+            .await?;
+
+        Ok(discount.map(|(discounted_fee, subsidy_size_usd)| {
+            let mut subsidized_fee = fee_data.normal_fee.clone();
+            subsidized_fee.total_fee = discounted_fee;
+            ResponseBatchFee {
+                subsidized_fee,
+                subsidy_size_usd,
+                ..fee_data.clone()
+            }
+        }))
+    }
+
+    /// Returns the fee discounted by the current subsidy along with the size of the discount in USD,
+    /// or `None` if the remaining budget doesn't cover the discount.
+    async fn subsidy_discount(
+        &self,
+        normal_fee: &BigUint,
+        token: TokenLike,
+    ) -> Result<Option<(BigUint, Ratio<BigUint>)>, SubmitError> {
+        let discount =
+            normal_fee * BigUint::from(self.subsidy_discount_percent) / BigUint::from(100u32);
+        if discount.is_zero() {
+            return Ok(None);
+        }
+
+        let token_price_in_usd = self
+            .ticker
+            .get_token_price(token, TokenPriceRequestType::USDForOneWei)
+            .await?;
+        let subsidy_size_usd =
+            big_decimal_to_ratio(&token_price_in_usd).map_err(SubmitError::Internal)? * &discount;
+        if !self
+            .can_subsidize(subsidy_size_usd.clone())
+            .await
+            .map_err(SubmitError::Internal)?
+        {
+            return Ok(None);
+        }
+
+        Ok(Some((normal_fee - discount, subsidy_size_usd)))
+    }
+
+    pub async fn store_subsidy_data(
+        &self,
+        hash: TxHash,
+        normal_fee: BigUint,
+        subsidized_fee: BigUint,
+        token_id: TokenId,
+    ) -> Result<(), anyhow::Error> {
+        let token_price_in_usd = self
+            .ticker
+            .get_token_price(TokenLike::Id(token_id), TokenPriceRequestType::USDForOneWei)
+            .await?;
+
+        let full_cost_usd = big_decimal_to_ratio(&token_price_in_usd)? * &normal_fee;
+        let subsidized_cost_usd = big_decimal_to_ratio(&token_price_in_usd)? * &subsidized_fee;
+        if full_cost_usd < subsidized_cost_usd {
+            return Err(anyhow::Error::msg(
+                "Trying to subsidize transaction which should not be subsidized",
+            ));
+        }
+
+        let subsidy = Subsidy {
+            usd_amount_scaled: ratio_to_scaled_u64(&full_cost_usd - &subsidized_cost_usd),
+            full_cost_usd_scaled: ratio_to_scaled_u64(full_cost_usd),
+            token_id,
+            token_amount: biguint_to_big_decimal(subsidized_fee),
+            full_cost_token: biguint_to_big_decimal(normal_fee),
+            subsidy_type: self.current_subsidy_type.clone(),
+            tx_hash: hash,
+        };
+
+        self.pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .store_subsidy(subsidy)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Submits the transaction to the mempool. The response contains the hash of the queued transaction
+    /// of the same account with the same nonce, if the submitted transaction has replaced it by paying a higher fee.
+    pub async fn submit_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitTxResponse, SubmitError> {
+        let tx_types = [tx.variance_name()];
+        let result = self
+            .process_tx(tx, signature, extracted_request_metadata)
+            .await;
+        report_submission(&tx_types, &result);
+        result
+    }
+
+    async fn process_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitTxResponse, SubmitError> {
+        let labels = vec![
+            ("stage", "api".to_string()),
+            ("name", tx.variance_name()),
+            ("token", tx.token_id().to_string()),
+        ];
+        // The initial state of processing tx
+        metrics::increment_counter!("process_tx_count", &labels);
+
+        // The same settings are used for the whole submission, even if they are reloaded meanwhile.
+        let settings = self.settings.current();
+        self.check_mempool_capacity(&settings, 1).await?;
+        self.check_pending_txs_limit(&settings, Some(&tx)).await?;
+        self.check_rate_limits(&settings, &extracted_request_metadata, &[tx.account()])?;
+
+        self.verify_and_queue_tx(&settings, tx, signature, extracted_request_metadata, None)
+            .await
+    }
+
+    /// Performs only the cheap checks of the transaction and returns its hash right away.
+    /// The signatures and the fee are verified in the background, meanwhile the receipt of
+    /// the transaction has the `PendingVerification` status. The transaction that fails
+    /// the verification never reaches the mempool and its receipt gets the `RejectedPreBlock`
+    /// status. If the verification can't be completed because of the server failure,
+    /// the transaction is forgotten and may be submitted again. The transaction left pending
+    /// by the server restart is forgotten after `ASYNC_VERIFICATION_TIMEOUT_SECS`.
+    pub async fn submit_tx_async(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxHash, SubmitError> {
+        let tx_types = [tx.variance_name()];
+        let tx_hash = tx.hash();
+        let created_at = Utc::now();
+        let settings = self.settings.current();
+        let admission = async {
+            if tx.is_close() {
+                return Err(SubmitError::AccountCloseDisabled);
+            }
+            self.check_mempool_capacity(&settings, 1).await?;
+            self.check_rate_limits(&settings, &extracted_request_metadata, &[tx.account()])?;
+            let permit = self
+                .async_verifications
+                .clone()
+                .try_acquire_owned()
+                .map_err(|_| SubmitError::RateLimited(ASYNC_VERIFICATION_RETRY_AFTER))?;
+            self.pool
+                .access_storage()
+                .await
+                .map_err(SubmitError::internal)?
+                .chain()
+                .mempool_schema()
+                .store_async_submitted_tx(tx_hash, created_at)
+                .await
+                .map_err(SubmitError::internal)?;
+            Ok(permit)
+        }
+        .await;
+        // Accepted submissions are reported once the verification is completed.
+        if admission.is_err() {
+            report_submission(&tx_types, &admission);
+        }
+        let permit = admission?;
+
+        let tx_sender = self.clone();
+        tokio::spawn(async move {
+            let result = async {
+                tx_sender
+                    .check_pending_txs_limit(&settings, Some(&tx))
+                    .await?;
+                tx_sender
+                    .verify_and_queue_tx(&settings, tx, signature, extracted_request_metadata, None)
+                    .await
+            }
+            .await;
+            drop(permit);
+            report_submission(&tx_types, &result);
+
+            let mut storage = match tx_sender.pool.access_storage().await {
+                Ok(storage) => storage,
+                Err(err) => {
+                    vlog::error!(
+                        "Can't update the asynchronously submitted transaction {}: {}",
+                        tx_hash,
+                        err
+                    );
+                    return;
+                }
+            };
+            let mut schema = storage.chain().mempool_schema();
+            let updated = match result {
+                Ok(_) => schema.remove_async_submitted_tx(tx_hash).await,
+                Err(err) if err.is_transient() => {
+                    vlog::warn!(
+                        "Verification of the transaction {} has failed: {}",
+                        tx_hash,
+                        err
+                    );
+                    schema.remove_async_submitted_tx(tx_hash).await
+                }
+                Err(err) => {
+                    vlog::debug!(
+                        "Transaction {} is rejected before the block: {}",
+                        tx_hash,
+                        err
+                    );
+                    schema
+                        .reject_async_submitted_tx(tx_hash, &err.to_string())
+                        .await
+                }
+            };
+            if let Err(err) = updated {
+                vlog::error!(
+                    "Can't update the asynchronously submitted transaction {}: {}",
+                    tx_hash,
+                    err
+                );
+            }
+        });
+        Ok(tx_hash)
+    }
+
+    /// Verifies the transaction which passed the submission limits and sends it to the mempool.
+    /// If the hash of the queued transaction to replace is given, the mempool swaps it with
+    /// the verified transaction.
+    async fn verify_and_queue_tx(
+        &self,
+        settings: &TxSenderSettings,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+        replaced_tx_hash: Option<TxHash>,
+    ) -> Result<SubmitTxResponse, SubmitError> {
+        let (verified_tx, fee_data_for_subsidy, fee_exemption, token) = self
+            .check_tx(settings, tx.clone(), signature, extracted_request_metadata)
+            .await?;
+
+        let mut mempool_sender = self.mempool_tx_sender.clone();
+        let replaced_tx_hash = match replaced_tx_hash {
+            Some(replaced_tx_hash) => {
+                let (sender, receiver) = oneshot::channel();
+                let item = MempoolTransactionRequest::ReplaceTx(
+                    replaced_tx_hash,
+                    Box::new(verified_tx),
+                    sender,
+                );
+                mempool_sender
+                    .send(item)
+                    .await
+                    .map_err(SubmitError::internal)?;
+
+                if !receiver.await.map_err(SubmitError::internal)?? {
+                    return Err(SubmitError::ReplaceTx(ReplaceTxError::NotQueued));
+                }
+                Some(replaced_tx_hash)
+            }
+            None => {
+                let (sender, receiver) = oneshot::channel();
+                let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
+                mempool_sender
+                    .send(item)
+                    .await
+                    .map_err(SubmitError::internal)?;
+
+                receiver.await.map_err(SubmitError::internal)??
+            }
+        };
+
+        // Only the transactions accepted by the mempool are recorded to the audit log.
+        if let Some(exemption) = &fee_exemption {
+            self.store_fee_exemption_usage(&tx, exemption).await?;
+        }
+
+        // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
+        if let Some(fee_data_for_subsidy) = fee_data_for_subsidy {
+            // The following two bad scenarios are possible when applying subsidy for the tx:
+            // - The subsidy is stored, but the tx is then rejected by the state keeper
+            // - The tx is accepted by the state keeper, but the the `store_subsidy_data` returns an error for some reason
+            //
+            // Trying to omit these scenarios unfortunately leads to large code restructure
+            // which is not worth it for subsidies (we prefer stability here)
+            self.store_subsidy_data(
+                tx.hash(),
+                fee_data_for_subsidy.normal_fee.total_fee,
+                fee_data_for_subsidy.subsidized_fee.total_fee,
+                token.id,
+            )
+            .await
+            .map_err(|e| {
+                metrics::increment_counter!("tx_sender.submit_tx.store_subsidy_data_fail");
+                SubmitError::Other(format!(
+                    "Failed to store the subsidy to database. Reason: {}",
+                    e
+                ))
+            })?;
+        }
+
+        // if everything is OK, return the transactions hashes.
+        Ok(SubmitTxResponse {
+            tx_hash: TxHashSerializeWrapper(tx.hash()),
+            replaced_tx_hash: replaced_tx_hash.map(TxHashSerializeWrapper),
+        })
+    }
+
+    /// Same as `submit_tx`, but deduplicates the submissions by the client-provided request ID.
+    /// If a transaction of the same account was already accepted with this ID, its hash is returned
+    /// without submitting the transaction again (and without the hash of the replaced transaction),
+    /// so clients can safely retry the request.
+    /// Request IDs expire after `TX_REQUEST_ID_TTL`.
+    pub async fn submit_tx_with_request_id(
+        &self,
+        request_id: String,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitTxResponse, SubmitError> {
+        if request_id.is_empty() || request_id.len() > MAX_REQUEST_ID_LENGTH {
+            return Err(SubmitError::invalid_params(format!(
+                "request ID must be from 1 to {} characters long",
+                MAX_REQUEST_ID_LENGTH
+            )));
+        }
+
+        // Close operation does not exist so we will never met this error
+        let account_id = tx.account_id().or(Err(SubmitError::AccountCloseDisabled))?;
+        let tx_hash = tx.hash();
+        // The request ID is reserved before the submission, so the concurrent retries don't submit
+        // the transaction again. The reservation is released if the transaction is rejected.
+        let pending_before = Utc::now()
+            - Duration::from_std(TX_REQUEST_ID_SUBMISSION_TIMEOUT)
+                .map_err(SubmitError::internal)?;
+        let reserved_tx = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?
+            .misc_schema()
+            .reserve_tx_request_id(account_id, &request_id, tx_hash, pending_before)
+            .await
+            .map_err(SubmitError::internal)?;
+        if let Some(reserved_tx) = reserved_tx {
+            if reserved_tx.tx_hash != tx_hash {
+                return Err(SubmitError::invalid_params(
+                    "request ID was already used for another transaction",
+                ));
+            }
+            if !reserved_tx.submitted {
+                return Err(SubmitError::other(
+                    "transaction with this request ID is being submitted, retry later",
+                ));
+            }
+            return Ok(SubmitTxResponse {
+                tx_hash: TxHashSerializeWrapper(tx_hash),
+                replaced_tx_hash: None,
+            });
+        }
+
+        let result = self
+            .submit_tx(tx, signature, extracted_request_metadata)
+            .await;
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let mut misc_schema = storage.misc_schema();
+        match &result {
+            Ok(_) => misc_schema
+                .mark_tx_request_id_submitted(account_id, &request_id)
+                .await
+                .map_err(SubmitError::internal)?,
+            Err(_) => misc_schema
+                .release_tx_request_id(account_id, &request_id)
+                .await
+                .map_err(SubmitError::internal)?,
+        }
+        result
+    }
+
+    /// Performs all the checks of `submit_tx` against the last committed state
+    /// without sending the transaction to the mempool.
+    pub async fn validate_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<TxHash, SubmitError> {
+        let settings = self.settings.current();
+        let (verified_tx, _, _, _) = self
+            .check_tx(&settings, tx, signature, extracted_request_metadata)
+            .await?;
+
+        let account_id = verified_tx
+            .tx
+            .account_id()
+            .or(Err(SubmitError::AccountCloseDisabled))?;
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?;
+
+        // Same check as the one performed by the mempool.
+        let nonce = storage
+            .chain()
+            .account_schema()
+            .estimate_nonce(account_id)
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?
+            .unwrap_or_default();
+        if verified_tx.tx.nonce() < nonce {
+            return Err(SubmitError::TxAdd(TxAddError::NonceMismatch));
+        }
+
+        let (_, committed_state) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?;
+        for (token, amount) in required_balances(&verified_tx.tx) {
+            let balance = committed_state
+                .as_ref()
+                .map(|account| account.get_balance(token))
+                .unwrap_or_default();
+            if balance < amount {
+                return Err(SubmitError::TxAdd(TxAddError::InsufficientBalance));
+            }
+        }
+
+        Ok(verified_tx.tx.hash())
     }
 
-    pub async fn store_subsidy_data(
+    /// Checks the zkSync and Ethereum signatures of the transaction without submitting it
+    /// and reports which of the checks failed. The signatures are verified by the signature
+    /// checker, errors in the other fields of the transaction are returned as they are.
+    pub async fn check_tx_signatures(
         &self,
-        hash: TxHash,
-        normal_fee: BigUint,
-        subsidized_fee: BigUint,
-        token_id: TokenId,
-    ) -> Result<(), anyhow::Error> {
-        let token_price_in_usd = self
-            .ticker
-            .get_token_price(TokenLike::Id(token_id), TokenPriceRequestType::USDForOneWei)
-            .await?;
-
-        let full_cost_usd = big_decimal_to_ratio(&token_price_in_usd)? * &normal_fee;
-        let subsidized_cost_usd = big_decimal_to_ratio(&token_price_in_usd)? * &subsidized_fee;
-        if full_cost_usd < subsidized_cost_usd {
-            return Err(anyhow::Error::msg(
-                "Trying to subsidize transaction which should not be subsidized",
-            ));
-        }
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<TxSignatureCheck, SubmitError> {
+        let account_id = tx.account_id().or(Err(SubmitError::AccountCloseDisabled))?;
+        let is_change_pubkey = matches!(tx, ZkSyncTx::ChangePubKey(_));
+        let token = self.token_info_from_id(tx.token_id()).await?;
+        let tx_sender = self
+            .get_tx_sender(&tx)
+            .await
+            .or(Err(SubmitError::TxAdd(TxAddError::DbError)))?;
 
-        let subsidy = Subsidy {
-            usd_amount_scaled: ratio_to_scaled_u64(&full_cost_usd - &subsidized_cost_usd),
-            full_cost_usd_scaled: ratio_to_scaled_u64(full_cost_usd),
-            token_id,
-            token_amount: biguint_to_big_decimal(subsidized_fee),
-            full_cost_token: biguint_to_big_decimal(normal_fee),
-            subsidy_type: self.current_subsidy_type.clone(),
-            tx_hash: hash,
+        // The transaction is checked without the Ethereum signature first, so only its zkSync
+        // signature and the Ethereum authorization of `ChangePubKey` are verified.
+        let verified_tx = match verify_tx_without_eth_signature(
+            &tx,
+            tx_sender,
+            token.clone(),
+            self.sign_verify_requests.clone(),
+        )
+        .await
+        {
+            Ok(verified_tx) => verified_tx.unwrap_tx(),
+            Err(SubmitError::TxAdd(TxAddError::IncorrectTx(err))) if err.is_signature_error() => {
+                return Ok(TxSignatureCheck {
+                    zksync_signature_valid: Some(false),
+                    // The authorization of `ChangePubKey` is checked before its signature.
+                    eth_signature_valid: if is_change_pubkey { Some(true) } else { None },
+                    error: Some(err.to_string()),
+                });
+            }
+            Err(SubmitError::TxAdd(err @ TxAddError::ChangePkNotAuthorized)) => {
+                return Ok(TxSignatureCheck {
+                    zksync_signature_valid: None,
+                    eth_signature_valid: Some(false),
+                    error: Some(err.to_string()),
+                });
+            }
+            Err(SubmitError::TxAdd(TxAddError::IncorrectTx(err))) if err.is_eth_auth_error() => {
+                return Ok(TxSignatureCheck {
+                    zksync_signature_valid: None,
+                    eth_signature_valid: Some(false),
+                    error: Some(err.to_string()),
+                });
+            }
+            Err(err) => return Err(err),
         };
 
-        self.pool
+        // `ChangePubKey` is signed with the new key and has no Ethereum signature
+        // apart from the authorization checked above.
+        if is_change_pubkey {
+            return Ok(TxSignatureCheck {
+                zksync_signature_valid: Some(true),
+                eth_signature_valid: Some(true),
+                error: None,
+            });
+        }
+
+        let mut storage = self
+            .pool
             .access_storage()
-            .await?
-            .misc_schema()
-            .store_subsidy(subsidy)
-            .await?;
+            .await
+            .map_err(SubmitError::internal)?;
+        let (_, account) = storage
+            .chain()
+            .account_schema()
+            .last_committed_state_for_account(account_id)
+            .await
+            .map_err(|_| SubmitError::TxAdd(TxAddError::DbError))?;
+        drop(storage);
+        let account_pub_key_hash = account.map(|account| account.pub_key_hash);
+        // The signer is cached by the signature checker, so it's not verified again.
+        if verified_tx.tx.signer_pub_key_hash() != account_pub_key_hash {
+            return Ok(TxSignatureCheck {
+                zksync_signature_valid: Some(false),
+                eth_signature_valid: None,
+                error: Some(
+                    "Transaction is not signed with the signing key of the account".to_string(),
+                ),
+            });
+        }
 
-        Ok(())
+        let msg_to_sign = tx
+            .get_ethereum_sign_message(token.clone())
+            .map(String::into_bytes);
+        let mut eth_check = verify_tx_info_message_signature(
+            &tx,
+            tx_sender,
+            token,
+            self.get_tx_sender_type(&tx).await?,
+            signature.tx_signature().clone(),
+            msg_to_sign,
+            self.sign_verify_requests.clone(),
+        )
+        .await
+        .map(drop);
+        if let (Ok(()), ZkSyncTx::Swap(tx)) = (&eth_check, &tx) {
+            eth_check = if signature.is_single() {
+                Err(SubmitError::TxAdd(TxAddError::MissingEthSignature))
+            } else {
+                let signatures = signature.orders_signatures();
+                match self
+                    .verify_order_eth_signature(&tx.orders.0, signatures.0.clone())
+                    .await
+                {
+                    Ok(()) => {
+                        self.verify_order_eth_signature(&tx.orders.1, signatures.1.clone())
+                            .await
+                    }
+                    Err(err) => Err(err),
+                }
+            };
+        }
+
+        match eth_check {
+            Ok(()) => Ok(TxSignatureCheck {
+                zksync_signature_valid: Some(true),
+                eth_signature_valid: Some(true),
+                error: None,
+            }),
+            Err(
+                err @ SubmitError::TxAdd(
+                    TxAddError::IncorrectEthSignature
+                    | TxAddError::MissingEthSignature
+                    | TxAddError::EIP1271SignatureVerificationFail,
+                ),
+            )
+            | Err(err @ SubmitError::IncorrectTx(_)) => Ok(TxSignatureCheck {
+                zksync_signature_valid: Some(true),
+                eth_signature_valid: Some(false),
+                error: Some(err.to_string()),
+            }),
+            Err(err) => Err(err),
+        }
     }
 
-    pub async fn submit_tx(
+    /// Checks the transaction and its signatures, returning the verified transaction,
+    /// fee data in case the transaction is subsidized, the exemption from the fee check
+    /// applied to the transaction and the resolved transaction token.
+    async fn check_tx(
         &self,
+        settings: &TxSenderSettings,
         tx: ZkSyncTx,
         signature: TxEthSignatureVariant,
         extracted_request_metadata: Option<RequestMetadata>,
-    ) -> Result<TxHash, SubmitError> {
-        let labels = vec![
-            ("stage", "api".to_string()),
-            ("name", tx.variance_name()),
-            ("token", tx.token_id().to_string()),
-        ];
-        // The initial state of processing tx
-        metrics::increment_counter!("process_tx_count", &labels);
-
+    ) -> Result<
+        (
+            SignedZkSyncTx,
+            Option<ResponseFee>,
+            Option<FeeExemption>,
+            Token,
+        ),
+        SubmitError,
+    > {
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
         }
+        self.compliance_hook.check_txs(&[&tx]).await?;
 
         if let ZkSyncTx::ForcedExit(forced_exit) = &tx {
             self.check_forced_exit(forced_exit).await?;
@@ -511,7 +1590,7 @@ impl TxSender {
 
         let is_whitelisted_initiator = tx
             .account_id()
-            .map(|account_id| self.fee_free_accounts.contains(&account_id))
+            .map(|account_id| settings.fee_free_accounts.contains(&account_id))
             .unwrap_or(false);
 
         let tx_fee_info = if !is_whitelisted_initiator {
@@ -519,6 +1598,12 @@ impl TxSender {
         } else {
             None
         };
+        // Exempted transactions are accepted without the fee check, every such acceptance is logged.
+        let fee_exemption = match tx_fee_info {
+            Some(_) => settings.fee_exemption(tx.account(), &tx.variance_name()),
+            None => None,
+        };
+        let tx_fee_info = tx_fee_info.filter(|_| fee_exemption.is_none());
 
         let sign_verify_channel = self.sign_verify_requests.clone();
 
@@ -526,7 +1611,7 @@ impl TxSender {
 
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
             let should_enforce_fee = !matches!(tx_type, TxFeeTypes::ChangePubKey { .. })
-                || self.enforce_pubkey_change_fee;
+                || settings.enforce_pubkey_change_fee;
 
             let fee_allowed = self.ticker.token_allowed_for_fees(token.clone()).await?;
 
@@ -550,18 +1635,39 @@ impl TxSender {
             {
                 fee_data_for_subsidy = Some(required_fee_data.clone());
                 required_fee_data.subsidized_fee
+            } else if let Some(discounted_fee_data) = self
+                .apply_subsidy_discount(Some(tx.account()), &required_fee_data, token.clone())
+                .await?
+            {
+                let discounted_fee = discounted_fee_data.subsidized_fee.clone();
+                fee_data_for_subsidy = Some(discounted_fee_data);
+                discounted_fee
             } else {
                 required_fee_data.normal_fee
             };
 
-            // Converting `BitUint` to `BigInt` is safe.
-            let required_fee: BigDecimal = required_fee_data.total_fee.to_bigint().unwrap().into();
-            let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
-            // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
-            let scaled_provided_fee = scale_user_fee_up(provided_fee);
-            if required_fee >= scaled_provided_fee && should_enforce_fee {
-                return Err(SubmitError::TxAdd(TxAddError::TxFeeTooLow));
+            if should_enforce_fee {
+                // The fee is quoted against the current gas price, the tolerance covers
+                // the price change between quoting the fee and sending the transaction.
+                let fee_token = self.token_info_from_id(token).await?;
+                self.check_fee(
+                    settings,
+                    TxAddError::TxFeeTooLow,
+                    &fee_token,
+                    &required_fee_data.total_fee,
+                    &provided_fee,
+                )
+                .await?;
             }
+
+            fee_data_for_subsidy = fee_data_for_subsidy.and_then(|mut fee_data| {
+                fee_data.subsidized_fee.total_fee = paid_subsidized_fee(
+                    &fee_data.normal_fee.total_fee,
+                    &fee_data.subsidized_fee.total_fee,
+                    &provided_fee,
+                )?;
+                Some(fee_data)
+            });
         }
 
         let tx_sender = self
@@ -592,42 +1698,7 @@ impl TxSender {
                 .await?;
         }
 
-        let (sender, receiver) = oneshot::channel();
-        let item = MempoolTransactionRequest::NewTx(Box::new(verified_tx), sender);
-        let mut mempool_sender = self.mempool_tx_sender.clone();
-        mempool_sender
-            .send(item)
-            .await
-            .map_err(SubmitError::internal)?;
-
-        receiver.await.map_err(SubmitError::internal)??;
-
-        // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
-        if let Some(fee_data_for_subsidy) = fee_data_for_subsidy {
-            // The following two bad scenarios are possible when applying subsidy for the tx:
-            // - The subsidy is stored, but the tx is then rejected by the state keeper
-            // - The tx is accepted by the state keeper, but the the `store_subsidy_data` returns an error for some reason
-            //
-            // Trying to omit these scenarios unfortunately leads to large code restructure
-            // which is not worth it for subsidies (we prefer stability here)
-            self.store_subsidy_data(
-                tx.hash(),
-                fee_data_for_subsidy.normal_fee.total_fee,
-                fee_data_for_subsidy.subsidized_fee.total_fee,
-                token.id,
-            )
-            .await
-            .map_err(|e| {
-                metrics::increment_counter!("tx_sender.submit_tx.store_subsidy_data_fail");
-                SubmitError::Other(format!(
-                    "Failed to store the subsidy to database. Reason: {}",
-                    e
-                ))
-            })?;
-        }
-
-        // if everything is OK, return the transactions hashes.
-        Ok(tx.hash())
+        Ok((verified_tx, fee_data_for_subsidy, fee_exemption, token))
     }
 
     pub async fn submit_txs_batch(
@@ -635,6 +1706,20 @@ impl TxSender {
         txs: Vec<TxWithSignature>,
         eth_signatures: Option<EthBatchSignatures>,
         extracted_request_metadata: Option<RequestMetadata>,
+    ) -> Result<SubmitBatchResponse, SubmitError> {
+        let tx_types: Vec<_> = txs.iter().map(|tx| tx.tx.variance_name()).collect();
+        let result = self
+            .process_txs_batch(txs, eth_signatures, extracted_request_metadata)
+            .await;
+        report_submission(&tx_types, &result);
+        result
+    }
+
+    async fn process_txs_batch(
+        &self,
+        txs: Vec<TxWithSignature>,
+        eth_signatures: Option<EthBatchSignatures>,
+        extracted_request_metadata: Option<RequestMetadata>,
     ) -> Result<SubmitBatchResponse, SubmitError> {
         // Bring the received signatures into a vector for simplified work.
         let eth_signatures = EthBatchSignatures::api_arg_to_vec(eth_signatures);
@@ -663,9 +1748,18 @@ impl TxSender {
             return Err(SubmitError::TxAdd(TxAddError::EthSignaturesLimitExceeded));
         }
 
+        let settings = self.settings.current();
+        let senders: Vec<_> = txs.iter().map(|tx| tx.tx.account()).collect();
+        self.check_mempool_capacity(&settings, txs.len()).await?;
+        self.check_pending_txs_limit(&settings, txs.iter().map(|tx| &tx.tx))
+            .await?;
+        self.check_rate_limits(&settings, &extracted_request_metadata, &senders)?;
+
         if txs.iter().any(|tx| tx.tx.is_close()) {
             return Err(SubmitError::AccountCloseDisabled);
         }
+        let batch_txs: Vec<_> = txs.iter().map(|tx| &tx.tx).collect();
+        self.compliance_hook.check_txs(&batch_txs).await?;
 
         // Checking fees data
         let mut provided_total_usd_fee = BigDecimal::from(0);
@@ -673,16 +1767,30 @@ impl TxSender {
 
         let eth_token = TokenLike::Id(TokenId(0));
 
-        let mut token_fees = HashMap::<Address, BigUint>::new();
-        let mut token_fees_ids = vec![];
+        // Fees provided in each token. Transactions with zero fee don't contribute to this map,
+        // so a batch may move any tokens as long as the fee is paid by a single transaction in
+        // another (fee-allowed) token. This is the common pattern for withdrawals from exchanges.
+        let mut token_fees = HashMap::<TokenId, BigUint>::new();
+        // Indices of the transactions exempted from the fee checks.
+        let mut fee_exemptions = Vec::new();
+        // Senders, fee types and recipients of the transactions the fee is required for.
+        let mut discount_candidates = Vec::new();
 
-        for tx in &txs {
+        for (idx, tx) in txs.iter().enumerate() {
             let tx_fee_info = tx.tx.get_fee_info();
 
             if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
-                // Save the transaction type before moving on to the next one, otherwise
-                // the total fee won't get affected by it.
-                transaction_types.push((tx_type, address));
+                match settings.fee_exemption(tx.tx.account(), &tx.tx.variance_name()) {
+                    // Exempted transactions don't add to the required fee,
+                    // but the fee they provide still counts.
+                    Some(exemption) => fee_exemptions.push((idx, exemption)),
+                    // Save the transaction type before moving on to the next one, otherwise
+                    // the total fee won't get affected by it.
+                    None => {
+                        transaction_types.push((tx_type, address));
+                        discount_candidates.push((Some(tx.tx.account()), tx_type, address));
+                    }
+                }
 
                 if provided_fee == BigUint::zero() {
                     continue;
@@ -710,10 +1818,7 @@ impl TxSender {
                     .await?;
 
                 let token_data = self.token_info_from_id(token).await?;
-                token_fees_ids.push(token_data.id);
-                let mut token_fee = token_fees.remove(&token_data.address).unwrap_or_default();
-                token_fee += &provided_fee;
-                token_fees.insert(token_data.address, token_fee);
+                *token_fees.entry(token_data.id).or_default() += &provided_fee;
 
                 provided_total_usd_fee +=
                     BigDecimal::from(provided_fee.clone().to_bigint().unwrap())
@@ -723,7 +1828,10 @@ impl TxSender {
 
         let mut fee_data_for_subsidy: Option<ResponseBatchFee> = None;
 
-        // Only one token in batch
+        // The set of tokens used to pay the fee, it's used to attribute the subsidy.
+        let fee_token_ids: Vec<TokenId> = token_fees.keys().copied().collect();
+
+        // The whole fee is paid in one token, it may differ from the tokens being transferred.
         if token_fees.len() == 1 {
             let (batch_token, fee_paid) = token_fees.into_iter().next().unwrap();
             let batch_token_fee = self
@@ -742,23 +1850,39 @@ impl TxSender {
             {
                 fee_data_for_subsidy = Some(batch_token_fee.clone());
                 batch_token_fee.subsidized_fee.total_fee
+            } else if let Some(discounted_fee_data) = self
+                .apply_batch_subsidy_discount(
+                    &discount_candidates,
+                    &batch_token_fee,
+                    batch_token.into(),
+                )
+                .await?
+            {
+                let discounted_fee = discounted_fee_data.subsidized_fee.total_fee.clone();
+                fee_data_for_subsidy = Some(discounted_fee_data);
+                discounted_fee
             } else {
                 batch_token_fee.normal_fee.total_fee
             };
 
-            let user_provided_fee =
-                scale_user_fee_up(BigDecimal::from(fee_paid.to_bigint().unwrap()));
-            let required_normal_fee = BigDecimal::from(required_fee.to_bigint().unwrap());
+            let fee_token = self.token_info_from_id(batch_token).await?;
+            self.check_fee(
+                &settings,
+                TxAddError::TxBatchFeeTooLow,
+                &fee_token,
+                &required_fee,
+                &fee_paid,
+            )
+            .await?;
 
-            // Not enough fee
-            if required_normal_fee > user_provided_fee {
-                vlog::error!(
-                    "User provided batch fee in token is too low, required: {}, provided (scaled): {}",
-                    required_normal_fee.to_string(),
-                    user_provided_fee.to_string(),
-                );
-                return Err(SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow));
-            }
+            fee_data_for_subsidy = fee_data_for_subsidy.and_then(|mut fee_data| {
+                fee_data.subsidized_fee.total_fee = paid_subsidized_fee(
+                    &fee_data.normal_fee.total_fee,
+                    &fee_data.subsidized_fee.total_fee,
+                    &fee_paid,
+                )?;
+                Some(fee_data)
+            });
         } else {
             // Calculate required fee for ethereum token
             let required_eth_fee = self
@@ -777,6 +1901,17 @@ impl TxSender {
             {
                 fee_data_for_subsidy = Some(required_eth_fee.clone());
                 required_eth_fee.subsidized_fee.total_fee
+            } else if let Some(discounted_fee_data) = self
+                .apply_batch_subsidy_discount(
+                    &discount_candidates,
+                    &required_eth_fee,
+                    eth_token.clone(),
+                )
+                .await?
+            {
+                let discounted_fee = discounted_fee_data.subsidized_fee.total_fee.clone();
+                fee_data_for_subsidy = Some(discounted_fee_data);
+                discounted_fee
             } else {
                 required_eth_fee.normal_fee.total_fee
             };
@@ -790,7 +1925,10 @@ impl TxSender {
                 BigDecimal::from(required_fee.to_bigint().unwrap()) * &eth_price_in_usd;
 
             // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
-            let scaled_provided_fee_in_usd = scale_user_fee_up(provided_total_usd_fee.clone());
+            let scaled_provided_fee_in_usd = scale_user_fee_up(
+                provided_total_usd_fee.clone(),
+                settings.fee_tolerance_percent,
+            );
             if required_total_usd_fee > scaled_provided_fee_in_usd {
                 vlog::error!(
                     "User provided batch fee is too low, required: {}, provided: {} (scaled: {}); difference {}",
@@ -801,6 +1939,22 @@ impl TxSender {
                 );
                 return Err(SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow));
             }
+
+            // The subsidy is stored in ETH, so the fee paid in different tokens is converted to it.
+            if !eth_price_in_usd.is_zero() {
+                let provided_eth_fee =
+                    big_decimal_to_ratio(&(provided_total_usd_fee / eth_price_in_usd))
+                        .map_err(SubmitError::Internal)?
+                        .to_integer();
+                fee_data_for_subsidy = fee_data_for_subsidy.and_then(|mut fee_data| {
+                    fee_data.subsidized_fee.total_fee = paid_subsidized_fee(
+                        &fee_data.normal_fee.total_fee,
+                        &fee_data.subsidized_fee.total_fee,
+                        &provided_eth_fee,
+                    )?;
+                    Some(fee_data)
+                });
+            }
         }
 
         for tx in txs.iter() {
@@ -876,6 +2030,10 @@ impl TxSender {
         verified_txs.extend(verified_batch.into_iter());
 
         let tx_hashes: Vec<TxHash> = verified_txs.iter().map(|tx| tx.tx.hash()).collect();
+        let exempted_txs: Vec<_> = fee_exemptions
+            .into_iter()
+            .map(|(idx, exemption)| (verified_txs[idx].tx.clone(), exemption))
+            .collect();
 
         let (sender, receiver) = oneshot::channel();
         let item =
@@ -888,12 +2046,17 @@ impl TxSender {
 
         receiver.await.map_err(SubmitError::internal)??;
 
+        // Only the transactions accepted by the mempool are recorded to the audit log.
+        for (tx, exemption) in &exempted_txs {
+            self.store_fee_exemption_usage(tx, exemption).await?;
+        }
+
         let batch_hash = TxHash::batch_hash(&tx_hashes);
 
         // fee_data_for_subsidy has Some value only if the batch of transactions is subsidised
         if let Some(fee_data) = fee_data_for_subsidy {
-            let subsidy_token_id = if token_fees_ids.len() == 1 {
-                token_fees_ids[0]
+            let subsidy_token_id = if fee_token_ids.len() == 1 {
+                fee_token_ids[0]
             } else {
                 // When there are more than token to pay the fee with,
                 // we get the price of the batch in ETH and then convert it to USD.
@@ -1014,21 +2177,123 @@ impl TxSender {
     }
 }
 
+/// Runs the task that periodically removes the expired request IDs.
+#[must_use]
+pub fn run_tx_request_ids_cleaner(pool: ConnectionPool) -> JoinHandle<()> {
+    async fn remove_expired_request_ids(pool: &ConnectionPool) -> anyhow::Result<u64> {
+        let removed = pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .remove_expired_tx_request_ids(Utc::now() - Duration::from_std(TX_REQUEST_ID_TTL)?)
+            .await?;
+        Ok(removed)
+    }
+
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(TX_REQUEST_IDS_CLEANUP_INTERVAL);
+        loop {
+            timer.tick().await;
+            if let Err(err) = remove_expired_request_ids(&pool).await {
+                vlog::warn!("Can't remove the expired request IDs: {}", err);
+            }
+        }
+    })
+}
+
+/// Returns the amounts of tokens the transaction initiator has to own for the transaction
+/// to be executed. Orders of the `Swap` transaction are not taken into account.
+fn required_balances(tx: &ZkSyncTx) -> HashMap<TokenId, BigUint> {
+    let mut balances = HashMap::<TokenId, BigUint>::new();
+    let mut add = |token: TokenId, amount: &BigUint| {
+        *balances.entry(token).or_default() += amount;
+    };
+
+    match tx {
+        ZkSyncTx::Transfer(tx) => {
+            add(tx.token, &tx.amount);
+            add(tx.token, &tx.fee);
+        }
+        ZkSyncTx::Withdraw(tx) => {
+            add(tx.token, &tx.amount);
+            add(tx.token, &tx.fee);
+        }
+        ZkSyncTx::ForcedExit(tx) => add(tx.token, &tx.fee),
+        ZkSyncTx::ChangePubKey(tx) => add(tx.fee_token, &tx.fee),
+        ZkSyncTx::MintNFT(tx) => add(tx.fee_token, &tx.fee),
+        ZkSyncTx::Swap(tx) => add(tx.fee_token, &tx.fee),
+        ZkSyncTx::WithdrawNFT(tx) => {
+            add(tx.token, &BigUint::from(1u32));
+            add(tx.fee_token, &tx.fee);
+        }
+        ZkSyncTx::Close(_) => {}
+    }
+    balances.retain(|_, amount| !amount.is_zero());
+    balances
+}
+
+/// Checks that the timestamp of the signed request is close enough to the current time.
+fn check_request_timestamp(request_time: DateTime<Utc>) -> Result<(), SubmitError> {
+    let current_time = Utc::now();
+    let validness_interval = Duration::minutes(VALIDNESS_INTERVAL_MINUTES);
+
+    if current_time - validness_interval > request_time
+        || current_time + validness_interval < request_time
+    {
+        return Err(SubmitError::InvalidParams(format!(
+            "Timestamp differs by more than {} minutes",
+            VALIDNESS_INTERVAL_MINUTES
+        )));
+    }
+    Ok(())
+}
+
 async fn send_verify_request_and_recv(
     request: VerifySignatureRequest,
     mut req_channel: mpsc::Sender<VerifySignatureRequest>,
     receiver: oneshot::Receiver<Result<VerifiedTx, TxAddError>>,
 ) -> Result<VerifiedTx, SubmitError> {
+    let start = Instant::now();
+    let kind = request.data.kind();
     // Send the check request.
     req_channel
         .send(request)
         .await
         .map_err(SubmitError::internal)?;
     // Wait for the check result.
-    receiver
+    let result = receiver
         .await
         .map_err(|err| internal_error!(err))?
-        .map_err(SubmitError::TxAdd)
+        .map_err(SubmitError::TxAdd);
+    // Includes the time spent in the queue of the signature checker.
+    metrics::histogram!("api.tx_sender.verify_signatures", start.elapsed(), "kind" => kind);
+    result
+}
+
+/// Sends the transaction without its Ethereum signature to the signature checker, so only
+/// the transaction itself is verified: its fields, the zkSync signature and the Ethereum
+/// authorization of `ChangePubKey`.
+async fn verify_tx_without_eth_signature(
+    tx: &ZkSyncTx,
+    tx_sender: Address,
+    token: Token,
+    req_channel: mpsc::Sender<VerifySignatureRequest>,
+) -> Result<VerifiedTx, SubmitError> {
+    let (sender, receiver) = oneshot::channel();
+    let request = VerifySignatureRequest {
+        data: RequestData::Tx(TxRequest {
+            tx: SignedZkSyncTx {
+                tx: tx.clone(),
+                eth_sign_data: None,
+                created_at: Utc::now(),
+            },
+            sender: tx_sender,
+            token,
+        }),
+        response: sender,
+    };
+
+    send_verify_request_and_recv(request, req_channel, receiver).await
 }
 
 /// Send a request for Ethereum signature verification and wait for the response.
@@ -1188,18 +2453,32 @@ async fn verify_txs_batch_signature(
     send_verify_request_and_recv(request, req_channel, receiver).await
 }
 
+/// Returns the subsidized fee raised to the fee actually provided by the user, so that only
+/// the waived part of the fee is charged from the subsidy budget. Returns `None` if nothing
+/// was waived, i.e. the provided fee covers the normal fee.
+fn paid_subsidized_fee(
+    normal_fee: &BigUint,
+    subsidized_fee: &BigUint,
+    provided_fee: &BigUint,
+) -> Option<BigUint> {
+    if provided_fee >= normal_fee {
+        return None;
+    }
+    Some(std::cmp::max(subsidized_fee, provided_fee).clone())
+}
+
 /// Scales the fee provided by user up to check whether the provided fee is enough to cover our expenses for
 /// maintaining the protocol.
 ///
-/// We calculate both `provided_fee * 1.05` and `provided_fee + 1 cent` and choose the maximum.
+/// We calculate both `provided_fee * (1 + tolerance)` and `provided_fee + 1 cent` and choose the maximum.
 /// This is required since the price may change between signing the transaction and sending it to the server.
-fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
+fn scale_user_fee_up(provided_total_usd_fee: BigDecimal, tolerance_percent: u32) -> BigDecimal {
     let one_cent = BigDecimal::from_str("0.01").unwrap();
 
     // This formula is needed when the fee is really small.
     //
     // We don't compare it with any of the following scaled numbers, because
-    // a) Scaling by two (100%) is always greater than scaling by 5%.
+    // a) Scaling by two (100%) is always greater than scaling by the tolerance.
     // b) It is intended as a smaller substitute for 1 cent scaling when
     // scaling by 1 cent means scaling more than 2x.
     if provided_total_usd_fee < one_cent {
@@ -1208,9 +2487,10 @@ fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
         return scaled_by_two_provided_fee_in_usd;
     }
 
-    // Scale by 5%.
-    let scaled_percent_provided_fee_in_usd =
-        provided_total_usd_fee.clone() * BigDecimal::from(105u32) / BigDecimal::from(100u32);
+    // Scale by the tolerance.
+    let scaled_percent_provided_fee_in_usd = provided_total_usd_fee.clone()
+        * BigDecimal::from(100 + tolerance_percent)
+        / BigDecimal::from(100u32);
 
     // Scale by 1 cent.
     let scaled_one_cent_provided_fee_in_usd = provided_total_usd_fee + one_cent;
@@ -1226,12 +2506,39 @@ fn scale_user_fee_up(provided_total_usd_fee: BigDecimal) -> BigDecimal {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_paid_subsidized_fee() {
+        let normal_fee = BigUint::from(100u32);
+        let subsidized_fee = BigUint::from(70u32);
+
+        // The user paid exactly the subsidized fee, the whole discount is charged.
+        assert_eq!(
+            paid_subsidized_fee(&normal_fee, &subsidized_fee, &BigUint::from(70u32)),
+            Some(BigUint::from(70u32))
+        );
+        // The tolerance allows to pay less, but no more than the discount is charged.
+        assert_eq!(
+            paid_subsidized_fee(&normal_fee, &subsidized_fee, &BigUint::from(65u32)),
+            Some(BigUint::from(70u32))
+        );
+        // Only the part of the discount the user didn't pay is charged.
+        assert_eq!(
+            paid_subsidized_fee(&normal_fee, &subsidized_fee, &BigUint::from(90u32)),
+            Some(BigUint::from(90u32))
+        );
+        // Nothing is waived if the user paid the full fee.
+        assert_eq!(
+            paid_subsidized_fee(&normal_fee, &subsidized_fee, &BigUint::from(100u32)),
+            None
+        );
+    }
+
     #[test]
     fn test_scaling_user_fee_by_two() {
         let provided_fee = BigDecimal::from_str("0.005").unwrap();
         let provided_fee_scaled_by_two = BigDecimal::from_str("0.01").unwrap();
 
-        let scaled_fee = scale_user_fee_up(provided_fee);
+        let scaled_fee = scale_user_fee_up(provided_fee, 5);
 
         assert_eq!(provided_fee_scaled_by_two, scaled_fee);
     }
@@ -1241,7 +2548,7 @@ mod tests {
         let provided_fee = BigDecimal::from_str("0.015").unwrap();
         let provided_fee_scaled_by_cent = BigDecimal::from_str("0.025").unwrap();
 
-        let scaled_fee = scale_user_fee_up(provided_fee);
+        let scaled_fee = scale_user_fee_up(provided_fee, 5);
 
         assert_eq!(provided_fee_scaled_by_cent, scaled_fee);
     }
@@ -1251,8 +2558,181 @@ mod tests {
         let provided_fee = BigDecimal::from_str("0.30").unwrap();
         let provided_fee_scaled_by_five_percent = BigDecimal::from_str("0.315").unwrap();
 
-        let scaled_fee = scale_user_fee_up(provided_fee);
+        let scaled_fee = scale_user_fee_up(provided_fee, 5);
 
         assert_eq!(provided_fee_scaled_by_five_percent, scaled_fee);
     }
+
+    #[test]
+    fn mempool_full_error() {
+        use crate::api_server::rest::v02::error::{ErrorCode, TxError};
+        use actix_web::http::StatusCode;
+
+        let error = SubmitError::MempoolFull {
+            depth: 1000,
+            retry_after: std::time::Duration::from_secs(30),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Mempool is full (1000 transactions are queued), retry after 30 seconds."
+        );
+
+        let error = TxError::submit_rejected(error);
+        assert_eq!(error.code, ErrorCode::MempoolFull);
+        assert_eq!(error.http_status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(error.retry_after, Some(30));
+    }
+
+    #[test]
+    fn fee_too_low_error() {
+        use crate::api_server::rest::v02::error::{ErrorCode, TxError};
+        use actix_web::http::StatusCode;
+
+        let token = Token::new(
+            TokenId(0),
+            Address::zero(),
+            "ETH",
+            18,
+            zksync_types::TokenKind::ERC20,
+        );
+        // Message is the same as the one of the `TxAdd` error, the fees are reported separately.
+        let error = SubmitError::fee_too_low(
+            TxAddError::TxBatchFeeTooLow,
+            &token,
+            1050u32.into(),
+            1000u32.into(),
+        );
+        assert_eq!(
+            error.to_string(),
+            SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow).to_string()
+        );
+        match &error {
+            SubmitError::FeeTooLow { shortfall, .. } => {
+                assert_eq!(*shortfall, BigUint::from(50u32))
+            }
+            _ => unreachable!(),
+        }
+        // Shortfall doesn't underflow if the provided fee covers the required one.
+        match SubmitError::fee_too_low(
+            TxAddError::TxFeeTooLow,
+            &token,
+            1000u32.into(),
+            1010u32.into(),
+        ) {
+            SubmitError::FeeTooLow { shortfall, .. } => assert_eq!(shortfall, BigUint::from(0u32)),
+            _ => unreachable!(),
+        }
+
+        let error = TxError::submit_rejected(error);
+        assert_eq!(error.code, ErrorCode::FeeTooLow);
+        assert_eq!(error.http_status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error.retry_after, None);
+        let data = error.data.expect("Fees should be reported");
+        assert_eq!(data["requiredFee"], "1050");
+        assert_eq!(data["providedFee"], "1000");
+        assert_eq!(data["shortfall"], "50");
+    }
+
+    #[test]
+    fn pending_txs_limit() {
+        let nonces =
+            |nonces: &[u32]| -> HashSet<Nonce> { nonces.iter().copied().map(Nonce).collect() };
+        let queued: HashMap<_, _> = vec![
+            (AccountId(1), nonces(&[1, 2, 3])),
+            (AccountId(2), nonces(&[7])),
+        ]
+        .into_iter()
+        .collect();
+
+        // Account without the queued transactions.
+        let submitted = vec![(AccountId(3), nonces(&[1, 2, 3]))]
+            .into_iter()
+            .collect();
+        assert_eq!(find_pending_txs_overflow(&submitted, &queued, 3), None);
+        // Replacements are accepted even if the limit is reached.
+        let submitted = vec![(AccountId(1), nonces(&[3]))].into_iter().collect();
+        assert_eq!(find_pending_txs_overflow(&submitted, &queued, 3), None);
+        // New transaction doesn't fit.
+        let submitted = vec![(AccountId(1), nonces(&[3, 4]))].into_iter().collect();
+        assert_eq!(
+            find_pending_txs_overflow(&submitted, &queued, 3),
+            Some((AccountId(1), 3))
+        );
+        // Batch doesn't fit as a whole.
+        let submitted = vec![(AccountId(2), nonces(&[8, 9, 10]))]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            find_pending_txs_overflow(&submitted, &queued, 3),
+            Some((AccountId(2), 1))
+        );
+    }
+
+    #[test]
+    fn rejection_reasons() {
+        let token = Token::new(
+            TokenId(0),
+            Address::zero(),
+            "ETH",
+            18,
+            zksync_types::TokenKind::ERC20,
+        );
+        // Errors of the same kind share the reason regardless of the submission data.
+        let fee_errors = [
+            SubmitError::fee_too_low(
+                TxAddError::TxFeeTooLow,
+                &token,
+                1050u32.into(),
+                1000u32.into(),
+            ),
+            SubmitError::fee_too_low(
+                TxAddError::TxBatchFeeTooLow,
+                &token,
+                20u32.into(),
+                1u32.into(),
+            ),
+            SubmitError::TxAdd(TxAddError::TxFeeTooLow),
+            SubmitError::TxAdd(TxAddError::TxBatchFeeTooLow),
+        ];
+        for error in &fee_errors {
+            assert_eq!(error.reason(), "fee_too_low");
+        }
+
+        assert_eq!(
+            SubmitError::IncorrectTx("'fast' field must not be set".to_string()).reason(),
+            "incorrect_tx"
+        );
+        assert_eq!(
+            SubmitError::TxAdd(TxAddError::NonceMismatch).reason(),
+            "nonce_mismatch"
+        );
+        assert_eq!(
+            SubmitError::RateLimited(std::time::Duration::from_secs(1)).reason(),
+            "rate_limited"
+        );
+        assert_eq!(SubmitError::other("database is down").reason(), "internal");
+    }
+
+    #[test]
+    fn transient_submit_errors() {
+        let transient_errors = [
+            SubmitError::TxAdd(TxAddError::DbError),
+            SubmitError::PriceError(PriceError::api_error("price source is down")),
+            SubmitError::mempool_communication("mempool is down"),
+            SubmitError::other("database is down"),
+        ];
+        for error in &transient_errors {
+            assert!(error.is_transient(), "{:?}", error);
+        }
+
+        let permanent_errors = [
+            SubmitError::TxAdd(TxAddError::IncorrectEthSignature),
+            SubmitError::TxAdd(TxAddError::TxFeeTooLow),
+            SubmitError::PriceError(PriceError::token_not_found("unknown token")),
+            SubmitError::InappropriateFeeToken,
+        ];
+        for error in &permanent_errors {
+            assert!(!error.is_transient(), "{:?}", error);
+        }
+    }
 }
@@ -0,0 +1,280 @@
+//! Settings of the transaction submission that can be changed without the API restart.
+//!
+//! Settings are initialized from the config, the operator may override them via the admin API.
+//! Overrides are stored in the database and reloaded periodically by the background task, so they
+//! take effect on all the API replicas and the submissions don't access the database to get them.
+//! The fee exemptions added via the admin API are reloaded along with the overrides.
+//! Settings are swapped as a whole: every submission uses the snapshot taken when it started,
+//! so the reload doesn't affect the submissions in progress.
+
+// Built-in uses
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+// External uses
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+// Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
+use zksync_storage::ConnectionPool;
+use zksync_types::{AccountId, Address, FeeExemption};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TxSenderSettings {
+    /// List of account IDs that do not have to pay fees for operations.
+    pub fee_free_accounts: BTreeSet<AccountId>,
+    pub enforce_pubkey_change_fee: bool,
+    /// Provided fee is scaled up by this percentage (but at least by 1 cent) before the check in USD,
+    /// since the price may change between quoting the fee and submitting the transaction.
+    pub fee_tolerance_percent: u32,
+    /// Senders and transaction types exempted from the fee checks,
+    /// the exemptions added via the admin API are stored in the database separately.
+    pub fee_exempt_senders: BTreeSet<Address>,
+    pub fee_exempt_tx_types: BTreeSet<String>,
+    /// Exemptions added via the admin API, they can't be overridden as the settings.
+    #[serde(skip)]
+    pub stored_fee_exemptions: HashSet<FeeExemption>,
+
+    pub rate_limit_enabled: bool,
+    pub rate_limit_ip_burst: u32,
+    pub rate_limit_ip_per_minute: u32,
+    pub rate_limit_account_burst: u32,
+    pub rate_limit_account_per_minute: u32,
+
+    /// Maximum number of transactions in the mempool, zero if the mempool is unbounded.
+    pub mempool_capacity: usize,
+    pub mempool_full_retry_after_sec: u64,
+    /// Maximum number of transactions of a single account in the mempool, zero if unlimited.
+    pub max_pending_txs_per_account: usize,
+}
+
+impl TxSenderSettings {
+    pub fn from_config(config: &CommonApiConfig) -> Self {
+        Self {
+            fee_free_accounts: config.fee_free_accounts.iter().copied().collect(),
+            enforce_pubkey_change_fee: config.enforce_pubkey_change_fee,
+            fee_tolerance_percent: config.fee_tolerance_percent.min(100),
+            fee_exempt_senders: config.fee_exempt_senders.iter().copied().collect(),
+            fee_exempt_tx_types: config.fee_exempt_tx_types.iter().cloned().collect(),
+            stored_fee_exemptions: HashSet::new(),
+            rate_limit_enabled: config.rate_limit_enabled,
+            rate_limit_ip_burst: config.rate_limit_ip_burst,
+            rate_limit_ip_per_minute: config.rate_limit_ip_per_minute,
+            rate_limit_account_burst: config.rate_limit_account_burst,
+            rate_limit_account_per_minute: config.rate_limit_account_per_minute,
+            mempool_capacity: config.mempool_capacity,
+            mempool_full_retry_after_sec: config.mempool_full_retry_after_sec,
+            max_pending_txs_per_account: config.max_pending_txs_per_account,
+        }
+    }
+
+    pub fn mempool_full_retry_after(&self) -> Duration {
+        Duration::from_secs(self.mempool_full_retry_after_sec)
+    }
+
+    /// Returns the exemption from the fee checks applicable to the transaction of the given sender
+    /// and type, if any. The sender exemption takes precedence over the transaction type one.
+    pub fn fee_exemption(&self, sender: Address, tx_type: &str) -> Option<FeeExemption> {
+        let sender_exemption = FeeExemption::Sender(sender);
+        if self.fee_exempt_senders.contains(&sender)
+            || self.stored_fee_exemptions.contains(&sender_exemption)
+        {
+            return Some(sender_exemption);
+        }
+        let tx_type_exemption = FeeExemption::TxType(tx_type.to_string());
+        if self.fee_exempt_tx_types.contains(tx_type)
+            || self.stored_fee_exemptions.contains(&tx_type_exemption)
+        {
+            return Some(tx_type_exemption);
+        }
+        None
+    }
+
+    /// Returns the settings with the given fields replaced. Fields are named
+    /// as in the JSON representation of the settings, e.g. `mempoolCapacity`.
+    pub fn with_overrides<'a>(
+        &self,
+        overrides: impl IntoIterator<Item = (&'a str, &'a serde_json::Value)>,
+    ) -> Result<Self, String> {
+        let mut settings = serde_json::to_value(self).map_err(|err| err.to_string())?;
+        let fields = settings
+            .as_object_mut()
+            .expect("settings are serialized to the JSON object");
+        for (name, value) in overrides {
+            if !fields.contains_key(name) {
+                return Err(format!("Unknown setting {}", name));
+            }
+            fields.insert(name.to_string(), value.clone());
+        }
+
+        let settings: Self =
+            serde_json::from_value(settings).map_err(|err| format!("Invalid settings: {}", err))?;
+        if settings.fee_tolerance_percent > 100 {
+            return Err("Fee tolerance can't be greater than 100 percent".to_string());
+        }
+        Ok(settings)
+    }
+}
+
+/// Settings shared by the copies of the `TxSender`, the config settings are used
+/// until the overrides are loaded.
+#[derive(Debug, Clone)]
+pub struct SharedTxSenderSettings {
+    base: Arc<TxSenderSettings>,
+    current: Arc<RwLock<Arc<TxSenderSettings>>>,
+    reload_interval: Duration,
+}
+
+impl SharedTxSenderSettings {
+    pub fn new(config: &CommonApiConfig) -> Self {
+        let base = Arc::new(TxSenderSettings::from_config(config));
+        Self {
+            current: Arc::new(RwLock::new(base.clone())),
+            base,
+            reload_interval: config.settings_reload_interval(),
+        }
+    }
+
+    /// Returns the snapshot of the current settings.
+    pub fn current(&self) -> Arc<TxSenderSettings> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Periodically reloads the overrides set via the admin API.
+    #[must_use]
+    pub fn keep_updated(self, pool: ConnectionPool) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(self.reload_interval);
+            loop {
+                timer.tick().await;
+                if let Err(err) = self.reload(&pool).await {
+                    vlog::warn!("Can't reload the submission settings: {}", err);
+                }
+            }
+        })
+    }
+
+    async fn reload(&self, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        let overrides = storage
+            .misc_schema()
+            .load_tx_sender_setting_overrides()
+            .await?;
+        let fee_exemptions = storage.misc_schema().load_fee_exemptions().await?;
+        drop(storage);
+
+        let mut settings = self
+            .base
+            .with_overrides(
+                overrides
+                    .iter()
+                    .map(|setting| (setting.name.as_str(), &setting.value)),
+            )
+            .map_err(anyhow::Error::msg)?;
+        settings.stored_fee_exemptions = fee_exemptions
+            .into_iter()
+            .map(|record| record.exemption)
+            .collect();
+
+        let mut current = self.current.write().unwrap();
+        if **current != settings {
+            vlog::info!("Submission settings are changed: {:?}", settings);
+            *current = Arc::new(settings);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn base_settings() -> TxSenderSettings {
+        TxSenderSettings {
+            fee_free_accounts: vec![AccountId(1)].into_iter().collect(),
+            enforce_pubkey_change_fee: true,
+            fee_tolerance_percent: 5,
+            fee_exempt_senders: BTreeSet::new(),
+            fee_exempt_tx_types: vec!["ForcedExit".to_string()].into_iter().collect(),
+            stored_fee_exemptions: HashSet::new(),
+            rate_limit_enabled: true,
+            rate_limit_ip_burst: 100,
+            rate_limit_ip_per_minute: 60,
+            rate_limit_account_burst: 20,
+            rate_limit_account_per_minute: 10,
+            mempool_capacity: 100_000,
+            mempool_full_retry_after_sec: 30,
+            max_pending_txs_per_account: 100,
+        }
+    }
+
+    #[test]
+    fn settings_overrides() {
+        let base = base_settings();
+
+        let capacity = json!(10);
+        let exempt_types = json!(["Transfer", "Withdraw"]);
+        let settings = base
+            .with_overrides(vec![
+                ("mempoolCapacity", &capacity),
+                ("feeExemptTxTypes", &exempt_types),
+            ])
+            .unwrap();
+        assert_eq!(settings.mempool_capacity, 10);
+        assert!(settings.fee_exempt_tx_types.contains("Withdraw"));
+        assert!(!settings.fee_exempt_tx_types.contains("ForcedExit"));
+        // Settings that are not overridden are kept.
+        assert_eq!(settings.fee_tolerance_percent, 5);
+        assert_eq!(settings.fee_free_accounts, base.fee_free_accounts);
+
+        let value = json!(true);
+        assert!(base.with_overrides(vec![("unknown", &value)]).is_err());
+        let value = json!("many");
+        assert!(base
+            .with_overrides(vec![("mempoolCapacity", &value)])
+            .is_err());
+        let value = json!(150);
+        assert!(base
+            .with_overrides(vec![("feeTolerancePercent", &value)])
+            .is_err());
+    }
+
+    #[test]
+    fn fee_exemptions() {
+        let mut settings = base_settings();
+        let config_sender = Address::repeat_byte(1);
+        let stored_sender = Address::repeat_byte(2);
+        settings.fee_exempt_senders.insert(config_sender);
+        settings.stored_fee_exemptions = vec![
+            FeeExemption::Sender(stored_sender),
+            FeeExemption::TxType("Withdraw".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        // Sender exemption takes precedence over the transaction type one.
+        assert_eq!(
+            settings.fee_exemption(config_sender, "ForcedExit"),
+            Some(FeeExemption::Sender(config_sender))
+        );
+        assert_eq!(
+            settings.fee_exemption(stored_sender, "Withdraw"),
+            Some(FeeExemption::Sender(stored_sender))
+        );
+        assert_eq!(
+            settings.fee_exemption(Address::random(), "ForcedExit"),
+            Some(FeeExemption::TxType("ForcedExit".to_string()))
+        );
+        assert_eq!(
+            settings.fee_exemption(Address::random(), "Withdraw"),
+            Some(FeeExemption::TxType("Withdraw".to_string()))
+        );
+        assert_eq!(settings.fee_exemption(Address::random(), "Transfer"), None);
+    }
+}
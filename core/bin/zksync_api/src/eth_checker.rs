@@ -2,18 +2,40 @@
 //! onchain `ChangePubKey` authorization or EIP1271 signature
 //! verification.
 
-use web3::{contract::Options, types::Address};
-use zksync_contracts::eip1271_contract;
+use ethabi::{ParamType, Token};
+use std::str::FromStr;
+use web3::{
+    contract::{tokens::Tokenize, Options},
+    types::{Address, BlockId, BlockNumber, U256},
+};
+use zksync_contracts::{
+    eip1271_contract, ens_contract, ens_resolver_contract, erc20_contract, erc20_metadata_contract,
+    governance_contract,
+};
 use zksync_eth_client::ethereum_gateway::EthereumGateway;
 use zksync_types::{
     tx::EIP1271Signature,
-    {Nonce, PubKeyHash},
+    {Nonce, PubKeyHash, TokenId},
 };
 
 /// isValidSignature return value according to EIP1271 standard
 /// bytes4(keccak256("isValidSignature(bytes32,bytes)")
 pub const EIP1271_SUCCESS_RETURN_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
 
+/// Address of the ENS registry, it's the same for the mainnet and the public testnets.
+pub const ENS_REGISTRY_ADDRESS: &str = "00000000000C2E074eC69A0dFb2997BA6C7d2e1e";
+
+/// Outcome of the simulated call of the ERC-20 method that should return `true` on success.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Erc20CallOutcome {
+    ReturnedTrue,
+    ReturnedFalse,
+    /// The call succeeded, but returned nothing.
+    NoReturnValue,
+    /// The call reverted or the node is unavailable.
+    Failed(String),
+}
+
 #[derive(Clone)]
 pub struct EthereumChecker {
     client: EthereumGateway,
@@ -68,6 +90,160 @@ impl EthereumChecker {
         Ok(received == EIP1271_SUCCESS_RETURN_VALUE)
     }
 
+    /// Computes the `namehash` of the ENS name according to EIP-137.
+    fn ens_namehash(name: &str) -> [u8; 32] {
+        let mut node = [0u8; 32];
+        if name.is_empty() {
+            return node;
+        }
+        for label in name.rsplit('.') {
+            let mut bytes = Vec::with_capacity(64);
+            bytes.extend_from_slice(&node);
+            bytes.extend_from_slice(&tiny_keccak::keccak256(label.as_bytes()));
+            node = tiny_keccak::keccak256(&bytes);
+        }
+        node
+    }
+
+    /// Resolves the ENS name to the address through the ENS registry.
+    /// Returns `None` if the name has no resolver or no address set.
+    pub async fn resolve_ens_name(&self, name: &str) -> Result<Option<Address>, anyhow::Error> {
+        let node = Self::ens_namehash(&name.to_lowercase());
+        let registry = Address::from_str(ENS_REGISTRY_ADDRESS).unwrap();
+
+        let resolver: Address = self
+            .client
+            .call_contract_function(
+                "resolver",
+                (node,),
+                None,
+                Options::default(),
+                None,
+                registry,
+                ens_contract(),
+            )
+            .await?;
+        if resolver.is_zero() {
+            return Ok(None);
+        }
+
+        let address: Address = self
+            .client
+            .call_contract_function(
+                "addr",
+                (node,),
+                None,
+                Options::default(),
+                None,
+                resolver,
+                ens_resolver_contract(),
+            )
+            .await?;
+        Ok(Some(address).filter(|address| !address.is_zero()))
+    }
+
+    /// Checks whether there's a smart contract deployed at the given address.
+    pub async fn is_contract(&self, address: Address) -> Result<bool, anyhow::Error> {
+        let code = self.client.get_code(address).await?;
+        Ok(!code.is_empty())
+    }
+
+    /// Returns the bytecode deployed at the given address.
+    pub async fn contract_code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        self.client.get_code(address).await
+    }
+
+    /// Simulates the call of the ERC-20 method with `eth_call`, nothing is sent to the network.
+    pub async fn simulate_erc20_call<P: Tokenize>(
+        &self,
+        token_address: Address,
+        func: &str,
+        params: P,
+        from: Address,
+    ) -> Result<Erc20CallOutcome, anyhow::Error> {
+        let data = erc20_contract()
+            .function(func)?
+            .encode_input(&params.into_tokens())?;
+        let output = match self.client.call(from, token_address, data).await {
+            Ok(output) => output,
+            Err(err) => return Ok(Erc20CallOutcome::Failed(err.to_string())),
+        };
+        if output.is_empty() {
+            return Ok(Erc20CallOutcome::NoReturnValue);
+        }
+
+        match ethabi::decode(&[ParamType::Bool], &output)?.as_slice() {
+            [Token::Bool(true)] => Ok(Erc20CallOutcome::ReturnedTrue),
+            [Token::Bool(false)] => Ok(Erc20CallOutcome::ReturnedFalse),
+            _ => anyhow::bail!("Unexpected output of {}: {}", func, hex::encode(output)),
+        }
+    }
+
+    /// Returns the id assigned to the token by the governance contract,
+    /// or `None` if the token wasn't added to the contract.
+    /// The contract state is read at the block with the given number of confirmations,
+    /// so the id is the same as the one of the `NewToken` event processed by the server.
+    pub async fn registered_token_id(
+        &self,
+        governance_address: Address,
+        token_address: Address,
+        confirmations: u64,
+    ) -> Result<Option<TokenId>, anyhow::Error> {
+        let latest_block = self.client.block_number().await?.as_u64();
+        let block = BlockId::Number(BlockNumber::Number(
+            latest_block.saturating_sub(confirmations).into(),
+        ));
+        let token_id: U256 = self
+            .client
+            .call_contract_function(
+                "tokenIds",
+                token_address,
+                None,
+                Options::default(),
+                block,
+                governance_address,
+                governance_contract(),
+            )
+            .await?;
+        // Zero id is reserved for Ether, the contract returns it for unknown tokens.
+        Ok(Some(TokenId(token_id.as_u32())).filter(|id| **id != 0))
+    }
+
+    /// Loads the symbol and the decimals of the ERC-20 token.
+    pub async fn erc20_metadata(
+        &self,
+        token_address: Address,
+    ) -> Result<(String, u8), anyhow::Error> {
+        let symbol: String = self
+            .client
+            .call_contract_function(
+                "symbol",
+                (),
+                None,
+                Options::default(),
+                None,
+                token_address,
+                erc20_metadata_contract(),
+            )
+            .await?;
+        let decimals: U256 = self
+            .client
+            .call_contract_function(
+                "decimals",
+                (),
+                None,
+                Options::default(),
+                None,
+                token_address,
+                erc20_metadata_contract(),
+            )
+            .await?;
+        if decimals > U256::from(u8::MAX) {
+            anyhow::bail!("Token decimals {} are out of range", decimals);
+        }
+        Ok((symbol, decimals.as_u32() as u8))
+    }
+
     pub async fn is_new_pubkey_hash_authorized(
         &self,
         address: Address,
@@ -87,6 +263,14 @@ impl EthereumChecker {
             .map_err(|e| anyhow::format_err!("Failed to query contract authFacts: {}", e))?;
         Ok(auth_fact.as_slice() == tiny_keccak::keccak256(&pub_key_hash.data[..]))
     }
+
+    /// Checks whether the zkSync contract is in the exodus mode.
+    pub async fn is_exodus_mode(&self) -> Result<bool, anyhow::Error> {
+        self.client
+            .call_main_contract_function("exodusMode", (), None, Options::default(), None)
+            .await
+            .map_err(|e| anyhow::format_err!("Failed to query contract exodusMode: {}", e))
+    }
 }
 
 #[cfg(test)]
@@ -144,6 +328,20 @@ mod tests {
         assert!(result, "Signature is incorrect");
     }
 
+    /// Checks the `namehash` implementation against the values from EIP-137.
+    #[test]
+    fn ens_namehash() {
+        assert_eq!(EthereumChecker::ens_namehash(""), [0u8; 32]);
+        assert_eq!(
+            hex::encode(EthereumChecker::ens_namehash("eth")),
+            "93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"
+        );
+        assert_eq!(
+            hex::encode(EthereumChecker::ens_namehash("foo.eth")),
+            "de9b09fd7c5f901e23a3f19fecc54828e9c848539801e86591bd9801b019f84f"
+        );
+    }
+
     /// This test checks that the actual signature data taken from
     /// mainnet / Argent smart wallet is valid in our codebase.
     #[test]
@@ -0,0 +1,195 @@
+//! Scale of the gas price used by the fee ticker.
+//!
+//! Fees are calculated from the average network gas price, while the transactions
+//! of `eth_sender` may end up paying more when the gas price grows. The scale is
+//! adjusted by the ratio between the gas price actually paid for the recent Ethereum
+//! transactions and the average network gas price, so the collected fees cover the
+//! real L1 costs.
+
+// Built-in deps
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+// External deps
+use num::{rational::Ratio, BigUint, ToPrimitive, Zero};
+use tokio::task::JoinHandle;
+// Workspace deps
+use zksync_storage::ConnectionPool;
+use zksync_utils::ratio_to_big_decimal;
+
+/// Interval of the gas price scale recalculation.
+const SCALE_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+/// Number of the last confirmed Ethereum transactions taken into account.
+const RECENT_TRANSACTIONS: u32 = 50;
+
+/// Scale used until enough data about the paid gas prices is collected.
+pub fn default_gas_price_scale() -> Ratio<BigUint> {
+    Ratio::new(BigUint::from(130u32), BigUint::from(100u32))
+}
+
+fn min_gas_price_scale() -> Ratio<BigUint> {
+    Ratio::new(BigUint::from(110u32), BigUint::from(100u32))
+}
+
+fn max_gas_price_scale() -> Ratio<BigUint> {
+    Ratio::from_integer(BigUint::from(3u32))
+}
+
+/// Safety margin applied on top of the ratio between the paid and the average gas prices.
+fn safety_margin() -> Ratio<BigUint> {
+    Ratio::new(BigUint::from(110u32), BigUint::from(100u32))
+}
+
+#[derive(Debug, Clone)]
+struct ScaleState {
+    factor: Ratio<BigUint>,
+    pinned: bool,
+}
+
+/// Gas price scale shared between all the copies of the fee ticker.
+#[derive(Debug, Clone)]
+pub struct GasPriceScale {
+    state: Arc<RwLock<ScaleState>>,
+}
+
+impl Default for GasPriceScale {
+    fn default() -> Self {
+        Self::new(default_gas_price_scale())
+    }
+}
+
+impl GasPriceScale {
+    pub fn new(factor: Ratio<BigUint>) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(ScaleState {
+                factor,
+                pinned: false,
+            })),
+        }
+    }
+
+    /// Returns the current scale.
+    pub fn factor(&self) -> Ratio<BigUint> {
+        self.state.read().unwrap().factor.clone()
+    }
+
+    /// Returns `true` if the scale was pinned and isn't adjusted automatically.
+    pub fn is_pinned(&self) -> bool {
+        self.state.read().unwrap().pinned
+    }
+
+    /// Sets the scale and stops its automatic adjustment.
+    pub fn pin(&self, factor: Ratio<BigUint>) {
+        *self.state.write().unwrap() = ScaleState {
+            factor,
+            pinned: true,
+        };
+    }
+
+    /// Resumes the automatic adjustment of the scale.
+    pub fn unpin(&self) {
+        self.state.write().unwrap().pinned = false;
+    }
+
+    /// Adjusts the scale given the average gas price paid for the recent Ethereum
+    /// transactions and the average network gas price.
+    ///
+    /// The new value is averaged with the current one to smooth out the spikes.
+    pub fn adjust(&self, paid_gas_price: &BigUint, average_gas_price: &BigUint) {
+        if average_gas_price.is_zero() {
+            return;
+        }
+
+        let observed =
+            Ratio::new(paid_gas_price.clone(), average_gas_price.clone()) * safety_margin();
+        let observed = observed.clamp(min_gas_price_scale(), max_gas_price_scale());
+
+        let mut state = self.state.write().unwrap();
+        if state.pinned {
+            return;
+        }
+        state.factor = (&state.factor + observed) / BigUint::from(2u32);
+    }
+
+    /// Periodically adjusts the scale based on the gas prices paid by `eth_sender`.
+    #[must_use]
+    pub fn keep_updated(self, db_pool: ConnectionPool) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(SCALE_UPDATE_INTERVAL);
+            loop {
+                timer.tick().await;
+                if let Err(err) = self.update(&db_pool).await {
+                    vlog::warn!("Can't update the gas price scale: {}", err);
+                }
+            }
+        })
+    }
+
+    async fn update(&self, db_pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = db_pool.access_storage().await?;
+        let average_gas_price = storage.ethereum_schema().load_average_gas_price().await?;
+        let paid_gas_price = storage
+            .ethereum_schema()
+            .load_average_paid_gas_price(RECENT_TRANSACTIONS)
+            .await?;
+
+        if let (Some(average_gas_price), Some(paid_gas_price)) = (average_gas_price, paid_gas_price)
+        {
+            let average_gas_price: BigUint = average_gas_price.to_string().parse()?;
+            self.adjust(&paid_gas_price, &average_gas_price);
+        }
+
+        let factor = ratio_to_big_decimal(&self.factor(), 4);
+        metrics::gauge!(
+            "ticker.gas_price_scale",
+            factor.to_f64().unwrap_or_default()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ratio(numer: u32, denom: u32) -> Ratio<BigUint> {
+        Ratio::new(BigUint::from(numer), BigUint::from(denom))
+    }
+
+    #[test]
+    fn adjusts_to_paid_gas_price() {
+        let scale = GasPriceScale::new(ratio(13, 10));
+
+        // Paid gas price is twice higher than the average one: 2 * 1.1 = 2.2,
+        // averaged with the current value: (1.3 + 2.2) / 2 = 1.75.
+        scale.adjust(&BigUint::from(200u32), &BigUint::from(100u32));
+        assert_eq!(scale.factor(), ratio(175, 100));
+
+        // The observed value is clamped: (1.75 + 1.1) / 2 = 1.425.
+        scale.adjust(&BigUint::from(50u32), &BigUint::from(100u32));
+        assert_eq!(scale.factor(), ratio(1425, 1000));
+
+        // Zero average gas price is ignored.
+        scale.adjust(&BigUint::from(50u32), &BigUint::zero());
+        assert_eq!(scale.factor(), ratio(1425, 1000));
+    }
+
+    #[test]
+    fn pinned_scale_is_not_adjusted() {
+        let scale = GasPriceScale::default();
+        let copy = scale.clone();
+
+        copy.pin(ratio(2, 1));
+        assert!(scale.is_pinned());
+        assert_eq!(scale.factor(), ratio(2, 1));
+
+        scale.adjust(&BigUint::from(100u32), &BigUint::from(100u32));
+        assert_eq!(scale.factor(), ratio(2, 1));
+
+        // After unpinning the scale is adjusted starting from the pinned value.
+        scale.unpin();
+        scale.adjust(&BigUint::from(100u32), &BigUint::from(100u32));
+        assert_eq!(scale.factor(), ratio(155, 100));
+    }
+}
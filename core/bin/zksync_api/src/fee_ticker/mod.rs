@@ -12,6 +12,7 @@ use std::time::Duration;
 
 // External deps
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
 use num::{
     rational::Ratio,
     traits::{Inv, Pow},
@@ -25,6 +26,7 @@ use tokio::time::Instant;
 // Workspace deps
 
 use zksync_config::configs::ticker::TokenPriceSource;
+use zksync_eth_client::EthereumGateway;
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::{
@@ -36,17 +38,22 @@ use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 
 // Local deps
 use crate::fee_ticker::constants::AMORTIZED_COST_PER_CHUNK;
+pub use crate::fee_ticker::gas_price_scale::GasPriceScale;
 pub use crate::fee_ticker::ticker_info::{FeeTickerInfo, TickerInfo};
 use crate::fee_ticker::validator::FeeTokenValidator;
 use crate::fee_ticker::{
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI,
+        coinmarkercap::CoinMarketCapAPI,
+        fallback::{FallbackPriceAPI, PriceSource},
+        uniswap::UniswapPriceAPI,
+        FeeTickerAPI, TickerApi, CONNECTION_TIMEOUT,
     },
-    validator::{watcher::UniswapTokenWatcher, MarketUpdater},
+    validator::{policy::FeeTokenPolicy, watcher::UniswapTokenWatcher, MarketUpdater},
 };
 
 mod constants;
+pub mod gas_price_scale;
 mod ticker_api;
 pub(crate) mod ticker_info;
 pub mod validator;
@@ -156,6 +163,8 @@ pub struct ResponseFee {
     pub normal_fee: Fee,
     pub subsidized_fee: Fee,
     pub subsidy_size_usd: Ratio<BigUint>,
+    /// Time of the oldest price update among the prices used to calculate the fee.
+    pub price_updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone)]
@@ -163,6 +172,8 @@ pub struct ResponseBatchFee {
     pub normal_fee: BatchFee,
     pub subsidized_fee: BatchFee,
     pub subsidy_size_usd: Ratio<BigUint>,
+    /// Time of the oldest price update among the prices used to calculate the fee.
+    pub price_updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Error)]
@@ -194,6 +205,7 @@ pub struct FeeTicker {
     info: Box<dyn FeeTickerInfo>,
     config: TickerConfig,
     validator: FeeTokenValidator,
+    gas_price_scale: GasPriceScale,
 }
 
 const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
@@ -202,49 +214,111 @@ const CPK_CREATE2_FEE_TYPE: OutputFeeType = OutputFeeType::ChangePubKey(
 // Make no more than (Number of tokens) queries per 5 minutes to database is a good result
 // for updating names for tokens.
 const TOKEN_INVALIDATE_CACHE: Duration = Duration::from_secs(5 * 60);
+/// Interval of removing the old fee quotes from the fee history.
+const FEE_HISTORY_CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
 
 #[must_use]
 pub fn run_updaters(
     db_pool: ConnectionPool,
     config: &zksync_config::TickerConfig,
+    eth_gateway: EthereumGateway,
 ) -> Vec<JoinHandle<()>> {
     let cache = (db_pool.clone(), TokenDBCache::new(TOKEN_INVALIDATE_CACHE));
 
     let watcher = UniswapTokenWatcher::new(config.uniswap_url.clone());
 
-    let updater = MarketUpdater::new(cache, watcher);
-    let mut tasks = vec![tokio::spawn(
-        updater.keep_updated(config.token_market_update_time),
-    )];
+    let policy = FeeTokenPolicy::new(
+        BigDecimal::try_from(config.liquidity_volume).expect("Valid f64 for decimal"),
+        HashSet::from_iter(config.unconditionally_valid_tokens.iter().copied()),
+    );
+
+    let updater = MarketUpdater::new(cache, watcher, policy);
+    let mut tasks = vec![
+        tokio::spawn(updater.keep_updated(config.token_market_update_time)),
+        run_fee_history_cleaner(db_pool.clone(), config.fee_history_max_age()),
+    ];
     let client = reqwest::ClientBuilder::new()
         .timeout(CONNECTION_TIMEOUT)
         .connect_timeout(CONNECTION_TIMEOUT)
         .build()
         .expect("Failed to build reqwest::Client");
-    let (price_source, base_url) = config.price_source();
-    let price_updater = match price_source {
-        TokenPriceSource::CoinMarketCap => {
-            let token_price_api =
-                CoinMarketCapAPI::new(client, base_url.parse().expect("Correct CoinMarketCap url"));
-
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
-            tokio::spawn(ticker_api.keep_price_updated())
-        }
-
-        TokenPriceSource::CoinGecko => tokio::spawn(async move {
-            let token_price_api =
-                CoinGeckoAPI::new(client, base_url.parse().expect("Correct CoinGecko url"))
+    let price_sources = config.price_sources();
+    let uniswap_config = config.clone();
+    let price_updater = tokio::spawn(async move {
+        let mut sources = Vec::with_capacity(price_sources.len());
+        for (price_source, base_url, max_price_age) in price_sources {
+            let source = match price_source {
+                TokenPriceSource::CoinMarketCap => PriceSource::new(
+                    "CoinMarketCap",
+                    CoinMarketCapAPI::new(
+                        client.clone(),
+                        base_url.parse().expect("Correct CoinMarketCap url"),
+                    ),
+                    max_price_age,
+                ),
+                TokenPriceSource::CoinGecko => PriceSource::new(
+                    "CoinGecko",
+                    CoinGeckoAPI::new(
+                        client.clone(),
+                        base_url.parse().expect("Correct CoinGecko url"),
+                    )
                     .await
-                    .expect("failed to init CoinGecko client");
-            let ticker_api = TickerApi::new(db_pool, token_price_api);
+                    .expect("failed to init CoinGecko client"),
+                    max_price_age,
+                ),
+                TokenPriceSource::Uniswap => PriceSource::new(
+                    "Uniswap",
+                    UniswapPriceAPI::new(
+                        eth_gateway.clone(),
+                        uniswap_config.uniswap_factory_address,
+                        uniswap_config.uniswap_weth_address,
+                        uniswap_config.uniswap_usd_token_address,
+                        uniswap_config.uniswap_usd_token_decimals,
+                        uniswap_config.uniswap_twap_period_blocks,
+                    ),
+                    max_price_age,
+                ),
+            };
+            sources.push(source);
+        }
+        let ticker_api = TickerApi::new(db_pool, FallbackPriceAPI::new(sources));
 
-            ticker_api.keep_price_updated().await;
-        }),
-    };
+        ticker_api.keep_price_updated().await;
+    });
     tasks.push(price_updater);
     tasks
 }
 
+/// Runs the task that periodically removes the fee quotes older than `max_age` from the fee history.
+#[must_use]
+fn run_fee_history_cleaner(db_pool: ConnectionPool, max_age: Duration) -> JoinHandle<()> {
+    async fn remove_old_fee_quotes(
+        db_pool: &ConnectionPool,
+        max_age: Duration,
+    ) -> anyhow::Result<()> {
+        let removed = db_pool
+            .access_storage()
+            .await?
+            .misc_schema()
+            .remove_old_fee_quotes(Utc::now() - chrono::Duration::from_std(max_age)?)
+            .await?;
+        if removed > 0 {
+            vlog::info!("Removed {} old fee quotes from the fee history", removed);
+        }
+        Ok(())
+    }
+
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(FEE_HISTORY_CLEANUP_INTERVAL);
+        loop {
+            timer.tick().await;
+            if let Err(err) = remove_old_fee_quotes(&db_pool, max_age).await {
+                vlog::warn!("Can't remove the old fee quotes: {}", err);
+            }
+        }
+    })
+}
+
 impl FeeTicker {
     pub fn new(
         info: Box<dyn FeeTickerInfo>,
@@ -255,6 +329,7 @@ impl FeeTicker {
             info,
             config,
             validator,
+            gas_price_scale: GasPriceScale::default(),
         }
     }
 
@@ -287,11 +362,16 @@ impl FeeTicker {
 }
 
 impl FeeTicker {
-    /// Increases the gas price by a constant coefficient.
+    /// Increases the gas price by the current gas price scale.
     /// Due to the high volatility of gas prices, we are include the risk
     /// in the fee in order not to go into negative territory.
-    fn risk_gas_price_estimate(gas_price: BigUint) -> BigUint {
-        gas_price * BigUint::from(130u32) / BigUint::from(100u32)
+    fn risk_gas_price_estimate(&self, gas_price: BigUint) -> BigUint {
+        (Ratio::from_integer(gas_price) * self.gas_price_scale.factor()).to_integer()
+    }
+
+    /// Returns the gas price scale used by the ticker.
+    pub fn gas_price_scale(&self) -> &GasPriceScale {
+        &self.gas_price_scale
     }
 
     pub async fn get_token_price(
@@ -333,9 +413,10 @@ impl FeeTicker {
         let token = self.info.get_token(token).await?;
 
         let gas_price_wei = self.info.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
+        let scale_gas_price = self.risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
+        let price_updated_at = self.price_updated_at(&token).await?;
 
         let (fee_type, gas_tx_amount, op_chunks) = self.gas_tx_amount(tx_type, recipient).await?;
 
@@ -394,6 +475,7 @@ impl FeeTicker {
                 normal_fee,
                 subsidized_fee,
                 subsidy_size_usd,
+                price_updated_at,
             });
         }
 
@@ -402,6 +484,7 @@ impl FeeTicker {
             normal_fee: normal_fee.clone(),
             subsidized_fee: normal_fee,
             subsidy_size_usd: Ratio::from(BigUint::from(0u32)),
+            price_updated_at,
         })
     }
 
@@ -416,9 +499,10 @@ impl FeeTicker {
         let token = self.info.get_token(token).await?;
 
         let gas_price_wei = self.info.get_gas_price_wei().await?;
-        let scale_gas_price = Self::risk_gas_price_estimate(gas_price_wei.clone());
+        let scale_gas_price = self.risk_gas_price_estimate(gas_price_wei.clone());
         let wei_price_usd = self.wei_price_usd().await?;
         let token_usd_risk = self.token_usd_risk(&token).await?;
+        let price_updated_at = self.price_updated_at(&token).await?;
 
         let mut total_normal_gas_tx_amount = Ratio::from(BigUint::zero());
         let mut total_op_chunks = Ratio::from(BigUint::zero());
@@ -515,6 +599,7 @@ impl FeeTicker {
             normal_fee,
             subsidized_fee,
             subsidy_size_usd,
+            price_updated_at,
         })
     }
 
@@ -530,6 +615,19 @@ impl FeeTicker {
         Ok(res)
     }
 
+    /// Returns the time of the oldest price update among the ETH and the given token prices.
+    pub async fn price_updated_at(&self, token: &Token) -> anyhow::Result<DateTime<Utc>> {
+        let eth_price = self
+            .info
+            .get_last_token_price(TokenLike::Id(TokenId(0)))
+            .await?;
+        let token_price = self
+            .info
+            .get_last_token_price(TokenLike::Id(token.id))
+            .await?;
+        Ok(eth_price.last_updated.min(token_price.last_updated))
+    }
+
     pub async fn token_usd_risk(&self, token: &Token) -> anyhow::Result<Ratio<BigUint>> {
         let start = Instant::now();
         let token_risk_factor = self
@@ -552,18 +650,50 @@ impl FeeTicker {
             .ok_or_else(|| anyhow::format_err!("Token is not acceptable for fee"))
     }
 
+    /// Persists the fee quoted by the public fee endpoints in the background, so the fee history
+    /// can be analyzed later. Failures are only logged, since they must not affect the response.
+    pub fn record_fee_quote(&self, token: TokenLike, fee: Fee) {
+        let ticker = self.clone();
+        tokio::spawn(async move {
+            if let Err(err) = ticker.store_fee_quote(token.clone(), &fee).await {
+                vlog::warn!("Can't store the quoted fee for token {:?}: {}", token, err);
+            }
+        });
+    }
+
+    async fn store_fee_quote(&self, token: TokenLike, fee: &Fee) -> anyhow::Result<()> {
+        let token = self.info.get_token(token).await?;
+        let token_price = self
+            .info
+            .get_last_token_price(TokenLike::Id(token.id))
+            .await?;
+        self.info
+            .store_fee_quote(token.id, fee, &token_price.usd_price)
+            .await
+    }
+
     /// Returns `true` if account does not yet exist in the zkSync network.
     pub async fn is_account_new(&self, address: Address) -> anyhow::Result<bool> {
         self.info.is_account_new(address).await
     }
 
-    async fn gas_tx_amount(
+    /// Returns the fee type the transaction is charged by, e.g. the transfers
+    /// to the new accounts are charged as `TransferToNew`.
+    pub async fn output_fee_type(
         &self,
         tx_type: TxFeeTypes,
         recipient: Address,
-    ) -> anyhow::Result<(OutputFeeType, BigUint, BigUint)> {
-        let start = Instant::now();
-        let (fee_type, op_chunks) = match tx_type {
+    ) -> anyhow::Result<OutputFeeType> {
+        let (fee_type, _) = self.fee_type_and_chunks(tx_type, recipient).await?;
+        Ok(fee_type)
+    }
+
+    async fn fee_type_and_chunks(
+        &self,
+        tx_type: TxFeeTypes,
+        recipient: Address,
+    ) -> anyhow::Result<(OutputFeeType, usize)> {
+        let fee_type_and_chunks = match tx_type {
             TxFeeTypes::Withdraw => (OutputFeeType::Withdraw, WithdrawOp::CHUNKS),
             TxFeeTypes::FastWithdraw => (OutputFeeType::FastWithdraw, WithdrawOp::CHUNKS),
             TxFeeTypes::WithdrawNFT => (OutputFeeType::WithdrawNFT, WithdrawNFTOp::CHUNKS),
@@ -581,6 +711,16 @@ impl FeeTicker {
             }
             TxFeeTypes::MintNFT => (OutputFeeType::MintNFT, MintNFTOp::CHUNKS),
         };
+        Ok(fee_type_and_chunks)
+    }
+
+    async fn gas_tx_amount(
+        &self,
+        tx_type: TxFeeTypes,
+        recipient: Address,
+    ) -> anyhow::Result<(OutputFeeType, BigUint, BigUint)> {
+        let start = Instant::now();
+        let (fee_type, op_chunks) = self.fee_type_and_chunks(tx_type, recipient).await?;
 
         let gas_tx_amount = if matches!(
             fee_type,
@@ -16,7 +16,7 @@ use crate::fee_ticker::{
 };
 
 use super::*;
-use crate::fee_ticker::ticker_info::BlocksInFutureAggregatedOperations;
+use crate::fee_ticker::ticker_info::{BlocksInFutureAggregatedOperations, TokenPriceCache};
 
 const TEST_FAST_WITHDRAW_COEFF: f64 = 10.0;
 
@@ -195,6 +195,15 @@ impl FeeTickerInfo for MockTickerInfo {
         unreachable!("incorrect token input")
     }
 
+    async fn store_fee_quote(
+        &self,
+        _token_id: TokenId,
+        _fee: &Fee,
+        _token_price_usd: &Ratio<BigUint>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
     }
@@ -708,3 +717,27 @@ fn test_zero_price_token_fee() {
     ))
     .unwrap_err();
 }
+
+#[test]
+fn test_token_price_cache() {
+    let cache = TokenPriceCache::default();
+    let price = |usd_price: u32| TokenPrice {
+        usd_price: Ratio::from_integer(BigUint::from(usd_price)),
+        last_updated: Utc::now(),
+    };
+
+    assert!(cache.get(TokenId(1)).is_none());
+    cache.insert(TokenId(1), price(10));
+    assert_eq!(
+        cache.get(TokenId(1)).unwrap().usd_price,
+        Ratio::from_integer(BigUint::from(10u32))
+    );
+
+    // Refreshing the cache drops the prices of the tokens that are not stored anymore.
+    cache.replace_all(vec![(TokenId(2), price(20))].into_iter().collect());
+    assert!(cache.get(TokenId(1)).is_none());
+    assert_eq!(
+        cache.get(TokenId(2)).unwrap().usd_price,
+        Ratio::from_integer(BigUint::from(20u32))
+    );
+}
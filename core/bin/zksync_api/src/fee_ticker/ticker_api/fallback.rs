@@ -0,0 +1,317 @@
+//! Token price API that queries several price sources in the order of priority.
+//!
+//! If the preferred source is unavailable or returns an outdated price, the next one is used.
+//! When no source is able to provide a price, an error is returned and `TickerApi` keeps the
+//! last price stored in the database, so the fee ticker keeps working with the cached price.
+
+// Built-in deps
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+// External deps
+use async_trait::async_trait;
+use chrono::Utc;
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_types::{Token, TokenPrice};
+
+/// Number of consecutive failures after which the price source is considered unhealthy.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+/// Time during which an unhealthy price source is not queried.
+const UNHEALTHY_SOURCE_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Debug, Default)]
+struct SourceHealth {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+/// Single price source with its own staleness limit and health state.
+pub struct PriceSource {
+    name: String,
+    api: Box<dyn TokenPriceAPI + Send + Sync>,
+    max_price_age: chrono::Duration,
+    health: Mutex<SourceHealth>,
+}
+
+impl std::fmt::Debug for PriceSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PriceSource")
+            .field("name", &self.name)
+            .field("max_price_age", &self.max_price_age)
+            .field("health", &self.health)
+            .finish()
+    }
+}
+
+impl PriceSource {
+    pub fn new(
+        name: impl Into<String>,
+        api: impl TokenPriceAPI + Send + Sync + 'static,
+        max_price_age: Duration,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            api: Box::new(api),
+            max_price_age: chrono::Duration::from_std(max_price_age)
+                .expect("Price age limit is too big"),
+            health: Mutex::default(),
+        }
+    }
+
+    fn is_healthy(&self) -> bool {
+        let mut health = self.health.lock().unwrap();
+        match health.unhealthy_until {
+            Some(until) if until > Instant::now() => false,
+            Some(_) => {
+                // Cooldown is over, give the source another chance.
+                *health = SourceHealth::default();
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn report_success(&self) {
+        *self.health.lock().unwrap() = SourceHealth::default();
+    }
+
+    fn report_failure(&self) {
+        let mut health = self.health.lock().unwrap();
+        health.consecutive_failures += 1;
+        if health.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+            vlog::warn!(
+                "Price source {} failed {} times in a row, it won't be used for {:?}",
+                self.name,
+                health.consecutive_failures,
+                UNHEALTHY_SOURCE_COOLDOWN
+            );
+            health.unhealthy_until = Some(Instant::now() + UNHEALTHY_SOURCE_COOLDOWN);
+        }
+    }
+}
+
+/// Token price API with ordered fallback between several price sources.
+#[derive(Debug)]
+pub struct FallbackPriceAPI {
+    sources: Vec<PriceSource>,
+}
+
+impl FallbackPriceAPI {
+    /// Creates a new API instance. Sources are queried in the given order.
+    pub fn new(sources: Vec<PriceSource>) -> Self {
+        assert!(!sources.is_empty(), "At least one price source is required");
+        Self { sources }
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for FallbackPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        self.get_price_with_source(token)
+            .await
+            .map(|(price, _)| price)
+    }
+
+    async fn get_price_with_source(
+        &self,
+        token: &Token,
+    ) -> Result<(TokenPrice, Option<String>), PriceError> {
+        let mut api_error = None;
+        let mut not_found_error = None;
+
+        for source in &self.sources {
+            if !source.is_healthy() {
+                continue;
+            }
+
+            match source.api.get_price(token).await {
+                Ok(price) => {
+                    source.report_success();
+                    if Utc::now() - price.last_updated > source.max_price_age {
+                        vlog::warn!(
+                            "Price of token {} from {} is outdated: last updated at {}",
+                            token.symbol,
+                            source.name,
+                            price.last_updated
+                        );
+                        api_error = Some(PriceError::api_error(format!(
+                            "{} returned an outdated price",
+                            source.name
+                        )));
+                        continue;
+                    }
+                    return Ok((price, Some(source.name.clone())));
+                }
+                // The source works, it just doesn't know about this token.
+                Err(err @ PriceError::TokenNotFound(_)) => {
+                    source.report_success();
+                    not_found_error.get_or_insert(err);
+                }
+                Err(err) => {
+                    vlog::warn!(
+                        "Can't get price of token {} from {}: {}",
+                        token.symbol,
+                        source.name,
+                        err
+                    );
+                    metrics::increment_counter!("ticker.price_source.failure", "source" => source.name.clone());
+                    source.report_failure();
+                    api_error = Some(err);
+                }
+            }
+        }
+
+        // Token is reported as unknown only if none of the sources has failed, otherwise
+        // the cached price would be overwritten because of a temporary outage.
+        Err(api_error
+            .or(not_found_error)
+            .unwrap_or_else(|| PriceError::api_error("All price sources are unavailable")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::{rational::Ratio, BigUint};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use zksync_types::{TokenId, TokenKind};
+
+    #[derive(Debug, Clone)]
+    enum MockResponse {
+        Price { usd: u32, age: chrono::Duration },
+        NotFound,
+        Error,
+    }
+
+    #[derive(Debug, Clone)]
+    struct MockPriceAPI {
+        response: MockResponse,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl MockPriceAPI {
+        fn new(response: MockResponse) -> Self {
+            Self {
+                response,
+                calls: Arc::default(),
+            }
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[async_trait]
+    impl TokenPriceAPI for MockPriceAPI {
+        async fn get_price(&self, _token: &Token) -> Result<TokenPrice, PriceError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match &self.response {
+                MockResponse::Price { usd, age } => Ok(TokenPrice {
+                    usd_price: Ratio::from_integer(BigUint::from(*usd)),
+                    last_updated: Utc::now() - *age,
+                }),
+                MockResponse::NotFound => Err(PriceError::token_not_found("unknown token")),
+                MockResponse::Error => Err(PriceError::api_error("service unavailable")),
+            }
+        }
+    }
+
+    fn fresh_price(usd: u32) -> MockResponse {
+        MockResponse::Price {
+            usd,
+            age: chrono::Duration::zero(),
+        }
+    }
+
+    fn source(name: &str, api: &MockPriceAPI) -> PriceSource {
+        PriceSource::new(name, api.clone(), Duration::from_secs(60))
+    }
+
+    fn token() -> Token {
+        Token::new(TokenId(1), Default::default(), "DAI", 18, TokenKind::ERC20)
+    }
+
+    async fn usd_price(api: &FallbackPriceAPI) -> Result<u32, PriceError> {
+        let price = api.get_price(&token()).await?;
+        Ok(price.usd_price.to_integer().to_string().parse().unwrap())
+    }
+
+    #[tokio::test]
+    async fn uses_sources_in_order() {
+        let primary = MockPriceAPI::new(fresh_price(1));
+        let fallback = MockPriceAPI::new(fresh_price(2));
+        let api = FallbackPriceAPI::new(vec![
+            source("primary", &primary),
+            source("fallback", &fallback),
+        ]);
+
+        assert_eq!(usd_price(&api).await.unwrap(), 1);
+        assert_eq!(fallback.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_error_and_outdated_price() {
+        let failing = MockPriceAPI::new(MockResponse::Error);
+        let outdated = MockPriceAPI::new(MockResponse::Price {
+            usd: 2,
+            age: chrono::Duration::hours(1),
+        });
+        let fallback = MockPriceAPI::new(fresh_price(3));
+        let api = FallbackPriceAPI::new(vec![
+            source("failing", &failing),
+            source("outdated", &outdated),
+            source("fallback", &fallback),
+        ]);
+
+        assert_eq!(usd_price(&api).await.unwrap(), 3);
+
+        let (_, source) = api.get_price_with_source(&token()).await.unwrap();
+        assert_eq!(source.as_deref(), Some("fallback"));
+    }
+
+    #[tokio::test]
+    async fn reports_not_found_only_without_failures() {
+        let not_found = MockPriceAPI::new(MockResponse::NotFound);
+        let failing = MockPriceAPI::new(MockResponse::Error);
+
+        let api = FallbackPriceAPI::new(vec![
+            source("not_found", &not_found),
+            source("other_not_found", &not_found),
+        ]);
+        assert!(matches!(
+            usd_price(&api).await,
+            Err(PriceError::TokenNotFound(_))
+        ));
+
+        let api = FallbackPriceAPI::new(vec![
+            source("not_found", &not_found),
+            source("failing", &failing),
+        ]);
+        assert!(matches!(
+            usd_price(&api).await,
+            Err(PriceError::ApiError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn skips_unhealthy_source() {
+        let failing = MockPriceAPI::new(MockResponse::Error);
+        let fallback = MockPriceAPI::new(fresh_price(2));
+        let api = FallbackPriceAPI::new(vec![
+            source("failing", &failing),
+            source("fallback", &fallback),
+        ]);
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES + 2 {
+            assert_eq!(usd_price(&api).await.unwrap(), 2);
+        }
+        assert_eq!(failing.calls(), MAX_CONSECUTIVE_FAILURES as usize);
+    }
+}
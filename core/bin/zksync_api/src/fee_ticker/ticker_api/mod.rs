@@ -12,6 +12,8 @@ use zksync_types::{Token, TokenId, TokenPrice};
 
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod fallback;
+pub mod uniswap;
 
 const UPDATE_PRICE_INTERVAL_SECS: u64 = 10 * 60;
 /// The limit of time we are willing to wait for response.
@@ -22,6 +24,14 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_millis(700);
 #[async_trait]
 pub trait TokenPriceAPI {
     async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError>;
+
+    /// Returns the price along with the name of the source that provided it, if it's known.
+    async fn get_price_with_source(
+        &self,
+        token: &Token,
+    ) -> Result<(TokenPrice, Option<String>), PriceError> {
+        self.get_price(token).await.map(|price| (price, None))
+    }
 }
 
 /// Api responsible for querying for TokenPrices
@@ -37,7 +47,7 @@ pub(super) struct TickerApi<T: TokenPriceAPI> {
     token_price_api: T,
 }
 
-impl<T: TokenPriceAPI> TickerApi<T> {
+impl<T: TokenPriceAPI + Sync> TickerApi<T> {
     pub fn new(db_pool: ConnectionPool, token_price_api: T) -> Self {
         Self {
             db_pool,
@@ -58,39 +68,63 @@ impl<T: TokenPriceAPI> TickerApi<T> {
             .map_err(|err| PriceError::DBError(err.to_string()))?;
         Ok(tokens.into_values().collect())
     }
+    /// Stores the price of the token. Prices actually fetched from the API
+    /// are also added to the prices history.
     async fn update_stored_value(
         &self,
         token_id: TokenId,
         price: TokenPrice,
+        source: Option<String>,
+        fetched: bool,
     ) -> Result<(), anyhow::Error> {
         let mut storage = self
             .db_pool
             .access_storage()
             .await
             .map_err(|e| format_err!("Can't access storage: {}", e))?;
-
-        storage
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .map_err(|e| format_err!("Can't start transaction: {}", e))?;
+
+        if fetched {
+            transaction
+                .tokens_schema()
+                .store_token_price_history(token_id, &price)
+                .await
+                .map_err(|e| format_err!("Can't store token price history: {}", e))?;
+        }
+        transaction
             .tokens_schema()
-            .update_historical_ticker_price(token_id, price)
+            .update_historical_ticker_price(token_id, price, source.as_deref())
             .await
             .map_err(|e| format_err!("Can't update historical ticker price from storage: {}", e))?;
+        transaction
+            .commit()
+            .await
+            .map_err(|e| format_err!("Can't commit transaction: {}", e))?;
 
         Ok(())
     }
     async fn update_price(&self, token: &Token) -> Result<(), PriceError> {
         let start = Instant::now();
-        let api_price = match self.token_price_api.get_price(token).await {
-            Ok(api_price) => api_price,
-
-            // Database contain this token, but is not listed in CoinGecko(CoinMarketCap)
-            Err(PriceError::TokenNotFound(_)) => TokenPrice {
-                usd_price: Ratio::from_integer(0u32.into()),
-                last_updated: Utc::now(),
-            },
-            Err(e) => return Err(e),
-        };
-
-        self.update_stored_value(token.id, api_price.clone())
+        let (api_price, source, fetched) =
+            match self.token_price_api.get_price_with_source(token).await {
+                Ok((api_price, source)) => (api_price, source, true),
+
+                // Database contain this token, but is not listed in CoinGecko(CoinMarketCap)
+                Err(PriceError::TokenNotFound(_)) => (
+                    TokenPrice {
+                        usd_price: Ratio::from_integer(0u32.into()),
+                        last_updated: Utc::now(),
+                    },
+                    None,
+                    false,
+                ),
+                Err(e) => return Err(e),
+            };
+
+        self.update_stored_value(token.id, api_price, source, fetched)
             .await
             .map_err(|err| PriceError::DBError(err.to_string()))?;
         metrics::histogram!("ticker.update_price", start.elapsed());
@@ -0,0 +1,430 @@
+// Built-in deps
+use std::{collections::HashMap, sync::Mutex, time::Instant};
+// External deps
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use num::{rational::Ratio, BigUint, Zero};
+use web3::{
+    contract::Options,
+    types::{BlockId, BlockNumber as Web3BlockNumber, U256},
+};
+// Workspace deps
+use super::TokenPriceAPI;
+use crate::fee_ticker::PriceError;
+use zksync_contracts::{uniswap_v2_factory_contract, uniswap_v2_pair_contract};
+use zksync_eth_client::ethereum_gateway::EthereumGateway;
+use zksync_types::{Address, Token, TokenPrice};
+
+/// Number of decimals of the wrapped Ether.
+const WETH_DECIMALS: u8 = 18;
+/// Accumulated prices are UQ112x112 fixed point numbers.
+const PRICE_RESOLUTION: usize = 112;
+
+/// Price source computing the time-weighted average prices (TWAP) of the Uniswap V2 pairs.
+///
+/// The pair accumulates the prices of its tokens multiplied by the time they were held, so the
+/// average price over a period is the difference of the accumulated prices at its ends divided
+/// by its length. The accumulated prices are observed in the latest block and `twap_period_blocks`
+/// blocks before it, so the price can't be moved by a single block, unlike the spot price.
+///
+/// Token price in USD is its average price in the pair with WETH multiplied by the average price
+/// of WETH in the pair with the USD stablecoin.
+#[derive(Debug)]
+pub struct UniswapPriceAPI {
+    eth: EthereumGateway,
+    factory_address: Address,
+    weth_address: Address,
+    usd_token_address: Address,
+    usd_token_decimals: u8,
+    twap_period_blocks: u64,
+    /// Addresses of the pairs by the addresses of their tokens, pairs are never removed.
+    pairs: Mutex<HashMap<(Address, Address), Address>>,
+}
+
+impl UniswapPriceAPI {
+    pub fn new(
+        eth: EthereumGateway,
+        factory_address: Address,
+        weth_address: Address,
+        usd_token_address: Address,
+        usd_token_decimals: u8,
+        twap_period_blocks: u64,
+    ) -> Self {
+        Self {
+            eth,
+            factory_address,
+            weth_address,
+            usd_token_address,
+            usd_token_decimals,
+            twap_period_blocks,
+            pairs: Mutex::default(),
+        }
+    }
+
+    async fn pair_address(
+        &self,
+        token_a: Address,
+        token_b: Address,
+    ) -> Result<Address, PriceError> {
+        let tokens = sort_tokens(token_a, token_b);
+        if let Some(pair) = self.pairs.lock().unwrap().get(&tokens) {
+            return Ok(*pair);
+        }
+
+        let pair: Address = self
+            .eth
+            .call_contract_function(
+                "getPair",
+                tokens,
+                None,
+                Options::default(),
+                None,
+                self.factory_address,
+                uniswap_v2_factory_contract(),
+            )
+            .await
+            .map_err(|err| {
+                PriceError::api_error(format!("Uniswap pair request failed: {}", err))
+            })?;
+        if pair.is_zero() {
+            return Err(PriceError::token_not_found(format!(
+                "Uniswap pair of {:#x} and {:#x} doesn't exist",
+                token_a, token_b
+            )));
+        }
+        self.pairs.lock().unwrap().insert(tokens, pair);
+        Ok(pair)
+    }
+
+    async fn observe(&self, pair: Address, block: u64) -> Result<Observation, PriceError> {
+        let block = Some(BlockId::Number(Web3BlockNumber::Number(block.into())));
+        let contract = uniswap_v2_pair_contract();
+        let (reserve0, reserve1, timestamp): (U256, U256, U256) = self
+            .eth
+            .call_contract_function(
+                "getReserves",
+                (),
+                None,
+                Options::default(),
+                block,
+                pair,
+                contract.clone(),
+            )
+            .await
+            .map_err(PriceError::api_error)?;
+        let price0_cumulative: U256 = self
+            .eth
+            .call_contract_function(
+                "price0CumulativeLast",
+                (),
+                None,
+                Options::default(),
+                block,
+                pair,
+                contract.clone(),
+            )
+            .await
+            .map_err(PriceError::api_error)?;
+        let price1_cumulative: U256 = self
+            .eth
+            .call_contract_function(
+                "price1CumulativeLast",
+                (),
+                None,
+                Options::default(),
+                block,
+                pair,
+                contract,
+            )
+            .await
+            .map_err(PriceError::api_error)?;
+
+        Observation::new(
+            (reserve0, reserve1, timestamp),
+            price0_cumulative,
+            price1_cumulative,
+        )
+    }
+
+    /// Returns the average price of the whole `base` token in the whole `quote` tokens
+    /// along with the time of the last trade it is averaged up to.
+    async fn average_price(
+        &self,
+        (base, base_decimals): (Address, u8),
+        (quote, quote_decimals): (Address, u8),
+        latest_block: u64,
+    ) -> Result<(Ratio<BigUint>, u32), PriceError> {
+        let pair = self.pair_address(base, quote).await?;
+        let older = self
+            .observe(pair, latest_block.saturating_sub(self.twap_period_blocks))
+            .await?;
+        let newer = self.observe(pair, latest_block).await?;
+        let base_is_token0 = sort_tokens(base, quote).0 == base;
+
+        let price = average_price(&older, &newer, base_is_token0).ok_or_else(|| {
+            PriceError::api_error(format!("Uniswap pair {:#x} has no liquidity", pair))
+        })?;
+        Ok((
+            scale_price(price, base_decimals, quote_decimals),
+            newer.timestamp,
+        ))
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for UniswapPriceAPI {
+    async fn get_price(&self, token: &Token) -> Result<TokenPrice, PriceError> {
+        let start = Instant::now();
+
+        let latest_block = self
+            .eth
+            .block_number()
+            .await
+            .map_err(|err| PriceError::api_error(format!("Ethereum request failed: {}", err)))?
+            .as_u64();
+        let (eth_price, eth_observed_at) = self
+            .average_price(
+                (self.weth_address, WETH_DECIMALS),
+                (self.usd_token_address, self.usd_token_decimals),
+                latest_block,
+            )
+            .await?;
+
+        // ETH is priced as WETH.
+        let (usd_price, observed_at) = if token.id.0 == 0 {
+            (eth_price, eth_observed_at)
+        } else {
+            let (price_in_eth, observed_at) = self
+                .average_price(
+                    (token.address, token.decimals),
+                    (self.weth_address, WETH_DECIMALS),
+                    latest_block,
+                )
+                .await?;
+            (price_in_eth * eth_price, observed_at.min(eth_observed_at))
+        };
+
+        metrics::histogram!("ticker.uniswap.request", start.elapsed());
+        Ok(TokenPrice {
+            usd_price,
+            last_updated: Utc.timestamp(i64::from(observed_at), 0),
+        })
+    }
+}
+
+/// Accumulated prices of the pair as of its last trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Observation {
+    /// `blockTimestampLast` of the pair, i.e. the time of the last trade.
+    timestamp: u32,
+    reserve0: U256,
+    reserve1: U256,
+    price0_cumulative: U256,
+    price1_cumulative: U256,
+}
+
+impl Observation {
+    fn new(
+        (reserve0, reserve1, timestamp): (U256, U256, U256),
+        price0_cumulative: U256,
+        price1_cumulative: U256,
+    ) -> Result<Self, PriceError> {
+        if timestamp > U256::from(u32::MAX) {
+            return Err(PriceError::api_error(format!(
+                "Invalid Uniswap pair timestamp {}",
+                timestamp
+            )));
+        }
+        Ok(Self {
+            timestamp: timestamp.as_u32(),
+            reserve0,
+            reserve1,
+            price0_cumulative,
+            price1_cumulative,
+        })
+    }
+}
+
+/// Tokens of the pair are ordered by their addresses.
+fn sort_tokens(token_a: Address, token_b: Address) -> (Address, Address) {
+    if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    }
+}
+
+/// Average price of the base token in the quote tokens between the observations, both in
+/// the smallest units. If the pair wasn't traded in between, the price has remained the same
+/// since the last trade, so the price given by the reserves is returned.
+fn average_price(
+    older: &Observation,
+    newer: &Observation,
+    base_is_token0: bool,
+) -> Option<Ratio<BigUint>> {
+    // Timestamps and the accumulated prices are expected to overflow, see `UniswapV2Pair`.
+    let elapsed = newer.timestamp.wrapping_sub(older.timestamp);
+    if elapsed == 0 {
+        let (base_reserve, quote_reserve) = if base_is_token0 {
+            (newer.reserve0, newer.reserve1)
+        } else {
+            (newer.reserve1, newer.reserve0)
+        };
+        if base_reserve.is_zero() {
+            return None;
+        }
+        return Some(Ratio::new(
+            u256_to_biguint(quote_reserve),
+            u256_to_biguint(base_reserve),
+        ));
+    }
+
+    let (older_cumulative, newer_cumulative) = if base_is_token0 {
+        (older.price0_cumulative, newer.price0_cumulative)
+    } else {
+        (older.price1_cumulative, newer.price1_cumulative)
+    };
+    let accumulated = newer_cumulative.overflowing_sub(older_cumulative).0;
+    Some(Ratio::new(
+        u256_to_biguint(accumulated),
+        BigUint::from(elapsed) << PRICE_RESOLUTION,
+    ))
+}
+
+/// Converts the price in the smallest units into the price of the whole tokens.
+fn scale_price(price: Ratio<BigUint>, base_decimals: u8, quote_decimals: u8) -> Ratio<BigUint> {
+    let base_unit = BigUint::from(10u32).pow(u32::from(base_decimals));
+    let quote_unit = BigUint::from(10u32).pow(u32::from(quote_decimals));
+    price * Ratio::new(base_unit, quote_unit)
+}
+
+fn u256_to_biguint(value: U256) -> BigUint {
+    if value.is_zero() {
+        return BigUint::zero();
+    }
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    BigUint::from_bytes_be(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Accumulated price of `price` held for `secs` seconds.
+    fn accumulated(price: Ratio<BigUint>, secs: u32) -> U256 {
+        let value = (price.numer() << PRICE_RESOLUTION) * BigUint::from(secs) / price.denom();
+        U256::from_big_endian(&value.to_bytes_be())
+    }
+
+    fn observation(
+        timestamp: u32,
+        price0_cumulative: U256,
+        price1_cumulative: U256,
+    ) -> Observation {
+        Observation::new(
+            (U256::from(1000), U256::from(2000), U256::from(timestamp)),
+            price0_cumulative,
+            price1_cumulative,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_observation() {
+        let observation = Observation::new(
+            (U256::from(1), U256::from(2), U256::from(1_600_000_000u32)),
+            U256::from(3),
+            U256::from(4),
+        )
+        .unwrap();
+        assert_eq!(observation.timestamp, 1_600_000_000);
+        assert_eq!(observation.reserve0, U256::from(1));
+        assert_eq!(observation.reserve1, U256::from(2));
+        assert_eq!(observation.price0_cumulative, U256::from(3));
+        assert_eq!(observation.price1_cumulative, U256::from(4));
+
+        let invalid = Observation::new(
+            (U256::from(1), U256::from(2), U256::from(u64::MAX)),
+            U256::from(3),
+            U256::from(4),
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn average_price_of_both_tokens() {
+        let price0 = |n: u32| Ratio::new(BigUint::from(n), BigUint::from(1u32));
+        let price1 = |n: u32| Ratio::new(BigUint::from(1u32), BigUint::from(n));
+        // Price was 2 for 100 seconds and 4 for 300 seconds, so the average is 3.5.
+        let older = observation(1000, U256::from(7), U256::from(11));
+        let newer = observation(
+            1400,
+            U256::from(7) + accumulated(price0(2), 100) + accumulated(price0(4), 300),
+            U256::from(11) + accumulated(price1(2), 100) + accumulated(price1(4), 300),
+        );
+
+        assert_eq!(
+            average_price(&older, &newer, true),
+            Some(Ratio::new(BigUint::from(7u32), BigUint::from(2u32)))
+        );
+        // (1/2 * 100 + 1/4 * 300) / 400 = 5/16
+        assert_eq!(
+            average_price(&older, &newer, false),
+            Some(Ratio::new(BigUint::from(5u32), BigUint::from(16u32)))
+        );
+    }
+
+    #[test]
+    fn average_price_with_overflow() {
+        let price = Ratio::new(BigUint::from(3u32), BigUint::from(1u32));
+        let older_cumulative = U256::MAX - U256::from(5);
+        let newer_cumulative = older_cumulative.overflowing_add(accumulated(price, 60)).0;
+        // Timestamp overflows as well.
+        let older = observation(u32::MAX - 10, older_cumulative, U256::zero());
+        let newer = observation(49, newer_cumulative, U256::zero());
+
+        assert_eq!(
+            average_price(&older, &newer, true),
+            Some(Ratio::new(BigUint::from(3u32), BigUint::from(1u32)))
+        );
+    }
+
+    #[test]
+    fn price_of_idle_pair() {
+        let older = observation(1000, U256::from(7), U256::from(11));
+        assert_eq!(
+            average_price(&older, &older, true),
+            Some(Ratio::new(BigUint::from(2u32), BigUint::from(1u32)))
+        );
+        assert_eq!(
+            average_price(&older, &older, false),
+            Some(Ratio::new(BigUint::from(1u32), BigUint::from(2u32)))
+        );
+
+        let mut empty = older;
+        empty.reserve0 = U256::zero();
+        assert_eq!(average_price(&empty, &empty, true), None);
+    }
+
+    #[test]
+    fn scaled_price() {
+        // 1 WETH (18 decimals) is traded for 2000 USDC (6 decimals).
+        let raw_price = Ratio::new(
+            BigUint::from(2000u32) * BigUint::from(10u32).pow(6),
+            BigUint::from(10u32).pow(18),
+        );
+        assert_eq!(
+            scale_price(raw_price, WETH_DECIMALS, 6),
+            Ratio::from_integer(BigUint::from(2000u32))
+        );
+    }
+
+    #[test]
+    fn tokens_order() {
+        let low = Address::from_low_u64_be(1);
+        let high = Address::from_low_u64_be(2);
+        assert_eq!(sort_tokens(low, high), (low, high));
+        assert_eq!(sort_tokens(high, low), (low, high));
+    }
+}
@@ -5,18 +5,23 @@
 #[cfg(test)]
 use std::any::Any;
 
-use std::time::Instant;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 // External deps
 use anyhow::format_err;
 use async_trait::async_trait;
 use chrono::Utc;
 use num::rational::Ratio;
 use num::BigUint;
+use tokio::task::JoinHandle;
 // Workspace deps
 use zksync_storage::ConnectionPool;
 use zksync_token_db_cache::TokenDBCache;
 use zksync_types::aggregated_operations::AggregatedActionType;
-use zksync_types::{Address, Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{Address, Fee, Token, TokenId, TokenLike, TokenPrice};
 // Local deps
 use crate::fee_ticker::PriceError;
 
@@ -60,15 +65,62 @@ pub trait FeeTickerInfo: FeeTickerClone + Send + Sync + 'static {
 
     async fn get_token(&self, token: TokenLike) -> Result<Token, anyhow::Error>;
 
+    /// Persist the quoted fee along with the token price it was calculated from
+    async fn store_fee_quote(
+        &self,
+        token_id: TokenId,
+        fee: &Fee,
+        token_price_usd: &Ratio<BigUint>,
+    ) -> anyhow::Result<()>;
+
     /// Make boxed value to any. Helpful for downcasting in tests
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
+/// Interval of reloading the token prices from the database into the memory.
+const PRICE_CACHE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+/// Cached prices that were not refreshed for this time are loaded from the database again.
+const PRICE_CACHE_TTL: Duration = Duration::from_secs(2 * 60);
+
+/// In-memory copy of the token prices stored by the price updaters,
+/// so the fee calculation doesn't access the database for every price.
+#[derive(Debug, Clone, Default)]
+pub struct TokenPriceCache {
+    prices: Arc<RwLock<HashMap<TokenId, (TokenPrice, Instant)>>>,
+}
+
+impl TokenPriceCache {
+    /// Returns the cached price if it was refreshed within the TTL.
+    pub fn get(&self, token_id: TokenId) -> Option<TokenPrice> {
+        let prices = self.prices.read().unwrap();
+        prices
+            .get(&token_id)
+            .filter(|(_, cached_at)| cached_at.elapsed() < PRICE_CACHE_TTL)
+            .map(|(price, _)| price.clone())
+    }
+
+    pub fn insert(&self, token_id: TokenId, price: TokenPrice) {
+        let mut prices = self.prices.write().unwrap();
+        prices.insert(token_id, (price, Instant::now()));
+    }
+
+    /// Replaces all the cached prices with the given ones.
+    pub fn replace_all(&self, new_prices: HashMap<TokenId, TokenPrice>) {
+        let now = Instant::now();
+        let new_prices = new_prices
+            .into_iter()
+            .map(|(token_id, price)| (token_id, (price, now)))
+            .collect();
+        *self.prices.write().unwrap() = new_prices;
+    }
+}
+
 #[derive(Clone)]
 pub struct TickerInfo {
     db: ConnectionPool,
     token_db_cache: TokenDBCache,
+    price_cache: TokenPriceCache,
 }
 
 impl TickerInfo {
@@ -76,8 +128,37 @@ impl TickerInfo {
         Self {
             db,
             token_db_cache: Default::default(),
+            price_cache: Default::default(),
         }
     }
+
+    /// Periodically reloads the token prices into the memory, so the requests
+    /// for fees are served without waiting for the database.
+    #[must_use]
+    pub fn keep_price_cache_updated(&self) -> JoinHandle<()> {
+        let info = self.clone();
+        tokio::spawn(async move {
+            let mut timer = tokio::time::interval(PRICE_CACHE_REFRESH_INTERVAL);
+            loop {
+                timer.tick().await;
+                if let Err(err) = info.refresh_price_cache().await {
+                    vlog::warn!("Can't refresh the cached token prices: {}", err);
+                }
+            }
+        })
+    }
+
+    async fn refresh_price_cache(&self) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        let prices = storage
+            .tokens_schema()
+            .load_historical_ticker_prices()
+            .await?;
+        self.price_cache.replace_all(prices);
+        metrics::histogram!("ticker_info.refresh_price_cache", start.elapsed());
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -201,12 +282,18 @@ impl FeeTickerInfo for TickerInfo {
             });
         }
 
+        if let Some(cached_price) = self.price_cache.get(token.id) {
+            metrics::histogram!("ticker_info.get_last_token_price", start.elapsed(), "type" => "cached");
+            return Ok(cached_price);
+        }
+
         let historical_price = self
             .get_ticker_price(token.id)
             .await
             .map_err(|e| vlog::warn!("Failed to get historical ticker price: {}", e));
 
         if let Ok(Some(historical_price)) = historical_price {
+            self.price_cache.insert(token.id, historical_price.clone());
             return Ok(historical_price);
         }
 
@@ -258,6 +345,22 @@ impl FeeTickerInfo for TickerInfo {
         result
     }
 
+    async fn store_fee_quote(
+        &self,
+        token_id: TokenId,
+        fee: &Fee,
+        token_price_usd: &Ratio<BigUint>,
+    ) -> anyhow::Result<()> {
+        let start = Instant::now();
+        let mut storage = self.db.access_storage().await?;
+        storage
+            .misc_schema()
+            .store_fee_quote(token_id, fee, token_price_usd)
+            .await?;
+        metrics::histogram!("ticker_info.store_fee_quote", start.elapsed());
+        Ok(())
+    }
+
     #[cfg(test)]
     fn into_any(self: Box<Self>) -> Box<dyn Any> {
         self
@@ -1,3 +1,4 @@
+use num::{rational::Ratio, BigUint};
 #[cfg(test)]
 use std::{collections::HashMap, sync::Arc};
 #[cfg(test)]
@@ -25,6 +26,7 @@ pub(crate) struct TokenInDBCache {
 pub struct TokenInMemoryCache {
     tokens: Arc<Mutex<HashMap<TokenLike, Token>>>,
     market: Arc<Mutex<HashMap<TokenId, TokenMarketVolume>>>,
+    fee_tokens: Arc<Mutex<HashMap<TokenId, bool>>>,
 }
 
 impl TokenInDBCache {
@@ -130,6 +132,48 @@ impl TokenCacheWrapper {
             }
         }
     }
+
+    pub async fn is_fee_token(&self, token_id: TokenId) -> anyhow::Result<Option<bool>> {
+        match self {
+            Self::DB(cache) => {
+                cache
+                    .pool
+                    .access_storage()
+                    .await?
+                    .tokens_schema()
+                    .is_fee_token(token_id)
+                    .await
+            }
+            #[cfg(test)]
+            Self::Memory(cache) => Ok(cache.fee_tokens.lock().await.get(&token_id).copied()),
+        }
+    }
+
+    /// Returns `true` if the flag has changed.
+    pub async fn update_fee_token_flag(
+        &mut self,
+        token_id: TokenId,
+        is_fee_token: bool,
+        market_volume: &Ratio<BigUint>,
+    ) -> anyhow::Result<bool> {
+        match self {
+            Self::DB(cache) => {
+                cache
+                    .pool
+                    .access_storage()
+                    .await?
+                    .tokens_schema()
+                    .update_fee_token_flag(token_id, is_fee_token, market_volume)
+                    .await
+            }
+            #[cfg(test)]
+            Self::Memory(cache) => {
+                let previous = cache.fee_tokens.lock().await.insert(token_id, is_fee_token);
+                Ok(previous != Some(is_fee_token))
+            }
+        }
+    }
+
     pub async fn get_all_tokens(&self) -> anyhow::Result<Vec<Token>> {
         match self {
             Self::DB(cache) => {
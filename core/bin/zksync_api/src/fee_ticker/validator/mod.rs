@@ -2,6 +2,7 @@
 //! an entity which decides whether certain ERC20 token is suitable for paying fees.
 
 pub mod cache;
+pub(crate) mod policy;
 pub mod watcher;
 
 // Built-in uses
@@ -20,25 +21,34 @@ use zksync_types::{
 };
 
 // Local uses
-use crate::fee_ticker::validator::{cache::TokenCacheWrapper, watcher::TokenWatcher};
+use crate::fee_ticker::validator::{
+    cache::TokenCacheWrapper, policy::FeeTokenPolicy, watcher::TokenWatcher,
+};
 
-use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
+use zksync_utils::big_decimal_to_ratio;
 
 const CRITICAL_NUMBER_OF_ERRORS: u32 = 500;
 
 /// We don't want to send requests to the Internet for every request from users.
 /// Market updater periodically updates the values of the token market in the cache  
+/// and applies the fee token policy to the updated values.
 #[derive(Clone, Debug)]
 pub(crate) struct MarketUpdater<W> {
     tokens_cache: TokenCacheWrapper,
     watcher: W,
+    policy: FeeTokenPolicy,
 }
 
 impl<W: TokenWatcher> MarketUpdater<W> {
-    pub(crate) fn new(cache: impl Into<TokenCacheWrapper>, watcher: W) -> Self {
+    pub(crate) fn new(
+        cache: impl Into<TokenCacheWrapper>,
+        watcher: W,
+        policy: FeeTokenPolicy,
+    ) -> Self {
         Self {
             tokens_cache: cache.into(),
             watcher,
+            policy,
         }
     }
 
@@ -56,6 +66,25 @@ impl<W: TokenWatcher> MarketUpdater<W> {
         {
             vlog::error!("Error in updating token market volume {}", e);
         }
+
+        let is_fee_token = self.policy.evaluate(token, &market);
+        match self
+            .tokens_cache
+            .update_fee_token_flag(token.id, is_fee_token, &market.market_volume)
+            .await
+        {
+            Ok(true) => vlog::info!(
+                "Token {} is {} for paying fees",
+                token.symbol,
+                if is_fee_token {
+                    "accepted"
+                } else {
+                    "not accepted"
+                }
+            ),
+            Ok(false) => {}
+            Err(e) => vlog::error!("Error in updating fee token flag {}", e),
+        }
         Ok(market)
     }
 
@@ -95,11 +124,9 @@ impl<W: TokenWatcher> MarketUpdater<W> {
 /// Fee token validator decides whether certain ERC20 token is suitable for paying fees.
 #[derive(Debug, Clone)]
 pub struct FeeTokenValidator {
-    // Storage for unconditionally valid tokens, such as ETH
-    unconditionally_valid: HashSet<Address>,
     tokens_cache: TokenCacheWrapper,
     available_time: chrono::Duration,
-    liquidity_volume: BigDecimal,
+    policy: FeeTokenPolicy,
 }
 
 impl FeeTokenValidator {
@@ -110,10 +137,9 @@ impl FeeTokenValidator {
         unconditionally_valid: HashSet<Address>,
     ) -> Self {
         Self {
-            unconditionally_valid,
             tokens_cache: cache.into(),
             available_time,
-            liquidity_volume,
+            policy: FeeTokenPolicy::new(liquidity_volume, unconditionally_valid),
         }
     }
 
@@ -121,9 +147,13 @@ impl FeeTokenValidator {
     pub(crate) async fn token_allowed(&self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
         if let Some(token) = token {
-            if self.unconditionally_valid.contains(&token.address) {
+            if self.policy.is_unconditionally_valid(&token) {
                 return Ok(true);
             }
+            // Use the decision of the fee token policy if it was already made.
+            if let Some(is_fee_token) = self.tokens_cache.is_fee_token(token.id).await? {
+                return Ok(is_fee_token);
+            }
             self.check_token(token).await
         } else {
             // Unknown tokens aren't suitable for our needs, obviously.
@@ -145,7 +175,7 @@ impl FeeTokenValidator {
         if Utc::now() - volume.last_updated > self.available_time {
             vlog::warn!("Token market amount for {} is not relevant", &token.symbol)
         }
-        let allowed = ratio_to_big_decimal(&volume.market_volume, 2) >= self.liquidity_volume;
+        let allowed = self.policy.evaluate(&token, &volume);
         metrics::histogram!("ticker.validator.check_token", start.elapsed());
         Ok(allowed)
     }
@@ -242,7 +272,7 @@ mod tests {
             unconditionally_valid,
         );
 
-        let mut updater = MarketUpdater::new(cache, watcher);
+        let mut updater = MarketUpdater::new(cache, watcher, validator.policy.clone());
         updater.update_all_tokens(all_tokens).await.unwrap();
 
         let new_dai_token_market = validator
@@ -283,5 +313,23 @@ mod tests {
         assert!(dai_allowed);
         assert!(!phnx_allowed);
         assert!(eth_allowed);
+
+        // Decisions of the fee token policy are stored for the updated tokens.
+        assert_eq!(
+            validator
+                .tokens_cache
+                .is_fee_token(dai_token.id)
+                .await
+                .unwrap(),
+            Some(true)
+        );
+        assert_eq!(
+            validator
+                .tokens_cache
+                .is_fee_token(phnx_token.id)
+                .await
+                .unwrap(),
+            Some(false)
+        );
     }
 }
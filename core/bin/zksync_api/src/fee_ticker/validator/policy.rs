@@ -0,0 +1,40 @@
+//! Fee token policy decides whether the token should be accepted for paying fees
+//! given the state of its market.
+
+// Built-in uses
+use std::collections::HashSet;
+// External uses
+use bigdecimal::BigDecimal;
+// Workspace uses
+use zksync_types::{tokens::TokenMarketVolume, Address, Token};
+use zksync_utils::ratio_to_big_decimal;
+
+#[derive(Debug, Clone)]
+pub(crate) struct FeeTokenPolicy {
+    // Tokens that are accepted for fees regardless of their market, such as ETH.
+    unconditionally_valid: HashSet<Address>,
+    // Minimal liquidity (in USD) of the token market.
+    liquidity_volume: BigDecimal,
+}
+
+impl FeeTokenPolicy {
+    pub(crate) fn new(
+        liquidity_volume: BigDecimal,
+        unconditionally_valid: HashSet<Address>,
+    ) -> Self {
+        Self {
+            unconditionally_valid,
+            liquidity_volume,
+        }
+    }
+
+    pub(crate) fn is_unconditionally_valid(&self, token: &Token) -> bool {
+        self.unconditionally_valid.contains(&token.address)
+    }
+
+    /// Returns `true` if the token with the given market volume is acceptable for paying fees.
+    pub(crate) fn evaluate(&self, token: &Token, volume: &TokenMarketVolume) -> bool {
+        self.is_unconditionally_valid(token)
+            || ratio_to_big_decimal(&volume.market_volume, 2) >= self.liquidity_volume
+    }
+}
@@ -3,6 +3,9 @@
 pub mod api_server;
 pub mod eth_checker;
 pub mod fee_ticker;
+pub mod nft_metadata;
 pub mod signature_checker;
+pub mod token_compatibility;
+pub mod token_metadata;
 pub mod tx_error;
 pub mod utils;
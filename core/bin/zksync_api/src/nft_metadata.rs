@@ -0,0 +1,122 @@
+//! Module capable of fetching the NFT metadata through the IPFS gateway.
+//!
+//! Content hash of the NFT is the SHA-256 digest of the content stored in IPFS,
+//! so it's converted to the CIDv0 content identifier before the request.
+
+use std::time::{Duration, Instant};
+
+use zksync_types::H256;
+
+/// Timeout of the request to the IPFS gateway.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Multihash prefix of the SHA-256 digest: hash function code and digest length.
+const SHA256_MULTIHASH_PREFIX: [u8; 2] = [0x12, 0x20];
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+#[derive(Debug, Clone)]
+pub struct IpfsClient {
+    client: reqwest::Client,
+    gateway_url: String,
+}
+
+impl IpfsClient {
+    pub fn new(gateway_url: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            gateway_url,
+        }
+    }
+
+    /// Returns CIDv0 of the content with the given SHA-256 digest.
+    pub fn content_id(content_hash: &H256) -> String {
+        let mut multihash = SHA256_MULTIHASH_PREFIX.to_vec();
+        multihash.extend_from_slice(content_hash.as_bytes());
+        base58_encode(&multihash)
+    }
+
+    /// Fetches the JSON metadata stored in IPFS under the given content hash.
+    pub async fn fetch_json(&self, content_hash: &H256) -> anyhow::Result<serde_json::Value> {
+        let start = Instant::now();
+        let url = format!(
+            "{}/{}",
+            self.gateway_url.trim_end_matches('/'),
+            Self::content_id(content_hash)
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| anyhow::format_err!("IPFS gateway request failed: {}", err))?;
+        if !response.status().is_success() {
+            anyhow::bail!(
+                "IPFS gateway responded with status {} for {}",
+                response.status(),
+                url
+            );
+        }
+        let metadata = response
+            .json()
+            .await
+            .map_err(|err| anyhow::format_err!("NFT metadata is not a valid JSON: {}", err))?;
+
+        metrics::histogram!("api.ipfs.fetch_json", start.elapsed());
+        Ok(metadata)
+    }
+}
+
+fn base58_encode(bytes: &[u8]) -> String {
+    // Digits of the number in base 58, least significant first.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Leading zero bytes are encoded as the first alphabet character.
+    let leading_zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    std::iter::repeat(BASE58_ALPHABET[0])
+        .take(leading_zeros)
+        .chain(
+            digits
+                .iter()
+                .rev()
+                .map(|&digit| BASE58_ALPHABET[digit as usize]),
+        )
+        .map(char::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn content_id() {
+        let content_hash =
+            H256::from_str("46d44814b9c5af141c3aaab7c05dc5e844ead5f91f12858b021eba45768b4c0e")
+                .unwrap();
+        assert_eq!(
+            IpfsClient::content_id(&content_hash),
+            "QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o"
+        );
+    }
+
+    #[test]
+    fn base58_leading_zeros() {
+        assert_eq!(base58_encode(&[]), "");
+        assert_eq!(base58_encode(&[0, 0, 1]), "112");
+        assert_eq!(base58_encode(&[0xff]), "5Q");
+    }
+}
@@ -3,6 +3,11 @@
 //! Main routine of this module operates a multithreaded event loop,
 //! which is used to spawn concurrent tasks to efficiently check the
 //! transactions signatures.
+//!
+//! Signatures themselves are verified on the dedicated CPU pool, so the
+//! submission load doesn't block the async runtime. Only the checks that
+//! query the Ethereum node (EIP-1271 signatures and onchain `ChangePubKey`
+//! authorization) are performed by the tasks.
 
 // Built-in uses
 use std::collections::HashSet;
@@ -16,13 +21,14 @@ use futures::{
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::api::CommonApiConfig;
 use zksync_eth_client::EthereumGateway;
 use zksync_types::{
     tx::{error::TxAddError, EthBatchSignData, EthSignData, TxEthSignature},
     Address, Order, SignedZkSyncTx, Token, ZkSyncTx,
 };
 // Local uses
-use crate::eth_checker::EthereumChecker;
+use crate::{eth_checker::EthereumChecker, utils::cpu_pool::CpuPool};
 use zksync_types::tx::TransactionError;
 
 /// `TxVariant` is used to form a verify request. It is possible to wrap
@@ -33,6 +39,7 @@ pub enum TxVariant {
     Batch(Vec<SignedZkSyncTx>, Option<EthBatchSignData>),
     Order(Box<Order>),
     Toggle2FA,
+    CancelTx,
 }
 
 /// Wrapper on a `TxVariant` which guarantees that (a batch of)
@@ -50,10 +57,16 @@ impl VerifiedTx {
     pub async fn verify(
         request_data: RequestData,
         eth_checker: &EthereumChecker,
+        pool: &CpuPool,
     ) -> Result<Self, TxAddError> {
-        verify_eth_signature(&request_data, eth_checker).await?;
+        let batch_hash_message = verify_eth_signature(&request_data, eth_checker, pool).await?;
         let mut tx_variant = request_data.get_tx_variant();
-        verify_tx_correctness(&mut tx_variant)?;
+        if let Some(message) = batch_hash_message {
+            attach_batch_hash_signature(&mut tx_variant, message);
+        }
+        let tx_variant = pool
+            .run(move || verify_tx_correctness(&mut tx_variant).map(|()| tx_variant))
+            .await?;
 
         Ok(Self(tx_variant))
     }
@@ -71,6 +84,7 @@ impl VerifiedTx {
             TxVariant::Batch(_, _) => panic!("called `unwrap_tx` on a `Batch` value"),
             TxVariant::Order(_) => panic!("called `unwrap_tx` on an `Order` value"),
             TxVariant::Toggle2FA => panic!("called `unwrap_tx` on an `Toggle2FA` value"),
+            TxVariant::CancelTx => panic!("called `unwrap_tx` on a `CancelTx` value"),
         }
     }
 
@@ -81,15 +95,34 @@ impl VerifiedTx {
             TxVariant::Tx(_) => panic!("called `unwrap_batch` on a `Tx` value"),
             TxVariant::Order(_) => panic!("called `unwrap_batch` on an `Order` value"),
             TxVariant::Toggle2FA => panic!("called `unwrap_batch` on an `Toggle2FA` value"),
+            TxVariant::CancelTx => panic!("called `unwrap_batch` on a `CancelTx` value"),
+        }
+    }
+}
+
+/// Sets the single batch signature over the batch hash as the Ethereum signature of
+/// every batch transaction that wasn't signed on its own, so it's stored along with them.
+fn attach_batch_hash_signature(tx_variant: &mut TxVariant, message: Vec<u8>) {
+    if let TxVariant::Batch(txs, Some(batch_sign_data)) = tx_variant {
+        let signature = batch_sign_data.signatures[0].clone();
+        for tx in txs.iter_mut().filter(|tx| tx.eth_sign_data.is_none()) {
+            tx.eth_sign_data = Some(EthSignData {
+                signature: signature.clone(),
+                message: message.clone(),
+            });
         }
+        batch_sign_data.message = message;
     }
 }
 
 /// Verifies the Ethereum signature of the (batch of) transaction(s).
+/// Returns the message covering the batch hash if the batch was signed with it.
 async fn verify_eth_signature(
     request_data: &RequestData,
     eth_checker: &EthereumChecker,
-) -> Result<(), TxAddError> {
+    pool: &CpuPool,
+) -> Result<Option<Vec<u8>>, TxAddError> {
+    let mut batch_hash_message = None;
     match request_data {
         RequestData::Tx(request) => {
             verify_eth_signature_single_tx(
@@ -97,6 +130,7 @@ async fn verify_eth_signature(
                 request.sender,
                 request.token.clone(),
                 eth_checker,
+                pool,
             )
             .await?;
         }
@@ -109,14 +143,21 @@ async fn verify_eth_signature(
                 return Err(TxAddError::Other);
             }
             if let Some(batch_sign_data) = &request.batch_sign_data {
-                verify_eth_signature_txs_batch(txs, accounts, batch_sign_data, eth_checker).await?;
+                batch_hash_message = verify_eth_signature_txs_batch(
+                    txs,
+                    accounts,
+                    batch_sign_data,
+                    eth_checker,
+                    pool,
+                )
+                .await?;
             }
             // In case there're signatures provided for some of transactions
             // we still verify them.
             for ((tx, &account), token) in
                 txs.iter().zip(accounts.iter()).zip(tokens.iter().cloned())
             {
-                verify_eth_signature_single_tx(tx, account, token, eth_checker).await?;
+                verify_eth_signature_single_tx(tx, account, token, eth_checker, pool).await?;
             }
         }
         RequestData::Order(request) => {
@@ -125,6 +166,7 @@ async fn verify_eth_signature(
                 &request.sign_data.message,
                 request.sender,
                 eth_checker,
+                pool,
             )
             .await;
             if !signature_correct {
@@ -137,6 +179,20 @@ async fn verify_eth_signature(
                 &request.sign_data.message,
                 request.sender,
                 eth_checker,
+                pool,
+            )
+            .await;
+            if !signature_correct {
+                return Err(TxAddError::IncorrectEthSignature);
+            }
+        }
+        RequestData::CancelTx(request) => {
+            let signature_correct = verify_ethereum_signature(
+                &request.sign_data.signature,
+                &request.sign_data.message,
+                request.sender,
+                eth_checker,
+                pool,
             )
             .await;
             if !signature_correct {
@@ -145,7 +201,7 @@ async fn verify_eth_signature(
         }
     }
 
-    Ok(())
+    Ok(batch_hash_message)
 }
 
 /// Given a single Ethereum signature and a message, checks that it
@@ -155,10 +211,14 @@ async fn verify_ethereum_signature(
     message: &[u8],
     sender_address: Address,
     eth_checker: &EthereumChecker,
+    pool: &CpuPool,
 ) -> bool {
     let signer_account = match eth_signature {
         TxEthSignature::EthereumSignature(packed_signature) => {
-            packed_signature.signature_recover_signer(message)
+            let packed_signature = packed_signature.clone();
+            let message = message.to_vec();
+            pool.run(move || packed_signature.signature_recover_signer(&message))
+                .await
         }
         TxEthSignature::EIP1271Signature(signature) => {
             return eth_checker
@@ -178,6 +238,7 @@ async fn verify_eth_signature_single_tx(
     sender_address: Address,
     token: Token,
     eth_checker: &EthereumChecker,
+    pool: &CpuPool,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
@@ -202,9 +263,14 @@ async fn verify_eth_signature_single_tx(
     // Check the signature.
     if let Some(sign_data) = &tx.eth_sign_data {
         let signature = &sign_data.signature;
-        let mut signature_correct =
-            verify_ethereum_signature(signature, &sign_data.message, sender_address, eth_checker)
-                .await;
+        let mut signature_correct = verify_ethereum_signature(
+            signature,
+            &sign_data.message,
+            sender_address,
+            eth_checker,
+            pool,
+        )
+        .await;
         if !signature_correct {
             let old_message = tx.get_old_ethereum_sign_message(token);
             if let Some(message) = old_message {
@@ -213,6 +279,7 @@ async fn verify_eth_signature_single_tx(
                     message.as_bytes(),
                     sender_address,
                     eth_checker,
+                    pool,
                 )
                 .await;
             }
@@ -229,11 +296,45 @@ async fn verify_eth_signature_single_tx(
     Ok(())
 }
 
+/// Verifies the batch signatures. A single signature may also be made over the message covering
+/// the hashes of the batch transactions, such a message is returned if the signature is valid
+/// for every sender of the batch.
 async fn verify_eth_signature_txs_batch(
     txs: &[SignedZkSyncTx],
     senders: &[Address],
     batch_sign_data: &EthBatchSignData,
     eth_checker: &EthereumChecker,
+    pool: &CpuPool,
+) -> Result<Option<Vec<u8>>, TxAddError> {
+    let result =
+        verify_eth_signature_batch_message(txs, senders, batch_sign_data, eth_checker, pool).await;
+    let signature = match (&result, batch_sign_data.signatures.as_slice()) {
+        (Err(TxAddError::IncorrectEthSignature), [signature]) => signature,
+        _ => return result.map(|()| None),
+    };
+
+    let start = Instant::now();
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.hash()).collect();
+    let message = EthBatchSignData::get_batch_hash_sign_message(&tx_hashes);
+    let senders: HashSet<_> = senders.iter().copied().collect();
+    for sender in senders {
+        if !verify_ethereum_signature(signature, &message, sender, eth_checker, pool).await {
+            return Err(TxAddError::IncorrectEthSignature);
+        }
+    }
+    metrics::histogram!(
+        "signature_checker.verify_eth_signature_batch_hash",
+        start.elapsed()
+    );
+    Ok(Some(message))
+}
+
+async fn verify_eth_signature_batch_message(
+    txs: &[SignedZkSyncTx],
+    senders: &[Address],
+    batch_sign_data: &EthBatchSignData,
+    eth_checker: &EthereumChecker,
+    pool: &CpuPool,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Cache for verified senders.
@@ -262,6 +363,7 @@ async fn verify_eth_signature_txs_batch(
                 &batch_sign_data.message,
                 *sender,
                 eth_checker,
+                pool,
             )
             .await;
             if !signature_correct {
@@ -271,6 +373,7 @@ async fn verify_eth_signature_txs_batch(
                         old_message.as_slice(),
                         *sender,
                         eth_checker,
+                        pool,
                     )
                     .await;
                 }
@@ -308,7 +411,7 @@ fn verify_tx_correctness(tx: &mut TxVariant) -> Result<(), TxAddError> {
         TxVariant::Order(order) => order
             .check_correctness()
             .map_err(|err| TxAddError::IncorrectTx(TransactionError::OrderError(err)))?,
-        TxVariant::Toggle2FA => {} // There is no data to check correctness of
+        TxVariant::Toggle2FA | TxVariant::CancelTx => {} // There is no data to check correctness of
     }
     Ok(())
 }
@@ -346,6 +449,12 @@ pub struct Toggle2FARequest {
     pub sender: Address,
 }
 
+#[derive(Debug)]
+pub struct CancelTxRequest {
+    pub sign_data: EthSignData,
+    pub sender: Address,
+}
+
 /// Request for the signature check.
 #[derive(Debug)]
 pub struct VerifySignatureRequest {
@@ -360,6 +469,7 @@ pub enum RequestData {
     Batch(BatchRequest),
     Order(OrderRequest),
     Toggle2FA(Toggle2FARequest),
+    CancelTx(CancelTxRequest),
 }
 
 impl RequestData {
@@ -371,6 +481,18 @@ impl RequestData {
             }
             RequestData::Order(request) => TxVariant::Order(request.order.clone()),
             RequestData::Toggle2FA(_) => TxVariant::Toggle2FA,
+            RequestData::CancelTx(_) => TxVariant::CancelTx,
+        }
+    }
+
+    /// Name of the request kind used in the metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RequestData::Tx(_) => "tx",
+            RequestData::Batch(_) => "batch",
+            RequestData::Order(_) => "order",
+            RequestData::Toggle2FA(_) => "toggle_2fa",
+            RequestData::CancelTx(_) => "cancel_tx",
         }
     }
 }
@@ -380,23 +502,31 @@ impl RequestData {
 pub fn start_sign_checker(
     client: EthereumGateway,
     input: mpsc::Receiver<VerifySignatureRequest>,
+    config: &CommonApiConfig,
 ) -> JoinHandle<()> {
     let eth_checker = EthereumChecker::new(client);
+    let pool = CpuPool::new(
+        "signature_checker",
+        config.signature_verifier_threads,
+        config.signature_verifier_queue_size,
+    );
 
     /// Basically it receives the requests through the channel and verifies signatures,
     /// notifying the request sender about the check result.
     async fn checker_routine(
         mut input: mpsc::Receiver<VerifySignatureRequest>,
         eth_checker: EthereumChecker,
+        pool: CpuPool,
     ) {
         while let Some(VerifySignatureRequest { data, response }) = input.next().await {
             let eth_checker = eth_checker.clone();
+            let pool = pool.clone();
             tokio::spawn(async move {
-                let resp = VerifiedTx::verify(data, &eth_checker).await;
+                let resp = VerifiedTx::verify(data, &eth_checker, &pool).await;
 
                 response.send(resp).unwrap_or_default();
             });
         }
     }
-    tokio::spawn(checker_routine(input, eth_checker))
+    tokio::spawn(checker_routine(input, eth_checker, pool))
 }
@@ -0,0 +1,301 @@
+//! Sanity checks of the ERC-20 contracts run before the token is listed.
+//!
+//! zkSync contract supports the tokens that return nothing from the transfer methods,
+//! but the tokens changing the balances on their own (fee-on-transfer and rebasing ones)
+//! break the accounting of the deposited funds and make the withdrawals fail.
+//!
+//! Nothing is sent to the network: the transfers are simulated with `eth_call`, and
+//! the balance-changing mechanics are detected by the well-known function selectors
+//! of the deployed bytecode. Implementations behind proxies are not scanned.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use web3::types::{Address, U256};
+use zksync_api_types::v02::token::{
+    CompatibilityCheckStatus, TokenCompatibilityCheck, TokenCompatibilityReport,
+};
+
+use crate::eth_checker::{Erc20CallOutcome, EthereumChecker};
+
+/// Functions of the tokens that charge a fee on every transfer.
+const FEE_ON_TRANSFER_SIGNATURES: &[&str] = &[
+    "_taxFee()",
+    "taxFee()",
+    "_liquidityFee()",
+    "setTaxFeePercent(uint256)",
+    "setFeePercent(uint256)",
+    "transferFee()",
+    "reflectionFromToken(uint256,bool)",
+];
+/// Functions of the tokens that change the balances of the holders without transfers.
+const REBASING_SIGNATURES: &[&str] = &[
+    "rebase()",
+    "rebase(uint256,int256)",
+    "sharesOf(address)",
+    "getSharesByPooledEth(uint256)",
+    "scaledBalanceOf(address)",
+    "rebasingCreditsPerToken()",
+];
+
+/// Reports are reused during this interval, so the public endpoint doesn't query Ethereum on every request.
+const REPORT_TTL: Duration = Duration::from_secs(10 * 60);
+/// Maximum number of the cached reports.
+const MAX_CACHED_REPORTS: usize = 10_000;
+
+const PUSH1: u8 = 0x60;
+const PUSH4: u8 = 0x63;
+const PUSH32: u8 = 0x7f;
+
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = tiny_keccak::keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Collects the 4-byte constants pushed by the bytecode, function dispatcher compares
+/// the selector of the call with them. Data of the other push instructions is skipped.
+fn pushed_selectors(code: &[u8]) -> HashSet<[u8; 4]> {
+    let mut selectors = HashSet::new();
+    let mut pos = 0;
+    while pos < code.len() {
+        let opcode = code[pos];
+        if opcode == PUSH4 && pos + 5 <= code.len() {
+            selectors.insert([code[pos + 1], code[pos + 2], code[pos + 3], code[pos + 4]]);
+        }
+        pos += 1;
+        if (PUSH1..=PUSH32).contains(&opcode) {
+            pos += (opcode - PUSH1 + 1) as usize;
+        }
+    }
+    selectors
+}
+
+/// Returns the signatures of the functions found in the bytecode.
+fn find_functions(code: &[u8], signatures: &[&'static str]) -> Vec<&'static str> {
+    let selectors = pushed_selectors(code);
+    signatures
+        .iter()
+        .copied()
+        .filter(|signature| selectors.contains(&selector(signature)))
+        .collect()
+}
+
+fn check(
+    name: &str,
+    status: CompatibilityCheckStatus,
+    details: Option<String>,
+) -> TokenCompatibilityCheck {
+    TokenCompatibilityCheck {
+        name: name.to_string(),
+        status,
+        details,
+    }
+}
+
+fn functions_check(
+    name: &str,
+    code: &[u8],
+    signatures: &[&'static str],
+) -> TokenCompatibilityCheck {
+    let found = find_functions(code, signatures);
+    if found.is_empty() {
+        check(name, CompatibilityCheckStatus::Passed, None)
+    } else {
+        check(
+            name,
+            CompatibilityCheckStatus::Failed,
+            Some(format!("Contract has functions {}", found.join(", "))),
+        )
+    }
+}
+
+fn transfer_check(
+    name: &str,
+    outcome: anyhow::Result<Erc20CallOutcome>,
+) -> TokenCompatibilityCheck {
+    match outcome {
+        Ok(Erc20CallOutcome::ReturnedTrue) => check(name, CompatibilityCheckStatus::Passed, None),
+        Ok(Erc20CallOutcome::NoReturnValue) => check(
+            name,
+            CompatibilityCheckStatus::Warning,
+            Some("Method doesn't return a value".to_string()),
+        ),
+        Ok(Erc20CallOutcome::ReturnedFalse) => check(
+            name,
+            CompatibilityCheckStatus::Failed,
+            Some("Zero amount transfer returned `false`".to_string()),
+        ),
+        Ok(Erc20CallOutcome::Failed(err)) => check(
+            name,
+            CompatibilityCheckStatus::Inconclusive,
+            Some(format!("Simulated call failed: {}", err)),
+        ),
+        Err(err) => check(
+            name,
+            CompatibilityCheckStatus::Failed,
+            Some(err.to_string()),
+        ),
+    }
+}
+
+#[derive(Clone)]
+pub struct TokenCompatibilityChecker {
+    eth_checker: EthereumChecker,
+    /// Transfers are simulated on behalf of the zkSync contract, since it holds the deposited tokens.
+    contract_addr: Address,
+    reports: Arc<Mutex<HashMap<Address, (Instant, TokenCompatibilityReport)>>>,
+}
+
+impl TokenCompatibilityChecker {
+    pub fn new(eth_checker: EthereumChecker, contract_addr: Address) -> Self {
+        Self {
+            eth_checker,
+            contract_addr,
+            reports: Default::default(),
+        }
+    }
+
+    /// Returns the report of the token, the checks are run if there is no recent report.
+    pub async fn check(&self, address: Address) -> TokenCompatibilityReport {
+        if let Some((checked_at, report)) = self.reports.lock().unwrap().get(&address) {
+            if checked_at.elapsed() < REPORT_TTL {
+                return report.clone();
+            }
+        }
+
+        let report = self.run_checks(address).await;
+        let mut reports = self.reports.lock().unwrap();
+        if reports.len() >= MAX_CACHED_REPORTS {
+            reports.retain(|_, (checked_at, _)| checked_at.elapsed() < REPORT_TTL);
+        }
+        // Reports aren't cached while the cache is full of the recent ones.
+        if reports.len() < MAX_CACHED_REPORTS {
+            reports.insert(address, (Instant::now(), report.clone()));
+        }
+        report
+    }
+
+    /// Runs all the checks against the token contract.
+    async fn run_checks(&self, address: Address) -> TokenCompatibilityReport {
+        let code = match self.eth_checker.contract_code(address).await {
+            Ok(code) if code.is_empty() => {
+                let checks = vec![check(
+                    "contract",
+                    CompatibilityCheckStatus::Failed,
+                    Some("No contract is deployed at the address".to_string()),
+                )];
+                return TokenCompatibilityReport::new(address, checks);
+            }
+            Ok(code) => code,
+            Err(err) => {
+                let checks = vec![check(
+                    "contract",
+                    CompatibilityCheckStatus::Inconclusive,
+                    Some(format!("Can't load the contract code: {}", err)),
+                )];
+                return TokenCompatibilityReport::new(address, checks);
+            }
+        };
+
+        let mut checks = vec![check("contract", CompatibilityCheckStatus::Passed, None)];
+        checks.push(match self.eth_checker.erc20_metadata(address).await {
+            Ok((symbol, decimals)) => check(
+                "metadata",
+                CompatibilityCheckStatus::Passed,
+                Some(format!("Symbol {}, decimals {}", symbol, decimals)),
+            ),
+            Err(err) => check(
+                "metadata",
+                CompatibilityCheckStatus::Failed,
+                Some(err.to_string()),
+            ),
+        });
+
+        // Zero amount transfers to itself don't depend on the balance of the contract.
+        let outcome = self
+            .eth_checker
+            .simulate_erc20_call(
+                address,
+                "transfer",
+                (self.contract_addr, U256::zero()),
+                self.contract_addr,
+            )
+            .await;
+        checks.push(transfer_check("transferReturnValue", outcome));
+        let outcome = self
+            .eth_checker
+            .simulate_erc20_call(
+                address,
+                "transferFrom",
+                (self.contract_addr, self.contract_addr, U256::zero()),
+                self.contract_addr,
+            )
+            .await;
+        checks.push(transfer_check("transferFromReturnValue", outcome));
+
+        checks.push(functions_check(
+            "feeOnTransfer",
+            &code,
+            FEE_ON_TRANSFER_SIGNATURES,
+        ));
+        checks.push(functions_check("rebasing", &code, REBASING_SIGNATURES));
+
+        let report = TokenCompatibilityReport::new(address, checks);
+        if !report.compatible {
+            vlog::info!("Token {:?} didn't pass the compatibility checks", address);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectors() {
+        assert_eq!(
+            selector("transfer(address,uint256)"),
+            [0xa9, 0x05, 0x9c, 0xbb]
+        );
+    }
+
+    #[test]
+    fn find_functions_in_bytecode() {
+        let rebase = selector("rebase()");
+        let shares_of = selector("sharesOf(address)");
+
+        // PUSH4 <rebase> PUSH1 0x00 PUSH32 <sharesOf padded>
+        let mut code = vec![PUSH4];
+        code.extend_from_slice(&rebase);
+        code.extend_from_slice(&[PUSH1, 0x00, PUSH32, PUSH4]);
+        code.extend_from_slice(&shares_of);
+        code.extend_from_slice(&[0u8; 27]);
+
+        // Selector inside the data of another push is not a function of the contract.
+        assert_eq!(find_functions(&code, REBASING_SIGNATURES), vec!["rebase()"]);
+        assert!(find_functions(&code, FEE_ON_TRANSFER_SIGNATURES).is_empty());
+        // Truncated push at the end of the code.
+        assert!(find_functions(&[PUSH4, rebase[0]], REBASING_SIGNATURES).is_empty());
+    }
+
+    #[test]
+    fn report_compatibility() {
+        let passed = check("contract", CompatibilityCheckStatus::Passed, None);
+        let warning = check(
+            "transferReturnValue",
+            CompatibilityCheckStatus::Warning,
+            None,
+        );
+        let failed = check("rebasing", CompatibilityCheckStatus::Failed, None);
+
+        let report =
+            TokenCompatibilityReport::new(Address::zero(), vec![passed.clone(), warning.clone()]);
+        assert!(report.compatible);
+        let report = TokenCompatibilityReport::new(Address::zero(), vec![passed, warning, failed]);
+        assert!(!report.compatible);
+    }
+}
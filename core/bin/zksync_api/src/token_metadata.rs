@@ -0,0 +1,226 @@
+//! Periodic reconciliation of the stored token metadata with the ERC-20 contracts.
+//!
+//! Tokens that were stored with the default `ERC20-{id}` symbol get the symbol from the contract.
+//! Symbols set by the operator are never overwritten, their mismatches as well as the other changes
+//! that can't be applied safely (mismatching decimals or a symbol already used by another token)
+//! are flagged for the operator review instead.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tokio::task::JoinHandle;
+use zksync_eth_client::EthereumGateway;
+use zksync_storage::ConnectionPool;
+use zksync_types::{Token, TokenId, TokenKind};
+
+use crate::eth_checker::EthereumChecker;
+
+/// Result of the comparison of the stored token metadata with the contract.
+#[derive(Debug, Clone, PartialEq)]
+enum MetadataUpdate {
+    UpToDate,
+    /// The token should be stored with the new metadata.
+    Update(Token),
+    /// The mismatch requires the operator review.
+    Conflict,
+}
+
+/// Compares the stored token with the metadata of the contract.
+///
+/// Decimals are never updated automatically, since they change the meaning
+/// of the amounts shown to the users and used in the fee calculation.
+/// Only the default symbols are replaced, the other ones are curated.
+fn reconcile(
+    token: &Token,
+    contract_symbol: &str,
+    contract_decimals: u8,
+    symbol_owners: &HashMap<String, TokenId>,
+) -> MetadataUpdate {
+    if token.decimals != contract_decimals {
+        return MetadataUpdate::Conflict;
+    }
+    if token.symbol == contract_symbol {
+        return MetadataUpdate::UpToDate;
+    }
+    if token.symbol != format!("ERC20-{}", token.id) {
+        return MetadataUpdate::Conflict;
+    }
+
+    match symbol_owners.get(&contract_symbol.to_lowercase()) {
+        Some(owner) if *owner != token.id => MetadataUpdate::Conflict,
+        _ => {
+            let mut token = token.clone();
+            token.symbol = contract_symbol.to_string();
+            MetadataUpdate::Update(token)
+        }
+    }
+}
+
+struct TokenMetadataUpdater {
+    pool: ConnectionPool,
+    eth_checker: EthereumChecker,
+}
+
+impl TokenMetadataUpdater {
+    async fn update(&self) -> anyhow::Result<()> {
+        let start = Instant::now();
+        // Connection isn't held while the contracts are queried.
+        let tokens = {
+            let mut storage = self.pool.access_storage().await?;
+            storage.tokens_schema().load_tokens().await?
+        };
+        let mut symbol_owners: HashMap<String, TokenId> = tokens
+            .values()
+            .map(|token| (token.symbol.to_lowercase(), token.id))
+            .collect();
+
+        let mut updated = 0;
+        let mut conflicts = 0;
+        // Ether has no contract.
+        for token in tokens
+            .values()
+            .filter(|token| token.kind == TokenKind::ERC20 && *token.id != 0)
+        {
+            let (symbol, decimals) = match self.eth_checker.erc20_metadata(token.address).await {
+                Ok(metadata) => metadata,
+                Err(err) => {
+                    vlog::debug!("Can't load metadata of the token {}: {}", token.id, err);
+                    continue;
+                }
+            };
+
+            let mut storage = self.pool.access_storage().await?;
+            match reconcile(token, &symbol, decimals, &symbol_owners) {
+                MetadataUpdate::UpToDate => {
+                    storage
+                        .tokens_schema()
+                        .remove_token_metadata_conflict(token.id)
+                        .await?;
+                }
+                MetadataUpdate::Update(new_token) => {
+                    vlog::info!(
+                        "Symbol of the token {} is changed from {} to {}",
+                        token.id,
+                        token.symbol,
+                        new_token.symbol
+                    );
+                    let mut transaction = storage.start_transaction().await?;
+                    transaction
+                        .tokens_schema()
+                        .store_or_update_token(new_token.clone())
+                        .await?;
+                    transaction
+                        .tokens_schema()
+                        .remove_token_metadata_conflict(token.id)
+                        .await?;
+                    transaction.commit().await?;
+
+                    symbol_owners.remove(&token.symbol.to_lowercase());
+                    symbol_owners.insert(new_token.symbol.to_lowercase(), token.id);
+                    updated += 1;
+                }
+                MetadataUpdate::Conflict => {
+                    storage
+                        .tokens_schema()
+                        .flag_token_metadata_conflict(token, &symbol, decimals)
+                        .await?;
+                    conflicts += 1;
+                }
+            }
+        }
+
+        metrics::histogram!("api.token_metadata.update", start.elapsed());
+        metrics::gauge!("api.token_metadata.conflicts", conflicts as f64);
+        if updated > 0 || conflicts > 0 {
+            vlog::info!(
+                "Token metadata is reconciled: {} tokens updated, {} conflicts",
+                updated,
+                conflicts
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Runs the task that periodically reconciles the stored token metadata with the ERC-20 contracts.
+#[must_use]
+pub fn run_token_metadata_updater(
+    pool: ConnectionPool,
+    eth_gateway: EthereumGateway,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let updater = TokenMetadataUpdater {
+        pool,
+        eth_checker: EthereumChecker::new(eth_gateway),
+    };
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(interval);
+        loop {
+            timer.tick().await;
+            if let Err(err) = updater.update().await {
+                vlog::warn!("Can't reconcile the token metadata: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::Address;
+
+    fn token(id: u32, symbol: &str, decimals: u8) -> Token {
+        Token::new(
+            TokenId(id),
+            Address::from_low_u64_be(id as u64),
+            symbol,
+            decimals,
+            TokenKind::ERC20,
+        )
+    }
+
+    #[test]
+    fn reconcile_metadata() {
+        let symbol_owners: HashMap<String, TokenId> = vec![
+            ("erc20-1".to_string(), TokenId(1)),
+            ("dai".to_string(), TokenId(2)),
+            ("usdc".to_string(), TokenId(3)),
+        ]
+        .into_iter()
+        .collect();
+        let listed = token(1, "ERC20-1", 18);
+        let curated = token(3, "USDC", 6);
+
+        assert_eq!(
+            reconcile(&listed, "ERC20-1", 18, &symbol_owners),
+            MetadataUpdate::UpToDate
+        );
+        assert_eq!(
+            reconcile(&listed, "USDT", 18, &symbol_owners),
+            MetadataUpdate::Update(token(1, "USDT", 18))
+        );
+        // The case of the token's own symbol can be fixed.
+        assert_eq!(
+            reconcile(&listed, "erc20-1", 18, &symbol_owners),
+            MetadataUpdate::Update(token(1, "erc20-1", 18))
+        );
+        // Symbol of another token and the decimals mismatch require the review.
+        assert_eq!(
+            reconcile(&listed, "DAI", 18, &symbol_owners),
+            MetadataUpdate::Conflict
+        );
+        assert_eq!(
+            reconcile(&listed, "USDT", 6, &symbol_owners),
+            MetadataUpdate::Conflict
+        );
+        // Symbols set by the operator are not overwritten.
+        assert_eq!(
+            reconcile(&curated, "USDC", 6, &symbol_owners),
+            MetadataUpdate::UpToDate
+        );
+        assert_eq!(
+            reconcile(&curated, "USDC.e", 6, &symbol_owners),
+            MetadataUpdate::Conflict
+        );
+    }
+}
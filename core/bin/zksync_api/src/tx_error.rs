@@ -15,3 +15,21 @@ pub enum Toggle2FAError {
     #[error("Request to enable 2FA should not have PubKeyHash field set")]
     UnusedPubKeyHash,
 }
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
+pub enum ReplaceTxError {
+    #[error("Database unavailable")]
+    DbError,
+
+    #[error("Transaction is not queued or was submitted as a part of a batch")]
+    NotQueued,
+
+    #[error("Replacement transaction must have the same account and nonce")]
+    AccountOrNonceMismatch,
+
+    #[error("Replacement transaction is identical to the queued one")]
+    SameTx,
+
+    #[error("Can not cancel transactions of a CREATE2 account")]
+    CREATE2,
+}
@@ -0,0 +1,201 @@
+//! Bounded pool of threads dedicated to the CPU-heavy computations, such as
+//! the signature verification, so they don't block the threads of the async runtime.
+//!
+//! Jobs wait in the queue of the limited size, submitters wait for the free place
+//! in the queue. Saturation of the pool is reported via metrics labeled by the pool name.
+
+// Built-in uses
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::thread;
+use std::time::Instant;
+// External uses
+use tokio::sync::{mpsc, oneshot};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+#[derive(Debug)]
+struct PoolStats {
+    name: &'static str,
+    threads: usize,
+    queued: AtomicUsize,
+    busy: AtomicUsize,
+}
+
+impl PoolStats {
+    fn report(&self) {
+        let busy = self.busy.load(Ordering::SeqCst);
+        metrics::gauge!("cpu_pool.queued_jobs", self.queued.load(Ordering::SeqCst) as f64, "pool" => self.name);
+        metrics::gauge!("cpu_pool.busy_threads", busy as f64, "pool" => self.name);
+        metrics::gauge!("cpu_pool.saturation", busy as f64 / self.threads as f64, "pool" => self.name);
+    }
+}
+
+/// Accounts the job in the queue until it's started or dropped unstarted, e.g. if the submitter
+/// was cancelled while waiting for the free place in the queue.
+#[derive(Debug)]
+struct QueuedJob(Arc<PoolStats>);
+
+impl QueuedJob {
+    fn new(stats: Arc<PoolStats>) -> Self {
+        stats.queued.fetch_add(1, Ordering::SeqCst);
+        stats.report();
+        Self(stats)
+    }
+}
+
+impl Drop for QueuedJob {
+    fn drop(&mut self) {
+        self.0.queued.fetch_sub(1, Ordering::SeqCst);
+        self.0.report();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CpuPool {
+    sender: mpsc::Sender<Job>,
+    stats: Arc<PoolStats>,
+}
+
+impl CpuPool {
+    /// Spawns the pool threads. They are stopped once all the copies of the pool are dropped.
+    pub fn new(name: &'static str, threads: usize, queue_size: usize) -> Self {
+        assert!(threads > 0, "CPU pool should have at least one thread");
+        assert!(queue_size > 0, "CPU pool queue should fit at least one job");
+        let (sender, receiver) = mpsc::channel::<Job>(queue_size);
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        for i in 0..threads {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("{}_{}", name, i))
+                .spawn(move || loop {
+                    // The lock is released as soon as the job is received.
+                    let job = receiver.lock().unwrap().blocking_recv();
+                    match job {
+                        Some(job) => job(),
+                        None => break,
+                    }
+                })
+                .expect("failed to start CPU pool thread");
+        }
+
+        Self {
+            sender,
+            stats: Arc::new(PoolStats {
+                name,
+                threads,
+                queued: AtomicUsize::new(0),
+                busy: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Runs the function on the pool thread and returns its result.
+    /// Panic of the function is resumed in the calling task.
+    pub async fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (result_sender, result_receiver) = oneshot::channel();
+        let stats = self.stats.clone();
+        let queued_at = Instant::now();
+        let queued_job = QueuedJob::new(self.stats.clone());
+        let job = Box::new(move || {
+            drop(queued_job);
+            stats.busy.fetch_add(1, Ordering::SeqCst);
+            stats.report();
+            metrics::histogram!("cpu_pool.queue_wait", queued_at.elapsed(), "pool" => stats.name);
+
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+
+            stats.busy.fetch_sub(1, Ordering::SeqCst);
+            stats.report();
+            result_sender.send(result).unwrap_or_default();
+        });
+
+        if self.sender.send(job).await.is_err() {
+            panic!("CPU pool {} has stopped", self.stats.name);
+        }
+
+        match result_receiver.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(panic)) => panic::resume_unwind(panic),
+            Err(_) => panic!("CPU pool {} dropped the job", self.stats.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn runs_jobs_on_pool_threads() {
+        let pool = CpuPool::new("test_pool", 2, 1);
+
+        let jobs = (0..10u64).map(|i| {
+            pool.run(move || {
+                let name = thread::current().name().map(ToOwned::to_owned);
+                (i * i, name)
+            })
+        });
+        let results = futures::future::join_all(jobs).await;
+
+        for (i, (square, thread_name)) in results.into_iter().enumerate() {
+            assert_eq!(square, (i * i) as u64);
+            assert!(thread_name.unwrap().starts_with("test_pool_"));
+        }
+        assert_eq!(pool.stats.queued.load(Ordering::SeqCst), 0);
+        assert_eq!(pool.stats.busy.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn resumes_panics() {
+        let pool = CpuPool::new("test_pool", 1, 1);
+
+        let result = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.run(|| panic!("job panicked")).await }
+        })
+        .await;
+        assert!(result.unwrap_err().is_panic());
+        // The thread survives the panic of the job.
+        assert_eq!(pool.run(|| 1).await, 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled_jobs_leave_queue() {
+        let pool = CpuPool::new("test_pool", 1, 1);
+        let (unblock_sender, unblock_receiver) = std::sync::mpsc::channel::<()>();
+
+        // Occupy the thread and the queue, so the next submitter waits for the free place.
+        let blocking_job = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.run(move || unblock_receiver.recv().ok()).await }
+        });
+        let queued_job = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.run(|| ()).await }
+        });
+        let waiting_job = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.run(|| ()).await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        assert_eq!(pool.stats.queued.load(Ordering::SeqCst), 2);
+
+        waiting_job.abort();
+        assert!(waiting_job.await.unwrap_err().is_cancelled());
+        assert_eq!(pool.stats.queued.load(Ordering::SeqCst), 1);
+
+        unblock_sender.send(()).unwrap();
+        blocking_job.await.unwrap();
+        queued_job.await.unwrap();
+        assert_eq!(pool.stats.queued.load(Ordering::SeqCst), 0);
+    }
+}
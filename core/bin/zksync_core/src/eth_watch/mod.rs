@@ -287,6 +287,15 @@ impl<W: EthClient> EthWatch<W> {
 
         // TODO maybe retry? It can be the only problem is database
         receiver.await.expect("Mempool actor was dropped")?;
+        // Store the current block, so the API can report the number of confirmations.
+        let (sender, receiver) = oneshot::channel();
+        self.mempool_tx_sender
+            .send(MempoolTransactionRequest::UpdateLastEthBlock(
+                current_ethereum_block,
+                sender,
+            ))
+            .await?;
+        receiver.await.expect("Mempool actor was dropped")?;
         // The backup block number is not used.
         let state = ETHState::new(
             current_ethereum_block,
@@ -151,7 +151,11 @@ async fn fake_mempool(
                 }
                 channel.send(Ok(())).unwrap_or_default()
             }
+            MempoolTransactionRequest::ReplaceTx(_, _, _) => unreachable!(),
             MempoolTransactionRequest::NewTxsBatch(_, _, _) => unreachable!(),
+            MempoolTransactionRequest::UpdateLastEthBlock(_, channel) => {
+                channel.send(Ok(())).unwrap_or_default()
+            }
         }
     }
 }
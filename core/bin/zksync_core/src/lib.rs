@@ -10,7 +10,9 @@ use futures::{channel::mpsc, future};
 use tokio::task::JoinHandle;
 use zksync_config::{ChainConfig, ZkSyncConfig};
 use zksync_eth_client::EthereumGateway;
-use zksync_mempool::{run_mempool_block_handler, run_mempool_tx_handler};
+use zksync_mempool::{
+    run_mempool_block_handler, run_mempool_expiration_task, run_mempool_tx_handler,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{tokens::get_genesis_token_list, Token, TokenId, TokenKind};
 
@@ -108,6 +110,8 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_tx_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.state_keeper.mempool_max_nonce_gap,
+        config.chain.state_keeper.mempool_insert_window(),
     );
 
     // Run health check api for core
@@ -172,12 +176,19 @@ pub async fn run_core(
         connection_pool.clone(),
         mempool_block_request_receiver,
         config.chain.state_keeper.block_chunk_sizes.clone(),
+        config.chain.state_keeper.mempool_tx_ordering,
+    );
+    let mempool_expiration_task = run_mempool_expiration_task(
+        connection_pool.clone(),
+        config.chain.state_keeper.mempool_tx_max_age(),
+        config.chain.state_keeper.mempool_expiration_interval(),
     );
 
     // Start token handler.
     let token_handler_task = run_token_handler(
         connection_pool.clone(),
         eth_gateway.clone(),
+        config.contracts.contract_addr,
         &config.token_handler,
         eth_watch_req_sender.clone(),
     );
@@ -204,6 +215,7 @@ pub async fn run_core(
         tx_event_emitter_task,
         mempool_block_handler_task,
         mempool_tx_handler_task,
+        mempool_expiration_task,
         private_api_task,
     ];
 
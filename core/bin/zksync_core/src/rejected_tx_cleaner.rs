@@ -1,10 +1,14 @@
-//! The cleaner is responsible for removing rejected transactions from the database
-//! that were stored 2 or more weeks ago (this value is configurable as well as the actor's sleep time).
+//! The cleaner is responsible for removing rejected transactions (and transactions expired in the mempool
+//! or rejected before reaching it)
+//! from the database that were stored 2 or more weeks ago (this value is configurable as well as the actor's sleep time).
 //!
 //! The purpose is not to store the information about the failed transaction execution
 //! which is useful only for a short period of time. Since such transactions are not actually
 //! included in the block and don't affect the state hash, there is no much sense to keep
 //! them forever.
+//!
+//! Transactions left pending the asynchronous verification by the API restart are removed
+//! once the verification times out, regardless of the max age.
 
 // External uses
 use tokio::{task::JoinHandle, time};
@@ -33,6 +37,22 @@ pub fn run_rejected_tx_cleaner(config: &DBConfig, db_pool: ConnectionPool) -> Jo
             {
                 vlog::error!("Can't delete rejected transactions {:?}", e);
             }
+            if let Err(e) = storage
+                .chain()
+                .mempool_schema()
+                .remove_expired_txs(chrono::Utc::now() - max_age)
+                .await
+            {
+                vlog::error!("Can't delete expired transactions {:?}", e);
+            }
+            if let Err(e) = storage
+                .chain()
+                .mempool_schema()
+                .remove_async_submitted_txs(chrono::Utc::now() - max_age)
+                .await
+            {
+                vlog::error!("Can't delete asynchronously submitted transactions {:?}", e);
+            }
             timer.tick().await;
         }
     })
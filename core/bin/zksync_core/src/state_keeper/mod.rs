@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
 // External uses
@@ -20,13 +20,13 @@ use zksync_types::{
     gas_counter::GasCounter,
     mempool::SignedTxVariant,
     tx::ZkSyncTx,
-    Address, PriorityOp, SignedZkSyncTx,
+    AccountId, Address, Nonce, PriorityOp, SignedZkSyncTx,
 };
 // Local uses
 use self::{
     pending_block::PendingBlock,
     root_hash_calculator::{BlockRootHashJob, RootHashCalculator},
-    types::{ApplyOutcome, StateKeeperConfig},
+    types::{ApplyOutcome, PriorityOpApplyOutcome, StateKeeperConfig},
     utils::system_time_timestamp,
 };
 use crate::{
@@ -352,7 +352,9 @@ impl ZkSyncStateKeeper {
         for tx in block.block_transactions {
             match tx {
                 ExecutedOperations::Tx(tx) => {
-                    if let ApplyOutcome::NotIncluded = self.apply_tx(&tx.signed_tx) {
+                    if let ApplyOutcome::NotIncluded | ApplyOutcome::Parked =
+                        self.apply_tx(&tx.signed_tx)
+                    {
                         // It's not necessary to include all txs because we can change the rules, cause of some bugs.
                         // However, it's not a common situation in most of the cases it should be applied as is
                         vlog::error!("The transaction was not included in a block when a block recovery was performed: {:?}", &tx.signed_tx)
@@ -392,10 +394,10 @@ impl ZkSyncStateKeeper {
             .collect::<VecDeque<_>>();
         while let Some(priority_op) = priority_op_queue.pop_front() {
             match self.apply_priority_op(&priority_op) {
-                ApplyOutcome::Included(exec_op) => {
+                PriorityOpApplyOutcome::Included(exec_op) => {
                     executed_ops.push(exec_op);
                 }
-                ApplyOutcome::NotIncluded => {
+                PriorityOpApplyOutcome::NotIncluded => {
                     self.seal_pending_block().await;
 
                     priority_op_queue.push_front(priority_op);
@@ -419,6 +421,10 @@ impl ZkSyncStateKeeper {
 
                             tx_queue.push_front(variant);
                         }
+                        ApplyOutcome::Parked => {
+                            // The transaction stays in the mempool and will be proposed again.
+                            metrics::increment_counter!("state_keeper.parked_txs");
+                        }
                     }
                 }
                 SignedTxVariant::Batch(batch) => {
@@ -434,6 +440,9 @@ impl ZkSyncStateKeeper {
 
                             tx_queue.push_front(variant);
                         }
+                        ApplyOutcome::Parked => {
+                            metrics::increment_counter!("state_keeper.parked_txs");
+                        }
                     }
                 }
             }
@@ -479,11 +488,11 @@ impl ZkSyncStateKeeper {
     }
 
     // Err if there is no space in current block
-    fn apply_priority_op(&mut self, priority_op: &PriorityOp) -> ApplyOutcome<ExecutedOperations> {
+    fn apply_priority_op(&mut self, priority_op: &PriorityOp) -> PriorityOpApplyOutcome {
         let start = Instant::now();
         let chunks_needed = priority_op.data.chunks();
         if self.pending_block.chunks_left < chunks_needed {
-            return ApplyOutcome::NotIncluded;
+            return PriorityOpApplyOutcome::NotIncluded;
         }
 
         // Check if adding this transaction to the block won't make the contract operations
@@ -501,7 +510,7 @@ impl ZkSyncStateKeeper {
         {
             // We've reached the gas limit, seal the block.
             // This transaction will go into the next one.
-            return ApplyOutcome::NotIncluded;
+            return PriorityOpApplyOutcome::NotIncluded;
         }
 
         let OpSuccess {
@@ -526,7 +535,36 @@ impl ZkSyncStateKeeper {
         );
 
         metrics::histogram!("state_keeper.apply_priority_op", start.elapsed());
-        ApplyOutcome::Included(exec_result)
+        PriorityOpApplyOutcome::Included(exec_result)
+    }
+
+    /// Checks whether some of the transactions are ahead of the nonce of their account,
+    /// taking into account the nonces used by the preceding transactions.
+    /// Such transactions would fail, so they are parked until the missing nonces are executed.
+    fn has_nonce_gap(&self, txs: &[SignedZkSyncTx]) -> bool {
+        let mut nonces: HashMap<AccountId, Nonce> = HashMap::new();
+        for tx in txs {
+            let account_id = match tx.tx.account_id() {
+                Ok(account_id) => account_id,
+                Err(_) => continue,
+            };
+            let nonce = match nonces.get(&account_id) {
+                Some(nonce) => *nonce,
+                None => match self.state.get_account(account_id) {
+                    Some(account) => account.nonce,
+                    // Transaction of the unknown account fails anyway.
+                    None => continue,
+                },
+            };
+
+            if tx.tx.nonce() > nonce {
+                return true;
+            }
+            if tx.tx.nonce() == nonce {
+                nonces.insert(account_id, nonce + 1);
+            }
+        }
+        false
     }
 
     fn apply_batch(
@@ -537,6 +575,10 @@ impl ZkSyncStateKeeper {
         metrics::gauge!("tx_batch_size", txs.len() as f64);
         let start = Instant::now();
 
+        if self.has_nonce_gap(txs) {
+            return ApplyOutcome::Parked;
+        }
+
         let chunks_needed = self.state.chunks_for_batch(txs);
 
         // If we can't add the tx to the block due to the size limit, we return this tx,
@@ -648,6 +690,10 @@ impl ZkSyncStateKeeper {
 
     fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> ApplyOutcome<ExecutedOperations> {
         let start = Instant::now();
+
+        if self.has_nonce_gap(std::slice::from_ref(tx)) {
+            return ApplyOutcome::Parked;
+        }
         let chunks_needed = self.state.chunks_for_tx(tx);
 
         // If we can't add the tx to the block due to the size limit, we return this tx,
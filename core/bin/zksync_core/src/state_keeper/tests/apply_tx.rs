@@ -1,4 +1,4 @@
-use zksync_types::{AccountId, TokenId};
+use zksync_types::{AccountId, TokenId, ZkSyncTx};
 
 use super::utils::*;
 
@@ -92,3 +92,33 @@ fn not_enough_chunks() {
     let result = tester.state_keeper.apply_tx(&withdraw);
     assert!(result.is_not_included());
 }
+
+/// Checks if withdrawal ahead of the account nonce is parked until the gap is filled.
+#[test]
+fn future_nonce() {
+    let mut tester = StateKeeperTester::new(6, 1, 1);
+    let withdraw = create_account_and_withdrawal(
+        &mut tester,
+        TokenId(0),
+        AccountId(1),
+        400u32,
+        145u32,
+        Default::default(),
+    );
+    let mut next_withdraw = withdraw.clone();
+    if let ZkSyncTx::Withdraw(tx) = &mut next_withdraw.tx {
+        tx.nonce = tx.nonce + 1;
+    }
+
+    let old_pending_block = tester.state_keeper.pending_block.clone();
+    let result = tester.state_keeper.apply_tx(&next_withdraw);
+    let pending_block = tester.state_keeper.pending_block.clone();
+
+    assert!(result.is_parked());
+    assert_eq!(pending_block.chunks_left, old_pending_block.chunks_left);
+    assert!(pending_block.success_operations.is_empty());
+    assert!(pending_block.failed_txs.is_empty());
+
+    assert!(tester.state_keeper.apply_tx(&withdraw).is_included());
+    assert!(!tester.state_keeper.apply_tx(&next_withdraw).is_parked());
+}
@@ -3,7 +3,7 @@ use futures::channel::oneshot;
 use itertools::Itertools;
 // Workspace uses
 use zksync_mempool::ProposedBlock;
-use zksync_types::{Account, AccountId, Address};
+use zksync_types::{block::ExecutedOperations, Account, AccountId, Address};
 // Local uses
 use crate::state_keeper::init_params::ZkSyncStateInitParams;
 
@@ -21,11 +21,44 @@ pub enum StateKeeperTestkitRequest {
 pub enum ApplyOutcome<T> {
     Included(T),
     NotIncluded,
+    /// Transaction is ahead of the account nonce, it's kept in the mempool
+    /// until the transactions with the missing nonces are executed.
+    Parked,
 }
 
 impl<T> ApplyOutcome<T> {
     pub fn assert_included(&self, msg: &str) {
-        if matches!(self, Self::NotIncluded) {
+        if !matches!(self, Self::Included(_)) {
+            panic!("{}", msg)
+        }
+    }
+
+    #[cfg(test)]
+    pub fn is_included(&self) -> bool {
+        matches!(self, Self::Included(_))
+    }
+
+    #[cfg(test)]
+    pub fn is_not_included(&self) -> bool {
+        !self.is_included()
+    }
+
+    #[cfg(test)]
+    pub fn is_parked(&self) -> bool {
+        matches!(self, Self::Parked)
+    }
+}
+
+/// Outcome of the priority operation, which unlike the transactions never waits for the nonce.
+#[derive(Debug)]
+pub enum PriorityOpApplyOutcome {
+    Included(ExecutedOperations),
+    NotIncluded,
+}
+
+impl PriorityOpApplyOutcome {
+    pub fn assert_included(&self, msg: &str) {
+        if !matches!(self, Self::Included(_)) {
             panic!("{}", msg)
         }
     }
@@ -4,6 +4,7 @@
 //! To set the name and the decimals parameter for the token, a match is searched for with the
 //! token list (which is taken from the environment). If the token address is not found in the
 //! trusted token list, then the default values are used (name = "ERC20-{id}", decimals = 18).
+//! Tokens that fail the ERC-20 compatibility checks are stored with the `None` kind.
 
 // Built-in deps
 use std::collections::HashMap;
@@ -14,12 +15,13 @@ use futures::{
 };
 use tokio::task::JoinHandle;
 // Workspace uses
+use zksync_api::{eth_checker::EthereumChecker, token_compatibility::TokenCompatibilityChecker};
 use zksync_config::TokenHandlerConfig;
 use zksync_notifier::Notifier;
 use zksync_storage::{tokens::StoreTokenError, ConnectionPool, StorageProcessor};
 use zksync_types::{
     tokens::{NewTokenEvent, Token, TokenInfo},
-    Address, TokenId, TokenKind, U256,
+    Address, TokenKind, U256,
 };
 // Local uses
 use crate::eth_watch::EthWatchRequest;
@@ -32,6 +34,7 @@ struct TokenHandler {
     poll_interval: std::time::Duration,
     eth_watcher_req: mpsc::Sender<EthWatchRequest>,
     eth_client: EthereumGateway,
+    compatibility_checker: TokenCompatibilityChecker,
     token_list: HashMap<Address, TokenInfo>,
     last_eth_block: Option<u64>,
     notifier: Option<Notifier>,
@@ -42,6 +45,7 @@ impl TokenHandler {
         connection_pool: ConnectionPool,
         eth_watcher_req: mpsc::Sender<EthWatchRequest>,
         eth_client: EthereumGateway,
+        contract_addr: Address,
         config: TokenHandlerConfig,
     ) -> Self {
         let poll_interval = config.poll_interval();
@@ -54,9 +58,13 @@ impl TokenHandler {
         let webhook_url = reqwest::Url::parse(&config.webhook_url).ok();
         let notifier = webhook_url.map(Notifier::with_mattermost);
 
+        let compatibility_checker =
+            TokenCompatibilityChecker::new(EthereumChecker::new(eth_client.clone()), contract_addr);
+
         Self {
             connection_pool,
             eth_client,
+            compatibility_checker,
             token_list,
             poll_interval,
             notifier,
@@ -94,6 +102,22 @@ impl TokenHandler {
             .is_ok()
     }
 
+    /// Tokens that aren't ERC-20 contracts or fail the compatibility checks
+    /// are stored with the `None` kind, so they aren't listed by the API.
+    async fn token_kind(&self, address: Address) -> TokenKind {
+        if !self.is_contract_erc20(address).await {
+            return TokenKind::None;
+        }
+        if !self.compatibility_checker.check(address).await.compatible {
+            vlog::warn!(
+                "Token {:?} didn't pass the ERC-20 compatibility checks",
+                address
+            );
+            return TokenKind::None;
+        }
+        TokenKind::ERC20
+    }
+
     async fn save_new_tokens(
         &self,
         storage: &mut StorageProcessor<'_>,
@@ -102,11 +126,40 @@ impl TokenHandler {
         let mut transaction = storage.start_transaction().await?;
         let mut token_schema = transaction.tokens_schema();
 
-        let last_token_id = TokenId(token_schema.get_max_token_id().await?);
+        // Tokens can be listed through the API before the event is processed,
+        // so the ids of the stored tokens may have gaps.
+        let stored_tokens = token_schema.load_tokens().await?;
         let mut new_tokens = Vec::new();
 
         for token_event in tokens {
-            if token_event.id.0 <= last_token_id.0 {
+            if let Some(stored_token) = stored_tokens.get(&token_event.id) {
+                if stored_token.address == token_event.address {
+                    continue;
+                }
+                // Token listed through the API doesn't match the event, e.g. the
+                // governance contract was read at the block that was reorganized.
+                vlog::warn!(
+                    "Token {} is stored with address {:?}, but the event assigns it to {:?}, replacing it",
+                    token_event.id,
+                    stored_token.address,
+                    token_event.address
+                );
+                let token_kind = self.token_kind(token_event.address).await;
+                let decimals = self
+                    .token_list
+                    .get(&token_event.address)
+                    .map(|token_info| token_info.decimals)
+                    .unwrap_or(18);
+                // The default symbol is unique, metadata is updated by the next listing request.
+                let token = Token::new(
+                    token_event.id,
+                    token_event.address,
+                    &format!("ERC20-{}", token_event.id),
+                    decimals,
+                    token_kind,
+                );
+                token_schema.store_or_update_token(token.clone()).await?;
+                new_tokens.push(token);
                 continue;
             }
 
@@ -115,12 +168,7 @@ impl TokenHandler {
             let default_symbol = format!("ERC20-{}", token_event.id);
             let default_decimals = 18;
 
-            let is_erc20 = self.is_contract_erc20(token_event.address).await;
-            let token_kind = if is_erc20 {
-                TokenKind::ERC20
-            } else {
-                TokenKind::None
-            };
+            let token_kind = self.token_kind(token_event.address).await;
 
             let token_from_list = {
                 let token_info = self.token_list.get(&token_event.address).cloned();
@@ -241,13 +289,19 @@ impl TokenHandler {
 pub fn run_token_handler(
     db_pool: ConnectionPool,
     eth_client: EthereumGateway,
+    contract_addr: Address,
     config: &TokenHandlerConfig,
     eth_watcher_req: mpsc::Sender<EthWatchRequest>,
 ) -> JoinHandle<()> {
     let config = config.clone();
     tokio::spawn(async move {
-        let mut token_handler =
-            TokenHandler::new(db_pool, eth_watcher_req, eth_client, config.clone());
+        let mut token_handler = TokenHandler::new(
+            db_pool,
+            eth_watcher_req,
+            eth_client,
+            contract_addr,
+            config.clone(),
+        );
 
         token_handler.run().await
     })
@@ -81,6 +81,7 @@ pub(super) trait DatabaseInterface {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()>;
 
     /// Loads the stored Ethereum operations stats.
@@ -256,6 +257,7 @@ impl DatabaseInterface for Database {
         connection: &mut StorageProcessor<'_>,
         hash: &H256,
         op: &ETHOperation,
+        gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut transaction = connection.start_transaction().await?;
 
@@ -313,6 +315,12 @@ impl DatabaseInterface for Database {
         }
 
         transaction.ethereum_schema().confirm_eth_tx(hash).await?;
+        if let Some(gas_used) = gas_used {
+            transaction
+                .ethereum_schema()
+                .save_eth_tx_gas_used(hash, gas_used.as_u64())
+                .await?;
+        }
         transaction.commit().await?;
 
         Ok(())
@@ -472,7 +472,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                     // Transaction is pending, nothing to do yet.
                     return Ok(OperationCommitment::Pending);
                 }
-                TxCheckOutcome::Committed => {
+                TxCheckOutcome::Committed(gas_used) => {
                     let mut connection = self.db.acquire_connection().await?;
                     let mut transaction = connection.start_transaction().await?;
 
@@ -506,7 +506,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
                         op.id, op.op_type, tx_hash, self.zksync_operation_description(op),
                     );
                     self.db
-                        .confirm_operation(&mut transaction, tx_hash, op)
+                        .confirm_operation(&mut transaction, tx_hash, op, gas_used)
                         .await?;
                     transaction.commit().await?;
                     return Ok(OperationCommitment::Committed);
@@ -602,7 +602,7 @@ impl<DB: DatabaseInterface> ETHSender<DB> {
             Some(status) if status.success => {
                 // Check if transaction has enough confirmations.
                 if status.confirmations >= self.options.sender.wait_confirmations {
-                    TxCheckOutcome::Committed
+                    TxCheckOutcome::Committed(status.gas_used)
                 } else {
                     TxCheckOutcome::Pending
                 }
@@ -282,6 +282,7 @@ impl DatabaseInterface for MockDatabase {
         _connection: &mut StorageProcessor<'_>,
         hash: &H256,
         _op: &ETHOperation,
+        _gas_used: Option<U256>,
     ) -> anyhow::Result<()> {
         let mut eth_operations = self.eth_operations.write().await;
         let mut op_idx: Option<i64> = None;
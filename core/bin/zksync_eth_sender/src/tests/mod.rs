@@ -78,6 +78,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -91,6 +92,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: true,
         receipt: None,
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -104,6 +106,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -117,6 +120,7 @@ async fn transaction_state() {
         confirmations: WAIT_CONFIRMATIONS - 1,
         success: false,
         receipt: Some(Default::default()),
+        gas_used: None,
     };
     eth_sender
         .ethereum
@@ -139,7 +143,7 @@ async fn transaction_state() {
             )
             .await
             .unwrap(),
-        TxCheckOutcome::Committed
+        TxCheckOutcome::Committed(None)
     );
 
     // Pending operation (no enough confirmations).
@@ -5,7 +5,7 @@
 
 // Built-in deps
 // External uses
-use zksync_basic_types::TransactionReceipt;
+use zksync_basic_types::{TransactionReceipt, U256};
 // Workspace uses
 use zksync_storage::ethereum::records::ETHStats as StorageETHStats;
 
@@ -36,8 +36,8 @@ impl From<StorageETHStats> for ETHStats {
 /// The result of the check for the Ethereum transaction commitment.
 #[derive(Debug, PartialEq)]
 pub enum TxCheckOutcome {
-    /// Transaction was committed and confirmed.
-    Committed,
+    /// Transaction was committed and confirmed. Contains the amount of gas used by it, if known.
+    Committed(Option<U256>),
     /// Transaction is pending yet.
     Pending,
     /// Transaction is considered stuck, a replacement should be made.
@@ -5,7 +5,7 @@ use serde::Deserialize;
 // Workspace uses
 use zksync_types::{
     event::{account::*, EventData, ZkSyncEvent},
-    AccountId, TokenId,
+    AccountId, Address, TokenId,
 };
 // Local uses
 
@@ -13,6 +13,7 @@ use zksync_types::{
 #[serde(deny_unknown_fields)]
 pub struct AccountFilter {
     pub accounts: Option<HashSet<AccountId>>,
+    pub addresses: Option<HashSet<Address>>,
     pub tokens: Option<HashSet<TokenId>>,
     pub status: Option<AccountStateChangeStatus>,
 }
@@ -45,6 +46,13 @@ impl AccountFilter {
                 return false;
             }
         }
+        // Events without addresses are filtered out.
+        if let Some(addresses) = &self.addresses {
+            match account_event.update_details.address {
+                Some(address) if addresses.contains(&address) => {}
+                _ => return false,
+            }
+        }
         true
     }
 }
@@ -52,13 +60,14 @@ impl AccountFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zksync_types::event::test_data::get_account_event;
+    use zksync_types::event::test_data::{get_account_address, get_account_event};
 
     #[test]
     fn test_account_filter() {
         // Match all events.
         let mut account_filter = AccountFilter {
             accounts: None,
+            addresses: None,
             tokens: None,
             status: None,
         };
@@ -122,5 +131,38 @@ mod tests {
             AccountStateChangeStatus::Committed,
         );
         assert!(account_filter.matches(&event));
+
+        // Filter by address instead of the account id.
+        let mut account_filter = AccountFilter {
+            accounts: None,
+            addresses: Some(
+                [get_account_address(AccountId(1000))]
+                    .iter()
+                    .copied()
+                    .collect(),
+            ),
+            tokens: None,
+            status: Some(AccountStateChangeStatus::Finalized),
+        };
+        let event = get_account_event(
+            AccountId(1000),
+            Some(TokenId(20)),
+            AccountStateChangeStatus::Finalized,
+        );
+        assert!(account_filter.matches(&event));
+        let event = get_account_event(
+            AccountId(2000),
+            Some(TokenId(20)),
+            AccountStateChangeStatus::Finalized,
+        );
+        assert!(!account_filter.matches(&event));
+        // Both filters should match.
+        account_filter.accounts = Some([AccountId(2000)].iter().copied().collect());
+        let event = get_account_event(
+            AccountId(1000),
+            Some(TokenId(20)),
+            AccountStateChangeStatus::Finalized,
+        );
+        assert!(!account_filter.matches(&event));
     }
 }
@@ -92,6 +92,15 @@ fn test_filters_deserialize() {
                 "status": "rejected"
             }
         }"#,
+        r#"{
+            "account": {
+                "addresses": ["0x0000000000000000000000000000000000000001"]
+            },
+            "transaction": {
+                "addresses": ["0x0000000000000000000000000000000000000001"],
+                "status": "finalized"
+            }
+        }"#,
         r#"{
             "account": {
                 "status": "committed",
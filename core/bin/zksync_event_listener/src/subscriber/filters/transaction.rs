@@ -5,7 +5,7 @@ use serde::Deserialize;
 // Workspace uses
 use zksync_types::{
     event::{transaction::*, EventData, ZkSyncEvent},
-    AccountId, TokenId,
+    AccountId, Address, TokenId,
 };
 // Local uses
 
@@ -14,6 +14,7 @@ use zksync_types::{
 pub struct TransactionFilter {
     pub types: Option<HashSet<TransactionType>>,
     pub accounts: Option<HashSet<AccountId>>,
+    pub addresses: Option<HashSet<Address>>,
     pub tokens: Option<HashSet<TokenId>>,
     pub status: Option<TransactionStatus>,
 }
@@ -47,6 +48,13 @@ impl TransactionFilter {
                 return false;
             }
         }
+        // Events without addresses are filtered out.
+        if let Some(addresses) = &self.addresses {
+            match tx_event.address {
+                Some(address) if addresses.contains(&address) => {}
+                _ => return false,
+            }
+        }
         true
     }
 }
@@ -54,7 +62,7 @@ impl TransactionFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zksync_types::event::test_data::get_transaction_event;
+    use zksync_types::event::test_data::{get_account_address, get_transaction_event};
 
     #[test]
     fn test_transaction_filter() {
@@ -62,6 +70,7 @@ mod tests {
         let mut tx_filter = TransactionFilter {
             types: None,
             accounts: None,
+            addresses: None,
             tokens: None,
             status: None,
         };
@@ -141,5 +150,26 @@ mod tests {
             );
             assert!(tx_filter.matches(&event));
         }
+        // Add addresses filter, only one of the accounts is left.
+        tx_filter.addresses = Some(
+            [get_account_address(AccountId(34))]
+                .iter()
+                .copied()
+                .collect(),
+        );
+        let event = get_transaction_event(
+            TransactionType::Transfer,
+            AccountId(12),
+            TokenId(1),
+            TransactionStatus::Rejected,
+        );
+        assert!(!tx_filter.matches(&event));
+        let event = get_transaction_event(
+            TransactionType::Transfer,
+            AccountId(34),
+            TokenId(1),
+            TransactionStatus::Rejected,
+        );
+        assert!(tx_filter.matches(&event));
     }
 }
@@ -5,6 +5,9 @@ use reqwest::StatusCode;
 use serde::{de::DeserializeOwned, ser::Serialize};
 use thiserror::Error;
 
+// Workspace uses
+use zksync_api_types::v02;
+
 // Local uses
 use super::error::ErrorBody;
 
@@ -75,6 +78,18 @@ impl Client {
             url,
         }
     }
+
+    pub(crate) fn delete_with_scope(
+        &self,
+        scope: impl AsRef<str>,
+        method: impl AsRef<str>,
+    ) -> ClientRequestBuilder {
+        let url = self.endpoint(scope.as_ref(), method.as_ref());
+        ClientRequestBuilder {
+            inner: self.inner.delete(&url),
+            url,
+        }
+    }
 }
 
 /// API specific wrapper over the `reqwest::RequestBuilder`.
@@ -97,6 +112,18 @@ impl ClientRequestBuilder {
         }
     }
 
+    /// Add a header to the request.
+    ///
+    /// See [reqwest] documentation for details
+    ///
+    /// [reqwest]: https://docs.rs/reqwest/latest/reqwest/struct.RequestBuilder.html#method.header
+    pub fn header(self, key: &str, value: &str) -> Self {
+        Self {
+            inner: self.inner.header(key, value),
+            url: self.url,
+        }
+    }
+
     /// Send a JSON body.
     ///
     /// See [reqwest] documentation for details
@@ -109,6 +136,14 @@ impl ClientRequestBuilder {
         }
     }
 
+    /// Sends the request and returns the response body as is.
+    ///
+    /// Used for the responses that are not wrapped into the common structure,
+    /// e.g. the newline-delimited JSON streams.
+    pub async fn send_text(self) -> self::Result<String> {
+        Ok(self.inner.send().await?.text().await?)
+    }
+
     /// Constructs the Request and sends it to the target URL, returning a future Response.
     ///
     /// This method takes account of the responses structure and the error handling specific.
@@ -119,14 +154,35 @@ impl ClientRequestBuilder {
         if status.is_success() {
             Ok(response.json().await.map_err(ClientError::Parse)?)
         } else {
+            let body = response.bytes().await?;
+            // API v0.2 errors are reported with the corresponding HTTP status code,
+            // but still have the regular response structure.
+            if is_v02_error(&body) {
+                if let Ok(response) = serde_json::from_slice(&body) {
+                    return Ok(response);
+                }
+            }
+
             if status == StatusCode::NOT_FOUND {
                 return Err(ClientError::NotFound(self.url));
             }
 
             Err(ClientError::BadRequest {
                 http_code: status,
-                body: response.json().await.map_err(ClientError::Parse)?,
+                body: serde_json::from_slice(&body).unwrap_or_default(),
             })
         }
     }
 }
+
+/// Checks whether the body of the unsuccessful response is the API v0.2 error response.
+fn is_v02_error(body: &[u8]) -> bool {
+    matches!(
+        serde_json::from_slice(body),
+        Ok(v02::Response {
+            status: v02::ResultStatus::Error,
+            error: Some(_),
+            ..
+        })
+    )
+}
@@ -1,7 +1,11 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
-    pagination::{ApiEither, PaginationQuery},
+    account::{
+        AccountExitProofQuery, AccountStateQuery, AccountsBatchRequest, BalanceHistoryQuery,
+        EIP1271SignatureCheckRequest,
+    },
+    pagination::{AccountTxsFilters, ApiEither, PaginationQuery},
     Response,
 };
 use zksync_types::{tx::TxHash, SerialId};
@@ -29,6 +33,22 @@ impl Client {
         .await
     }
 
+    pub async fn account_state_for_block(
+        &self,
+        account_id_or_address: &str,
+        block: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/state", account_id_or_address),
+        )
+        .query(&AccountStateQuery {
+            block: block.to_owned(),
+        })
+        .send()
+        .await
+    }
+
     pub async fn account_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<TxHash>>,
@@ -43,6 +63,38 @@ impl Client {
         .await
     }
 
+    pub async fn account_txs_with_filters(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        account_id_or_address: &str,
+        filters: &AccountTxsFilters,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions", account_id_or_address),
+        )
+        .query(&pagination_query)
+        .query(filters)
+        .send()
+        .await
+    }
+
+    pub async fn account_txs_with_counterparty(
+        &self,
+        pagination_query: &PaginationQuery<ApiEither<TxHash>>,
+        account_id_or_address: &str,
+        counterparty: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/transactions", account_id_or_address),
+        )
+        .query(&pagination_query)
+        .query(&[("counterparty", counterparty)])
+        .send()
+        .await
+    }
+
     pub async fn account_pending_txs(
         &self,
         pagination_query: &PaginationQuery<ApiEither<SerialId>>,
@@ -56,4 +108,151 @@ impl Client {
         .send()
         .await
     }
+
+    pub async fn account_pending_priority_ops(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/pending_priority_ops", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_nfts(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nfts", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_nonce(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/nonce", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn accounts_by_pubkey_hash(&self, pub_key_hash: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/by_pubkey_hash/{}", pub_key_hash),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn accounts_batch(&self, accounts: Vec<String>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "accounts/batch")
+            .body(&AccountsBatchRequest { accounts })
+            .send()
+            .await
+    }
+
+    pub async fn account_pubkey_history(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/pubkey_history", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_balance_history(
+        &self,
+        account_id_or_address: &str,
+        query: &BalanceHistoryQuery,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/balance_history", account_id_or_address),
+        )
+        .query(query)
+        .send()
+        .await
+    }
+
+    pub async fn account_pending_balance(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/pending_balance", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_is_contract_wallet(
+        &self,
+        account_id_or_address: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/is_contract_wallet", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_check_eip1271_signature(
+        &self,
+        account_id_or_address: &str,
+        request: &EIP1271SignatureCheckRequest,
+    ) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/check_eip1271_signature", account_id_or_address),
+        )
+        .body(request)
+        .send()
+        .await
+    }
+
+    pub async fn account_fees(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/fees", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_exit_proof(
+        &self,
+        account_id_or_address: &str,
+        token: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/proof", account_id_or_address),
+        )
+        .query(&AccountExitProofQuery {
+            token: token.to_string(),
+        })
+        .send()
+        .await
+    }
+
+    pub async fn account_registration(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/registration", account_id_or_address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn account_mempool(&self, account_id_or_address: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("accounts/{}/mempool", account_id_or_address),
+        )
+        .send()
+        .await
+    }
 }
@@ -1,6 +1,7 @@
 use crate::rest::client::{Client, Result};
 
 use zksync_api_types::v02::{
+    block::{BlocksExportQuery, FinalizedBlocksQuery},
     pagination::{ApiEither, PaginationQuery},
     Response,
 };
@@ -13,10 +14,70 @@ impl Client {
             .await
     }
 
-    pub async fn transaction_in_block(&self, block_number: u32, tx_index: u32) -> Result<Response> {
+    pub async fn transaction_in_block(
+        &self,
+        block_position: &str,
+        tx_index: u32,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/transactions/{}", block_position, tx_index),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn pending_block(&self) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "blocks/pending")
+            .send()
+            .await
+    }
+
+    pub async fn finalized_blocks(&self, since_block: BlockNumber) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "blocks/finalized")
+            .query(&FinalizedBlocksQuery { since_block })
+            .send()
+            .await
+    }
+
+    pub async fn blocks_export(&self, from: &str, to: &str) -> Result<String> {
+        self.get_with_scope(super::API_V02_SCOPE, "blocks/export")
+            .query(&BlocksExportQuery {
+                from: from.to_owned(),
+                to: to.to_owned(),
+            })
+            .send_text()
+            .await
+    }
+
+    pub async fn block_by_root(&self, hash: &str) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("blocks/by_root/{}", hash))
+            .send()
+            .await
+    }
+
+    pub async fn block_stats(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/stats", block_position),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn block_proof(&self, block_position: &str) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("blocks/{}/proof", block_position),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn block_state_diff(&self, block_position: &str) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
-            &format!("blocks/{}/transactions/{}", block_number, tx_index),
+            &format!("blocks/{}/state_diff", block_position),
         )
         .send()
         .await
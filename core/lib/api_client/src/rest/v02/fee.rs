@@ -1,7 +1,8 @@
 // Local uses
 use crate::rest::client::{Client, Result};
+use chrono::{DateTime, Utc};
 use zksync_api_types::v02::{
-    fee::{ApiTxFeeTypes, BatchFeeRequest, TxFeeRequest, TxInBatchFeeRequest},
+    fee::{ApiTxFeeTypes, BatchFeeRequest, FeeHistoryQuery, TxFeeRequest, TxInBatchFeeRequest},
     Response,
 };
 use zksync_types::{Address, TokenLike};
@@ -36,4 +37,22 @@ impl Client {
             .send()
             .await
     }
+
+    pub async fn fee_history(
+        &self,
+        tx_type: Option<String>,
+        token: Option<String>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "fee/history")
+            .query(&FeeHistoryQuery {
+                tx_type,
+                token,
+                from,
+                to,
+            })
+            .send()
+            .await
+    }
 }
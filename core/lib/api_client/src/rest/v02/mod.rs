@@ -2,6 +2,8 @@ pub mod account;
 pub mod block;
 pub mod config;
 pub mod fee;
+pub mod mempool;
+pub mod stats;
 pub mod status;
 pub mod token;
 pub mod transaction;
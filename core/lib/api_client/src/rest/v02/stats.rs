@@ -0,0 +1,14 @@
+use crate::rest::client::{Client, Result};
+use zksync_api_types::v02::{block::BlocksStatsQuery, Response};
+
+impl Client {
+    pub async fn blocks_stats(&self, from: &str, to: &str) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, "stats/blocks")
+            .query(&BlocksStatsQuery {
+                from: from.to_owned(),
+                to: to.to_owned(),
+            })
+            .send()
+            .await
+    }
+}
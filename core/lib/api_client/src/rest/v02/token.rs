@@ -1,9 +1,11 @@
 use crate::rest::client::{Client, Result};
+use chrono::{DateTime, Utc};
 use zksync_api_types::v02::{
     pagination::{ApiEither, PaginationQuery},
+    token::{TokenListingRequest, TokenPriceHistoryQuery},
     Response,
 };
-use zksync_types::{tx::TxHash, TokenId, TokenLike};
+use zksync_types::{tx::TxHash, Address, TokenId, TokenLike};
 
 impl Client {
     pub async fn token_pagination(
@@ -16,6 +18,13 @@ impl Client {
             .await
     }
 
+    pub async fn list_token(&self, address: Address) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "tokens")
+            .body(&TokenListingRequest { address })
+            .send()
+            .await
+    }
+
     pub async fn token_by_id(&self, token: &TokenLike) -> Result<Response> {
         self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/{}", token))
             .send()
@@ -31,12 +40,64 @@ impl Client {
         .await
     }
 
+    pub async fn token_price_with_sources(
+        &self,
+        token: &TokenLike,
+        token_id_or_usd: &str,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/priceIn/{}/sources", token, token_id_or_usd),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn token_compatibility(&self, address: Address) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{:?}/compatibility", address),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn token_price_history(
+        &self,
+        token: &TokenLike,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/price_history", token),
+        )
+        .query(&TokenPriceHistoryQuery { from, to })
+        .send()
+        .await
+    }
+
+    pub async fn token_fee_decisions(&self, token: &TokenLike) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("tokens/{}/fee_decisions", token),
+        )
+        .send()
+        .await
+    }
+
     pub async fn nft_by_id(&self, id: TokenId) -> Result<Response> {
         self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/nft/{}", id))
             .send()
             .await
     }
 
+    pub async fn nft_metadata(&self, id: TokenId) -> Result<Response> {
+        self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/nft/{}/metadata", id))
+            .send()
+            .await
+    }
+
     pub async fn nft_owner_by_id(&self, id: TokenId) -> Result<Response> {
         self.get_with_scope(super::API_V02_SCOPE, &format!("tokens/nft/{}/owner", id))
             .send()
@@ -1,9 +1,18 @@
 use crate::rest::client::{Client, Result};
 use zksync_api_types::{
-    v02::{transaction::IncomingTxBatch, Response},
+    v02::{
+        transaction::{
+            CancelTx, IncomingTxBatch, TxInBlockStatus, TxStatusesRequest, TxToDecode, TxWaitQuery,
+            REQUEST_ID_HEADER,
+        },
+        Response,
+    },
     TxWithSignature,
 };
-use zksync_types::tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx};
+use zksync_types::{
+    tx::{EthBatchSignatures, TxEthSignatureVariant, TxHash, ZkSyncTx},
+    SerialId,
+};
 
 impl Client {
     pub async fn submit_tx(
@@ -17,6 +26,84 @@ impl Client {
             .await
     }
 
+    pub async fn submit_tx_with_request_id(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+        request_id: &str,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions")
+            .header(REQUEST_ID_HEADER, request_id)
+            .body(&TxWithSignature { tx, signature })
+            .send()
+            .await
+    }
+
+    pub async fn submit_tx_async(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/async")
+            .body(&TxWithSignature { tx, signature })
+            .send()
+            .await
+    }
+
+    pub async fn validate_tx(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/validate")
+            .body(&TxWithSignature { tx, signature })
+            .send()
+            .await
+    }
+
+    pub async fn verify_tx_signature(
+        &self,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/verify_signature")
+            .body(&TxWithSignature { tx, signature })
+            .send()
+            .await
+    }
+
+    pub async fn decode_tx(&self, tx: TxToDecode) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/decode")
+            .body(&tx)
+            .send()
+            .await
+    }
+
+    pub async fn cancel_tx(&self, tx_hash: TxHash, cancel_tx: CancelTx) -> Result<Response> {
+        self.delete_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}", tx_hash.to_string()),
+        )
+        .body(&cancel_tx)
+        .send()
+        .await
+    }
+
+    pub async fn replace_tx(
+        &self,
+        tx_hash: TxHash,
+        tx: ZkSyncTx,
+        signature: TxEthSignatureVariant,
+    ) -> Result<Response> {
+        self.post_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/replace", tx_hash.to_string()),
+        )
+        .body(&TxWithSignature { tx, signature })
+        .send()
+        .await
+    }
+
     pub async fn submit_batch(
         &self,
         txs: Vec<TxWithSignature>,
@@ -37,6 +124,37 @@ impl Client {
         .await
     }
 
+    pub async fn priority_op_status(&self, serial_id: SerialId) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/priority_op/{}", serial_id),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn wait_tx_status(
+        &self,
+        tx_hash: TxHash,
+        status: TxInBlockStatus,
+        timeout: Option<u64>,
+    ) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/wait", tx_hash.to_string()),
+        )
+        .query(&TxWaitQuery { status, timeout })
+        .send()
+        .await
+    }
+
+    pub async fn tx_statuses(&self, tx_hashes: Vec<TxHash>) -> Result<Response> {
+        self.post_with_scope(super::API_V02_SCOPE, "transactions/status/batch")
+            .body(&TxStatusesRequest { tx_hashes })
+            .send()
+            .await
+    }
+
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -46,6 +164,33 @@ impl Client {
         .await
     }
 
+    pub async fn tx_raw_data(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/raw", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn tx_proof(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/proof", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
+    pub async fn tx_l1_commit(&self, tx_hash: TxHash) -> Result<Response> {
+        self.get_with_scope(
+            super::API_V02_SCOPE,
+            &format!("transactions/{}/l1_commit", tx_hash.to_string()),
+        )
+        .send()
+        .await
+    }
+
     pub async fn get_batch(&self, batch_hash: TxHash) -> Result<Response> {
         self.get_with_scope(
             super::API_V02_SCOPE,
@@ -1,15 +1,20 @@
 use std::collections::{BTreeMap, HashMap};
 
+use chrono::{DateTime, Utc};
 use num::{BigUint, ToPrimitive};
 use serde::{Deserialize, Serialize};
 
 use zksync_types::{
+    tx::{ChangePubKeyType, EIP1271Signature, TxHash},
     AccountId, Address, BlockNumber, Nonce, PriorityOp, PubKeyHash, TokenId, ZkSyncPriorityOp,
+    H256,
 };
-use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
+use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper, ZeroPrefixHexSerde};
 
-use super::pagination::PaginationDirection;
+use super::block::StoredBlockInfo;
+use super::pagination::{AccountTxDirection, PaginationDirection};
 use super::token::NFT;
+use super::transaction::{L1Transaction, TxInBlockStatus};
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
 #[serde(rename_all = "camelCase")]
@@ -33,6 +38,91 @@ pub struct Account {
     pub account_type: Option<EthAccountType>,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonce {
+    pub committed: Nonce,
+    pub finalized: Nonce,
+    /// Nonce of the next transaction, taking into account the transactions in the mempool.
+    pub next: Nonce,
+}
+
+/// `ChangePubKey` transaction executed for the account.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PubKeyChange {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub block_number: BlockNumber,
+    pub new_pub_key_hash: PubKeyHash,
+    pub auth_type: ChangePubKeyType,
+    pub status: TxInBlockStatus,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Balances of the account in the last committed state and the ones
+/// with its queued mempool transactions applied on top of it.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPendingBalances {
+    pub committed: BTreeMap<String, BigUintSerdeWrapper>,
+    pub pending: BTreeMap<String, BigUintSerdeWrapper>,
+}
+
+/// Transaction of the account waiting in the mempool.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMempoolTx {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    pub tx_type: String,
+    pub nonce: Nonce,
+    /// Whether some nonces are missing between the previous queued transaction
+    /// (or the committed account nonce) and this one. Such a transaction can't
+    /// be executed until the missing ones are submitted.
+    pub nonce_gap: bool,
+}
+
+/// Transactions initiated by the account that are queued in the mempool,
+/// ordered by nonce.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountMempool {
+    pub committed_nonce: Nonce,
+    pub txs: Vec<AccountMempoolTx>,
+}
+
+/// Fees paid by the account in the successfully executed transactions.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountFees {
+    /// Total fees keyed by the token symbol.
+    pub total: BTreeMap<String, BigUintSerdeWrapper>,
+    /// Fees keyed by the transaction type and then by the token symbol.
+    pub by_tx_type: BTreeMap<String, BTreeMap<String, BigUintSerdeWrapper>>,
+}
+
+/// Registration status of the account in the last committed state.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRegistration {
+    /// `None` if no account id has been assigned to the address yet.
+    pub account_id: Option<AccountId>,
+    /// Block in which the account was created.
+    pub created_in_block: Option<BlockNumber>,
+    /// Whether the account has set its signing key via `ChangePubKey`.
+    pub signing_key_set: bool,
+}
+
+/// NFTs owned by the account in its committed and finalized states.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNfts {
+    pub committed: BTreeMap<TokenId, NFT>,
+    pub finalized: BTreeMap<TokenId, NFT>,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum AccountAddressOrId {
@@ -92,6 +182,36 @@ impl OngoingDeposit {
     }
 }
 
+/// Priority operation observed on L1 that isn't executed in a block yet.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingPriorityOp {
+    /// Operation data, including the token and the amount of deposits.
+    pub op: L1Transaction,
+    pub received_on_block: u64,
+    /// Ethereum block after which the operation is expected to be accepted by the server.
+    pub expected_accept_block: u64,
+    /// Ethereum block until which the operation must be executed.
+    pub deadline_block: u64,
+}
+
+impl PendingPriorityOp {
+    pub fn new(priority_op: PriorityOp, confirmations_for_eth_event: u64) -> Self {
+        let tx_hash = priority_op.tx_hash();
+        Self {
+            op: L1Transaction::from_pending_op(
+                priority_op.data,
+                priority_op.eth_hash,
+                priority_op.serial_id,
+                tx_hash,
+            ),
+            received_on_block: priority_op.eth_block,
+            expected_accept_block: priority_op.eth_block + confirmations_for_eth_event,
+            deadline_block: priority_op.deadline_block,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct DepositingFunds {
@@ -106,6 +226,28 @@ pub struct DepositingAccountBalances {
     pub balances: HashMap<String, DepositingFunds>,
 }
 
+/// Query of the account state as of the given block.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateQuery {
+    /// Block number, `lastCommitted` or `lastFinalized`.
+    pub block: String,
+}
+
+/// Maximum number of accounts that can be requested in a single `AccountsBatchRequest`.
+pub const MAX_ACCOUNTS_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsBatchRequest {
+    /// Account ids or addresses.
+    pub accounts: Vec<String>,
+}
+
+/// Committed states of the requested accounts keyed by the requested id or address,
+/// `None` for the unknown ones.
+pub type AccountsBatchResponse = HashMap<String, Option<Account>>;
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IncomingAccountTxsQuery {
@@ -113,5 +255,89 @@ pub struct IncomingAccountTxsQuery {
     pub limit: u32,
     pub direction: PaginationDirection,
     pub token: Option<String>,
+    /// Id or address of the counterparty: only the transactions
+    /// between the two accounts are returned.
+    #[serde(alias = "counterparty")]
     pub second_account: Option<String>,
+    pub tx_direction: Option<AccountTxDirection>,
+    pub tx_type: Option<String>,
+    pub from_time: Option<DateTime<Utc>>,
+    pub to_time: Option<DateTime<Utc>>,
+}
+
+/// Time period that points of the account balance history are aggregated by.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum BalanceHistoryResolution {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl Default for BalanceHistoryResolution {
+    fn default() -> Self {
+        Self::Day
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceHistoryQuery {
+    /// Token id or symbol.
+    pub token: String,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub resolution: Option<BalanceHistoryResolution>,
+}
+
+/// Balance of the account at the end of the time period.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalancePoint {
+    /// Start of the time period.
+    pub timestamp: DateTime<Utc>,
+    /// The last block of the period that changed the balance.
+    pub block_number: BlockNumber,
+    pub balance: BigUintSerdeWrapper,
+}
+
+/// Message and signature to be checked against the `isValidSignature` method
+/// of the account's smart contract wallet.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EIP1271SignatureCheckRequest {
+    /// Raw message, the Ethereum signed message prefix is added by the server.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub message: Vec<u8>,
+    pub signature: EIP1271Signature,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountExitProofQuery {
+    /// Token id, address or symbol.
+    pub token: String,
+}
+
+/// Arguments of the contract's `performExodus` method for the account balance in the last
+/// finalized state, except for the zkSNARK proof itself. Instead, it contains Merkle paths
+/// of the account and its balance that the exit circuit is built from.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountExitProof {
+    pub stored_block_info: StoredBlockInfo,
+    pub owner: Address,
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    pub amount: BigUintSerdeWrapper,
+    pub nft_creator_id: AccountId,
+    pub nft_creator_address: Address,
+    pub nft_serial_id: u32,
+    pub nft_content_hash: H256,
+    /// Sibling hashes on the path from the account leaf to the state root, leaf level first.
+    pub account_path: Vec<H256>,
+    /// Sibling hashes on the path from the balance leaf to the root of the account
+    /// balance tree, leaf level first.
+    pub balance_path: Vec<H256>,
 }
@@ -1,7 +1,24 @@
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use zksync_crypto::{serialization::FrSerde, Fr};
-use zksync_types::{BlockNumber, H256};
+use zksync_crypto::{proof::EncodedAggregatedProof, serialization::FrSerde, Fr};
+use zksync_types::{
+    aggregated_operations::AggregatedActionType, tx::TxHash, AccountId, Address, BlockNumber,
+    Nonce, PubKeyHash, TokenId, H256,
+};
+use zksync_utils::BigUintSerdeWrapper;
+
+use super::transaction::Transaction;
+
+/// Maximum number of blocks the statistics can be requested for at once.
+pub const MAX_BLOCKS_STATS_RANGE: u32 = 1000;
+
+/// Maximum number of blocks that can be exported at once.
+pub const MAX_BLOCKS_EXPORT_RANGE: u32 = 10_000;
+
+/// Maximum number of finalized blocks returned at once.
+pub const MAX_FINALIZED_BLOCKS: u32 = 1000;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
@@ -17,9 +34,183 @@ pub struct BlockInfo {
     #[serde(with = "FrSerde")]
     pub new_state_root: Fr,
     pub block_size: u64,
+    /// Number of the transactions and priority operations executed in the block.
+    pub tx_count: u64,
     pub commit_tx_hash: Option<H256>,
     pub verify_tx_hash: Option<H256>,
     pub committed_at: DateTime<Utc>,
     pub finalized_at: Option<DateTime<Utc>>,
     pub status: BlockStatus,
+    /// Confirmed Ethereum transactions that committed, proved and executed the block.
+    pub l1_transactions: Vec<BlockL1Transaction>,
+}
+
+/// Ethereum transaction of the aggregated operation covering the block.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockL1Transaction {
+    pub action_type: AggregatedActionType,
+    pub tx_hash: H256,
+    /// Gas used by the whole transaction, which may cover several blocks.
+    /// `None` for the transactions confirmed before the gas usage was recorded.
+    pub gas_used: Option<u64>,
+}
+
+/// Block data as it is stored by the contract in the `StoredBlockInfo` structure.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlockInfo {
+    pub block_number: BlockNumber,
+    pub priority_operations: u64,
+    pub pending_onchain_operations_hash: H256,
+    pub timestamp: u64,
+    pub state_hash: H256,
+    pub commitment: H256,
+}
+
+/// Data required to re-verify the block proof against the verifier contract.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockProofInfo {
+    pub block_number: BlockNumber,
+    pub commitment: H256,
+    /// Public input of the block circuit, which is the commitment with the three
+    /// most significant bits cleared.
+    #[serde(with = "FrSerde")]
+    pub public_input: Fr,
+    /// `None` until the aggregated proof covering the block is created.
+    pub aggregated_proof: Option<AggregatedProofInfo>,
+}
+
+/// Aggregated proof covering a range of blocks.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregatedProofInfo {
+    pub first_block: BlockNumber,
+    pub last_block: BlockNumber,
+    /// Proof encoded the way it is passed to the `proveBlocks` method of the contract.
+    pub proof: EncodedAggregatedProof,
+    /// Confirmed Ethereum transaction that published the proof.
+    pub prove_tx_hash: Option<H256>,
+}
+
+/// Statistics of the transactions executed in a range of blocks.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlocksStats {
+    pub from_block: BlockNumber,
+    pub to_block: BlockNumber,
+    /// Number of the transactions and priority operations keyed by their type.
+    pub tx_counts: BTreeMap<String, u64>,
+    /// Fees collected in the successful transactions keyed by the token symbol.
+    pub fees: BTreeMap<String, BigUintSerdeWrapper>,
+    /// Number of the unique addresses participating in the transactions.
+    pub active_accounts: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct BlocksStatsQuery {
+    /// Block number or `lastCommitted`/`lastFinalized`.
+    pub from: String,
+    /// Block number or `lastCommitted`/`lastFinalized`.
+    pub to: String,
+}
+
+/// Block that is being formed by the state keeper and is not committed yet.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingBlockInfo {
+    pub block_number: BlockNumber,
+    /// Number of chunks occupied by the executed operations.
+    pub chunks_used: u64,
+    /// Maximum number of chunks the block can contain.
+    pub max_chunks: u64,
+    pub timestamp: u64,
+    /// Successful operations in the order of their execution followed by the failed transactions.
+    pub transactions: Vec<PendingBlockTx>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingBlockTx {
+    pub tx_hash: TxHash,
+    pub tx_type: String,
+    pub block_index: Option<u32>,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct FinalizedBlocksQuery {
+    /// Only the blocks with greater numbers are returned.
+    pub since_block: BlockNumber,
+}
+
+/// Blocks finalized after the requested one.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalizedBlocks {
+    /// At most `MAX_FINALIZED_BLOCKS` blocks in the ascending order.
+    pub blocks: Vec<BlockInfo>,
+    /// Number of the last finalized block, the request should be repeated
+    /// until the last returned block reaches it.
+    pub last_finalized: BlockNumber,
+}
+
+/// Changes of the accounts state made by the block.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateDiff {
+    pub block_number: BlockNumber,
+    /// Accounts touched by the block in the ascending order of their ids.
+    pub accounts: Vec<AccountStateDiff>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateDiff {
+    pub account_id: AccountId,
+    pub address: Option<Address>,
+    /// Whether the account was created in the block.
+    pub created: bool,
+    pub old_nonce: Nonce,
+    pub new_nonce: Nonce,
+    /// `None` if the public key hash was not changed in the block.
+    pub pub_key_hash: Option<PubKeyHashChange>,
+    /// Balances of the tokens changed in the block.
+    pub balances: BTreeMap<TokenId, BalanceChange>,
+    /// NFTs minted by the account in the block.
+    pub minted_nfts: Vec<TokenId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PubKeyHashChange {
+    pub old: PubKeyHash,
+    pub new: PubKeyHash,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub old: BigUintSerdeWrapper,
+    pub new: BigUintSerdeWrapper,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct BlocksExportQuery {
+    /// Block number or `lastCommitted`/`lastFinalized`.
+    pub from: String,
+    /// Block number or `lastCommitted`/`lastFinalized`.
+    pub to: String,
+}
+
+/// Block along with its transactions, a single line of the blocks export.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportedBlock {
+    #[serde(flatten)]
+    pub block: BlockInfo,
+    /// Transactions and priority operations in the order of their execution.
+    pub transactions: Vec<Transaction>,
 }
@@ -1,7 +1,13 @@
-use num::BigUint;
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
-use zksync_types::{tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenLike, TxFeeTypes};
-use zksync_utils::BigUintSerdeAsRadix10Str;
+use zksync_types::{
+    tokens::ChangePubKeyFeeTypeArg, Address, BatchFee, Fee, TokenId, TokenLike, TxFeeTypes,
+};
+use zksync_utils::{BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
+
+/// Maximum number of quoted fees returned by the fee history endpoint.
+pub const MAX_FEE_HISTORY_ENTRIES: u32 = 1000;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -12,24 +18,26 @@ pub struct ApiFee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Time of the oldest price update among the prices the fee was calculated from.
+    pub price_updated_at: DateTime<Utc>,
 }
 
-impl From<Fee> for ApiFee {
-    fn from(fee: Fee) -> Self {
+impl ApiFee {
+    pub fn from_fee(fee: Fee, price_updated_at: DateTime<Utc>) -> Self {
         ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            price_updated_at,
         }
     }
-}
 
-impl From<BatchFee> for ApiFee {
-    fn from(fee: BatchFee) -> Self {
+    pub fn from_batch_fee(fee: BatchFee, price_updated_at: DateTime<Utc>) -> Self {
         ApiFee {
             gas_fee: fee.gas_fee,
             zkp_fee: fee.zkp_fee,
             total_fee: fee.total_fee,
+            price_updated_at,
         }
     }
 }
@@ -92,3 +100,32 @@ pub struct BatchFeeRequest {
     pub transactions: Vec<TxInBatchFeeRequest>,
     pub token_like: TokenLike,
 }
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeeHistoryQuery {
+    /// Name of the fee type, e.g. `Transfer`, `TransferToNew` or `ChangePubKey`.
+    pub tx_type: Option<String>,
+    /// Token ID, address or symbol.
+    pub token: Option<String>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// Fee quoted by the server along with the prices it was calculated from.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryEntry {
+    pub tx_type: String,
+    pub token_id: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_price_wei: BigUint,
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub token_price_usd: Ratio<BigUint>,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub gas_fee: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub zkp_fee: BigUint,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub total_fee: BigUint,
+    pub quoted_at: DateTime<Utc>,
+}
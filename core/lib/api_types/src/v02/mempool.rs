@@ -0,0 +1,102 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use zksync_types::{
+    tx::{EthSignData, TxEthSignature, TxHash},
+    AccountId, Nonce, ZkSyncTx,
+};
+
+/// Maximum number of the mempool transactions returned in one request.
+pub const MAX_MEMPOOL_TXS_LIMIT: u32 = 100;
+
+/// Version of the mempool snapshot format, bumped on the incompatible changes.
+pub const MEMPOOL_SNAPSHOT_VERSION: u32 = 1;
+
+/// Summary of the transactions waiting in the mempool.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolStats {
+    pub size: u32,
+    /// Number of the transactions that are parts of the batches.
+    pub batched_txs: u32,
+    pub oldest_tx_created_at: Option<DateTime<Utc>>,
+    pub oldest_tx_age_sec: Option<u64>,
+    /// Number of the transactions of every type present in the mempool, e.g. `Transfer`.
+    pub txs_by_type: BTreeMap<String, u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTxsQuery {
+    pub tx_type: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// Transaction waiting in the mempool.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTxInfo {
+    pub tx_hash: TxHash,
+    pub tx_type: String,
+    /// ID of the batch the transaction belongs to, `None` for the single transactions.
+    pub batch_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    /// Transaction was returned to the mempool from the reverted block.
+    pub reverted: bool,
+    pub tx: ZkSyncTx,
+}
+
+/// Full contents of the mempool exported for the debugging.
+/// Transactions are listed in the order they were added to the mempool and the maps are sorted,
+/// so the snapshots of the same mempool state are identical.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolSnapshot {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// Nonces of the accounts sending the transactions in the last committed state,
+    /// accounts that are not committed yet are omitted.
+    pub committed_nonces: BTreeMap<AccountId, Nonce>,
+    pub txs: Vec<MempoolSnapshotTx>,
+    /// Ethereum signatures of the batches by the batch ID.
+    pub batch_signatures: BTreeMap<i64, Vec<TxEthSignature>>,
+}
+
+/// Transaction in the mempool snapshot along with its signatures.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolSnapshotTx {
+    pub tx_hash: TxHash,
+    pub tx_type: String,
+    /// ID of the batch the transaction belongs to, `None` for the single transactions.
+    pub batch_id: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    /// Transaction was returned to the mempool from the reverted block.
+    pub reverted: bool,
+    pub tx: ZkSyncTx,
+    pub eth_sign_data: Option<EthSignData>,
+    /// Reasons the transaction can't be included into the next block, empty if it is ready.
+    pub held_reasons: Vec<MempoolHoldReason>,
+}
+
+/// Reason the transaction is held in the mempool.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+pub enum MempoolHoldReason {
+    /// Account of the transaction is not committed yet.
+    #[serde(rename_all = "camelCase")]
+    UnknownAccount { account_id: AccountId },
+    /// Transactions with the preceding nonces are missing from the mempool.
+    #[serde(rename_all = "camelCase")]
+    NonceGap { expected_nonce: Nonce },
+    /// Nonce is already used by the committed transaction.
+    #[serde(rename_all = "camelCase")]
+    NonceTooLow { committed_nonce: Nonce },
+    /// Validity period of the transaction hasn't started yet.
+    #[serde(rename_all = "camelCase")]
+    NotValidYet { valid_from: u64 },
+    /// Validity period of the transaction is over, it will be evicted.
+    #[serde(rename_all = "camelCase")]
+    ValidityEnded { valid_until: u64 },
+}
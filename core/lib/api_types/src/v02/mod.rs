@@ -2,15 +2,17 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
-use zksync_types::network::Network;
+use zksync_types::{network::Network, Address};
 
 pub mod account;
 pub mod block;
 pub mod fee;
+pub mod mempool;
 pub mod pagination;
 pub mod status;
 pub mod token;
 pub mod transaction;
+pub mod ws;
 
 #[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -39,6 +41,9 @@ pub struct Request {
     pub resource: String,
     pub args: HashMap<String, String>,
     pub timestamp: DateTime<Utc>,
+    /// Address the ENS name from the request was resolved to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_address: Option<Address>,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use either::Either;
 use serde::{Deserialize, Serialize, Serializer};
 use std::str::FromStr;
@@ -132,10 +133,43 @@ pub struct PendingOpsRequest {
     pub serial_id: ApiEither<SerialId>,
 }
 
+/// Direction of a transaction relative to the account whose history is requested.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum AccountTxDirection {
+    /// Transactions received by the account.
+    Incoming,
+    /// Transactions sent by the account.
+    Outgoing,
+}
+
+/// Optional filters of the account transactions history.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTxsFilters {
+    pub tx_direction: Option<AccountTxDirection>,
+    /// Type of the operation, e.g. `Transfer` or `Deposit`.
+    pub tx_type: Option<String>,
+    /// Lower bound (inclusive) of the transaction creation time.
+    pub from_time: Option<DateTime<Utc>>,
+    /// Upper bound (inclusive) of the transaction creation time.
+    pub to_time: Option<DateTime<Utc>>,
+}
+
+impl AccountTxsFilters {
+    pub fn is_empty(&self) -> bool {
+        self.tx_direction.is_none()
+            && self.tx_type.is_none()
+            && self.from_time.is_none()
+            && self.to_time.is_none()
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct AccountTxsRequest {
     pub address: Address,
     pub tx_hash: ApiEither<TxHash>,
     pub token: Option<TokenId>,
     pub second_address: Option<Address>,
+    pub filters: AccountTxsFilters,
 }
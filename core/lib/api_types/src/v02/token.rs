@@ -1,6 +1,14 @@
 use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
 use zksync_types::{AccountId, Address, Token, TokenId, H256};
+use zksync_utils::UnsignedRatioSerializeAsDecimal;
+
+/// Max number of the fee token policy decisions returned by the API.
+pub const MAX_FEE_TOKEN_DECISIONS: u32 = 100;
+/// Max number of the token prices returned by the price history endpoint.
+pub const MAX_TOKEN_PRICE_HISTORY_ENTRIES: u32 = 1000;
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -22,6 +30,102 @@ pub struct TokenPrice {
     pub price: BigDecimal,
 }
 
+/// Origin of the USD price of the token.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceSourceInfo {
+    pub token_id: TokenId,
+    /// Name of the price source, `None` if it's unknown.
+    pub source: Option<String>,
+    /// Time the price was fetched from the source.
+    pub last_updated: DateTime<Utc>,
+}
+
+/// Token price along with the USD prices it's derived from.
+/// Price in another token is derived from the USD prices of both tokens.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcedTokenPrice {
+    #[serde(flatten)]
+    pub price: TokenPrice,
+    pub sources: Vec<TokenPriceSourceInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CompatibilityCheckStatus {
+    Passed,
+    /// The token deviates from the standard, but it's supported by zkSync.
+    Warning,
+    Failed,
+    /// The check can't be performed, e.g. the simulated call reverted.
+    Inconclusive,
+}
+
+/// Result of the single ERC-20 compatibility check.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCompatibilityCheck {
+    pub name: String,
+    pub status: CompatibilityCheckStatus,
+    pub details: Option<String>,
+}
+
+/// Results of the sanity checks run against the ERC-20 contract before the token is listed.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenCompatibilityReport {
+    pub address: Address,
+    /// `false` if any of the checks has failed, such tokens are not listed.
+    pub compatible: bool,
+    pub checks: Vec<TokenCompatibilityCheck>,
+}
+
+impl TokenCompatibilityReport {
+    pub fn new(address: Address, checks: Vec<TokenCompatibilityCheck>) -> Self {
+        let compatible = checks
+            .iter()
+            .all(|check| check.status != CompatibilityCheckStatus::Failed);
+        Self {
+            address,
+            compatible,
+            checks,
+        }
+    }
+}
+
+/// Request to list the token that was added to the governance contract.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct TokenListingRequest {
+    pub address: Address,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct TokenPriceHistoryQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// Token price fetched by the fee ticker.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenPriceHistoryEntry {
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub usd_price: Ratio<BigUint>,
+    pub last_updated: DateTime<Utc>,
+}
+
+/// Decision of the fee token policy on whether the token is accepted for paying fees.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiFeeTokenDecision {
+    pub token_id: TokenId,
+    pub is_fee_token: bool,
+    /// Market volume of the token in USD the decision was based on.
+    pub market_volume: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
+
 impl ApiToken {
     pub fn from_token_and_eligibility(token: Token, eligibility: bool) -> Self {
         ApiToken {
@@ -73,3 +177,16 @@ pub struct ApiNFT {
     pub current_factory: Address,
     pub withdrawn_factory: Option<Address>,
 }
+
+/// JSON metadata of the NFT stored in IPFS under its content hash.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiNFTMetadata {
+    pub id: TokenId,
+    pub content_hash: H256,
+    pub creator_id: AccountId,
+    pub creator_address: Address,
+    pub serial_id: u32,
+    pub metadata: serde_json::Value,
+    pub fetched_at: DateTime<Utc>,
+}
@@ -1,17 +1,22 @@
-use crate::{v02::block::BlockStatus, TxWithSignature};
+use std::collections::HashMap;
+
+use crate::{
+    v02::{block::BlockStatus, token::ApiNFT},
+    TxWithSignature,
+};
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use zksync_types::{
     tx::{
-        ChangePubKey, Close, EthBatchSignatures, ForcedExit, MintNFT, Swap, Transfer,
-        TxEthSignature, TxHash, Withdraw, WithdrawNFT,
+        ChangePubKey, Close, EthBatchSignatures, ForcedExit, MintNFT, Order, Swap, TimeRange,
+        Transfer, TxEthSignature, TxHash, Withdraw, WithdrawNFT,
     },
-    AccountId, Address, BlockNumber, EthBlockId, PubKeyHash, SerialId, TokenId, ZkSyncOp,
-    ZkSyncPriorityOp, H256,
+    AccountId, Address, BlockNumber, EthBlockId, Nonce, PubKeyHash, SerialId, TokenId, ZkSyncOp,
+    ZkSyncPriorityOp, ZkSyncTx, H256,
 };
-use zksync_utils::{BigUintSerdeAsRadix10Str, ZeroPrefixHexSerde};
+use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper, ZeroPrefixHexSerde};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -23,10 +28,46 @@ pub struct IncomingTxBatch {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
 #[serde(rename_all = "camelCase")]
 pub enum TxInBlockStatus {
+    /// Transaction was accepted without the verification, which is still in progress.
+    PendingVerification,
     Queued,
     Committed,
     Finalized,
     Rejected,
+    /// Transaction was evicted from the mempool without being executed.
+    Expired,
+    /// Transaction was accepted without the verification and failed it afterwards,
+    /// so it never reached the mempool.
+    RejectedPreBlock,
+}
+
+impl TxInBlockStatus {
+    /// Checks whether a transaction with this status has reached the `target` one,
+    /// or will never reach it because it was rejected or expired.
+    pub fn has_reached(self, target: TxInBlockStatus) -> bool {
+        match self {
+            TxInBlockStatus::Rejected
+            | TxInBlockStatus::Expired
+            | TxInBlockStatus::RejectedPreBlock => true,
+            TxInBlockStatus::Finalized => !matches!(
+                target,
+                TxInBlockStatus::Rejected
+                    | TxInBlockStatus::Expired
+                    | TxInBlockStatus::RejectedPreBlock
+            ),
+            TxInBlockStatus::Committed => matches!(
+                target,
+                TxInBlockStatus::PendingVerification
+                    | TxInBlockStatus::Queued
+                    | TxInBlockStatus::Committed
+            ),
+            TxInBlockStatus::Queued => matches!(
+                target,
+                TxInBlockStatus::PendingVerification | TxInBlockStatus::Queued
+            ),
+            TxInBlockStatus::PendingVerification => target == TxInBlockStatus::PendingVerification,
+        }
+    }
 }
 
 impl From<BlockStatus> for TxInBlockStatus {
@@ -43,34 +84,311 @@ impl From<BlockStatus> for TxInBlockStatus {
 pub struct TxData {
     pub tx: Transaction,
     pub eth_signature: Option<String>,
+    /// Message the user signed to produce `eth_signature`.
+    /// Returned as plain text, or as a `0x`-prefixed hex string if it is not valid UTF-8.
+    pub eth_sign_payload: Option<String>,
+    /// Context of the batch, set only if the transaction was submitted as a part of it.
+    pub batch: Option<TxBatchContext>,
+    /// NFT created by the `MintNFT` or withdrawn by the `WithdrawNFT` transaction.
+    /// Set only once the NFT exists in the committed state.
+    pub nft: Option<ApiNFT>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+/// L2 transaction in the form it was signed and submitted by the user.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxRawData {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    /// Canonical serialized transaction bytes.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub tx_bytes: Vec<u8>,
+    /// Packed Ethereum signature of the transaction, if it was required.
+    pub eth_signature: Option<TxEthSignature>,
+}
+
+/// L2 transaction to be decoded, either in the JSON form or as the canonical bytes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum TxToDecode {
+    Bytes(TxBytes),
+    Tx(ZkSyncTx),
+}
+
+/// Canonical serialized transaction bytes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxBytes {
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub tx_bytes: Vec<u8>,
+}
+
+/// L2 transaction decoded by the server.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedTx {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub tx_hash: TxHash,
+    /// Canonical serialized transaction bytes.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub tx_bytes: Vec<u8>,
+    pub tx: ZkSyncTx,
+    /// Message the user has to sign with the Ethereum key, if the transaction requires it.
+    pub eth_sign_message: Option<String>,
+    /// Whether the transaction is well-formed and signed with the zkSync key.
+    /// Transactions decoded from the bytes are never signed.
+    pub valid: bool,
+    /// Reason the transaction is not valid.
+    pub error: Option<String>,
+}
+
+/// Result of the transaction signatures check.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSignatureCheck {
+    /// Whether the zkSync signature is correct and made with the current signing key of the account.
+    /// `None` if the check was not performed because the Ethereum authorization of `ChangePubKey`
+    /// is invalid, it's checked first.
+    pub zksync_signature_valid: Option<bool>,
+    /// Whether the Ethereum signature (or the Ethereum authorization of `ChangePubKey`) is correct
+    /// or not required for the transaction. `None` if the check was not performed because the zkSync
+    /// signature is invalid.
+    pub eth_signature_valid: Option<bool>,
+    /// Description of the failed check, if any.
+    pub error: Option<String>,
+}
+
+/// Data required to prove that the operation was included into the block committed on L1.
+///
+/// The block commitment is computed by the smart contract from the block header fields and
+/// the block public data, so the operation is proven to be included if its public data is
+/// found at `public_data_offset` in the block public data and the commitment recomputed from
+/// the fields below matches the one stored on L1.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxInclusionProof {
+    pub block_number: BlockNumber,
+    pub block_index: u32,
+    pub fee_account: AccountId,
+    /// State root hash before the block, encoded for the smart contract.
+    pub previous_root_hash: H256,
+    /// State root hash after the block, encoded for the smart contract.
+    pub new_root_hash: H256,
+    pub timestamp: u64,
+    /// Public data of the whole block, padded with noops.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub public_data: Vec<u8>,
+    /// Bitmask of chunks that contain onchain operations.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub onchain_op_commitment: Vec<u8>,
+    /// Offset of the operation public data within the block public data.
+    pub public_data_offset: u32,
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub op_public_data: Vec<u8>,
+    pub block_commitment: H256,
+}
+
+/// Location of the operation public data within the Ethereum transaction
+/// that committed the block.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TxL1Commit {
+    pub commit_tx_hash: H256,
+    pub block_number: BlockNumber,
+    pub block_index: u32,
+    /// Offset of the operation public data within the calldata of the commit transaction.
+    pub calldata_offset: u32,
+    /// Length of the operation public data.
+    pub public_data_length: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxBatchContext {
+    pub batch_id: u32,
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub batch_hash: TxHash,
+    /// Hashes of all the batch transactions in the order they were submitted.
+    pub transaction_hashes: Vec<TxHashSerializeWrapper>,
+    /// Ethereum signatures for the whole batch.
+    pub eth_signatures: Vec<TxEthSignature>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct L1Receipt {
     pub status: TxInBlockStatus,
     pub eth_block: EthBlockId,
     pub rollup_block: Option<BlockNumber>,
+    /// Index of the operation within the rollup block.
+    pub block_index: Option<u32>,
     pub id: SerialId,
+    /// Time the operation was received by the server.
+    pub created_at: Option<DateTime<Utc>>,
+    /// Time the block with the operation was committed.
+    pub committed_at: Option<DateTime<Utc>>,
+    /// Time the block with the operation was finalized.
+    pub finalized_at: Option<DateTime<Utc>>,
+    /// Expected time of the block finalization, set only for committed operations.
+    pub estimated_finalization: Option<DateTime<Utc>>,
+    /// Number of Ethereum blocks mined on top of `eth_block`, according to the
+    /// latest block seen by the Ethereum watcher.
+    pub confirmations: Option<u64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct L2Receipt {
     #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
     pub tx_hash: TxHash,
     pub rollup_block: Option<BlockNumber>,
+    /// Index of the transaction within the rollup block.
+    pub block_index: Option<u32>,
     pub status: TxInBlockStatus,
     pub fail_reason: Option<String>,
+    /// Fee charged for the executed transaction.
+    pub fee: Option<BigUintSerdeWrapper>,
+    pub fee_token: Option<TokenId>,
+    /// Time the operation was received by the server.
+    pub created_at: Option<DateTime<Utc>>,
+    /// Time the block with the operation was committed.
+    pub committed_at: Option<DateTime<Utc>>,
+    /// Time the block with the operation was finalized.
+    pub finalized_at: Option<DateTime<Utc>>,
+    /// Expected time of the block finalization, set only for committed transactions.
+    pub estimated_finalization: Option<DateTime<Utc>>,
+    /// Parties of the swap, set only for the swaps included in a block.
+    pub swap: Option<SwapReceipt>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapReceipt {
+    pub submitter_id: AccountId,
+    pub submitter_nonce: Nonce,
+    pub orders: (SwapOrderReceipt, SwapOrderReceipt),
+}
+
+impl From<&Swap> for SwapReceipt {
+    fn from(swap: &Swap) -> Self {
+        Self {
+            submitter_id: swap.submitter_id,
+            submitter_nonce: swap.nonce,
+            orders: (
+                SwapOrderReceipt::from(&swap.orders.0),
+                SwapOrderReceipt::from(&swap.orders.1),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapOrderReceipt {
+    #[serde(serialize_with = "ZeroPrefixHexSerde::serialize")]
+    pub order_hash: TxHash,
+    pub account_id: AccountId,
+    pub nonce: Nonce,
+    /// Hash of the public key the order is signed with.
+    pub signer: PubKeyHash,
+    /// Packed zkSync signature of the order.
+    #[serde(with = "ZeroPrefixHexSerde")]
+    pub signature: Vec<u8>,
+}
+
+impl From<&Order> for SwapOrderReceipt {
+    fn from(order: &Order) -> Self {
+        Self {
+            order_hash: order.hash(),
+            account_id: order.account_id,
+            nonce: order.nonce,
+            signer: PubKeyHash::from_pubkey(&order.signature.pub_key.0),
+            signature: order
+                .signature
+                .signature
+                .serialize_packed()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(untagged)]
 pub enum Receipt {
     L1(L1Receipt),
     L2(L2Receipt),
 }
 
+impl Receipt {
+    pub fn status(&self) -> TxInBlockStatus {
+        match self {
+            Receipt::L1(receipt) => receipt.status,
+            Receipt::L2(receipt) => receipt.status,
+        }
+    }
+
+    pub fn rollup_block(&self) -> Option<BlockNumber> {
+        match self {
+            Receipt::L1(receipt) => receipt.rollup_block,
+            Receipt::L2(receipt) => receipt.rollup_block,
+        }
+    }
+
+    pub fn block_index(&self) -> Option<u32> {
+        match self {
+            Receipt::L1(receipt) => receipt.block_index,
+            Receipt::L2(receipt) => receipt.block_index,
+        }
+    }
+
+    pub fn set_block_timestamps(
+        &mut self,
+        committed_at: Option<DateTime<Utc>>,
+        finalized_at: Option<DateTime<Utc>>,
+    ) {
+        match self {
+            Receipt::L1(receipt) => {
+                receipt.committed_at = committed_at;
+                receipt.finalized_at = finalized_at;
+            }
+            Receipt::L2(receipt) => {
+                receipt.committed_at = committed_at;
+                receipt.finalized_at = finalized_at;
+            }
+        }
+    }
+
+    pub fn set_estimated_finalization(&mut self, time: Option<DateTime<Utc>>) {
+        match self {
+            Receipt::L1(receipt) => receipt.estimated_finalization = time,
+            Receipt::L2(receipt) => receipt.estimated_finalization = time,
+        }
+    }
+
+    /// Sets the number of confirmations of the Ethereum transaction that created
+    /// the priority operation. Does nothing for L2 receipts.
+    pub fn set_confirmations(&mut self, last_eth_block: u64) {
+        if let Receipt::L1(receipt) = self {
+            receipt.confirmations = Some(last_eth_block.saturating_sub(receipt.eth_block.0));
+        }
+    }
+}
+
+/// Header with the client-provided ID of the transaction submission request.
+/// Submissions with the same request ID are only processed once.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Maximum time in seconds the server waits for a transaction to reach the requested status.
+pub const MAX_TX_WAIT_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct TxWaitQuery {
+    pub status: TxInBlockStatus,
+    /// Timeout in seconds, `MAX_TX_WAIT_TIMEOUT_SECS` is used if not specified.
+    pub timeout: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Transaction {
@@ -83,6 +401,8 @@ pub struct Transaction {
     pub fail_reason: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
     pub batch_id: Option<u32>,
+    /// Time window the transaction can be executed within, not set for the priority operations.
+    pub time_range: Option<TimeRange>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,6 +412,15 @@ pub enum TransactionData {
     L2(L2Transaction),
 }
 
+impl TransactionData {
+    pub fn time_range(&self) -> Option<TimeRange> {
+        match self {
+            TransactionData::L1(_) => None,
+            TransactionData::L2(tx) => tx.time_range(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum L2Transaction {
@@ -102,10 +431,26 @@ pub enum L2Transaction {
     ChangePubKey(Box<ChangePubKey>),
     ForcedExit(Box<ForcedExitData>),
     MintNFT(Box<MintNFT>),
-    Swap(Box<Swap>),
+    Swap(Box<SwapData>),
     WithdrawNFT(Box<WithdrawNFTData>),
 }
 
+impl L2Transaction {
+    /// Returns the time range of the transaction, `MintNFT` can be executed at any time.
+    pub fn time_range(&self) -> Option<TimeRange> {
+        match self {
+            L2Transaction::Transfer(tx) => Some(tx.time_range.unwrap_or_default()),
+            L2Transaction::Withdraw(tx) => Some(tx.tx.time_range.unwrap_or_default()),
+            L2Transaction::Close(tx) => Some(tx.time_range),
+            L2Transaction::ChangePubKey(tx) => Some(tx.time_range.unwrap_or_default()),
+            L2Transaction::ForcedExit(tx) => Some(tx.tx.time_range.unwrap_or_default()),
+            L2Transaction::MintNFT(_) => None,
+            L2Transaction::Swap(tx) => Some(tx.tx.time_range()),
+            L2Transaction::WithdrawNFT(tx) => Some(tx.tx.time_range),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ForcedExitData {
@@ -122,6 +467,15 @@ pub struct WithdrawData {
     pub eth_tx_hash: Option<H256>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapData {
+    #[serde(flatten)]
+    pub tx: Swap,
+    /// Hashes of both orders, the receipt of the swap can be requested by either of them.
+    pub order_hashes: (TxHashSerializeWrapper, TxHashSerializeWrapper),
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WithdrawNFTData {
@@ -250,6 +604,18 @@ pub struct BatchStatus {
     pub last_state: TxInBlockStatus,
 }
 
+/// Maximum number of transaction hashes that can be requested in a single `TxStatusesRequest`.
+pub const MAX_TX_STATUSES_BATCH_SIZE: usize = 100;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TxStatusesRequest {
+    pub tx_hashes: Vec<TxHash>,
+}
+
+/// Receipts of the requested transactions, `None` for the unknown ones.
+pub type TxStatusesResponse = HashMap<TxHashSerializeWrapper, Option<Receipt>>;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Toggle2FA {
@@ -296,3 +662,38 @@ impl Toggle2FA {
 pub struct Toggle2FAResponse {
     pub success: bool,
 }
+
+/// Request to remove a queued transaction from the mempool.
+/// Must be signed by the Ethereum key of the transaction sender.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTx {
+    #[serde(with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    pub signature: TxEthSignature,
+}
+
+impl CancelTx {
+    pub fn get_ethereum_sign_message(&self, tx_hash: TxHash) -> String {
+        format!(
+            "Cancel zkSync transaction {}\n\
+            Timestamp: {}",
+            tx_hash.to_string(),
+            self.timestamp.timestamp_millis()
+        )
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelTxResponse {
+    pub success: bool,
+}
+
+/// Result of the transaction submission.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmitTxResponse {
+    pub tx_hash: TxHashSerializeWrapper,
+    /// Queued transaction of the same account with the same nonce replaced by the submitted one.
+    pub replaced_tx_hash: Option<TxHashSerializeWrapper>,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+use crate::v02::transaction::{Receipt, TxHashSerializeWrapper, TxInBlockStatus};
+
+/// Subject of the `api/v0.2/ws` subscription.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WsTopic {
+    #[serde(rename_all = "camelCase")]
+    TxStatus { tx_hash: TxHashSerializeWrapper },
+}
+
+/// Message sent by the client over the `api/v0.2/ws` connection.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum WsRequest {
+    Subscribe { topic: WsTopic },
+    Unsubscribe { topic: WsTopic },
+}
+
+/// Message pushed by the server to the subscribed clients.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WsNotification {
+    #[serde(rename_all = "camelCase")]
+    TxStatus {
+        tx_hash: TxHashSerializeWrapper,
+        receipt: Receipt,
+    },
+}
+
+impl WsNotification {
+    /// Returns the topic this notification is sent for.
+    pub fn topic(&self) -> WsTopic {
+        match self {
+            WsNotification::TxStatus { tx_hash, .. } => WsTopic::TxStatus {
+                tx_hash: tx_hash.clone(),
+            },
+        }
+    }
+
+    /// Checks whether no more notifications will be sent for the topic.
+    pub fn is_final(&self) -> bool {
+        match self {
+            WsNotification::TxStatus { receipt, .. } => matches!(
+                receipt.status(),
+                TxInBlockStatus::Finalized
+                    | TxInBlockStatus::Rejected
+                    | TxInBlockStatus::Expired
+                    | TxInBlockStatus::RejectedPreBlock
+            ),
+        }
+    }
+}
@@ -2,11 +2,11 @@ use num::{rational::Ratio, BigUint};
 /// External uses
 use serde::Deserialize;
 /// Built-in uses
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::time::Duration;
 use zksync_utils::scaled_u64_to_ratio;
 // Workspace uses
-use zksync_types::AccountId;
+use zksync_types::{AccountId, Address};
 // Local uses
 use crate::envy_load;
 
@@ -35,7 +35,7 @@ pub struct ApiConfig {
 impl ApiConfig {
     pub fn from_env() -> Self {
         Self {
-            common: envy_load!("common", "API_COMMON_"),
+            common: CommonApiConfig::from_env(),
             admin: envy_load!("admin", "API_ADMIN_"),
             rest: envy_load!("rest", "API_REST_"),
             json_rpc: envy_load!("json_rpc", "API_JSON_RPC_"),
@@ -53,8 +53,35 @@ impl CommonApiConfig {
         scaled_u64_to_ratio(self.max_subsidy_usd_scaled)
     }
 
+    pub fn mempool_full_retry_after(&self) -> Duration {
+        Duration::from_secs(self.mempool_full_retry_after_sec)
+    }
+
+    pub fn settings_reload_interval(&self) -> Duration {
+        Duration::from_secs(self.settings_reload_interval_sec)
+    }
+
     pub fn from_env() -> Self {
-        envy_load!("common", "API_COMMON_")
+        let config: Self = envy_load!("common", "API_COMMON_");
+        config.validate();
+        config
+    }
+
+    /// Checks the options the API server can't start with, so the misconfiguration is reported
+    /// when the config is loaded rather than by the component using it.
+    fn validate(&self) {
+        assert!(
+            self.signature_verifier_threads > 0,
+            "Cannot load config <common>: signature_verifier_threads should be positive"
+        );
+        assert!(
+            self.signature_verifier_queue_size > 0,
+            "Cannot load config <common>: signature_verifier_queue_size should be positive"
+        );
+        assert!(
+            self.settings_reload_interval_sec > 0,
+            "Cannot load config <common>: settings_reload_interval_sec should be positive"
+        );
     }
 }
 
@@ -122,12 +149,76 @@ pub struct CommonApiConfig {
 
     /// The name of current subsidy. It is needed to conveniently fetch historical data regarding subsidies for different partners
     pub subsidy_name: String,
+
+    /// Addresses of the accounts which transactions are subsidized by the current subsidy.
+    #[serde(default)]
+    pub subsidized_accounts: Vec<Address>,
+    /// Names of the fee types (e.g. `Transfer` or `Withdraw`) subsidized by the current subsidy.
+    #[serde(default)]
+    pub subsidized_tx_types: Vec<String>,
+    /// Part of the fee (in percent) covered by the current subsidy, 100 means that the fee is zero.
+    pub subsidy_discount_percent: u32,
+
+    /// Number of threads dedicated to the signature verification.
+    pub signature_verifier_threads: usize,
+    /// Maximum number of verification jobs waiting for the free thread.
+    /// Also limits the number of the asynchronously submitted transactions verified at once.
+    pub signature_verifier_queue_size: usize,
+
+    /// Whether the transaction submissions are rate limited per IP and per sender account.
+    pub rate_limit_enabled: bool,
+    /// Number of submissions allowed from a single IP in a burst.
+    pub rate_limit_ip_burst: u32,
+    /// Number of submissions per minute the IP limit is replenished with.
+    pub rate_limit_ip_per_minute: u32,
+    /// Number of submissions allowed from a single account in a burst.
+    pub rate_limit_account_burst: u32,
+    /// Number of submissions per minute the account limit is replenished with.
+    pub rate_limit_account_per_minute: u32,
+    /// Addresses of the proxies (e.g. Cloudflare or the load balancer) allowed to report the IP
+    /// of the user in the `CF-Connecting-IP` header. The header of the other peers is ignored.
+    /// If the list is empty, the header is taken from any peer.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+
+    /// Maximum number of transactions waiting in the mempool, submissions that don't fit are rejected.
+    /// Zero disables the limit.
+    pub mempool_capacity: usize,
+    /// Delay suggested to the clients whose submissions were rejected because the mempool is full.
+    pub mempool_full_retry_after_sec: u64,
+    /// Maximum number of transactions of a single account waiting in the mempool, submissions
+    /// above it are rejected. Replacements of the queued transactions are not limited.
+    /// Zero disables the limit.
+    pub max_pending_txs_per_account: usize,
+
+    /// Percentage the provided fee is scaled up by (but at least by 1 cent) before comparing it
+    /// with the required one in USD, since the prices may change between quoting the fee and
+    /// submitting the transaction.
+    pub fee_tolerance_percent: u32,
+
+    /// Addresses of the senders which transactions are exempted from the fee checks,
+    /// e.g. the accounts of the operator-sponsored operations.
+    /// More exemptions can be added at runtime via the admin API.
+    #[serde(default)]
+    pub fee_exempt_senders: Vec<Address>,
+    /// Types of the transactions (e.g. `ForcedExit`) exempted from the fee checks.
+    #[serde(default)]
+    pub fee_exempt_tx_types: Vec<String>,
+
+    /// Interval of reloading the submission settings overridden via the admin API.
+    pub settings_reload_interval_sec: u64,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TokenConfig {
     /// The interval of updating tokens from database
     pub invalidate_token_cache_period_sec: u64,
+    /// URL of the IPFS gateway used to fetch the NFT metadata, the content identifier is appended to it.
+    pub ipfs_gateway_url: String,
+    /// Period for which the fetched NFT metadata is cached.
+    pub nft_metadata_ttl_sec: u64,
+    /// Interval of reconciling the stored token metadata with the ERC-20 contracts.
+    pub metadata_refresh_interval_sec: u64,
 }
 
 impl TokenConfig {
@@ -138,6 +229,14 @@ impl TokenConfig {
     pub fn invalidate_token_cache_period(&self) -> Duration {
         Duration::from_secs(self.invalidate_token_cache_period_sec)
     }
+
+    pub fn nft_metadata_ttl(&self) -> Duration {
+        Duration::from_secs(self.nft_metadata_ttl_sec)
+    }
+
+    pub fn metadata_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.metadata_refresh_interval_sec)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -263,6 +362,28 @@ mod tests {
                 subsidized_ips: vec!["127.0.0.1".to_owned()],
                 max_subsidy_usd_scaled: 20000,
                 subsidy_name: String::from("PartnerName"),
+                subsidized_accounts: vec!["0x8f6cfe7a1fe6a1bdd9b7d6bb2bb1f5ab9a3d1da6"
+                    .parse()
+                    .unwrap()],
+                subsidized_tx_types: vec!["Transfer".to_owned(), "Withdraw".to_owned()],
+                subsidy_discount_percent: 50,
+                signature_verifier_threads: 4,
+                signature_verifier_queue_size: 1000,
+                rate_limit_enabled: true,
+                rate_limit_ip_burst: 100,
+                rate_limit_ip_per_minute: 60,
+                rate_limit_account_burst: 20,
+                rate_limit_account_per_minute: 10,
+                trusted_proxies: vec!["10.0.0.1".parse().unwrap()],
+                mempool_capacity: 100_000,
+                mempool_full_retry_after_sec: 30,
+                max_pending_txs_per_account: 100,
+                fee_tolerance_percent: 5,
+                fee_exempt_senders: vec!["0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+                    .parse()
+                    .unwrap()],
+                fee_exempt_tx_types: vec!["ForcedExit".to_owned()],
+                settings_reload_interval_sec: 10,
             },
             admin: AdminApiConfig {
                 port: 8080,
@@ -297,6 +418,9 @@ mod tests {
             prometheus: PrometheusConfig { port: 3312 },
             token_config: TokenConfig {
                 invalidate_token_cache_period_sec: 10,
+                ipfs_gateway_url: "https://ipfs.io/ipfs/".into(),
+                nft_metadata_ttl_sec: 3600,
+                metadata_refresh_interval_sec: 21600,
             },
         }
     }
@@ -311,9 +435,30 @@ API_COMMON_ENFORCE_PUBKEY_CHANGE_FEE=true
 API_COMMON_SUBSIDIZED_IPS="127.0.0.1"
 API_COMMON_MAX_SUBSIDY_USD_SCALED=20000
 API_COMMON_SUBSIDY_NAME=PartnerName
+API_COMMON_SUBSIDIZED_ACCOUNTS="0x8f6cfe7a1fe6a1bdd9b7d6bb2bb1f5ab9a3d1da6"
+API_COMMON_SUBSIDIZED_TX_TYPES="Transfer,Withdraw"
+API_COMMON_SUBSIDY_DISCOUNT_PERCENT=50
 API_COMMON_MAX_NUMBER_OF_TRANSACTIONS_PER_BATCH=200
 API_COMMON_MAX_NUMBER_OF_AUTHORS_PER_BATCH=10
+API_COMMON_SIGNATURE_VERIFIER_THREADS=4
+API_COMMON_SIGNATURE_VERIFIER_QUEUE_SIZE=1000
+API_COMMON_RATE_LIMIT_ENABLED=true
+API_COMMON_RATE_LIMIT_IP_BURST=100
+API_COMMON_RATE_LIMIT_IP_PER_MINUTE=60
+API_COMMON_RATE_LIMIT_ACCOUNT_BURST=20
+API_COMMON_RATE_LIMIT_ACCOUNT_PER_MINUTE=10
+API_COMMON_TRUSTED_PROXIES="10.0.0.1"
+API_COMMON_MEMPOOL_CAPACITY=100000
+API_COMMON_MEMPOOL_FULL_RETRY_AFTER_SEC=30
+API_COMMON_MAX_PENDING_TXS_PER_ACCOUNT=100
+API_COMMON_FEE_TOLERANCE_PERCENT=5
+API_COMMON_FEE_EXEMPT_SENDERS="0xde03a0b5963f75f1c8485b355ff6d30f3093bde7"
+API_COMMON_FEE_EXEMPT_TX_TYPES="ForcedExit"
+API_COMMON_SETTINGS_RELOAD_INTERVAL_SEC=10
 API_TOKEN_INVALIDATE_TOKEN_CACHE_PERIOD_SEC="10"
+API_TOKEN_IPFS_GATEWAY_URL="https://ipfs.io/ipfs/"
+API_TOKEN_NFT_METADATA_TTL_SEC="3600"
+API_TOKEN_METADATA_REFRESH_INTERVAL_SEC="21600"
 API_ADMIN_PORT="8080"
 API_ADMIN_URL="http://127.0.0.1:8080"
 API_ADMIN_SECRET_AUTH="sample"
@@ -371,4 +516,12 @@ API_PROMETHEUS_PORT="3312"
             SocketAddr::new(bind_broadcast_addr, config.web3.port)
         );
     }
+
+    #[test]
+    #[should_panic(expected = "signature_verifier_queue_size should be positive")]
+    fn rejects_empty_signature_verifier_queue() {
+        let mut config = expected_config().common;
+        config.signature_verifier_queue_size = 0;
+        config.validate();
+    }
 }
@@ -74,6 +74,17 @@ pub struct Eth {
     pub network: Network,
 }
 
+/// Order in which the ready mempool transactions are included into the proposed blocks.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolTxOrdering {
+    /// Transactions are included in the order of their nonces.
+    Fifo,
+    /// Transactions paying the most (in USD) per chunk are included first,
+    /// transactions of the same account keep the order of their nonces.
+    FeePerChunk,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct StateKeeper {
     /// Block sizes to be generated by server. Has to contain only values set in the `supported_block_chunks_sizes`,
@@ -94,6 +105,19 @@ pub struct StateKeeper {
     pub block_prove_deadline: u64,
     pub block_execute_deadline: u64,
     pub max_aggregated_tx_gas: usize,
+    /// Queued transactions are evicted from the mempool after this amount of seconds.
+    pub mempool_tx_max_age: u64,
+    /// Time (in seconds) between the checks for the expired mempool transactions.
+    pub mempool_expiration_interval: u64,
+    /// Order in which the mempool transactions are included into the blocks.
+    pub mempool_tx_ordering: MempoolTxOrdering,
+    /// Maximum distance between the nonce of the accepted transaction and the next nonce of the account.
+    /// Transactions ahead of the account nonce wait in the mempool until the gap is filled.
+    pub mempool_max_nonce_gap: u32,
+    /// Maximum time (in milliseconds) the mempool collects the queued submissions to insert them
+    /// with a single statement. The mempool doesn't wait for the new submissions, so the transactions
+    /// are inserted at once if no more submissions are queued. Zero disables the coalescing of the inserts.
+    pub mempool_insert_window: u64,
 }
 
 impl StateKeeper {
@@ -113,6 +137,18 @@ impl StateKeeper {
     pub fn block_execute_deadline(&self) -> Duration {
         Duration::from_secs(self.block_execute_deadline)
     }
+
+    pub fn mempool_tx_max_age(&self) -> Duration {
+        Duration::from_secs(self.mempool_tx_max_age)
+    }
+
+    pub fn mempool_expiration_interval(&self) -> Duration {
+        Duration::from_secs(self.mempool_expiration_interval)
+    }
+
+    pub fn mempool_insert_window(&self) -> Duration {
+        Duration::from_millis(self.mempool_insert_window)
+    }
 }
 
 #[cfg(test)]
@@ -147,6 +183,11 @@ mod tests {
                 block_prove_deadline: 3_000,
                 block_execute_deadline: 4_000,
                 max_aggregated_tx_gas: 4_000_000,
+                mempool_tx_max_age: 86_400,
+                mempool_expiration_interval: 60,
+                mempool_tx_ordering: MempoolTxOrdering::FeePerChunk,
+                mempool_max_nonce_gap: 16,
+                mempool_insert_window: 5,
             },
         }
     }
@@ -175,6 +216,11 @@ CHAIN_STATE_KEEPER_BLOCK_COMMIT_DEADLINE="300"
 CHAIN_STATE_KEEPER_BLOCK_PROVE_DEADLINE="3000"
 CHAIN_STATE_KEEPER_BLOCK_EXECUTE_DEADLINE="4000"
 CHAIN_STATE_KEEPER_MAX_AGGREGATED_TX_GAS="4000000"
+CHAIN_STATE_KEEPER_MEMPOOL_TX_MAX_AGE="86400"
+CHAIN_STATE_KEEPER_MEMPOOL_EXPIRATION_INTERVAL="60"
+CHAIN_STATE_KEEPER_MEMPOOL_TX_ORDERING="fee_per_chunk"
+CHAIN_STATE_KEEPER_MEMPOOL_MAX_NONCE_GAP="16"
+CHAIN_STATE_KEEPER_MEMPOOL_INSERT_WINDOW="5"
         "#;
         set_env(config);
 
@@ -18,6 +18,9 @@ pub struct DBConfig {
     pub rejected_transactions_max_age: u64,
     /// Sleep time (in hours) of the actor responsible for deleting failed transactions from the database.
     pub rejected_transactions_cleaner_interval: u64,
+    /// Queries taking longer than this amount of milliseconds are logged along with their SQL
+    /// and the storage method that issued them.
+    pub slow_query_threshold_ms: u64,
 }
 
 impl DBConfig {
@@ -34,6 +37,10 @@ impl DBConfig {
     pub fn rejected_transactions_cleaner_interval(&self) -> time::Duration {
         time::Duration::from_secs(self.rejected_transactions_cleaner_interval)
     }
+
+    pub fn slow_query_threshold(&self) -> time::Duration {
+        time::Duration::from_millis(self.slow_query_threshold_ms)
+    }
 }
 
 #[cfg(test)]
@@ -47,6 +54,7 @@ mod tests {
             url: "postgres://postgres@localhost/plasma".into(),
             rejected_transactions_max_age: 336,
             rejected_transactions_cleaner_interval: 24,
+            slow_query_threshold_ms: 1000,
         }
     }
 
@@ -57,6 +65,7 @@ DATABASE_POOL_SIZE="10"
 DATABASE_URL="postgres://postgres@localhost/plasma"
 DATABASE_REJECTED_TRANSACTIONS_MAX_AGE="336"
 DATABASE_REJECTED_TRANSACTIONS_CLEANER_INTERVAL="24"
+DATABASE_SLOW_QUERY_THRESHOLD_MS="1000"
         "#;
         set_env(config);
 
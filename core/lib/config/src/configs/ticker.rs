@@ -1,5 +1,7 @@
-use num::{rational::Ratio, BigUint};
+// Built-in uses
+use std::time::Duration;
 // External uses
+use num::{rational::Ratio, BigUint};
 use serde::Deserialize;
 // Workspace uses
 use zksync_types::Address;
@@ -11,6 +13,7 @@ use crate::envy_load;
 pub enum TokenPriceSource {
     CoinGecko,
     CoinMarketCap,
+    Uniswap,
 }
 
 /// Configuration for the fee ticker.
@@ -18,16 +21,34 @@ pub enum TokenPriceSource {
 pub struct TickerConfig {
     /// Indicator of the API to be used for getting token prices.
     pub token_price_source: TokenPriceSource,
+    /// Price sources to be used in the specified order if the main one is unavailable.
+    #[serde(default)]
+    pub fallback_price_sources: Vec<TokenPriceSource>,
     /// URL of CoinMarketCap API. Can be set to the mock server for local development.
     pub coinmarketcap_base_url: String,
     /// URL of CoinGecko API. Can be set to the mock server for local development.
     pub coingecko_base_url: String,
+    /// Prices from CoinMarketCap older than this are not used.
+    pub coinmarketcap_max_price_age_secs: u64,
+    /// Prices from CoinGecko older than this are not used.
+    pub coingecko_max_price_age_secs: u64,
+    /// Prices from Uniswap older than this are not used.
+    pub uniswap_max_price_age_secs: u64,
     /// Coefficient for scaling all fees in percent.
     pub scale_fee_percent: u32,
     /// Coefficient for the fee price for fast withdrawal requests.
     pub fast_processing_coeff: f64,
     /// Url to uniswap api
     pub uniswap_url: String,
+    /// Address of the Uniswap V2 factory used to find the pairs for the average prices.
+    pub uniswap_factory_address: Address,
+    /// Address of the wrapped Ether, tokens are priced in the pairs with it.
+    pub uniswap_weth_address: Address,
+    /// Address of the USD stablecoin, wrapped Ether is priced in the pair with it.
+    pub uniswap_usd_token_address: Address,
+    pub uniswap_usd_token_decimals: u8,
+    /// Number of the Ethereum blocks the Uniswap prices are averaged over.
+    pub uniswap_twap_period_blocks: u64,
     /// The volume of tokens to confirm their liquidity
     pub liquidity_volume: f64,
     /// Time when liquidity check results are valid
@@ -40,6 +61,8 @@ pub struct TickerConfig {
     pub number_of_ticker_actors: u8,
     /// Subsidized price for ChangePubKey in cents scaled by SUBSIDY_USD_AMOUNTS_SCALE
     pub subsidy_cpk_price_usd_scaled: u64,
+    /// Fee quotes older than this are removed from the fee history.
+    pub fee_history_max_age_hours: u64,
 }
 
 impl TickerConfig {
@@ -51,13 +74,52 @@ impl TickerConfig {
         envy_load!("fee_ticker", "FEE_TICKER_")
     }
 
+    pub fn fee_history_max_age(&self) -> Duration {
+        Duration::from_secs(self.fee_history_max_age_hours * 3600)
+    }
+
     /// Returns the token price source type and the corresponding API URL.
     pub fn price_source(&self) -> (TokenPriceSource, String) {
-        let url = match self.token_price_source {
+        (
+            self.token_price_source,
+            self.price_source_url(self.token_price_source),
+        )
+    }
+
+    /// Returns all the token price sources in the order of priority along with
+    /// their API URLs and price age limits. Duplicates are skipped.
+    pub fn price_sources(&self) -> Vec<(TokenPriceSource, String, Duration)> {
+        let mut sources = Vec::new();
+        for source in std::iter::once(self.token_price_source)
+            .chain(self.fallback_price_sources.iter().copied())
+        {
+            if sources.iter().any(|(added, _, _)| *added == source) {
+                continue;
+            }
+            sources.push((
+                source,
+                self.price_source_url(source),
+                self.max_price_age(source),
+            ));
+        }
+        sources
+    }
+
+    fn price_source_url(&self, source: TokenPriceSource) -> String {
+        match source {
             TokenPriceSource::CoinGecko => self.coingecko_base_url.clone(),
             TokenPriceSource::CoinMarketCap => self.coinmarketcap_base_url.clone(),
+            TokenPriceSource::Uniswap => self.uniswap_url.clone(),
+        }
+    }
+
+    fn max_price_age(&self, source: TokenPriceSource) -> Duration {
+        let secs = match source {
+            TokenPriceSource::CoinGecko => self.coingecko_max_price_age_secs,
+            TokenPriceSource::CoinMarketCap => self.coinmarketcap_max_price_age_secs,
+            TokenPriceSource::Uniswap => self.uniswap_max_price_age_secs,
         };
-        (self.token_price_source, url)
+        Duration::from_secs(secs)
     }
 }
 
@@ -69,17 +131,27 @@ mod tests {
     fn expected_config() -> TickerConfig {
         TickerConfig {
             token_price_source: TokenPriceSource::CoinGecko,
+            fallback_price_sources: vec![TokenPriceSource::Uniswap],
             coinmarketcap_base_url: "http://127.0.0.1:9876".into(),
             coingecko_base_url: "http://127.0.0.1:9876".into(),
+            coinmarketcap_max_price_age_secs: 3600,
+            coingecko_max_price_age_secs: 7200,
+            uniswap_max_price_age_secs: 600,
             scale_fee_percent: 100,
             fast_processing_coeff: 10.0f64,
             uniswap_url: "http://127.0.0.1:9975/graphql".to_string(),
+            uniswap_factory_address: addr("5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"),
+            uniswap_weth_address: addr("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"),
+            uniswap_usd_token_address: addr("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+            uniswap_usd_token_decimals: 6,
+            uniswap_twap_period_blocks: 100,
             liquidity_volume: 100.0,
             available_liquidity_seconds: 1000,
             unconditionally_valid_tokens: vec![addr("0000000000000000000000000000000000000000")],
             token_market_update_time: 120,
             number_of_ticker_actors: 4,
             subsidy_cpk_price_usd_scaled: 100,
+            fee_history_max_age_hours: 720,
         }
     }
 
@@ -87,10 +159,19 @@ mod tests {
     fn from_env() {
         let config = r#"
 FEE_TICKER_TOKEN_PRICE_SOURCE="CoinGecko"
+FEE_TICKER_FALLBACK_PRICE_SOURCES="Uniswap"
+FEE_TICKER_COINMARKETCAP_MAX_PRICE_AGE_SECS=3600
+FEE_TICKER_COINGECKO_MAX_PRICE_AGE_SECS=7200
+FEE_TICKER_UNISWAP_MAX_PRICE_AGE_SECS=600
 FEE_TICKER_COINMARKETCAP_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_COINGECKO_BASE_URL="http://127.0.0.1:9876"
 FEE_TICKER_FAST_PROCESSING_COEFF="10"
 FEE_TICKER_UNISWAP_URL=http://127.0.0.1:9975/graphql
+FEE_TICKER_UNISWAP_FACTORY_ADDRESS="0x5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f"
+FEE_TICKER_UNISWAP_WETH_ADDRESS="0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2"
+FEE_TICKER_UNISWAP_USD_TOKEN_ADDRESS="0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"
+FEE_TICKER_UNISWAP_USD_TOKEN_DECIMALS=6
+FEE_TICKER_UNISWAP_TWAP_PERIOD_BLOCKS=100
 FEE_TICKER_AVAILABLE_LIQUIDITY_SECONDS=1000
 FEE_TICKER_TOKEN_MARKET_UPDATE_TIME=120
 FEE_TICKER_UNCONDITIONALLY_VALID_TOKENS="0x0000000000000000000000000000000000000000"
@@ -99,6 +180,7 @@ FEE_TICKER_NUMBER_OF_TICKER_ACTORS="4"
 FEE_TICKER_SUBSIDIZED_TOKENS_LIMITS=156
 FEE_TICKER_SCALE_FEE_PERCENT=100
 FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
+FEE_TICKER_FEE_HISTORY_MAX_AGE_HOURS=720
         "#;
         set_env(config);
 
@@ -128,5 +210,31 @@ FEE_TICKER_SUBSIDY_CPK_PRICE_USD_SCALED=100
             config.price_source(),
             (TokenPriceSource::CoinMarketCap, COINMARKETCAP_URL.into())
         );
+
+        config.fallback_price_sources = vec![
+            TokenPriceSource::Uniswap,
+            TokenPriceSource::CoinMarketCap,
+            TokenPriceSource::CoinGecko,
+        ];
+        assert_eq!(
+            config.price_sources(),
+            vec![
+                (
+                    TokenPriceSource::CoinMarketCap,
+                    COINMARKETCAP_URL.into(),
+                    Duration::from_secs(3600)
+                ),
+                (
+                    TokenPriceSource::Uniswap,
+                    config.uniswap_url.clone(),
+                    Duration::from_secs(600)
+                ),
+                (
+                    TokenPriceSource::CoinGecko,
+                    COINGECKO_URL.into(),
+                    Duration::from_secs(7200)
+                ),
+            ]
+        );
     }
 }
@@ -19,6 +19,13 @@ const UPGRADE_GATEKEEPER_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/UpgradeGatekeeper.sol/UpgradeGatekeeper.json";
 const FORCED_EXIT_CONTRACT_FILE: &str =
     "contracts/artifacts/cache/solpp-generated-contracts/ForcedExit.sol/ForcedExit.json";
+const IENS_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IENS.sol/IENS.json";
+const IENS_RESOLVER_CONTRACT_FILE: &str =
+    "contracts/artifacts/cache/solpp-generated-contracts/IENSResolver.sol/IENSResolver.json";
+const ERC20_METADATA_ABI_FILE: &str = "etc/web3-abi/ERC20.json";
+const UNISWAP_V2_FACTORY_ABI_FILE: &str = "etc/web3-abi/UniswapV2Factory.json";
+const UNISWAP_V2_PAIR_ABI_FILE: &str = "etc/web3-abi/UniswapV2Pair.json";
 
 fn read_file_to_json_value(path: &str) -> io::Result<serde_json::Value> {
     let zksync_home = std::env::var("ZKSYNC_HOME").unwrap_or_else(|_| ".".into());
@@ -91,6 +98,30 @@ pub fn erc20_contract() -> Contract {
     Contract::load(abi_string.as_bytes()).expect("erc20 contract abi")
 }
 
+/// ERC-20 interface including the optional metadata methods (`name`, `symbol` and `decimals`).
+pub fn erc20_metadata_contract() -> Contract {
+    let abi_string = read_file_to_json_value(ERC20_METADATA_ABI_FILE)
+        .expect("couldn't read ERC20_METADATA_ABI_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("erc20 metadata contract abi")
+}
+
+/// Part of the Uniswap V2 factory interface used to find the pair of tokens.
+pub fn uniswap_v2_factory_contract() -> Contract {
+    let abi_string = read_file_to_json_value(UNISWAP_V2_FACTORY_ABI_FILE)
+        .expect("couldn't read UNISWAP_V2_FACTORY_ABI_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("uniswap v2 factory contract abi")
+}
+
+/// Part of the Uniswap V2 pair interface used to observe the cumulative prices.
+pub fn uniswap_v2_pair_contract() -> Contract {
+    let abi_string = read_file_to_json_value(UNISWAP_V2_PAIR_ABI_FILE)
+        .expect("couldn't read UNISWAP_V2_PAIR_ABI_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("uniswap v2 pair contract abi")
+}
+
 pub fn eip1271_contract() -> Contract {
     let abi_string = read_file_to_json_value(IEIP1271_CONTRACT_FILE)
         .expect("couldn't read IEIP1271_CONTRACT_FILE")
@@ -116,3 +147,21 @@ pub fn forced_exit_contract() -> Contract {
         .to_string();
     Contract::load(abi_string.as_bytes()).expect("forced_exit contract abi")
 }
+
+pub fn ens_contract() -> Contract {
+    let abi_string = read_file_to_json_value(IENS_CONTRACT_FILE)
+        .expect("couldn't read IENS_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from IENS_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("ens contract abi")
+}
+
+pub fn ens_resolver_contract() -> Contract {
+    let abi_string = read_file_to_json_value(IENS_RESOLVER_CONTRACT_FILE)
+        .expect("couldn't read IENS_RESOLVER_CONTRACT_FILE")
+        .get("abi")
+        .expect("couldn't get abi from IENS_RESOLVER_CONTRACT_FILE")
+        .to_string();
+    Contract::load(abi_string.as_bytes()).expect("ens resolver contract abi")
+}
@@ -13,7 +13,7 @@ use web3::{
     },
     transports::Http,
     types::{
-        Address, BlockId, BlockNumber, Bytes, Filter, Log, Transaction, TransactionId,
+        Address, BlockId, BlockNumber, Bytes, CallRequest, Filter, Log, Transaction, TransactionId,
         TransactionReceipt, H160, H256, U256, U64,
     },
     Web3,
@@ -342,6 +342,36 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
         Ok(balance)
     }
 
+    pub async fn get_code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let code = self.inner.web3.eth().code(address, None).await?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.get_code", start.elapsed());
+        Ok(code.0)
+    }
+
+    /// Executes the call with `eth_call` and returns the raw output.
+    pub async fn call(
+        &self,
+        from: Address,
+        to: Address,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        #[cfg(feature = "with-metrics")]
+        let start = Instant::now();
+        let request = CallRequest {
+            from: Some(from),
+            to: Some(to),
+            data: Some(Bytes(data)),
+            ..Default::default()
+        };
+        let output = self.inner.web3.eth().call(request, None).await?;
+        #[cfg(feature = "with-metrics")]
+        metrics::histogram!("eth_client.direct.call", start.elapsed());
+        Ok(output.0)
+    }
+
     pub async fn sender_eth_balance(&self) -> Result<U256, anyhow::Error> {
         self.eth_balance(self.inner.sender_account).await
     }
@@ -434,6 +464,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
             Some(TransactionReceipt {
                 block_number: Some(tx_block_number),
                 status: Some(status),
+                gas_used,
                 ..
             }) => {
                 let current_block = match current_block {
@@ -454,6 +485,7 @@ impl<S: EthereumSigner> ETHDirectClient<S> {
                     confirmations,
                     success,
                     receipt,
+                    gas_used,
                 }))
             }
             _ => Ok(None),
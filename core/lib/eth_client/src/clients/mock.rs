@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::Error;
-use ethabi::{Address, Contract};
+use ethabi::{Address, Contract, Token};
 use std::collections::{HashMap, HashSet};
 use tokio::sync::RwLock;
 use web3::contract::tokens::{Detokenize, Tokenize};
@@ -22,6 +22,7 @@ struct MockEthereumInner {
     gas_price: U256,
     tx_statuses: Arc<RwLock<HashMap<H256, ExecutedTxStatus>>>,
     sent_txs: Arc<RwLock<HashSet<Vec<u8>>>>,
+    main_contract_responses: Arc<RwLock<HashMap<String, Vec<Token>>>>,
 }
 
 /// Mock Ethereum client is capable of recording all the incoming requests for the further analysis.
@@ -37,6 +38,7 @@ impl Default for MockEthereumInner {
             gas_price: 100.into(),
             tx_statuses: Default::default(),
             sent_txs: Default::default(),
+            main_contract_responses: Default::default(),
         }
     }
 }
@@ -83,6 +85,7 @@ impl MockEthereum {
             confirmations,
             success: true,
             receipt: None,
+            gas_used: None,
         };
         self.inner.tx_statuses.write().await.insert(tx_hash, status);
     }
@@ -95,9 +98,20 @@ impl MockEthereum {
             confirmations,
             success: false,
             receipt: Some(Default::default()),
+            gas_used: None,
         };
         self.inner.tx_statuses.write().await.insert(*hash, status);
     }
+
+    /// Sets the value returned by the calls of the main contract function.
+    pub async fn add_main_contract_response(&mut self, func: &str, tokens: Vec<Token>) {
+        self.inner
+            .main_contract_responses
+            .write()
+            .await
+            .insert(func.to_string(), tokens);
+    }
+
     pub async fn get_tx_status(
         &self,
         hash: H256,
@@ -193,6 +207,21 @@ impl MockEthereum {
         unreachable!()
     }
 
+    /// Pretends that every address is an externally owned account.
+    pub async fn get_code(&self, _address: Address) -> Result<Vec<u8>, Error> {
+        Ok(Vec::new())
+    }
+
+    /// Every call returns nothing, as if it was made to an externally owned account.
+    pub async fn call(
+        &self,
+        _from: Address,
+        _to: Address,
+        _data: Vec<u8>,
+    ) -> Result<Vec<u8>, Error> {
+        Ok(Vec::new())
+    }
+
     pub async fn contract_balance(
         &self,
         _token_address: Address,
@@ -219,7 +248,7 @@ impl MockEthereum {
 
     pub async fn call_main_contract_function<R, A, P, B>(
         &self,
-        _func: &str,
+        func: &str,
         _params: P,
         _from: A,
         _options: Options,
@@ -231,7 +260,15 @@ impl MockEthereum {
         B: Into<Option<BlockId>>,
         P: Tokenize,
     {
-        todo!()
+        let tokens = self
+            .inner
+            .main_contract_responses
+            .read()
+            .await
+            .get(func)
+            .cloned()
+            .ok_or_else(|| anyhow::format_err!("No response for the {} call is set", func))?;
+        Ok(R::from_tokens(tokens)?)
     }
 
     pub async fn logs(&self, _filter: Filter) -> anyhow::Result<Vec<Log>> {
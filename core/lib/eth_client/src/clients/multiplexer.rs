@@ -160,6 +160,19 @@ impl MultiplexerEthereumClient {
         multiple_call!(self, allowance(token_address, erc20_abi));
     }
 
+    pub async fn get_code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        multiple_call!(self, get_code(address));
+    }
+
+    pub async fn call(
+        &self,
+        from: Address,
+        to: Address,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        multiple_call!(self, call(from, to, data));
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn call_contract_function<R, A, B, P>(
         &self,
@@ -31,6 +31,8 @@ pub struct ExecutedTxStatus {
     /// Receipt for a transaction. Will be set to `Some` only if the transaction
     /// failed during execution.
     pub receipt: Option<TransactionReceipt>,
+    /// Amount of gas used by the transaction.
+    pub gas_used: Option<U256>,
 }
 /// Information about transaction failure.
 #[derive(Debug, Clone)]
@@ -186,6 +188,21 @@ impl EthereumGateway {
         delegate_call!(self.allowance(token_address, erc20_abi))
     }
 
+    /// Returns the bytecode deployed at the given address, empty for externally owned accounts.
+    pub async fn get_code(&self, address: Address) -> Result<Vec<u8>, anyhow::Error> {
+        delegate_call!(self.get_code(address))
+    }
+
+    /// Executes the call with `eth_call` and returns the raw output, nothing is sent to the network.
+    pub async fn call(
+        &self,
+        from: Address,
+        to: Address,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, anyhow::Error> {
+        delegate_call!(self.call(from, to, data))
+    }
+
     pub async fn get_tx_status(
         &self,
         hash: H256,
@@ -1,14 +1,22 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
+use num::{rational::Ratio, BigUint};
 
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_types::{
     mempool::SignedTxVariant,
     tx::{error::TxAddError, TxHash},
-    PriorityOp,
+    PriorityOp, TokenId,
 };
 
 use crate::state::MempoolState;
 
+/// Interval of reloading the token prices used to order the transactions by the fee.
+const FEE_TOKEN_PRICES_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Clone, Debug, Default)]
 pub struct ProposedBlock {
     pub priority_ops: Vec<PriorityOp>,
@@ -47,9 +55,23 @@ pub(crate) struct MempoolBlocksHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolBlocksRequest>,
     pub max_block_size_chunks: usize,
+    pub tx_ordering: MempoolTxOrdering,
+    pub fee_token_prices: HashMap<TokenId, Ratio<BigUint>>,
+    pub fee_token_prices_updated_at: Option<Instant>,
 }
 
 impl MempoolBlocksHandler {
+    async fn update_fee_token_prices(&mut self) -> Result<(), TxAddError> {
+        let is_outdated = self.fee_token_prices_updated_at.map_or(true, |updated_at| {
+            updated_at.elapsed() >= FEE_TOKEN_PRICES_UPDATE_INTERVAL
+        });
+        if is_outdated {
+            self.fee_token_prices = self.mempool_state.load_fee_token_prices().await?;
+            self.fee_token_prices_updated_at = Some(Instant::now());
+        }
+        Ok(())
+    }
+
     async fn propose_new_block(
         &mut self,
         current_unprocessed_priority_op: u64,
@@ -64,12 +86,20 @@ impl MempoolBlocksHandler {
             .get_transaction_queue(executed_txs)
             .await?;
 
+        let fee_token_prices = match self.tx_ordering {
+            MempoolTxOrdering::Fifo => None,
+            MempoolTxOrdering::FeePerChunk => {
+                self.update_fee_token_prices().await?;
+                Some(&self.fee_token_prices)
+            }
+        };
         let (txs, priority_ops, chunks_left) = tx_queue
             .select_transactions(
                 self.max_block_size_chunks,
                 current_unprocessed_priority_op,
                 block_timestamp,
                 &self.mempool_state,
+                fee_token_prices,
             )
             .await?;
 
@@ -109,10 +139,10 @@ impl MempoolBlocksHandler {
 
     pub async fn run(mut self) {
         vlog::info!("Block mempool handler is running");
-        // We have to clean garbage from mempool before running the block generator.
-        // Remove any possible duplicates of already executed transactions
-        // from the database.
-        self.mempool_state.collect_garbage().await;
+        // We have to recover the mempool before running the block generator:
+        // remove the repeated and already executed transactions from the database,
+        // and expire the ones which nonces are used already.
+        self.mempool_state.recover().await;
         while let Some(request) = self.requests.next().await {
             match request {
                 MempoolBlocksRequest::GetBlock(block) => {
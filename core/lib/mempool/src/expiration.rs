@@ -0,0 +1,184 @@
+//! Eviction of the transactions that can't be executed anymore.
+//!
+//! Transaction is evicted from the mempool once its validity period has ended, or if it
+//! waits for the execution for too long (e.g. because of the nonce gap). Receipts of the
+//! evicted transactions show they expired along with the reason. Batches are evicted as a whole.
+
+// Built-in uses
+use std::time::{Duration, Instant};
+
+// External uses
+use chrono::{DateTime, Utc};
+use tokio::task::JoinHandle;
+
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_types::{mempool::SignedTxVariant, tx::TxHash, SignedZkSyncTx};
+
+/// Returns the reason to evict the transaction, if it should be evicted.
+fn expiration_reason(
+    tx: &SignedZkSyncTx,
+    now: DateTime<Utc>,
+    max_age: chrono::Duration,
+) -> Option<String> {
+    let valid_until = tx.tx.time_range().valid_until;
+    if valid_until < now.timestamp() as u64 {
+        Some(format!(
+            "Transaction validity period ended at {}",
+            valid_until
+        ))
+    } else if now - tx.created_at > max_age {
+        Some(format!(
+            "Transaction was not executed within {} seconds",
+            max_age.num_seconds()
+        ))
+    } else {
+        None
+    }
+}
+
+/// Finds the transactions to evict along with the reasons of the eviction.
+fn find_expired_txs<'a>(
+    txs: impl IntoIterator<Item = &'a SignedTxVariant>,
+    now: DateTime<Utc>,
+    max_age: chrono::Duration,
+) -> Vec<(TxHash, String)> {
+    let mut expired = Vec::new();
+    for tx in txs {
+        match tx {
+            SignedTxVariant::Tx(tx) => {
+                if let Some(reason) = expiration_reason(tx, now, max_age) {
+                    expired.push((tx.hash(), reason));
+                }
+            }
+            SignedTxVariant::Batch(batch) => {
+                let reason = batch
+                    .txs
+                    .iter()
+                    .find_map(|tx| expiration_reason(tx, now, max_age));
+                if let Some(reason) = reason {
+                    expired.extend(batch.txs.iter().map(|tx| (tx.hash(), reason.clone())));
+                }
+            }
+        }
+    }
+    expired
+}
+
+async fn evict_expired_txs(pool: &ConnectionPool, max_age: chrono::Duration) -> QueryResult<()> {
+    let start = Instant::now();
+    let mut storage = pool.access_storage().await?;
+    let txs = storage.chain().mempool_schema().load_txs(&[]).await?;
+
+    let expired = find_expired_txs(&txs, Utc::now(), max_age);
+    if !expired.is_empty() {
+        storage
+            .chain()
+            .mempool_schema()
+            .expire_txs(&expired)
+            .await?;
+        vlog::info!(
+            "Evicted {} expired transactions from the mempool",
+            expired.len()
+        );
+    }
+
+    metrics::counter!("mempool.expired_txs", expired.len() as u64);
+    metrics::histogram!("mempool.evict_expired_txs", start.elapsed());
+    Ok(())
+}
+
+/// Runs the task that periodically evicts the expired transactions from the mempool.
+#[must_use]
+pub fn run_mempool_expiration_task(
+    db_pool: ConnectionPool,
+    max_age: Duration,
+    interval: Duration,
+) -> JoinHandle<()> {
+    let max_age = chrono::Duration::from_std(max_age).unwrap();
+    tokio::spawn(async move {
+        let mut timer = tokio::time::interval(interval);
+        loop {
+            timer.tick().await;
+            if let Err(err) = evict_expired_txs(&db_pool, max_age).await {
+                vlog::warn!("Can't evict the expired mempool transactions: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{
+        mempool::SignedTxsBatch,
+        tx::{TimeRange, Transfer},
+        AccountId, Address, Nonce, TokenId, ZkSyncTx,
+    };
+
+    fn transfer(nonce: u32, valid_until: u64, created_at: DateTime<Utc>) -> SignedZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(4242),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            20u32.into(),
+            Nonce(nonce),
+            TimeRange::new(0, valid_until),
+            None,
+        );
+
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at,
+        }
+    }
+
+    #[test]
+    fn expired_txs() {
+        let now = Utc::now();
+        let max_age = chrono::Duration::hours(1);
+        let now_timestamp = now.timestamp() as u64;
+
+        let fresh = transfer(1, u64::MAX, now);
+        let outdated = transfer(2, now_timestamp - 1, now);
+        let old = transfer(3, u64::MAX, now - chrono::Duration::hours(2));
+        let batch = SignedTxsBatch {
+            txs: vec![
+                transfer(4, u64::MAX, now),
+                transfer(5, now_timestamp - 1, now),
+            ],
+            batch_id: 1,
+            eth_signatures: Vec::new(),
+        };
+        let fresh_batch = SignedTxsBatch {
+            txs: vec![transfer(6, u64::MAX, now), transfer(7, now_timestamp, now)],
+            batch_id: 2,
+            eth_signatures: Vec::new(),
+        };
+
+        let txs = vec![
+            SignedTxVariant::Tx(fresh),
+            SignedTxVariant::Tx(outdated.clone()),
+            SignedTxVariant::Tx(old.clone()),
+            SignedTxVariant::Batch(batch.clone()),
+            SignedTxVariant::Batch(fresh_batch),
+        ];
+        let expired = find_expired_txs(&txs, now, max_age);
+
+        let validity_reason = format!("Transaction validity period ended at {}", now_timestamp - 1);
+        let age_reason = "Transaction was not executed within 3600 seconds".to_string();
+        assert_eq!(
+            expired,
+            vec![
+                (outdated.hash(), validity_reason.clone()),
+                (old.hash(), age_reason),
+                // The whole batch is evicted with the reason of the expired transaction.
+                (batch.txs[0].hash(), validity_reason.clone()),
+                (batch.txs[1].hash(), validity_reason),
+            ]
+        );
+    }
+}
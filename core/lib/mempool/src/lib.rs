@@ -4,30 +4,39 @@
 //! 1) Storing txs to the database
 //! 2) Getting txs from database.
 //! 3) When polled return vector of the transactions in the queue.
+//! 4) Evicting transactions that can't be executed anymore.
+//! 5) Reconciling the stored transactions with the executed operations on restart.
 //!
 //! For better consistency, we always store all txs in the database and get them only if they are requested.
 //!
 //! Communication channel with other actors:
 //! Mempool does not push information to other actors, only accepts requests. (see `MempoolRequest`)
 
+// Built-in uses
+use std::time::Duration;
+
 // External uses
 use futures::channel::mpsc;
 
 use tokio::task::JoinHandle;
 
 // Workspace uses
+use zksync_config::configs::chain::MempoolTxOrdering;
 use zksync_storage::ConnectionPool;
 
 // Local uses
 use crate::block_handler::MempoolBlocksHandler;
 pub use crate::block_handler::{GetBlockRequest, MempoolBlocksRequest, ProposedBlock};
+pub use crate::expiration::run_mempool_expiration_task;
 use crate::mempool_transactions_queue::MempoolTransactionsQueue;
 use crate::state::MempoolState;
-pub use crate::transactions_handler::MempoolTransactionRequest;
 use crate::transactions_handler::MempoolTransactionsHandler;
+pub use crate::transactions_handler::{pays_higher_fee, MempoolTransactionRequest};
 
 mod block_handler;
+mod expiration;
 mod mempool_transactions_queue;
+mod recovery;
 mod state;
 mod transactions_handler;
 
@@ -38,6 +47,8 @@ pub fn run_mempool_tx_handler(
     db_pool: ConnectionPool,
     tx_requests: mpsc::Receiver<MempoolTransactionRequest>,
     block_chunk_sizes: Vec<usize>,
+    max_nonce_gap: u32,
+    insert_window: Duration,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool.clone());
     let max_block_size_chunks = *block_chunk_sizes
@@ -49,6 +60,8 @@ pub fn run_mempool_tx_handler(
         mempool_state,
         requests: tx_requests,
         max_block_size_chunks,
+        max_nonce_gap,
+        insert_window,
     };
     tokio::spawn(handler.run())
 }
@@ -58,6 +71,7 @@ pub fn run_mempool_block_handler(
     db_pool: ConnectionPool,
     block_requests: mpsc::Receiver<MempoolBlocksRequest>,
     block_chunk_sizes: Vec<usize>,
+    tx_ordering: MempoolTxOrdering,
 ) -> JoinHandle<()> {
     let mempool_state = MempoolState::new(db_pool);
     let max_block_size_chunks = *block_chunk_sizes
@@ -69,6 +83,9 @@ pub fn run_mempool_block_handler(
         mempool_state,
         requests: block_requests,
         max_block_size_chunks,
+        tx_ordering,
+        fee_token_prices: Default::default(),
+        fee_token_prices_updated_at: None,
     };
 
     tokio::spawn(blocks_handler.run())
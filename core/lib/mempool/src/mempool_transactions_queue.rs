@@ -1,13 +1,61 @@
 use crate::MempoolState;
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, VecDeque};
+use num::{rational::Ratio, BigUint, Zero};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use zksync_types::mempool::SignedTxVariant;
 use zksync_types::tx::error::TxAddError;
-use zksync_types::PriorityOp;
+use zksync_types::{AccountId, Nonce, PriorityOp, TokenId, TokenLike, ZkSyncTx};
+
+/// Accounts which nonces are used by the transaction: the senders of all the batch transactions,
+/// and both the submitter and the accounts of the orders for the swap.
+fn signers(tx: &SignedTxVariant) -> Vec<AccountId> {
+    let txs = match tx {
+        SignedTxVariant::Tx(tx) => std::slice::from_ref(tx),
+        SignedTxVariant::Batch(batch) => batch.txs.as_slice(),
+    };
+    let mut signers = Vec::new();
+    for tx in txs {
+        signers.extend(tx.tx.account_id().ok());
+        if let ZkSyncTx::Swap(swap) = &tx.tx {
+            signers.push(swap.orders.0.account_id);
+            signers.push(swap.orders.1.account_id);
+        }
+    }
+    signers
+}
+
+/// Root of the set the transaction belongs to.
+fn find_group(parents: &mut [usize], mut index: usize) -> usize {
+    while parents[index] != index {
+        parents[index] = parents[parents[index]];
+        index = parents[index];
+    }
+    index
+}
+
+/// Value of the fee (in USD) paid per chunk of the block. Chunks are estimated without
+/// the storage access, i.e. transfers are assumed to go to the existing accounts.
+fn fee_per_chunk(
+    tx: &SignedTxVariant,
+    fee_token_prices: &HashMap<TokenId, Ratio<BigUint>>,
+) -> Ratio<BigUint> {
+    let mut fee = Ratio::from_integer(BigUint::zero());
+    let mut chunks = 0;
+    for tx in tx.get_transactions() {
+        if let Some((_, TokenLike::Id(token_id), _, amount)) = tx.tx.get_fee_info() {
+            if let Some(price) = fee_token_prices.get(&token_id) {
+                fee += price * Ratio::from_integer(amount);
+            }
+        }
+        chunks += tx.tx.min_chunks();
+    }
+    fee / BigUint::from(chunks.max(1))
+}
 
 #[derive(Debug, Clone)]
 struct MempoolPendingTransaction {
     valid_from: u64,
+    valid_until: u64,
     tx: SignedTxVariant,
 }
 
@@ -80,14 +128,15 @@ impl MempoolTransactionsQueue {
     }
 
     fn add_l2_transaction(&mut self, tx: SignedTxVariant) {
+        let txs = tx.get_transactions();
         self.pending_l2_transactions
             .push(MempoolPendingTransaction {
-                valid_from: tx
-                    .get_transactions()
-                    .into_iter()
-                    .map(|tx| tx.tx.valid_from())
-                    .max()
-                    .unwrap_or(0),
+                valid_from: txs.iter().map(|tx| tx.tx.valid_from()).max().unwrap_or(0),
+                valid_until: txs
+                    .iter()
+                    .map(|tx| tx.tx.time_range().valid_until)
+                    .min()
+                    .unwrap_or(u64::MAX),
                 tx,
             });
     }
@@ -99,7 +148,11 @@ impl MempoolTransactionsQueue {
 
             while let Some(pending_tx) = self.pending_l2_transactions.peek() {
                 if pending_tx.valid_from <= block_timestamp {
-                    ready_pending_l2_operations.push(pending_tx.tx.clone());
+                    // Expired transactions are never proposed, they stay in the database
+                    // until they are evicted by the expiration task.
+                    if pending_tx.valid_until >= block_timestamp {
+                        ready_pending_l2_operations.push(pending_tx.tx.clone());
+                    }
                     self.pending_l2_transactions.pop();
                 } else {
                     break;
@@ -125,19 +178,109 @@ impl MempoolTransactionsQueue {
             .append(&mut ready_pending_l2_operations);
     }
 
-    /// Collect txs depending on desired chunks and execution time
+    /// Accounts which nonces are used by the ready transactions.
+    fn ready_l2_accounts(&self) -> Vec<AccountId> {
+        let accounts: HashSet<_> = self
+            .ready_l2_transactions
+            .iter()
+            .flat_map(|tx| tx.get_transactions())
+            .filter_map(|tx| tx.tx.account_id().ok())
+            .collect();
+        accounts.into_iter().collect()
+    }
+
+    /// Removes the ready transactions ahead of the next nonce of their account. The state keeper
+    /// would park them, so they would only take the chunks of the block from the executable ones.
+    /// They stay in the database and are proposed once the missing nonces are queued.
+    fn remove_l2_transactions_with_nonce_gap(&mut self, next_nonces: &HashMap<AccountId, Nonce>) {
+        self.ready_l2_transactions.retain(|tx| {
+            tx.get_transactions()
+                .iter()
+                .all(|tx| match tx.tx.account_id() {
+                    Ok(account_id) => next_nonces
+                        .get(&account_id)
+                        .map_or(true, |next_nonce| tx.tx.nonce() < *next_nonce),
+                    // Transaction of the unknown account fails anyway.
+                    Err(_) => true,
+                })
+        });
+    }
+
+    /// Reorders the ready transactions so the ones paying the most per chunk go first.
+    /// Transactions sharing a signer (e.g. a batch and a transaction of one of its senders) are
+    /// grouped and taken in the original order, so the nonces of every account are respected.
+    /// The group competes with the others by the fee of its next transaction. Ties keep the
+    /// original order.
+    fn order_ready_l2_transactions_by_fee(
+        &mut self,
+        fee_token_prices: &HashMap<TokenId, Ratio<BigUint>>,
+    ) {
+        let txs: Vec<_> = self.ready_l2_transactions.drain(..).collect();
+        let mut parents: Vec<_> = (0..txs.len()).collect();
+        let mut account_txs = HashMap::new();
+        for (position, tx) in txs.iter().enumerate() {
+            for signer in signers(tx) {
+                let other = *account_txs.entry(signer).or_insert(position);
+                let (root, other_root) = (
+                    find_group(&mut parents, position),
+                    find_group(&mut parents, other),
+                );
+                parents[root] = other_root;
+            }
+        }
+
+        let mut group_txs: Vec<VecDeque<(usize, SignedTxVariant)>> = Vec::new();
+        let mut group_indices = HashMap::new();
+        for (position, tx) in txs.into_iter().enumerate() {
+            let group = find_group(&mut parents, position);
+            let index = *group_indices.entry(group).or_insert_with(|| {
+                group_txs.push(VecDeque::new());
+                group_txs.len() - 1
+            });
+            group_txs[index].push_back((position, tx));
+        }
+
+        let next_tx = |index: usize, txs: &VecDeque<(usize, SignedTxVariant)>| {
+            txs.front().map(|(position, tx)| {
+                (
+                    fee_per_chunk(tx, fee_token_prices),
+                    Reverse(*position),
+                    index,
+                )
+            })
+        };
+        let mut next_txs: BinaryHeap<_> = group_txs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, txs)| next_tx(index, txs))
+            .collect();
+        while let Some((_, _, index)) = next_txs.pop() {
+            let (_, tx) = group_txs[index].pop_front().unwrap();
+            self.ready_l2_transactions.push_back(tx);
+            next_txs.extend(next_tx(index, &group_txs[index]));
+        }
+    }
+
+    /// Collect txs depending on desired chunks and execution time.
+    /// If the prices of the fee tokens are provided, txs paying the most per chunk are selected first.
     pub(crate) async fn select_transactions(
         &mut self,
         chunks: usize,
         current_unprocessed_priority_op: u64,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        fee_token_prices: Option<&HashMap<TokenId, Ratio<BigUint>>>,
     ) -> Result<(Vec<SignedTxVariant>, Vec<PriorityOp>, usize), TxAddError> {
         let (chunks_left, priority_ops) =
             self.select_l1_transactions(chunks, current_unprocessed_priority_op);
 
         let (chunks_left, executed_txs) = self
-            .select_l2_transactions(chunks_left, block_timestamp, mempool_state)
+            .select_l2_transactions(
+                chunks_left,
+                block_timestamp,
+                mempool_state,
+                fee_token_prices,
+            )
             .await?;
 
         Ok((executed_txs, priority_ops, chunks_left))
@@ -180,8 +323,18 @@ impl MempoolTransactionsQueue {
         mut chunks_left: usize,
         block_timestamp: u64,
         mempool_state: &MempoolState,
+        fee_token_prices: Option<&HashMap<TokenId, Ratio<BigUint>>>,
     ) -> Result<(usize, Vec<SignedTxVariant>), TxAddError> {
         self.prepare_new_ready_l2_transactions(block_timestamp);
+        if !self.ready_l2_transactions.is_empty() {
+            let next_nonces = mempool_state
+                .load_next_nonces(&self.ready_l2_accounts())
+                .await?;
+            self.remove_l2_transactions_with_nonce_gap(&next_nonces);
+        }
+        if let Some(fee_token_prices) = fee_token_prices {
+            self.order_ready_l2_transactions_by_fee(fee_token_prices);
+        }
 
         let mut txs_for_commit = Vec::new();
 
@@ -230,6 +383,26 @@ mod tests {
         })
     }
 
+    fn get_transfer(account_id: u32, nonce: u32, fee_token: u32, fee: u32) -> SignedTxVariant {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(fee_token),
+            500u32.into(),
+            fee.into(),
+            Nonce(nonce),
+            Default::default(),
+            None,
+        );
+
+        SignedTxVariant::Tx(SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        })
+    }
+
     fn get_withdraw() -> SignedTxVariant {
         let withdraw = Withdraw::new(
             AccountId(3),
@@ -416,4 +589,182 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_expired_transactions() {
+        let mut transactions_queue = MempoolTransactionsQueue {
+            ready_l2_transactions: VecDeque::new(),
+            pending_l2_transactions: BinaryHeap::new(),
+            l1_transactions: Default::default(),
+        };
+
+        let expired_transfer = get_transfer_with_timestamps(0, 9);
+        let transfer = get_transfer_with_timestamps(0, 10);
+        transactions_queue.add_l2_transaction(expired_transfer);
+        transactions_queue.add_l2_transaction(transfer.clone());
+
+        transactions_queue.prepare_new_ready_l2_transactions(10);
+        assert_eq!(transactions_queue.ready_l2_transactions.len(), 1);
+        assert_eq!(
+            transactions_queue.ready_l2_transactions[0].hashes(),
+            transfer.hashes()
+        );
+        assert!(transactions_queue.pending_l2_transactions.is_empty());
+    }
+
+    #[test]
+    fn test_fee_per_chunk_ordering() {
+        let mut transactions_queue = MempoolTransactionsQueue {
+            ready_l2_transactions: VecDeque::new(),
+            pending_l2_transactions: BinaryHeap::new(),
+            l1_transactions: Default::default(),
+        };
+
+        let cheap_first = get_transfer(1, 1, 0, 10);
+        let expensive_second = get_transfer(1, 2, 0, 1000);
+        let medium = get_transfer(2, 1, 0, 100);
+        // Token 1 is twice as expensive as token 0.
+        let medium_in_other_token = get_transfer(5, 1, 1, 60);
+        let unknown_token = get_transfer(4, 1, 2, 1_000_000);
+        let withdraw = get_withdraw();
+
+        for tx in vec![
+            &cheap_first,
+            &expensive_second,
+            &medium,
+            &medium_in_other_token,
+            &unknown_token,
+            &withdraw,
+        ] {
+            transactions_queue.add_l2_transaction(tx.clone());
+        }
+        transactions_queue.prepare_new_ready_l2_transactions(0);
+
+        let fee_token_prices = vec![
+            (TokenId(0), Ratio::from_integer(BigUint::from(1u32))),
+            (TokenId(1), Ratio::from_integer(BigUint::from(2u32))),
+        ]
+        .into_iter()
+        .collect();
+        transactions_queue.order_ready_l2_transactions_by_fee(&fee_token_prices);
+
+        let hashes: Vec<_> = transactions_queue
+            .ready_l2_transactions
+            .iter()
+            .map(|tx| tx.hashes())
+            .collect();
+        assert_eq!(
+            hashes,
+            vec![
+                medium_in_other_token.hashes(),
+                medium.hashes(),
+                // The expensive transaction has to wait for the previous one of the same account.
+                cheap_first.hashes(),
+                expensive_second.hashes(),
+                withdraw.hashes(),
+                // Fee in the token without a known price is worth nothing.
+                unknown_token.hashes(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nonce_gap_transactions_are_removed() {
+        let mut transactions_queue = MempoolTransactionsQueue {
+            ready_l2_transactions: VecDeque::new(),
+            pending_l2_transactions: BinaryHeap::new(),
+            l1_transactions: Default::default(),
+        };
+        let single_tx = |tx: SignedTxVariant| match tx {
+            SignedTxVariant::Tx(tx) => tx,
+            SignedTxVariant::Batch(_) => unreachable!(),
+        };
+
+        let next = get_transfer(1, 5, 0, 10);
+        let parked = get_transfer(1, 7, 0, 10);
+        let parked_batch = SignedTxVariant::batch(
+            vec![
+                single_tx(get_transfer(2, 3, 0, 10)),
+                single_tx(get_transfer(1, 8, 0, 10)),
+            ],
+            1,
+            Vec::new(),
+        );
+        let unknown_account = get_transfer(3, 100, 0, 10);
+        transactions_queue.ready_l2_transactions =
+            vec![next.clone(), parked, parked_batch, unknown_account.clone()].into();
+
+        // The nonce 6 of the account 1 is missing, so its later transactions are parked
+        // along with the batch using them.
+        let next_nonces = vec![(AccountId(1), Nonce(6)), (AccountId(2), Nonce(4))]
+            .into_iter()
+            .collect();
+        let mut accounts = transactions_queue.ready_l2_accounts();
+        accounts.sort_unstable();
+        assert_eq!(accounts, vec![AccountId(1), AccountId(2), AccountId(3)]);
+        transactions_queue.remove_l2_transactions_with_nonce_gap(&next_nonces);
+
+        let hashes: Vec<_> = transactions_queue
+            .ready_l2_transactions
+            .iter()
+            .map(|tx| tx.hashes())
+            .collect();
+        assert_eq!(hashes, vec![next.hashes(), unknown_account.hashes()]);
+    }
+
+    #[test]
+    fn test_fee_ordering_respects_all_signers() {
+        let mut transactions_queue = MempoolTransactionsQueue {
+            ready_l2_transactions: VecDeque::new(),
+            pending_l2_transactions: BinaryHeap::new(),
+            l1_transactions: Default::default(),
+        };
+        let single_tx = |tx: SignedTxVariant| match tx {
+            SignedTxVariant::Tx(tx) => tx,
+            SignedTxVariant::Batch(_) => unreachable!(),
+        };
+
+        let cheap = get_transfer(1, 1, 0, 10);
+        // The fee of the batch is paid by the account 2, but it also uses the next nonce of the account 1.
+        let expensive_batch = SignedTxVariant::batch(
+            vec![
+                single_tx(get_transfer(1, 2, 0, 0)),
+                single_tx(get_transfer(2, 1, 0, 10_000)),
+            ],
+            1,
+            Vec::new(),
+        );
+        let next_after_batch = get_transfer(2, 2, 0, 1000);
+        let medium = get_transfer(3, 1, 0, 100);
+
+        // Ready transactions are already sorted by the nonce.
+        transactions_queue.ready_l2_transactions = vec![
+            cheap.clone(),
+            expensive_batch.clone(),
+            medium.clone(),
+            next_after_batch.clone(),
+        ]
+        .into();
+
+        let fee_token_prices = vec![(TokenId(0), Ratio::from_integer(BigUint::from(1u32)))]
+            .into_iter()
+            .collect();
+        transactions_queue.order_ready_l2_transactions_by_fee(&fee_token_prices);
+
+        let hashes: Vec<_> = transactions_queue
+            .ready_l2_transactions
+            .iter()
+            .map(|tx| tx.hashes())
+            .collect();
+        assert_eq!(
+            hashes,
+            vec![
+                medium.hashes(),
+                // The batch has to wait for the previous transaction of any of its senders.
+                cheap.hashes(),
+                expensive_batch.hashes(),
+                next_after_batch.hashes(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,377 @@
+//! Recovery of the mempool state on restart.
+//!
+//! Server may crash at any point between executing the transactions and removing them from the
+//! mempool, or between storing the transaction and responding to the submitter who then retries
+//! the submission. Before the first block is proposed, the stored transactions are reconciled
+//! with the executed operations: repeated copies and executed transactions are removed, and
+//! the transactions which nonces are used already are expired, so no transaction is proposed twice.
+//! Nonce gaps are only reported, since such transactions are parked until the gap is filled.
+
+// Built-in uses
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::time::Instant;
+
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult};
+use zksync_types::{mempool::SignedTxVariant, tx::TxHash, AccountId, Nonce};
+
+/// Changes of the mempool required to make it consistent with the executed operations.
+#[derive(Debug, Default, PartialEq)]
+struct RecoveryPlan {
+    /// Transactions executed already, they are removed from the mempool.
+    executed: Vec<TxHash>,
+    /// Transactions that can't be executed anymore along with the reasons, they are expired.
+    stale: Vec<(TxHash, String)>,
+    /// Accounts which queued transactions don't continue the committed nonce.
+    nonce_gaps: Vec<AccountId>,
+}
+
+/// Returns the reason to expire the transaction, if its nonce can't be used anymore.
+fn stale_nonce_reason(
+    account_id: AccountId,
+    nonce: Nonce,
+    committed_nonces: &HashMap<AccountId, Nonce>,
+    queued_nonces: &HashMap<AccountId, BTreeSet<Nonce>>,
+) -> Option<String> {
+    let committed_nonce = committed_nonces
+        .get(&account_id)
+        .copied()
+        .unwrap_or_default();
+    if nonce < committed_nonce {
+        Some(format!("Nonce {} is used already", *nonce))
+    } else if queued_nonces
+        .get(&account_id)
+        .map_or(false, |nonces| nonces.contains(&nonce))
+    {
+        Some(format!(
+            "Another transaction with nonce {} is queued earlier",
+            *nonce
+        ))
+    } else {
+        None
+    }
+}
+
+/// Reconciles the queued transactions with the executed ones and the committed nonces of the accounts.
+/// Batches are executed all together, so if any transaction of the batch can't be executed,
+/// the whole batch is dropped.
+fn plan_recovery(
+    txs: &VecDeque<SignedTxVariant>,
+    executed: &HashSet<TxHash>,
+    committed_nonces: &HashMap<AccountId, Nonce>,
+) -> RecoveryPlan {
+    let mut plan = RecoveryPlan::default();
+    let mut seen_hashes = HashSet::new();
+    let mut queued_nonces: HashMap<AccountId, BTreeSet<Nonce>> = HashMap::new();
+
+    for variant in txs {
+        let hashes = variant.hashes();
+        // Repeated copies are removed by the storage, the earliest one is reconciled.
+        if hashes.iter().all(|hash| seen_hashes.contains(hash)) {
+            continue;
+        }
+        seen_hashes.extend(hashes.iter().copied());
+
+        let variant_txs = variant.get_transactions();
+        if hashes.iter().any(|hash| executed.contains(hash)) {
+            for tx in &variant_txs {
+                let tx_hash = tx.hash();
+                if executed.contains(&tx_hash) {
+                    plan.executed.push(tx_hash);
+                } else {
+                    plan.stale
+                        .push((tx_hash, "Part of the batch is executed already".to_string()));
+                }
+            }
+            continue;
+        }
+
+        let mut variant_nonces = Vec::new();
+        let mut reason = None;
+        for tx in &variant_txs {
+            let account_id = match tx.tx.account_id() {
+                Ok(account_id) => account_id,
+                Err(_) => continue,
+            };
+            reason =
+                stale_nonce_reason(account_id, tx.tx.nonce(), committed_nonces, &queued_nonces);
+            if reason.is_some() {
+                break;
+            }
+            variant_nonces.push((account_id, tx.tx.nonce()));
+        }
+
+        match reason {
+            Some(reason) => plan
+                .stale
+                .extend(variant_txs.iter().map(|tx| (tx.hash(), reason.clone()))),
+            None => {
+                for (account_id, nonce) in variant_nonces {
+                    queued_nonces.entry(account_id).or_default().insert(nonce);
+                }
+            }
+        }
+    }
+
+    for (account_id, nonces) in queued_nonces {
+        let mut expected_nonce = committed_nonces
+            .get(&account_id)
+            .copied()
+            .unwrap_or_default();
+        for nonce in nonces {
+            if nonce != expected_nonce {
+                plan.nonce_gaps.push(account_id);
+                break;
+            }
+            expected_nonce = expected_nonce + 1;
+        }
+    }
+    plan.nonce_gaps.sort_unstable();
+
+    plan
+}
+
+/// Makes the stored mempool consistent with the executed operations, should be called before
+/// the first block is proposed.
+pub(crate) async fn recover_mempool(db_pool: &ConnectionPool) -> QueryResult<()> {
+    let start = Instant::now();
+    let mut storage = db_pool.access_storage().await?;
+    let mut transaction = storage.start_transaction().await?;
+
+    let duplicates = transaction
+        .chain()
+        .mempool_schema()
+        .remove_duplicate_txs()
+        .await?;
+    let txs = transaction.chain().mempool_schema().load_txs(&[]).await?;
+
+    let tx_hashes: Vec<_> = txs.iter().flat_map(|tx| tx.hashes()).collect();
+    let executed = transaction
+        .chain()
+        .mempool_schema()
+        .get_executed_tx_hashes(&tx_hashes)
+        .await?;
+    let mut committed_nonces = HashMap::new();
+    for tx in txs.iter().flat_map(|tx| tx.get_transactions()) {
+        if let Ok(account_id) = tx.tx.account_id() {
+            if !committed_nonces.contains_key(&account_id) {
+                let nonce = transaction
+                    .chain()
+                    .account_schema()
+                    .estimate_nonce(account_id)
+                    .await?
+                    .unwrap_or_default();
+                committed_nonces.insert(account_id, nonce);
+            }
+        }
+    }
+
+    let plan = plan_recovery(&txs, &executed, &committed_nonces);
+    transaction
+        .chain()
+        .mempool_schema()
+        .remove_txs(&plan.executed)
+        .await?;
+    transaction
+        .chain()
+        .mempool_schema()
+        .expire_txs(&plan.stale)
+        .await?;
+    transaction.commit().await?;
+
+    vlog::info!(
+        "Mempool is recovered: removed {} repeated and {} executed transactions, expired {} transactions, \
+         {} accounts have nonce gaps",
+        duplicates,
+        plan.executed.len(),
+        plan.stale.len(),
+        plan.nonce_gaps.len()
+    );
+    metrics::counter!("mempool.recovery.duplicate_txs", duplicates);
+    metrics::counter!("mempool.recovery.executed_txs", plan.executed.len() as u64);
+    metrics::counter!("mempool.recovery.stale_txs", plan.stale.len() as u64);
+    metrics::gauge!(
+        "mempool.recovery.nonce_gap_accounts",
+        plan.nonce_gaps.len() as f64
+    );
+    metrics::histogram!("mempool.recovery", start.elapsed());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use zksync_types::{
+        mempool::SignedTxsBatch,
+        tx::{TimeRange, Transfer},
+        Address, SignedZkSyncTx, TokenId, ZkSyncTx,
+    };
+
+    fn transfer(account_id: u32, nonce: u32) -> SignedZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(account_id),
+            Address::repeat_byte(account_id as u8),
+            Address::random(),
+            TokenId(0),
+            500u32.into(),
+            20u32.into(),
+            Nonce(nonce),
+            TimeRange::default(),
+            None,
+        );
+
+        SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn batch(txs: Vec<SignedZkSyncTx>) -> SignedTxVariant {
+        SignedTxVariant::Batch(SignedTxsBatch {
+            txs,
+            batch_id: 1,
+            eth_signatures: Vec::new(),
+        })
+    }
+
+    fn committed(nonces: &[(u32, u32)]) -> HashMap<AccountId, Nonce> {
+        nonces
+            .iter()
+            .map(|(account_id, nonce)| (AccountId(*account_id), Nonce(*nonce)))
+            .collect()
+    }
+
+    /// Crash before anything is executed: the mempool is kept as is.
+    #[test]
+    fn nothing_executed() {
+        let txs = vec![
+            SignedTxVariant::Tx(transfer(1, 0)),
+            SignedTxVariant::Tx(transfer(1, 1)),
+            batch(vec![transfer(2, 5), transfer(3, 0)]),
+        ]
+        .into();
+
+        let plan = plan_recovery(&txs, &HashSet::new(), &committed(&[(2, 5)]));
+        assert_eq!(plan, RecoveryPlan::default());
+    }
+
+    /// Crash after the block is executed, but before its transactions are removed from the mempool.
+    #[test]
+    fn executed_not_removed() {
+        let executed_tx = transfer(1, 0);
+        let executed_batch = vec![transfer(2, 0), transfer(3, 0)];
+        let queued_tx = transfer(1, 1);
+        let txs = vec![
+            SignedTxVariant::Tx(executed_tx.clone()),
+            batch(executed_batch.clone()),
+            SignedTxVariant::Tx(queued_tx),
+        ]
+        .into();
+        let executed = vec![
+            executed_tx.hash(),
+            executed_batch[0].hash(),
+            executed_batch[1].hash(),
+        ];
+
+        let plan = plan_recovery(
+            &txs,
+            &executed.iter().copied().collect(),
+            &committed(&[(1, 1), (2, 1), (3, 1)]),
+        );
+        assert_eq!(
+            plan,
+            RecoveryPlan {
+                executed,
+                ..Default::default()
+            }
+        );
+    }
+
+    /// Crash in the middle of storing the executed batch: the rest of the batch can't be executed on its own.
+    #[test]
+    fn batch_partially_executed() {
+        let batch_txs = vec![transfer(1, 0), transfer(2, 0)];
+        let txs = vec![batch(batch_txs.clone())].into();
+        let executed = vec![batch_txs[0].hash()].into_iter().collect();
+
+        let plan = plan_recovery(&txs, &executed, &committed(&[(1, 1)]));
+        assert_eq!(
+            plan,
+            RecoveryPlan {
+                executed: vec![batch_txs[0].hash()],
+                stale: vec![(
+                    batch_txs[1].hash(),
+                    "Part of the batch is executed already".to_string()
+                )],
+                ..Default::default()
+            }
+        );
+    }
+
+    /// Crash after the transaction is stored, but before the response: the submission is retried.
+    #[test]
+    fn submission_retried() {
+        let tx = transfer(1, 0);
+        let txs = vec![SignedTxVariant::Tx(tx.clone()), SignedTxVariant::Tx(tx)].into();
+
+        let plan = plan_recovery(&txs, &HashSet::new(), &HashMap::new());
+        assert_eq!(plan, RecoveryPlan::default());
+    }
+
+    /// Crash after the replacement is executed, but before the replaced transaction is removed,
+    /// or after two transactions with the same nonce are queued.
+    #[test]
+    fn nonce_used() {
+        let replaced_tx = transfer(1, 0);
+        let first_tx = transfer(2, 3);
+        let second_tx = transfer(2, 3);
+        let batch_txs = vec![transfer(3, 0), transfer(2, 3)];
+        let txs = vec![
+            SignedTxVariant::Tx(replaced_tx.clone()),
+            SignedTxVariant::Tx(first_tx),
+            SignedTxVariant::Tx(second_tx.clone()),
+            batch(batch_txs.clone()),
+        ]
+        .into();
+
+        let plan = plan_recovery(&txs, &HashSet::new(), &committed(&[(1, 1), (2, 3)]));
+        let queued_reason = "Another transaction with nonce 3 is queued earlier".to_string();
+        assert_eq!(
+            plan,
+            RecoveryPlan {
+                stale: vec![
+                    (replaced_tx.hash(), "Nonce 0 is used already".to_string()),
+                    (second_tx.hash(), queued_reason.clone()),
+                    // The whole batch is dropped because of the one transaction.
+                    (batch_txs[0].hash(), queued_reason.clone()),
+                    (batch_txs[1].hash(), queued_reason),
+                ],
+                ..Default::default()
+            }
+        );
+    }
+
+    /// Transactions with the nonce gaps are kept, since they are parked until the gap is filled.
+    #[test]
+    fn nonce_gaps() {
+        let txs = vec![
+            SignedTxVariant::Tx(transfer(1, 2)),
+            SignedTxVariant::Tx(transfer(2, 0)),
+            SignedTxVariant::Tx(transfer(2, 2)),
+            SignedTxVariant::Tx(transfer(3, 1)),
+            SignedTxVariant::Tx(transfer(3, 0)),
+        ]
+        .into();
+
+        let plan = plan_recovery(&txs, &HashSet::new(), &committed(&[(1, 1)]));
+        assert_eq!(
+            plan,
+            RecoveryPlan {
+                nonce_gaps: vec![AccountId(1), AccountId(2)],
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -1,14 +1,16 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 
+use num::{rational::Ratio, BigUint};
+
 use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     mempool::{SignedTxVariant, SignedTxsBatch},
     tx::{error::TxAddError, TxHash},
-    Address, TransferOp, TransferToNewOp, ZkSyncTx,
+    AccountId, Address, Nonce, TokenId, TransferOp, TransferToNewOp, ZkSyncTx,
 };
 
-use crate::MempoolTransactionsQueue;
+use crate::{recovery::recover_mempool, MempoolTransactionsQueue};
 
 #[derive(Debug, Clone)]
 pub(crate) struct MempoolState {
@@ -98,16 +100,79 @@ impl MempoolState {
         }
     }
 
-    pub async fn collect_garbage(&self) {
-        let mut storage = self.db_pool.access_storage().await.expect("Db error");
-        // Remove any possible duplicates of already executed transactions
-        // from the database.
-        storage
+    /// Loads the next nonce of every given account which the transactions can't skip: the first
+    /// one after the committed nonce that is not used by the mempool transactions. Transactions
+    /// executed in the pending block are still stored in the mempool, so their nonces are counted
+    /// even if the committed nonce is not updated yet. Unknown accounts are omitted.
+    pub async fn load_next_nonces(
+        &self,
+        account_ids: &[AccountId],
+    ) -> Result<HashMap<AccountId, Nonce>, TxAddError> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let committed_nonces = storage
+            .chain()
+            .account_schema()
+            .estimate_nonces(account_ids)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let mut queued_nonces = storage
             .chain()
             .mempool_schema()
-            .collect_garbage()
+            .get_queued_nonces(account_ids)
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        Ok(committed_nonces
+            .into_iter()
+            .map(|(account_id, mut nonce)| {
+                let queued = queued_nonces.remove(&account_id).unwrap_or_default();
+                while queued.contains(&nonce) {
+                    nonce = nonce + 1;
+                }
+                (account_id, nonce)
+            })
+            .collect())
+    }
+
+    /// Reconciles the stored transactions with the executed operations,
+    /// so the transactions are not proposed twice after the restart.
+    pub async fn recover(&self) {
+        recover_mempool(&self.db_pool).await.expect("Db error");
+    }
+
+    /// Loads the prices in USD of the smallest units of the tokens, the tokens without
+    /// a known price are skipped.
+    pub async fn load_fee_token_prices(
+        &self,
+    ) -> Result<HashMap<TokenId, Ratio<BigUint>>, TxAddError> {
+        let mut storage = self
+            .db_pool
+            .access_storage()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+        let tokens = storage
+            .tokens_schema()
+            .load_tokens()
             .await
-            .expect("Db error");
+            .map_err(|_| TxAddError::DbError)?;
+        let prices = storage
+            .tokens_schema()
+            .load_historical_ticker_prices()
+            .await
+            .map_err(|_| TxAddError::DbError)?;
+
+        Ok(prices
+            .into_iter()
+            .filter_map(|(token_id, price)| {
+                let token = tokens.get(&token_id)?;
+                let unit = BigUint::from(10u32).pow(token.decimals as u32);
+                Some((token_id, price.usd_price / unit))
+            })
+            .collect())
     }
 
     pub fn new(db_pool: ConnectionPool) -> Self {
@@ -1,21 +1,41 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::time::Duration;
+
 use futures::channel::{mpsc, oneshot};
 use futures::StreamExt;
+use tokio::time::Instant;
 
-use zksync_storage::ConnectionPool;
+use zksync_storage::{ConnectionPool, StorageProcessor};
 use zksync_types::{
     mempool::SignedTxsBatch,
-    tx::{error::TxAddError, TxEthSignature},
-    PriorityOp, SignedZkSyncTx,
+    tx::{error::TxAddError, TxEthSignature, TxHash},
+    AccountId, Nonce, PriorityOp, SignedZkSyncTx, ZkSyncTx,
 };
 
 use crate::state::MempoolState;
 
+/// Maximum number of the submitted transactions inserted with a single statement.
+const MAX_COALESCED_TXS: usize = 100;
+
+type TxAddResponse = oneshot::Sender<Result<Option<TxHash>, TxAddError>>;
+
 #[derive(Debug)]
 pub enum MempoolTransactionRequest {
     /// Add new transaction to mempool, transaction should be previously checked
     /// for correctness (including its Ethereum and ZKSync signatures).
-    /// oneshot is used to receive tx add result.
-    NewTx(Box<SignedZkSyncTx>, oneshot::Sender<Result<(), TxAddError>>),
+    /// The queued transaction of the same account with the same nonce is replaced
+    /// if the new one pays a higher fee.
+    /// oneshot is used to receive tx add result: the hash of the replaced transaction, if any.
+    NewTx(Box<SignedZkSyncTx>, TxAddResponse),
+    /// Replace the queued transaction with the given hash by the new one requested by the sender,
+    /// the new transaction should be previously checked for correctness and have the same account
+    /// and nonce. The transactions are swapped in a single database transaction.
+    /// oneshot is used to receive the result: `false` if the transaction is not queued anymore.
+    ReplaceTx(
+        TxHash,
+        Box<SignedZkSyncTx>,
+        oneshot::Sender<Result<bool, TxAddError>>,
+    ),
 
     /// Add new priority ops, confirmed or not
     NewPriorityOps(
@@ -32,6 +52,105 @@ pub enum MempoolTransactionRequest {
         Vec<TxEthSignature>,
         oneshot::Sender<Result<(), TxAddError>>,
     ),
+    /// Store the latest Ethereum block seen by the Ethereum watcher.
+    UpdateLastEthBlock(u64, oneshot::Sender<Result<(), TxAddError>>),
+}
+
+/// Checks whether the new transaction pays a strictly higher fee in the same token than the queued one.
+pub fn pays_higher_fee(new_tx: &ZkSyncTx, queued_tx: &ZkSyncTx) -> bool {
+    match (new_tx.get_fee_info(), queued_tx.get_fee_info()) {
+        (Some((_, new_token, _, new_fee)), Some((_, queued_token, _, queued_fee))) => {
+            new_token == queued_token && new_fee > queued_fee
+        }
+        _ => false,
+    }
+}
+
+/// Nonces of the account known to the mempool.
+#[derive(Debug, Clone)]
+struct AccountNonces {
+    /// Nonce of the account after the committed transactions.
+    committed: Nonce,
+    /// Nonces of the queued transactions of the account.
+    queued: HashSet<Nonce>,
+    /// Next nonce after the queued transactions following the committed nonce without a gap.
+    next: Nonce,
+}
+
+impl AccountNonces {
+    fn new(committed: Nonce, queued: HashSet<Nonce>) -> Self {
+        let mut nonces = Self {
+            committed,
+            queued,
+            next: committed,
+        };
+        nonces.advance_next();
+        nonces
+    }
+
+    fn advance_next(&mut self) {
+        while self.queued.contains(&self.next) {
+            self.next = self.next + 1;
+        }
+    }
+
+    /// Checks the nonce of the new transaction against the committed nonce of the account
+    /// and the next nonce after the transactions already queued for the account.
+    /// Nonces ahead of the next one are accepted within the `max_nonce_gap` window,
+    /// such transactions are parked by the state keeper until the gap is filled.
+    /// The window starts from the first missing nonce, so the parked transactions
+    /// can't move it further.
+    fn check(&self, nonce: Nonce, max_nonce_gap: u32) -> Result<(), TxAddError> {
+        if nonce < self.committed {
+            return Err(TxAddError::NonceMismatch);
+        }
+        if *nonce > self.next.saturating_add(max_nonce_gap) {
+            return Err(TxAddError::NonceTooFarAhead);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the transaction with the given nonce is already queued for the account.
+    fn is_queued(&self, nonce: Nonce) -> bool {
+        self.queued.contains(&nonce)
+    }
+
+    /// Records the nonce of the transaction added to the mempool.
+    fn add(&mut self, nonce: Nonce) {
+        self.queued.insert(nonce);
+        self.advance_next();
+    }
+
+    /// Forgets the nonce of the transaction that failed to be stored.
+    fn remove(&mut self, nonce: Nonce) {
+        self.queued.remove(&nonce);
+        if nonce < self.next {
+            self.next = nonce;
+        }
+    }
+}
+
+/// Loads the committed nonce of the account and the nonces of its queued transactions.
+async fn load_account_nonces(
+    storage: &mut StorageProcessor<'_>,
+    account_id: AccountId,
+) -> Result<AccountNonces, TxAddError> {
+    let committed_nonce = storage
+        .chain()
+        .account_schema()
+        .estimate_nonce(account_id)
+        .await
+        .map_err(|_| TxAddError::DbError)?
+        .unwrap_or_default();
+    let queued_nonces = storage
+        .chain()
+        .mempool_schema()
+        .get_queued_nonces(&[account_id])
+        .await
+        .map_err(|_| TxAddError::DbError)?
+        .remove(&account_id)
+        .unwrap_or_default();
+    Ok(AccountNonces::new(committed_nonce, queued_nonces))
 }
 
 pub(crate) struct MempoolTransactionsHandler {
@@ -39,48 +158,225 @@ pub(crate) struct MempoolTransactionsHandler {
     pub mempool_state: MempoolState,
     pub requests: mpsc::Receiver<MempoolTransactionRequest>,
     pub max_block_size_chunks: usize,
+    pub max_nonce_gap: u32,
+    /// Time to wait for more submitted transactions to insert them together.
+    pub insert_window: Duration,
 }
 
 impl MempoolTransactionsHandler {
-    async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
-        // Correctness should be checked by `signature_checker`, thus
-        // `tx.check_correctness()` is not invoked here.
-        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
-            vlog::error!("Mempool storage access error: {}", err);
-            TxAddError::DbError
-        })?;
+    /// Adds the submitted transactions to the mempool, returning the result for every transaction.
+    /// Transactions that don't replace the queued ones are inserted with a single statement,
+    /// the others are processed one by one after the preceding transactions are inserted.
+    async fn add_txs(&mut self, txs: &[SignedZkSyncTx]) -> Vec<Result<Option<TxHash>, TxAddError>> {
+        let mut storage = match self.db_pool.access_storage().await {
+            Ok(storage) => storage,
+            Err(err) => {
+                vlog::error!("Mempool storage access error: {}", err);
+                return vec![Err(TxAddError::DbError); txs.len()];
+            }
+        };
 
-        let nonce = storage
-            .chain()
-            .account_schema()
+        let mut results = Vec::with_capacity(txs.len());
+        // Indices of the transactions waiting for the insertion.
+        let mut pending = Vec::new();
+        // Nonces used by the preceding transactions of the same account.
+        let mut account_nonces: HashMap<AccountId, AccountNonces> = HashMap::new();
+        for (idx, tx) in txs.iter().enumerate() {
+            // Correctness should be checked by `signature_checker`, thus
+            // `tx.check_correctness()` is not invoked here.
             // Close operation does not exist so we will never met this error
-            .estimate_nonce(tx.account_id().map_err(|_| TxAddError::Other)?)
+            let account_id = match tx.account_id() {
+                Ok(account_id) => account_id,
+                Err(_) => {
+                    results.push(Err(TxAddError::Other));
+                    continue;
+                }
+            };
+            let nonces = match account_nonces.entry(account_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => match load_account_nonces(&mut storage, account_id).await {
+                    Ok(nonces) => entry.insert(nonces),
+                    Err(err) => {
+                        results.push(Err(err));
+                        continue;
+                    }
+                },
+            };
+            if let Err(err) = nonces.check(tx.nonce(), self.max_nonce_gap) {
+                results.push(Err(err));
+                continue;
+            }
+            let replaces_queued_tx = nonces.is_queued(tx.nonce());
+            nonces.add(tx.nonce());
+
+            if !replaces_queued_tx {
+                pending.push(idx);
+                results.push(Ok(None));
+                continue;
+            }
+            // The replaced transaction may be one of the pending ones.
+            Self::insert_pending_txs(
+                &mut storage,
+                txs,
+                &mut pending,
+                &mut results,
+                &mut account_nonces,
+            )
+            .await;
+            results.push(Self::replace_or_insert_tx(&mut storage, account_id, tx).await);
+        }
+        Self::insert_pending_txs(
+            &mut storage,
+            txs,
+            &mut pending,
+            &mut results,
+            &mut account_nonces,
+        )
+        .await;
+
+        for (tx, result) in txs.iter().zip(&results) {
+            if result.is_ok() {
+                let labels = vec![
+                    ("stage", "mempool".to_string()),
+                    ("name", tx.tx.variance_name()),
+                    ("token", tx.tx.token_id().to_string()),
+                ];
+                metrics::histogram!("process_tx", tx.elapsed(), &labels);
+            }
+        }
+        results
+    }
+
+    /// Inserts the pending transactions with a single statement. If the insertion fails,
+    /// the transactions are inserted one by one, so a single faulty transaction doesn't
+    /// reject the others. Nonces of the rejected transactions are forgotten, so the following
+    /// transactions of the same accounts are checked as if they were not submitted.
+    async fn insert_pending_txs(
+        storage: &mut StorageProcessor<'_>,
+        txs: &[SignedZkSyncTx],
+        pending: &mut Vec<usize>,
+        results: &mut [Result<Option<TxHash>, TxAddError>],
+        account_nonces: &mut HashMap<AccountId, AccountNonces>,
+    ) {
+        if pending.is_empty() {
+            return;
+        }
+        metrics::histogram!("mempool.coalesced_txs", pending.len() as f64);
+        let pending_txs: Vec<_> = pending.iter().map(|&idx| txs[idx].clone()).collect();
+        let err = match storage
+            .chain()
+            .mempool_schema()
+            .insert_txs(&pending_txs)
+            .await
+        {
+            Ok(()) => {
+                pending.clear();
+                return;
+            }
+            Err(err) => err,
+        };
+
+        vlog::warn!(
+            "Failed to insert {} transactions together, inserting them one by one: {}",
+            pending.len(),
+            err
+        );
+        for idx in pending.drain(..) {
+            let tx = &txs[idx];
+            if let Err(err) = storage.chain().mempool_schema().insert_tx(tx).await {
+                vlog::error!("Mempool storage access error: {}", err);
+                results[idx] = Err(TxAddError::DbError);
+                if let Some(nonces) = tx
+                    .account_id()
+                    .ok()
+                    .and_then(|account_id| account_nonces.get_mut(&account_id))
+                {
+                    nonces.remove(tx.nonce());
+                }
+            }
+        }
+    }
+
+    /// Replaces the queued transaction of the account with the same nonce if there is one,
+    /// inserts the transaction otherwise.
+    async fn replace_or_insert_tx(
+        storage: &mut StorageProcessor<'_>,
+        account_id: AccountId,
+        tx: &SignedZkSyncTx,
+    ) -> Result<Option<TxHash>, TxAddError> {
+        // Transaction with the same nonce can only be replaced by the one paying a higher fee.
+        let queued_tx = storage
+            .chain()
+            .mempool_schema()
+            .get_queued_tx_by_nonce(account_id, tx.nonce())
             .await
             .map_err(|_| TxAddError::DbError)?
-            .unwrap_or_default();
+            .filter(|queued_tx| queued_tx.hash() != tx.hash());
+        let mut replaced_tx_hash = None;
+        if let Some(queued_tx) = queued_tx {
+            if !pays_higher_fee(&tx.tx, &queued_tx.tx) {
+                return Err(TxAddError::ReplacementFeeTooLow);
+            }
+            let replaced = storage
+                .chain()
+                .mempool_schema()
+                .replace_tx(queued_tx.hash(), tx)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+            if replaced {
+                metrics::increment_counter!("mempool.replaced_txs");
+                replaced_tx_hash = Some(queued_tx.hash());
+            }
+        }
 
-        if tx.nonce() < nonce {
-            return Err(TxAddError::NonceMismatch);
+        // The queued transaction may have been executed or removed in the meantime.
+        if replaced_tx_hash.is_none() {
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_tx(tx)
+                .await
+                .map_err(|err| {
+                    vlog::error!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
         }
 
-        storage
+        Ok(replaced_tx_hash)
+    }
+
+    async fn replace_tx(
+        &mut self,
+        tx_hash: TxHash,
+        tx: SignedZkSyncTx,
+    ) -> Result<bool, TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let replaced = storage
             .chain()
             .mempool_schema()
-            .insert_tx(&tx)
+            .replace_tx(tx_hash, &tx)
             .await
             .map_err(|err| {
                 vlog::error!("Mempool storage access error: {}", err);
                 TxAddError::DbError
             })?;
 
-        let labels = vec![
-            ("stage", "mempool".to_string()),
-            ("name", tx.tx.variance_name()),
-            ("token", tx.tx.token_id().to_string()),
-        ];
-        metrics::histogram!("process_tx", tx.elapsed(), &labels);
-
-        Ok(())
+        if replaced {
+            metrics::increment_counter!("mempool.replaced_txs");
+            let labels = vec![
+                ("stage", "mempool".to_string()),
+                ("name", tx.tx.variance_name()),
+                ("token", tx.tx.token_id().to_string()),
+            ];
+            metrics::histogram!("process_tx", tx.elapsed(), &labels);
+        }
+        Ok(replaced)
     }
 
     /// Add priority operations to the mempool. For a better UX, we save unconfirmed transactions
@@ -133,6 +429,22 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
+    async fn update_last_eth_block(&mut self, block_number: u64) -> Result<(), TxAddError> {
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            vlog::error!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        storage
+            .chain()
+            .mempool_schema()
+            .update_last_watched_eth_block(block_number)
+            .await
+            .map_err(|err| {
+                vlog::error!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })
+    }
+
     async fn add_batch(
         &mut self,
         txs: Vec<SignedZkSyncTx>,
@@ -149,21 +461,21 @@ impl MempoolTransactionsHandler {
             TxAddError::DbError
         })?;
 
+        // Nonces used by the preceding transactions of the batch.
+        let mut account_nonces: HashMap<AccountId, AccountNonces> = HashMap::new();
         for tx in txs.iter() {
             // Correctness should be checked by `signature_checker`, thus
             // `tx.check_correctness()` is not invoked here.
-            let nonce = storage
-                .chain()
-                .account_schema()
-                // Close operation does not exist so we will never met this error
-                .estimate_nonce(tx.account_id().map_err(|_| TxAddError::Other)?)
-                .await
-                .map_err(|_| TxAddError::DbError)?
-                .unwrap_or_default();
-
-            if tx.nonce() < nonce {
-                return Err(TxAddError::NonceMismatch);
-            }
+            // Close operation does not exist so we will never met this error
+            let account_id = tx.account_id().map_err(|_| TxAddError::Other)?;
+            let nonces = match account_nonces.entry(account_id) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(load_account_nonces(&mut storage, account_id).await?)
+                }
+            };
+            nonces.check(tx.nonce(), self.max_nonce_gap)?;
+            nonces.add(tx.nonce());
         }
 
         if self.mempool_state.chunks_for_batch(&batch).await? > self.max_block_size_chunks {
@@ -193,13 +505,58 @@ impl MempoolTransactionsHandler {
         Ok(())
     }
 
+    /// Collects the transactions already queued after the first one, for at most the insert window.
+    /// Doesn't wait for the new submissions, so the transactions are inserted at once if no more
+    /// submissions are queued. Returns the request that can't be coalesced with the transactions,
+    /// if it's queued before them.
+    fn collect_new_txs(
+        &mut self,
+        txs: &mut Vec<(SignedZkSyncTx, TxAddResponse)>,
+    ) -> Option<MempoolTransactionRequest> {
+        let deadline = Instant::now() + self.insert_window;
+        while txs.len() < MAX_COALESCED_TXS && Instant::now() < deadline {
+            match self.requests.try_next() {
+                Ok(Some(MempoolTransactionRequest::NewTx(tx, resp))) => txs.push((*tx, resp)),
+                Ok(request) => return request,
+                // No more submissions are queued.
+                Err(_) => break,
+            }
+        }
+        None
+    }
+
+    async fn handle_new_txs(&mut self, txs: Vec<(SignedZkSyncTx, TxAddResponse)>) {
+        let (txs, responses): (Vec<_>, Vec<_>) = txs.into_iter().unzip();
+        let results = self.add_txs(&txs).await;
+        for (resp, tx_add_result) in responses.into_iter().zip(results) {
+            resp.send(tx_add_result).unwrap_or_default();
+        }
+    }
+
     pub async fn run(mut self) {
         vlog::info!("Transaction mempool handler is running");
-        while let Some(request) = self.requests.next().await {
+        let mut next_request = None;
+        loop {
+            let request = match next_request.take() {
+                Some(request) => request,
+                None => match self.requests.next().await {
+                    Some(request) => request,
+                    None => break,
+                },
+            };
             match request {
                 MempoolTransactionRequest::NewTx(tx, resp) => {
-                    let tx_add_result = self.add_tx(*tx).await;
-                    resp.send(tx_add_result).unwrap_or_default();
+                    // Transactions submitted during the spike are inserted together,
+                    // every submitter still receives the result of its own transaction.
+                    let mut txs = vec![(*tx, resp)];
+                    if !self.insert_window.is_zero() {
+                        next_request = self.collect_new_txs(&mut txs);
+                    }
+                    self.handle_new_txs(txs).await;
+                }
+                MempoolTransactionRequest::ReplaceTx(tx_hash, tx, resp) => {
+                    let result = self.replace_tx(tx_hash, *tx).await;
+                    resp.send(result).unwrap_or_default();
                 }
                 MempoolTransactionRequest::NewTxsBatch(txs, eth_signatures, resp) => {
                     let tx_add_result = self.add_batch(txs, eth_signatures).await;
@@ -209,7 +566,128 @@ impl MempoolTransactionsHandler {
                     let tx_add_result = self.add_priority_ops(ops, confirmed).await;
                     resp.send(tx_add_result).unwrap_or_default();
                 }
+                MempoolTransactionRequest::UpdateLastEthBlock(block_number, resp) => {
+                    let result = self.update_last_eth_block(block_number).await;
+                    resp.send(result).unwrap_or_default();
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zksync_types::{
+        tx::{Transfer, Withdraw},
+        AccountId, Address, Nonce, TokenId,
+    };
+
+    fn transfer(fee: u32, fee_token: u32) -> ZkSyncTx {
+        let transfer = Transfer::new(
+            AccountId(42),
+            Address::zero(),
+            Address::zero(),
+            TokenId(fee_token),
+            100u32.into(),
+            fee.into(),
+            Nonce(1),
+            Default::default(),
+            None,
+        );
+        ZkSyncTx::Transfer(Box::new(transfer))
+    }
+
+    #[test]
+    fn replacement_fee() {
+        let queued_tx = transfer(10, 0);
+
+        assert!(pays_higher_fee(&transfer(11, 0), &queued_tx));
+        assert!(!pays_higher_fee(&transfer(10, 0), &queued_tx));
+        assert!(!pays_higher_fee(&transfer(9, 0), &queued_tx));
+        // Fees in different tokens are not comparable.
+        assert!(!pays_higher_fee(&transfer(100, 1), &queued_tx));
+
+        let withdraw = Withdraw::new(
+            AccountId(42),
+            Address::zero(),
+            Address::zero(),
+            TokenId(0),
+            100u32.into(),
+            20u32.into(),
+            Nonce(1),
+            Default::default(),
+            None,
+        );
+        assert!(pays_higher_fee(
+            &ZkSyncTx::Withdraw(Box::new(withdraw)),
+            &queued_tx
+        ));
+    }
+
+    #[test]
+    fn nonce_window() {
+        // Nonces 5 and 6 are queued.
+        let nonces = AccountNonces::new(Nonce(5), vec![Nonce(5), Nonce(6)].into_iter().collect());
+        assert_eq!(nonces.next, Nonce(7));
+
+        assert!(matches!(
+            nonces.check(Nonce(4), 2),
+            Err(TxAddError::NonceMismatch)
+        ));
+        // Replacement of the queued transaction.
+        assert!(nonces.check(Nonce(5), 2).is_ok());
+        assert!(nonces.is_queued(Nonce(5)));
+        assert!(nonces.check(Nonce(7), 2).is_ok());
+        // Transactions ahead of the next nonce are parked.
+        assert!(nonces.check(Nonce(9), 2).is_ok());
+        assert!(matches!(
+            nonces.check(Nonce(10), 2),
+            Err(TxAddError::NonceTooFarAhead)
+        ));
+        assert!(matches!(
+            nonces.check(Nonce(8), 0),
+            Err(TxAddError::NonceTooFarAhead)
+        ));
+    }
+
+    #[test]
+    fn parked_txs_dont_move_nonce_window() {
+        let mut nonces = AccountNonces::new(Nonce(5), HashSet::new());
+        nonces.add(Nonce(7));
+        // Nonce 5 is still missing, so the window is not moved by the parked transaction.
+        assert_eq!(nonces.next, Nonce(5));
+        assert!(nonces.check(Nonce(7), 2).is_ok());
+        assert!(matches!(
+            nonces.check(Nonce(8), 2),
+            Err(TxAddError::NonceTooFarAhead)
+        ));
+
+        // Once the gap is filled, the window starts after the parked transactions.
+        nonces.add(Nonce(5));
+        assert_eq!(nonces.next, Nonce(6));
+        nonces.add(Nonce(6));
+        assert_eq!(nonces.next, Nonce(8));
+        assert!(nonces.check(Nonce(10), 2).is_ok());
+    }
+
+    #[test]
+    fn failed_txs_release_nonces() {
+        let mut nonces = AccountNonces::new(Nonce(5), vec![Nonce(5)].into_iter().collect());
+        nonces.add(Nonce(6));
+        nonces.add(Nonce(7));
+        assert_eq!(nonces.next, Nonce(8));
+
+        // Transaction with the nonce 6 failed to be stored, so the window starts from it again.
+        nonces.remove(Nonce(6));
+        assert!(!nonces.is_queued(Nonce(6)));
+        assert_eq!(nonces.next, Nonce(6));
+        assert!(matches!(
+            nonces.check(Nonce(9), 2),
+            Err(TxAddError::NonceTooFarAhead)
+        ));
+        // Removing the parked transaction doesn't move the window.
+        nonces.remove(Nonce(7));
+        assert_eq!(nonces.next, Nonce(6));
+    }
+}
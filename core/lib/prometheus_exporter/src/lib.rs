@@ -3,7 +3,7 @@
 use metrics_exporter_prometheus::PrometheusBuilder;
 use num::rational::Ratio;
 use num::{BigUint, ToPrimitive};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Add;
 use std::time::{Duration, Instant};
 use tokio::task::JoinHandle;
@@ -18,8 +18,11 @@ const QUERY_INTERVAL: Duration = Duration::from_secs(30);
 
 pub fn run_operation_counter(connection_pool: ConnectionPool) -> JoinHandle<()> {
     tokio::spawn(async move {
+        let mut mempool_tx_types = HashSet::new();
         loop {
-            if let Err(e) = prometheus_exporter_iteration(connection_pool.clone()).await {
+            if let Err(e) =
+                prometheus_exporter_iteration(connection_pool.clone(), &mut mempool_tx_types).await
+            {
                 vlog::error!("Prometheus error: {}", e);
             }
             sleep(QUERY_INTERVAL).await;
@@ -27,7 +30,12 @@ pub fn run_operation_counter(connection_pool: ConnectionPool) -> JoinHandle<()>
     })
 }
 
-async fn prometheus_exporter_iteration(connection_pool: ConnectionPool) -> QueryResult<()> {
+/// `mempool_tx_types` are the transaction types reported on the previous iterations,
+/// the depth of the types that have left the mempool is reset to zero.
+async fn prometheus_exporter_iteration(
+    connection_pool: ConnectionPool,
+    mempool_tx_types: &mut HashSet<String>,
+) -> QueryResult<()> {
     let mut storage = connection_pool.access_storage().await?;
     let mut transaction = storage.start_transaction().await?;
 
@@ -51,12 +59,26 @@ async fn prometheus_exporter_iteration(connection_pool: ConnectionPool) -> Query
 
     metrics::gauge!("stored_rejected_txs", rejected_txs as f64);
 
-    let mempool_size = transaction
+    let mempool_stats = transaction
         .chain()
         .mempool_schema()
-        .get_mempool_size()
+        .get_mempool_stats()
         .await?;
-    metrics::gauge!("mempool_size", mempool_size as f64);
+    metrics::gauge!("mempool_size", mempool_stats.size as f64);
+    metrics::gauge!("mempool_batched_txs", mempool_stats.batched_txs as f64);
+    metrics::gauge!(
+        "mempool_oldest_tx_age_sec",
+        mempool_stats.oldest_tx_age_sec.unwrap_or(0) as f64
+    );
+    for tx_type in mempool_tx_types.iter() {
+        if !mempool_stats.txs_by_type.contains_key(tx_type) {
+            metrics::gauge!("mempool_depth", 0.0, "type" => tx_type.clone());
+        }
+    }
+    for (tx_type, count) in mempool_stats.txs_by_type {
+        metrics::gauge!("mempool_depth", count as f64, "type" => tx_type.clone());
+        mempool_tx_types.insert(tx_type);
+    }
 
     transaction.commit().await?;
     Ok(())
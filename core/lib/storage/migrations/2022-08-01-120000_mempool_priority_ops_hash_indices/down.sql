@@ -0,0 +1 @@
+DROP INDEX IF EXISTS mempool_priority_operations_eth_hash_idx;
@@ -0,0 +1,2 @@
+CREATE INDEX IF NOT EXISTS mempool_priority_operations_eth_hash_idx
+    ON mempool_priority_operations USING hash (eth_hash);
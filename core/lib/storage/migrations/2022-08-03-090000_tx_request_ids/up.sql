@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS tx_request_ids (
+    account_id BIGINT NOT NULL,
+    request_id TEXT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, request_id)
+);
+CREATE INDEX IF NOT EXISTS tx_request_ids_created_at_idx ON tx_request_ids (created_at);
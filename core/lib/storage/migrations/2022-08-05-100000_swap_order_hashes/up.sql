@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS swap_orders (
+    order_hash BYTEA NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    sequence_number BIGINT NOT NULL,
+    PRIMARY KEY (order_hash, tx_hash)
+);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS mint_nft_updates_creator_account_id_nonce_idx;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS mint_nft_updates_creator_account_id_nonce_idx ON mint_nft_updates (creator_account_id, nonce);
@@ -0,0 +1,7 @@
+-- The latest Ethereum block seen by the Ethereum watcher.
+CREATE TABLE last_watched_eth_block (
+    -- enforce single record
+    id bool PRIMARY KEY NOT NULL DEFAULT true,
+    CONSTRAINT single_last_watched_eth_block CHECK (id),
+    block_number BIGINT NOT NULL
+);
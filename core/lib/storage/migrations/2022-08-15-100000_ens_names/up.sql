@@ -0,0 +1,6 @@
+-- Cache of the ENS names resolved through the Ethereum node.
+CREATE TABLE IF NOT EXISTS ens_names (
+    name TEXT PRIMARY KEY,
+    address BYTEA NOT NULL,
+    resolved_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS blocks_commitment_idx;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS blocks_commitment_idx ON blocks USING hash (commitment);
@@ -0,0 +1 @@
+ALTER TABLE eth_operations ADD COLUMN IF NOT EXISTS gas_used BIGINT;
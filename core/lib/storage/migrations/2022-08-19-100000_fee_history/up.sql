@@ -0,0 +1,13 @@
+-- Fees quoted by the fee ticker along with the prices they were calculated from.
+CREATE TABLE IF NOT EXISTS fee_history (
+    id BIGSERIAL PRIMARY KEY,
+    tx_type TEXT NOT NULL,
+    token_id INTEGER NOT NULL,
+    gas_price_wei NUMERIC NOT NULL,
+    token_price_usd NUMERIC NOT NULL,
+    gas_fee NUMERIC NOT NULL,
+    zkp_fee NUMERIC NOT NULL,
+    total_fee NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+CREATE INDEX IF NOT EXISTS fee_history_created_at_idx ON fee_history (created_at);
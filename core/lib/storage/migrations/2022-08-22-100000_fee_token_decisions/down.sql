@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fee_token_decisions;
+DROP TABLE IF EXISTS fee_tokens;
@@ -0,0 +1,15 @@
+-- Current decision of the fee token policy for each token.
+CREATE TABLE IF NOT EXISTS fee_tokens (
+    token_id INTEGER PRIMARY KEY REFERENCES tokens(id) ON UPDATE CASCADE,
+    is_fee_token BOOLEAN NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+-- History of the fee token policy decisions.
+CREATE TABLE IF NOT EXISTS fee_token_decisions (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    is_fee_token BOOLEAN NOT NULL,
+    market_volume NUMERIC NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX IF NOT EXISTS fee_token_decisions_token_id_idx ON fee_token_decisions (token_id);
@@ -0,0 +1,9 @@
+-- Every token price fetched by the fee ticker.
+CREATE TABLE IF NOT EXISTS token_price_history (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    usd_price NUMERIC NOT NULL,
+    last_updated TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX IF NOT EXISTS token_price_history_token_id_last_updated_idx
+    ON token_price_history (token_id, last_updated);
@@ -0,0 +1,6 @@
+-- Budgets of the subsidies, USD amounts are scaled by 10^6 as in the `subsidies` table.
+CREATE TABLE IF NOT EXISTS subsidy_budgets (
+    subsidy_type VARCHAR PRIMARY KEY,
+    budget_usd_scale6 BIGINT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
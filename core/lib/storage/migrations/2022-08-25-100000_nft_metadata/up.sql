@@ -0,0 +1,6 @@
+-- JSON metadata of the NFTs fetched from IPFS by their content hash.
+CREATE TABLE IF NOT EXISTS nft_metadata (
+    token_id INTEGER PRIMARY KEY,
+    metadata JSONB NOT NULL,
+    fetched_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
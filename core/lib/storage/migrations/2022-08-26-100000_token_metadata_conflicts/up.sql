@@ -0,0 +1,9 @@
+-- Mismatches between the stored token metadata and the ERC-20 contracts waiting for the operator review.
+CREATE TABLE IF NOT EXISTS token_metadata_conflicts (
+    token_id INTEGER PRIMARY KEY REFERENCES tokens(id) ON UPDATE CASCADE,
+    stored_symbol TEXT NOT NULL,
+    stored_decimals SMALLINT NOT NULL,
+    contract_symbol TEXT NOT NULL,
+    contract_decimals SMALLINT NOT NULL,
+    detected_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
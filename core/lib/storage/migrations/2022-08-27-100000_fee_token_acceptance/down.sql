@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fee_token_acceptance_changes;
+ALTER TABLE fee_tokens DROP COLUMN IF EXISTS manual;
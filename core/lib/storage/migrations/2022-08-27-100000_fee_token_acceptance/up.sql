@@ -0,0 +1,12 @@
+-- Tokens accepted or rejected for the fee payment by the operator are not managed by the fee token policy.
+ALTER TABLE fee_tokens ADD COLUMN IF NOT EXISTS manual BOOLEAN NOT NULL DEFAULT false;
+-- Audit log of the manual changes of the token acceptance for the fee payment.
+CREATE TABLE IF NOT EXISTS fee_token_acceptance_changes (
+    id BIGSERIAL PRIMARY KEY,
+    token_id INTEGER NOT NULL REFERENCES tokens(id) ON UPDATE CASCADE,
+    -- NULL means that the manual override was removed.
+    is_fee_token BOOLEAN,
+    changed_by TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX IF NOT EXISTS fee_token_acceptance_changes_token_id_idx ON fee_token_acceptance_changes (token_id);
@@ -0,0 +1 @@
+ALTER TABLE ticker_price DROP COLUMN IF EXISTS source;
@@ -0,0 +1,2 @@
+-- Name of the price source the stored price was fetched from.
+ALTER TABLE ticker_price ADD COLUMN IF NOT EXISTS source TEXT;
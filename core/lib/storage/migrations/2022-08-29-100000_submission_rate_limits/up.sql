@@ -0,0 +1,7 @@
+-- Token buckets limiting the transaction submissions, shared by all the API replicas.
+-- Key is either `ip:<address>` or `account:<address>`.
+CREATE TABLE IF NOT EXISTS submission_rate_limits (
+    key TEXT PRIMARY KEY,
+    tokens DOUBLE PRECISION NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -0,0 +1,9 @@
+-- Transactions evicted from the mempool without being executed.
+-- Kept so the receipt of the transaction can tell why it will never be executed.
+CREATE TABLE IF NOT EXISTS expired_mempool_txs (
+    tx_hash TEXT PRIMARY KEY,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    expired_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+CREATE INDEX IF NOT EXISTS expired_mempool_txs_expired_at_index ON expired_mempool_txs (expired_at);
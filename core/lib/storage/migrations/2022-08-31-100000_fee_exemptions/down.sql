@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS fee_exemption_usages;
+DROP TABLE IF EXISTS fee_exemptions;
@@ -0,0 +1,19 @@
+-- Sender addresses and transaction types exempted from the fee checks by the operator.
+-- Kind is either `sender` or `txType`.
+CREATE TABLE IF NOT EXISTS fee_exemptions (
+    kind TEXT NOT NULL,
+    value TEXT NOT NULL,
+    added_by TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (kind, value)
+);
+-- Audit log of the transactions accepted without the fee check.
+CREATE TABLE IF NOT EXISTS fee_exemption_usages (
+    id BIGSERIAL PRIMARY KEY,
+    tx_hash BYTEA NOT NULL,
+    sender BYTEA NOT NULL,
+    tx_type TEXT NOT NULL,
+    exemption_kind TEXT NOT NULL,
+    exemption_value TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
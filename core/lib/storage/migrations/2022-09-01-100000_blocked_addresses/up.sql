@@ -0,0 +1,9 @@
+-- Addresses blocked by the operator. List is either `sender` or `recipient`.
+CREATE TABLE IF NOT EXISTS blocked_addresses (
+    address BYTEA NOT NULL,
+    list TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    added_by TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (address, list)
+);
@@ -0,0 +1,8 @@
+-- Settings of the transaction submission overridden by the operator at runtime.
+-- Values are JSON in the format of the corresponding `TxSenderSettings` field.
+CREATE TABLE IF NOT EXISTS tx_sender_settings (
+    name TEXT NOT NULL PRIMARY KEY,
+    value JSONB NOT NULL,
+    updated_by TEXT NOT NULL,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
@@ -0,0 +1,12 @@
+-- Transactions submitted with the asynchronous verification that haven't reached the mempool.
+-- A transaction is stored as pending once it's accepted and is removed once it's queued in the
+-- mempool. If it fails the verification, the reason is kept so the receipt can tell why
+-- the transaction never reached the mempool.
+CREATE TABLE IF NOT EXISTS async_submitted_txs (
+    tx_hash TEXT PRIMARY KEY,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    rejected_at TIMESTAMP WITH TIME ZONE
+);
+CREATE INDEX IF NOT EXISTS async_submitted_txs_updated_at_index
+    ON async_submitted_txs (COALESCE(rejected_at, created_at));
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS mempool_txs_account_nonce_index;
@@ -0,0 +1,8 @@
+-- Transactions of the account are looked up by the ID of the account paying the fee and by the nonce
+-- (see `MempoolSchema::get_queued_tx_by_nonce`), the expression should match the one used by the queries.
+CREATE INDEX IF NOT EXISTS mempool_txs_account_nonce_index ON mempool_txs (
+    (COALESCE(
+        tx->>'accountId', tx->>'submitterId', tx->>'creatorId', tx->>'initiatorAccountId'
+    )::bigint),
+    ((tx->>'nonce')::bigint)
+);
@@ -0,0 +1 @@
+ALTER TABLE tx_request_ids DROP COLUMN IF EXISTS submitted;
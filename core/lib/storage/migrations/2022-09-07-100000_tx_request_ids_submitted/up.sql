@@ -0,0 +1 @@
+ALTER TABLE tx_request_ids ADD COLUMN IF NOT EXISTS submitted BOOLEAN NOT NULL DEFAULT false;
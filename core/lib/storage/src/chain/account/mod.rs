@@ -1,12 +1,15 @@
 // Built-in deps
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 // External imports
+use chrono::{DateTime, Utc};
 use num::{BigUint, Zero};
 use sqlx::{types::BigDecimal, Acquire};
 // Workspace imports
+use zksync_api_types::v02::account::{BalanceHistoryResolution, BalancePoint};
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_STORAGE_ACCOUNT_ID, NFT_TOKEN_ID};
 use zksync_types::{
-    Account, AccountId, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash, TokenId,
+    Account, AccountId, AccountUpdate, AccountUpdates, Address, BlockNumber, Nonce, PubKeyHash,
+    TokenId,
 };
 // Local imports
 use self::records::*;
@@ -111,6 +114,39 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(current_nonce.map(|v| Nonce(v as u32)))
     }
 
+    /// Same as `estimate_nonce`, but for several accounts at once.
+    /// Accounts that are neither committed nor verified are omitted.
+    pub async fn estimate_nonces(
+        &mut self,
+        account_ids: &[AccountId],
+    ) -> QueryResult<HashMap<AccountId, Nonce>> {
+        let start = Instant::now();
+        let account_ids: Vec<_> = account_ids.iter().map(|id| i64::from(**id)).collect();
+
+        let records = sqlx::query!(
+            r#"
+            SELECT u.account_id AS "account_id!", COALESCE(committed_nonce.nonce, accounts.nonce) AS nonce
+            FROM UNNEST($1::bigint[]) AS u(account_id)
+            LEFT JOIN committed_nonce ON committed_nonce.account_id = u.account_id
+            LEFT JOIN accounts ON accounts.id = u.account_id
+            "#,
+            &account_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let nonces = records
+            .into_iter()
+            .filter_map(|record| {
+                let nonce = record.nonce?;
+                Some((AccountId(record.account_id as u32), Nonce(nonce as u32)))
+            })
+            .collect();
+
+        metrics::histogram!("sql.chain.account.estimate_nonces", start.elapsed());
+        Ok(nonces)
+    }
+
     /// Fetches account type from the database
     pub async fn account_type_by_id(
         &mut self,
@@ -327,6 +363,186 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(((last_block, account), account_state))
     }
 
+    /// Reconstructs the state of the account as of the given block.
+    /// The state is restored from the last verified state of the account: the updates of the
+    /// blocks committed after it are applied, and the updates made after the given block are
+    /// reverted, so only the updates between the given and the last verified blocks are loaded.
+    /// Returns the state together with the number of the last block that updated the account.
+    pub async fn account_state_for_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<(BlockNumber, Account)>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let (last_block, verified_state) = AccountSchema(&mut transaction)
+            .account_and_last_block(account_id)
+            .await?;
+        let last_verified_block = BlockSchema(&mut transaction)
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        let result = if block_number >= last_verified_block {
+            let updates = AccountSchema(&mut transaction)
+                .account_updates_in_range(account_id, last_verified_block, block_number)
+                .await?;
+            let last_update_in_block = match updates.last() {
+                Some((block, _)) => *block,
+                None => BlockNumber(last_block as u32),
+            };
+            let account_state = updates
+                .into_iter()
+                .map(|(_, upd)| upd)
+                .fold(verified_state, Account::apply_update);
+            account_state.map(|account| (last_update_in_block, account))
+        } else {
+            let updates = AccountSchema(&mut transaction)
+                .account_updates_in_range(account_id, block_number, last_verified_block)
+                .await?;
+            let account_state = updates
+                .into_iter()
+                .rev()
+                .map(|(_, upd)| upd.reversed_update())
+                .fold(verified_state, Account::apply_update);
+            match account_state {
+                Some(account) => {
+                    let last_update_in_block = AccountSchema(&mut transaction)
+                        .last_update_block(account_id, block_number)
+                        .await?
+                        .unwrap_or_default();
+                    Some((last_update_in_block, account))
+                }
+                None => None,
+            }
+        };
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.account.account_state_for_block", start.elapsed());
+        Ok(result)
+    }
+
+    /// Loads the updates of the account made in the blocks `(from_block, to_block]`
+    /// in the order they were applied.
+    async fn account_updates_in_range(
+        &mut self,
+        account_id: AccountId,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<(BlockNumber, AccountUpdate)>> {
+        let account_balance_diff = sqlx::query_as!(
+            StorageAccountUpdate,
+            "
+                SELECT * FROM account_balance_updates
+                WHERE account_id = $1 AND block_number > $2 AND block_number <= $3
+            ",
+            i64::from(*account_id),
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let account_creation_diff = sqlx::query_as!(
+            StorageAccountCreation,
+            "
+                SELECT * FROM account_creates
+                WHERE account_id = $1 AND block_number > $2 AND block_number <= $3
+            ",
+            i64::from(*account_id),
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let account_pubkey_diff = sqlx::query_as!(
+            StorageAccountPubkeyUpdate,
+            "
+                SELECT * FROM account_pubkey_updates
+                WHERE account_id = $1 AND block_number > $2 AND block_number <= $3
+            ",
+            i64::from(*account_id),
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let mint_nft_updates = sqlx::query_as!(
+            StorageMintNFTUpdate,
+            "
+                SELECT * FROM mint_nft_updates
+                WHERE creator_account_id = $1 AND block_number > $2 AND block_number <= $3
+            ",
+            *account_id as i32,
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut account_diff = Vec::new();
+        account_diff.extend(
+            account_balance_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_creation_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(
+            account_pubkey_diff
+                .into_iter()
+                .map(StorageAccountDiff::from),
+        );
+        account_diff.extend(mint_nft_updates.into_iter().map(StorageAccountDiff::from));
+        account_diff.sort_by(StorageAccountDiff::cmp_order);
+
+        Ok(account_diff
+            .into_iter()
+            .map(|diff| {
+                let block_number = BlockNumber(diff.block_number() as u32);
+                let (_, update) = diff.into();
+                (block_number, update)
+            })
+            .collect())
+    }
+
+    /// Returns the number of the last block not greater than the given one that updated the account.
+    async fn last_update_block(
+        &mut self,
+        account_id: AccountId,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let last_block = sqlx::query!(
+            r#"
+                SELECT MAX(block_number) as "max?" FROM (
+                    SELECT MAX(block_number) as block_number FROM account_balance_updates
+                    WHERE account_id = $1 AND block_number <= $2
+                    UNION ALL
+                    SELECT MAX(block_number) FROM account_creates
+                    WHERE account_id = $1 AND block_number <= $2
+                    UNION ALL
+                    SELECT MAX(block_number) FROM account_pubkey_updates
+                    WHERE account_id = $1 AND block_number <= $2
+                    UNION ALL
+                    SELECT MAX(block_number) FROM mint_nft_updates
+                    WHERE creator_account_id = $1 AND block_number <= $2
+                ) AS updates
+            "#,
+            i64::from(*account_id),
+            i64::from(*block_number)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .max;
+
+        Ok(last_block.map(|block| BlockNumber(block as u32)))
+    }
+
     /// Loads the last verified state for the account (i.e. the one obtained in the last block
     /// which was both committed and verified).
     pub async fn last_verified_state_for_account(
@@ -348,7 +564,7 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         account_id: AccountId,
     ) -> QueryResult<(i64, Option<Account>)> {
         let start = Instant::now();
-        let mut transaction = self.0.conn().begin().await?;
+        let mut transaction = self.0.pg_conn().begin().await?;
 
         // `accounts::table` is updated only after the block verification, so we should
         // just load the account with the provided ID.
@@ -430,6 +646,80 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(account_id)
     }
 
+    /// Resolves ids of several accounts by their addresses at once.
+    /// Addresses of the accounts that don't exist are absent in the result.
+    pub async fn account_ids_by_addresses(
+        &mut self,
+        addresses: &[Address],
+    ) -> QueryResult<HashMap<Address, AccountId>> {
+        let start = Instant::now();
+        let addresses: Vec<Vec<u8>> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let records = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (address) address, account_id FROM account_creates
+                WHERE address = ANY($1) AND is_create = true
+                ORDER BY address, block_number DESC
+            "#,
+            &addresses
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let account_ids = records
+            .into_iter()
+            .map(|record| {
+                (
+                    Address::from_slice(&record.address),
+                    AccountId(record.account_id as u32),
+                )
+            })
+            .collect();
+        metrics::histogram!(
+            "sql.chain.account.account_ids_by_addresses",
+            start.elapsed()
+        );
+        Ok(account_ids)
+    }
+
+    /// Returns ids of the accounts which have the given public key hash in the committed state.
+    pub async fn account_ids_by_pubkey_hash(
+        &mut self,
+        pub_key_hash: &PubKeyHash,
+    ) -> QueryResult<Vec<AccountId>> {
+        let start = Instant::now();
+        let records = sqlx::query!(
+            r#"
+                SELECT account_id FROM (
+                    SELECT DISTINCT ON (account_id) account_id, new_pubkey_hash
+                    FROM account_pubkey_updates
+                    WHERE account_id IN (
+                        SELECT account_id FROM account_pubkey_updates
+                        WHERE new_pubkey_hash = $1
+                    )
+                    ORDER BY account_id, block_number DESC, update_order_id DESC
+                ) AS last_updates
+                WHERE new_pubkey_hash = $1
+                ORDER BY account_id
+            "#,
+            &pub_key_hash.data[..]
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let account_ids = records
+            .into_iter()
+            .map(|record| AccountId(record.account_id as u32))
+            .collect();
+        metrics::histogram!(
+            "sql.chain.account.account_ids_by_pubkey_hash",
+            start.elapsed()
+        );
+        Ok(account_ids)
+    }
+
     pub async fn account_address_by_id(
         &mut self,
         account_id: AccountId,
@@ -448,6 +738,67 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(address)
     }
 
+    /// Returns the number of the block in which the account was created.
+    pub async fn account_creation_block(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let result = sqlx::query!(
+            r#"
+                SELECT block_number FROM account_creates
+                WHERE account_id = $1 AND is_create = true
+                ORDER BY block_number DESC
+                LIMIT 1
+            "#,
+            i64::from(*account_id)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        let block_number = result.map(|record| BlockNumber(record.block_number as u32));
+        metrics::histogram!("sql.chain.account.account_creation_block", start.elapsed());
+        Ok(block_number)
+    }
+
+    /// Loads the addresses of the given accounts from the `account_creates` table.
+    /// Accounts that were not found are omitted from the result.
+    pub async fn account_addresses_by_ids(
+        &mut self,
+        account_ids: &[AccountId],
+    ) -> QueryResult<HashMap<AccountId, Address>> {
+        let start = Instant::now();
+        let account_ids: Vec<i64> = account_ids
+            .iter()
+            .map(|account_id| i64::from(**account_id))
+            .collect();
+        let records = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (account_id) account_id, address FROM account_creates
+                WHERE account_id = ANY($1) AND is_create = true
+                ORDER BY account_id, block_number DESC
+            "#,
+            &account_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let addresses = records
+            .into_iter()
+            .map(|record| {
+                (
+                    AccountId(record.account_id as u32),
+                    Address::from_slice(&record.address),
+                )
+            })
+            .collect();
+        metrics::histogram!(
+            "sql.chain.account.account_addresses_by_ids",
+            start.elapsed()
+        );
+        Ok(addresses)
+    }
+
     /// Obtains the last committed block that affects the account.
     pub async fn last_committed_block_with_update_for_acc(
         &mut self,
@@ -535,6 +886,107 @@ impl<'a, 'c> AccountSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Returns the balance of the account in the given token at the end of every
+    /// `resolution` period within `[from, to]` that changed it. If the account had
+    /// the balance before `from`, it is returned as the first point with `from` timestamp.
+    pub async fn get_account_balance_history(
+        &mut self,
+        account_id: AccountId,
+        token_id: TokenId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        resolution: BalanceHistoryResolution,
+    ) -> QueryResult<Vec<BalancePoint>> {
+        let start = Instant::now();
+        let period = match resolution {
+            BalanceHistoryResolution::Hour => "hour",
+            BalanceHistoryResolution::Day => "day",
+            BalanceHistoryResolution::Week => "week",
+            BalanceHistoryResolution::Month => "month",
+        };
+        let mut transaction = self.0.start_transaction().await?;
+
+        let initial_balance = sqlx::query!(
+            r#"
+                SELECT account_balance_updates.block_number, account_balance_updates.new_balance
+                FROM account_balance_updates
+                INNER JOIN blocks ON blocks.number = account_balance_updates.block_number
+                WHERE account_balance_updates.account_id = $1
+                    AND account_balance_updates.coin_id = $2
+                    AND to_timestamp(blocks.timestamp) < $3
+                ORDER BY account_balance_updates.block_number DESC,
+                    account_balance_updates.update_order_id DESC
+                LIMIT 1
+            "#,
+            i64::from(*account_id),
+            *token_id as i32,
+            from
+        )
+        .fetch_optional(transaction.conn())
+        .await?
+        .map(|record| BalancePoint {
+            timestamp: from,
+            block_number: BlockNumber(record.block_number as u32),
+            balance: record
+                .new_balance
+                .to_bigint()
+                .unwrap()
+                .to_biguint()
+                .unwrap()
+                .into(),
+        });
+
+        // For every period only the latest balance update is taken.
+        // Periods are aligned in UTC regardless of the database time zone.
+        let records = sqlx::query!(
+            r#"
+                SELECT DISTINCT ON (date_trunc($5, to_timestamp(blocks.timestamp), 'UTC'))
+                    date_trunc($5, to_timestamp(blocks.timestamp), 'UTC') as "period!",
+                    account_balance_updates.block_number,
+                    account_balance_updates.new_balance
+                FROM account_balance_updates
+                INNER JOIN blocks ON blocks.number = account_balance_updates.block_number
+                WHERE account_balance_updates.account_id = $1
+                    AND account_balance_updates.coin_id = $2
+                    AND to_timestamp(blocks.timestamp) BETWEEN $3 AND $4
+                ORDER BY date_trunc($5, to_timestamp(blocks.timestamp), 'UTC'),
+                    account_balance_updates.block_number DESC,
+                    account_balance_updates.update_order_id DESC
+            "#,
+            i64::from(*account_id),
+            *token_id as i32,
+            from,
+            to,
+            period
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        let history = initial_balance
+            .into_iter()
+            .chain(records.into_iter().map(|record| {
+                BalancePoint {
+                    timestamp: record.period,
+                    block_number: BlockNumber(record.block_number as u32),
+                    balance: record
+                        .new_balance
+                        .to_bigint()
+                        .unwrap()
+                        .to_biguint()
+                        .unwrap()
+                        .into(),
+                }
+            }))
+            .collect();
+
+        metrics::histogram!(
+            "sql.chain.account.get_account_balance_history",
+            start.elapsed()
+        );
+        Ok(history)
+    }
+
     pub async fn get_account_nft_balance(&mut self, address: Address) -> QueryResult<u32> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -195,6 +195,13 @@ impl NewExecutedTransaction {
             .into_iter()
             .map(|id| id.0 as i32)
             .collect();
+        let order_hashes = match &exec_tx.signed_tx.tx {
+            ZkSyncTx::Swap(swap) => vec![
+                swap.orders.0.hash().as_ref().to_vec(),
+                swap.orders.1.hash().as_ref().to_vec(),
+            ],
+            _ => Vec::new(),
+        };
         Ok(Self {
             block_number: i64::from(*block),
             tx_hash: exec_tx.signed_tx.hash().as_ref().to_vec(),
@@ -212,6 +219,7 @@ impl NewExecutedTransaction {
             batch_id: exec_tx.batch_id,
             affected_accounts,
             used_tokens,
+            order_hashes,
         })
     }
 }
@@ -257,6 +265,7 @@ impl TransactionItem {
             tx_hash,
             block_index: item.block_index.map(|i| i as u32),
             block_number,
+            time_range: op.time_range(),
             op,
             status,
             fail_reason: item.fail_reason,
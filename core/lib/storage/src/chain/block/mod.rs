@@ -1,6 +1,8 @@
 // Built-in deps
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 // External imports
+use chrono::Duration;
+use num::bigint::ToBigInt;
 // Workspace imports
 use zksync_api_types::{
     v02::{
@@ -14,12 +16,12 @@ use zksync_types::{
     aggregated_operations::AggregatedActionType,
     block::{Block, BlockMetadata, ExecutedOperations, IncompleteBlock, PendingBlock},
     event::block::BlockStatus,
-    AccountId, BlockNumber, Fr, ZkSyncOp, H256, U256,
+    AccountId, BlockNumber, Fr, TokenId, ZkSyncOp, H256, U256,
 };
 // Local imports
 use self::records::{
-    BlockTransactionItem, StorageBlock, StorageBlockDetails, StorageBlockMetadata,
-    StoragePendingBlock, StorageRootHash, TransactionItem,
+    BlockTransactionItem, BlocksStats, StorageBlock, StorageBlockDetails, StorageBlockMetadata,
+    StorageBlockTimestamps, StoragePendingBlock, StorageRootHash, TransactionItem,
 };
 use crate::{
     chain::operations::{
@@ -392,6 +394,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 blocks.number AS "block_number!",
                 blocks.root_hash AS "new_state_root!",
                 blocks.block_size AS "block_size!",
+                (
+                    SELECT COUNT(*) FROM executed_transactions
+                    WHERE executed_transactions.block_number = blocks.number
+                ) + (
+                    SELECT COUNT(*) FROM executed_priority_operations
+                    WHERE executed_priority_operations.block_number = blocks.number
+                ) AS "tx_count!",
                 committed.final_hash AS "commit_tx_hash?",
                 verified.final_hash AS "verify_tx_hash?",
                 committed.created_at AS "committed_at!",
@@ -456,6 +465,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 blocks.number AS "block_number!",
                 blocks.root_hash AS "new_state_root!",
                 blocks.block_size AS "block_size!",
+                (
+                    SELECT COUNT(*) FROM executed_transactions
+                    WHERE executed_transactions.block_number = blocks.number
+                ) + (
+                    SELECT COUNT(*) FROM executed_priority_operations
+                    WHERE executed_priority_operations.block_number = blocks.number
+                ) AS "tx_count!",
                 committed.final_hash AS "commit_tx_hash?",
                 verified.final_hash AS "verify_tx_hash?",
                 committed.created_at AS "committed_at!",
@@ -587,6 +603,13 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 blocks.number AS "block_number!",
                 blocks.root_hash AS "new_state_root!",
                 blocks.block_size AS "block_size!",
+                (
+                    SELECT COUNT(*) FROM executed_transactions
+                    WHERE executed_transactions.block_number = blocks.number
+                ) + (
+                    SELECT COUNT(*) FROM executed_priority_operations
+                    WHERE executed_priority_operations.block_number = blocks.number
+                ) AS "tx_count!",
                 committed.final_hash AS "commit_tx_hash?",
                 verified.final_hash AS "verify_tx_hash?",
                 committed.created_at AS "committed_at!",
@@ -723,6 +746,92 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block_number <= last_finalized_block)
     }
 
+    /// Returns the average time passed between the commitment and the execution of a block,
+    /// computed over the last `blocks_count` executed blocks.
+    /// Returns `None` if no blocks were executed yet.
+    pub async fn average_block_finalization_time(
+        &mut self,
+        blocks_count: u32,
+    ) -> QueryResult<Option<Duration>> {
+        let start = Instant::now();
+        let average_secs = sqlx::query!(
+            r#"
+            WITH last_executed AS (
+                SELECT
+                    execute_aggregated_blocks_binding.block_number,
+                    aggregate_operations.created_at
+                FROM aggregate_operations
+                    INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+                WHERE aggregate_operations.confirmed = true
+                ORDER BY execute_aggregated_blocks_binding.block_number DESC
+                LIMIT $1
+            )
+            SELECT
+                AVG(EXTRACT(EPOCH FROM last_executed.created_at - aggregate_operations.created_at))::float8 AS "average_secs"
+            FROM last_executed
+                INNER JOIN commit_aggregated_blocks_binding ON commit_aggregated_blocks_binding.block_number = last_executed.block_number
+                INNER JOIN aggregate_operations ON aggregate_operations.id = commit_aggregated_blocks_binding.op_id
+            "#,
+            i64::from(blocks_count)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .average_secs;
+
+        metrics::histogram!(
+            "sql.chain.block.average_block_finalization_time",
+            start.elapsed()
+        );
+        Ok(average_secs.map(|secs| Duration::milliseconds((secs * 1000.0) as i64)))
+    }
+
+    /// Loads the times of the confirmed commit and execute operations for the given blocks.
+    /// Blocks that were not committed yet are omitted from the result.
+    pub async fn load_blocks_timestamps(
+        &mut self,
+        blocks: &[BlockNumber],
+    ) -> QueryResult<Vec<StorageBlockTimestamps>> {
+        let start = Instant::now();
+        let block_numbers: Vec<i64> = blocks.iter().map(|block| i64::from(**block)).collect();
+        let timestamps = sqlx::query_as!(
+            StorageBlockTimestamps,
+            r#"
+            WITH committed AS (
+                SELECT
+                    commit_aggregated_blocks_binding.block_number,
+                    MAX(aggregate_operations.created_at) AS created_at
+                FROM aggregate_operations
+                    INNER JOIN commit_aggregated_blocks_binding ON aggregate_operations.id = commit_aggregated_blocks_binding.op_id
+                WHERE aggregate_operations.confirmed = true
+                    AND commit_aggregated_blocks_binding.block_number = ANY($1)
+                GROUP BY commit_aggregated_blocks_binding.block_number
+            ),
+            executed AS (
+                SELECT
+                    execute_aggregated_blocks_binding.block_number,
+                    MAX(aggregate_operations.created_at) AS created_at
+                FROM aggregate_operations
+                    INNER JOIN execute_aggregated_blocks_binding ON aggregate_operations.id = execute_aggregated_blocks_binding.op_id
+                WHERE aggregate_operations.confirmed = true
+                    AND execute_aggregated_blocks_binding.block_number = ANY($1)
+                GROUP BY execute_aggregated_blocks_binding.block_number
+            )
+            SELECT
+                committed.block_number AS "block_number!",
+                committed.created_at AS "committed_at!",
+                executed.created_at AS "finalized_at?"
+            FROM committed
+                LEFT JOIN executed ON committed.block_number = executed.block_number
+            "#,
+            &block_numbers
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.block.load_blocks_timestamps", start.elapsed());
+        Ok(timestamps)
+    }
+
     pub async fn pending_block_chunks_left(&mut self) -> QueryResult<Option<usize>> {
         let start = Instant::now();
         let maybe_block_chunks = sqlx::query!(
@@ -1539,6 +1648,116 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(block_number)
     }
 
+    /// Returns the number of the block with the given commitment.
+    pub async fn get_block_number_by_commitment(
+        &mut self,
+        commitment: &[u8],
+    ) -> QueryResult<Option<BlockNumber>> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            "SELECT number FROM blocks WHERE commitment = $1 ORDER BY number LIMIT 1",
+            commitment
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+        let block_number = record.map(|r| BlockNumber(r.number as u32));
+
+        metrics::histogram!(
+            "sql.chain.block.get_block_number_by_commitment",
+            start.elapsed()
+        );
+        Ok(block_number)
+    }
+
+    /// Collects the statistics of the transactions executed in the blocks
+    /// from `from_block` to `to_block` inclusive.
+    pub async fn get_blocks_stats(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<BlocksStats> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let (from_block, to_block) = (i64::from(*from_block), i64::from(*to_block));
+
+        let tx_counts = sqlx::query!(
+            r#"
+            SELECT tx_type AS "tx_type!", COUNT(*) AS "count!" FROM (
+                SELECT tx->>'type' AS tx_type FROM executed_transactions
+                WHERE block_number BETWEEN $1 AND $2
+                UNION ALL
+                SELECT operation->>'type' AS tx_type FROM executed_priority_operations
+                WHERE block_number BETWEEN $1 AND $2
+            ) AS txs
+            GROUP BY tx_type
+            "#,
+            from_block,
+            to_block
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| (record.tx_type, record.count as u64))
+        .collect();
+
+        // Fee token is stored as `feeToken` for the transactions that may pay fee in a
+        // token other than the transferred one and as `token` for the rest of them.
+        let fees = sqlx::query!(
+            r#"
+            SELECT COALESCE(tx->>'feeToken', tx->>'token')::integer AS "token_id!",
+                SUM((tx->>'fee')::numeric) AS "total_fee!"
+            FROM executed_transactions
+            WHERE block_number BETWEEN $1 AND $2 AND success = true
+                AND tx->>'fee' IS NOT NULL
+            GROUP BY 1
+            "#,
+            from_block,
+            to_block
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| {
+            (
+                TokenId(record.token_id as u32),
+                record.total_fee.to_bigint().unwrap().to_biguint().unwrap(),
+            )
+        })
+        .collect();
+
+        let active_accounts = sqlx::query!(
+            r#"
+            SELECT COUNT(DISTINCT address) AS "count!" FROM (
+                SELECT from_account AS address FROM executed_transactions
+                WHERE block_number BETWEEN $1 AND $2
+                UNION ALL
+                SELECT to_account AS address FROM executed_transactions
+                WHERE block_number BETWEEN $1 AND $2 AND to_account IS NOT NULL
+                UNION ALL
+                SELECT from_account AS address FROM executed_priority_operations
+                WHERE block_number BETWEEN $1 AND $2
+                UNION ALL
+                SELECT to_account AS address FROM executed_priority_operations
+                WHERE block_number BETWEEN $1 AND $2
+            ) AS addresses
+            "#,
+            from_block,
+            to_block
+        )
+        .fetch_one(transaction.conn())
+        .await?
+        .count as u64;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.block.get_blocks_stats", start.elapsed());
+        Ok(BlocksStats {
+            tx_counts,
+            fees,
+            active_accounts,
+        })
+    }
+
     pub async fn get_block_transactions_hashes(
         &mut self,
         block_number: BlockNumber,
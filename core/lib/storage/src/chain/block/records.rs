@@ -1,13 +1,27 @@
+// Built-in deps
+use std::collections::BTreeMap;
 // External imports
 use chrono::prelude::*;
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 use sqlx::FromRow;
 // Workspace imports
-use zksync_types::{event::block::BlockDetails, BlockNumber};
+use zksync_types::{event::block::BlockDetails, BlockNumber, TokenId};
 use zksync_utils::{BytesToHexSerde, OptionBytesToHexSerde, SyncBlockPrefix, ZeroxPrefix};
 // Local imports
 
+/// Statistics of the transactions executed in a range of blocks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BlocksStats {
+    /// Number of the executed transactions and priority operations keyed by their type.
+    pub tx_counts: BTreeMap<String, u64>,
+    /// Fees collected in the successful transactions keyed by the token.
+    pub fees: BTreeMap<TokenId, BigUint>,
+    /// Number of the unique addresses participating in the transactions.
+    pub active_accounts: u64,
+}
+
 #[derive(Debug, FromRow)]
 pub struct StorageBlock {
     pub number: i64,
@@ -64,6 +78,9 @@ pub struct StorageBlockDetails {
 
     pub block_size: i64,
 
+    /// Number of the transactions and priority operations executed in the block.
+    pub tx_count: i64,
+
     #[serde(with = "OptionBytesToHexSerde::<ZeroxPrefix>")]
     pub commit_tx_hash: Option<Vec<u8>>,
 
@@ -75,6 +92,13 @@ pub struct StorageBlockDetails {
     pub verified_at: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StorageBlockTimestamps {
+    pub block_number: i64,
+    pub committed_at: DateTime<Utc>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, PartialEq)]
 pub struct BlockTransactionItem {
     pub tx_hash: String,
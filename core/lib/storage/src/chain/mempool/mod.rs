@@ -1,8 +1,18 @@
 // Built-in deps
-use std::{collections::VecDeque, convert::TryFrom, str::FromStr, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    convert::TryFrom,
+    str::FromStr,
+    time::Instant,
+};
 // External imports
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
 // Workspace imports
+use zksync_api_types::v02::mempool::{
+    MempoolHoldReason, MempoolSnapshot, MempoolSnapshotTx, MempoolStats, MempoolTxInfo,
+    MEMPOOL_SNAPSHOT_VERSION,
+};
 use zksync_api_types::v02::pagination::PaginationDirection;
 use zksync_api_types::v02::transaction::{
     ApiTxBatch, BatchStatus, TxHashSerializeWrapper, TxInBlockStatus,
@@ -11,11 +21,13 @@ use zksync_types::{
     block::IncompleteBlock,
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
+    AccountId, Address, BlockNumber, ExecutedOperations, ExecutedPriorityOp, ExecutedTx, Nonce,
     PriorityOp, SerialId, SignedZkSyncTx, ZkSyncPriorityOp, H256,
 };
 // Local imports
-use self::records::{MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock};
+use self::records::{
+    AsyncSubmittedTx, ExpiredMempoolTx, MempoolPriorityOp, MempoolTx, QueuedBatchTx, RevertedBlock,
+};
 use crate::{QueryResult, StorageProcessor};
 
 use crate::chain::operations::records::{
@@ -24,6 +36,10 @@ use crate::chain::operations::records::{
 
 pub mod records;
 
+/// Transactions pending the asynchronous verification for longer than this are considered forgotten:
+/// their verification was interrupted, e.g. by the restart of the API server.
+pub const ASYNC_VERIFICATION_TIMEOUT_SECS: i64 = 10 * 60;
+
 /// Schema for persisting transactions awaiting for the execution.
 ///
 /// This schema holds the transactions that are received by the `mempool` module, but not yet have
@@ -268,6 +284,42 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Inserts the transactions that are not a part of some batch with a single statement,
+    /// transactions keep the order they are passed in.
+    pub async fn insert_txs(&mut self, txs: &[SignedZkSyncTx]) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut tx_hashes = Vec::with_capacity(txs.len());
+        let mut tx_values = Vec::with_capacity(txs.len());
+        let mut created_at = Vec::with_capacity(txs.len());
+        let mut eth_sign_data = Vec::with_capacity(txs.len());
+        for tx_data in txs {
+            tx_hashes.push(hex::encode(tx_data.tx.hash().as_ref()));
+            tx_values.push(serde_json::to_value(&tx_data.tx)?);
+            created_at.push(tx_data.created_at);
+            // Missing signature data is passed as JSON `null` and stored as `NULL`.
+            eth_sign_data.push(serde_json::to_value(&tx_data.eth_sign_data)?);
+        }
+
+        // Special case: batch_id == 0 <==> transaction is not a part of some batch
+        sqlx::query!(
+            r#"
+            INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
+            SELECT u.tx_hash, u.tx, u.created_at, NULLIF(u.eth_sign_data, 'null'::jsonb), 0
+                FROM UNNEST ($1::text[], $2::jsonb[], $3::timestamptz[], $4::jsonb[])
+                AS u(tx_hash, tx, created_at, eth_sign_data)
+            "#,
+            &tx_hashes,
+            &tx_values,
+            &created_at,
+            &eth_sign_data,
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.mempool.insert_txs", start.elapsed());
+        Ok(())
+    }
+
     pub async fn remove_tx(&mut self, tx: &[u8]) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx);
@@ -284,6 +336,27 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Removes the queued transaction that is not a part of a batch.
+    /// Returns `false` if the transaction is not queued anymore, e.g. it was already
+    /// executed in the pending block.
+    pub async fn remove_queued_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM mempool_txs
+            WHERE tx_hash = $1 AND batch_id = 0 AND reverted = false
+            "#,
+            hex::encode(tx_hash)
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "remove_queued_tx");
+        Ok(removed > 0)
+    }
+
     pub async fn remove_txs(&mut self, txs: &[TxHash]) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hashes: Vec<_> = txs.iter().map(hex::encode).collect();
@@ -300,6 +373,93 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Returns the queued transaction of the account with the given nonce if it was submitted
+    /// on its own rather than as a part of a batch.
+    /// Only the account that pays the fee (e.g. the swap submitter) is taken into account.
+    pub async fn get_queued_tx_by_nonce(
+        &mut self,
+        account_id: AccountId,
+        nonce: Nonce,
+    ) -> QueryResult<Option<SignedZkSyncTx>> {
+        let start = Instant::now();
+        let mempool_tx = sqlx::query_as!(
+            MempoolTx,
+            r#"
+            SELECT * FROM mempool_txs
+            WHERE batch_id = 0 AND reverted = false
+                AND COALESCE(
+                    tx->>'accountId', tx->>'submitterId', tx->>'creatorId', tx->>'initiatorAccountId'
+                )::bigint = $1
+                AND (tx->>'nonce')::bigint = $2
+            ORDER BY id
+            LIMIT 1
+            "#,
+            i64::from(*account_id),
+            i64::from(*nonce)
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_queued_tx_by_nonce");
+        mempool_tx
+            .map(SignedZkSyncTx::try_from)
+            .transpose()
+            .map_err(anyhow::Error::from)
+    }
+
+    /// Atomically replaces the queued transaction that is not a part of a batch with the new one.
+    /// The replaced transaction is remembered as expired, so its receipt tells what replaced it.
+    /// Returns `false` without any changes if the transaction is not queued anymore.
+    pub async fn replace_tx(
+        &mut self,
+        tx_hash: TxHash,
+        new_tx: &SignedZkSyncTx,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let created_at = sqlx::query!(
+            r#"
+            DELETE FROM mempool_txs
+            WHERE tx_hash = $1 AND batch_id = 0 AND reverted = false
+            RETURNING created_at
+            "#,
+            hex::encode(tx_hash)
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| record.created_at)
+        .min();
+        let created_at = match created_at {
+            Some(created_at) => created_at,
+            None => return Ok(false),
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO expired_mempool_txs (tx_hash, reason, created_at, expired_at)
+            VALUES ($1, $2, $3, now())
+            ON CONFLICT (tx_hash) DO UPDATE
+            SET reason = EXCLUDED.reason, created_at = EXCLUDED.created_at, expired_at = EXCLUDED.expired_at
+            "#,
+            hex::encode(tx_hash),
+            format!("Replaced by the transaction {}", new_tx.hash()),
+            created_at
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction
+            .chain()
+            .mempool_schema()
+            .insert_tx(new_tx)
+            .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "replace_tx");
+        Ok(true)
+    }
+
     /// Checks if the memory pool contains transaction with the given hash.
     pub async fn contains_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
         let start = Instant::now();
@@ -334,6 +494,23 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             .map_err(anyhow::Error::from)
     }
 
+    /// Returns queued zkSync transaction with the given hash if it was submitted
+    /// on its own rather than as a part of a batch.
+    pub async fn get_single_tx(&mut self, tx_hash: &[u8]) -> QueryResult<Option<SignedZkSyncTx>> {
+        let start = Instant::now();
+
+        let mempool_tx = self
+            .get_mempool_tx(tx_hash)
+            .await?
+            .filter(|mempool_tx| mempool_tx.batch_id == 0);
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_single_tx");
+        mempool_tx
+            .map(SignedZkSyncTx::try_from)
+            .transpose()
+            .map_err(anyhow::Error::from)
+    }
+
     /// Returns mempool transaction as it is stored in the database.
     async fn get_mempool_tx(&mut self, tx_hash: &[u8]) -> QueryResult<Option<MempoolTx>> {
         let start = Instant::now();
@@ -353,54 +530,61 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(mempool_tx)
     }
 
-    /// Removes transactions that are already committed.
-    /// Though it's unlikely that mempool schema will ever contain a committed
-    /// transaction, it's better to ensure that we won't process the same transaction
-    /// again. One possible scenario for having already-processed txs in the database
-    /// is a failure of `remove_txs` method, which won't cause a panic on server, but will
-    /// left txs in the database.
-    ///
-    /// This method is expected to be initially invoked on the server start, and then
-    /// invoked periodically with a big interval (to prevent possible database bloating).
-    pub async fn collect_garbage(&mut self) -> QueryResult<()> {
+    /// Removes the repeated copies of the queued transactions, e.g. the ones stored again because
+    /// the submission was retried after the crash. The earliest copy is kept, so the order of
+    /// the transactions is preserved. Batch is never removed partially: if any of its transactions
+    /// repeats an earlier one, the whole batch is removed, since it would fail anyway.
+    /// Returns the number of the removed transactions.
+    pub async fn remove_duplicate_txs(&mut self) -> QueryResult<u64> {
         let start = Instant::now();
-        let all_txs = self.load_txs(&[]).await?;
-        let mut tx_hashes_to_remove = Vec::new();
-
-        for tx in all_txs {
-            let should_remove = match &tx {
-                SignedTxVariant::Tx(tx) => {
-                    let tx_hash = tx.hash();
-                    self.0
-                        .chain()
-                        .operations_ext_schema()
-                        .get_tx_by_hash(tx_hash.as_ref())
-                        .await
-                        .expect("DB issue while restoring the mempool state")
-                        .is_some()
-                }
-                SignedTxVariant::Batch(batch) => {
-                    // We assume that for batch one executed transaction <=> all the transactions are executed.
-                    let tx_hash = batch.txs[0].hash();
-                    self.0
-                        .chain()
-                        .operations_ext_schema()
-                        .get_tx_by_hash(tx_hash.as_ref())
-                        .await
-                        .expect("DB issue while restoring the mempool state")
-                        .is_some()
-                }
-            };
 
-            if should_remove {
-                tx_hashes_to_remove.extend(tx.hashes())
-            }
-        }
+        // Special case: batch_id == 0 <==> transaction is not a part of some batch.
+        let removed = sqlx::query!(
+            r#"
+            WITH duplicates AS (
+                SELECT duplicate.id, duplicate.batch_id FROM mempool_txs AS duplicate
+                INNER JOIN mempool_txs AS original
+                ON duplicate.tx_hash = original.tx_hash AND duplicate.id > original.id
+            )
+            DELETE FROM mempool_txs
+            WHERE id IN (SELECT id FROM duplicates)
+                OR (batch_id != 0 AND batch_id IN (SELECT batch_id FROM duplicates))
+            "#
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
 
-        self.remove_txs(&tx_hashes_to_remove).await?;
+        metrics::histogram!("sql.chain.mempool.remove_duplicate_txs", start.elapsed());
+        Ok(removed)
+    }
 
-        metrics::histogram!("sql.chain.mempool.collect_garbage", start.elapsed());
-        Ok(())
+    /// Returns the hashes of the given transactions that are executed already.
+    pub async fn get_executed_tx_hashes(
+        &mut self,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<HashSet<TxHash>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes
+            .iter()
+            .map(|hash| hash.as_ref().to_vec())
+            .collect();
+
+        let executed = sqlx::query!(
+            r#"
+            SELECT tx_hash FROM executed_transactions
+            WHERE tx_hash = ANY($1)
+            "#,
+            &tx_hashes
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .filter_map(|row| TxHash::from_slice(&row.tx_hash))
+        .collect();
+
+        metrics::histogram!("sql.chain.mempool.get_executed_tx_hashes", start.elapsed());
+        Ok(executed)
     }
 
     pub async fn insert_priority_ops(
@@ -529,6 +713,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(ops.into_iter().map(|op| op.into()).collect())
     }
 
+    /// Loads the pending priority operation by the hash of the Ethereum transaction that created it.
     pub async fn get_pending_operation_by_hash(
         &mut self,
         tx_hash: H256,
@@ -537,8 +722,8 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
             MempoolPriorityOp,
             r#"
                 SELECT serial_id,data,deadline_block,eth_hash,
-                       tx_hash,eth_block,eth_block_index,created_at 
-                FROM mempool_priority_operations 
+                       tx_hash,eth_block,eth_block_index,created_at
+                FROM mempool_priority_operations
                 WHERE eth_hash = $1
             "#,
             tx_hash.as_bytes().to_vec()
@@ -548,6 +733,198 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .map(|op| op.into());
         Ok(op)
     }
+
+    pub async fn get_pending_operation_by_serial_id(
+        &mut self,
+        serial_id: SerialId,
+    ) -> QueryResult<Option<PriorityOp>> {
+        let start = Instant::now();
+        let op = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+                SELECT serial_id,data,deadline_block,eth_hash,
+                       tx_hash,eth_block,eth_block_index,created_at
+                FROM mempool_priority_operations
+                WHERE serial_id = $1
+            "#,
+            serial_id as i64
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|op| op.into());
+
+        metrics::histogram!(
+            "sql.chain.mempool.get_pending_operation_by_serial_id",
+            start.elapsed()
+        );
+        Ok(op)
+    }
+
+    /// Stores the latest Ethereum block seen by the Ethereum watcher.
+    pub async fn update_last_watched_eth_block(&mut self, block_number: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "INSERT INTO last_watched_eth_block (block_number) VALUES ($1)
+            ON CONFLICT (id) DO UPDATE SET block_number = $1",
+            block_number as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.mempool.update_last_watched_eth_block",
+            start.elapsed()
+        );
+        Ok(())
+    }
+
+    /// Loads the latest Ethereum block seen by the Ethereum watcher.
+    pub async fn get_last_watched_eth_block(&mut self) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let block_number = sqlx::query!("SELECT block_number FROM last_watched_eth_block")
+            .fetch_optional(self.0.conn())
+            .await?
+            .map(|record| record.block_number as u64);
+
+        metrics::histogram!(
+            "sql.chain.mempool.get_last_watched_eth_block",
+            start.elapsed()
+        );
+        Ok(block_number)
+    }
+
+    /// Returns the greatest nonce among the mempool transactions of the account.
+    /// Only the account that pays the fee (e.g. the swap submitter) is taken into account.
+    pub async fn get_max_nonce_for_account(
+        &mut self,
+        account_id: AccountId,
+    ) -> QueryResult<Option<Nonce>> {
+        let start = Instant::now();
+        let nonce = sqlx::query!(
+            r#"
+            SELECT MAX((tx->>'nonce')::bigint) as "nonce"
+            FROM mempool_txs
+            WHERE COALESCE(
+                tx->>'accountId', tx->>'submitterId', tx->>'creatorId', tx->>'initiatorAccountId'
+            )::bigint = $1
+            "#,
+            i64::from(*account_id)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .nonce
+        .map(|nonce| Nonce(nonce as u32));
+
+        metrics::histogram!(
+            "sql.chain.mempool.get_max_nonce_for_account",
+            start.elapsed()
+        );
+        Ok(nonce)
+    }
+
+    /// Returns the nonces of the mempool transactions of the given accounts.
+    /// Only the account that pays the fee (e.g. the swap submitter) is taken into account.
+    pub async fn get_queued_nonces(
+        &mut self,
+        account_ids: &[AccountId],
+    ) -> QueryResult<HashMap<AccountId, HashSet<Nonce>>> {
+        let start = Instant::now();
+        let account_ids: Vec<_> = account_ids.iter().map(|id| i64::from(**id)).collect();
+        let records = sqlx::query!(
+            r#"
+            SELECT
+                COALESCE(
+                    tx->>'accountId', tx->>'submitterId', tx->>'creatorId', tx->>'initiatorAccountId'
+                )::bigint AS "account_id!",
+                (tx->>'nonce')::bigint AS "nonce!"
+            FROM mempool_txs
+            WHERE COALESCE(
+                tx->>'accountId', tx->>'submitterId', tx->>'creatorId', tx->>'initiatorAccountId'
+            )::bigint = ANY($1)
+            "#,
+            &account_ids
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut nonces = HashMap::<AccountId, HashSet<Nonce>>::new();
+        for record in records {
+            nonces
+                .entry(AccountId(record.account_id as u32))
+                .or_default()
+                .insert(Nonce(record.nonce as u32));
+        }
+
+        metrics::histogram!("sql.chain.mempool.get_queued_nonces", start.elapsed());
+        Ok(nonces)
+    }
+
+    /// Loads the mempool transactions that may change the balances of the account:
+    /// the ones it initiates or pays the fee for and the ones it receives funds from.
+    pub async fn get_account_txs(
+        &mut self,
+        account_id: AccountId,
+        address: Address,
+    ) -> QueryResult<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+        let txs: Vec<MempoolTx> = sqlx::query_as!(
+            MempoolTx,
+            r#"
+            SELECT * FROM mempool_txs
+            WHERE reverted = false AND (
+                $1 IN (
+                    tx->>'from', tx->>'to', tx->>'account', tx->>'target',
+                    tx->>'creatorAddress', tx->>'submitterAddress',
+                    tx->'orders'->0->>'recipient', tx->'orders'->1->>'recipient'
+                )
+                OR $2 IN (
+                    tx->>'accountId', tx->>'initiatorAccountId', tx->>'creatorId', tx->>'submitterId',
+                    tx->'orders'->0->>'accountId', tx->'orders'->1->>'accountId'
+                )
+            )
+            ORDER BY id
+            "#,
+            format!("{:?}", address),
+            account_id.to_string()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let txs = txs
+            .into_iter()
+            .map(SignedZkSyncTx::try_from)
+            .collect::<Result<_, _>>()?;
+
+        metrics::histogram!("sql.chain.mempool.get_account_txs", start.elapsed());
+        Ok(txs)
+    }
+
+    /// Loads all the priority operations for the given L2 address that are
+    /// observed on L1 but not yet executed in a block.
+    pub async fn get_pending_priority_ops(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Vec<PriorityOp>> {
+        let start = Instant::now();
+        let ops = sqlx::query_as!(
+            MempoolPriorityOp,
+            r#"
+            SELECT serial_id,data,deadline_block,eth_hash,
+                   tx_hash,eth_block,eth_block_index,created_at
+            FROM mempool_priority_operations
+            WHERE l2_address = $1
+            ORDER BY serial_id"#,
+            address.as_bytes().to_vec()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.mempool.get_pending_priority_ops",
+            start.elapsed()
+        );
+        Ok(ops.into_iter().map(|op| op.into()).collect())
+    }
+
     pub async fn get_pending_deposits(&mut self, address: Address) -> QueryResult<Vec<PriorityOp>> {
         let ops = sqlx::query_as!(
             MempoolPriorityOp,
@@ -588,6 +965,362 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(size.unwrap_or(0) as u32)
     }
 
+    /// Collects the statistics of the transactions waiting in the mempool.
+    pub async fn get_mempool_stats(&mut self) -> QueryResult<MempoolStats> {
+        let start = Instant::now();
+
+        let records = sqlx::query!(
+            r#"
+            SELECT tx->>'type' AS "tx_type!", COUNT(*) AS "count!",
+                COUNT(*) FILTER (WHERE batch_id != 0) AS "batched!",
+                MIN(created_at) AS "oldest_created_at!"
+            FROM mempool_txs
+            GROUP BY tx->>'type'
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut stats = MempoolStats {
+            size: 0,
+            batched_txs: 0,
+            oldest_tx_created_at: None,
+            oldest_tx_age_sec: None,
+            txs_by_type: Default::default(),
+        };
+        for record in records {
+            stats.size += record.count as u32;
+            stats.batched_txs += record.batched as u32;
+            stats
+                .txs_by_type
+                .insert(record.tx_type, record.count as u32);
+            stats.oldest_tx_created_at = Some(match stats.oldest_tx_created_at {
+                Some(oldest) => oldest.min(record.oldest_created_at),
+                None => record.oldest_created_at,
+            });
+        }
+        stats.oldest_tx_age_sec = stats
+            .oldest_tx_created_at
+            .map(|created_at| (Utc::now() - created_at).num_seconds().max(0) as u64);
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_mempool_stats");
+        Ok(stats)
+    }
+
+    /// Loads the transactions waiting in the mempool starting from the oldest one.
+    /// Transactions can be optionally filtered by the type, e.g. `Transfer`.
+    pub async fn load_mempool_txs(
+        &mut self,
+        tx_type: Option<&str>,
+        limit: u32,
+        offset: u32,
+    ) -> QueryResult<Vec<MempoolTxInfo>> {
+        let start = Instant::now();
+
+        let records: Vec<MempoolTx> = sqlx::query_as!(
+            MempoolTx,
+            r#"
+            SELECT * FROM mempool_txs
+            WHERE $1::text IS NULL OR tx->>'type' = $1
+            ORDER BY id
+            LIMIT $2 OFFSET $3
+            "#,
+            tx_type,
+            i64::from(limit),
+            i64::from(offset)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut txs = Vec::with_capacity(records.len());
+        for record in records {
+            let tx_type = record.tx["type"].as_str().unwrap_or_default().to_string();
+            let batch_id = Some(record.batch_id).filter(|&batch_id| batch_id != 0);
+            let created_at = record.created_at;
+            let reverted = record.reverted;
+            let tx = SignedZkSyncTx::try_from(record)?.tx;
+            txs.push(MempoolTxInfo {
+                tx_hash: tx.hash(),
+                tx_type,
+                batch_id,
+                created_at,
+                reverted,
+                tx,
+            });
+        }
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_mempool_txs");
+        Ok(txs)
+    }
+
+    /// Dumps the whole mempool including the reverted transactions and the batch signatures.
+    /// Every transaction is annotated with the reasons it can't be executed in the next block.
+    pub async fn load_mempool_snapshot(&mut self) -> QueryResult<MempoolSnapshot> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let records: Vec<MempoolTx> =
+            sqlx::query_as!(MempoolTx, "SELECT * FROM mempool_txs ORDER BY id")
+                .fetch_all(transaction.conn())
+                .await?;
+
+        let mut txs = Vec::with_capacity(records.len());
+        for record in records {
+            let tx_type = record.tx["type"].as_str().unwrap_or_default().to_string();
+            let batch_id = Some(record.batch_id).filter(|&batch_id| batch_id != 0);
+            let reverted = record.reverted;
+            let signed_tx = SignedZkSyncTx::try_from(record)?;
+            txs.push(MempoolSnapshotTx {
+                tx_hash: signed_tx.tx.hash(),
+                tx_type,
+                batch_id,
+                created_at: signed_tx.created_at,
+                reverted,
+                tx: signed_tx.tx,
+                eth_sign_data: signed_tx.eth_sign_data,
+                held_reasons: Vec::new(),
+            });
+        }
+
+        let batch_ids: Vec<i64> = txs.iter().filter_map(|tx| tx.batch_id).dedup().collect();
+        let signatures = sqlx::query!(
+            "SELECT batch_id, eth_signature FROM txs_batches_signatures
+            WHERE batch_id = ANY($1)
+            ORDER BY id",
+            &batch_ids
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let mut batch_signatures = BTreeMap::<i64, Vec<TxEthSignature>>::new();
+        for signature in signatures {
+            batch_signatures
+                .entry(signature.batch_id)
+                .or_default()
+                .push(serde_json::from_value(signature.eth_signature)?);
+        }
+
+        let account_ids: Vec<AccountId> = txs
+            .iter()
+            .filter_map(|tx| tx.tx.account_id().ok())
+            .unique()
+            .collect();
+        let committed_nonces: BTreeMap<_, _> = transaction
+            .chain()
+            .account_schema()
+            .estimate_nonces(&account_ids)
+            .await?
+            .into_iter()
+            .collect();
+        transaction.commit().await?;
+
+        let created_at = Utc::now();
+        find_held_txs(&mut txs, &committed_nonces, created_at.timestamp() as u64);
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_mempool_snapshot");
+        Ok(MempoolSnapshot {
+            version: MEMPOOL_SNAPSHOT_VERSION,
+            created_at,
+            committed_nonces,
+            txs,
+            batch_signatures,
+        })
+    }
+
+    /// Removes the given transactions from the mempool and remembers the reason of the eviction,
+    /// so the receipt of the transaction shows it expired.
+    pub async fn expire_txs(&mut self, txs: &[(TxHash, String)]) -> QueryResult<()> {
+        let start = Instant::now();
+        let (tx_hashes, reasons): (Vec<_>, Vec<_>) = txs
+            .iter()
+            .map(|(tx_hash, reason)| (hex::encode(tx_hash), reason.clone()))
+            .unzip();
+
+        let mut transaction = self.0.start_transaction().await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO expired_mempool_txs (tx_hash, reason, created_at, expired_at)
+            SELECT DISTINCT ON (mempool_txs.tx_hash) mempool_txs.tx_hash, u.reason, mempool_txs.created_at, now()
+            FROM mempool_txs
+            INNER JOIN UNNEST ($1::text[], $2::text[]) AS u(tx_hash, reason)
+                ON mempool_txs.tx_hash = u.tx_hash
+            ON CONFLICT (tx_hash) DO UPDATE
+            SET reason = EXCLUDED.reason, created_at = EXCLUDED.created_at, expired_at = EXCLUDED.expired_at
+            "#,
+            &tx_hashes,
+            &reasons
+        )
+        .execute(transaction.conn())
+        .await?;
+        let txs: Vec<_> = txs.iter().map(|(tx_hash, _)| *tx_hash).collect();
+        transaction
+            .chain()
+            .mempool_schema()
+            .remove_txs(&txs)
+            .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "expire_txs");
+        Ok(())
+    }
+
+    /// Loads the transactions with the given hashes that were evicted from the mempool.
+    pub async fn load_expired_txs(
+        &mut self,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<Vec<ExpiredMempoolTx>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(hex::encode).collect();
+
+        let txs = sqlx::query_as!(
+            ExpiredMempoolTx,
+            "SELECT * FROM expired_mempool_txs WHERE tx_hash = ANY($1)",
+            &tx_hashes
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_expired_txs");
+        Ok(txs)
+    }
+
+    /// Forgets the transactions that were evicted from the mempool before the given time.
+    pub async fn remove_expired_txs(&mut self, expired_before: DateTime<Utc>) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            "DELETE FROM expired_mempool_txs WHERE expired_at < $1",
+            expired_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "remove_expired_txs");
+        Ok(removed)
+    }
+
+    /// Stores the transaction accepted with the asynchronous verification as pending.
+    /// The previous rejection of the same transaction is forgotten.
+    pub async fn store_async_submitted_tx(
+        &mut self,
+        tx_hash: TxHash,
+        created_at: DateTime<Utc>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO async_submitted_txs (tx_hash, reason, created_at, rejected_at)
+            VALUES ($1, NULL, $2, NULL)
+            ON CONFLICT (tx_hash) DO UPDATE
+            SET reason = NULL, created_at = EXCLUDED.created_at, rejected_at = NULL
+            "#,
+            hex::encode(tx_hash),
+            created_at
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "store_async_submitted_tx");
+        Ok(())
+    }
+
+    /// Marks the pending transaction as the one that failed the asynchronous verification.
+    /// The rejection is stored even if the pending transaction has been forgotten already
+    /// because its verification took longer than `ASYNC_VERIFICATION_TIMEOUT_SECS`.
+    pub async fn reject_async_submitted_tx(
+        &mut self,
+        tx_hash: TxHash,
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO async_submitted_txs (tx_hash, reason, created_at, rejected_at)
+            VALUES ($1, $2, now(), now())
+            ON CONFLICT (tx_hash) DO UPDATE
+            SET reason = $2, rejected_at = now()
+            "#,
+            hex::encode(tx_hash),
+            reason
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "reject_async_submitted_tx");
+        Ok(())
+    }
+
+    /// Forgets the pending transaction, either because it has reached the mempool
+    /// or because its verification couldn't be completed.
+    pub async fn remove_async_submitted_tx(&mut self, tx_hash: TxHash) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            "DELETE FROM async_submitted_txs WHERE tx_hash = $1",
+            hex::encode(tx_hash)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "remove_async_submitted_tx");
+        Ok(())
+    }
+
+    /// Loads the transactions with the given hashes that are pending the asynchronous verification
+    /// or have failed it. Transactions pending for longer than `ASYNC_VERIFICATION_TIMEOUT_SECS`
+    /// are considered forgotten.
+    pub async fn load_async_submitted_txs(
+        &mut self,
+        tx_hashes: &[TxHash],
+    ) -> QueryResult<Vec<AsyncSubmittedTx>> {
+        let start = Instant::now();
+        let tx_hashes: Vec<_> = tx_hashes.iter().map(hex::encode).collect();
+        let pending_since = Utc::now() - chrono::Duration::seconds(ASYNC_VERIFICATION_TIMEOUT_SECS);
+
+        let txs = sqlx::query_as!(
+            AsyncSubmittedTx,
+            r#"
+            SELECT * FROM async_submitted_txs
+            WHERE tx_hash = ANY($1) AND (rejected_at IS NOT NULL OR created_at >= $2)
+            "#,
+            &tx_hashes,
+            pending_since
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "load_async_submitted_txs");
+        Ok(txs)
+    }
+
+    /// Forgets the transactions that were rejected before the given time, as well as the ones
+    /// pending for longer than `ASYNC_VERIFICATION_TIMEOUT_SECS`, which are left behind
+    /// if the server is restarted during the verification.
+    pub async fn remove_async_submitted_txs(
+        &mut self,
+        rejected_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+        let pending_since = Utc::now() - chrono::Duration::seconds(ASYNC_VERIFICATION_TIMEOUT_SECS);
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM async_submitted_txs
+            WHERE rejected_at < $1 OR (rejected_at IS NULL AND created_at < $2)
+            "#,
+            rejected_before,
+            pending_since
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "remove_async_submitted_txs");
+        Ok(removed)
+    }
+
     /// Get info about batch in mempool.
     pub async fn get_queued_batch_info(
         &mut self,
@@ -1011,6 +1744,13 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         )
         .execute(transaction.conn())
         .await?;
+        sqlx::query!(
+            r"DELETE FROM swap_orders
+            WHERE tx_hash IN (SELECT tx_hash FROM executed_transactions WHERE block_number > $1)",
+            *last_block_number as i64
+        )
+        .execute(transaction.conn())
+        .await?;
         sqlx::query!(
             r"DELETE FROM executed_transactions
             WHERE block_number > $1",
@@ -1027,3 +1767,68 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 }
+
+/// Fills the reasons the mempool transactions can't be executed in the block created at `now`.
+/// Transactions of an account are executed in the order of their nonces starting from the
+/// committed one, so a missing nonce holds all the transactions following it.
+pub(crate) fn find_held_txs(
+    txs: &mut [MempoolSnapshotTx],
+    committed_nonces: &BTreeMap<AccountId, Nonce>,
+    now: u64,
+) {
+    let mut queued_nonces = HashMap::<AccountId, BTreeSet<Nonce>>::new();
+    for tx in txs.iter() {
+        if let Ok(account_id) = tx.tx.account_id() {
+            queued_nonces
+                .entry(account_id)
+                .or_default()
+                .insert(tx.tx.nonce());
+        }
+    }
+    // The first nonce after the consecutive queued ones, i.e. the first missing one.
+    let next_nonces: HashMap<AccountId, Nonce> = queued_nonces
+        .into_iter()
+        .filter_map(|(account_id, nonces)| {
+            let mut next_nonce = *committed_nonces.get(&account_id)?;
+            for nonce in nonces.range(next_nonce..) {
+                if *nonce != next_nonce {
+                    break;
+                }
+                next_nonce = Nonce(*next_nonce + 1);
+            }
+            Some((account_id, next_nonce))
+        })
+        .collect();
+
+    for tx in txs {
+        if let Ok(account_id) = tx.tx.account_id() {
+            let nonce = tx.tx.nonce();
+            match (
+                committed_nonces.get(&account_id),
+                next_nonces.get(&account_id),
+            ) {
+                (Some(&committed_nonce), _) if nonce < committed_nonce => tx
+                    .held_reasons
+                    .push(MempoolHoldReason::NonceTooLow { committed_nonce }),
+                (Some(_), Some(&expected_nonce)) if nonce > expected_nonce => tx
+                    .held_reasons
+                    .push(MempoolHoldReason::NonceGap { expected_nonce }),
+                (Some(_), _) => {}
+                (None, _) => tx
+                    .held_reasons
+                    .push(MempoolHoldReason::UnknownAccount { account_id }),
+            }
+        }
+
+        let time_range = tx.tx.time_range();
+        if time_range.valid_from > now {
+            tx.held_reasons.push(MempoolHoldReason::NotValidYet {
+                valid_from: time_range.valid_from,
+            });
+        } else if time_range.valid_until < now {
+            tx.held_reasons.push(MempoolHoldReason::ValidityEnded {
+                valid_until: time_range.valid_until,
+            });
+        }
+    }
+}
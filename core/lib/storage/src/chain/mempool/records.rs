@@ -6,7 +6,8 @@ use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
 // Workspace imports
-use zksync_types::{PriorityOp, SignedZkSyncTx, H256};
+use zksync_api_types::v02::transaction::{L2Receipt, Receipt, TxInBlockStatus};
+use zksync_types::{tx::TxHash, PriorityOp, SignedZkSyncTx, H256};
 
 // Local imports
 
@@ -31,7 +32,6 @@ pub(crate) struct MempoolTx {
     pub batch_id: i64,
     #[allow(dead_code)]
     pub next_priority_op_serial_id: Option<i64>,
-    #[allow(dead_code)]
     pub reverted: bool,
 }
 
@@ -82,3 +82,72 @@ impl From<MempoolPriorityOp> for PriorityOp {
         }
     }
 }
+
+/// Transaction evicted from the mempool without being executed.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct ExpiredMempoolTx {
+    pub tx_hash: String,
+    /// Human-readable reason of the eviction.
+    pub reason: String,
+    /// Time the transaction was received by the server.
+    pub created_at: DateTime<Utc>,
+    pub expired_at: DateTime<Utc>,
+}
+
+impl From<ExpiredMempoolTx> for Receipt {
+    fn from(tx: ExpiredMempoolTx) -> Self {
+        let tx_hash = hex::decode(&tx.tx_hash).expect("Incorrect tx hash stored in the database");
+        Receipt::L2(L2Receipt {
+            tx_hash: TxHash::from_slice(&tx_hash)
+                .expect("Incorrect tx hash stored in the database"),
+            rollup_block: None,
+            block_index: None,
+            status: TxInBlockStatus::Expired,
+            fail_reason: Some(tx.reason),
+            fee: None,
+            fee_token: None,
+            created_at: Some(tx.created_at),
+            committed_at: None,
+            finalized_at: None,
+            estimated_finalization: None,
+            swap: None,
+        })
+    }
+}
+
+/// Transaction submitted with the asynchronous verification that hasn't reached the mempool.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct AsyncSubmittedTx {
+    pub tx_hash: String,
+    /// Human-readable reason of the rejection, `None` while the verification is in progress.
+    pub reason: Option<String>,
+    /// Time the transaction was received by the server.
+    pub created_at: DateTime<Utc>,
+    pub rejected_at: Option<DateTime<Utc>>,
+}
+
+impl From<AsyncSubmittedTx> for Receipt {
+    fn from(tx: AsyncSubmittedTx) -> Self {
+        let tx_hash = hex::decode(&tx.tx_hash).expect("Incorrect tx hash stored in the database");
+        let status = if tx.reason.is_some() {
+            TxInBlockStatus::RejectedPreBlock
+        } else {
+            TxInBlockStatus::PendingVerification
+        };
+        Receipt::L2(L2Receipt {
+            tx_hash: TxHash::from_slice(&tx_hash)
+                .expect("Incorrect tx hash stored in the database"),
+            rollup_block: None,
+            block_index: None,
+            status,
+            fail_reason: tx.reason,
+            fee: None,
+            fee_token: None,
+            created_at: Some(tx.created_at),
+            committed_at: None,
+            finalized_at: None,
+            estimated_finalization: None,
+            swap: None,
+        })
+    }
+}
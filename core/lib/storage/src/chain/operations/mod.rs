@@ -10,8 +10,9 @@ use zksync_types::{
 };
 // Local imports
 use self::records::{
-    NewExecutedPriorityOperation, NewExecutedTransaction, StoredAggregatedOperation,
-    StoredCompleteWithdrawalsTransaction, StoredExecutedPriorityOperation, StoredPendingWithdrawal,
+    NewExecutedPriorityOperation, NewExecutedTransaction, StoredAggregatedEthTx,
+    StoredAggregatedOperation, StoredCompleteWithdrawalsTransaction,
+    StoredExecutedPriorityOperation, StoredPendingWithdrawal,
 };
 use crate::chain::operations::records::StoredExecutedTransaction;
 use crate::chain::operations_ext::OperationsExtSchema;
@@ -258,6 +259,22 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             )
             .execute(transaction.conn())
             .await?;
+
+            if !operation.order_hashes.is_empty() {
+                sqlx::query!(
+                    "
+                    INSERT INTO swap_orders (order_hash, tx_hash, sequence_number)
+                    SELECT u.order_hash, $2, $3
+                        FROM UNNEST ($1::bytea[]) AS u(order_hash)
+                    ON CONFLICT (order_hash, tx_hash) DO NOTHING
+                    ",
+                    &operation.order_hashes,
+                    &operation.tx_hash,
+                    &seq_no
+                )
+                .execute(transaction.conn())
+                .await?;
+            }
         }
         transaction.commit().await?;
         metrics::histogram!("sql.chain.operations.store_executed_tx", start.elapsed());
@@ -300,6 +317,12 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         )
         .execute(transaction.conn())
         .await?;
+        sqlx::query!(
+            "DELETE FROM swap_orders WHERE tx_hash = ANY ($1)",
+            &tx_hashes
+        )
+        .execute(transaction.conn())
+        .await?;
 
         transaction.commit().await?;
 
@@ -500,6 +523,45 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(eth_tx_hash)
     }
 
+    /// Loads the confirmed Ethereum transactions of the aggregated operations
+    /// that cover at least one block in the given range.
+    pub async fn get_aggregated_eth_txs_for_blocks(
+        &mut self,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> QueryResult<Vec<StoredAggregatedEthTx>> {
+        let start = Instant::now();
+        let txs = sqlx::query_as!(
+            StoredAggregatedEthTx,
+            r#"
+            SELECT
+                aggregate_operations.action_type,
+                aggregate_operations.from_block,
+                aggregate_operations.to_block,
+                eth_operations.final_hash AS "tx_hash!",
+                eth_operations.gas_used
+            FROM aggregate_operations
+                INNER JOIN eth_aggregated_ops_binding ON aggregate_operations.id = eth_aggregated_ops_binding.op_id
+                INNER JOIN eth_operations ON eth_operations.id = eth_aggregated_ops_binding.eth_op_id
+            WHERE aggregate_operations.confirmed = true
+                AND eth_operations.confirmed = true
+                AND aggregate_operations.from_block <= $2
+                AND aggregate_operations.to_block >= $1
+            ORDER BY aggregate_operations.id
+            "#,
+            i64::from(*from_block),
+            i64::from(*to_block)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations.get_aggregated_eth_txs_for_blocks",
+            start.elapsed()
+        );
+        Ok(txs)
+    }
+
     pub async fn store_aggregated_action(
         &mut self,
         operation: AggregatedOperation,
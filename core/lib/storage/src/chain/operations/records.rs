@@ -101,6 +101,7 @@ pub(crate) struct NewExecutedTransaction {
     pub batch_id: Option<i64>,
     pub affected_accounts: Vec<Vec<u8>>,
     pub used_tokens: Vec<i32>,
+    pub order_hashes: Vec<Vec<u8>>,
 }
 
 #[derive(Debug, Clone)]
@@ -119,6 +120,16 @@ pub(crate) struct StoredCompleteWithdrawalsTransaction {
     pub pending_withdrawals_queue_end_index: i64,
 }
 
+/// Confirmed Ethereum transaction of the aggregated operation.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredAggregatedEthTx {
+    pub action_type: String,
+    pub from_block: i64,
+    pub to_block: i64,
+    pub tx_hash: Vec<u8>,
+    pub gas_used: Option<i64>,
+}
+
 #[derive(Debug, Clone, FromRow)]
 pub struct StoredAggregatedOperation {
     pub id: i64,
@@ -1,23 +1,28 @@
 // Built-in deps
 // External imports
 // Workspace imports
-use zksync_api_types::v02::transaction::{
-    ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, Transaction,
-    TransactionData, TxData, TxInBlockStatus, WithdrawData, WithdrawNFTData,
+use zksync_api_types::v02::{
+    token::ApiNFT,
+    transaction::{
+        ForcedExitData, L1Receipt, L1Transaction, L2Receipt, L2Transaction, Receipt, SwapData,
+        SwapReceipt, Transaction, TransactionData, TxBatchContext, TxData, TxHashSerializeWrapper,
+        TxInBlockStatus, WithdrawData, WithdrawNFTData,
+    },
 };
 use zksync_types::{
     tx::{EthSignData, TxHash},
-    BlockNumber, EthBlockId, ZkSyncOp, ZkSyncTx, H256,
+    BlockNumber, EthBlockId, TokenLike, ZkSyncOp, ZkSyncTx, H256,
 };
 // Local imports
 use super::records::{StorageTxData, StorageTxReceipt};
+use crate::QueryResult;
 
 impl StorageTxReceipt {
     pub(super) fn receipt_from_storage_receipt(
         receipt: StorageTxReceipt,
         is_block_finalized: Option<bool>,
-    ) -> Receipt {
-        if receipt.block_number.is_some() {
+    ) -> QueryResult<Receipt> {
+        let receipt = if receipt.block_number.is_some() {
             let status = if receipt.success.unwrap() {
                 if is_block_finalized.unwrap() {
                     TxInBlockStatus::Finalized
@@ -34,26 +39,82 @@ impl StorageTxReceipt {
                     rollup_block: receipt
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
+                    block_index: receipt.block_index.map(|index| index as u32),
                     id: receipt.priority_op_serialid.unwrap() as u64,
+                    created_at: receipt.created_at,
+                    committed_at: None,
+                    finalized_at: None,
+                    estimated_finalization: None,
+                    confirmations: None,
                 })
             } else {
+                let tx: Option<ZkSyncTx> = receipt
+                    .tx
+                    .map(serde_json::from_value)
+                    .transpose()
+                    .map_err(|err| anyhow::format_err!("Malformed stored transaction: {}", err))?;
+                // Rejected transactions don't charge any fee.
+                let fee_info = match &tx {
+                    Some(tx) if status != TxInBlockStatus::Rejected => tx.get_fee_info(),
+                    _ => None,
+                };
+                let swap = match &tx {
+                    Some(ZkSyncTx::Swap(swap)) => Some(SwapReceipt::from(swap.as_ref())),
+                    _ => None,
+                };
+                let (fee, fee_token) = match fee_info {
+                    Some((_, TokenLike::Id(token_id), _, fee)) => {
+                        (Some(fee.into()), Some(token_id))
+                    }
+                    _ => (None, None),
+                };
                 Receipt::L2(L2Receipt {
                     status,
                     tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
                     rollup_block: receipt
                         .block_number
                         .map(|number| BlockNumber(number as u32)),
+                    block_index: receipt.block_index.map(|index| index as u32),
                     fail_reason: receipt.fail_reason,
+                    fee,
+                    fee_token,
+                    created_at: receipt.created_at,
+                    committed_at: None,
+                    finalized_at: None,
+                    estimated_finalization: None,
+                    swap,
                 })
             }
+        } else if let Some(eth_block) = receipt.eth_block {
+            Receipt::L1(L1Receipt {
+                status: TxInBlockStatus::Queued,
+                eth_block: EthBlockId(eth_block as u64),
+                rollup_block: None,
+                block_index: None,
+                id: receipt.priority_op_serialid.unwrap() as u64,
+                created_at: receipt.created_at,
+                committed_at: None,
+                finalized_at: None,
+                estimated_finalization: None,
+                confirmations: None,
+            })
         } else {
             Receipt::L2(L2Receipt {
                 status: TxInBlockStatus::Queued,
                 tx_hash: TxHash::from_slice(&receipt.tx_hash).unwrap(),
                 rollup_block: None,
+                block_index: None,
                 fail_reason: None,
+                fee: None,
+                fee_token: None,
+                created_at: receipt.created_at,
+                committed_at: None,
+                finalized_at: None,
+                estimated_finalization: None,
+                swap: None,
             })
-        }
+        };
+        Ok(receipt)
     }
 }
 
@@ -79,7 +140,16 @@ impl StorageTxData {
                 tx: *tx,
                 eth_tx_hash: complete_withdrawals_tx_hash,
             })),
-            ZkSyncTx::Swap(tx) => L2Transaction::Swap(tx),
+            ZkSyncTx::Swap(tx) => {
+                let order_hashes = (
+                    TxHashSerializeWrapper(tx.orders.0.hash()),
+                    TxHashSerializeWrapper(tx.orders.1.hash()),
+                );
+                L2Transaction::Swap(Box::new(SwapData {
+                    tx: *tx,
+                    order_hashes,
+                }))
+            }
         };
         TransactionData::L2(tx)
     }
@@ -88,6 +158,8 @@ impl StorageTxData {
         data: StorageTxData,
         is_block_finalized: Option<bool>,
         complete_withdrawals_tx_hash: Option<H256>,
+        batch: Option<TxBatchContext>,
+        nft: Option<ApiNFT>,
     ) -> TxData {
         let tx_hash = TxHash::from_slice(&data.tx_hash).unwrap();
         let batch_id = data.batch_id.map(|id| id as u32);
@@ -120,6 +192,7 @@ impl StorageTxData {
                 tx_hash,
                 block_index: data.block_index.map(|i| i as u32),
                 block_number,
+                time_range: op.time_range(),
                 op,
                 status,
                 fail_reason: data.fail_reason,
@@ -135,6 +208,7 @@ impl StorageTxData {
                 tx_hash,
                 block_index: data.block_index.map(|i| i as u32),
                 block_number: None,
+                time_range: tx_data.time_range(),
                 op: tx_data,
                 status: TxInBlockStatus::Queued,
                 fail_reason: None,
@@ -142,10 +216,22 @@ impl StorageTxData {
                 batch_id,
             }
         };
-        let eth_signature = data.eth_sign_data.map(|eth_sign_data| {
-            let eth_sign_data: EthSignData = serde_json::from_value(eth_sign_data).unwrap();
-            eth_sign_data.signature.to_string()
+        let eth_sign_data: Option<EthSignData> = data
+            .eth_sign_data
+            .map(|eth_sign_data| serde_json::from_value(eth_sign_data).unwrap());
+        let eth_signature = eth_sign_data
+            .as_ref()
+            .map(|eth_sign_data| eth_sign_data.signature.to_string());
+        let eth_sign_payload = eth_sign_data.map(|eth_sign_data| {
+            String::from_utf8(eth_sign_data.message)
+                .unwrap_or_else(|err| format!("0x{}", hex::encode(err.into_bytes())))
         });
-        TxData { tx, eth_signature }
+        TxData {
+            tx,
+            eth_signature,
+            eth_sign_payload,
+            batch,
+            nft,
+        }
     }
 }
@@ -1,30 +1,40 @@
 // Built-in deps
-use std::time::Instant;
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    time::Instant,
+};
 
 // External imports
 use chrono::{DateTime, Utc};
+use num::bigint::ToBigInt;
 
 // Workspace imports
 use zksync_api_types::{
     v02::{
-        pagination::{AccountTxsRequest, PaginationDirection, PaginationQuery},
+        account::PubKeyChange,
+        pagination::{
+            AccountTxDirection, AccountTxsFilters, AccountTxsRequest, PaginationDirection,
+            PaginationQuery,
+        },
+        token::ApiNFT,
         transaction::{
-            ApiTxBatch, BatchStatus, Receipt, Transaction, TxData, TxHashSerializeWrapper,
-            TxInBlockStatus,
+            ApiTxBatch, BatchStatus, Receipt, Transaction, TxBatchContext, TxData,
+            TxHashSerializeWrapper, TxInBlockStatus,
         },
     },
     Either,
 };
 use zksync_crypto::params;
 use zksync_types::{
-    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, TokenId,
-    ZkSyncOp, ZkSyncTx, H256,
+    aggregated_operations::AggregatedActionType, tx::TxHash, Address, BlockNumber, SignedZkSyncTx,
+    TokenId, ZkSyncOp, ZkSyncTx, H256,
 };
 
 // Local imports
 use self::records::{
-    AccountCreatedAt, InBlockBatchTx, PriorityOpReceiptResponse, StorageTxData, StorageTxReceipt,
-    TransactionsHistoryItem, TxByHashResponse, TxReceiptResponse, Web3TxData, Web3TxReceipt,
+    AccountCreatedAt, AccountFee, InBlockBatchTx, PriorityOpReceiptResponse, StoragePubKeyChange,
+    StorageTxData, StorageTxReceipt, TransactionsHistoryItem, TxByHashResponse, TxReceiptResponse,
+    Web3TxData, Web3TxReceipt,
 };
 use crate::chain::operations_ext::records::SequenceNumberRecord;
 use crate::{
@@ -32,6 +42,7 @@ use crate::{
         block::records::TransactionItem,
         operations::{records::StoredExecutedPriorityOperation, OperationsSchema},
     },
+    query_metrics::MethodMetrics,
     QueryResult, StorageProcessor,
 };
 use itertools::Itertools;
@@ -39,6 +50,9 @@ use itertools::Itertools;
 pub(crate) mod conversion;
 pub mod records;
 
+/// Number of the last executed blocks used to estimate the finalization time of committed operations.
+const FINALIZATION_ESTIMATE_BLOCKS: u32 = 10;
+
 /// Direction to perform search of transactions to.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SearchDirection {
@@ -89,8 +103,101 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         result
     }
 
+    /// Loads the hash of the latest executed swap that filled the order with the given hash.
+    pub async fn get_swap_tx_hash_by_order_hash(
+        &mut self,
+        order_hash: &[u8],
+    ) -> QueryResult<Option<TxHash>> {
+        let start = Instant::now();
+        let tx_hash = sqlx::query!(
+            r#"
+            SELECT tx_hash FROM swap_orders
+            WHERE order_hash = $1
+            ORDER BY sequence_number DESC
+            LIMIT 1
+            "#,
+            order_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| TxHash::from_slice(&record.tx_hash).unwrap());
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_swap_tx_hash_by_order_hash",
+            start.elapsed()
+        );
+        Ok(tx_hash)
+    }
+
+    /// Stores the order hashes of at most `limit` executed swaps following the given sequence
+    /// number, so the swaps executed before the order hashes were stored can be found by them too.
+    /// Returns the sequence number of the last processed swap, or `None` if there are no swaps left.
+    pub async fn backfill_swap_orders(
+        &mut self,
+        after_sequence_number: i64,
+        limit: i64,
+    ) -> QueryResult<Option<i64>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let swaps = sqlx::query!(
+            r#"
+            SELECT sequence_number as "sequence_number!", tx_hash, tx
+            FROM executed_transactions
+            WHERE sequence_number > $1 AND tx->>'type' = 'Swap'
+            ORDER BY sequence_number
+            LIMIT $2
+            "#,
+            after_sequence_number,
+            limit
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let mut last_sequence_number = None;
+        for swap in swaps {
+            let tx: ZkSyncTx = serde_json::from_value(swap.tx)
+                .map_err(|err| anyhow::format_err!("Malformed stored transaction: {}", err))?;
+            let order_hashes = match tx {
+                ZkSyncTx::Swap(tx) => vec![
+                    tx.orders.0.hash().as_ref().to_vec(),
+                    tx.orders.1.hash().as_ref().to_vec(),
+                ],
+                _ => anyhow::bail!(
+                    "Stored transaction {} is not a swap",
+                    hex::encode(&swap.tx_hash)
+                ),
+            };
+            sqlx::query!(
+                "
+                    INSERT INTO swap_orders (order_hash, tx_hash, sequence_number)
+                    SELECT u.order_hash, $2, $3
+                        FROM UNNEST ($1::bytea[]) AS u(order_hash)
+                    ON CONFLICT (order_hash, tx_hash) DO NOTHING
+                    ",
+                &order_hashes,
+                &swap.tx_hash,
+                &swap.sequence_number
+            )
+            .execute(transaction.conn())
+            .await?;
+            last_sequence_number = Some(swap.sequence_number);
+        }
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.backfill_swap_orders",
+            start.elapsed()
+        );
+        Ok(last_sequence_number)
+    }
+
     pub async fn tx_receipt_api_v02(&mut self, hash: &[u8]) -> QueryResult<Option<Receipt>> {
         let start = Instant::now();
+        let method_metrics = MethodMetrics::start(
+            "operations_ext.tx_receipt_api_v02",
+            self.0.slow_query_threshold,
+        )
+        .arg("hash", hex::encode(hash));
         let mut transaction = self.0.start_transaction().await?;
         let hash_str = hex::encode(hash);
         let receipt: Option<StorageTxReceipt> = sqlx::query_as!(
@@ -100,30 +207,39 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     SELECT
                         tx_hash,
                         block_number,
+                        block_index,
                         success,
                         fail_reason,
                         Null::bigint as eth_block,
-                        Null::bigint as priority_op_serialid
+                        Null::bigint as priority_op_serialid,
+                        tx,
+                        created_at
                     FROM executed_transactions
                     WHERE tx_hash = $1
                 ), priority_op AS (
                     SELECT
                         tx_hash,
                         block_number,
+                        block_index,
                         true as success,
                         Null as fail_reason,
                         eth_block,
-                        priority_op_serialid
+                        priority_op_serialid,
+                        Null::jsonb as tx,
+                        created_at
                     FROM executed_priority_operations
                     WHERE tx_hash = $1 OR eth_hash = $1
                 ), mempool_tx AS (
                     SELECT
                         decode(tx_hash, 'hex'),
                         Null::bigint as block_number,
+                        Null::integer as block_index,
                         Null::boolean as success,
                         Null as fail_reason,
                         Null::bigint as eth_block,
-                        Null::bigint as priority_op_serialid
+                        Null::bigint as priority_op_serialid,
+                        Null::jsonb as tx,
+                        created_at
                     FROM mempool_txs
                     WHERE tx_hash = $2
                 ),
@@ -137,10 +253,13 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 SELECT
                     tx_hash as "tx_hash!",
                     block_number as "block_number?",
+                    block_index as "block_index?",
                     success as "success?",
                     fail_reason as "fail_reason?",
                     eth_block as "eth_block?",
-                    priority_op_serialid as "priority_op_serialid?"
+                    priority_op_serialid as "priority_op_serialid?",
+                    tx as "tx?",
+                    created_at as "created_at?"
                 FROM everything
             "#,
             hash,
@@ -149,23 +268,235 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         .fetch_optional(transaction.conn())
         .await?;
 
-        let result = if let Some(receipt) = receipt {
+        let mut result = if let Some(receipt) = receipt {
             let is_block_finalized =
                 is_block_finalized(&mut transaction, receipt.block_number).await?;
 
             Some(StorageTxReceipt::receipt_from_storage_receipt(
                 receipt,
                 is_block_finalized,
-            ))
+            )?)
+        } else if let Some(tx_hash) = TxHash::from_slice(hash) {
+            // Transaction may have been evicted from the mempool or not have reached it yet.
+            let expired_tx = transaction
+                .chain()
+                .mempool_schema()
+                .load_expired_txs(&[tx_hash])
+                .await?
+                .pop();
+            match expired_tx {
+                Some(tx) => Some(Receipt::from(tx)),
+                None => transaction
+                    .chain()
+                    .mempool_schema()
+                    .load_async_submitted_txs(&[tx_hash])
+                    .await?
+                    .pop()
+                    .map(Receipt::from),
+            }
         } else {
             None
         };
+        set_block_timestamps(&mut transaction, result.iter_mut()).await?;
+        set_confirmations(&mut transaction, result.iter_mut()).await?;
 
         transaction.commit().await?;
         metrics::histogram!(
             "sql.chain.operations_ext.tx_receipt_api_v02",
             start.elapsed()
         );
+        method_metrics.finish(result.iter().count());
+        Ok(result)
+    }
+
+    /// Loads receipts for several transactions at once.
+    ///
+    /// Both L2 transaction hashes and L1 (Ethereum) hashes of priority operations are accepted.
+    /// The resulting map is keyed by the requested hashes, and hashes that are not known
+    /// to the server are omitted from it.
+    pub async fn tx_receipts_api_v02(
+        &mut self,
+        hashes: &[TxHash],
+    ) -> QueryResult<HashMap<TxHash, Receipt>> {
+        let start = Instant::now();
+        let method_metrics = MethodMetrics::start(
+            "operations_ext.tx_receipts_api_v02",
+            self.0.slow_query_threshold,
+        )
+        .arg("hashes", hashes);
+        let mut transaction = self.0.start_transaction().await?;
+        let hashes_bytes: Vec<Vec<u8>> = hashes.iter().map(|hash| hash.as_ref().to_vec()).collect();
+        let hashes_str: Vec<String> = hashes.iter().map(hex::encode).collect();
+        let records = sqlx::query!(
+            r#"
+                WITH transaction AS (
+                    SELECT
+                        tx_hash as requested_hash,
+                        tx_hash,
+                        block_number,
+                        block_index,
+                        success,
+                        fail_reason,
+                        Null::bigint as eth_block,
+                        Null::bigint as priority_op_serialid,
+                        tx,
+                        created_at
+                    FROM executed_transactions
+                    WHERE tx_hash = ANY($1)
+                ), priority_op AS (
+                    SELECT
+                        CASE WHEN tx_hash = ANY($1) THEN tx_hash ELSE eth_hash END as requested_hash,
+                        tx_hash,
+                        block_number,
+                        block_index,
+                        true as success,
+                        Null as fail_reason,
+                        eth_block,
+                        priority_op_serialid,
+                        Null::jsonb as tx,
+                        created_at
+                    FROM executed_priority_operations
+                    WHERE tx_hash = ANY($1) OR eth_hash = ANY($1)
+                ), mempool_tx AS (
+                    SELECT
+                        decode(tx_hash, 'hex') as requested_hash,
+                        decode(tx_hash, 'hex'),
+                        Null::bigint as block_number,
+                        Null::integer as block_index,
+                        Null::boolean as success,
+                        Null as fail_reason,
+                        Null::bigint as eth_block,
+                        Null::bigint as priority_op_serialid,
+                        Null::jsonb as tx,
+                        created_at
+                    FROM mempool_txs
+                    WHERE tx_hash = ANY($2)
+                ), mempool_priority_op AS (
+                    SELECT
+                        eth_hash as requested_hash,
+                        eth_hash as tx_hash,
+                        Null::bigint as block_number,
+                        Null::integer as block_index,
+                        Null::boolean as success,
+                        Null as fail_reason,
+                        eth_block,
+                        serial_id as priority_op_serialid,
+                        Null::jsonb as tx,
+                        created_at
+                    FROM mempool_priority_operations
+                    WHERE eth_hash = ANY($1)
+                ),
+                everything AS (
+                    SELECT * FROM transaction
+                    UNION ALL
+                    SELECT * FROM priority_op
+                    UNION ALL
+                    SELECT * FROM mempool_tx
+                    UNION ALL
+                    SELECT * FROM mempool_priority_op
+                )
+                SELECT
+                    requested_hash as "requested_hash!",
+                    tx_hash as "tx_hash!",
+                    block_number as "block_number?",
+                    block_index as "block_index?",
+                    success as "success?",
+                    fail_reason as "fail_reason?",
+                    eth_block as "eth_block?",
+                    priority_op_serialid as "priority_op_serialid?",
+                    tx as "tx?",
+                    created_at as "created_at?"
+                FROM everything
+            "#,
+            &hashes_bytes,
+            &hashes_str
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+
+        let last_finalized_block = transaction
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        let mut result = HashMap::with_capacity(records.len());
+        for record in records {
+            let requested_hash = TxHash::from_slice(&record.requested_hash).unwrap();
+            let receipt = StorageTxReceipt {
+                tx_hash: record.tx_hash,
+                block_number: record.block_number,
+                block_index: record.block_index,
+                success: record.success,
+                fail_reason: record.fail_reason,
+                eth_block: record.eth_block,
+                priority_op_serialid: record.priority_op_serialid,
+                tx: record.tx,
+                created_at: record.created_at,
+            };
+            let is_block_finalized = receipt
+                .block_number
+                .map(|number| BlockNumber(number as u32) <= last_finalized_block);
+            // Executed operations go first in the query, so they take precedence
+            // over the possibly outdated mempool entries.
+            if let Entry::Vacant(entry) = result.entry(requested_hash) {
+                entry.insert(StorageTxReceipt::receipt_from_storage_receipt(
+                    receipt,
+                    is_block_finalized,
+                )?);
+            }
+        }
+        // Transactions that are not found may have been evicted from the mempool.
+        let missing_hashes: Vec<TxHash> = hashes
+            .iter()
+            .filter(|hash| !result.contains_key(*hash))
+            .copied()
+            .collect();
+        if !missing_hashes.is_empty() {
+            let expired_txs = transaction
+                .chain()
+                .mempool_schema()
+                .load_expired_txs(&missing_hashes)
+                .await?;
+            for tx in expired_txs {
+                let tx_hash = missing_hashes
+                    .iter()
+                    .find(|hash| hex::encode(hash) == tx.tx_hash);
+                if let Some(tx_hash) = tx_hash {
+                    result.insert(*tx_hash, Receipt::from(tx));
+                }
+            }
+        }
+        // Or not have reached the mempool after the asynchronous submission.
+        let missing_hashes: Vec<TxHash> = hashes
+            .iter()
+            .filter(|hash| !result.contains_key(*hash))
+            .copied()
+            .collect();
+        if !missing_hashes.is_empty() {
+            let async_txs = transaction
+                .chain()
+                .mempool_schema()
+                .load_async_submitted_txs(&missing_hashes)
+                .await?;
+            for tx in async_txs {
+                let tx_hash = missing_hashes
+                    .iter()
+                    .find(|hash| hex::encode(hash) == tx.tx_hash);
+                if let Some(tx_hash) = tx_hash {
+                    result.insert(*tx_hash, Receipt::from(tx));
+                }
+            }
+        }
+        set_block_timestamps(&mut transaction, result.values_mut()).await?;
+        set_confirmations(&mut transaction, result.values_mut()).await?;
+
+        transaction.commit().await?;
+        metrics::histogram!(
+            "sql.chain.operations_ext.tx_receipts_api_v02",
+            start.elapsed()
+        );
+        method_metrics.finish(result.len());
         Ok(result)
     }
 
@@ -251,6 +582,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
 
     pub async fn tx_data_api_v02(&mut self, hash: &[u8]) -> QueryResult<Option<TxData>> {
         let start = Instant::now();
+        let method_metrics = MethodMetrics::start(
+            "operations_ext.tx_data_api_v02",
+            self.0.slow_query_threshold,
+        )
+        .arg("hash", hex::encode(hash));
         let mut transaction = self.0.start_transaction().await?;
         let hash_str = hex::encode(hash);
         let data: Option<StorageTxData> = sqlx::query_as!(
@@ -337,6 +673,34 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
 
         transaction.commit().await?;
         metrics::histogram!("sql.chain.operations_ext.tx_data_api_v02", start.elapsed());
+        method_metrics.finish(result.iter().count());
+        Ok(result)
+    }
+
+    /// Loads the L2 transaction with the given hash in the form it was submitted by the user.
+    /// Executed transactions are looked up first, then the mempool.
+    pub async fn get_signed_tx(&mut self, hash: &[u8]) -> QueryResult<Option<SignedZkSyncTx>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let executed_tx = OperationsSchema(&mut transaction)
+            .get_executed_operation(hash)
+            .await?;
+        let result = if let Some(executed_tx) = executed_tx {
+            Some(SignedZkSyncTx {
+                tx: serde_json::from_value(executed_tx.tx)?,
+                eth_sign_data: executed_tx
+                    .eth_sign_data
+                    .map(serde_json::from_value)
+                    .transpose()?,
+                created_at: executed_tx.created_at,
+            })
+        } else {
+            transaction.chain().mempool_schema().get_tx(hash).await?
+        };
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.chain.operations_ext.get_signed_tx", start.elapsed());
         Ok(result)
     }
 
@@ -345,10 +709,16 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         op_id: u32,
     ) -> QueryResult<PriorityOpReceiptResponse> {
         let start = Instant::now();
+        let method_metrics = MethodMetrics::start(
+            "operations_ext.get_priority_op_receipt",
+            self.0.slow_query_threshold,
+        )
+        .arg("op_id", op_id);
         let stored_executed_prior_op = OperationsSchema(self.0)
             .get_executed_priority_operation(op_id)
             .await?;
 
+        let stored_executed_prior_op_found = stored_executed_prior_op.is_some();
         let result = match stored_executed_prior_op {
             Some(stored_executed_prior_op) => {
                 let verified = OperationsSchema(self.0)
@@ -377,6 +747,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             "sql.chain.operations_ext.get_priority_op_receipt",
             start.elapsed()
         );
+        method_metrics.finish(stored_executed_prior_op_found as usize);
         result
     }
 
@@ -1034,6 +1405,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                         query.from.address,
                         address,
                         query.from.token,
+                        &query.from.filters,
                         i64::from(query.limit),
                         id_from,
                         query.direction,
@@ -1048,6 +1420,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     .get_tx_seq_numbers_for_account(
                         query.from.address,
                         query.from.token,
+                        &query.from.filters,
                         i64::from(query.limit),
                         id_from,
                         query.direction,
@@ -1117,6 +1490,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         address: Address,
         second_address: Address,
         token: Option<TokenId>,
+        filters: &AccountTxsFilters,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
@@ -1133,6 +1507,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 LIMIT $5"
             }
         };
+        let filters_query = account_txs_filters_query(filters, executed_transaction_column, 6);
 
         let token_query = if token.is_some() {
             "AND token = $3"
@@ -1162,11 +1537,11 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                     block_index,
                     batch_id
                 FROM tx_hashes INNER JOIN executed_transactions 
-                    ON tx_hashes.tx_hash = executed_transactions.tx_hash
+                    ON tx_hashes.tx_hash = executed_transactions.tx_hash {}
                 {}
                 
             "#,
-            token_query, token_query, query_direction
+            token_query, token_query, filters_query, query_direction
         );
 
         Ok(sqlx::query_as(&query)
@@ -1175,6 +1550,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
             .bind(limit)
+            .bind(&filters.tx_type)
+            .bind(filters.from_time)
+            .bind(filters.to_time)
             .fetch_all(self.0.conn())
             .await?)
     }
@@ -1183,32 +1561,37 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         &mut self,
         address: Address,
         token: Option<TokenId>,
+        filters: &AccountTxsFilters,
         limit: i64,
         id_from: i64,
         direction: PaginationDirection,
     ) -> QueryResult<Vec<SequenceNumberRecord>> {
         let query_direction = match direction {
             PaginationDirection::Newer => {
-                "AND sequence_number  >= $3
-                ORDER BY sequence_number
+                "AND tx_filters.sequence_number  >= $3
+                ORDER BY tx_filters.sequence_number
                 LIMIT $4"
             }
             PaginationDirection::Older => {
-                "AND sequence_number <= $3
-                ORDER BY sequence_number DESC
+                "AND tx_filters.sequence_number <= $3
+                ORDER BY tx_filters.sequence_number DESC
                 LIMIT $4"
             }
         };
 
         let token_query = if token.is_some() {
-            "AND token = $2"
+            "AND tx_filters.token = $2"
         } else {
             ""
         };
 
         let query = format!(
-            "SELECT DISTINCT sequence_number, is_priority FROM tx_filters WHERE address = $1 {} {}",
-            token_query, query_direction
+            "SELECT DISTINCT tx_filters.sequence_number, tx_filters.is_priority FROM tx_filters {} \
+            WHERE tx_filters.address = $1 {} {} {}",
+            account_txs_filters_join(filters),
+            token_query,
+            account_txs_filters_query(filters, executed_operation_column, 5),
+            query_direction
         );
 
         Ok(sqlx::query_as(&query)
@@ -1216,6 +1599,9 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
             .bind(token.unwrap_or_default().0 as i32)
             .bind(id_from)
             .bind(limit)
+            .bind(&filters.tx_type)
+            .bind(filters.from_time)
+            .bind(filters.to_time)
             .fetch_all(self.0.conn())
             .await?)
     }
@@ -1354,10 +1740,14 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         address: Address,
         token: Option<TokenId>,
         second_address: Option<Address>,
+        filters: &AccountTxsFilters,
     ) -> QueryResult<u32> {
         let start = Instant::now();
 
-        let count = if let Some(second_address) = second_address {
+        let count = if !filters.is_empty() {
+            self.get_filtered_account_transactions_count(address, token, second_address, filters)
+                .await?
+        } else if let Some(second_address) = second_address {
             sqlx::query!(
                 r#"
                 WITH tx_hashes AS (
@@ -1402,6 +1792,160 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(count as u32)
     }
 
+    async fn get_filtered_account_transactions_count(
+        &mut self,
+        address: Address,
+        token: Option<TokenId>,
+        second_address: Option<Address>,
+        filters: &AccountTxsFilters,
+    ) -> QueryResult<i64> {
+        let token_query = if token.is_some() {
+            "AND tx_filters.token = $3"
+        } else {
+            ""
+        };
+
+        let query = if second_address.is_some() {
+            format!(
+                r#"
+                WITH tx_hashes AS (
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $1 {0}
+                    INTERSECT
+                    SELECT DISTINCT tx_hash FROM tx_filters
+                    WHERE address = $2 {0}
+                )
+                SELECT COUNT(*) FROM tx_hashes INNER JOIN executed_transactions
+                    ON tx_hashes.tx_hash = executed_transactions.tx_hash {1}
+                "#,
+                token_query,
+                account_txs_filters_query(filters, executed_transaction_column, 4)
+            )
+        } else {
+            format!(
+                r#"
+                SELECT COUNT(DISTINCT tx_filters.tx_hash) FROM tx_filters {}
+                WHERE tx_filters.address = $1 {} {}
+                "#,
+                account_txs_filters_join(filters),
+                token_query,
+                account_txs_filters_query(filters, executed_operation_column, 4)
+            )
+        };
+
+        let (count,): (i64,) = sqlx::query_as(&query)
+            .bind(address.as_bytes())
+            .bind(second_address.unwrap_or_default().as_bytes())
+            .bind(token.unwrap_or_default().0 as i32)
+            .bind(&filters.tx_type)
+            .bind(filters.from_time)
+            .bind(filters.to_time)
+            .fetch_one(self.0.conn())
+            .await?;
+        Ok(count)
+    }
+
+    /// Returns all the executed `ChangePubKey` transactions of the account in the execution order.
+    pub async fn get_account_pubkey_changes(
+        &mut self,
+        address: Address,
+    ) -> QueryResult<Vec<PubKeyChange>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let records = sqlx::query_as!(
+            StoragePubKeyChange,
+            r#"
+                SELECT DISTINCT ON (executed_transactions.sequence_number)
+                    executed_transactions.tx_hash,
+                    executed_transactions.block_number,
+                    executed_transactions.tx,
+                    executed_transactions.success,
+                    executed_transactions.fail_reason,
+                    executed_transactions.created_at
+                FROM tx_filters INNER JOIN executed_transactions
+                    ON executed_transactions.sequence_number = tx_filters.sequence_number
+                WHERE tx_filters.address = $1 AND NOT tx_filters.is_priority
+                    AND executed_transactions.tx->>'type' = 'ChangePubKey'
+                ORDER BY executed_transactions.sequence_number
+            "#,
+            address.as_bytes()
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let last_finalized = transaction
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+        transaction.commit().await?;
+
+        let mut changes = Vec::with_capacity(records.len());
+        for record in records {
+            let tx = match serde_json::from_value(record.tx)? {
+                ZkSyncTx::ChangePubKey(tx) => tx,
+                _ => continue,
+            };
+            let block_number = BlockNumber(record.block_number as u32);
+            let status = if !record.success {
+                TxInBlockStatus::Rejected
+            } else if block_number <= last_finalized {
+                TxInBlockStatus::Finalized
+            } else {
+                TxInBlockStatus::Committed
+            };
+            changes.push(PubKeyChange {
+                tx_hash: TxHash::from_slice(&record.tx_hash).unwrap(),
+                block_number,
+                new_pub_key_hash: tx.new_pk_hash,
+                auth_type: tx.get_auth_type(),
+                status,
+                fail_reason: record.fail_reason,
+                created_at: record.created_at,
+            });
+        }
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_account_pubkey_changes",
+            start.elapsed()
+        );
+        Ok(changes)
+    }
+
+    /// Returns the total fees paid by the account in the successfully executed transactions,
+    /// grouped by transaction type and fee token.
+    pub async fn get_account_fees(&mut self, address: Address) -> QueryResult<Vec<AccountFee>> {
+        let start = Instant::now();
+        // Fee token is stored as `feeToken` for the transactions that may pay fee in a
+        // token other than the transferred one and as `token` for the rest of them.
+        let records = sqlx::query!(
+            r#"
+                SELECT tx->>'type' as "tx_type!",
+                    COALESCE(tx->>'feeToken', tx->>'token')::integer as "token_id!",
+                    SUM((tx->>'fee')::numeric) as "total_fee!"
+                FROM executed_transactions
+                WHERE primary_account_address = $1 AND success = true
+                    AND tx->>'fee' IS NOT NULL
+                GROUP BY 1, 2
+                ORDER BY 1, 2
+            "#,
+            address.as_bytes()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let fees = records
+            .into_iter()
+            .map(|record| AccountFee {
+                tx_type: record.tx_type,
+                token_id: TokenId(record.token_id as u32),
+                total_fee: record.total_fee.to_bigint().unwrap().to_biguint().unwrap(),
+            })
+            .collect();
+
+        metrics::histogram!("sql.chain.operations_ext.get_account_fees", start.elapsed());
+        Ok(fees)
+    }
+
     /// Returns `created_at` for `block_number` fields for transaction with given hash.
     pub async fn get_tx_sequence_number_for_block(
         &mut self,
@@ -1547,6 +2091,77 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
         Ok(result)
     }
 
+    /// Loads the hashes of all the batch transactions along with the batch signatures.
+    /// Transactions are looked up both in executed transactions and in the mempool.
+    pub async fn get_batch_context(
+        &mut self,
+        batch_id: i64,
+    ) -> QueryResult<Option<TxBatchContext>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let tx_hashes: Vec<TxHash> = sqlx::query!(
+            r#"
+                WITH transactions AS (
+                    SELECT tx_hash, sequence_number as seq_no
+                    FROM executed_transactions
+                    WHERE batch_id = $1
+                ), mempool_transactions AS (
+                    SELECT decode(tx_hash, 'hex') as tx_hash, id as seq_no
+                    FROM mempool_txs
+                    WHERE batch_id = $1
+                ),
+                everything AS (
+                    SELECT * FROM transactions
+                    UNION ALL
+                    SELECT * FROM mempool_transactions
+                )
+                SELECT tx_hash as "tx_hash!" FROM everything
+                ORDER BY seq_no ASC
+            "#,
+            batch_id
+        )
+        .fetch_all(transaction.conn())
+        .await?
+        .into_iter()
+        .map(|record| TxHash::from_slice(&record.tx_hash).unwrap())
+        .unique()
+        .collect();
+
+        let result = if !tx_hashes.is_empty() {
+            let eth_signatures = sqlx::query!(
+                "SELECT eth_signature FROM txs_batches_signatures
+                WHERE batch_id = $1
+                ORDER BY id ASC",
+                batch_id
+            )
+            .fetch_all(transaction.conn())
+            .await?
+            .into_iter()
+            .map(|record| {
+                serde_json::from_value(record.eth_signature)
+                    .expect("failed to decode TxEthSignature")
+            })
+            .collect();
+
+            Some(TxBatchContext {
+                batch_id: batch_id as u32,
+                batch_hash: TxHash::batch_hash(&tx_hashes),
+                transaction_hashes: tx_hashes.into_iter().map(TxHashSerializeWrapper).collect(),
+                eth_signatures,
+            })
+        } else {
+            None
+        };
+        transaction.commit().await?;
+
+        metrics::histogram!(
+            "sql.chain.operations_ext.get_batch_context",
+            start.elapsed()
+        );
+        Ok(result)
+    }
+
     pub async fn get_batch_info(&mut self, batch_hash: TxHash) -> QueryResult<Option<ApiTxBatch>> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
@@ -1987,7 +2602,7 @@ async fn complete_withdrawals_tx_hash(
 ) -> QueryResult<Option<H256>> {
     let result = if let Some(tx_type) = data.op.get("type") {
         let tx_type = tx_type.as_str().unwrap();
-        if tx_type == "Withdraw" || tx_type == "ForcedExit" {
+        if tx_type == "Withdraw" || tx_type == "ForcedExit" || tx_type == "WithdrawNFT" {
             transaction
                 .chain()
                 .operations_schema()
@@ -2002,6 +2617,35 @@ async fn complete_withdrawals_tx_hash(
     Ok(result)
 }
 
+/// Loads the NFT created by the `MintNFT` or withdrawn by the `WithdrawNFT` transaction.
+async fn tx_nft(
+    transaction: &mut StorageProcessor<'_>,
+    data: &StorageTxData,
+) -> QueryResult<Option<ApiNFT>> {
+    // Only L2 transactions that were successfully executed can refer to an existing NFT.
+    if data.eth_hash.is_some() || data.success != Some(true) {
+        return Ok(None);
+    }
+    let token_id = match serde_json::from_value(data.op.clone()) {
+        Ok(ZkSyncTx::MintNFT(tx)) => {
+            transaction
+                .tokens_schema()
+                .get_minted_nft_id(tx.creator_id, tx.nonce, tx.content_hash)
+                .await?
+        }
+        Ok(ZkSyncTx::WithdrawNFT(tx)) => Some(tx.token),
+        _ => None,
+    };
+    if let Some(token_id) = token_id {
+        transaction
+            .tokens_schema()
+            .get_nft_with_factories(token_id)
+            .await
+    } else {
+        Ok(None)
+    }
+}
+
 async fn is_block_finalized(
     transaction: &mut StorageProcessor<'_>,
     block_number: Option<i64>,
@@ -2021,6 +2665,156 @@ async fn is_block_finalized(
     }
 }
 
+/// Sets the commit and finalization times of the blocks the operations were included in.
+/// For committed operations, also sets the expected finalization time, which is based on
+/// the average finalization time of the recently executed blocks.
+async fn set_block_timestamps<'r>(
+    transaction: &mut StorageProcessor<'_>,
+    receipts: impl IntoIterator<Item = &'r mut Receipt>,
+) -> QueryResult<()> {
+    let receipts: Vec<_> = receipts
+        .into_iter()
+        .filter(|receipt| receipt.rollup_block().is_some())
+        .collect();
+    if receipts.is_empty() {
+        return Ok(());
+    }
+
+    let blocks: Vec<_> = receipts
+        .iter()
+        .filter_map(|receipt| receipt.rollup_block())
+        .collect();
+    let timestamps: HashMap<_, _> = transaction
+        .chain()
+        .block_schema()
+        .load_blocks_timestamps(&blocks)
+        .await?
+        .into_iter()
+        .map(|timestamps| (BlockNumber(timestamps.block_number as u32), timestamps))
+        .collect();
+    let finalization_time = if receipts
+        .iter()
+        .any(|receipt| receipt.status() == TxInBlockStatus::Committed)
+    {
+        transaction
+            .chain()
+            .block_schema()
+            .average_block_finalization_time(FINALIZATION_ESTIMATE_BLOCKS)
+            .await?
+    } else {
+        None
+    };
+
+    for receipt in receipts {
+        let block_timestamps = receipt
+            .rollup_block()
+            .and_then(|block| timestamps.get(&block));
+        let committed_at = block_timestamps.map(|timestamps| timestamps.committed_at);
+        let finalized_at = block_timestamps.and_then(|timestamps| timestamps.finalized_at);
+        receipt.set_block_timestamps(committed_at, finalized_at);
+
+        if receipt.status() == TxInBlockStatus::Committed {
+            let estimate = committed_at
+                .zip(finalization_time)
+                .map(|(committed_at, finalization_time)| committed_at + finalization_time);
+            receipt.set_estimated_finalization(estimate);
+        }
+    }
+    Ok(())
+}
+
+/// Sets the number of Ethereum confirmations of the priority operations based on
+/// the latest block seen by the Ethereum watcher.
+async fn set_confirmations<'r>(
+    transaction: &mut StorageProcessor<'_>,
+    receipts: impl IntoIterator<Item = &'r mut Receipt>,
+) -> QueryResult<()> {
+    let mut receipts = receipts
+        .into_iter()
+        .filter(|receipt| matches!(receipt, Receipt::L1(_)))
+        .peekable();
+    if receipts.peek().is_none() {
+        return Ok(());
+    }
+
+    if let Some(last_eth_block) = transaction
+        .chain()
+        .mempool_schema()
+        .get_last_watched_eth_block()
+        .await?
+    {
+        for receipt in receipts {
+            receipt.set_confirmations(last_eth_block);
+        }
+    }
+    Ok(())
+}
+
+/// Joins the executed operations to `tx_filters` if they are needed to apply the filters.
+fn account_txs_filters_join(filters: &AccountTxsFilters) -> &'static str {
+    if filters.is_empty() {
+        ""
+    } else {
+        "LEFT JOIN executed_transactions ON NOT tx_filters.is_priority \
+            AND executed_transactions.sequence_number = tx_filters.sequence_number \
+        LEFT JOIN executed_priority_operations ON tx_filters.is_priority \
+            AND executed_priority_operations.sequence_number = tx_filters.sequence_number"
+    }
+}
+
+/// Column of an executed transaction.
+fn executed_transaction_column(column: &str) -> String {
+    if column == "type" {
+        "executed_transactions.tx->>'type'".to_string()
+    } else {
+        format!("executed_transactions.{}", column)
+    }
+}
+
+/// Column of either an executed transaction or an executed priority operation
+/// joined by `account_txs_filters_join`.
+fn executed_operation_column(column: &str) -> String {
+    if column == "type" {
+        "COALESCE(executed_transactions.tx->>'type', executed_priority_operations.operation->>'type')"
+            .to_string()
+    } else {
+        format!(
+            "COALESCE(executed_transactions.{0}, executed_priority_operations.{0})",
+            column
+        )
+    }
+}
+
+/// Builds the conditions for the account transactions filters.
+/// The account address is expected to be bound to `$1`, the transaction type and
+/// the time range bounds to `$first_param`, `$first_param + 1` and `$first_param + 2`.
+fn account_txs_filters_query(
+    filters: &AccountTxsFilters,
+    column: impl Fn(&str) -> String,
+    first_param: usize,
+) -> String {
+    let mut query = String::new();
+    match filters.tx_direction {
+        Some(AccountTxDirection::Incoming) => {
+            query += &format!(" AND {} = $1", column("to_account"));
+        }
+        Some(AccountTxDirection::Outgoing) => {
+            query += &format!(" AND {} = $1", column("from_account"));
+        }
+        None => {}
+    }
+    if filters.tx_type.is_some() {
+        query += &format!(" AND {} = ${}", column("type"), first_param);
+    }
+    if filters.from_time.is_some() {
+        query += &format!(" AND {} >= ${}", column("created_at"), first_param + 1);
+    }
+    if filters.to_time.is_some() {
+        query += &format!(" AND {} <= ${}", column("created_at"), first_param + 2);
+    }
+    query
+}
+
 async fn tx_data_from_storage(
     transaction: &mut StorageProcessor<'_>,
     data: StorageTxData,
@@ -2029,9 +2823,25 @@ async fn tx_data_from_storage(
 
     let is_block_finalized = is_block_finalized(transaction, data.block_number).await?;
 
+    // Special case: batch_id == 0 <==> transaction is not a part of some batch
+    let batch = match data.batch_id {
+        Some(batch_id) if batch_id != 0 => {
+            transaction
+                .chain()
+                .operations_ext_schema()
+                .get_batch_context(batch_id)
+                .await?
+        }
+        _ => None,
+    };
+
+    let nft = tx_nft(transaction, &data).await?;
+
     Ok(StorageTxData::data_from_storage_data(
         data,
         is_block_finalized,
         complete_withdrawals_tx_hash,
+        batch,
+        nft,
     ))
 }
@@ -4,10 +4,12 @@
 
 // External imports
 use chrono::prelude::*;
+use num::BigUint;
 use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 use sqlx::FromRow;
 // Workspace imports
+use zksync_types::TokenId;
 // Local imports
 use crate::prover::records::ProverRun;
 
@@ -155,10 +157,13 @@ pub struct InBlockBatchTx {
 pub struct StorageTxReceipt {
     pub tx_hash: Vec<u8>,
     pub block_number: Option<i64>,
+    pub block_index: Option<i32>,
     pub success: Option<bool>,
     pub fail_reason: Option<String>,
     pub eth_block: Option<i64>,
     pub priority_op_serialid: Option<i64>,
+    pub tx: Option<Value>,
+    pub created_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, FromRow, PartialEq)]
@@ -199,8 +204,26 @@ pub struct Web3TxReceipt {
     pub success: bool,
 }
 
+#[derive(Debug, FromRow, Clone, PartialEq)]
+pub struct StoragePubKeyChange {
+    pub tx_hash: Vec<u8>,
+    pub block_number: i64,
+    pub tx: Value,
+    pub success: bool,
+    pub fail_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, FromRow, Clone, PartialEq)]
 pub struct SequenceNumberRecord {
     pub sequence_number: i64,
     pub is_priority: bool,
 }
+
+/// Total fee paid by the account in a single token for a single transaction type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountFee {
+    pub tx_type: String,
+    pub token_id: TokenId,
+    pub total_fee: BigUint,
+}
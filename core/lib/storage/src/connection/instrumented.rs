@@ -0,0 +1,148 @@
+//! Executor that logs the slow statements along with the storage method that issued them.
+//!
+//! Every schema method runs its statements on the connection returned by `StorageProcessor::conn`,
+//! which captures the location of the call. Unlike the `sqlx` log of the slow statements, the
+//! warning tells which method and request is slow. The arguments of the statements can't be
+//! decoded back, the methods that need them logged are wrapped into `MethodMetrics`.
+
+// Built-in deps
+use std::{
+    panic::Location,
+    time::{Duration, Instant},
+};
+// External imports
+use either::Either;
+use futures_util::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt};
+use sqlx::{
+    database::HasStatement,
+    postgres::{PgQueryResult, PgRow, PgTypeInfo},
+    Describe, Error as SqlxError, Execute, Executor, PgConnection, Postgres,
+};
+
+/// Connection of the storage processor that measures the duration of the statements.
+#[derive(Debug)]
+pub struct InstrumentedConnection<'c> {
+    conn: &'c mut PgConnection,
+    slow_query_threshold: Option<Duration>,
+    location: &'static Location<'static>,
+}
+
+impl<'c> InstrumentedConnection<'c> {
+    pub(crate) fn new(
+        conn: &'c mut PgConnection,
+        slow_query_threshold: Option<Duration>,
+        location: &'static Location<'static>,
+    ) -> Self {
+        Self {
+            conn,
+            slow_query_threshold,
+            location,
+        }
+    }
+
+    fn logger<'q>(&self, sql: &'q str) -> StatementLogger<'q> {
+        StatementLogger {
+            sql,
+            rows: 0,
+            start: Instant::now(),
+            slow_query_threshold: self.slow_query_threshold,
+            location: self.location,
+        }
+    }
+}
+
+/// Logs the statement when it's dropped, i.e. when its results are consumed or discarded.
+struct StatementLogger<'q> {
+    sql: &'q str,
+    rows: u64,
+    start: Instant,
+    slow_query_threshold: Option<Duration>,
+    location: &'static Location<'static>,
+}
+
+impl<'q> Drop for StatementLogger<'q> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        let threshold = match self.slow_query_threshold {
+            Some(threshold) => threshold,
+            None => return,
+        };
+        if elapsed >= threshold {
+            metrics::increment_counter!("sql.slow_statements");
+            vlog::warn!(
+                "Slow statement issued at {}:{} took {:?}, rows: {}, SQL: {}",
+                self.location.file(),
+                self.location.line(),
+                elapsed,
+                self.rows,
+                self.sql.split_whitespace().collect::<Vec<_>>().join(" ")
+            );
+        }
+    }
+}
+
+impl<'c> Executor<'c> for InstrumentedConnection<'c> {
+    type Database = Postgres;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<PgQueryResult, PgRow>, SqlxError>>
+    where
+        'c: 'e,
+        E: Execute<'q, Postgres>,
+    {
+        let mut logger = self.logger(query.sql());
+        self.conn
+            .fetch_many(query)
+            .map(move |step| {
+                // Completion of the statement reports the number of the returned rows as well.
+                if let Ok(Either::Left(result)) = &step {
+                    logger.rows += result.rows_affected();
+                }
+                step
+            })
+            .boxed()
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<PgRow>, SqlxError>>
+    where
+        'c: 'e,
+        E: Execute<'q, Postgres>,
+    {
+        let mut logger = self.logger(query.sql());
+        let row = self.conn.fetch_optional(query);
+        async move {
+            let row = row.await;
+            if let Ok(Some(_)) = &row {
+                logger.rows += 1;
+            }
+            row
+        }
+        .boxed()
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [PgTypeInfo],
+    ) -> BoxFuture<'e, Result<<Postgres as HasStatement<'q>>::Statement, SqlxError>>
+    where
+        'c: 'e,
+    {
+        self.conn.prepare_with(sql, parameters)
+    }
+
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Postgres>, SqlxError>>
+    where
+        'c: 'e,
+    {
+        self.conn.describe(sql)
+    }
+}
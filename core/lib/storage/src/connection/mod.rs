@@ -1,10 +1,12 @@
 // Built-in deps
-use std::{fmt, time::Duration, time::Instant};
+use std::{fmt, str::FromStr, time::Duration, time::Instant};
 // External imports
 use async_trait::async_trait;
 use deadpool::managed::{Manager, PoolConfig, RecycleResult, Timeouts};
 use deadpool::Runtime;
-use sqlx::{Connection, Error as SqlxError, PgConnection};
+use sqlx::{
+    postgres::PgConnectOptions, ConnectOptions, Connection, Error as SqlxError, PgConnection,
+};
 use tokio::time;
 // Local imports
 // use self::recoverable_connection::RecoverableConnection;
@@ -12,6 +14,7 @@ use crate::{get_database_replica_url, get_database_url, StorageProcessor};
 use zksync_utils::parse_env;
 
 pub mod holder;
+pub mod instrumented;
 
 type Pool = deadpool::managed::Pool<DbPool>;
 
@@ -19,19 +22,34 @@ pub type PooledConnection = deadpool::managed::Object<DbPool>;
 
 pub const DB_CONNECTION_RETRIES: u32 = 3;
 
+/// Returns the options of the connection to the database with the given URL.
+/// If the slow query threshold is set, the slow statements are logged by the storage
+/// processor along with the method that issued them instead of `sqlx`.
+pub(crate) fn connect_options(
+    url: &str,
+    slow_query_threshold: Option<Duration>,
+) -> Result<PgConnectOptions, SqlxError> {
+    let mut options = PgConnectOptions::from_str(url)?;
+    if let Some(threshold) = slow_query_threshold {
+        options.log_slow_statements(log::LevelFilter::Off, threshold);
+    }
+    Ok(options)
+}
+
 #[derive(Clone)]
 pub struct DbPool {
-    url: String,
+    options: PgConnectOptions,
 }
 
 impl DbPool {
-    fn create(url: impl Into<String>, max_size: usize) -> Pool {
+    fn create(url: &str, max_size: usize, slow_query_threshold: Option<Duration>) -> Pool {
         let pool_config = PoolConfig {
             max_size,
             timeouts: Timeouts::wait_millis(20_000), // wait 20 seconds before returning error
             runtime: Runtime::Tokio1,
         };
-        Pool::from_config(DbPool { url: url.into() }, pool_config)
+        let options = connect_options(url, slow_query_threshold).expect("invalid database URL");
+        Pool::from_config(DbPool { options }, pool_config)
     }
 }
 
@@ -40,7 +58,7 @@ impl Manager for DbPool {
     type Type = PgConnection;
     type Error = SqlxError;
     async fn create(&self) -> Result<PgConnection, SqlxError> {
-        PgConnection::connect(&self.url).await
+        self.options.connect().await
     }
     async fn recycle(&self, obj: &mut PgConnection) -> RecycleResult<SqlxError> {
         Ok(obj.ping().await?)
@@ -55,6 +73,10 @@ impl Manager for DbPool {
 #[derive(Clone)]
 pub struct ConnectionPool {
     pool: Pool,
+    database_url: String,
+    max_size: usize,
+    /// Statements taking longer are logged along with the storage method that issued them.
+    slow_query_threshold: Option<Duration>,
 }
 
 impl fmt::Debug for ConnectionPool {
@@ -71,9 +93,7 @@ impl ConnectionPool {
         let database_url = get_database_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
 
-        let pool = DbPool::create(database_url, max_size as usize);
-
-        Self { pool }
+        Self::create(database_url, max_size as usize)
     }
 
     /// Establishes a pool of the connections to the replica of database and
@@ -84,9 +104,25 @@ impl ConnectionPool {
         let database_url = get_database_replica_url();
         let max_size = pool_max_size.unwrap_or_else(|| parse_env("DATABASE_POOL_SIZE"));
 
-        let pool = DbPool::create(database_url, max_size as usize);
+        Self::create(database_url, max_size as usize)
+    }
+
+    fn create(database_url: String, max_size: usize) -> Self {
+        let pool = DbPool::create(&database_url, max_size, None);
+        Self {
+            pool,
+            database_url,
+            max_size,
+            slow_query_threshold: None,
+        }
+    }
 
-        Self { pool }
+    /// Makes the storage log the statements and the instrumented methods that take longer
+    /// than the threshold, which is expected to be taken from `DBConfig::slow_query_threshold`.
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.pool = DbPool::create(&self.database_url, self.max_size, Some(threshold));
+        self.slow_query_threshold = Some(threshold);
+        self
     }
     /// Creates a `StorageProcessor` entity over a recoverable connection.
     /// Upon a database outage connection will block the thread until
@@ -101,7 +137,9 @@ impl ConnectionPool {
         let connection = self.get_pooled_connection().await;
         metrics::histogram!("sql.connection_acquire", start.elapsed());
 
-        Ok(StorageProcessor::from_pool(connection))
+        let mut storage = StorageProcessor::from_pool(connection);
+        storage.slow_query_threshold = self.slow_query_threshold;
+        Ok(storage)
     }
 
     async fn get_pooled_connection(&self) -> PooledConnection {
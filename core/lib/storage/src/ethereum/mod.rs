@@ -13,6 +13,7 @@ use zksync_types::{
     },
     BlockNumber, H256, U256,
 };
+use zksync_utils::big_decimal_to_ratio;
 // Local imports
 use self::records::{ETHOperationData, ETHParams, ETHStats, ETHTxHash, StorageETHOperation};
 use crate::{chain::operations::records::StoredAggregatedOperation, QueryResult, StorageProcessor};
@@ -292,6 +293,25 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(confirmed)
     }
 
+    /// Stores the amount of gas used by the confirmed Ethereum transaction.
+    pub async fn save_eth_tx_gas_used(&mut self, hash: &H256, gas_used: u64) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let eth_op_id = EthereumSchema(&mut transaction).get_eth_op_id(hash).await?;
+        sqlx::query!(
+            "UPDATE eth_operations SET gas_used = $1 WHERE id = $2",
+            gas_used as i64,
+            eth_op_id
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.ethereum.save_eth_tx_gas_used", start.elapsed());
+        Ok(())
+    }
+
     /// Retrieves the Ethereum operation ID given the tx hash.
     async fn get_eth_op_id(&mut self, hash: &H256) -> QueryResult<i64> {
         let start = Instant::now();
@@ -468,6 +488,36 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(average_gas_price)
     }
 
+    /// Loads the average gas price paid for the last confirmed Ethereum transactions.
+    pub async fn load_average_paid_gas_price(
+        &mut self,
+        last_transactions: u32,
+    ) -> QueryResult<Option<BigUint>> {
+        let start = Instant::now();
+        let average_gas_price = sqlx::query!(
+            r#"
+            SELECT AVG(last_used_gas_price) AS average_gas_price FROM (
+                SELECT last_used_gas_price FROM eth_operations
+                WHERE confirmed = true
+                ORDER BY id DESC
+                LIMIT $1
+            ) AS recent_operations
+            "#,
+            i64::from(last_transactions)
+        )
+        .fetch_one(self.0.conn())
+        .await?
+        .average_gas_price
+        .map(|price| {
+            big_decimal_to_ratio(&price)
+                .expect("Negative gas price stored in DB")
+                .to_integer()
+        });
+
+        metrics::histogram!("sql.ethereum.load_average_paid_gas_price", start.elapsed());
+        Ok(average_gas_price)
+    }
+
     /// Loads the stored Ethereum operations stats.
     pub async fn load_stats(&mut self) -> QueryResult<ETHStats> {
         let start = Instant::now();
@@ -698,6 +748,32 @@ impl<'a, 'c> EthereumSchema<'a, 'c> {
         Ok(final_hash)
     }
 
+    /// Returns the hash of the confirmed Ethereum transaction that sent the aggregated
+    /// operation with the given ID.
+    pub async fn aggregated_op_final_hash_by_id(
+        &mut self,
+        aggregated_op_id: i64,
+    ) -> QueryResult<Option<H256>> {
+        let start = Instant::now();
+        let final_hash = sqlx::query!(
+            "SELECT eth_operations.final_hash FROM eth_aggregated_ops_binding
+                INNER JOIN eth_operations ON eth_aggregated_ops_binding.eth_op_id = eth_operations.id
+            WHERE eth_aggregated_ops_binding.op_id = $1 AND eth_operations.confirmed = true
+            LIMIT 1",
+            aggregated_op_id
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .and_then(|record| record.final_hash)
+        .map(|hash| H256::from_slice(&hash));
+
+        metrics::histogram!(
+            "sql.ethereum.aggregated_op_final_hash_by_id",
+            start.elapsed()
+        );
+        Ok(final_hash)
+    }
+
     // Updates eth_parameters with given nonce and last block.
     // It updates last_verified_block only if it is greater than given last block.
     pub async fn update_eth_parameters(&mut self, last_block: BlockNumber) -> QueryResult<()> {
@@ -169,17 +169,32 @@ impl<'a, 'c> EventSchema<'a, 'c> {
     ) -> QueryResult<()> {
         let start = Instant::now();
         let mut transaction = self.0.start_transaction().await?;
-        // Store new account event for each update in the block.
-        let events: Vec<_> = transaction
+        let state_diff = transaction
             .chain()
             .state_schema()
             .load_state_diff_for_block(block_number)
-            .await?
+            .await?;
+        // Only `Create` and `Delete` updates contain the address of the account,
+        // load it for the rest of them.
+        let account_ids: Vec<_> = state_diff
+            .iter()
+            .map(|(account_id, _)| *account_id)
+            .collect();
+        let addresses = transaction
+            .chain()
+            .account_schema()
+            .account_addresses_by_ids(&account_ids)
+            .await?;
+        // Store new account event for each update in the block.
+        let events: Vec<_> = state_diff
             .into_iter()
             .map(|(account_id, update)| {
                 let update_type = AccountStateChangeType::from(&update);
                 AccountUpdateDetails::from_account_update(account_id, update).map(
-                    |update_details| {
+                    |mut update_details| {
+                        if update_details.address.is_none() {
+                            update_details.address = addresses.get(&account_id).copied();
+                        }
                         let account_event = AccountEvent {
                             update_type,
                             status,
@@ -75,13 +75,15 @@
 #![allow(clippy::toplevel_ref_arg, clippy::suspicious_else_formatting)]
 
 // Built-in deps
-use std::env;
+use std::{env, panic::Location, time::Duration};
 // External imports
-use sqlx::{postgres::Postgres, Connection, PgConnection, Transaction};
+use sqlx::{postgres::Postgres, ConnectOptions, Connection, PgConnection, Transaction};
 // Workspace imports
 use zksync_types::{ActionType, BlockNumber};
 // Local imports
-use crate::connection::{holder::ConnectionHolder, PooledConnection};
+use crate::connection::{
+    holder::ConnectionHolder, instrumented::InstrumentedConnection, PooledConnection,
+};
 
 // mod schema;
 #[cfg(test)]
@@ -98,6 +100,7 @@ pub mod forced_exit_requests;
 pub mod listener;
 pub mod misc;
 pub mod prover;
+mod query_metrics;
 pub mod test_data;
 pub mod tokens;
 pub mod utils;
@@ -130,6 +133,8 @@ pub fn get_database_url() -> String {
 pub struct StorageProcessor<'a> {
     conn: ConnectionHolder<'a>,
     in_transaction: bool,
+    /// Threshold of the slow statements, they are not logged if it's not set.
+    pub(crate) slow_query_threshold: Option<Duration>,
 }
 
 #[derive(sqlx::Type, Debug, Clone, PartialEq, Eq)]
@@ -152,20 +157,25 @@ impl<'a> StorageProcessor<'a> {
     /// Creates a `StorageProcessor` using an unique sole connection to the database.
     pub async fn establish_connection<'b>() -> QueryResult<StorageProcessor<'b>> {
         let database_url = get_database_url();
-        let connection = PgConnection::connect(&database_url).await?;
+        let connection = connection::connect_options(&database_url, None)?
+            .connect()
+            .await?;
         Ok(StorageProcessor {
             conn: ConnectionHolder::Direct(connection),
             in_transaction: false,
+            slow_query_threshold: None,
         })
     }
 
     pub async fn start_transaction<'c: 'b, 'b>(
         &'c mut self,
     ) -> Result<StorageProcessor<'b>, anyhow::Error> {
-        let transaction = self.conn().begin().await?;
+        let slow_query_threshold = self.slow_query_threshold;
+        let transaction = self.pg_conn().begin().await?;
 
         let mut processor = StorageProcessor::from_transaction(transaction);
         processor.in_transaction = true;
+        processor.slow_query_threshold = slow_query_threshold;
 
         Ok(processor)
     }
@@ -179,6 +189,7 @@ impl<'a> StorageProcessor<'a> {
         StorageProcessor {
             conn: ConnectionHolder::Transaction(conn),
             in_transaction: true,
+            slow_query_threshold: None,
         }
     }
 
@@ -198,6 +209,7 @@ impl<'a> StorageProcessor<'a> {
         Self {
             conn: ConnectionHolder::Pooled(conn),
             in_transaction: false,
+            slow_query_threshold: None,
         }
     }
 
@@ -243,11 +255,20 @@ impl<'a> StorageProcessor<'a> {
         misc::MiscSchema(self)
     }
 
-    fn conn(&mut self) -> &mut PgConnection {
+    fn pg_conn(&mut self) -> &mut PgConnection {
         match &mut self.conn {
             ConnectionHolder::Pooled(conn) => conn,
             ConnectionHolder::Direct(conn) => conn,
             ConnectionHolder::Transaction(conn) => conn,
         }
     }
+
+    /// Returns the connection to run the statements on, the slow ones are logged
+    /// along with the location of the call, i.e. the storage method issuing them.
+    #[track_caller]
+    fn conn(&mut self) -> InstrumentedConnection<'_> {
+        let location = Location::caller();
+        let slow_query_threshold = self.slow_query_threshold;
+        InstrumentedConnection::new(self.pg_conn(), slow_query_threshold, location)
+    }
 }
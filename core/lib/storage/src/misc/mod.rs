@@ -1,13 +1,26 @@
 // Built-in deps
-use std::time::Instant;
+use std::{convert::TryFrom, time::Instant};
 // External imports
+use chrono::{DateTime, Utc};
+use num::{rational::Ratio, BigUint};
 use sqlx::types::BigDecimal;
+use zksync_types::{
+    tx::TxHash, AccountId, Address, AddressList, BlockedAddress, Fee, FeeExemption,
+    FeeExemptionRecord, FeeExemptionUsage, TokenId,
+};
+use zksync_utils::{biguint_to_big_decimal, ratio_to_big_decimal};
 // Local imports
 
-use self::records::Subsidy;
+use self::records::{
+    DbBlockedAddress, DbFeeExemption, DbFeeExemptionUsage, RateLimitBucket, RateLimitTokens,
+    ReservedTxRequestId, StoredFeeQuote, Subsidy, SubsidyStats, TxSenderSettingOverride,
+};
 use crate::{QueryResult, StorageProcessor};
 use num::ToPrimitive;
 
+/// Precision of the token prices stored along with the quoted fees.
+const STORED_USD_PRICE_PRECISION: usize = 18;
+
 pub mod records;
 
 /// MiscSchema should be used for various features not directly related to the main zkSync functionality
@@ -64,4 +77,731 @@ impl<'a, 'c> MiscSchema<'a, 'c> {
         metrics::histogram!("sql.token.get_total_used_subsidy_for_type", start.elapsed());
         Ok(sum)
     }
+
+    /// Loads the total amounts of the subsidy and the number of the subsidized transactions.
+    pub async fn get_subsidy_stats(&mut self, subsidy_type: &str) -> QueryResult<SubsidyStats> {
+        let start = Instant::now();
+        let record = sqlx::query!(
+            r#"
+            SELECT SUM(usd_amount_scale6) as used, SUM(full_cost_usd_scale6) as full_cost, COUNT(*) as count
+            FROM subsidies
+            WHERE subsidy_type = $1
+            "#,
+            subsidy_type
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.get_subsidy_stats", start.elapsed());
+        Ok(SubsidyStats {
+            used_usd_scaled: record.used.unwrap_or_else(|| BigDecimal::from(0)),
+            full_cost_usd_scaled: record.full_cost.unwrap_or_else(|| BigDecimal::from(0)),
+            subsidized_txs: record.count.unwrap_or_default(),
+        })
+    }
+
+    /// Loads the budget of the subsidy scaled by SUBSIDY_USD_AMOUNTS_SCALE.
+    /// `None` means that the budget wasn't set in the database.
+    pub async fn get_subsidy_budget(&mut self, subsidy_type: &str) -> QueryResult<Option<u64>> {
+        let start = Instant::now();
+        let budget = sqlx::query!(
+            "SELECT budget_usd_scale6 FROM subsidy_budgets WHERE subsidy_type = $1",
+            subsidy_type
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.budget_usd_scale6 as u64);
+
+        metrics::histogram!("sql.misc.get_subsidy_budget", start.elapsed());
+        Ok(budget)
+    }
+
+    /// Sets the budget of the subsidy scaled by SUBSIDY_USD_AMOUNTS_SCALE.
+    pub async fn set_subsidy_budget(
+        &mut self,
+        subsidy_type: &str,
+        budget_usd_scaled: u64,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO subsidy_budgets ( subsidy_type, budget_usd_scale6, updated_at )
+            VALUES ( $1, $2, now() )
+            ON CONFLICT (subsidy_type)
+            DO UPDATE SET budget_usd_scale6 = $2, updated_at = now()
+            "#,
+            subsidy_type,
+            budget_usd_scaled as i64
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.set_subsidy_budget", start.elapsed());
+        Ok(())
+    }
+
+    /// Reserves the client-provided request ID of the account for the transaction with the given hash.
+    /// Returns the transaction the ID was already reserved for, if any.
+    ///
+    /// Reservations that were not marked as submitted until `pending_before` are considered abandoned
+    /// (e.g. the server was restarted during the submission) and are taken over.
+    pub async fn reserve_tx_request_id(
+        &mut self,
+        account_id: AccountId,
+        request_id: &str,
+        tx_hash: TxHash,
+        pending_before: DateTime<Utc>,
+    ) -> QueryResult<Option<ReservedTxRequestId>> {
+        let start = Instant::now();
+
+        let reserved = sqlx::query!(
+            r#"
+            INSERT INTO tx_request_ids ( account_id, request_id, tx_hash )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT ( account_id, request_id ) DO UPDATE
+            SET tx_hash = $3, created_at = now()
+            WHERE NOT tx_request_ids.submitted AND tx_request_ids.created_at < $4
+            "#,
+            i64::from(*account_id),
+            request_id,
+            tx_hash.as_ref(),
+            pending_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected()
+            > 0;
+        let reserved_tx = if reserved {
+            None
+        } else {
+            let record = sqlx::query!(
+                r#"
+                SELECT tx_hash, submitted FROM tx_request_ids
+                WHERE account_id = $1 AND request_id = $2
+                "#,
+                i64::from(*account_id),
+                request_id
+            )
+            .fetch_one(self.0.conn())
+            .await?;
+            Some(ReservedTxRequestId {
+                tx_hash: TxHash::from_slice(&record.tx_hash).unwrap(),
+                submitted: record.submitted,
+            })
+        };
+
+        metrics::histogram!("sql.misc.reserve_tx_request_id", start.elapsed());
+        Ok(reserved_tx)
+    }
+
+    /// Marks the transaction of the reserved request ID as submitted.
+    pub async fn mark_tx_request_id_submitted(
+        &mut self,
+        account_id: AccountId,
+        request_id: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            UPDATE tx_request_ids SET submitted = true
+            WHERE account_id = $1 AND request_id = $2
+            "#,
+            i64::from(*account_id),
+            request_id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.mark_tx_request_id_submitted", start.elapsed());
+        Ok(())
+    }
+
+    /// Releases the request ID whose transaction was rejected, so it can be used again.
+    pub async fn release_tx_request_id(
+        &mut self,
+        account_id: AccountId,
+        request_id: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            DELETE FROM tx_request_ids
+            WHERE account_id = $1 AND request_id = $2 AND NOT submitted
+            "#,
+            i64::from(*account_id),
+            request_id
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.release_tx_request_id", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the request IDs reserved before the given time, so they can be reused.
+    pub async fn remove_expired_tx_request_ids(
+        &mut self,
+        created_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM tx_request_ids
+            WHERE created_at < $1
+            "#,
+            created_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.misc.remove_expired_tx_request_ids", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Caches the address the ENS name was resolved to.
+    pub async fn store_ens_name(&mut self, name: &str, address: Address) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO ens_names ( name, address, resolved_at )
+            VALUES ( $1, $2, now() )
+            ON CONFLICT ( name ) DO UPDATE
+            SET address = $2, resolved_at = now()
+            "#,
+            name,
+            address.as_bytes()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.store_ens_name", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the cached address of the ENS name if it was resolved after the given time.
+    pub async fn get_ens_name_address(
+        &mut self,
+        name: &str,
+        resolved_after: DateTime<Utc>,
+    ) -> QueryResult<Option<Address>> {
+        let start = Instant::now();
+        let address = sqlx::query!(
+            r#"
+            SELECT address FROM ens_names
+            WHERE name = $1 AND resolved_at > $2
+            "#,
+            name,
+            resolved_after
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| Address::from_slice(&record.address));
+
+        metrics::histogram!("sql.misc.get_ens_name_address", start.elapsed());
+        Ok(address)
+    }
+
+    /// Stores the fee quoted by the fee ticker along with the token price it was calculated from.
+    pub async fn store_fee_quote(
+        &mut self,
+        token_id: TokenId,
+        fee: &Fee,
+        token_price_usd: &Ratio<BigUint>,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_history ( tx_type, token_id, gas_price_wei, token_price_usd, gas_fee, zkp_fee, total_fee )
+            VALUES ( $1, $2, $3, $4, $5, $6, $7 )
+            "#,
+            fee.fee_type.name(),
+            token_id.0 as i32,
+            biguint_to_big_decimal(fee.gas_price_wei.clone()),
+            ratio_to_big_decimal(token_price_usd, STORED_USD_PRICE_PRECISION),
+            biguint_to_big_decimal(fee.gas_fee.clone()),
+            biguint_to_big_decimal(fee.zkp_fee.clone()),
+            biguint_to_big_decimal(fee.total_fee.clone())
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.store_fee_quote", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the fees quoted in the given time range in the ascending order.
+    /// Quotes can be optionally filtered by the fee type and the token.
+    pub async fn load_fee_history(
+        &mut self,
+        tx_type: Option<&str>,
+        token_id: Option<TokenId>,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: u32,
+    ) -> QueryResult<Vec<StoredFeeQuote>> {
+        let start = Instant::now();
+        let quotes = sqlx::query_as!(
+            StoredFeeQuote,
+            r#"
+            SELECT * FROM fee_history
+            WHERE created_at >= $1 AND created_at <= $2
+                AND ($3::text IS NULL OR tx_type = $3)
+                AND ($4::integer IS NULL OR token_id = $4)
+            ORDER BY created_at ASC, id ASC
+            LIMIT $5
+            "#,
+            from,
+            to,
+            tx_type,
+            token_id.map(|id| id.0 as i32),
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.load_fee_history", start.elapsed());
+        Ok(quotes)
+    }
+
+    /// Removes the fee quotes made before the given time.
+    pub async fn remove_old_fee_quotes(
+        &mut self,
+        created_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM fee_history
+            WHERE created_at < $1
+            "#,
+            created_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.misc.remove_old_fee_quotes", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Refills the shared rate limit buckets and takes the given number of tokens from them,
+    /// returning the updated buckets. The bucket which doesn't exist yet is created full.
+    /// Buckets are locked in the same order, so the concurrent updates can't deadlock.
+    pub async fn take_rate_limit_tokens(
+        &mut self,
+        updates: &[RateLimitTokens],
+    ) -> QueryResult<Vec<RateLimitBucket>> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let keys: Vec<_> = updates.iter().map(|update| update.key.clone()).collect();
+        let capacities: Vec<_> = updates.iter().map(|update| update.capacity).collect();
+        let refill_rates: Vec<_> = updates.iter().map(|update| update.refill_per_sec).collect();
+        let taken: Vec<_> = updates.iter().map(|update| update.taken).collect();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO submission_rate_limits ( key, tokens, updated_at )
+            SELECT u.key, u.capacity, now()
+            FROM UNNEST ($1::text[], $2::float8[]) AS u ( key, capacity )
+            ORDER BY u.key
+            ON CONFLICT ( key ) DO NOTHING
+            "#,
+            &keys,
+            &capacities
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            r#"
+            SELECT key FROM submission_rate_limits
+            WHERE key = ANY($1)
+            ORDER BY key
+            FOR UPDATE
+            "#,
+            &keys
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        let buckets = sqlx::query_as!(
+            RateLimitBucket,
+            r#"
+            UPDATE submission_rate_limits AS b
+            SET tokens = GREATEST(
+                    LEAST(
+                        u.capacity,
+                        b.tokens + EXTRACT(EPOCH FROM now() - b.updated_at)::float8 * u.refill_per_sec
+                    ) - u.taken,
+                    0
+                ),
+                updated_at = now()
+            FROM UNNEST ($1::text[], $2::float8[], $3::float8[], $4::float8[])
+                AS u ( key, capacity, refill_per_sec, taken )
+            WHERE b.key = u.key
+            RETURNING b.key as "key!", b.tokens as "tokens!", b.updated_at as "updated_at!"
+            "#,
+            &keys,
+            &capacities,
+            &refill_rates,
+            &taken
+        )
+        .fetch_all(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.misc.take_rate_limit_tokens", start.elapsed());
+        Ok(buckets)
+    }
+
+    /// Removes the buckets that weren't used since the given time.
+    /// Such buckets are full already, so removing them doesn't change the limits.
+    pub async fn remove_stale_rate_limit_buckets(
+        &mut self,
+        updated_before: DateTime<Utc>,
+    ) -> QueryResult<u64> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM submission_rate_limits
+            WHERE updated_at < $1
+            "#,
+            updated_before
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.misc.remove_stale_rate_limit_buckets", start.elapsed());
+        Ok(removed)
+    }
+
+    /// Loads the fee exemptions added by the operator.
+    pub async fn load_fee_exemptions(&mut self) -> QueryResult<Vec<FeeExemptionRecord>> {
+        let start = Instant::now();
+
+        let exemptions = sqlx::query_as!(
+            DbFeeExemption,
+            r#"
+            SELECT * FROM fee_exemptions
+            ORDER BY kind, value
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let exemptions = exemptions
+            .into_iter()
+            .map(|record| {
+                let exemption = FeeExemption::from_parts(&record.kind, &record.value)
+                    .map_err(anyhow::Error::msg)?;
+                Ok(FeeExemptionRecord {
+                    exemption,
+                    added_by: record.added_by,
+                    created_at: record.created_at,
+                })
+            })
+            .collect::<QueryResult<_>>()?;
+
+        metrics::histogram!("sql.misc.load_fee_exemptions", start.elapsed());
+        Ok(exemptions)
+    }
+
+    /// Adds the fee exemption, does nothing if it exists already.
+    pub async fn add_fee_exemption(
+        &mut self,
+        exemption: &FeeExemption,
+        added_by: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_exemptions ( kind, value, added_by, created_at )
+            VALUES ( $1, $2, $3, now() )
+            ON CONFLICT ( kind, value ) DO NOTHING
+            "#,
+            exemption.kind(),
+            exemption.value(),
+            added_by
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.add_fee_exemption", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the fee exemption. Returns `false` if there was no such exemption.
+    pub async fn remove_fee_exemption(&mut self, exemption: &FeeExemption) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM fee_exemptions
+            WHERE kind = $1 AND value = $2
+            "#,
+            exemption.kind(),
+            exemption.value()
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.misc.remove_fee_exemption", start.elapsed());
+        Ok(removed > 0)
+    }
+
+    /// Records the transaction accepted without the fee check to the audit log.
+    pub async fn store_fee_exemption_usage(
+        &mut self,
+        tx_hash: TxHash,
+        sender: Address,
+        tx_type: &str,
+        exemption: &FeeExemption,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_exemption_usages ( tx_hash, sender, tx_type, exemption_kind, exemption_value, created_at )
+            VALUES ( $1, $2, $3, $4, $5, now() )
+            "#,
+            tx_hash.as_ref(),
+            sender.as_bytes(),
+            tx_type,
+            exemption.kind(),
+            exemption.value()
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.store_fee_exemption_usage", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the latest transactions accepted without the fee check, newest first.
+    pub async fn load_fee_exemption_usages(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<FeeExemptionUsage>> {
+        let start = Instant::now();
+
+        let usages = sqlx::query_as!(
+            DbFeeExemptionUsage,
+            r#"
+            SELECT * FROM fee_exemption_usages
+            ORDER BY id DESC
+            LIMIT $1
+            "#,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let usages = usages
+            .into_iter()
+            .map(|usage| {
+                let exemption =
+                    FeeExemption::from_parts(&usage.exemption_kind, &usage.exemption_value)
+                        .map_err(anyhow::Error::msg)?;
+                Ok(FeeExemptionUsage {
+                    tx_hash: TxHash::from_slice(&usage.tx_hash)
+                        .ok_or_else(|| anyhow::format_err!("Invalid transaction hash"))?,
+                    sender: Address::from_slice(&usage.sender),
+                    tx_type: usage.tx_type,
+                    exemption,
+                    created_at: usage.created_at,
+                })
+            })
+            .collect::<QueryResult<_>>()?;
+
+        metrics::histogram!("sql.misc.load_fee_exemption_usages", start.elapsed());
+        Ok(usages)
+    }
+
+    /// Loads the addresses blocked by the operator.
+    pub async fn load_blocked_addresses(&mut self) -> QueryResult<Vec<BlockedAddress>> {
+        let start = Instant::now();
+
+        let records = sqlx::query_as!(
+            DbBlockedAddress,
+            r#"
+            SELECT * FROM blocked_addresses
+            ORDER BY list, address
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let addresses = records
+            .into_iter()
+            .map(BlockedAddress::try_from)
+            .collect::<Result<_, _>>()?;
+
+        metrics::histogram!("sql.misc.load_blocked_addresses", start.elapsed());
+        Ok(addresses)
+    }
+
+    /// Returns the entries of the senders on the sender list and of the recipients on the recipient list.
+    pub async fn find_blocked_addresses(
+        &mut self,
+        senders: &[Address],
+        recipients: &[Address],
+    ) -> QueryResult<Vec<BlockedAddress>> {
+        let start = Instant::now();
+        let senders: Vec<_> = senders
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let recipients: Vec<_> = recipients
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+
+        let records = sqlx::query_as!(
+            DbBlockedAddress,
+            r#"
+            SELECT * FROM blocked_addresses
+            WHERE (list = 'sender' AND address = ANY($1)) OR (list = 'recipient' AND address = ANY($2))
+            ORDER BY list, address
+            "#,
+            &senders,
+            &recipients
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+        let addresses = records
+            .into_iter()
+            .map(BlockedAddress::try_from)
+            .collect::<Result<_, _>>()?;
+
+        metrics::histogram!("sql.misc.find_blocked_addresses", start.elapsed());
+        Ok(addresses)
+    }
+
+    /// Adds the address to the list, does nothing if it's there already.
+    pub async fn add_blocked_address(
+        &mut self,
+        address: Address,
+        list: AddressList,
+        reason: &str,
+        added_by: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO blocked_addresses ( address, list, reason, added_by, created_at )
+            VALUES ( $1, $2, $3, $4, now() )
+            ON CONFLICT ( address, list ) DO NOTHING
+            "#,
+            address.as_bytes(),
+            list.as_str(),
+            reason,
+            added_by
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.add_blocked_address", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the address from the list. Returns `false` if it wasn't there.
+    pub async fn remove_blocked_address(
+        &mut self,
+        address: Address,
+        list: AddressList,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM blocked_addresses
+            WHERE address = $1 AND list = $2
+            "#,
+            address.as_bytes(),
+            list.as_str()
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!("sql.misc.remove_blocked_address", start.elapsed());
+        Ok(removed > 0)
+    }
+
+    /// Loads the settings of the transaction submission overridden by the operator.
+    pub async fn load_tx_sender_setting_overrides(
+        &mut self,
+    ) -> QueryResult<Vec<TxSenderSettingOverride>> {
+        let start = Instant::now();
+
+        let overrides = sqlx::query_as!(
+            TxSenderSettingOverride,
+            r#"
+            SELECT * FROM tx_sender_settings
+            ORDER BY name
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.load_tx_sender_setting_overrides", start.elapsed());
+        Ok(overrides)
+    }
+
+    /// Overrides the setting of the transaction submission, replacing the previous override.
+    pub async fn set_tx_sender_setting_override(
+        &mut self,
+        name: &str,
+        value: &serde_json::Value,
+        updated_by: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO tx_sender_settings ( name, value, updated_by, updated_at )
+            VALUES ( $1, $2, $3, now() )
+            ON CONFLICT ( name )
+            DO UPDATE SET value = $2, updated_by = $3, updated_at = now()
+            "#,
+            name,
+            value,
+            updated_by
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.misc.set_tx_sender_setting_override", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the override of the setting. Returns `false` if the setting wasn't overridden.
+    pub async fn remove_tx_sender_setting_override(&mut self, name: &str) -> QueryResult<bool> {
+        let start = Instant::now();
+
+        let removed = sqlx::query!(
+            r#"
+            DELETE FROM tx_sender_settings
+            WHERE name = $1
+            "#,
+            name
+        )
+        .execute(self.0.conn())
+        .await?
+        .rows_affected();
+
+        metrics::histogram!(
+            "sql.misc.remove_tx_sender_setting_override",
+            start.elapsed()
+        );
+        Ok(removed > 0)
+    }
 }
@@ -1,8 +1,12 @@
+// Built-in deps
+use std::convert::TryFrom;
 // External imports
-use sqlx::types::BigDecimal;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::BigDecimal, FromRow};
 // Workspace imports
 // Local imports
-use zksync_types::{tx::TxHash, TokenId};
+use zksync_types::{tx::TxHash, Address, BlockedAddress, TokenId};
 
 pub struct Subsidy {
     pub tx_hash: TxHash,
@@ -13,3 +17,105 @@ pub struct Subsidy {
     pub full_cost_token: BigDecimal,
     pub subsidy_type: String,
 }
+
+/// Totals of the subsidy, USD amounts are scaled by 10^6.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubsidyStats {
+    pub used_usd_scaled: BigDecimal,
+    pub full_cost_usd_scaled: BigDecimal,
+    pub subsidized_txs: i64,
+}
+
+/// Request ID reserved for the transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReservedTxRequestId {
+    pub tx_hash: TxHash,
+    /// Whether the transaction was submitted, otherwise the submission is still in progress.
+    pub submitted: bool,
+}
+
+/// Fee quoted by the fee ticker.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct StoredFeeQuote {
+    pub id: i64,
+    pub tx_type: String,
+    pub token_id: i32,
+    pub gas_price_wei: BigDecimal,
+    pub token_price_usd: BigDecimal,
+    pub gas_fee: BigDecimal,
+    pub zkp_fee: BigDecimal,
+    pub total_fee: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Token bucket limiting the transaction submissions.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct RateLimitBucket {
+    pub key: String,
+    pub tokens: f64,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Tokens taken from the shared rate limit bucket since the last update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitTokens {
+    pub key: String,
+    pub capacity: f64,
+    pub refill_per_sec: f64,
+    pub taken: f64,
+}
+
+/// Fee exemption added by the operator.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct DbFeeExemption {
+    pub kind: String,
+    pub value: String,
+    pub added_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Transaction accepted without the fee check.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct DbFeeExemptionUsage {
+    pub id: i64,
+    pub tx_hash: Vec<u8>,
+    pub sender: Vec<u8>,
+    pub tx_type: String,
+    pub exemption_kind: String,
+    pub exemption_value: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Address blocked by the operator.
+#[derive(Debug, Clone, FromRow, PartialEq)]
+pub struct DbBlockedAddress {
+    pub address: Vec<u8>,
+    pub list: String,
+    pub reason: String,
+    pub added_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TryFrom<DbBlockedAddress> for BlockedAddress {
+    type Error = anyhow::Error;
+
+    fn try_from(record: DbBlockedAddress) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address: Address::from_slice(&record.address),
+            list: record.list.parse().map_err(anyhow::Error::msg)?,
+            reason: record.reason,
+            added_by: record.added_by,
+            created_at: record.created_at,
+        })
+    }
+}
+
+/// Setting of the transaction submission overridden by the operator.
+#[derive(Debug, Clone, FromRow, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSenderSettingOverride {
+    pub name: String,
+    pub value: serde_json::Value,
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
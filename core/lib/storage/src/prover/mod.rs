@@ -324,6 +324,33 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(proof)
     }
 
+    /// Gets the latest stored aggregated proof covering the block along with
+    /// the range of blocks it was created for.
+    pub async fn load_aggregated_proof_for_block(
+        &mut self,
+        block_number: BlockNumber,
+    ) -> QueryResult<Option<(BlockNumber, BlockNumber, AggregatedProof)>> {
+        let start = Instant::now();
+        let proof = sqlx::query_as!(
+            StoredAggregatedProof,
+            "SELECT * FROM aggregated_proofs WHERE first_block <= $1 AND last_block >= $1
+            ORDER BY created_at DESC LIMIT 1",
+            i64::from(*block_number),
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|stored| {
+            (
+                BlockNumber(stored.first_block as u32),
+                BlockNumber(stored.last_block as u32),
+                serde_json::from_value(stored.proof).unwrap(),
+            )
+        });
+
+        metrics::histogram!("sql", start.elapsed(), "prover" => "load_aggregated_proof_for_block");
+        Ok(proof)
+    }
+
     /// Stores witness for a block
     pub async fn store_witness(
         &mut self,
@@ -0,0 +1,65 @@
+//! Instrumentation of the schema methods.
+//!
+//! Every statement is logged along with the location of the storage method that issued it if it
+//! takes longer than the slow query threshold (see `connection::instrumented`), but the arguments
+//! of the statement can't be logged. Schema methods that need more details wrap their body into
+//! `MethodMetrics`, which reports the duration and the number of the loaded rows labeled by the
+//! method name, and logs the method along with its arguments if it is slow.
+
+// Built-in deps
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+pub(crate) struct MethodMetrics {
+    method: &'static str,
+    args: Vec<(&'static str, String)>,
+    start: Instant,
+    slow_query_threshold: Option<Duration>,
+}
+
+impl MethodMetrics {
+    /// Starts measuring the method, the threshold is taken from the storage processor.
+    pub fn start(method: &'static str, slow_query_threshold: Option<Duration>) -> Self {
+        Self {
+            method,
+            args: Vec::new(),
+            start: Instant::now(),
+            slow_query_threshold,
+        }
+    }
+
+    /// Adds the argument of the method to be logged if the method turns out slow.
+    pub fn arg(mut self, name: &'static str, value: impl fmt::Debug) -> Self {
+        self.args.push((name, format!("{:?}", value)));
+        self
+    }
+
+    /// Reports the method that has loaded the given number of rows.
+    pub fn finish(self, rows: usize) {
+        let elapsed = self.start.elapsed();
+        metrics::histogram!("sql.method.duration", elapsed, "method" => self.method);
+        metrics::histogram!("sql.method.rows", rows as f64, "method" => self.method);
+
+        let is_slow = self
+            .slow_query_threshold
+            .map_or(false, |threshold| elapsed >= threshold);
+        if is_slow {
+            let args = self
+                .args
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            vlog::warn!(
+                "Slow storage method {}({}) took {:?} and loaded {} rows",
+                self.method,
+                args,
+                elapsed,
+                rows
+            );
+        }
+    }
+}
@@ -1,15 +1,19 @@
 // External imports
+use chrono::{Duration, TimeZone, Utc};
 use num::{BigUint, Zero};
 // Workspace imports
+use zksync_api_types::v02::account::BalanceHistoryResolution;
 use zksync_crypto::params::{MIN_NFT_TOKEN_ID, NFT_TOKEN_ID};
 use zksync_types::{
     aggregated_operations::AggregatedActionType, helpers::apply_updates, AccountId, AccountMap,
-    AccountUpdate, Address, BlockNumber, Nonce, Token, TokenId, TokenKind,
+    AccountUpdate, Address, BlockNumber, Nonce, PubKeyHash, Token, TokenId, TokenKind,
 };
 // Local imports
 use super::block::apply_random_updates;
 use crate::chain::operations::OperationsSchema;
-use crate::test_data::{gen_sample_block, gen_unique_aggregated_operation, generate_nft};
+use crate::test_data::{
+    gen_sample_block, gen_unique_aggregated_operation, generate_nft, BLOCK_SIZE_CHUNKS,
+};
 use crate::tests::{create_rng, db_test, ACCOUNT_MUTEX};
 use crate::{
     chain::{
@@ -323,6 +327,258 @@ async fn test_get_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()>
     Ok(())
 }
 
+/// Checks that the account state is reconstructed as of the requested block.
+#[db_test]
+async fn test_account_state_for_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let address = Address::random();
+    let updates1 = vec![
+        (
+            AccountId(1),
+            AccountUpdate::Create {
+                address,
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            AccountId(1),
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(1),
+                balance_update: (TokenId(0), BigUint::zero(), BigUint::from(100u32)),
+            },
+        ),
+    ];
+    let updates2 = vec![(
+        AccountId(1),
+        AccountUpdate::UpdateBalance {
+            old_nonce: Nonce(1),
+            new_nonce: Nonce(2),
+            balance_update: (TokenId(0), BigUint::from(100u32), BigUint::from(300u32)),
+        },
+    )];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(2), &updates1, 0)
+        .await?;
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(4), &updates2, 0)
+        .await?;
+
+    // The account doesn't exist before its creation.
+    let state = AccountSchema(&mut storage)
+        .account_state_for_block(AccountId(1), BlockNumber(1))
+        .await?;
+    assert!(state.is_none());
+
+    let (last_block, account) = AccountSchema(&mut storage)
+        .account_state_for_block(AccountId(1), BlockNumber(3))
+        .await?
+        .expect("Account should exist");
+    assert_eq!(last_block, BlockNumber(2));
+    assert_eq!(account.address, address);
+    assert_eq!(account.nonce, Nonce(1));
+    assert_eq!(account.get_balance(TokenId(0)), BigUint::from(100u32));
+
+    let (last_block, account) = AccountSchema(&mut storage)
+        .account_state_for_block(AccountId(1), BlockNumber(5))
+        .await?
+        .expect("Account should exist");
+    assert_eq!(last_block, BlockNumber(4));
+    assert_eq!(account.nonce, Nonce(2));
+    assert_eq!(account.get_balance(TokenId(0)), BigUint::from(300u32));
+
+    // Once the blocks are verified, the earlier states are restored by reverting the updates.
+    for block_number in 1..=4 {
+        OperationsSchema(&mut storage)
+            .store_aggregated_action(gen_unique_aggregated_operation(
+                BlockNumber(block_number),
+                AggregatedActionType::ExecuteBlocks,
+                BLOCK_SIZE_CHUNKS,
+            ))
+            .await?;
+    }
+    OperationsSchema(&mut storage)
+        .confirm_aggregated_operations(
+            BlockNumber(1),
+            BlockNumber(4),
+            AggregatedActionType::ExecuteBlocks,
+        )
+        .await?;
+    for block_number in [2, 4] {
+        StateSchema(&mut storage)
+            .apply_state_update(BlockNumber(block_number))
+            .await?;
+    }
+
+    let state = AccountSchema(&mut storage)
+        .account_state_for_block(AccountId(1), BlockNumber(1))
+        .await?;
+    assert!(state.is_none());
+
+    let (last_block, account) = AccountSchema(&mut storage)
+        .account_state_for_block(AccountId(1), BlockNumber(3))
+        .await?
+        .expect("Account should exist");
+    assert_eq!(last_block, BlockNumber(2));
+    assert_eq!(account.nonce, Nonce(1));
+    assert_eq!(account.get_balance(TokenId(0)), BigUint::from(100u32));
+
+    let (last_block, account) = AccountSchema(&mut storage)
+        .account_state_for_block(AccountId(1), BlockNumber(4))
+        .await?
+        .expect("Account should exist");
+    assert_eq!(last_block, BlockNumber(4));
+    assert_eq!(account.nonce, Nonce(2));
+    assert_eq!(account.get_balance(TokenId(0)), BigUint::from(300u32));
+
+    Ok(())
+}
+
+/// Checks that several account ids are resolved by their addresses at once
+/// and vice versa.
+#[db_test]
+async fn test_account_ids_by_addresses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let addresses = vec![Address::random(), Address::random(), Address::random()];
+    let updates = vec![
+        (
+            AccountId(1),
+            AccountUpdate::Create {
+                address: addresses[0],
+                nonce: Nonce(0),
+            },
+        ),
+        (
+            AccountId(2),
+            AccountUpdate::Create {
+                address: addresses[1],
+                nonce: Nonce(0),
+            },
+        ),
+    ];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    let account_ids = AccountSchema(&mut storage)
+        .account_ids_by_addresses(&addresses)
+        .await?;
+    assert_eq!(account_ids.len(), 2);
+    assert_eq!(account_ids[&addresses[0]], AccountId(1));
+    assert_eq!(account_ids[&addresses[1]], AccountId(2));
+    assert!(!account_ids.contains_key(&addresses[2]));
+
+    let account_addresses = AccountSchema(&mut storage)
+        .account_addresses_by_ids(&[AccountId(1), AccountId(2), AccountId(3)])
+        .await?;
+    assert_eq!(account_addresses.len(), 2);
+    assert_eq!(account_addresses[&AccountId(1)], addresses[0]);
+    assert_eq!(account_addresses[&AccountId(2)], addresses[1]);
+    assert!(!account_addresses.contains_key(&AccountId(3)));
+
+    Ok(())
+}
+
+/// Checks that the block the account was created in is loaded.
+#[db_test]
+async fn test_account_creation_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let updates = vec![(
+        AccountId(1),
+        AccountUpdate::Create {
+            address: Address::random(),
+            nonce: Nonce(0),
+        },
+    )];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(3), &updates, 0)
+        .await?;
+
+    let block_number = AccountSchema(&mut storage)
+        .account_creation_block(AccountId(1))
+        .await?;
+    assert_eq!(block_number, Some(BlockNumber(3)));
+
+    let block_number = AccountSchema(&mut storage)
+        .account_creation_block(AccountId(2))
+        .await?;
+    assert!(block_number.is_none());
+
+    Ok(())
+}
+
+/// Checks that accounts are found by the public key hash they have in the committed state.
+#[db_test]
+async fn test_account_ids_by_pubkey_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let old_pub_key_hash = PubKeyHash::from_bytes(&[1u8; 20]).unwrap();
+    let new_pub_key_hash = PubKeyHash::from_bytes(&[2u8; 20]).unwrap();
+    let mut updates = Vec::new();
+    for account_id in [AccountId(1), AccountId(2)] {
+        updates.push((
+            account_id,
+            AccountUpdate::Create {
+                address: Address::random(),
+                nonce: Nonce(0),
+            },
+        ));
+        updates.push((
+            account_id,
+            AccountUpdate::ChangePubKeyHash {
+                old_pub_key_hash: PubKeyHash::default(),
+                new_pub_key_hash: old_pub_key_hash,
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(1),
+            },
+        ));
+    }
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(1), &updates, 0)
+        .await?;
+
+    let account_ids = AccountSchema(&mut storage)
+        .account_ids_by_pubkey_hash(&old_pub_key_hash)
+        .await?;
+    assert_eq!(account_ids, vec![AccountId(1), AccountId(2)]);
+
+    // The second account rotates its key, so only the first one keeps the old key.
+    let updates = vec![(
+        AccountId(2),
+        AccountUpdate::ChangePubKeyHash {
+            old_pub_key_hash,
+            new_pub_key_hash,
+            old_nonce: Nonce(1),
+            new_nonce: Nonce(2),
+        },
+    )];
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(BlockNumber(2), &updates, 0)
+        .await?;
+
+    let account_ids = AccountSchema(&mut storage)
+        .account_ids_by_pubkey_hash(&old_pub_key_hash)
+        .await?;
+    assert_eq!(account_ids, vec![AccountId(1)]);
+    let account_ids = AccountSchema(&mut storage)
+        .account_ids_by_pubkey_hash(&new_pub_key_hash)
+        .await?;
+    assert_eq!(account_ids, vec![AccountId(2)]);
+
+    Ok(())
+}
+
 #[db_test]
 async fn test_get_account_nft_balance(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let address = Address::random();
@@ -534,3 +790,114 @@ async fn test_get_nft_owner(mut storage: StorageProcessor<'_>) -> QueryResult<()
 
     Ok(())
 }
+
+/// Checks that the balance history is aggregated by the requested time periods.
+#[db_test]
+async fn test_account_balance_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let _lock = ACCOUNT_MUTEX.lock().await;
+    let day = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+    let account_id = AccountId(1);
+    let mut updates = vec![(
+        account_id,
+        AccountUpdate::Create {
+            address: Address::random(),
+            nonce: Nonce(0),
+        },
+    )];
+    // Balances are changed in blocks 1 and 2 within the same day and in block 3
+    // two days later.
+    let changes = [
+        (day + Duration::hours(1), 100u32),
+        (day + Duration::hours(2), 200u32),
+        (day + Duration::days(2), 150u32),
+    ];
+    let mut old_balance = BigUint::zero();
+    for (i, (timestamp, balance)) in changes.iter().enumerate() {
+        let block_number = BlockNumber(i as u32 + 1);
+        let new_balance = BigUint::from(*balance);
+        updates.push((
+            account_id,
+            AccountUpdate::UpdateBalance {
+                old_nonce: Nonce(0),
+                new_nonce: Nonce(0),
+                balance_update: (TokenId(0), old_balance, new_balance.clone()),
+            },
+        ));
+        old_balance = new_balance;
+
+        let mut block = gen_sample_block(block_number, BLOCK_SIZE_CHUNKS, Default::default());
+        block.timestamp = timestamp.timestamp() as u64;
+        BlockSchema(&mut storage).save_full_block(block).await?;
+        StateSchema(&mut storage)
+            .commit_state_update(block_number, &updates, 0)
+            .await?;
+        updates.clear();
+    }
+
+    // Only the last update of the day is taken.
+    let history = AccountSchema(&mut storage)
+        .get_account_balance_history(
+            account_id,
+            TokenId(0),
+            day,
+            day + Duration::days(3),
+            BalanceHistoryResolution::Day,
+        )
+        .await?;
+    let points: Vec<_> = history
+        .into_iter()
+        .map(|point| (point.timestamp, point.block_number, point.balance.0))
+        .collect();
+    assert_eq!(
+        points,
+        vec![
+            (day, BlockNumber(2), BigUint::from(200u32)),
+            (
+                day + Duration::days(2),
+                BlockNumber(3),
+                BigUint::from(150u32)
+            ),
+        ]
+    );
+
+    // The balance before the requested interval is returned as the first point.
+    let from = day + Duration::minutes(90);
+    let history = AccountSchema(&mut storage)
+        .get_account_balance_history(
+            account_id,
+            TokenId(0),
+            from,
+            day + Duration::days(1),
+            BalanceHistoryResolution::Hour,
+        )
+        .await?;
+    let points: Vec<_> = history
+        .into_iter()
+        .map(|point| (point.timestamp, point.block_number, point.balance.0))
+        .collect();
+    assert_eq!(
+        points,
+        vec![
+            (from, BlockNumber(1), BigUint::from(100u32)),
+            (
+                day + Duration::hours(2),
+                BlockNumber(2),
+                BigUint::from(200u32)
+            ),
+        ]
+    );
+
+    // There's no history for other tokens.
+    let history = AccountSchema(&mut storage)
+        .get_account_balance_history(
+            account_id,
+            TokenId(1),
+            day,
+            day + Duration::days(3),
+            BalanceHistoryResolution::Day,
+        )
+        .await?;
+    assert!(history.is_empty());
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+// Built-in imports
+use std::collections::BTreeMap;
 // External imports
 // Workspace imports
 use zksync_api_types::v02::pagination::{
@@ -6,10 +8,10 @@ use zksync_api_types::v02::pagination::{
 use zksync_crypto::{convert::FeConvert, rand::XorShiftRng};
 use zksync_types::{
     aggregated_operations::AggregatedActionType,
-    block::Block,
+    block::{Block, ExecutedOperations},
     helpers::apply_updates,
     tx::{ChangePubKeyType, TxHash},
-    AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, TokenId,
+    AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, TokenId, H256,
 };
 // Local imports
 use super::operations_ext::{
@@ -17,7 +19,10 @@ use super::operations_ext::{
 };
 use crate::{
     chain::{
-        block::{records::StorageBlockDetails, BlockSchema},
+        block::{
+            records::{BlocksStats, StorageBlockDetails},
+            BlockSchema,
+        },
         operations::OperationsSchema,
         state::StateSchema,
     },
@@ -196,6 +201,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
                 actual_block_detail.new_state_root,
                 expected_block_detail.new_state_root
             );
+            assert_eq!(actual_block_detail.tx_count, expected_block_detail.tx_count);
             assert_eq!(
                 actual_block_detail.commit_tx_hash,
                 expected_block_detail.commit_tx_hash
@@ -232,6 +238,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
             block_number: 0,
             new_state_root: Default::default(),
             block_size: 0,
+            tx_count: 0,
             commit_tx_hash: None,
             verify_tx_hash: None,
             committed_at: chrono::DateTime::from_utc(
@@ -290,6 +297,7 @@ async fn test_find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) ->
         current_block_detail.block_number = *block.block_number as i64;
         current_block_detail.new_state_root = block.new_root_hash.to_bytes();
         current_block_detail.block_size = block.block_transactions.len() as i64;
+        current_block_detail.tx_count = block.block_transactions.len() as i64;
         current_block_detail.commit_tx_hash = Some(eth_tx_hash.as_ref().to_vec());
 
         // Add verification for the block if required.
@@ -1053,6 +1061,45 @@ async fn test_is_block_finalized(mut storage: StorageProcessor<'_>) -> QueryResu
     Ok(())
 }
 
+/// Checks that `average_block_finalization_time` and `load_blocks_timestamps` methods work correctly.
+#[db_test]
+async fn test_block_finalization_time(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // No blocks were executed yet, so the finalization time can't be estimated.
+    let average = storage
+        .chain()
+        .block_schema()
+        .average_block_finalization_time(10)
+        .await?;
+    assert!(average.is_none());
+
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    commit_block(&mut storage, BlockNumber(2)).await?;
+    verify_block(&mut storage, BlockNumber(1)).await?;
+
+    let mut timestamps = storage
+        .chain()
+        .block_schema()
+        .load_blocks_timestamps(&[BlockNumber(1), BlockNumber(2), BlockNumber(3)])
+        .await?;
+    timestamps.sort_by_key(|timestamps| timestamps.block_number);
+    // Block 3 was not committed, so it should not be present in the result.
+    assert_eq!(timestamps.len(), 2);
+    assert_eq!(timestamps[0].block_number, 1);
+    assert!(timestamps[0].finalized_at.is_some());
+    assert_eq!(timestamps[1].block_number, 2);
+    assert!(timestamps[1].finalized_at.is_none());
+
+    let average = storage
+        .chain()
+        .block_schema()
+        .average_block_finalization_time(10)
+        .await?
+        .expect("Block 1 is executed");
+    assert!(average >= chrono::Duration::zero());
+
+    Ok(())
+}
+
 /// Check that blocks are removed correctly.
 #[db_test]
 async fn test_remove_blocks(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1292,6 +1339,34 @@ async fn test_get_block_number_by_hash(mut storage: StorageProcessor<'_>) -> Que
     Ok(())
 }
 
+#[db_test]
+async fn test_get_block_number_by_commitment(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let expected_number = BlockNumber(1);
+    let mut block = gen_sample_block(expected_number, BLOCK_SIZE_CHUNKS, Default::default());
+    block.block_commitment = H256::repeat_byte(0x11);
+    storage
+        .chain()
+        .block_schema()
+        .save_full_block(block.clone())
+        .await?;
+
+    let actual_number = storage
+        .chain()
+        .block_schema()
+        .get_block_number_by_commitment(block.block_commitment.as_bytes())
+        .await?;
+    assert_eq!(actual_number, Some(expected_number));
+
+    let unknown = storage
+        .chain()
+        .block_schema()
+        .get_block_number_by_commitment(H256::repeat_byte(0x22).as_bytes())
+        .await?;
+    assert_eq!(unknown, None);
+
+    Ok(())
+}
+
 /// Check that `get_block_transactions_hashes` works correctly
 #[db_test]
 async fn test_get_block_transactions_hashes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -1393,3 +1468,46 @@ async fn test_incomplete_block_logic(mut storage: StorageProcessor<'_>) -> Query
 
     Ok(())
 }
+
+/// Checks that `get_blocks_stats` counts the operations of the requested blocks only.
+#[db_test]
+async fn test_get_blocks_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let mut expected_counts = BTreeMap::new();
+    for op in &setup.blocks[0].block_transactions {
+        let tx_type = match op {
+            ExecutedOperations::Tx(tx) => tx.signed_tx.tx.variance_name(),
+            ExecutedOperations::PriorityOp(op) => op.priority_op.data.variance_name(),
+        };
+        *expected_counts.entry(tx_type).or_insert(0u64) += 1;
+    }
+
+    let stats = BlockSchema(&mut storage)
+        .get_blocks_stats(BlockNumber(1), BlockNumber(1))
+        .await?;
+    assert_eq!(stats.tx_counts, expected_counts);
+    assert!(!stats.fees.is_empty());
+    assert!(stats.active_accounts > 0);
+
+    // Both blocks contain the same set of operations.
+    let stats = BlockSchema(&mut storage)
+        .get_blocks_stats(BlockNumber(1), BlockNumber(2))
+        .await?;
+    let expected_counts: BTreeMap<_, _> = expected_counts
+        .into_iter()
+        .map(|(tx_type, count)| (tx_type, count * 2))
+        .collect();
+    assert_eq!(stats.tx_counts, expected_counts);
+
+    // There are no operations in the blocks that do not exist.
+    let stats = BlockSchema(&mut storage)
+        .get_blocks_stats(BlockNumber(3), BlockNumber(5))
+        .await?;
+    assert_eq!(stats, BlocksStats::default());
+
+    Ok(())
+}
@@ -1,12 +1,16 @@
 // External imports
 use chrono::Utc;
 // Workspace imports
+use zksync_api_types::v02::{
+    mempool::{MempoolHoldReason, MempoolSnapshot, MempoolSnapshotTx, MEMPOOL_SNAPSHOT_VERSION},
+    transaction::TxInBlockStatus,
+};
 use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
 use zksync_types::{
     block::{Block, ExecutedOperations},
     mempool::SignedTxVariant,
-    priority_ops::FullExit,
-    tx::{ChangePubKey, Transfer, TxHash, Withdraw},
+    priority_ops::{Deposit, FullExit},
+    tx::{ChangePubKey, TimeRange, Transfer, TxHash, Withdraw},
     AccountId, Address, BlockNumber, ExecutedPriorityOp, ExecutedTx, FullExitOp, Nonce, PriorityOp,
     SignedZkSyncTx, TokenId, ZkSyncOp, ZkSyncPriorityOp, ZkSyncTx, H256,
 };
@@ -15,7 +19,7 @@ use crate::test_data::gen_eth_sign_data;
 use crate::tests::db_test;
 use crate::{
     chain::{
-        mempool::MempoolSchema,
+        mempool::{find_held_txs, MempoolSchema, ASYNC_VERIFICATION_TIMEOUT_SECS},
         operations::{
             records::{NewExecutedPriorityOperation, NewExecutedTransaction},
             OperationsSchema,
@@ -165,6 +169,28 @@ async fn store_load(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Checks that the transactions inserted with a single statement are loaded in the same order.
+#[db_test]
+async fn store_load_multiple(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut txs = zksync_txs();
+    // Transactions without the Ethereum signature data are stored as well.
+    txs[1].eth_sign_data = None;
+    MempoolSchema(&mut storage).insert_txs(&txs).await?;
+
+    let txs_from_db = MempoolSchema(&mut storage).load_txs(&[]).await?;
+    assert_eq!(txs_from_db.len(), txs.len());
+    for (tx, tx_from_db) in txs.iter().zip(txs_from_db) {
+        let tx_from_db = unwrap_tx(tx_from_db);
+        assert_eq!(tx_from_db.hash(), tx.hash(), "transaction changed");
+        assert_eq!(
+            tx_from_db.eth_sign_data, tx.eth_sign_data,
+            "sign data changed"
+        );
+    }
+
+    Ok(())
+}
+
 /// Checks the save&load routine for mempool schema.
 #[db_test]
 async fn store_load_batch(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -258,19 +284,82 @@ async fn remove_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
-/// Checks that already committed txs are removed by `collect_garbage` method.
+/// Checks that only the queued transactions submitted on their own can be cancelled.
 #[db_test]
-async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
-    // Insert several txs into the mempool schema.
+async fn remove_queued_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    let batch_signature = vec![gen_eth_sign_data("test message".to_owned()).signature; 2];
+    MempoolSchema(&mut storage).insert_tx(&txs[0]).await?;
+    MempoolSchema(&mut storage)
+        .insert_batch(&txs[1..], batch_signature)
+        .await?;
+
+    assert!(
+        MempoolSchema(&mut storage)
+            .remove_queued_tx(txs[0].hash())
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(txs[0].hash())
+            .await?
+    );
+    // The transaction is not queued anymore.
+    assert!(
+        !MempoolSchema(&mut storage)
+            .remove_queued_tx(txs[0].hash())
+            .await?
+    );
+    // Transactions of the batches can't be removed on their own.
+    assert!(
+        !MempoolSchema(&mut storage)
+            .remove_queued_tx(txs[1].hash())
+            .await?
+    );
+    assert!(
+        MempoolSchema(&mut storage)
+            .contains_tx(txs[1].hash())
+            .await?
+    );
+
+    Ok(())
+}
+
+/// Checks that the repeated copies of the transactions are removed and the executed ones are found.
+#[db_test]
+async fn remove_duplicates_and_find_executed(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     let txs = zksync_txs();
     for tx in &txs {
-        MempoolSchema(&mut storage)
-            .insert_tx(&tx.clone())
-            .await
-            .expect("Can't insert txs");
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    // The submission of the second transaction is retried after the crash.
+    MempoolSchema(&mut storage).insert_tx(&txs[1]).await?;
+    assert_eq!(
+        MempoolSchema(&mut storage).load_txs(&[]).await?.len(),
+        txs.len() + 1
+    );
+
+    let removed = MempoolSchema(&mut storage).remove_duplicate_txs().await?;
+    assert_eq!(removed, 1);
+    // The order of the transactions is preserved.
+    let txs_from_db = MempoolSchema(&mut storage).load_txs(&[]).await?;
+    assert_eq!(txs_from_db.len(), txs.len());
+    for (tx, tx_from_db) in txs.iter().zip(txs_from_db) {
+        assert_eq!(tx.hash(), unwrap_tx(tx_from_db).hash());
     }
 
-    // Add one executed transaction.
+    // Batch repeating one of the stored transactions is removed as a whole.
+    let batch = vec![gen_transfers(1).remove(0), txs[2].clone()];
+    MempoolSchema(&mut storage)
+        .insert_batch(&batch, Vec::new())
+        .await?;
+    let removed = MempoolSchema(&mut storage).remove_duplicate_txs().await?;
+    assert_eq!(removed, 2);
+    assert_eq!(
+        MempoolSchema(&mut storage).load_txs(&[]).await?.len(),
+        txs.len()
+    );
+
     let executed_tx = NewExecutedTransaction {
         block_number: 1,
         tx_hash: txs[0].hash().as_ref().to_vec(),
@@ -288,23 +377,17 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         batch_id: None,
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
+        order_hashes: Vec::new(),
     };
     OperationsSchema(&mut storage)
         .store_executed_tx(executed_tx)
         .await?;
 
-    // Collect the garbage. Execution transaction (very first one from the list)
-    // should be removed from the schema.
-    MempoolSchema(&mut storage).collect_garbage().await?;
-    let retained_hashes: Vec<_> = txs[1..].iter().map(|tx| tx.hash()).collect();
-
-    // Load the txs and check that they match the expected list.
-    let txs_from_db = MempoolSchema(&mut storage).load_txs(&[]).await?;
-    assert_eq!(txs_from_db.len(), retained_hashes.len());
-
-    for (expected_hash, tx_from_db) in retained_hashes.iter().zip(txs_from_db) {
-        assert_eq!(*expected_hash, unwrap_tx(tx_from_db).hash());
-    }
+    let tx_hashes: Vec<_> = txs.iter().map(|tx| tx.hash()).collect();
+    let executed = MempoolSchema(&mut storage)
+        .get_executed_tx_hashes(&tx_hashes)
+        .await?;
+    assert_eq!(executed, vec![txs[0].hash()].into_iter().collect());
 
     Ok(())
 }
@@ -353,6 +436,613 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
         );
     }
 
+    // Only the transaction submitted on its own should be returned by `get_single_tx`.
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_single_tx(txs[0].hash().as_ref())
+            .await?
+            .unwrap()
+            .hash(),
+        txs[0].hash()
+    );
+    for tx in &txs[1..] {
+        assert!(MempoolSchema(&mut storage)
+            .get_single_tx(tx.hash().as_ref())
+            .await?
+            .is_none());
+    }
+
+    Ok(())
+}
+
+/// Checks that pending priority operations are found by the Ethereum transaction hash.
+#[db_test]
+async fn get_pending_operation_by_hash(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let priority_op = PriorityOp {
+        serial_id: 1,
+        data: ZkSyncPriorityOp::FullExit(FullExit {
+            account_id: AccountId(0),
+            eth_address: Address::random(),
+            token: TokenId(0),
+            is_legacy: false,
+        }),
+        deadline_block: 100,
+        eth_hash: H256::random(),
+        eth_block: 10,
+        eth_block_index: Some(1),
+    };
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[priority_op.clone()], true)
+        .await?;
+
+    let by_eth_hash = MempoolSchema(&mut storage)
+        .get_pending_operation_by_hash(priority_op.eth_hash)
+        .await?
+        .expect("Operation should be found by the Ethereum hash");
+    assert_eq!(by_eth_hash.serial_id, priority_op.serial_id);
+
+    let unknown = MempoolSchema(&mut storage)
+        .get_pending_operation_by_hash(H256::random())
+        .await?;
+    assert!(unknown.is_none());
+
+    Ok(())
+}
+
+/// Checks that the greatest nonce of the account transactions in the mempool is found.
+#[db_test]
+async fn get_max_nonce_for_account(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Accounts 42 and 4242 have transfers with nonces 10 and 11, account 123 changes its pubkey with nonce 13.
+    for tx in zksync_txs() {
+        MempoolSchema(&mut storage).insert_tx(&tx).await?;
+    }
+
+    let nonce = MempoolSchema(&mut storage)
+        .get_max_nonce_for_account(AccountId(4242))
+        .await?;
+    assert_eq!(nonce, Some(Nonce(11)));
+
+    let nonce = MempoolSchema(&mut storage)
+        .get_max_nonce_for_account(AccountId(123))
+        .await?;
+    assert_eq!(nonce, Some(Nonce(13)));
+
+    let nonce = MempoolSchema(&mut storage)
+        .get_max_nonce_for_account(AccountId(1))
+        .await?;
+    assert_eq!(nonce, None);
+
+    Ok(())
+}
+
+/// Checks that the nonces of the queued transactions are grouped by the account.
+#[db_test]
+async fn get_queued_nonces(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Accounts 42 and 4242 have transfers with nonces 10 and 11, account 123 changes its pubkey with nonce 13.
+    for tx in zksync_txs() {
+        MempoolSchema(&mut storage).insert_tx(&tx).await?;
+    }
+
+    let nonces = MempoolSchema(&mut storage)
+        .get_queued_nonces(&[AccountId(42), AccountId(123), AccountId(1)])
+        .await?;
+    assert_eq!(nonces.len(), 2);
+    assert_eq!(
+        nonces[&AccountId(42)],
+        vec![Nonce(10)].into_iter().collect()
+    );
+    assert_eq!(
+        nonces[&AccountId(123)],
+        vec![Nonce(13)].into_iter().collect()
+    );
+
+    Ok(())
+}
+
+/// Checks that the mempool transactions are found both by the initiator account id
+/// and by the recipient address.
+#[db_test]
+async fn get_account_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    // Account 42 initiates the first transfer.
+    let account_txs = MempoolSchema(&mut storage)
+        .get_account_txs(AccountId(42), Address::random())
+        .await?;
+    assert_eq!(account_txs.len(), 1);
+    assert_eq!(account_txs[0].hash(), txs[0].hash());
+
+    // The second transfer is incoming for its recipient.
+    let recipient = match &txs[1].tx {
+        ZkSyncTx::Transfer(transfer) => transfer.to,
+        _ => unreachable!(),
+    };
+    let account_txs = MempoolSchema(&mut storage)
+        .get_account_txs(AccountId(1), recipient)
+        .await?;
+    assert_eq!(account_txs.len(), 1);
+    assert_eq!(account_txs[0].hash(), txs[1].hash());
+
+    let account_txs = MempoolSchema(&mut storage)
+        .get_account_txs(AccountId(1), Address::random())
+        .await?;
+    assert!(account_txs.is_empty());
+
+    Ok(())
+}
+
+/// Checks that both pending deposits and full exits are loaded for the address,
+/// while `get_pending_deposits` only returns the deposits.
+#[db_test]
+async fn get_pending_priority_ops(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::random();
+    let deposit = PriorityOp {
+        serial_id: 1,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: Address::random(),
+            token: TokenId(1),
+            amount: 100u32.into(),
+            to: address,
+        }),
+        deadline_block: 100,
+        eth_hash: H256::random(),
+        eth_block: 10,
+        eth_block_index: Some(1),
+    };
+    let full_exit = PriorityOp {
+        serial_id: 2,
+        data: ZkSyncPriorityOp::FullExit(FullExit {
+            account_id: AccountId(1),
+            eth_address: address,
+            token: TokenId(0),
+            is_legacy: false,
+        }),
+        deadline_block: 101,
+        eth_hash: H256::random(),
+        eth_block: 11,
+        eth_block_index: Some(1),
+    };
+    let other_deposit = PriorityOp {
+        serial_id: 3,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: address,
+            token: TokenId(1),
+            amount: 100u32.into(),
+            to: Address::random(),
+        }),
+        deadline_block: 102,
+        eth_hash: H256::random(),
+        eth_block: 12,
+        eth_block_index: Some(1),
+    };
+    MempoolSchema(&mut storage)
+        .insert_priority_ops(&[deposit, full_exit, other_deposit], true)
+        .await?;
+
+    let ops = MempoolSchema(&mut storage)
+        .get_pending_priority_ops(address)
+        .await?;
+    let serial_ids: Vec<_> = ops.iter().map(|op| op.serial_id).collect();
+    assert_eq!(serial_ids, vec![1, 2]);
+    assert_eq!(ops[1].deadline_block, 101);
+
+    let deposits = MempoolSchema(&mut storage)
+        .get_pending_deposits(address)
+        .await?;
+    assert_eq!(deposits.len(), 1);
+    assert_eq!(deposits[0].serial_id, 1);
+
+    Ok(())
+}
+
+/// Checks that the latest Ethereum block seen by the watcher is stored and overwritten.
+#[db_test]
+async fn last_watched_eth_block(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    assert!(MempoolSchema(&mut storage)
+        .get_last_watched_eth_block()
+        .await?
+        .is_none());
+
+    MempoolSchema(&mut storage)
+        .update_last_watched_eth_block(10)
+        .await?;
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_last_watched_eth_block()
+            .await?,
+        Some(10)
+    );
+
+    MempoolSchema(&mut storage)
+        .update_last_watched_eth_block(15)
+        .await?;
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .get_last_watched_eth_block()
+            .await?,
+        Some(15)
+    );
+
+    Ok(())
+}
+
+/// Checks the statistics and the listing of the mempool transactions.
+#[db_test]
+async fn mempool_stats_and_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let stats = MempoolSchema(&mut storage).get_mempool_stats().await?;
+    assert_eq!(stats.size, 0);
+    assert!(stats.oldest_tx_created_at.is_none());
+    assert!(stats.txs_by_type.is_empty());
+
+    // 2 transfers, withdraw and change pubkey.
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    let batch = gen_transfers(3);
+    MempoolSchema(&mut storage)
+        .insert_batch(&batch, Vec::new())
+        .await?;
+
+    let stats = MempoolSchema(&mut storage).get_mempool_stats().await?;
+    assert_eq!(stats.size, 7);
+    assert_eq!(stats.batched_txs, 3);
+    assert_eq!(stats.txs_by_type["Transfer"], 5);
+    assert_eq!(stats.txs_by_type["Withdraw"], 1);
+    assert_eq!(stats.txs_by_type["ChangePubKey"], 1);
+    assert!(stats.oldest_tx_created_at.is_some());
+
+    let all_txs = MempoolSchema(&mut storage)
+        .load_mempool_txs(None, 10, 0)
+        .await?;
+    assert_eq!(all_txs.len(), 7);
+    assert_eq!(all_txs[0].tx_hash, txs[0].hash());
+    assert_eq!(all_txs[0].batch_id, None);
+    assert!(all_txs[6].batch_id.is_some());
+
+    let transfers = MempoolSchema(&mut storage)
+        .load_mempool_txs(Some("Transfer"), 2, 1)
+        .await?;
+    let transfer_hashes: Vec<_> = transfers.iter().map(|tx| tx.tx_hash).collect();
+    assert_eq!(transfer_hashes, vec![txs[1].hash(), batch[0].hash()]);
+    assert!(transfers.iter().all(|tx| tx.tx_type == "Transfer"));
+
+    Ok(())
+}
+
+/// Checks that the expired transactions are moved out of the mempool along with the reason.
+#[db_test]
+async fn expire_mempool_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    let expired = vec![
+        (txs[0].hash(), "validity period ended".to_string()),
+        (txs[2].hash(), "too old".to_string()),
+    ];
+    MempoolSchema(&mut storage).expire_txs(&expired).await?;
+
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(txs[0].hash())
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(txs[2].hash())
+            .await?
+    );
+    assert!(
+        MempoolSchema(&mut storage)
+            .contains_tx(txs[1].hash())
+            .await?
+    );
+
+    let expired_txs = MempoolSchema(&mut storage)
+        .load_expired_txs(&[txs[0].hash(), txs[1].hash()])
+        .await?;
+    assert_eq!(expired_txs.len(), 1);
+    assert_eq!(expired_txs[0].tx_hash, hex::encode(txs[0].hash()));
+    assert_eq!(expired_txs[0].reason, "validity period ended");
+
+    // Receipts show the transactions expired.
+    let receipt = OperationsExtSchema(&mut storage)
+        .tx_receipt_api_v02(txs[0].hash().as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(receipt.status(), TxInBlockStatus::Expired);
+    let receipts = OperationsExtSchema(&mut storage)
+        .tx_receipts_api_v02(&[txs[1].hash(), txs[2].hash()])
+        .await?;
+    assert_eq!(receipts[&txs[1].hash()].status(), TxInBlockStatus::Queued);
+    assert_eq!(receipts[&txs[2].hash()].status(), TxInBlockStatus::Expired);
+
+    // Records are removed once they are too old.
+    let removed = MempoolSchema(&mut storage)
+        .remove_expired_txs(Utc::now() + chrono::Duration::seconds(1))
+        .await?;
+    assert_eq!(removed, 2);
+    let expired_txs = MempoolSchema(&mut storage)
+        .load_expired_txs(&[txs[0].hash(), txs[2].hash()])
+        .await?;
+    assert!(expired_txs.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the asynchronously submitted transactions that haven't reached the mempool
+/// are reported in the receipts as pending or rejected.
+#[db_test]
+async fn async_submitted_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs[..3] {
+        MempoolSchema(&mut storage)
+            .store_async_submitted_tx(tx.hash(), tx.created_at)
+            .await?;
+    }
+    MempoolSchema(&mut storage)
+        .reject_async_submitted_tx(txs[0].hash(), "invalid signature")
+        .await?;
+    // Transaction that has passed the verification is forgotten once it reaches the mempool.
+    MempoolSchema(&mut storage).insert_tx(&txs[1]).await?;
+    MempoolSchema(&mut storage)
+        .remove_async_submitted_tx(txs[1].hash())
+        .await?;
+
+    let async_txs = MempoolSchema(&mut storage)
+        .load_async_submitted_txs(&[txs[0].hash(), txs[1].hash()])
+        .await?;
+    assert_eq!(async_txs.len(), 1);
+    assert_eq!(async_txs[0].tx_hash, hex::encode(txs[0].hash()));
+    assert_eq!(async_txs[0].reason.as_deref(), Some("invalid signature"));
+    assert!(async_txs[0].rejected_at.is_some());
+
+    let receipt = OperationsExtSchema(&mut storage)
+        .tx_receipt_api_v02(txs[0].hash().as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(receipt.status(), TxInBlockStatus::RejectedPreBlock);
+    let receipts = OperationsExtSchema(&mut storage)
+        .tx_receipts_api_v02(&[txs[1].hash(), txs[2].hash()])
+        .await?;
+    assert_eq!(receipts[&txs[1].hash()].status(), TxInBlockStatus::Queued);
+    assert_eq!(
+        receipts[&txs[2].hash()].status(),
+        TxInBlockStatus::PendingVerification
+    );
+
+    // Resubmission of the rejected transaction makes it pending again.
+    MempoolSchema(&mut storage)
+        .store_async_submitted_tx(txs[0].hash(), txs[0].created_at)
+        .await?;
+    let receipt = OperationsExtSchema(&mut storage)
+        .tx_receipt_api_v02(txs[0].hash().as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(receipt.status(), TxInBlockStatus::PendingVerification);
+
+    // Pending transaction is forgotten if its verification was interrupted.
+    let interrupted_at =
+        Utc::now() - chrono::Duration::seconds(ASYNC_VERIFICATION_TIMEOUT_SECS + 60);
+    MempoolSchema(&mut storage)
+        .store_async_submitted_tx(txs[3].hash(), interrupted_at)
+        .await?;
+    let async_txs = MempoolSchema(&mut storage)
+        .load_async_submitted_txs(&[txs[3].hash()])
+        .await?;
+    assert!(async_txs.is_empty());
+    // Its rejection is still recorded if the verification completes late.
+    let late_tx = gen_transfers(1).pop().unwrap();
+    MempoolSchema(&mut storage)
+        .store_async_submitted_tx(late_tx.hash(), interrupted_at)
+        .await?;
+    MempoolSchema(&mut storage)
+        .reject_async_submitted_tx(late_tx.hash(), "invalid signature")
+        .await?;
+    let receipt = OperationsExtSchema(&mut storage)
+        .tx_receipt_api_v02(late_tx.hash().as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(receipt.status(), TxInBlockStatus::RejectedPreBlock);
+
+    // Rejected records are removed once they are too old, the interrupted pending ones
+    // are removed right away, while the recent pending ones are kept.
+    let removed = MempoolSchema(&mut storage)
+        .remove_async_submitted_txs(Utc::now() + chrono::Duration::seconds(1))
+        .await?;
+    assert_eq!(removed, 2);
+    let async_txs = MempoolSchema(&mut storage)
+        .load_async_submitted_txs(&[txs[0].hash(), txs[2].hash()])
+        .await?;
+    assert_eq!(async_txs.len(), 2);
+
+    Ok(())
+}
+
+/// Checks that the mempool snapshot contains all the transactions along with their signatures.
+#[db_test]
+async fn mempool_snapshot(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+    let batch = gen_transfers(2);
+    let batch_signature = gen_eth_sign_data("batch message".to_string()).signature;
+    let batch_id = MempoolSchema(&mut storage)
+        .insert_batch(&batch, vec![batch_signature.clone()])
+        .await?;
+
+    let snapshot = MempoolSchema(&mut storage).load_mempool_snapshot().await?;
+    assert_eq!(snapshot.version, MEMPOOL_SNAPSHOT_VERSION);
+    assert_eq!(snapshot.txs.len(), 6);
+    assert_eq!(snapshot.txs[0].tx_hash, txs[0].hash());
+    assert_eq!(snapshot.txs[0].eth_sign_data, txs[0].eth_sign_data);
+    assert_eq!(snapshot.txs[0].tx_type, "Transfer");
+    assert_eq!(snapshot.txs[5].batch_id, Some(batch_id));
+    assert_eq!(snapshot.batch_signatures[&batch_id], vec![batch_signature]);
+    // None of the accounts exist, so every transaction is held.
+    assert!(snapshot.committed_nonces.is_empty());
+    assert!(snapshot.txs.iter().all(|tx| matches!(
+        tx.held_reasons.as_slice(),
+        [MempoolHoldReason::UnknownAccount { .. }]
+    )));
+
+    // The snapshot can be restored from the exported file.
+    let exported = serde_json::to_string(&snapshot)?;
+    assert_eq!(
+        serde_json::from_str::<MempoolSnapshot>(&exported)?,
+        snapshot
+    );
+
+    Ok(())
+}
+
+/// Checks that the reasons the transactions are held in the mempool are determined correctly.
+#[test]
+fn held_mempool_txs() {
+    let snapshot_tx = |account_id: u32, nonce: u32, time_range: TimeRange| {
+        let tx = ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(account_id),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            100u32.into(),
+            10u32.into(),
+            Nonce(nonce),
+            time_range,
+            None,
+        )));
+        MempoolSnapshotTx {
+            tx_hash: tx.hash(),
+            tx_type: "Transfer".to_string(),
+            batch_id: None,
+            created_at: Utc::now(),
+            reverted: false,
+            tx,
+            eth_sign_data: None,
+            held_reasons: Vec::new(),
+        }
+    };
+    let now = 1_000;
+
+    let mut txs = vec![
+        snapshot_tx(1, 4, Default::default()),
+        snapshot_tx(1, 6, Default::default()),
+        snapshot_tx(1, 5, Default::default()),
+        snapshot_tx(1, 8, Default::default()),
+        snapshot_tx(2, 0, Default::default()),
+        snapshot_tx(3, 0, TimeRange::new(now + 10, u64::MAX)),
+        snapshot_tx(3, 1, TimeRange::new(0, now - 10)),
+    ];
+    let committed_nonces = vec![(AccountId(1), Nonce(5)), (AccountId(3), Nonce(0))]
+        .into_iter()
+        .collect();
+    find_held_txs(&mut txs, &committed_nonces, now);
+
+    let held_reasons: Vec<_> = txs.into_iter().map(|tx| tx.held_reasons).collect();
+    assert_eq!(
+        held_reasons,
+        vec![
+            vec![MempoolHoldReason::NonceTooLow {
+                committed_nonce: Nonce(5)
+            }],
+            vec![],
+            vec![],
+            vec![MempoolHoldReason::NonceGap {
+                expected_nonce: Nonce(7)
+            }],
+            vec![MempoolHoldReason::UnknownAccount {
+                account_id: AccountId(2)
+            }],
+            vec![MempoolHoldReason::NotValidYet {
+                valid_from: now + 10
+            }],
+            vec![MempoolHoldReason::ValidityEnded {
+                valid_until: now - 10
+            }],
+        ]
+    );
+}
+
+/// Checks that the queued transaction is atomically replaced by the one with the same nonce.
+#[db_test]
+async fn replace_mempool_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = zksync_txs();
+    MempoolSchema(&mut storage).insert_tx(&txs[0]).await?;
+    let batch = gen_transfers(1);
+    MempoolSchema(&mut storage)
+        .insert_batch(&batch, Vec::new())
+        .await?;
+
+    let queued_tx = MempoolSchema(&mut storage)
+        .get_queued_tx_by_nonce(AccountId(42), Nonce(10))
+        .await?
+        .expect("Transaction should be queued");
+    assert_eq!(queued_tx.hash(), txs[0].hash());
+    assert!(MempoolSchema(&mut storage)
+        .get_queued_tx_by_nonce(AccountId(42), Nonce(11))
+        .await?
+        .is_none());
+    // Transactions of the batches can't be replaced.
+    assert!(MempoolSchema(&mut storage)
+        .get_queued_tx_by_nonce(AccountId(0), Nonce(10))
+        .await?
+        .is_none());
+
+    let replacement = SignedZkSyncTx {
+        tx: ZkSyncTx::Transfer(Box::new(Transfer::new(
+            AccountId(42),
+            Address::random(),
+            Address::random(),
+            TokenId(0),
+            100u32.into(),
+            20u32.into(),
+            Nonce(10),
+            Default::default(),
+            None,
+        ))),
+        eth_sign_data: None,
+        created_at: Utc::now(),
+    };
+    assert!(
+        MempoolSchema(&mut storage)
+            .replace_tx(txs[0].hash(), &replacement)
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(txs[0].hash())
+            .await?
+    );
+    assert!(
+        MempoolSchema(&mut storage)
+            .contains_tx(replacement.hash())
+            .await?
+    );
+
+    let receipt = OperationsExtSchema(&mut storage)
+        .tx_receipt_api_v02(txs[0].hash().as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(receipt.status(), TxInBlockStatus::Expired);
+
+    // The transaction that is not queued anymore can't be replaced again.
+    assert!(
+        !MempoolSchema(&mut storage)
+            .replace_tx(txs[0].hash(), &txs[1])
+            .await?
+    );
+    assert!(
+        !MempoolSchema(&mut storage)
+            .contains_tx(txs[1].hash())
+            .await?
+    );
+
     Ok(())
 }
 
@@ -14,6 +14,7 @@ use crate::{
             records::{NewExecutedPriorityOperation, NewExecutedTransaction},
             OperationsSchema,
         },
+        operations_ext::OperationsExtSchema,
     },
     test_data::gen_unique_aggregated_operation,
     tests::db_test,
@@ -66,6 +67,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
         batch_id: Some(10),
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
+        order_hashes: Vec::new(),
     };
 
     OperationsSchema(&mut storage)
@@ -96,6 +98,50 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
     Ok(())
 }
 
+/// Checks that executed swaps can be found by the hashes of their orders.
+#[db_test]
+async fn swap_order_hashes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let order_hashes = vec![vec![0x01; 32], vec![0x02; 32]];
+    let executed_tx = NewExecutedTransaction {
+        block_number: 1,
+        tx_hash: vec![0xAB; 32],
+        tx: Default::default(),
+        operation: Default::default(),
+        from_account: Default::default(),
+        to_account: None,
+        success: true,
+        fail_reason: None,
+        block_index: None,
+        primary_account_address: Default::default(),
+        nonce: Default::default(),
+        created_at: chrono::Utc::now(),
+        eth_sign_data: None,
+        batch_id: None,
+        affected_accounts: Vec::new(),
+        used_tokens: Vec::new(),
+        order_hashes: order_hashes.clone(),
+    };
+
+    OperationsSchema(&mut storage)
+        .store_executed_tx(executed_tx.clone())
+        .await?;
+
+    for order_hash in &order_hashes {
+        let tx_hash = OperationsExtSchema(&mut storage)
+            .get_swap_tx_hash_by_order_hash(order_hash)
+            .await?
+            .expect("Swap should be found by the order hash");
+        assert_eq!(tx_hash.as_ref(), executed_tx.tx_hash.as_slice());
+    }
+
+    let unknown = OperationsExtSchema(&mut storage)
+        .get_swap_tx_hash_by_order_hash(&[0x03; 32])
+        .await?;
+    assert!(unknown.is_none());
+
+    Ok(())
+}
+
 /// Checks the save&load routine for executed priority operations.
 #[db_test]
 async fn executed_priority_operations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -161,6 +207,7 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
         batch_id: None,
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
+        order_hashes: Vec::new(),
     };
 
     let executed_priority_op = NewExecutedPriorityOperation {
@@ -236,6 +283,7 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
         batch_id: None,
         affected_accounts: Vec::new(),
         used_tokens: Vec::new(),
+        order_hashes: Vec::new(),
     };
 
     // Save the failed operation.
@@ -316,6 +364,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
         batch_id: None,
         affected_accounts: vec![Address::zero().as_bytes().to_vec()],
         used_tokens: vec![0],
+        order_hashes: Vec::new(),
     };
     let timestamp_2 = timestamp_1 - Duration::weeks(1);
     let mut executed_tx_2 = executed_tx_1.clone();
@@ -369,7 +418,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 4);
     assert_eq!(count_tx_filters, 4);
@@ -388,7 +437,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 2);
     assert_eq!(count_tx_filters, 2);
@@ -406,7 +455,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 1);
     assert_eq!(count_tx_filters, 1);
@@ -419,7 +468,7 @@ async fn remove_rejected_transactions(mut storage: StorageProcessor<'_>) -> Quer
     let count_tx_filters = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(Default::default(), None, None)
+        .get_account_transactions_count(Default::default(), None, None, &Default::default())
         .await?;
     assert_eq!(count, 1);
     assert_eq!(count_tx_filters, 1);
@@ -1,27 +1,32 @@
 // Built-in imports
 use std::collections::HashMap;
 // External imports
+use chrono::{Duration, Utc};
 // Workspace imports
 use zksync_api_types::v02::{
-    pagination::{AccountTxsRequest, ApiEither, PaginationDirection, PaginationQuery},
-    transaction::{Receipt, TxInBlockStatus},
+    pagination::{
+        AccountTxDirection, AccountTxsFilters, AccountTxsRequest, ApiEither, PaginationDirection,
+        PaginationQuery,
+    },
+    transaction::{Receipt, SwapReceipt, TxInBlockStatus},
 };
 use zksync_crypto::{franklin_crypto::bellman::pairing::ff::Field, Fr};
 use zksync_types::{
     aggregated_operations::{AggregatedActionType, AggregatedOperation},
     block::Block,
-    tx::TxHash,
-    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, ZkSyncOp, H256,
+    tx::{ChangePubKeyType, TxHash},
+    AccountId, AccountUpdate, BlockNumber, ExecutedOperations, Nonce, TokenId, ZkSyncOp, ZkSyncTx,
+    H256,
 };
 // Local imports
 use self::setup::TransactionsHistoryTestSetup;
 use crate::{
     chain::block::BlockSchema,
-    chain::operations::OperationsSchema,
+    chain::operations::{records::NewExecutedTransaction, OperationsSchema},
     chain::operations_ext::SearchDirection,
     test_data::{
-        dummy_ethereum_tx_hash, gen_sample_block, gen_unique_aggregated_operation,
-        BLOCK_SIZE_CHUNKS,
+        dummy_ethereum_tx_hash, gen_eth_sign_data, gen_sample_block,
+        gen_unique_aggregated_operation, BLOCK_SIZE_CHUNKS,
     },
     tests::{db_test, ACCOUNT_MUTEX},
     tokens::StoreTokenError,
@@ -386,6 +391,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 0)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -514,6 +520,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                     tx_hash: ApiEither::from(request.tx_hash),
                     token: None,
                     second_address: None,
+                    filters: Default::default(),
                 },
                 limit: request.limit,
                 direction: request.direction,
@@ -533,6 +540,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(1, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -551,6 +559,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 9)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 2,
             direction: PaginationDirection::Newer,
@@ -570,6 +579,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -585,6 +595,7 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
                 tx_hash: ApiEither::from(setup.get_tx_hash(0, 2)),
                 token: None,
                 second_address: None,
+                filters: Default::default(),
             },
             limit: 1,
             direction: PaginationDirection::Newer,
@@ -598,6 +609,188 @@ async fn get_account_transactions(mut storage: StorageProcessor<'_>) -> QueryRes
     Ok(())
 }
 
+/// Checks that `get_account_transactions` and `get_account_transactions_count`
+/// respect the transaction direction, type and time range filters.
+#[db_test]
+async fn get_account_transactions_with_filters(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let from = setup.from_zksync_account.address;
+    let to = setup.to_zksync_account.address;
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let test_data = vec![
+        (
+            "Incoming transfers.",
+            to,
+            AccountTxsFilters {
+                tx_direction: Some(AccountTxDirection::Incoming),
+                tx_type: Some("Transfer".to_string()),
+                ..Default::default()
+            },
+            vec![setup.get_tx_hash(0, 2), setup.get_tx_hash(0, 1)],
+        ),
+        (
+            "Outgoing deposits.",
+            from,
+            AccountTxsFilters {
+                tx_direction: Some(AccountTxDirection::Outgoing),
+                tx_type: Some("Deposit".to_string()),
+                ..Default::default()
+            },
+            vec![setup.get_tx_hash(0, 0)],
+        ),
+        (
+            "No incoming transfers for the sender.",
+            from,
+            AccountTxsFilters {
+                tx_direction: Some(AccountTxDirection::Incoming),
+                tx_type: Some("Transfer".to_string()),
+                ..Default::default()
+            },
+            vec![],
+        ),
+        (
+            "Time range in the future.",
+            from,
+            AccountTxsFilters {
+                from_time: Some(Utc::now() + Duration::days(1)),
+                ..Default::default()
+            },
+            vec![],
+        ),
+        (
+            "Withdrawals created before tomorrow.",
+            from,
+            AccountTxsFilters {
+                tx_type: Some("Withdraw".to_string()),
+                to_time: Some(Utc::now() + Duration::days(1)),
+                ..Default::default()
+            },
+            vec![setup.get_tx_hash(0, 5)],
+        ),
+    ];
+
+    for (test_name, address, filters, expected_resp) in test_data {
+        let count = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions_count(address, None, None, &filters)
+            .await?;
+        assert_eq!(
+            count as usize,
+            expected_resp.len(),
+            "\"{}\", failed",
+            test_name
+        );
+
+        let items = storage
+            .chain()
+            .operations_ext_schema()
+            .get_account_transactions(&PaginationQuery {
+                from: AccountTxsRequest {
+                    address,
+                    tx_hash: ApiEither::from(setup.get_tx_hash(0, 9)),
+                    token: None,
+                    second_address: None,
+                    filters,
+                },
+                limit: 10,
+                direction: PaginationDirection::Older,
+            })
+            .await?;
+        let actual_resp: Vec<TxHash> = items.unwrap().into_iter().map(|tx| tx.tx_hash).collect();
+        assert_eq!(actual_resp, expected_resp, "\"{}\", failed", test_name);
+    }
+
+    Ok(())
+}
+
+/// Checks that `ChangePubKey` transactions of the account are loaded with their statuses.
+#[db_test]
+async fn get_account_pubkey_changes(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let from = setup.from_zksync_account.address;
+    let to = setup.to_zksync_account.address;
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+    commit_block(&mut storage, BlockNumber(1)).await?;
+    commit_block(&mut storage, BlockNumber(2)).await?;
+    verify_block(&mut storage, BlockNumber(1)).await?;
+
+    let changes = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_pubkey_changes(from)
+        .await?;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].tx_hash, setup.get_tx_hash(0, 4));
+    assert_eq!(changes[0].block_number, BlockNumber(1));
+    assert_eq!(changes[0].status, TxInBlockStatus::Finalized);
+    assert_eq!(changes[1].tx_hash, setup.get_tx_hash(1, 4));
+    assert_eq!(changes[1].block_number, BlockNumber(2));
+    assert_eq!(changes[1].status, TxInBlockStatus::Committed);
+    for change in &changes {
+        assert_eq!(change.auth_type, ChangePubKeyType::ECDSA);
+        assert_eq!(
+            change.new_pub_key_hash,
+            setup.from_zksync_account.pubkey_hash
+        );
+    }
+
+    let changes = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_pubkey_changes(to)
+        .await?;
+    assert!(changes.is_empty());
+
+    Ok(())
+}
+
+/// Checks that fees paid by the account are grouped by the transaction type and fee token.
+#[db_test]
+async fn get_account_fees(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let from = setup.from_zksync_account.address;
+    let to = setup.to_zksync_account.address;
+    setup.add_block(1);
+    setup.add_block(2);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let fees = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_fees(from)
+        .await?;
+    let fees: Vec<_> = fees
+        .into_iter()
+        .map(|fee| (fee.tx_type, fee.token_id))
+        .collect();
+    // `TransferToNew` is stored as a `Transfer` and `Close` has no fee.
+    let expected = vec![
+        ("ChangePubKey".to_string(), TokenId(0)),
+        ("MintNFT".to_string(), TokenId(0)),
+        ("Swap".to_string(), TokenId(0)),
+        ("Transfer".to_string(), TokenId(1)),
+        ("Withdraw".to_string(), TokenId(2)),
+        ("WithdrawNFT".to_string(), TokenId(0)),
+    ];
+    assert_eq!(fees, expected);
+
+    let fees = storage
+        .chain()
+        .operations_ext_schema()
+        .get_account_fees(to)
+        .await?;
+    assert!(fees.is_empty());
+
+    Ok(())
+}
+
 /// Test `get_tx_created_at_and_block_number` method
 #[db_test]
 async fn get_tx_sequnecner_id(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -711,9 +904,30 @@ async fn get_batch_info(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     assert_eq!(actual_tx_hashes, tx_hashes);
     assert_eq!(batch_info.batch_status.last_state, TxInBlockStatus::Queued);
 
+    // Transaction data should contain the context of the batch.
+    let tx_data = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_data_api_v02(setup.get_tx_hash(0, 1).as_ref())
+        .await?
+        .unwrap();
+    let batch_context = tx_data.batch.unwrap();
+    let context_tx_hashes: Vec<TxHash> = batch_context
+        .transaction_hashes
+        .into_iter()
+        .map(|tx_hash| tx_hash.0)
+        .collect();
+    assert_eq!(batch_context.batch_hash, batch_hash);
+    assert_eq!(context_tx_hashes, tx_hashes);
+    assert!(batch_context.eth_signatures.is_empty());
+
     // Get batch from queued block.
     commit_schema_data(&mut storage, &setup).await?;
-    storage.chain().mempool_schema().collect_garbage().await?;
+    storage
+        .chain()
+        .mempool_schema()
+        .remove_txs(&tx_hashes)
+        .await?;
 
     let batch_info = storage
         .chain()
@@ -792,7 +1006,12 @@ async fn account_transactions_count(mut storage: StorageProcessor<'_>) -> QueryR
     let count_after_saving = storage
         .chain()
         .operations_ext_schema()
-        .get_account_transactions_count(setup.from_zksync_account.address, None, None)
+        .get_account_transactions_count(
+            setup.from_zksync_account.address,
+            None,
+            None,
+            &Default::default(),
+        )
         .await?;
     assert_eq!(count_after_saving, 10);
 
@@ -867,8 +1086,12 @@ async fn tx_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     commit_schema_data(&mut storage, &setup).await?;
 
     // Test receipt for L1 op.
-    let (expected_id, eth_hash) = match setup.blocks[0].block_transactions[0].clone() {
-        ExecutedOperations::PriorityOp(op) => (op.priority_op.serial_id, op.priority_op.eth_hash),
+    let (expected_id, eth_hash, eth_block) = match setup.blocks[0].block_transactions[0].clone() {
+        ExecutedOperations::PriorityOp(op) => (
+            op.priority_op.serial_id,
+            op.priority_op.eth_hash,
+            op.priority_op.eth_block,
+        ),
         ExecutedOperations::Tx(_) => {
             panic!("Should be L1 op")
         }
@@ -882,12 +1105,19 @@ async fn tx_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     match l1_receipt_by_tx_hash.unwrap() {
         Receipt::L1(receipt) => {
             assert_eq!(receipt.id, expected_id);
+            // The Ethereum watcher has not stored its head yet.
+            assert!(receipt.confirmations.is_none());
         }
         Receipt::L2(_) => {
             panic!("Should be L1 receipt");
         }
     }
 
+    storage
+        .chain()
+        .mempool_schema()
+        .update_last_watched_eth_block(eth_block + 5)
+        .await?;
     let l1_receipt_by_eth_hash = storage
         .chain()
         .operations_ext_schema()
@@ -896,6 +1126,7 @@ async fn tx_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     match l1_receipt_by_eth_hash.unwrap() {
         Receipt::L1(receipt) => {
             assert_eq!(receipt.id, expected_id);
+            assert_eq!(receipt.confirmations, Some(5));
         }
         Receipt::L2(_) => {
             panic!("Should be L1 receipt");
@@ -943,6 +1174,84 @@ async fn tx_receipt(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
     Ok(())
 }
 
+/// Test `tx_receipts_api_v02` method
+#[db_test]
+async fn tx_receipts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    let (expected_id, eth_hash) = match setup.blocks[0].block_transactions[0].clone() {
+        ExecutedOperations::PriorityOp(op) => (op.priority_op.serial_id, op.priority_op.eth_hash),
+        ExecutedOperations::Tx(_) => {
+            panic!("Should be L1 op")
+        }
+    };
+    let eth_hash = TxHash::from_slice(eth_hash.as_bytes()).unwrap();
+
+    setup.add_block(2);
+    let mempool_tx = match setup.blocks[1].block_transactions[2].clone() {
+        ExecutedOperations::Tx(tx) => tx.signed_tx,
+        ExecutedOperations::PriorityOp(_) => {
+            panic!("Should be L2 tx")
+        }
+    };
+    storage
+        .chain()
+        .mempool_schema()
+        .insert_tx(&mempool_tx)
+        .await?;
+
+    let unknown_hash = TxHash::from_slice(&[0xDE; 32]).unwrap();
+    let receipts = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipts_api_v02(&[
+            eth_hash,
+            setup.get_tx_hash(0, 2),
+            mempool_tx.hash(),
+            unknown_hash,
+        ])
+        .await?;
+
+    // Unknown hashes should not be present in the result.
+    assert_eq!(receipts.len(), 3);
+    assert!(!receipts.contains_key(&unknown_hash));
+
+    match &receipts[&eth_hash] {
+        Receipt::L1(receipt) => {
+            assert_eq!(receipt.id, expected_id);
+            assert_eq!(receipt.block_index, Some(0));
+        }
+        Receipt::L2(_) => panic!("Should be L1 receipt"),
+    }
+    match &receipts[&setup.get_tx_hash(0, 2)] {
+        Receipt::L2(receipt) => {
+            assert_eq!(receipt.tx_hash, setup.get_tx_hash(0, 2));
+            assert_eq!(receipt.status, TxInBlockStatus::Committed);
+            assert_eq!(receipt.block_index, Some(2));
+            assert!(receipt.fee.is_some());
+            assert!(receipt.fee_token.is_some());
+            assert!(receipt.created_at.is_some());
+            assert!(receipt.finalized_at.is_none());
+            // No blocks were executed, so there is no data to estimate the finalization time.
+            assert!(receipt.estimated_finalization.is_none());
+        }
+        Receipt::L1(_) => panic!("Should be L2 receipt"),
+    }
+    match &receipts[&mempool_tx.hash()] {
+        Receipt::L2(receipt) => {
+            assert_eq!(receipt.status, TxInBlockStatus::Queued);
+            assert!(receipt.fee.is_none());
+            assert!(receipt.created_at.is_some());
+            assert!(receipt.committed_at.is_none());
+        }
+        Receipt::L1(_) => panic!("Should be L2 receipt"),
+    }
+
+    Ok(())
+}
+
 /// Test `tx_data_api_v02` method
 #[db_test]
 async fn tx_data(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -971,11 +1280,11 @@ async fn tx_data(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         .chain()
         .operations_ext_schema()
         .tx_data_api_v02(setup.get_tx_hash(0, 0).as_ref())
-        .await?;
-    assert_eq!(
-        l1_data_by_tx_hash.unwrap().tx.tx_hash,
-        setup.get_tx_hash(0, 0)
-    );
+        .await?
+        .unwrap();
+    assert_eq!(l1_data_by_tx_hash.tx.tx_hash, setup.get_tx_hash(0, 0));
+    // Priority operations don't have the validity window.
+    assert!(l1_data_by_tx_hash.tx.time_range.is_none());
 
     let l1_data_by_eth_hash = storage
         .chain()
@@ -997,19 +1306,72 @@ async fn tx_data(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     // Test data for tx from mempool.
     setup.add_block(2);
-    let tx = match setup.blocks[1].block_transactions[2].clone() {
+    let mut tx = match setup.blocks[1].block_transactions[2].clone() {
         ExecutedOperations::Tx(tx) => tx.signed_tx,
         ExecutedOperations::PriorityOp(_) => {
             panic!("Should be L2 tx")
         }
     };
+    let eth_sign_data = gen_eth_sign_data("Transfer 1 ETH".to_string());
+    tx.eth_sign_data = Some(eth_sign_data.clone());
     storage.chain().mempool_schema().insert_tx(&tx).await?;
     let l2_data = storage
         .chain()
         .operations_ext_schema()
         .tx_data_api_v02(tx.hash().as_ref())
+        .await?
+        .unwrap();
+    assert_eq!(l2_data.tx.tx_hash, tx.hash());
+    assert_eq!(
+        l2_data.eth_signature,
+        Some(eth_sign_data.signature.to_string())
+    );
+    assert_eq!(l2_data.eth_sign_payload.as_deref(), Some("Transfer 1 ETH"));
+    let time_range = l2_data.tx.time_range.unwrap();
+    assert_eq!(time_range.valid_from, tx.tx.time_range().valid_from);
+    assert_eq!(time_range.valid_until, tx.tx.time_range().valid_until);
+
+    Ok(())
+}
+
+/// Test `get_signed_tx` method
+#[db_test]
+async fn get_signed_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    setup.add_block(1);
+    commit_schema_data(&mut storage, &setup).await?;
+
+    // Priority operations are not signed L2 transactions.
+    let signed_tx = storage
+        .chain()
+        .operations_ext_schema()
+        .get_signed_tx(setup.get_tx_hash(0, 0).as_ref())
         .await?;
-    assert_eq!(l2_data.unwrap().tx.tx_hash, tx.hash());
+    assert!(signed_tx.is_none());
+
+    // Executed L2 transaction.
+    let signed_tx = storage
+        .chain()
+        .operations_ext_schema()
+        .get_signed_tx(setup.get_tx_hash(0, 2).as_ref())
+        .await?;
+    assert_eq!(signed_tx.unwrap().tx.hash(), setup.get_tx_hash(0, 2));
+
+    // Transaction from mempool.
+    setup.add_block(2);
+    let tx = match setup.blocks[1].block_transactions[2].clone() {
+        ExecutedOperations::Tx(tx) => tx.signed_tx,
+        ExecutedOperations::PriorityOp(_) => {
+            panic!("Should be L2 tx")
+        }
+    };
+    storage.chain().mempool_schema().insert_tx(&tx).await?;
+    let signed_tx = storage
+        .chain()
+        .operations_ext_schema()
+        .get_signed_tx(tx.hash().as_ref())
+        .await?;
+    assert_eq!(signed_tx.unwrap().tx.get_bytes(), tx.tx.get_bytes());
 
     Ok(())
 }
@@ -1234,6 +1596,7 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
                     tx_hash: ApiEither::from(tx_hash),
                     token: None,
                     second_address: None,
+                    filters: Default::default(),
                 },
                 limit: 1,
                 direction: PaginationDirection::Older,
@@ -1256,3 +1619,70 @@ async fn test_getting_swap_for_acc(mut storage: StorageProcessor<'_>) -> QueryRe
 
     Ok(())
 }
+
+/// Checks that the receipt of a swap contains both orders, and that the swaps stored without
+/// the order hashes can be found by them after the backfill.
+#[db_test]
+async fn swap_receipt_and_orders_backfill(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut setup = TransactionsHistoryTestSetup::new();
+    let swap_op = setup.create_swap_tx_with_random_recipients(Some(0));
+    let executed_tx = swap_op.get_executed_tx().unwrap().clone();
+    let tx_hash = executed_tx.signed_tx.tx.hash();
+    let swap = match &executed_tx.signed_tx.tx {
+        ZkSyncTx::Swap(swap) => swap.as_ref().clone(),
+        _ => unreachable!(),
+    };
+
+    // Store the swap the way it was stored before the order hashes were introduced.
+    let mut stored_tx =
+        NewExecutedTransaction::prepare_stored_tx(executed_tx, BlockNumber(1), &mut storage)
+            .await?;
+    stored_tx.order_hashes.clear();
+    OperationsSchema(&mut storage)
+        .store_executed_tx(stored_tx)
+        .await?;
+
+    let receipt = storage
+        .chain()
+        .operations_ext_schema()
+        .tx_receipt_api_v02(tx_hash.as_ref())
+        .await?
+        .unwrap();
+    match receipt {
+        Receipt::L2(receipt) => assert_eq!(receipt.swap, Some(SwapReceipt::from(&swap))),
+        Receipt::L1(_) => panic!("Should be L2 receipt"),
+    }
+
+    let order_hash = swap.orders.0.hash();
+    let found = storage
+        .chain()
+        .operations_ext_schema()
+        .get_swap_tx_hash_by_order_hash(order_hash.as_ref())
+        .await?;
+    assert!(found.is_none());
+
+    let last_sequence_number = storage
+        .chain()
+        .operations_ext_schema()
+        .backfill_swap_orders(0, 10)
+        .await?;
+    assert!(last_sequence_number.is_some());
+    for order in [&swap.orders.0, &swap.orders.1] {
+        let found = storage
+            .chain()
+            .operations_ext_schema()
+            .get_swap_tx_hash_by_order_hash(order.hash().as_ref())
+            .await?;
+        assert_eq!(found, Some(tx_hash));
+    }
+
+    // There are no swaps left after the last processed one.
+    let next = storage
+        .chain()
+        .operations_ext_schema()
+        .backfill_swap_orders(last_sequence_number.unwrap(), 10)
+        .await?;
+    assert!(next.is_none());
+
+    Ok(())
+}
@@ -399,3 +399,75 @@ async fn test_update_eth_parameters(mut storage: StorageProcessor<'_>) -> QueryR
 
     Ok(())
 }
+
+/// Checks that the gas used by the confirmed transaction is returned
+/// together with the Ethereum transactions of the blocks, and that only
+/// confirmed transactions affect the average paid gas price.
+#[db_test]
+async fn ethereum_gas_used(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    EthereumSchema(&mut storage).initialize_eth_data().await?;
+
+    let block_number = BlockNumber(1);
+    OperationsSchema(&mut storage)
+        .store_aggregated_action(gen_unique_aggregated_operation(
+            block_number,
+            AggregatedActionType::CommitBlocks,
+            BLOCK_SIZE_CHUNKS,
+        ))
+        .await?;
+    let commit_operation = OperationsSchema(&mut storage)
+        .get_aggregated_op_that_affects_block(AggregatedActionType::CommitBlocks, block_number)
+        .await?;
+
+    let params = EthereumTxParams::new("CommitBlocks".into(), commit_operation);
+    let response = EthereumSchema(&mut storage)
+        .save_new_eth_tx(
+            AggregatedActionType::CommitBlocks,
+            params.op.clone(),
+            params.deadline_block as i64,
+            params.gas_price.clone(),
+            params.raw_tx.clone(),
+        )
+        .await?;
+    EthereumSchema(&mut storage)
+        .add_hash_entry(response.id, &params.hash)
+        .await?;
+
+    // The transaction isn't confirmed yet.
+    let txs = OperationsSchema(&mut storage)
+        .get_aggregated_eth_txs_for_blocks(block_number, block_number)
+        .await?;
+    assert!(txs.is_empty());
+    let paid_gas_price = EthereumSchema(&mut storage)
+        .load_average_paid_gas_price(10)
+        .await?;
+    assert!(paid_gas_price.is_none());
+
+    EthereumSchema(&mut storage)
+        .confirm_eth_tx(&params.hash)
+        .await?;
+    EthereumSchema(&mut storage)
+        .save_eth_tx_gas_used(&params.hash, 150_000)
+        .await?;
+
+    let paid_gas_price = EthereumSchema(&mut storage)
+        .load_average_paid_gas_price(10)
+        .await?;
+    assert_eq!(paid_gas_price, Some(params.gas_price.clone()));
+
+    let txs = OperationsSchema(&mut storage)
+        .get_aggregated_eth_txs_for_blocks(block_number, block_number)
+        .await?;
+    assert_eq!(txs.len(), 1);
+    assert_eq!(txs[0].action_type, "CommitBlocks");
+    assert_eq!(txs[0].tx_hash, params.hash.as_bytes().to_vec());
+    assert_eq!(txs[0].gas_used, Some(150_000));
+
+    // The next block isn't covered by the operation.
+    let txs = OperationsSchema(&mut storage)
+        .get_aggregated_eth_txs_for_blocks(block_number + 1, block_number + 1)
+        .await?;
+    assert!(txs.is_empty());
+
+    Ok(())
+}
@@ -211,7 +211,10 @@ async fn test_block_events(mut storage: StorageProcessor<'_>) -> QueryResult<()>
 
 fn check_account_event(event: &ZkSyncEvent, status: AccountStateChangeStatus) -> bool {
     match &event.data {
-        EventData::Account(account_event) => account_event.status == status,
+        // Every account in the test is created, so its address should be known.
+        EventData::Account(account_event) => {
+            account_event.status == status && account_event.update_details.address.is_some()
+        }
         _ => false,
     }
 }
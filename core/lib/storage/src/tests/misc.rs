@@ -1,8 +1,15 @@
+use chrono::{Duration, Utc};
+use num::{rational::Ratio, BigUint};
 use sqlx::types::BigDecimal;
-use zksync_types::TokenId;
+use zksync_types::{
+    tx::TxHash, AccountId, Address, AddressList, Fee, FeeExemption, OutputFeeType, TokenId,
+};
 
 use crate::tests::db_test;
-use crate::{misc::records::Subsidy, misc::MiscSchema};
+use crate::{
+    misc::records::{RateLimitTokens, ReservedTxRequestId, Subsidy},
+    misc::MiscSchema,
+};
 use crate::{QueryResult, StorageProcessor};
 
 fn get_subsidy(name: String, value: u64) -> Subsidy {
@@ -54,3 +61,445 @@ async fn stored_subsidy(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
 
     Ok(())
 }
+
+/// Checks the subsidy budget and the subsidy totals.
+#[db_test]
+async fn subsidy_budget_and_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let subsidy_name = "subsidy";
+
+    let budget = MiscSchema(&mut storage)
+        .get_subsidy_budget(subsidy_name)
+        .await?;
+    assert!(budget.is_none());
+
+    MiscSchema(&mut storage)
+        .set_subsidy_budget(subsidy_name, 1000)
+        .await?;
+    MiscSchema(&mut storage)
+        .set_subsidy_budget(subsidy_name, 2000)
+        .await?;
+    let budget = MiscSchema(&mut storage)
+        .get_subsidy_budget(subsidy_name)
+        .await?;
+    assert_eq!(budget, Some(2000));
+
+    let stats = MiscSchema(&mut storage)
+        .get_subsidy_stats(subsidy_name)
+        .await?;
+    assert_eq!(stats.used_usd_scaled, BigDecimal::from(0));
+    assert_eq!(stats.subsidized_txs, 0);
+
+    MiscSchema(&mut storage)
+        .store_subsidy(get_subsidy(subsidy_name.to_string(), 10))
+        .await?;
+    MiscSchema(&mut storage)
+        .store_subsidy(get_subsidy(subsidy_name.to_string(), 15))
+        .await?;
+    MiscSchema(&mut storage)
+        .store_subsidy(get_subsidy("another_subsidy".to_string(), 45))
+        .await?;
+    let stats = MiscSchema(&mut storage)
+        .get_subsidy_stats(subsidy_name)
+        .await?;
+    assert_eq!(stats.used_usd_scaled, BigDecimal::from(25));
+    assert_eq!(stats.full_cost_usd_scaled, BigDecimal::from(50));
+    assert_eq!(stats.subsidized_txs, 2);
+
+    Ok(())
+}
+
+/// Checks that the request ID is reserved for the first transaction of the account,
+/// that it can be released or taken over if abandoned, and that it can be reused once expired.
+#[db_test]
+async fn reserved_tx_request_id(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let request_id = "request_1";
+    let tx_hash = TxHash::from_slice(&[1; 32]).unwrap();
+    let another_tx_hash = TxHash::from_slice(&[2; 32]).unwrap();
+    let pending_before = Utc::now() - Duration::minutes(5);
+
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(AccountId(1), request_id, tx_hash, pending_before)
+        .await?;
+    assert!(reserved.is_none());
+
+    // Reserving the same request ID again should return the original transaction hash.
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(AccountId(1), request_id, another_tx_hash, pending_before)
+        .await?;
+    assert_eq!(
+        reserved,
+        Some(ReservedTxRequestId {
+            tx_hash,
+            submitted: false
+        })
+    );
+
+    // Released request ID can be reserved for another transaction.
+    MiscSchema(&mut storage)
+        .release_tx_request_id(AccountId(1), request_id)
+        .await?;
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(AccountId(1), request_id, tx_hash, pending_before)
+        .await?;
+    assert!(reserved.is_none());
+
+    // Abandoned reservation is taken over.
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(
+            AccountId(1),
+            request_id,
+            another_tx_hash,
+            Utc::now() + Duration::minutes(5),
+        )
+        .await?;
+    assert!(reserved.is_none());
+
+    // Submitted transaction keeps the request ID, it can't be released.
+    MiscSchema(&mut storage)
+        .mark_tx_request_id_submitted(AccountId(1), request_id)
+        .await?;
+    MiscSchema(&mut storage)
+        .release_tx_request_id(AccountId(1), request_id)
+        .await?;
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(
+            AccountId(1),
+            request_id,
+            tx_hash,
+            Utc::now() + Duration::minutes(5),
+        )
+        .await?;
+    assert_eq!(
+        reserved,
+        Some(ReservedTxRequestId {
+            tx_hash: another_tx_hash,
+            submitted: true
+        })
+    );
+
+    // Request IDs of different accounts don't interfere.
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(AccountId(2), request_id, another_tx_hash, pending_before)
+        .await?;
+    assert!(reserved.is_none());
+
+    let removed = MiscSchema(&mut storage)
+        .remove_expired_tx_request_ids(Utc::now() - Duration::hours(1))
+        .await?;
+    assert_eq!(removed, 0);
+    let removed = MiscSchema(&mut storage)
+        .remove_expired_tx_request_ids(Utc::now() + Duration::hours(1))
+        .await?;
+    assert_eq!(removed, 2);
+    let reserved = MiscSchema(&mut storage)
+        .reserve_tx_request_id(AccountId(1), request_id, another_tx_hash, pending_before)
+        .await?;
+    assert!(reserved.is_none());
+
+    Ok(())
+}
+
+/// Checks that the resolved ENS names are cached and the stale entries are not returned.
+#[db_test]
+async fn stored_ens_name(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let name = "zksync.eth";
+    let address = Address::repeat_byte(0x01);
+    let another_address = Address::repeat_byte(0x02);
+    let hour_ago = Utc::now() - Duration::hours(1);
+
+    let stored_address = MiscSchema(&mut storage)
+        .get_ens_name_address(name, hour_ago)
+        .await?;
+    assert!(stored_address.is_none());
+
+    MiscSchema(&mut storage)
+        .store_ens_name(name, address)
+        .await?;
+    let stored_address = MiscSchema(&mut storage)
+        .get_ens_name_address(name, hour_ago)
+        .await?;
+    assert_eq!(stored_address, Some(address));
+
+    // Resolving the name again should overwrite the cached address.
+    MiscSchema(&mut storage)
+        .store_ens_name(name, another_address)
+        .await?;
+    let stored_address = MiscSchema(&mut storage)
+        .get_ens_name_address(name, hour_ago)
+        .await?;
+    assert_eq!(stored_address, Some(another_address));
+
+    // Entries resolved before the given time are considered stale.
+    let stored_address = MiscSchema(&mut storage)
+        .get_ens_name_address(name, Utc::now() + Duration::hours(1))
+        .await?;
+    assert!(stored_address.is_none());
+
+    Ok(())
+}
+
+/// Checks that the quoted fees can be loaded and filtered by the fee type, token and time,
+/// and that the old quotes are removed.
+#[db_test]
+async fn stored_fee_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let fee = |fee_type| {
+        Fee::new(
+            fee_type,
+            Ratio::from_integer(BigUint::from(1_000_000u32)),
+            Ratio::from_integer(BigUint::from(2_000_000u32)),
+            BigUint::from(10_000u32),
+            BigUint::from(100u32),
+        )
+    };
+    let token_price = Ratio::new(BigUint::from(3u32), BigUint::from(2u32));
+    let hour_ago = Utc::now() - Duration::hours(1);
+    let hour_later = Utc::now() + Duration::hours(1);
+
+    MiscSchema(&mut storage)
+        .store_fee_quote(TokenId(0), &fee(OutputFeeType::Transfer), &token_price)
+        .await?;
+    MiscSchema(&mut storage)
+        .store_fee_quote(TokenId(1), &fee(OutputFeeType::Transfer), &token_price)
+        .await?;
+    MiscSchema(&mut storage)
+        .store_fee_quote(TokenId(0), &fee(OutputFeeType::Withdraw), &token_price)
+        .await?;
+
+    let quotes = MiscSchema(&mut storage)
+        .load_fee_history(None, None, hour_ago, hour_later, 10)
+        .await?;
+    assert_eq!(quotes.len(), 3);
+    assert_eq!(quotes[0].tx_type, "Transfer");
+    assert_eq!(
+        quotes[0].token_price_usd,
+        BigDecimal::from(3) / BigDecimal::from(2)
+    );
+    assert_eq!(quotes[0].gas_price_wei, BigDecimal::from(100));
+
+    let quotes = MiscSchema(&mut storage)
+        .load_fee_history(Some("Transfer"), None, hour_ago, hour_later, 10)
+        .await?;
+    assert_eq!(quotes.len(), 2);
+
+    let quotes = MiscSchema(&mut storage)
+        .load_fee_history(Some("Transfer"), Some(TokenId(1)), hour_ago, hour_later, 10)
+        .await?;
+    assert_eq!(quotes.len(), 1);
+    assert_eq!(quotes[0].token_id, 1);
+
+    let quotes = MiscSchema(&mut storage)
+        .load_fee_history(None, None, hour_ago, hour_later, 2)
+        .await?;
+    assert_eq!(quotes.len(), 2);
+
+    let quotes = MiscSchema(&mut storage)
+        .load_fee_history(None, None, hour_later, hour_later + Duration::hours(1), 10)
+        .await?;
+    assert!(quotes.is_empty());
+
+    let removed = MiscSchema(&mut storage)
+        .remove_old_fee_quotes(hour_ago)
+        .await?;
+    assert_eq!(removed, 0);
+    let removed = MiscSchema(&mut storage)
+        .remove_old_fee_quotes(hour_later)
+        .await?;
+    assert_eq!(removed, 3);
+
+    Ok(())
+}
+
+/// Checks that the rate limit buckets are created on the first use, updated and removed once stale.
+#[db_test]
+async fn rate_limit_buckets(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let ip_key = "ip:127.0.0.1";
+    let account_key = "account:0x0000000000000000000000000000000000000000";
+    let tokens = |key: &str, taken: f64| RateLimitTokens {
+        key: key.to_string(),
+        capacity: 10.0,
+        // Refill is negligible during the test.
+        refill_per_sec: 1e-6,
+        taken,
+    };
+
+    let buckets = MiscSchema(&mut storage)
+        .take_rate_limit_tokens(&[tokens(ip_key, 1.0)])
+        .await?;
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0].key, ip_key);
+    assert!((buckets[0].tokens - 9.0).abs() < 1e-3);
+
+    // Existing bucket is not reset, the new one is created full.
+    let mut buckets = MiscSchema(&mut storage)
+        .take_rate_limit_tokens(&[tokens(ip_key, 2.0), tokens(account_key, 0.0)])
+        .await?;
+    buckets.sort_by(|lhs, rhs| lhs.key.cmp(&rhs.key));
+    assert_eq!(buckets[0].key, account_key);
+    assert!((buckets[0].tokens - 10.0).abs() < 1e-3);
+    assert_eq!(buckets[1].key, ip_key);
+    assert!((buckets[1].tokens - 7.0).abs() < 1e-3);
+
+    // Bucket is never overdrawn.
+    let buckets = MiscSchema(&mut storage)
+        .take_rate_limit_tokens(&[tokens(ip_key, 100.0)])
+        .await?;
+    assert_eq!(buckets[0].tokens, 0.0);
+
+    let removed = MiscSchema(&mut storage)
+        .remove_stale_rate_limit_buckets(Utc::now() + Duration::minutes(1))
+        .await?;
+    assert_eq!(removed, 2);
+    let buckets = MiscSchema(&mut storage)
+        .take_rate_limit_tokens(&[tokens(ip_key, 0.0)])
+        .await?;
+    assert_eq!(buckets[0].tokens, 10.0);
+
+    Ok(())
+}
+
+/// Checks that the fee exemptions can be added and removed, and their usages are logged.
+#[db_test]
+async fn fee_exemptions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sender = Address::random();
+    let sender_exemption = FeeExemption::Sender(sender);
+    let tx_type_exemption = FeeExemption::TxType("ForcedExit".to_string());
+
+    MiscSchema(&mut storage)
+        .add_fee_exemption(&sender_exemption, "operator")
+        .await?;
+    MiscSchema(&mut storage)
+        .add_fee_exemption(&tx_type_exemption, "admin")
+        .await?;
+    // Adding the same exemption again does nothing.
+    MiscSchema(&mut storage)
+        .add_fee_exemption(&tx_type_exemption, "operator")
+        .await?;
+
+    let exemptions = MiscSchema(&mut storage).load_fee_exemptions().await?;
+    assert_eq!(exemptions.len(), 2);
+    assert_eq!(exemptions[0].exemption, sender_exemption);
+    assert_eq!(exemptions[0].added_by, "operator");
+    assert_eq!(exemptions[1].exemption, tx_type_exemption);
+    assert_eq!(exemptions[1].added_by, "admin");
+
+    let tx_hash = TxHash::from_slice(&[1u8; 32]).unwrap();
+    MiscSchema(&mut storage)
+        .store_fee_exemption_usage(tx_hash, sender, "Transfer", &sender_exemption)
+        .await?;
+    let usages = MiscSchema(&mut storage)
+        .load_fee_exemption_usages(10)
+        .await?;
+    assert_eq!(usages.len(), 1);
+    assert_eq!(usages[0].tx_hash, tx_hash);
+    assert_eq!(usages[0].sender, sender);
+    assert_eq!(usages[0].tx_type, "Transfer");
+    assert_eq!(usages[0].exemption, sender_exemption);
+
+    assert!(
+        MiscSchema(&mut storage)
+            .remove_fee_exemption(&sender_exemption)
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .remove_fee_exemption(&sender_exemption)
+            .await?
+    );
+    let exemptions = MiscSchema(&mut storage).load_fee_exemptions().await?;
+    assert_eq!(exemptions.len(), 1);
+
+    Ok(())
+}
+
+/// Checks that the blocked addresses are matched against the list they are added to.
+#[db_test]
+async fn blocked_addresses(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let sender = Address::random();
+    let recipient = Address::random();
+    MiscSchema(&mut storage)
+        .add_blocked_address(sender, AddressList::Sender, "sanctioned", "admin")
+        .await?;
+    MiscSchema(&mut storage)
+        .add_blocked_address(recipient, AddressList::Recipient, "sanctioned", "admin")
+        .await?;
+    // Adding the address again is a no-op.
+    MiscSchema(&mut storage)
+        .add_blocked_address(sender, AddressList::Sender, "another reason", "admin")
+        .await?;
+
+    let blocked = MiscSchema(&mut storage).load_blocked_addresses().await?;
+    assert_eq!(blocked.len(), 2);
+    assert_eq!(blocked[0].address, recipient);
+    assert_eq!(blocked[0].list, AddressList::Recipient);
+    assert_eq!(blocked[1].address, sender);
+    assert_eq!(blocked[1].reason, "sanctioned");
+    assert_eq!(blocked[1].added_by, "admin");
+
+    // The address is only blocked for the list it was added to.
+    let found = MiscSchema(&mut storage)
+        .find_blocked_addresses(&[recipient], &[sender])
+        .await?;
+    assert!(found.is_empty());
+    let found = MiscSchema(&mut storage)
+        .find_blocked_addresses(&[sender, Address::random()], &[recipient])
+        .await?;
+    assert_eq!(found.len(), 2);
+
+    assert!(
+        MiscSchema(&mut storage)
+            .remove_blocked_address(sender, AddressList::Sender)
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .remove_blocked_address(sender, AddressList::Sender)
+            .await?
+    );
+    let found = MiscSchema(&mut storage)
+        .find_blocked_addresses(&[sender], &[])
+        .await?;
+    assert!(found.is_empty());
+
+    Ok(())
+}
+
+/// Checks that the overrides of the submission settings are replaced and removed.
+#[db_test]
+async fn tx_sender_setting_overrides(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    MiscSchema(&mut storage)
+        .set_tx_sender_setting_override("mempoolCapacity", &serde_json::json!(1000), "admin")
+        .await?;
+    MiscSchema(&mut storage)
+        .set_tx_sender_setting_override("feeTolerancePercent", &serde_json::json!(10), "admin")
+        .await?;
+    // The override is replaced rather than duplicated.
+    MiscSchema(&mut storage)
+        .set_tx_sender_setting_override("mempoolCapacity", &serde_json::json!(500), "operator")
+        .await?;
+
+    let overrides = MiscSchema(&mut storage)
+        .load_tx_sender_setting_overrides()
+        .await?;
+    assert_eq!(overrides.len(), 2);
+    assert_eq!(overrides[0].name, "feeTolerancePercent");
+    assert_eq!(overrides[1].name, "mempoolCapacity");
+    assert_eq!(overrides[1].value, serde_json::json!(500));
+    assert_eq!(overrides[1].updated_by, "operator");
+
+    assert!(
+        MiscSchema(&mut storage)
+            .remove_tx_sender_setting_override("mempoolCapacity")
+            .await?
+    );
+    assert!(
+        !MiscSchema(&mut storage)
+            .remove_tx_sender_setting_override("mempoolCapacity")
+            .await?
+    );
+    let overrides = MiscSchema(&mut storage)
+        .load_tx_sender_setting_overrides()
+        .await?;
+    assert_eq!(overrides.len(), 1);
+
+    Ok(())
+}
@@ -125,6 +125,17 @@ async fn test_store_proof(storage: &mut StorageProcessor<'_>) -> QueryResult<()>
     assert!(loaded_proof.is_some());
     assert!(loaded_aggregated_proof.is_some());
 
+    // The aggregated proof can be found by the block it covers.
+    let (first_block, last_block, _) = ProverSchema(storage)
+        .load_aggregated_proof_for_block(BlockNumber(1))
+        .await?
+        .expect("Aggregated proof should cover the first block");
+    assert_eq!((first_block, last_block), (BlockNumber(1), BlockNumber(1)));
+    assert!(ProverSchema(storage)
+        .load_aggregated_proof_for_block(BlockNumber(2))
+        .await?
+        .is_none());
+
     Ok(())
 }
 
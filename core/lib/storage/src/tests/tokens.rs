@@ -6,8 +6,9 @@ use num::{rational::Ratio, BigUint};
 // Workspace imports
 use zksync_test_account::ZkSyncAccount;
 use zksync_types::{
-    tokens::TokenMarketVolume, AccountId, Address, BlockNumber, ExecutedOperations, ExecutedTx,
-    Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp, H256,
+    tokens::TokenMarketVolume, AccountId, AccountUpdate, Address, BlockNumber, ExecutedOperations,
+    ExecutedTx, Nonce, Token, TokenId, TokenKind, TokenLike, TokenPrice, WithdrawNFTOp, ZkSyncOp,
+    H256, NFT,
 };
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
@@ -15,7 +16,7 @@ use crate::tests::db_test;
 use crate::{
     chain::account::records::StorageMintNFTUpdate,
     diff::StorageAccountDiff,
-    tokens::{TokensSchema, STORED_USD_PRICE_PRECISION},
+    tokens::{ResolveTokenError, TokensSchema, STORED_USD_PRICE_PRECISION},
     QueryResult, StorageProcessor,
 };
 use zksync_crypto::params::MIN_NFT_TOKEN_ID;
@@ -155,7 +156,7 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
 
     storage
         .tokens_schema()
-        .update_historical_ticker_price(TOKEN_ID, price.clone())
+        .update_historical_ticker_price(TOKEN_ID, price.clone(), Some("CoinGecko"))
         .await?;
     // Load it again.
     let loaded = storage
@@ -176,6 +177,73 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
         price.last_updated.timestamp()
     );
 
+    // The price should also be loaded along with the prices of the other tokens.
+    let all_prices = storage
+        .tokens_schema()
+        .load_historical_ticker_prices()
+        .await?;
+    assert_eq!(all_prices[&TOKEN_ID].usd_price, expected_price);
+
+    // The source of the price is stored along with it.
+    let (loaded, source) = storage
+        .tokens_schema()
+        .get_ticker_price_with_source(TOKEN_ID)
+        .await?
+        .expect("couldn't load token price");
+    assert_eq!(loaded.usd_price, expected_price);
+    assert_eq!(source.as_deref(), Some("CoinGecko"));
+
+    Ok(())
+}
+
+/// Checks the store/load routine for `token_price_history` table.
+#[db_test]
+async fn test_token_price_history(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let now = Utc::now();
+
+    let prices: Vec<_> = (0..3)
+        .map(|i| TokenPrice {
+            usd_price: Ratio::from_integer(BigUint::from(100u32 + i)),
+            last_updated: now - chrono::Duration::minutes(10 * (3 - i as i64)),
+        })
+        .collect();
+    for price in &prices {
+        storage
+            .tokens_schema()
+            .store_token_price_history(TOKEN_ID, price)
+            .await?;
+    }
+
+    let history = storage
+        .tokens_schema()
+        .load_token_price_history(TOKEN_ID, now - chrono::Duration::hours(1), now, 10)
+        .await?;
+    assert_eq!(history.len(), 3);
+    for (loaded, expected) in history.iter().zip(&prices) {
+        assert_eq!(loaded.usd_price, expected.usd_price);
+        assert_eq!(
+            loaded.last_updated.timestamp(),
+            expected.last_updated.timestamp()
+        );
+    }
+
+    // Only the prices within the time range are returned.
+    let history = storage
+        .tokens_schema()
+        .load_token_price_history(TOKEN_ID, now - chrono::Duration::minutes(15), now, 10)
+        .await?;
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].usd_price, prices[2].usd_price);
+
+    // The limit is applied to the oldest prices.
+    let history = storage
+        .tokens_schema()
+        .load_token_price_history(TOKEN_ID, now - chrono::Duration::hours(1), now, 2)
+        .await?;
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].usd_price, prices[0].usd_price);
+
     Ok(())
 }
 
@@ -222,6 +290,280 @@ async fn test_market_volume(mut storage: StorageProcessor<'_>) -> QueryResult<()
     Ok(())
 }
 
+/// Checks that the fee token flag is updated and the decisions history is recorded only on changes.
+#[db_test]
+async fn test_fee_token_decisions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let high_volume = Ratio::from_integer(BigUint::from(500u32));
+    let low_volume = Ratio::from_integer(BigUint::from(5u32));
+
+    assert_eq!(storage.tokens_schema().is_fee_token(TOKEN_ID).await?, None);
+
+    // The first decision is always stored.
+    assert!(
+        storage
+            .tokens_schema()
+            .update_fee_token_flag(TOKEN_ID, true, &high_volume)
+            .await?
+    );
+    // The same decision doesn't change anything.
+    assert!(
+        !storage
+            .tokens_schema()
+            .update_fee_token_flag(TOKEN_ID, true, &high_volume)
+            .await?
+    );
+    assert!(
+        storage
+            .tokens_schema()
+            .update_fee_token_flag(TOKEN_ID, false, &low_volume)
+            .await?
+    );
+    assert_eq!(
+        storage.tokens_schema().is_fee_token(TOKEN_ID).await?,
+        Some(false)
+    );
+    let flags = storage
+        .tokens_schema()
+        .load_fee_token_flags(&[TOKEN_ID, TokenId(1)])
+        .await?;
+    assert_eq!(flags.len(), 1);
+    assert_eq!(flags.get(&TOKEN_ID), Some(&false));
+
+    let decisions = storage
+        .tokens_schema()
+        .load_fee_token_decisions(TOKEN_ID, 10)
+        .await?;
+    assert_eq!(decisions.len(), 2);
+    assert!(!decisions[0].is_fee_token);
+    assert_eq!(decisions[0].market_volume, low_volume);
+    assert!(decisions[1].is_fee_token);
+    assert_eq!(decisions[1].market_volume, high_volume);
+
+    let decisions = storage
+        .tokens_schema()
+        .load_fee_token_decisions(TOKEN_ID, 1)
+        .await?;
+    assert_eq!(decisions.len(), 1);
+    assert!(!decisions[0].is_fee_token);
+
+    Ok(())
+}
+
+/// Checks that the manual acceptance of the token overrides the fee token policy and is audited.
+#[db_test]
+async fn test_fee_token_acceptance(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    const TOKEN_ID: TokenId = TokenId(0);
+    let high_volume = Ratio::from_integer(BigUint::from(500u32));
+
+    storage
+        .tokens_schema()
+        .set_fee_token_acceptance(TOKEN_ID, Some(false), "operator")
+        .await?;
+    assert_eq!(
+        storage.tokens_schema().is_fee_token(TOKEN_ID).await?,
+        Some(false)
+    );
+    // The policy can't override the operator decision.
+    assert!(
+        !storage
+            .tokens_schema()
+            .update_fee_token_flag(TOKEN_ID, true, &high_volume)
+            .await?
+    );
+    assert_eq!(
+        storage.tokens_schema().is_fee_token(TOKEN_ID).await?,
+        Some(false)
+    );
+
+    // Once the override is removed, the policy manages the token again.
+    storage
+        .tokens_schema()
+        .set_fee_token_acceptance(TOKEN_ID, None, "admin")
+        .await?;
+    assert!(
+        storage
+            .tokens_schema()
+            .update_fee_token_flag(TOKEN_ID, true, &high_volume)
+            .await?
+    );
+    assert_eq!(
+        storage.tokens_schema().is_fee_token(TOKEN_ID).await?,
+        Some(true)
+    );
+
+    let changes = storage
+        .tokens_schema()
+        .load_fee_token_acceptance_changes(TOKEN_ID, 10)
+        .await?;
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].is_fee_token, None);
+    assert_eq!(changes[0].changed_by, "admin");
+    assert_eq!(changes[1].is_fee_token, Some(false));
+    assert_eq!(changes[1].changed_by, "operator");
+
+    Ok(())
+}
+
+/// Checks that the token symbols matching several tokens are reported as ambiguous.
+#[db_test]
+async fn test_resolve_token(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token_a = Token::new(
+        TokenId(1),
+        Address::from_low_u64_be(1),
+        "ABC",
+        18,
+        TokenKind::ERC20,
+    );
+    let token_b = Token::new(
+        TokenId(2),
+        Address::from_low_u64_be(2),
+        "Abc",
+        6,
+        TokenKind::ERC20,
+    );
+    storage
+        .tokens_schema()
+        .store_or_update_token(token_a.clone())
+        .await?;
+
+    // Symbol lookup is case-insensitive while it's unambiguous.
+    let token = storage
+        .tokens_schema()
+        .resolve_token(TokenLike::Symbol("abc".to_string()))
+        .await?;
+    assert_eq!(token, token_a);
+
+    storage
+        .tokens_schema()
+        .store_or_update_token(token_b.clone())
+        .await?;
+    let err = storage
+        .tokens_schema()
+        .resolve_token(TokenLike::Symbol("ABC".to_string()))
+        .await
+        .unwrap_err();
+    match err {
+        ResolveTokenError::AmbiguousSymbol { candidates, .. } => {
+            assert_eq!(candidates, vec![token_a.id, token_b.id])
+        }
+        err => panic!("Unexpected error: {:?}", err),
+    }
+
+    // Id and address are never ambiguous.
+    let token = storage
+        .tokens_schema()
+        .resolve_token(TokenLike::Address(token_b.address))
+        .await?;
+    assert_eq!(token, token_b);
+    let token = storage
+        .tokens_schema()
+        .resolve_token(TokenLike::Id(token_a.id))
+        .await?;
+    assert_eq!(token, token_a);
+
+    let err = storage
+        .tokens_schema()
+        .resolve_token(TokenLike::Id(TokenId(100)))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ResolveTokenError::NotFound));
+
+    Ok(())
+}
+
+/// Checks that the token metadata conflicts are flagged once and removed after the resolution.
+#[db_test]
+async fn test_token_metadata_conflicts(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token = Token::new(
+        TokenId(1),
+        Address::from_low_u64_be(1),
+        "ABC",
+        18,
+        TokenKind::ERC20,
+    );
+    storage
+        .tokens_schema()
+        .store_or_update_token(token.clone())
+        .await?;
+
+    storage
+        .tokens_schema()
+        .flag_token_metadata_conflict(&token, "ABC", 6)
+        .await?;
+    let conflicts = storage
+        .tokens_schema()
+        .load_token_metadata_conflicts()
+        .await?;
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].token_id, token.id);
+    assert_eq!(conflicts[0].stored_decimals, 18);
+    assert_eq!(conflicts[0].contract_decimals, 6);
+
+    // The repeated detection updates the existing conflict.
+    storage
+        .tokens_schema()
+        .flag_token_metadata_conflict(&token, "ABC", 8)
+        .await?;
+    let updated = storage
+        .tokens_schema()
+        .load_token_metadata_conflicts()
+        .await?;
+    assert_eq!(updated.len(), 1);
+    assert_eq!(updated[0].contract_decimals, 8);
+    assert_eq!(updated[0].detected_at, conflicts[0].detected_at);
+
+    storage
+        .tokens_schema()
+        .remove_token_metadata_conflict(token.id)
+        .await?;
+    assert!(storage
+        .tokens_schema()
+        .load_token_metadata_conflicts()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that the NFT metadata is cached and replaced on the next fetch.
+#[db_test]
+async fn test_nft_metadata(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let token_id = TokenId(MIN_NFT_TOKEN_ID);
+    assert!(storage
+        .tokens_schema()
+        .load_nft_metadata(token_id)
+        .await?
+        .is_none());
+
+    let metadata = serde_json::json!({ "name": "Test NFT" });
+    storage
+        .tokens_schema()
+        .store_nft_metadata(token_id, &metadata)
+        .await?;
+    let stored = storage
+        .tokens_schema()
+        .load_nft_metadata(token_id)
+        .await?
+        .unwrap();
+    assert_eq!(stored.metadata, metadata);
+
+    let metadata = serde_json::json!({ "name": "Updated NFT" });
+    storage
+        .tokens_schema()
+        .store_nft_metadata(token_id, &metadata)
+        .await?;
+    let updated = storage
+        .tokens_schema()
+        .load_nft_metadata(token_id)
+        .await?
+        .unwrap();
+    assert_eq!(updated.metadata, metadata);
+    assert!(updated.fetched_at >= stored.fetched_at);
+
+    Ok(())
+}
+
 /// Checks the store/load factories for nft
 #[db_test]
 async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -342,3 +684,48 @@ async fn test_nfts_with_factories(mut storage: StorageProcessor<'_>) -> QueryRes
 
     Ok(())
 }
+
+/// Checks that the NFT minted by the `MintNFT` transaction can be found by its creator and nonce.
+#[db_test]
+async fn test_minted_nft_id(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let creator_id = AccountId(5);
+    let nonce = Nonce(3);
+    let content_hash = H256::repeat_byte(0x42);
+    let token = NFT::new(
+        TokenId(MIN_NFT_TOKEN_ID + 1),
+        0,
+        creator_id,
+        Address::repeat_byte(0x01),
+        Address::repeat_byte(0x02),
+        None,
+        content_hash,
+    );
+    storage
+        .chain()
+        .state_schema()
+        .commit_state_update(
+            BlockNumber(1),
+            &[(
+                creator_id,
+                AccountUpdate::MintNFT {
+                    token: token.clone(),
+                    nonce,
+                },
+            )],
+            0,
+        )
+        .await?;
+
+    let token_id = TokensSchema(&mut storage)
+        .get_minted_nft_id(creator_id, nonce, content_hash)
+        .await?;
+    assert_eq!(token_id, Some(token.id));
+
+    // The creator didn't mint anything with other nonces.
+    let token_id = TokensSchema(&mut storage)
+        .get_minted_nft_id(creator_id, Nonce(4), content_hash)
+        .await?;
+    assert!(token_id.is_none());
+
+    Ok(())
+}
@@ -2,6 +2,7 @@
 use std::collections::{HashMap, HashSet};
 use std::time::Instant;
 // External imports
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 
 use thiserror::Error;
@@ -10,14 +11,19 @@ use zksync_api_types::v02::{
     pagination::{PaginationDirection, PaginationQuery},
     token::ApiNFT,
 };
-use zksync_types::{AccountId, Address, Token, TokenId, TokenLike, TokenPrice, NFT};
+use zksync_types::{AccountId, Address, Nonce, Token, TokenId, TokenLike, TokenPrice, H256, NFT};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DBMarketVolume, DbTickerPrice, DbToken, StorageApiNFT, StorageNFT, TokenKind};
+use self::records::{
+    DBMarketVolume, DbFeeTokenAcceptanceChange, DbFeeTokenDecision, DbTickerPrice, DbToken,
+    DbTokenMetadataConflict, StorageApiNFT, StorageNFT, StoredNFTMetadata, TokenKind,
+};
 
 use crate::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
-use zksync_types::tokens::TokenMarketVolume;
+use zksync_types::tokens::{
+    FeeTokenAcceptanceChange, FeeTokenDecision, TokenMarketVolume, TokenMetadataConflict,
+};
 
 pub mod records;
 
@@ -37,6 +43,21 @@ pub enum StoreTokenError {
     Other(anyhow::Error),
 }
 
+#[derive(Debug, Error)]
+pub enum ResolveTokenError {
+    #[error("Token not found")]
+    NotFound,
+    #[error(
+        "Token symbol {symbol} matches several tokens with ids {candidates:?}, use the token id or address instead"
+    )]
+    AmbiguousSymbol {
+        symbol: String,
+        candidates: Vec<TokenId>,
+    },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 impl<'a, 'c> TokensSchema<'a, 'c> {
     /// Persists the new token in the database.
     pub async fn store_token(&mut self, token: Token) -> Result<(), StoreTokenError> {
@@ -371,6 +392,32 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
+    /// Returns the ID of the NFT minted by the `MintNFT` transaction with the given
+    /// creator, nonce and content hash.
+    pub async fn get_minted_nft_id(
+        &mut self,
+        creator_id: AccountId,
+        nonce: Nonce,
+        content_hash: H256,
+    ) -> QueryResult<Option<TokenId>> {
+        let start = Instant::now();
+        let token_id = sqlx::query!(
+            r#"
+                SELECT token_id FROM mint_nft_updates
+                WHERE creator_account_id = $1 AND nonce = $2 AND content_hash = $3
+                LIMIT 1
+            "#,
+            *creator_id as i32,
+            *nonce as i64,
+            content_hash.as_bytes()
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| TokenId(record.token_id as u32));
+        metrics::histogram!("sql.token.get_minted_nft_id", start.elapsed());
+        Ok(token_id)
+    }
+
     pub async fn get_nft_with_factories(
         &mut self,
         token_id: TokenId,
@@ -457,6 +504,49 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_token.map(|t| t.into()))
     }
 
+    /// Resolves the token by its id, address or symbol.
+    ///
+    /// Unlike `get_token`, the symbol is required to match exactly one token,
+    /// so a case-insensitive query matching several tokens is reported as ambiguous.
+    pub async fn resolve_token(
+        &mut self,
+        token_like: TokenLike,
+    ) -> Result<Token, ResolveTokenError> {
+        let start = Instant::now();
+
+        let token = match token_like {
+            TokenLike::Symbol(token_symbol) => {
+                let mut tokens = sqlx::query_as!(
+                    DbToken,
+                    r#"
+                    SELECT id, address, decimals, kind as "kind: _", symbol FROM tokens
+                    WHERE lower(symbol) = lower($1)
+                    ORDER BY id
+                    "#,
+                    token_symbol
+                )
+                .fetch_all(self.0.conn())
+                .await
+                .map_err(anyhow::Error::from)?;
+
+                if tokens.len() > 1 {
+                    return Err(ResolveTokenError::AmbiguousSymbol {
+                        symbol: token_symbol,
+                        candidates: tokens
+                            .into_iter()
+                            .map(|token| TokenId(token.id as u32))
+                            .collect(),
+                    });
+                }
+                tokens.pop().map(Token::from)
+            }
+            token_like => self.get_token(token_like).await?,
+        };
+
+        metrics::histogram!("sql.token.resolve_token", start.elapsed());
+        token.ok_or(ResolveTokenError::NotFound)
+    }
+
     pub async fn get_token_market_volume(
         &mut self,
         token_id: TokenId,
@@ -505,6 +595,214 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Returns the current decision of the fee token policy for the given token.
+    /// `None` means that the policy hasn't evaluated the token yet.
+    pub async fn is_fee_token(&mut self, token_id: TokenId) -> QueryResult<Option<bool>> {
+        let start = Instant::now();
+        let is_fee_token = sqlx::query!(
+            "SELECT is_fee_token FROM fee_tokens WHERE token_id = $1",
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?
+        .map(|record| record.is_fee_token);
+
+        metrics::histogram!("sql.token.is_fee_token", start.elapsed());
+        Ok(is_fee_token)
+    }
+
+    /// Loads the decisions of the fee token policy for the given tokens.
+    /// Tokens without a decision are not included in the result.
+    pub async fn load_fee_token_flags(
+        &mut self,
+        tokens: &[TokenId],
+    ) -> QueryResult<HashMap<TokenId, bool>> {
+        let start = Instant::now();
+        let tokens: Vec<i32> = tokens.iter().map(|id| **id as i32).collect();
+        let flags = sqlx::query!(
+            r#"
+            SELECT token_id, is_fee_token FROM fee_tokens
+            WHERE token_id = ANY($1)
+            "#,
+            &tokens
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| (TokenId(record.token_id as u32), record.is_fee_token))
+        .collect();
+
+        metrics::histogram!("sql.token.load_fee_token_flags", start.elapsed());
+        Ok(flags)
+    }
+
+    /// Stores the decision of the fee token policy for the given token.
+    /// The decision is added to the history only if it differs from the current one.
+    /// Tokens with the acceptance set manually by the operator are left untouched.
+    ///
+    /// Returns `true` if the `is_fee_token` flag of the token has changed.
+    pub async fn update_fee_token_flag(
+        &mut self,
+        token_id: TokenId,
+        is_fee_token: bool,
+        market_volume: &Ratio<BigUint>,
+    ) -> QueryResult<bool> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        let current = sqlx::query!(
+            "SELECT is_fee_token, manual FROM fee_tokens WHERE token_id = $1",
+            *token_id as i32
+        )
+        .fetch_optional(transaction.conn())
+        .await?;
+        // Tokens accepted or rejected by the operator are not managed by the policy.
+        if let Some(current) = current {
+            if current.manual || current.is_fee_token == is_fee_token {
+                return Ok(false);
+            }
+        }
+
+        let now = Utc::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_tokens ( token_id, is_fee_token, updated_at )
+            VALUES ( $1, $2, $3 )
+            ON CONFLICT (token_id)
+            DO
+              UPDATE SET is_fee_token = $2, updated_at = $3
+            "#,
+            *token_id as i32,
+            is_fee_token,
+            now
+        )
+        .execute(transaction.conn())
+        .await?;
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_token_decisions ( token_id, is_fee_token, market_volume, created_at )
+            VALUES ( $1, $2, $3, $4 )
+            "#,
+            *token_id as i32,
+            is_fee_token,
+            ratio_to_big_decimal(market_volume, STORED_USD_PRICE_PRECISION),
+            now
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.update_fee_token_flag", start.elapsed());
+        Ok(true)
+    }
+
+    /// Manually accepts or rejects the token for the fee payment, overriding the fee token policy.
+    /// `None` removes the override, so the next policy decision applies to the token again.
+    /// The change is recorded to the audit log along with the one who made it.
+    pub async fn set_fee_token_acceptance(
+        &mut self,
+        token_id: TokenId,
+        is_fee_token: Option<bool>,
+        changed_by: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        match is_fee_token {
+            Some(is_fee_token) => {
+                sqlx::query!(
+                    r#"
+                    INSERT INTO fee_tokens ( token_id, is_fee_token, manual, updated_at )
+                    VALUES ( $1, $2, true, now() )
+                    ON CONFLICT (token_id)
+                    DO
+                      UPDATE SET is_fee_token = $2, manual = true, updated_at = now()
+                    "#,
+                    *token_id as i32,
+                    is_fee_token
+                )
+                .execute(transaction.conn())
+                .await?;
+            }
+            None => {
+                sqlx::query!(
+                    "UPDATE fee_tokens SET manual = false, updated_at = now() WHERE token_id = $1",
+                    *token_id as i32
+                )
+                .execute(transaction.conn())
+                .await?;
+            }
+        }
+        sqlx::query!(
+            r#"
+            INSERT INTO fee_token_acceptance_changes ( token_id, is_fee_token, changed_by, created_at )
+            VALUES ( $1, $2, $3, now() )
+            "#,
+            *token_id as i32,
+            is_fee_token,
+            changed_by
+        )
+        .execute(transaction.conn())
+        .await?;
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.token.set_fee_token_acceptance", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the latest manual changes of the token acceptance for the fee payment, newest first.
+    pub async fn load_fee_token_acceptance_changes(
+        &mut self,
+        token_id: TokenId,
+        limit: u32,
+    ) -> QueryResult<Vec<FeeTokenAcceptanceChange>> {
+        let start = Instant::now();
+        let changes = sqlx::query_as!(
+            DbFeeTokenAcceptanceChange,
+            r#"
+            SELECT * FROM fee_token_acceptance_changes
+            WHERE token_id = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            *token_id as i32,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.token.load_fee_token_acceptance_changes",
+            start.elapsed()
+        );
+        Ok(changes.into_iter().map(Into::into).collect())
+    }
+
+    /// Loads the latest decisions of the fee token policy for the given token, newest first.
+    pub async fn load_fee_token_decisions(
+        &mut self,
+        token_id: TokenId,
+        limit: u32,
+    ) -> QueryResult<Vec<FeeTokenDecision>> {
+        let start = Instant::now();
+        let decisions = sqlx::query_as!(
+            DbFeeTokenDecision,
+            r#"
+            SELECT * FROM fee_token_decisions
+            WHERE token_id = $1
+            ORDER BY id DESC
+            LIMIT $2
+            "#,
+            *token_id as i32,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_fee_token_decisions", start.elapsed());
+        Ok(decisions.into_iter().map(Into::into).collect())
+    }
+
     /// Given token id, returns its price in USD and a timestamp of the last update.
     pub async fn get_historical_ticker_price(
         &mut self,
@@ -527,7 +825,54 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(db_price.map(|p| p.into()))
     }
 
+    /// Loads the stored prices in USD for all the tokens.
+    pub async fn load_historical_ticker_prices(
+        &mut self,
+    ) -> QueryResult<HashMap<TokenId, TokenPrice>> {
+        let start = Instant::now();
+        let prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT * FROM ticker_price
+            "#,
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|price| (TokenId(price.token_id as u32), price.into()))
+        .collect();
+
+        metrics::histogram!("sql.token.load_historical_ticker_prices", start.elapsed());
+        Ok(prices)
+    }
+
+    /// Given token id, returns its price in USD along with the name of the source it was fetched from.
+    /// The source is `None` if it's unknown.
+    pub async fn get_ticker_price_with_source(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<(TokenPrice, Option<String>)>> {
+        let start = Instant::now();
+        let db_price = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT token_id, usd_price, last_updated, source FROM ticker_price
+            WHERE token_id = $1
+            "#,
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.get_ticker_price_with_source", start.elapsed());
+        Ok(db_price.map(|mut price| {
+            let source = price.source.take();
+            (price.into(), source)
+        }))
+    }
+
     /// Updates price in USD for the given token.
+    /// `source` is the name of the price source the price was fetched from.
     ///
     /// Note, that the price precision cannot be greater than `STORED_USD_PRICE_PRECISION`,
     /// so the number might get rounded.
@@ -535,20 +880,22 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         &mut self,
         token_id: TokenId,
         price: TokenPrice,
+        source: Option<&str>,
     ) -> QueryResult<()> {
         let start = Instant::now();
         let usd_price_rounded = ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION);
         sqlx::query!(
             r#"
-            INSERT INTO ticker_price ( token_id, usd_price, last_updated )
-            VALUES ( $1, $2, $3 )
+            INSERT INTO ticker_price ( token_id, usd_price, last_updated, source )
+            VALUES ( $1, $2, $3, $4 )
             ON CONFLICT (token_id)
             DO
-              UPDATE SET usd_price = $2, last_updated = $3
+              UPDATE SET usd_price = $2, last_updated = $3, source = $4
             "#,
             *token_id as i32,
             usd_price_rounded.clone(),
-            price.last_updated
+            price.last_updated,
+            source
         )
         .fetch_optional(self.0.conn())
         .await?;
@@ -557,6 +904,164 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Adds the fetched token price to the prices history.
+    pub async fn store_token_price_history(
+        &mut self,
+        token_id: TokenId,
+        price: &TokenPrice,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_price_history ( token_id, usd_price, last_updated )
+            VALUES ( $1, $2, $3 )
+            "#,
+            *token_id as i32,
+            ratio_to_big_decimal(&price.usd_price, STORED_USD_PRICE_PRECISION),
+            price.last_updated
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_token_price_history", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads the prices of the token updated within the given time range, oldest first.
+    pub async fn load_token_price_history(
+        &mut self,
+        token_id: TokenId,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        limit: u32,
+    ) -> QueryResult<Vec<TokenPrice>> {
+        let start = Instant::now();
+        let prices = sqlx::query_as!(
+            DbTickerPrice,
+            r#"
+            SELECT token_id, usd_price, last_updated FROM token_price_history
+            WHERE token_id = $1 AND last_updated >= $2 AND last_updated <= $3
+            ORDER BY last_updated ASC, id ASC
+            LIMIT $4
+            "#,
+            *token_id as i32,
+            from,
+            to,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_token_price_history", start.elapsed());
+        Ok(prices.into_iter().map(Into::into).collect())
+    }
+
+    /// Flags the mismatch between the stored token metadata and the ERC-20 contract for the operator review.
+    /// The time of the first detection is preserved if the conflict is already flagged.
+    pub async fn flag_token_metadata_conflict(
+        &mut self,
+        token: &Token,
+        contract_symbol: &str,
+        contract_decimals: u8,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO token_metadata_conflicts
+                (token_id, stored_symbol, stored_decimals, contract_symbol, contract_decimals, detected_at)
+            VALUES ($1, $2, $3, $4, $5, now())
+            ON CONFLICT (token_id)
+            DO UPDATE SET stored_symbol = $2, stored_decimals = $3, contract_symbol = $4, contract_decimals = $5
+            "#,
+            *token.id as i32,
+            token.symbol,
+            i16::from(token.decimals),
+            contract_symbol,
+            i16::from(contract_decimals)
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.flag_token_metadata_conflict", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the flagged conflict once the token metadata matches the contract.
+    pub async fn remove_token_metadata_conflict(&mut self, token_id: TokenId) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            "DELETE FROM token_metadata_conflicts WHERE token_id = $1",
+            *token_id as i32
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.remove_token_metadata_conflict", start.elapsed());
+        Ok(())
+    }
+
+    /// Loads all the token metadata conflicts waiting for the operator review.
+    pub async fn load_token_metadata_conflicts(
+        &mut self,
+    ) -> QueryResult<Vec<TokenMetadataConflict>> {
+        let start = Instant::now();
+        let conflicts = sqlx::query_as!(
+            DbTokenMetadataConflict,
+            r#"
+            SELECT token_id, stored_symbol, stored_decimals, contract_symbol, contract_decimals, detected_at
+            FROM token_metadata_conflicts
+            ORDER BY token_id
+            "#
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_token_metadata_conflicts", start.elapsed());
+        Ok(conflicts.into_iter().map(Into::into).collect())
+    }
+
+    /// Loads the cached metadata of the NFT.
+    pub async fn load_nft_metadata(
+        &mut self,
+        token_id: TokenId,
+    ) -> QueryResult<Option<StoredNFTMetadata>> {
+        let start = Instant::now();
+        let metadata = sqlx::query_as!(
+            StoredNFTMetadata,
+            "SELECT token_id, metadata, fetched_at FROM nft_metadata WHERE token_id = $1",
+            *token_id as i32
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_nft_metadata", start.elapsed());
+        Ok(metadata)
+    }
+
+    /// Stores the fetched metadata of the NFT, replacing the previously cached one.
+    pub async fn store_nft_metadata(
+        &mut self,
+        token_id: TokenId,
+        metadata: &serde_json::Value,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        sqlx::query!(
+            r#"
+            INSERT INTO nft_metadata (token_id, metadata, fetched_at)
+            VALUES ($1, $2, now())
+            ON CONFLICT (token_id)
+            DO UPDATE SET metadata = $2, fetched_at = now()
+            "#,
+            *token_id as i32,
+            metadata
+        )
+        .execute(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_nft_metadata", start.elapsed());
+        Ok(())
+    }
+
     pub async fn store_nft_factory(
         &mut self,
         creator_id: AccountId,
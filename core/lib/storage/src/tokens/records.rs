@@ -9,7 +9,10 @@ use crate::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
 use zksync_api_types::v02::token::ApiNFT;
 use zksync_types::{
-    tokens::{TokenMarketVolume, TokenPrice},
+    tokens::{
+        FeeTokenAcceptanceChange, FeeTokenDecision, TokenMarketVolume, TokenMetadataConflict,
+        TokenPrice,
+    },
     AccountId, Address, Token, TokenId, H256, NFT,
 };
 use zksync_utils::big_decimal_to_ratio;
@@ -23,6 +26,14 @@ pub struct DbToken {
     pub kind: TokenKind,
 }
 
+/// NFT metadata cached in the database.
+#[derive(Debug, Clone, PartialEq, FromRow)]
+pub struct StoredNFTMetadata {
+    pub token_id: i32,
+    pub metadata: serde_json::Value,
+    pub fetched_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Copy, PartialEq, sqlx::Type)]
 #[sqlx(type_name = "token_kind")]
 pub enum TokenKind {
@@ -80,6 +91,8 @@ pub struct DbTickerPrice {
     pub token_id: i32,
     pub usd_price: BigDecimal,
     pub last_updated: DateTime<Utc>,
+    /// Name of the price source, `None` for the prices stored before the sources were recorded.
+    pub source: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
@@ -168,3 +181,67 @@ impl From<DBMarketVolume> for TokenMarketVolume {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeTokenDecision {
+    pub id: i64,
+    pub token_id: i32,
+    pub is_fee_token: bool,
+    pub market_volume: BigDecimal,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeTokenAcceptanceChange {
+    pub id: i64,
+    pub token_id: i32,
+    pub is_fee_token: Option<bool>,
+    pub changed_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DbFeeTokenAcceptanceChange> for FeeTokenAcceptanceChange {
+    fn from(val: DbFeeTokenAcceptanceChange) -> Self {
+        Self {
+            token_id: TokenId(val.token_id as u32),
+            is_fee_token: val.is_fee_token,
+            changed_by: val.changed_by,
+            created_at: val.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbTokenMetadataConflict {
+    pub token_id: i32,
+    pub stored_symbol: String,
+    pub stored_decimals: i16,
+    pub contract_symbol: String,
+    pub contract_decimals: i16,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl From<DbTokenMetadataConflict> for TokenMetadataConflict {
+    fn from(val: DbTokenMetadataConflict) -> Self {
+        Self {
+            token_id: TokenId(val.token_id as u32),
+            stored_symbol: val.stored_symbol,
+            stored_decimals: val.stored_decimals as u8,
+            contract_symbol: val.contract_symbol,
+            contract_decimals: val.contract_decimals as u8,
+            detected_at: val.detected_at,
+        }
+    }
+}
+
+impl From<DbFeeTokenDecision> for FeeTokenDecision {
+    fn from(val: DbFeeTokenDecision) -> Self {
+        Self {
+            token_id: TokenId(val.token_id as u32),
+            is_fee_token: val.is_fee_token,
+            market_volume: big_decimal_to_ratio(&val.market_volume)
+                .expect("Market volume could not be negative"),
+            created_at: val.created_at,
+        }
+    }
+}
@@ -3,7 +3,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use tokio::sync::RwLock;
 
-use zksync_storage::StorageProcessor;
+use zksync_storage::{tokens::ResolveTokenError, StorageProcessor};
 use zksync_types::tokens::TokenMarketVolume;
 use zksync_types::{Token, TokenId, TokenLike, NFT};
 
@@ -45,33 +45,48 @@ impl TokenDBCache {
     }
 
     /// Performs case-insensitive token search.
+    ///
+    /// Returns an error if the token symbol matches several tokens.
     pub async fn get_token(
         &self,
         storage: &mut StorageProcessor<'_>,
         token_query: impl Into<TokenLike>,
     ) -> anyhow::Result<Option<Token>> {
+        match self.resolve_token(storage, token_query).await {
+            Ok(token) => Ok(Some(token)),
+            Err(ResolveTokenError::NotFound) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Resolves the token by its id, address or symbol, this is the single place
+    /// where the token identifiers coming from the API are interpreted.
+    ///
+    /// Only unambiguous symbols are stored in the cache, so the case-insensitive
+    /// cache lookup never hides the tokens with the same symbol.
+    pub async fn resolve_token(
+        &self,
+        storage: &mut StorageProcessor<'_>,
+        token_query: impl Into<TokenLike>,
+    ) -> Result<Token, ResolveTokenError> {
         let token_query = token_query.into();
         // Just return token from cache.
         if let Some((token, update_time)) = self.cache.read().await.get(&token_query.to_lowercase())
         {
             if update_time.elapsed() < self.token_invalidate_cache {
-                return Ok(Some(token.clone()));
+                return Ok(token.clone());
             }
         }
         // Tries to fetch token from the underlying database.
-        let token = {
-            storage
-                .tokens_schema()
-                .get_token(token_query.clone())
-                .await?
-        };
+        let token = storage
+            .tokens_schema()
+            .resolve_token(token_query.clone())
+            .await?;
         // Stores received token into the local cache.
-        if let Some(token) = &token {
-            self.cache
-                .write()
-                .await
-                .insert(token_query.to_lowercase(), (token.clone(), Instant::now()));
-        }
+        self.cache
+            .write()
+            .await
+            .insert(token_query.to_lowercase(), (token.clone(), Instant::now()));
 
         Ok(token)
     }
@@ -112,12 +127,22 @@ impl TokenDBCache {
 
     pub async fn fill_token_cache(&mut self, storage: &mut StorageProcessor<'_>) {
         let tokens = Self::get_all_tokens(storage).await.unwrap();
+        // Symbols that differ only in case can't be resolved by the case-insensitive lookup.
+        let mut symbols_count: HashMap<String, usize> = HashMap::new();
+        for token in &tokens {
+            *symbols_count
+                .entry(token.symbol.to_lowercase())
+                .or_default() += 1;
+        }
+
         let mut cache = self.cache.write().await;
         for token in tokens {
             let symbol = TokenLike::Symbol(token.symbol.clone());
             let token_id = TokenLike::Id(token.id);
             let address = TokenLike::Address(token.address);
-            cache.insert(symbol.to_lowercase(), (token.clone(), Instant::now()));
+            if symbols_count[&token.symbol.to_lowercase()] == 1 {
+                cache.insert(symbol.to_lowercase(), (token.clone(), Instant::now()));
+            }
             cache.insert(token_id.to_lowercase(), (token.clone(), Instant::now()));
             cache.insert(address.to_lowercase(), (token.clone(), Instant::now()));
         }
@@ -4,6 +4,11 @@ use serde::{Deserialize, Serialize};
 use zksync_basic_types::{BlockNumber, U256};
 use zksync_crypto::proof::EncodedAggregatedProof;
 
+/// Size of the function selector that precedes the ABI-encoded arguments in the calldata.
+const FUNCTION_SELECTOR_SIZE: usize = 4;
+/// Size of the ABI word.
+const ABI_WORD_SIZE: usize = 32;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlocksCommitOperation {
     pub last_committed_block: Block,
@@ -59,6 +64,30 @@ impl BlocksCommitOperation {
         vec![stored_block_info, Token::Array(blocks_to_commit)]
     }
 
+    /// Returns the offset of the public data of the given block within the calldata
+    /// of the `commitBlocks` contract call, including the function selector.
+    pub fn public_data_calldata_offset(&self, block_number: BlockNumber) -> Option<usize> {
+        let block_position = self
+            .blocks
+            .iter()
+            .position(|block| block.block_number == block_number)?;
+        let encoded = ethabi::encode(&self.get_eth_tx_args());
+        // Offsets of the dynamic values are stored in the heads relative to the start of the enclosing data.
+        let read_offset = |position: usize| {
+            U256::from_big_endian(&encoded[position..position + ABI_WORD_SIZE]).as_usize()
+        };
+
+        // `StoredBlockInfo` is a static tuple of 6 words, it is followed by the offset of the blocks array.
+        let blocks_array = read_offset(6 * ABI_WORD_SIZE);
+        // The array length is followed by the offsets of the dynamic `CommitBlockInfo` tuples.
+        let blocks_start = blocks_array + ABI_WORD_SIZE;
+        let block_info = blocks_start + read_offset(blocks_start + block_position * ABI_WORD_SIZE);
+        // Public data is the second field of `CommitBlockInfo`, its bytes are preceded by the length.
+        let public_data = block_info + read_offset(block_info + ABI_WORD_SIZE) + ABI_WORD_SIZE;
+
+        Some(FUNCTION_SELECTOR_SIZE + public_data)
+    }
+
     pub fn block_range(&self) -> (BlockNumber, BlockNumber) {
         let BlocksCommitOperation { blocks, .. } = self;
         (
@@ -141,7 +170,7 @@ impl BlocksExecuteOperation {
     }
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AggregatedActionType {
     CommitBlocks,
     CreateProofBlocks,
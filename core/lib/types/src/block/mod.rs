@@ -119,6 +119,14 @@ impl ExecutedOperations {
         }
     }
 
+    /// Returns the index of the operation in the block, if it was included into the block.
+    pub fn block_index(&self) -> Option<u32> {
+        match self {
+            ExecutedOperations::Tx(exec_tx) => exec_tx.block_index,
+            ExecutedOperations::PriorityOp(exec_op) => Some(exec_op.block_index),
+        }
+    }
+
     /// Attempts to get the executed L1 transaction.
     pub fn get_executed_tx(&self) -> Option<&ExecutedTx> {
         match self {
@@ -337,6 +345,23 @@ impl Block {
         executed_tx_pub_data
     }
 
+    /// Returns the offset of the operation public data within the block public data
+    /// along with the operation public data itself.
+    /// Returns `None` if there is no executed operation with the given index in the block.
+    pub fn get_op_public_data(&self, block_index: u32) -> Option<(usize, Vec<u8>)> {
+        let mut public_data_offset = 0;
+        for op in &self.block_transactions {
+            if let Some(executed_op) = op.get_executed_op() {
+                let public_data = executed_op.public_data();
+                if op.block_index() == Some(block_index) {
+                    return Some((public_data_offset, public_data));
+                }
+                public_data_offset += public_data.len();
+            }
+        }
+        None
+    }
+
     /// Returns eth_witness data and data_size for each operation that has it.
     pub fn get_eth_witness_data(&self) -> (Vec<u8>, Vec<u64>) {
         let mut eth_witness = Vec::new();
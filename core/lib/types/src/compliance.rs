@@ -0,0 +1,55 @@
+//! Address lists the transactions are checked against before being accepted.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::Address;
+
+/// List of the blocked addresses. Transactions sent from the address on the `Sender` list
+/// or moving funds to the address on the `Recipient` list are rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AddressList {
+    Sender,
+    Recipient,
+}
+
+impl AddressList {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sender => "sender",
+            Self::Recipient => "recipient",
+        }
+    }
+}
+
+impl fmt::Display for AddressList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AddressList {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sender" => Ok(Self::Sender),
+            "recipient" => Ok(Self::Recipient),
+            _ => Err(format!("Unknown address list {}", s)),
+        }
+    }
+}
+
+/// Address added to the list by the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedAddress {
+    pub address: Address,
+    pub list: AddressList,
+    pub reason: String,
+    /// Subject of the admin token the address was added with.
+    pub added_by: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     account::{AccountUpdate, PubKeyHash},
     aggregated_operations::AggregatedActionType,
-    AccountId, Nonce, TokenId,
+    AccountId, Address, Nonce, TokenId,
 };
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -43,6 +43,9 @@ pub struct AccountEvent {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountUpdateDetails {
     pub account_id: AccountId,
+    /// Address of the account. May be missing for the events created
+    /// before it was introduced.
+    pub address: Option<Address>,
     pub nonce: Nonce,
     pub new_pub_key_hash: Option<PubKeyHash>,
     pub token_id: Option<TokenId>,
@@ -55,15 +58,17 @@ impl AccountUpdateDetails {
         account_update: AccountUpdate,
     ) -> Option<Self> {
         match account_update {
-            AccountUpdate::Create { address: _, nonce } => Some(Self {
+            AccountUpdate::Create { address, nonce } => Some(Self {
                 account_id,
+                address: Some(address),
                 nonce,
                 new_pub_key_hash: None,
                 token_id: None,
                 new_balance: None,
             }),
-            AccountUpdate::Delete { address: _, nonce } => Some(Self {
+            AccountUpdate::Delete { address, nonce } => Some(Self {
                 account_id,
+                address: Some(address),
                 nonce,
                 new_pub_key_hash: None,
                 token_id: None,
@@ -75,6 +80,7 @@ impl AccountUpdateDetails {
                 balance_update,
             } => Some(Self {
                 account_id,
+                address: None,
                 nonce: new_nonce,
                 new_pub_key_hash: None,
                 token_id: Some(balance_update.0),
@@ -87,6 +93,7 @@ impl AccountUpdateDetails {
                 new_nonce,
             } => Some(Self {
                 account_id,
+                address: None,
                 nonce: new_nonce,
                 new_pub_key_hash: Some(new_pub_key_hash),
                 token_id: None,
@@ -6,7 +6,7 @@ use once_cell::sync::OnceCell;
 // Workspace uses
 // Local uses
 use super::{account::*, block::*, transaction::*, EventData, EventId, ZkSyncEvent};
-use crate::{AccountId, BlockNumber, Nonce, TokenId};
+use crate::{AccountId, Address, BlockNumber, Nonce, TokenId};
 
 /// Constructs default values for `BlockDetails` struct. Since block events
 /// can only be filtered by status, these fields are not used.
@@ -36,6 +36,11 @@ pub fn get_block_event(block_status: BlockStatus) -> ZkSyncEvent {
     }
 }
 
+/// Returns the address used in test events for the given account id.
+pub fn get_account_address(account_id: AccountId) -> Address {
+    Address::from_low_u64_be(*account_id as u64)
+}
+
 /// Construct account event with the given account id, token and status.
 pub fn get_account_event(
     account_id: AccountId,
@@ -52,6 +57,7 @@ pub fn get_account_event(
     };
     let update_details = AccountUpdateDetails {
         account_id,
+        address: Some(get_account_address(account_id)),
         nonce: Nonce(0),
         new_pub_key_hash: None,
         token_id,
@@ -82,6 +88,7 @@ pub fn get_transaction_event(
     let tx_event = TransactionEvent {
         tx_hash: String::new(),
         account_id,
+        address: Some(get_account_address(account_id)),
         token_id,
         block_number: BlockNumber(0),
         tx: Default::default(),
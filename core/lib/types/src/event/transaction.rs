@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 // Workspace uses
 // Local uses
 use super::account::AccountStateChangeStatus;
-use crate::{block::ExecutedOperations, AccountId, BlockNumber, TokenId};
+use crate::{block::ExecutedOperations, AccountId, Address, BlockNumber, TokenId};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -37,6 +37,9 @@ pub enum TransactionType {
 pub struct TransactionEvent {
     pub tx_hash: String,
     pub account_id: AccountId,
+    /// Address of the account affected by the transaction. May be missing
+    /// for the events created before it was introduced.
+    pub address: Option<Address>,
     pub token_id: TokenId,
     pub block_number: BlockNumber,
     pub tx: serde_json::Value,
@@ -63,6 +66,7 @@ impl TransactionEvent {
             ExecutedOperations::Tx(exec_tx) => Self {
                 tx_hash: exec_tx.signed_tx.tx.hash().to_string(),
                 account_id: exec_tx.signed_tx.account_id().ok()?, // Close events cannot be emitted.
+                address: Some(exec_tx.signed_tx.tx.account()),
                 token_id: exec_tx.signed_tx.token_id(),
                 block_number,
                 tx: serde_json::to_value(&exec_tx.signed_tx.tx).unwrap(),
@@ -78,6 +82,7 @@ impl TransactionEvent {
             ExecutedOperations::PriorityOp(exec_prior_op) => Self {
                 tx_hash: format!("{:#x}", exec_prior_op.priority_op.eth_hash),
                 account_id: exec_prior_op.account_id(),
+                address: Some(exec_prior_op.priority_op.data.to_account()),
                 token_id: exec_prior_op.priority_op.data.token_id(),
                 block_number,
                 tx: serde_json::to_value(&exec_prior_op.op).unwrap(),
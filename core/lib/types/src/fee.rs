@@ -1,9 +1,12 @@
+use chrono::{DateTime, Utc};
 use num::rational::Ratio;
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 
 use crate::helpers::{pack_fee_amount, unpack_fee_amount};
 use crate::tokens::ChangePubKeyFeeTypeArg;
+use crate::tx::TxHash;
+use crate::Address;
 use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str};
 
 /// Type of the fee calculation pattern.
@@ -23,6 +26,23 @@ pub enum OutputFeeType {
     ChangePubKey(ChangePubKeyFeeTypeArg),
 }
 
+impl OutputFeeType {
+    /// Returns the name of the fee type, `ChangePubKey` arguments are omitted.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Transfer => "Transfer",
+            Self::TransferToNew => "TransferToNew",
+            Self::Withdraw => "Withdraw",
+            Self::FastWithdraw => "FastWithdraw",
+            Self::WithdrawNFT => "WithdrawNFT",
+            Self::FastWithdrawNFT => "FastWithdrawNFT",
+            Self::Swap => "Swap",
+            Self::MintNFT => "MintNFT",
+            Self::ChangePubKey(_) => "ChangePubKey",
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Fee {
@@ -88,6 +108,67 @@ impl Fee {
     }
 }
 
+/// Rule exempting the transactions from the fee checks,
+/// e.g. for the operations sponsored by the operator such as forced exits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "value")]
+pub enum FeeExemption {
+    /// Transactions of the sender account.
+    Sender(Address),
+    /// Transactions of the type, e.g. `ForcedExit`.
+    TxType(String),
+}
+
+impl FeeExemption {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Sender(_) => "sender",
+            Self::TxType(_) => "txType",
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self {
+            Self::Sender(address) => format!("{:?}", address),
+            Self::TxType(tx_type) => tx_type.clone(),
+        }
+    }
+
+    /// Restores the exemption from its kind and value.
+    pub fn from_parts(kind: &str, value: &str) -> Result<Self, String> {
+        match kind {
+            "sender" => value
+                .parse()
+                .map(Self::Sender)
+                .map_err(|err| format!("Invalid sender address {}: {}", value, err)),
+            "txType" => Ok(Self::TxType(value.to_string())),
+            _ => Err(format!("Unknown fee exemption kind {}", kind)),
+        }
+    }
+}
+
+/// Fee exemption added by the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeExemptionRecord {
+    #[serde(flatten)]
+    pub exemption: FeeExemption,
+    /// Subject of the admin token the exemption was added with.
+    pub added_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Transaction accepted without the fee check because of the fee exemption.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeExemptionUsage {
+    pub tx_hash: TxHash,
+    pub sender: Address,
+    pub tx_type: String,
+    pub exemption: FeeExemption,
+    pub created_at: DateTime<Utc>,
+}
+
 fn total_fee(zkp_fee: &Ratio<BigUint>, gas_fee: &Ratio<BigUint>) -> (BigUint, BigUint, BigUint) {
     let zkp_fee = round_precision(zkp_fee, 18).ceil().to_integer();
     let gas_fee = round_precision(gas_fee, 18).ceil().to_integer();
@@ -39,6 +39,7 @@
 pub mod account;
 pub mod aggregated_operations;
 pub mod block;
+pub mod compliance;
 pub mod ethereum;
 pub mod event;
 pub mod fee;
@@ -60,7 +61,10 @@ mod tests;
 
 pub use self::account::{Account, AccountUpdate, PubKeyHash};
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
-pub use self::fee::{BatchFee, Fee, OutputFeeType, TotalFee};
+pub use self::compliance::{AddressList, BlockedAddress};
+pub use self::fee::{
+    BatchFee, Fee, FeeExemption, FeeExemptionRecord, FeeExemptionUsage, OutputFeeType, TotalFee,
+};
 pub use self::operations::{
     ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, MintNFTOp, SwapOp, TransferOp,
     TransferToNewOp, WithdrawNFTOp, WithdrawOp, ZkSyncOp,
@@ -3,7 +3,7 @@ use zksync_crypto::ff::Field;
 use zksync_crypto::Fr;
 
 use super::utils::*;
-use crate::block::Block;
+use crate::{aggregated_operations::BlocksCommitOperation, block::Block};
 
 /// Checks that we cannot create a block with invalid block sizes provided.
 #[test]
@@ -166,3 +166,48 @@ fn test_get_withdrawals_data() {
     // No more corresponding operations left.
     assert!(block.get_withdrawals_data().is_empty());
 }
+
+/// Checks that the calldata offset of the block public data points to the public data
+/// within the encoded `commitBlocks` arguments.
+#[test]
+fn test_public_data_calldata_offset() {
+    let create_block = |block_number: u32, operations| {
+        Block::new(
+            BlockNumber(block_number),
+            Fr::one(),
+            AccountId(0),
+            operations,
+            (0, 0),
+            100,
+            1_000_000.into(),
+            1_500_000.into(),
+            H256::default(),
+            0,
+        )
+    };
+    let commit_operation = BlocksCommitOperation {
+        last_committed_block: create_block(0, vec![]),
+        blocks: vec![
+            create_block(1, vec![create_change_pubkey_tx(), create_full_exit_op()]),
+            create_block(2, vec![create_withdraw_tx()]),
+        ],
+    };
+    let encoded = ethabi::encode(&commit_operation.get_eth_tx_args());
+
+    for block in &commit_operation.blocks {
+        let public_data = block.get_eth_public_data();
+        // Encoded arguments don't contain the function selector.
+        let offset = commit_operation
+            .public_data_calldata_offset(block.block_number)
+            .unwrap()
+            - 4;
+        assert_eq!(
+            &encoded[offset..offset + public_data.len()],
+            &public_data[..]
+        );
+    }
+
+    assert!(commit_operation
+        .public_data_calldata_offset(BlockNumber(3))
+        .is_none());
+}
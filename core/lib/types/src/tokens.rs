@@ -241,6 +241,42 @@ pub struct TokenMarketVolume {
     pub last_updated: DateTime<Utc>,
 }
 
+/// Decision of the fee token policy on whether the token can be used to pay fees.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeTokenDecision {
+    pub token_id: TokenId,
+    pub is_fee_token: bool,
+    /// Market volume of the token the decision was based on.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub market_volume: Ratio<BigUint>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Manual change of the token acceptance for the fee payment made by the operator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTokenAcceptanceChange {
+    pub token_id: TokenId,
+    /// `None` means that the manual override was removed and the fee token policy decides again.
+    pub is_fee_token: Option<bool>,
+    /// Subject of the admin token the change was made with.
+    pub changed_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Mismatch between the stored token metadata and the metadata of the ERC-20 contract
+/// that can't be reconciled automatically and requires the operator review.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenMetadataConflict {
+    pub token_id: TokenId,
+    pub stored_symbol: String,
+    pub stored_decimals: u8,
+    pub contract_symbol: String,
+    pub contract_decimals: u8,
+    pub detected_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Hash, Eq)]
 #[serde(untagged)]
 pub enum ChangePubKeyFeeTypeArg {
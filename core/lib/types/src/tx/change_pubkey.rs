@@ -405,6 +405,17 @@ impl ChangePubKey {
         }
     }
 
+    /// Returns the way the Ethereum account authorized the public key change.
+    pub fn get_auth_type(&self) -> ChangePubKeyType {
+        if let Some(auth_data) = &self.eth_auth_data {
+            auth_data.get_fee_type()
+        } else if self.eth_signature.is_some() {
+            ChangePubKeyType::ECDSA
+        } else {
+            ChangePubKeyType::Onchain
+        }
+    }
+
     /// Get part of the message that should be signed with Ethereum account key for the batch of transactions.
     /// The message for single `ChangePubKey` transaction is defined differently. The pattern is:
     ///
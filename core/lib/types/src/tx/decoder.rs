@@ -0,0 +1,256 @@
+//! Decoding of the L2 transactions from the canonical bytes they are signed in.
+//!
+//! The bytes don't contain the signatures, so the decoded transactions are unsigned.
+//! Only the current encoding (the one prefixed with the transaction version) is supported.
+
+use num::BigUint;
+use zksync_basic_types::{Address, H256};
+use zksync_crypto::params::{
+    AMOUNT_EXPONENT_BIT_WIDTH, AMOUNT_MANTISSA_BIT_WIDTH, CURRENT_TX_VERSION,
+    FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH, FR_ADDRESS_LEN, PRICE_BIT_WIDTH,
+};
+
+use crate::{
+    helpers::{unpack_fee_amount, unpack_token_amount},
+    tx::{
+        error::TxDecodeError, ChangePubKey, ForcedExit, MintNFT, Order, Swap, TimeRange, Transfer,
+        TxSignature, Withdraw, WithdrawNFT, ZkSyncTx,
+    },
+    AccountId, Nonce, PubKeyHash, TokenId,
+};
+
+const TOKEN_AMOUNT_BYTES: usize = (AMOUNT_EXPONENT_BIT_WIDTH + AMOUNT_MANTISSA_BIT_WIDTH) / 8;
+const FEE_AMOUNT_BYTES: usize = (FEE_EXPONENT_BIT_WIDTH + FEE_MANTISSA_BIT_WIDTH) / 8;
+
+impl ZkSyncTx {
+    /// Decodes the transaction from the bytes returned by `ZkSyncTx::get_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, TxDecodeError> {
+        let mut reader = TxBytesReader { bytes };
+        let tx_type = reader.versioned_header()?;
+
+        let tx = match 255u8.wrapping_sub(tx_type) {
+            Transfer::TX_TYPE => ZkSyncTx::Transfer(Box::new(Transfer::new(
+                reader.account_id()?,
+                reader.address()?,
+                reader.address()?,
+                reader.token_id()?,
+                reader.token_amount()?,
+                reader.fee_amount()?,
+                reader.nonce()?,
+                reader.time_range()?,
+                None,
+            ))),
+            Withdraw::TX_TYPE => {
+                let mut tx = Withdraw::new(
+                    reader.account_id()?,
+                    reader.address()?,
+                    reader.address()?,
+                    reader.token_id()?,
+                    BigUint::from(reader.u128()?),
+                    reader.fee_amount()?,
+                    reader.nonce()?,
+                    TimeRange::default(),
+                    None,
+                );
+                // Withdrawals signed without the time range are encoded without it.
+                tx.time_range = if reader.is_empty() {
+                    None
+                } else {
+                    Some(reader.time_range()?)
+                };
+                ZkSyncTx::Withdraw(Box::new(tx))
+            }
+            ChangePubKey::TX_TYPE => {
+                let mut tx = ChangePubKey::new(
+                    reader.account_id()?,
+                    reader.address()?,
+                    reader.pub_key_hash()?,
+                    reader.token_id()?,
+                    reader.fee_amount()?,
+                    reader.nonce()?,
+                    reader.time_range()?,
+                    None,
+                    None,
+                );
+                // Ethereum authorization is not a part of the signed bytes.
+                tx.eth_auth_data = None;
+                ZkSyncTx::ChangePubKey(Box::new(tx))
+            }
+            ForcedExit::TX_TYPE => ZkSyncTx::ForcedExit(Box::new(ForcedExit::new(
+                reader.account_id()?,
+                reader.address()?,
+                reader.token_id()?,
+                reader.fee_amount()?,
+                reader.nonce()?,
+                reader.time_range()?,
+                None,
+            ))),
+            MintNFT::TX_TYPE => {
+                let creator_id = reader.account_id()?;
+                let creator_address = reader.address()?;
+                let content_hash = H256::from_slice(reader.take(32)?);
+                let recipient = reader.address()?;
+                let fee_token = reader.token_id()?;
+                let fee = reader.fee_amount()?;
+                ZkSyncTx::MintNFT(Box::new(MintNFT::new(
+                    creator_id,
+                    creator_address,
+                    content_hash,
+                    recipient,
+                    fee,
+                    fee_token,
+                    reader.nonce()?,
+                    None,
+                )))
+            }
+            WithdrawNFT::TX_TYPE => ZkSyncTx::WithdrawNFT(Box::new(WithdrawNFT::new(
+                reader.account_id()?,
+                reader.address()?,
+                reader.address()?,
+                reader.token_id()?,
+                reader.token_id()?,
+                reader.fee_amount()?,
+                reader.nonce()?,
+                reader.time_range()?,
+                None,
+            ))),
+            Swap::TX_TYPE => {
+                let submitter_id = reader.account_id()?;
+                let submitter_address = reader.address()?;
+                let nonce = reader.nonce()?;
+                let orders = (reader.order()?, reader.order()?);
+                let fee_token = reader.token_id()?;
+                let fee = reader.fee_amount()?;
+                let amounts = (reader.token_amount()?, reader.token_amount()?);
+                ZkSyncTx::Swap(Box::new(Swap::new(
+                    submitter_id,
+                    submitter_address,
+                    nonce,
+                    orders,
+                    amounts,
+                    fee,
+                    fee_token,
+                    None,
+                )))
+            }
+            _ => return Err(TxDecodeError::UnknownTxType(tx_type)),
+        };
+
+        if !reader.is_empty() {
+            return Err(TxDecodeError::TrailingBytes(reader.bytes.len()));
+        }
+        Ok(tx)
+    }
+}
+
+/// Reads the fields of the transaction in the order they are encoded.
+struct TxBytesReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> TxBytesReader<'a> {
+    fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], TxDecodeError> {
+        if self.bytes.len() < len {
+            return Err(TxDecodeError::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, TxDecodeError> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Reads the type byte and checks the version that follows it.
+    fn versioned_header(&mut self) -> Result<u8, TxDecodeError> {
+        let tx_type = self.u8()?;
+        let version = self.u8()?;
+        if version != CURRENT_TX_VERSION {
+            return Err(TxDecodeError::UnsupportedVersion(version));
+        }
+        Ok(tx_type)
+    }
+
+    fn u32(&mut self) -> Result<u32, TxDecodeError> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    fn u64(&mut self) -> Result<u64, TxDecodeError> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn u128(&mut self) -> Result<u128, TxDecodeError> {
+        let mut buf = [0u8; 16];
+        buf.copy_from_slice(self.take(16)?);
+        Ok(u128::from_be_bytes(buf))
+    }
+
+    fn account_id(&mut self) -> Result<AccountId, TxDecodeError> {
+        self.u32().map(AccountId)
+    }
+
+    fn token_id(&mut self) -> Result<TokenId, TxDecodeError> {
+        self.u32().map(TokenId)
+    }
+
+    fn nonce(&mut self) -> Result<Nonce, TxDecodeError> {
+        self.u32().map(Nonce)
+    }
+
+    fn address(&mut self) -> Result<Address, TxDecodeError> {
+        Ok(Address::from_slice(self.take(20)?))
+    }
+
+    fn pub_key_hash(&mut self) -> Result<PubKeyHash, TxDecodeError> {
+        let data = self.take(FR_ADDRESS_LEN)?;
+        Ok(PubKeyHash::from_bytes(data).expect("slice has the size of the hash"))
+    }
+
+    fn token_amount(&mut self) -> Result<BigUint, TxDecodeError> {
+        unpack_token_amount(self.take(TOKEN_AMOUNT_BYTES)?).ok_or(TxDecodeError::IncorrectAmount)
+    }
+
+    fn fee_amount(&mut self) -> Result<BigUint, TxDecodeError> {
+        unpack_fee_amount(self.take(FEE_AMOUNT_BYTES)?).ok_or(TxDecodeError::IncorrectFee)
+    }
+
+    fn time_range(&mut self) -> Result<TimeRange, TxDecodeError> {
+        Ok(TimeRange::new(self.u64()?, self.u64()?))
+    }
+
+    fn order(&mut self) -> Result<Order, TxDecodeError> {
+        let msg_type = self.versioned_header()?;
+        if msg_type != Order::MSG_TYPE {
+            return Err(TxDecodeError::UnknownTxType(msg_type));
+        }
+        let account_id = self.account_id()?;
+        let recipient_address = self.address()?;
+        let nonce = self.nonce()?;
+        let token_sell = self.token_id()?;
+        let token_buy = self.token_id()?;
+        let price = (
+            BigUint::from_bytes_be(self.take(PRICE_BIT_WIDTH / 8)?),
+            BigUint::from_bytes_be(self.take(PRICE_BIT_WIDTH / 8)?),
+        );
+        Ok(Order {
+            account_id,
+            recipient_address,
+            nonce,
+            token_buy,
+            token_sell,
+            price,
+            amount: self.token_amount()?,
+            time_range: self.time_range()?,
+            signature: TxSignature::default(),
+        })
+    }
+}
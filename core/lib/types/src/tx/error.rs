@@ -14,6 +14,22 @@ pub enum ChangePubkeySignedDataError {
 #[error("Close operations are disabled")]
 pub struct CloseOperationsDisabled();
 
+#[derive(Debug, Error, PartialEq)]
+pub enum TxDecodeError {
+    #[error("Transaction bytes end unexpectedly")]
+    UnexpectedEnd,
+    #[error("Transaction bytes contain {0} extra bytes")]
+    TrailingBytes(usize),
+    #[error("Unknown transaction type: {0}")]
+    UnknownTxType(u8),
+    #[error("Unsupported transaction version: {0}")]
+    UnsupportedVersion(u8),
+    #[error("Amount is packed incorrectly")]
+    IncorrectAmount,
+    #[error("Fee is packed incorrectly")]
+    IncorrectFee,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
 pub enum TxAddError {
     #[error("Tx nonce is too low.")]
@@ -57,6 +73,15 @@ pub enum TxAddError {
 
     #[error("Too many Ethereum signatures provided")]
     EthSignaturesLimitExceeded,
+
+    #[error("Not enough balance to execute the transaction")]
+    InsufficientBalance,
+
+    #[error("Transaction with the same nonce is already queued, replacement must pay a higher fee in the same token")]
+    ReplacementFeeTooLow,
+
+    #[error("Tx nonce is too far ahead of the account nonce")]
+    NonceTooFarAhead,
 }
 
 #[derive(Error, Debug, Copy, Clone, Serialize, Deserialize)]
@@ -81,6 +106,35 @@ pub enum TransactionError {
     CloseError(#[from] close::TransactionError),
 }
 
+impl TransactionError {
+    /// Whether the transaction is rejected because of its zkSync signature rather than its fields.
+    pub fn is_signature_error(&self) -> bool {
+        matches!(
+            self,
+            TransactionError::WithdrawError(withdraw::TransactionError::WrongSignature)
+                | TransactionError::TransferError(transfer::TransactionError::WrongSignature)
+                | TransactionError::MintNFTError(mint_nft::TransactionError::WrongSignature)
+                | TransactionError::WithdrawNFTError(
+                    withdraw_nft::TransactionError::WrongSignature
+                )
+                | TransactionError::ChangePubKeyError(
+                    change_pubkey::TransactionError::WrongSignature
+                )
+                | TransactionError::SwapError(swap::TransactionError::WrongSignature)
+                | TransactionError::ForcedExitError(forced_exit::TransactionError::WrongSignature)
+                | TransactionError::CloseError(close::TransactionError::WrongSignature)
+        )
+    }
+
+    /// Whether the `ChangePubKey` is rejected because of its Ethereum authorization data.
+    pub fn is_eth_auth_error(&self) -> bool {
+        matches!(
+            self,
+            TransactionError::ChangePubKeyError(change_pubkey::TransactionError::InvalidAuthData)
+        )
+    }
+}
+
 pub const WRONG_AMOUNT_ERROR: &str = "Specified amount is greater than maximum supported amount";
 pub const WRONG_FEE_ERROR: &str = "Specified fee amount is greater than maximum supported fee";
 pub const FEE_AMOUNT_IS_NOT_PACKABLE: &str = "Specified fee is not packable";
@@ -2,6 +2,7 @@
 
 mod change_pubkey;
 mod close;
+mod decoder;
 mod forced_exit;
 mod mint_nft;
 mod primitives;
@@ -46,7 +47,7 @@ pub use self::primitives::{
     packed_signature::PackedSignature,
     signature::TxSignature,
     time_range::TimeRange,
-    tx_hash::TxHash,
+    tx_hash::{TxHash, TxHashDecodeError},
 };
 
 pub(crate) use self::primitives::signature_cache::VerifiedSignatureCache;
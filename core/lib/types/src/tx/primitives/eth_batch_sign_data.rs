@@ -3,7 +3,7 @@ use itertools::Itertools;
 // Workspace uses
 use zksync_basic_types::Address;
 // Local uses
-use super::eth_signature::TxEthSignature;
+use super::{eth_signature::TxEthSignature, tx_hash::TxHash};
 use crate::{Token, ZkSyncTx};
 use thiserror::Error;
 
@@ -65,6 +65,17 @@ impl EthBatchSignData {
         .into_bytes()
     }
 
+    /// Construct the message covering the hashes of all the batch transactions.
+    /// It can be signed instead of the human-readable message, in this case
+    /// the batch must have a single signature that is valid for every sender.
+    pub fn get_batch_hash_sign_message(tx_hashes: &[TxHash]) -> Vec<u8> {
+        format!(
+            "Batch hash: 0x{}",
+            hex::encode(TxHash::batch_hash(tx_hashes))
+        )
+        .into_bytes()
+    }
+
     fn group_message<I>(iter: I, address: Option<Address>) -> String
     where
         I: IntoIterator<Item = (ZkSyncTx, Token, Address)>,
@@ -130,3 +130,24 @@ fn test_batch_message() {
     let message = EthBatchSignData::get_batch_sign_message(txs);
     assert_eq!(message, expected.into_bytes());
 }
+
+/// Checks the message covering the hashes of the batch transactions.
+#[test]
+fn test_batch_hash_message() {
+    let transfer = ZkSyncTx::Transfer(Box::new(get_transfer()));
+    let withdraw = ZkSyncTx::Withdraw(Box::new(get_withdraw()));
+    let tx_hashes = vec![transfer.hash(), withdraw.hash()];
+    let batch_hash = TxHash::batch_hash(&tx_hashes);
+
+    let message = EthBatchSignData::get_batch_hash_sign_message(&tx_hashes);
+    assert_eq!(
+        message,
+        format!("Batch hash: 0x{}", hex::encode(batch_hash)).into_bytes()
+    );
+    // The order of the transactions is covered by the message.
+    let reversed_hashes = vec![withdraw.hash(), transfer.hash()];
+    assert_ne!(
+        message,
+        EthBatchSignData::get_batch_hash_sign_message(&reversed_hashes)
+    );
+}
@@ -1,4 +1,5 @@
 use num::{BigUint, Zero};
+use parity_crypto::digest::sha256;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
@@ -13,7 +14,7 @@ use zksync_crypto::{
 };
 use zksync_utils::{format_units, BigUintPairSerdeAsRadix10Str, BigUintSerdeAsRadix10Str};
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{TxHash, TxSignature, VerifiedSignatureCache};
 use crate::account::PubKeyHash;
 use crate::tx::error::{
     AMOUNT_IS_NOT_PACKABLE, FEE_AMOUNT_IS_NOT_PACKABLE, WRONG_ACCOUNT_ID, WRONG_AMOUNT_ERROR,
@@ -90,6 +91,14 @@ impl Order {
         out
     }
 
+    /// Returns the hash of the order, which can be used to find the swaps it was filled in.
+    pub fn hash(&self) -> TxHash {
+        let hash = sha256(&self.get_bytes());
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&hash);
+        TxHash { data: out }
+    }
+
     pub fn verify_signature(&self) -> Option<PubKeyHash> {
         self.signature
             .verify_musig(&self.get_bytes())
@@ -585,3 +585,135 @@ fn test_check_signature() {
 
     assert_eq!(hex::encode(signature), "4e3298ac8cc13868dbbc94ad6fb41085ffe05b3c2eee22f88b05e69b7a5126aea723d7a3e7282ef5a32d9479c9c8dde52b3e3c462dd445dcd8158ebb6edb6000");
 }
+
+#[test]
+fn test_decode_tx_bytes() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let time_range = TimeRange::new(rng.gen(), rng.gen());
+    let order = |rng: &mut XorShiftRng| Order {
+        account_id: gen_account_id(rng),
+        recipient_address: Address::from(rng.gen::<[u8; 20]>()),
+        nonce: Nonce(rng.gen()),
+        token_buy: gen_token_id(rng),
+        token_sell: gen_token_id(rng),
+        price: (BigUint::from(12u8), BigUint::from(18u8)),
+        amount: BigUint::from(12_000_000_000u64),
+        time_range,
+        signature: TxSignature::default(),
+    };
+
+    let mut withdraw_without_time_range = Withdraw::new(
+        gen_account_id(&mut rng),
+        Address::from(rng.gen::<[u8; 20]>()),
+        Address::from(rng.gen::<[u8; 20]>()),
+        gen_token_id(&mut rng),
+        BigUint::from(u128::MAX),
+        BigUint::from(1_000_000u32),
+        Nonce(rng.gen()),
+        time_range,
+        None,
+    );
+    withdraw_without_time_range.time_range = None;
+
+    let txs: Vec<ZkSyncTx> = vec![
+        Transfer::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            Address::from(rng.gen::<[u8; 20]>()),
+            gen_token_id(&mut rng),
+            BigUint::from(12_340_000_000u64),
+            BigUint::from(56_700_000u32),
+            Nonce(rng.gen()),
+            time_range,
+            None,
+        )
+        .into(),
+        Withdraw::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            Address::from(rng.gen::<[u8; 20]>()),
+            gen_token_id(&mut rng),
+            BigUint::from(12_345_678u32),
+            BigUint::from(1_000_000u32),
+            Nonce(rng.gen()),
+            time_range,
+            None,
+        )
+        .into(),
+        withdraw_without_time_range.into(),
+        ChangePubKey::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            PubKeyHash::from_bytes(&rng.gen::<[u8; 20]>()).unwrap(),
+            gen_token_id(&mut rng),
+            BigUint::from(1_000_000u32),
+            Nonce(rng.gen()),
+            time_range,
+            None,
+            None,
+        )
+        .into(),
+        ForcedExit::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            gen_token_id(&mut rng),
+            BigUint::from(1_000_000u32),
+            Nonce(rng.gen()),
+            time_range,
+            None,
+        )
+        .into(),
+        MintNFT::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            H256::from(rng.gen::<[u8; 32]>()),
+            Address::from(rng.gen::<[u8; 20]>()),
+            BigUint::from(1_000_000u32),
+            gen_token_id(&mut rng),
+            Nonce(rng.gen()),
+            None,
+        )
+        .into(),
+        WithdrawNFT::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            Address::from(rng.gen::<[u8; 20]>()),
+            gen_nft_token_id(&mut rng),
+            gen_token_id(&mut rng),
+            BigUint::from(1_000_000u32),
+            Nonce(rng.gen()),
+            time_range,
+            None,
+        )
+        .into(),
+        Swap::new(
+            gen_account_id(&mut rng),
+            Address::from(rng.gen::<[u8; 20]>()),
+            Nonce(rng.gen()),
+            (order(&mut rng), order(&mut rng)),
+            (
+                BigUint::from(12_000_000_000u64),
+                BigUint::from(18_000_000_000u64),
+            ),
+            BigUint::from(1_000_000u32),
+            gen_token_id(&mut rng),
+            None,
+        )
+        .into(),
+    ];
+
+    for tx in txs {
+        let bytes = tx.get_bytes();
+        let decoded = ZkSyncTx::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.get_bytes(), bytes);
+        assert_eq!(decoded.hash(), tx.hash());
+
+        assert_eq!(
+            ZkSyncTx::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+            error::TxDecodeError::UnexpectedEnd
+        );
+        let mut extended = bytes.clone();
+        extended.extend_from_slice(&[0; 20]);
+        assert!(ZkSyncTx::from_bytes(&extended).is_err());
+    }
+}
@@ -15,8 +15,8 @@ use crate::{
         TxHash, TxSignature, Withdraw, WithdrawNFT,
     },
     utils::deserialize_eth_message,
-    CloseOp, ForcedExitOp, Nonce, SwapOp, Token, TokenId, TokenLike, TransferOp, TxFeeTypes,
-    WithdrawNFTOp, WithdrawOp,
+    CloseOp, ForcedExitOp, Nonce, PubKeyHash, SwapOp, Token, TokenId, TokenLike, TransferOp,
+    TxFeeTypes, WithdrawNFTOp, WithdrawOp,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -168,6 +168,31 @@ impl ZkSyncTx {
         }
     }
 
+    /// Returns the addresses receiving the funds moved by the transaction.
+    pub fn recipients(&self) -> Vec<Address> {
+        match self {
+            ZkSyncTx::Transfer(tx) => vec![tx.to],
+            ZkSyncTx::Withdraw(tx) => vec![tx.to],
+            ZkSyncTx::Close(_) | ZkSyncTx::ChangePubKey(_) => Vec::new(),
+            ZkSyncTx::ForcedExit(tx) => vec![tx.target],
+            ZkSyncTx::Swap(tx) => {
+                vec![tx.orders.0.recipient_address, tx.orders.1.recipient_address]
+            }
+            ZkSyncTx::MintNFT(tx) => vec![tx.recipient],
+            ZkSyncTx::WithdrawNFT(tx) => vec![tx.to],
+        }
+    }
+
+    /// Returns the ids of the accounts sending the funds or initiating the transaction
+    /// whose addresses are not included in the transaction.
+    pub fn sender_account_ids(&self) -> Vec<AccountId> {
+        match self {
+            ZkSyncTx::ForcedExit(tx) => vec![tx.initiator_account_id],
+            ZkSyncTx::Swap(tx) => vec![tx.orders.0.account_id, tx.orders.1.account_id],
+            _ => Vec::new(),
+        }
+    }
+
     pub fn tokens(&self) -> Vec<TokenId> {
         let mut tokens = match self {
             ZkSyncTx::Transfer(tx) => vec![tx.token],
@@ -224,6 +249,21 @@ impl ZkSyncTx {
         }
     }
 
+    /// Restores the hash of the public key the transaction was signed with.
+    /// Returns `None` if the signature is incorrect.
+    pub fn signer_pub_key_hash(&self) -> Option<PubKeyHash> {
+        match self {
+            ZkSyncTx::Transfer(tx) => tx.verify_signature().map(|(signer, _)| signer),
+            ZkSyncTx::Withdraw(tx) => tx.verify_signature().map(|(signer, _)| signer),
+            ZkSyncTx::Close(tx) => tx.verify_signature(),
+            ZkSyncTx::ChangePubKey(tx) => tx.verify_signature().map(|(signer, _)| signer),
+            ZkSyncTx::ForcedExit(tx) => tx.verify_signature().map(|(signer, _)| signer),
+            ZkSyncTx::MintNFT(tx) => tx.verify_signature().map(|(signer, _)| signer),
+            ZkSyncTx::Swap(tx) => tx.verify_signature().map(|(signer, _)| signer),
+            ZkSyncTx::WithdrawNFT(tx) => tx.verify_signature().map(|(signer, _)| signer),
+        }
+    }
+
     pub fn is_backwards_compatible(&self) -> bool {
         match self {
             ZkSyncTx::Transfer(tx) => tx.is_backwards_compatible(),